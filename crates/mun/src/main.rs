@@ -7,8 +7,11 @@ use std::rc::Rc;
 use std::time::Duration;
 
 use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
-use mun_compiler::{Config, DisplayColor, PathOrInline, Target};
+use mun_compiler::{
+    Config, DisplayColor, Lint, LintLevel, LintLevels, MessageFormat, PathOrInline, Target,
+};
 use mun_runtime::{invoke_fn, ReturnTypeReflection, Runtime, RuntimeBuilder};
+use mun_syntax::{formatting, SourceFile};
 
 fn main() -> Result<(), failure::Error> {
     let matches = App::new("mun")
@@ -35,6 +38,12 @@ fn main() -> Result<(), failure::Error> {
                         .takes_value(true)
                         .help("optimize with possible levels 0-3"),
                 )
+                .arg(
+                    Arg::with_name("debug")
+                        .short("g")
+                        .long("debug")
+                        .help("generate debug information for native debuggers"),
+                )
                 .arg(
                     Arg::with_name("target")
                         .long("target")
@@ -48,6 +57,31 @@ fn main() -> Result<(), failure::Error> {
                         .possible_values(&["enable", "auto", "disable"])
                         .help("color text in terminal"),
                 )
+                .arg(
+                    Arg::with_name("message-format")
+                        .long("message-format")
+                        .takes_value(true)
+                        .possible_values(&["human", "json"])
+                        .help("the format in which diagnostics are emitted (defaults to human)"),
+                )
+                .arg(
+                    Arg::with_name("warn")
+                        .short("W")
+                        .long("warn")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .help("report a lint as a warning, e.g. `-W shadowed_bindings`"),
+                )
+                .arg(
+                    Arg::with_name("deny")
+                        .short("D")
+                        .long("deny")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .help("report a lint as an error; `-D warnings` turns all warnings into errors"),
+                )
                 .about("Compiles a local Mun file into a module"),
         )
         .subcommand(
@@ -71,11 +105,32 @@ fn main() -> Result<(), failure::Error> {
                         .help("how much to delay received filesystem events (in ms). This allows bundling of identical events, e.g. when several writes to the same file are detected. A high delay will make hot reloading less responsive. (defaults to 10 ms)"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("fmt")
+                .arg(
+                    Arg::with_name("INPUT")
+                        .help("Sets the input files to format")
+                        .required(true)
+                        .multiple(true)
+                        .index(1),
+                )
+                .arg(Arg::with_name("check").long("check").help(
+                    "Run in check mode.
+                    Exits with an error if any input file is not formatted, instead of writing it.",
+                ))
+                .about("Formats Mun source files"),
+        )
+        .subcommand(
+            SubCommand::with_name("language-server")
+                .about("Starts a language server that communicates over stdin and stdout"),
+        )
         .get_matches();
 
     match matches.subcommand() {
         ("build", Some(matches)) => build(matches)?,
         ("start", Some(matches)) => start(matches)?,
+        ("fmt", Some(matches)) => fmt(matches)?,
+        ("language-server", Some(_)) => mun_language_server::run_server()?,
         _ => unreachable!(),
     }
 
@@ -92,6 +147,43 @@ fn build(matches: &ArgMatches) -> Result<(), failure::Error> {
     }
 }
 
+/// Formats the source files specified. In check mode the files are left untouched and an error is
+/// returned if any of them is not formatted.
+fn fmt(matches: &ArgMatches) -> Result<(), failure::Error> {
+    let check = matches.is_present("check");
+    let mut unformatted = Vec::new();
+    // Safe because its a required arg
+    for path in matches.values_of("INPUT").unwrap() {
+        let text = std::fs::read_to_string(path)?;
+        let file = SourceFile::parse(&text);
+        if let Some(err) = file.errors().first() {
+            return Err(format_err!(
+                "{}: cannot format a file with syntax errors: {}",
+                path,
+                err
+            ));
+        }
+
+        let formatted = formatting::format(&file.tree());
+        if formatted != text {
+            if check {
+                unformatted.push(path);
+            } else {
+                std::fs::write(path, formatted)?;
+            }
+        }
+    }
+
+    if unformatted.is_empty() {
+        Ok(())
+    } else {
+        Err(format_err!(
+            "The following files are not formatted:\n{}",
+            unformatted.join("\n")
+        ))
+    }
+}
+
 /// Starts the runtime with the specified library and invokes function `entry`.
 fn start(matches: &ArgMatches) -> Result<(), failure::Error> {
     let runtime = runtime(matches)?;
@@ -157,6 +249,24 @@ fn compiler_options(matches: &ArgMatches) -> Result<mun_compiler::CompilerOption
         })
         .unwrap_or(DisplayColor::Auto);
 
+    let message_format = match matches.value_of("message-format") {
+        Some("json") => MessageFormat::Json,
+        _ => MessageFormat::Human,
+    };
+
+    let mut lint_levels = LintLevels::default();
+    for (arg, level) in [("warn", LintLevel::Warn), ("deny", LintLevel::Deny)].iter() {
+        for name in matches.values_of(arg).into_iter().flatten() {
+            if name == mun_compiler::WARNINGS {
+                lint_levels.set_warnings(*level);
+            } else {
+                let lint =
+                    Lint::from_name(name).ok_or_else(|| format_err!("unknown lint: `{}`", name))?;
+                lint_levels.set(lint, *level);
+            }
+        }
+    }
+
     Ok(mun_compiler::CompilerOptions {
         input: PathOrInline::Path(matches.value_of("INPUT").unwrap().into()), // Safe because its a required arg
         config: Config {
@@ -164,8 +274,11 @@ fn compiler_options(matches: &ArgMatches) -> Result<mun_compiler::CompilerOption
                 .value_of("target")
                 .map_or_else(Target::host_target, Target::search)?,
             optimization_lvl,
+            debug_info: matches.is_present("debug"),
             out_dir: None,
             display_color,
+            message_format,
+            lint_levels,
         },
     })
 }
@@ -280,12 +280,15 @@ pub struct StructInfo {
     pub num_fields: u16,
     #[doc = " Struct memory kind"]
     pub memory_kind: StructMemoryKind,
+    #[doc = " Struct fields' previous names, or null if no field was renamed. The previous name of a"]
+    #[doc = " field that was not renamed is null."]
+    pub field_renamed_from: *const *const ::std::os::raw::c_char,
 }
 #[test]
 fn bindgen_test_layout_StructInfo() {
     assert_eq!(
         ::std::mem::size_of::<StructInfo>(),
-        32usize,
+        40usize,
         concat!("Size of: ", stringify!(StructInfo))
     );
     assert_eq!(
@@ -343,6 +346,212 @@ fn bindgen_test_layout_StructInfo() {
             stringify!(memory_kind)
         )
     );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<StructInfo>())).field_renamed_from as *const _ as usize },
+        32usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(StructInfo),
+            "::",
+            stringify!(field_renamed_from)
+        )
+    );
+}
+#[doc = " Represents an array declaration."]
+#[doc = ""]
+#[doc = " <div rustbindgen derive=\"Clone\" derive=\"Debug\"></div>"]
+#[repr(C)]
+#[derive(Clone, Debug)]
+pub struct ArrayInfo {
+    #[doc = " Array elements' information"]
+    pub element_type: *const TypeInfo,
+    #[doc = " Number of elements, if the array has a fixed size"]
+    pub length: u32,
+    #[doc = " Whether the array is a growable, garbage collected array"]
+    pub is_dynamic: bool,
+}
+#[test]
+fn bindgen_test_layout_ArrayInfo() {
+    assert_eq!(
+        ::std::mem::size_of::<ArrayInfo>(),
+        16usize,
+        concat!("Size of: ", stringify!(ArrayInfo))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<ArrayInfo>(),
+        8usize,
+        concat!("Alignment of ", stringify!(ArrayInfo))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<ArrayInfo>())).element_type as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(ArrayInfo),
+            "::",
+            stringify!(element_type)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<ArrayInfo>())).length as *const _ as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(ArrayInfo),
+            "::",
+            stringify!(length)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<ArrayInfo>())).is_dynamic as *const _ as usize },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(ArrayInfo),
+            "::",
+            stringify!(is_dynamic)
+        )
+    );
+}
+#[doc = " Represents an enum declaration."]
+#[doc = ""]
+#[doc = " The discriminant of an enum value is stored as a `u8` at the start of the value and equals"]
+#[doc = " the index of the active variant. The field offsets of a variant are relative to the start of"]
+#[doc = " the enum value."]
+#[doc = ""]
+#[doc = " <div rustbindgen derive=\"Debug\"></div>"]
+#[repr(C)]
+#[derive(Debug)]
+pub struct EnumInfo {
+    #[doc = " Enum variants' names"]
+    pub variant_names: *const *const ::std::os::raw::c_char,
+    #[doc = " Enum variants' information"]
+    pub variant_infos: *const StructInfo,
+    #[doc = " Number of enum variants"]
+    pub num_variants: u16,
+    #[doc = " Enum memory kind"]
+    pub memory_kind: StructMemoryKind,
+}
+#[test]
+fn bindgen_test_layout_EnumInfo() {
+    assert_eq!(
+        ::std::mem::size_of::<EnumInfo>(),
+        24usize,
+        concat!("Size of: ", stringify!(EnumInfo))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<EnumInfo>(),
+        8usize,
+        concat!("Alignment of ", stringify!(EnumInfo))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<EnumInfo>())).variant_names as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(EnumInfo),
+            "::",
+            stringify!(variant_names)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<EnumInfo>())).variant_infos as *const _ as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(EnumInfo),
+            "::",
+            stringify!(variant_infos)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<EnumInfo>())).num_variants as *const _ as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(EnumInfo),
+            "::",
+            stringify!(num_variants)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<EnumInfo>())).memory_kind as *const _ as usize },
+        18usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(EnumInfo),
+            "::",
+            stringify!(memory_kind)
+        )
+    );
+}
+#[doc = " Represents a global variable, whose storage lives in the assembly."]
+#[doc = ""]
+#[doc = " <div rustbindgen derive=\"Debug\"></div>"]
+#[repr(C)]
+#[derive(Debug)]
+pub struct GlobalInfo {
+    #[doc = " Global variable name"]
+    pub name: *const ::std::os::raw::c_char,
+    #[doc = " Global variable type"]
+    pub type_info: *const TypeInfo,
+    #[doc = " Global variable address"]
+    pub address: *mut ::std::os::raw::c_void,
+    #[doc = " Whether the global variable is mutable"]
+    pub is_mutable: bool,
+}
+#[test]
+fn bindgen_test_layout_GlobalInfo() {
+    assert_eq!(
+        ::std::mem::size_of::<GlobalInfo>(),
+        32usize,
+        concat!("Size of: ", stringify!(GlobalInfo))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<GlobalInfo>(),
+        8usize,
+        concat!("Alignment of ", stringify!(GlobalInfo))
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<GlobalInfo>())).name as *const _ as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(GlobalInfo),
+            "::",
+            stringify!(name)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<GlobalInfo>())).type_info as *const _ as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(GlobalInfo),
+            "::",
+            stringify!(type_info)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<GlobalInfo>())).address as *const _ as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(GlobalInfo),
+            "::",
+            stringify!(address)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<GlobalInfo>())).is_mutable as *const _ as usize },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(GlobalInfo),
+            "::",
+            stringify!(is_mutable)
+        )
+    );
 }
 #[doc = " Represents a module declaration."]
 #[doc = ""]
@@ -360,12 +569,16 @@ pub struct ModuleInfo {
     pub types: *const *const TypeInfo,
     #[doc = " Number of module types"]
     pub num_types: u32,
+    #[doc = " Module global variables"]
+    pub globals: *const GlobalInfo,
+    #[doc = " Number of module global variables"]
+    pub num_globals: u32,
 }
 #[test]
 fn bindgen_test_layout_ModuleInfo() {
     assert_eq!(
         ::std::mem::size_of::<ModuleInfo>(),
-        40usize,
+        56usize,
         concat!("Size of: ", stringify!(ModuleInfo))
     );
     assert_eq!(
@@ -423,6 +636,26 @@ fn bindgen_test_layout_ModuleInfo() {
             stringify!(num_types)
         )
     );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<ModuleInfo>())).globals as *const _ as usize },
+        40usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(ModuleInfo),
+            "::",
+            stringify!(globals)
+        )
+    );
+    assert_eq!(
+        unsafe { &(*(::std::ptr::null::<ModuleInfo>())).num_globals as *const _ as usize },
+        48usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(ModuleInfo),
+            "::",
+            stringify!(num_globals)
+        )
+    );
 }
 #[doc = " Represents a function dispatch table. This is used for runtime linking."]
 #[doc = ""]
@@ -27,6 +27,37 @@ impl TypeInfo {
         }
     }
 
+    /// Retrieves the type's array information, if available.
+    pub fn as_array(&self) -> Option<&ArrayInfo> {
+        if self.group.is_array() {
+            let ptr = (self as *const TypeInfo).cast::<u8>();
+            let ptr = ptr.wrapping_add(mem::size_of::<TypeInfo>());
+            let offset = ptr.align_offset(mem::align_of::<ArrayInfo>());
+            let ptr = ptr.wrapping_add(offset);
+            Some(unsafe { &*ptr.cast::<ArrayInfo>() })
+        } else {
+            None
+        }
+    }
+
+    /// Retrieves the type's enum information, if available.
+    pub fn as_enum(&self) -> Option<&EnumInfo> {
+        if self.group.is_enum() {
+            let ptr = (self as *const TypeInfo).cast::<u8>();
+            let ptr = ptr.wrapping_add(mem::size_of::<TypeInfo>());
+            let offset = ptr.align_offset(mem::align_of::<EnumInfo>());
+            let ptr = ptr.wrapping_add(offset);
+            Some(unsafe { &*ptr.cast::<EnumInfo>() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether this is the type of Mun's garbage collected `string`.
+    pub fn is_string(&self) -> bool {
+        *self == *<str as HasStaticTypeInfo>::type_info()
+    }
+
     /// Returns the size of the type in bits
     pub fn size_in_bits(&self) -> usize {
         self.size_in_bits
@@ -192,6 +223,23 @@ impl StructInfo {
         }
     }
 
+    /// Returns the previous name of each of the struct's fields, if it was renamed.
+    pub fn field_renamed_from(&self) -> impl Iterator<Item = Option<&str>> {
+        let field_renamed_from = if self.num_fields == 0 || self.field_renamed_from.is_null() {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.field_renamed_from, self.num_fields as usize) }
+        };
+
+        field_renamed_from.iter().map(|n| {
+            if n.is_null() {
+                None
+            } else {
+                Some(unsafe { str::from_utf8_unchecked(CStr::from_ptr(*n).to_bytes()) })
+            }
+        })
+    }
+
     /// Returns the index of the field matching the specified `field_name`.
     pub fn find_field_index(
         type_name: &str,
@@ -212,6 +260,72 @@ impl StructInfo {
     }
 }
 
+impl ArrayInfo {
+    /// Returns the type of the array's elements.
+    pub fn element_type(&self) -> &TypeInfo {
+        unsafe { &*self.element_type }
+    }
+}
+
+impl EnumInfo {
+    /// Returns the enum's variant names.
+    pub fn variant_names(&self) -> impl Iterator<Item = &str> {
+        let variant_names = if self.num_variants == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.variant_names, self.num_variants as usize) }
+        };
+
+        variant_names
+            .iter()
+            .map(|n| unsafe { str::from_utf8_unchecked(CStr::from_ptr(*n).to_bytes()) })
+    }
+
+    /// Returns the enum's variant information. The field offsets of each variant are relative to
+    /// the start of the enum value.
+    pub fn variant_infos(&self) -> &[StructInfo] {
+        if self.num_variants == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.variant_infos, self.num_variants as usize) }
+        }
+    }
+
+    /// Returns the index of the variant matching the specified `variant_name`.
+    pub fn find_variant_index(
+        type_name: &str,
+        enum_info: &EnumInfo,
+        variant_name: &str,
+    ) -> Result<usize, String> {
+        enum_info
+            .variant_names()
+            .enumerate()
+            .find(|(_, name)| *name == variant_name)
+            .map(|(idx, _)| idx)
+            .ok_or_else(|| {
+                format!(
+                    "Enum `{}` does not contain variant `{}`.",
+                    type_name, variant_name
+                )
+            })
+    }
+}
+
+impl GlobalInfo {
+    /// Returns the global variable's name.
+    pub fn name(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(CStr::from_ptr(self.name).to_bytes()) }
+    }
+
+    /// Returns the global variable's type.
+    pub fn type_info(&self) -> &TypeInfo {
+        unsafe { &*self.type_info }
+    }
+}
+
+unsafe impl Send for GlobalInfo {}
+unsafe impl Sync for GlobalInfo {}
+
 impl ModuleInfo {
     /// Returns the module's full path.
     pub fn path(&self) -> &str {
@@ -252,6 +366,15 @@ impl ModuleInfo {
             }
         }
     }
+
+    /// Returns the module's global variables.
+    pub fn globals(&self) -> &[GlobalInfo] {
+        if self.num_globals == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.globals, self.num_globals as usize) }
+        }
+    }
 }
 
 unsafe impl Send for ModuleInfo {}
@@ -382,6 +505,20 @@ mod tests {
         struct_info: StructInfo,
     }
 
+    /// A dummy struct for initializing an array's `TypeInfo`
+    #[allow(dead_code)]
+    struct ArrayTypeInfo {
+        type_info: TypeInfo,
+        array_info: ArrayInfo,
+    }
+
+    /// A dummy struct for initializing an enum's `TypeInfo`
+    #[allow(dead_code)]
+    struct EnumTypeInfo {
+        type_info: TypeInfo,
+        enum_info: EnumInfo,
+    }
+
     fn fake_type_info(name: &CStr, group: TypeGroup, size: u32, alignment: u8) -> TypeInfo {
         TypeInfo {
             guid: FAKE_TYPE_GUID,
@@ -451,6 +588,18 @@ mod tests {
         assert!(!type_info.group.is_fundamental());
     }
 
+    #[test]
+    fn test_type_info_group_array() {
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let type_group = TypeGroup::ArrayTypes;
+        let type_info = fake_type_info(&type_name, type_group, 1, 1);
+
+        assert_eq!(type_info.group, type_group);
+        assert!(type_info.group.is_array());
+        assert!(!type_info.group.is_struct());
+        assert!(!type_info.group.is_fundamental());
+    }
+
     #[test]
     fn test_type_info_eq() {
         let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
@@ -544,6 +693,7 @@ mod tests {
             field_offsets: field_offsets.as_ptr(),
             num_fields: field_names.len() as u16,
             memory_kind,
+            field_renamed_from: ptr::null(),
         }
     }
 
@@ -577,6 +727,28 @@ mod tests {
         }
         assert_eq!(struct_info.field_types(), field_types);
         assert_eq!(struct_info.field_offsets(), field_offsets);
+        assert_eq!(struct_info.field_renamed_from().count(), 0);
+    }
+
+    #[test]
+    fn test_struct_info_field_renamed_from() {
+        let field_name = CString::new(FAKE_FIELD_NAME).expect("Invalid fake field name.");
+        let renamed_from = CString::new("old_field").expect("Invalid fake field name.");
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let type_info = fake_type_info(&type_name, TypeGroup::FundamentalTypes, 1, 1);
+
+        let field_names = &[field_name.as_ptr(), field_name.as_ptr()];
+        let field_types = &[&type_info, &type_info];
+        let field_offsets = &[0, 1];
+        let field_renamed_from = &[renamed_from.as_ptr(), ptr::null()];
+        let mut struct_info =
+            fake_struct_info(field_names, field_types, field_offsets, Default::default());
+        struct_info.field_renamed_from = field_renamed_from.as_ptr();
+
+        assert_eq!(
+            struct_info.field_renamed_from().collect::<Vec<_>>(),
+            vec![Some("old_field"), None]
+        );
     }
 
     #[test]
@@ -595,10 +767,84 @@ mod tests {
         assert_eq!(struct_info.memory_kind, struct_memory_kind);
     }
 
+    #[test]
+    fn test_array_info() {
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let element_type_info = fake_type_info(&type_name, TypeGroup::FundamentalTypes, 32, 4);
+
+        let array_name = CString::new(FAKE_ARRAY_NAME).expect("Invalid fake array name.");
+        let array_type_info = ArrayTypeInfo {
+            type_info: fake_type_info(&array_name, TypeGroup::ArrayTypes, 96, 4),
+            array_info: ArrayInfo {
+                element_type: &element_type_info,
+                length: 3,
+                is_dynamic: false,
+            },
+        };
+
+        let array_info = array_type_info.type_info.as_array().unwrap();
+        assert_eq!(array_info.element_type(), &element_type_info);
+        assert_eq!(array_info.length, 3);
+        assert!(!array_info.is_dynamic);
+        assert!(array_type_info.type_info.as_struct().is_none());
+        assert!(element_type_info.as_array().is_none());
+    }
+
+    #[test]
+    fn test_enum_info() {
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let field_type_info = fake_type_info(&type_name, TypeGroup::FundamentalTypes, 32, 4);
+
+        let field_name = CString::new(FAKE_FIELD_NAME).expect("Invalid fake field name.");
+        let field_names = &[field_name.as_ptr()];
+        let field_types = &[&field_type_info];
+        let field_offsets = &[4];
+        let variant_infos = [
+            fake_struct_info(field_names, field_types, field_offsets, Default::default()),
+            fake_struct_info(&[], &[], &[], Default::default()),
+        ];
+
+        let variant_names = [
+            CString::new("Some").expect("Invalid fake variant name."),
+            CString::new("None").expect("Invalid fake variant name."),
+        ];
+        let variant_name_ptrs = [variant_names[0].as_ptr(), variant_names[1].as_ptr()];
+
+        let enum_name = CString::new(FAKE_ENUM_NAME).expect("Invalid fake enum name.");
+        let enum_type_info = EnumTypeInfo {
+            type_info: fake_type_info(&enum_name, TypeGroup::EnumTypes, 64, 4),
+            enum_info: EnumInfo {
+                variant_names: variant_name_ptrs.as_ptr(),
+                variant_infos: variant_infos.as_ptr(),
+                num_variants: 2,
+                memory_kind: StructMemoryKind::Value,
+            },
+        };
+
+        let enum_info = enum_type_info.type_info.as_enum().unwrap();
+        assert_eq!(
+            enum_info.variant_names().collect::<Vec<_>>(),
+            vec!["Some", "None"]
+        );
+        assert_eq!(enum_info.variant_infos().len(), 2);
+        assert_eq!(enum_info.variant_infos()[0].field_types(), field_types);
+        assert_eq!(enum_info.variant_infos()[0].field_offsets(), field_offsets);
+        assert_eq!(enum_info.variant_infos()[1].num_fields, 0);
+        assert_eq!(enum_info.memory_kind, StructMemoryKind::Value);
+        assert_eq!(
+            EnumInfo::find_variant_index(FAKE_ENUM_NAME, enum_info, "None"),
+            Ok(1)
+        );
+        assert!(EnumInfo::find_variant_index(FAKE_ENUM_NAME, enum_info, "Other").is_err());
+        assert!(enum_type_info.type_info.as_struct().is_none());
+        assert!(field_type_info.as_enum().is_none());
+    }
+
     fn fake_module_info(
         path: &CStr,
         functions: &[FunctionDefinition],
         types: &[&TypeInfo],
+        globals: &[GlobalInfo],
     ) -> ModuleInfo {
         ModuleInfo {
             path: path.as_ptr(),
@@ -606,16 +852,20 @@ mod tests {
             num_functions: functions.len() as u32,
             types: types.as_ptr().cast::<*const TypeInfo>(),
             num_types: types.len() as u32,
+            globals: globals.as_ptr(),
+            num_globals: globals.len() as u32,
         }
     }
 
     const FAKE_MODULE_PATH: &str = "path::to::module";
     const FAKE_STRUCT_NAME: &str = "StructName";
+    const FAKE_ARRAY_NAME: &str = "[type-name; 3]";
+    const FAKE_ENUM_NAME: &str = "EnumName";
 
     #[test]
     fn test_module_info_path() {
         let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
-        let module = fake_module_info(&module_path, &[], &[]);
+        let module = fake_module_info(&module_path, &[], &[], &[]);
 
         assert_eq!(module.path(), FAKE_MODULE_PATH);
     }
@@ -625,7 +875,7 @@ mod tests {
         let functions = &[];
         let types = &[];
         let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
-        let module = fake_module_info(&module_path, functions, types);
+        let module = fake_module_info(&module_path, functions, types, &[]);
 
         assert_eq!(module.functions().len(), functions.len());
         assert_eq!(module.types().len(), types.len());
@@ -652,7 +902,7 @@ mod tests {
         let types = &[unsafe { mem::transmute(&struct_type_info) }];
 
         let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
-        let module = fake_module_info(&module_path, functions, types);
+        let module = fake_module_info(&module_path, functions, types, &[]);
 
         let result_functions = module.functions();
         assert_eq!(result_functions.len(), functions.len());
@@ -681,6 +931,35 @@ mod tests {
                 assert_eq!(lhs_struct.field_types(), rhs_struct.field_types());
             }
         }
+        assert!(module.globals().is_empty());
+    }
+
+    const FAKE_GLOBAL_NAME: &str = "GRAVITY";
+
+    #[test]
+    fn test_module_info_globals() {
+        let type_name = CString::new(FAKE_TYPE_NAME).expect("Invalid fake type name.");
+        let type_info = fake_type_info(&type_name, TypeGroup::FundamentalTypes, 32, 4);
+
+        let mut value = 9.81f32;
+        let global_name = CString::new(FAKE_GLOBAL_NAME).expect("Invalid fake global name.");
+        let global_info = GlobalInfo {
+            name: global_name.as_ptr(),
+            type_info: &type_info,
+            address: (&mut value as *mut f32).cast::<c_void>(),
+            is_mutable: true,
+        };
+        let globals = &[global_info];
+
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let module = fake_module_info(&module_path, &[], &[], globals);
+
+        let result_globals = module.globals();
+        assert_eq!(result_globals.len(), globals.len());
+        assert_eq!(result_globals[0].name(), FAKE_GLOBAL_NAME);
+        assert_eq!(result_globals[0].type_info(), &type_info);
+        assert_eq!(result_globals[0].address, globals[0].address);
+        assert!(result_globals[0].is_mutable);
     }
 
     fn fake_dispatch_table(
@@ -907,7 +1186,7 @@ mod tests {
     #[test]
     fn test_assembly_info_dependencies() {
         let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
-        let module = fake_module_info(&module_path, &[], &[]);
+        let module = fake_module_info(&module_path, &[], &[], &[]);
 
         let dispatch_table = fake_dispatch_table(&[], &mut []);
 
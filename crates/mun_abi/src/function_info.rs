@@ -57,11 +57,11 @@ pub trait IntoFunctionDefinition {
 
 macro_rules! into_function_info_impl {
     ($(
-        extern "C" fn($($T:ident),*) -> $R:ident;
+        extern $abi:literal fn($($T:ident),*) -> $R:ident;
     )+) => {
         $(
             impl<$R: HasStaticTypeInfo, $($T: HasStaticTypeInfo,)*> IntoFunctionDefinition
-            for extern "C" fn($($T),*) -> $R
+            for extern $abi fn($($T),*) -> $R
             {
                 fn into<S: AsRef<str>>(self, name: S) -> (FunctionDefinition, FunctionDefinitionStorage) {
                     FunctionDefinitionStorage::new_function(
@@ -74,7 +74,7 @@ macro_rules! into_function_info_impl {
             }
 
             impl<$($T: HasStaticTypeInfo,)*> IntoFunctionDefinition
-            for extern "C" fn($($T),*)
+            for extern $abi fn($($T),*)
             {
                 fn into<S: AsRef<str>>(self, name: S) -> (FunctionDefinition, FunctionDefinitionStorage) {
                     FunctionDefinitionStorage::new_function(
@@ -101,4 +101,17 @@ into_function_info_impl! {
     extern "C" fn(A, B, C, D, E, F, G, H) -> R;
     extern "C" fn(A, B, C, D, E, F, G, H, I) -> R;
     extern "C" fn(A, B, C, D, E, F, G, H, I, J) -> R;
+
+    // Functions that can unwind, e.g. to report a runtime error from within Mun code
+    extern "C-unwind" fn() -> R;
+    extern "C-unwind" fn(A) -> R;
+    extern "C-unwind" fn(A, B) -> R;
+    extern "C-unwind" fn(A, B, C) -> R;
+    extern "C-unwind" fn(A, B, C, D) -> R;
+    extern "C-unwind" fn(A, B, C, D, E) -> R;
+    extern "C-unwind" fn(A, B, C, D, E, F) -> R;
+    extern "C-unwind" fn(A, B, C, D, E, F, G) -> R;
+    extern "C-unwind" fn(A, B, C, D, E, F, G, H) -> R;
+    extern "C-unwind" fn(A, B, C, D, E, F, G, H, I) -> R;
+    extern "C-unwind" fn(A, B, C, D, E, F, G, H, I, J) -> R;
 }
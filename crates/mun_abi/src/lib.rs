@@ -13,6 +13,7 @@ mod type_info;
 
 pub use autogen::*;
 pub use function_info::{FunctionDefinitionStorage, IntoFunctionDefinition};
+pub use static_type_map::StaticTypeMap;
 pub use type_info::HasStaticTypeInfo;
 
 /// The Mun ABI prelude
@@ -73,6 +74,10 @@ pub enum TypeGroup {
     FundamentalTypes = 0,
     /// Struct types (i.e. record, tuple, or unit structs)
     StructTypes = 1,
+    /// Array types (i.e. `[T; N]` or `[T]`)
+    ArrayTypes = 2,
+    /// Enum types (i.e. tagged unions of record, tuple, or unit variants)
+    EnumTypes = 3,
 }
 
 impl TypeGroup {
@@ -91,4 +96,20 @@ impl TypeGroup {
             _ => false,
         }
     }
+
+    /// Returns whether this is an array type.
+    pub fn is_array(self) -> bool {
+        match self {
+            TypeGroup::ArrayTypes => true,
+            _ => false,
+        }
+    }
+
+    /// Returns whether this is an enum type.
+    pub fn is_enum(self) -> bool {
+        match self {
+            TypeGroup::EnumTypes => true,
+            _ => false,
+        }
+    }
 }
@@ -3,11 +3,20 @@ use std::any::TypeId;
 use std::cell::RefCell;
 use std::collections::HashMap;
 
+/// A map that stores a single `'static` value per type. This can be used to emulate generic
+/// statics, which Rust does not support.
 pub struct StaticTypeMap<T: 'static> {
     map: ReentrantMutex<RefCell<HashMap<TypeId, &'static T>>>,
 }
 
+impl<T: 'static> Default for StaticTypeMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: 'static> StaticTypeMap<T> {
+    /// Constructs an empty `StaticTypeMap`.
     pub fn new() -> Self {
         Self {
             map: ReentrantMutex::new(RefCell::new(HashMap::default())),
@@ -157,6 +157,33 @@ impl_has_type_info_name!(
     TypeInfo => "TypeInfo"
 );
 
+/// Mun's `string` is an immutable, garbage collected string. A value of type `string` is passed
+/// around as an object pointer, i.e. a `*const *mut std::ffi::c_void`.
+impl HasStaticTypeInfo for str {
+    fn type_info() -> &'static TypeInfo {
+        static TYPE_INFO: OnceCell<TypeInfo> = OnceCell::new();
+        TYPE_INFO.get_or_init(|| {
+            static TYPE_INFO_NAME: OnceCell<CString> = OnceCell::new();
+            let type_info_name: &'static CString =
+                TYPE_INFO_NAME.get_or_init(|| CString::new("core::string").unwrap());
+
+            TypeInfo {
+                guid: Guid {
+                    b: md5::compute(&type_info_name.as_bytes()).0,
+                },
+                name: type_info_name.as_ptr(),
+                group: TypeGroup::FundamentalTypes,
+                size_in_bits: (std::mem::size_of::<*const *mut std::ffi::c_void>() * 8)
+                    .try_into()
+                    .expect("size of T is larger than the maximum allowed ABI size. Please file a bug."),
+                alignment: (std::mem::align_of::<*const *mut std::ffi::c_void>())
+                    .try_into()
+                    .expect("alignment of T is larger than the maximum allowed ABI size. Please file a bug."),
+            }
+        })
+    }
+}
+
 #[cfg(target_pointer_width = "64")]
 impl HasStaticTypeInfo for usize {
     fn type_info() -> &'static TypeInfo {
@@ -187,7 +214,7 @@ impl HasStaticTypeInfo for isize {
 
 #[cfg(test)]
 mod tests {
-    use super::HasStaticTypeInfoName;
+    use super::{HasStaticTypeInfo, HasStaticTypeInfoName};
 
     #[test]
     fn ptr_test() {
@@ -200,4 +227,21 @@ mod tests {
         let ty = <*const *const std::ffi::c_void>::type_name();
         assert_eq!(ty.to_str().unwrap(), "*const *const core::void");
     }
+
+    #[test]
+    fn string_test() {
+        let type_info = <str as HasStaticTypeInfo>::type_info();
+        assert_eq!(type_info.name(), "core::string");
+        assert_eq!(
+            type_info.guid.b,
+            md5::compute("core::string".as_bytes()).0,
+            "the guid must be stable across compilations"
+        );
+        assert_eq!(
+            type_info.size_in_bytes(),
+            std::mem::size_of::<*const *mut std::ffi::c_void>()
+        );
+        assert!(type_info.is_string());
+        assert!(!<u8 as HasStaticTypeInfo>::type_info().is_string());
+    }
 }
@@ -12,6 +12,7 @@ use inkwell::{
     AddressSpace, OptimizationLevel,
 };
 use mun_target::spec;
+use std::collections::HashSet;
 use std::io::{self, Write};
 use std::{
     path::{Path, PathBuf},
@@ -99,7 +100,8 @@ pub struct ModuleBuilder<'a, D: IrDatabase> {
 }
 
 impl<'a, D: IrDatabase> ModuleBuilder<'a, D> {
-    /// Constructs module for the given `hir::FileId` at the specified output file location.
+    /// Constructs module for the package with the given root `hir::FileId` at the specified output
+    /// file location.
     pub fn new(db: &'a D, file_id: FileId) -> Result<Self, failure::Error> {
         let target = db.target();
 
@@ -140,25 +142,41 @@ impl<'a, D: IrDatabase> ModuleBuilder<'a, D> {
 
     /// Constructs an object file.
     pub fn build(self) -> Result<ObjectFile, failure::Error> {
-        let group_ir = self.db.group_ir(self.file_id);
-        let file = self.db.file_ir(self.file_id);
+        let package = hir::Module::from(self.file_id).package(self.db);
+        let group_ir = self.db.group_ir(package);
 
         // Clone the LLVM modules so that we can modify it without modifying the cached value.
         self.assembly_module
             .link_in_module(group_ir.llvm_module.clone())
             .map_err(|e| CodeGenerationError::ModuleLinkerError(e.to_string()))?;
 
-        self.assembly_module
-            .link_in_module(file.llvm_module.clone())
-            .map_err(|e| CodeGenerationError::ModuleLinkerError(e.to_string()))?;
+        // Link the modules of all files in the package
+        let mut api = HashSet::new();
+        for module in package.modules(self.db) {
+            let file = self.db.file_ir(module.file_id());
+            self.assembly_module
+                .link_in_module(file.llvm_module.clone())
+                .map_err(|e| CodeGenerationError::ModuleLinkerError(e.to_string()))?;
+            api.extend(file.api.iter().cloned());
+        }
+
+        // The path of the assembly is the name of its root module
+        let module_path = self
+            .db
+            .file_relative_path(package.root_module(self.db).file_id())
+            .file_stem()
+            .unwrap_or_default()
+            .to_owned();
 
         // Generate the `get_info` method.
         symbols::gen_reflection_ir(
             self.db,
             &self.assembly_module,
-            &file.api,
+            &module_path,
+            &api,
             &group_ir.dispatch_table,
             &group_ir.type_table,
+            &group_ir.globals,
         );
 
         // Optimize the assembly module
@@ -242,6 +260,31 @@ pub(crate) fn gen_string_array(
     }
 }
 
+/// Generates a global array from the specified list of optional strings, in which absent strings
+/// are null. If all strings are absent, a null pointer is returned instead.
+pub(crate) fn gen_optional_string_array(
+    module: &Module,
+    strings: Vec<Option<String>>,
+    name: &str,
+) -> PointerValue {
+    let str_type = module.get_context().i8_type().ptr_type(AddressSpace::Const);
+
+    if strings.iter().all(Option::is_none) {
+        str_type.ptr_type(AddressSpace::Const).const_null()
+    } else {
+        let strings = strings
+            .into_iter()
+            .map(|s| match s {
+                Some(s) => intern_string(module, &s, name),
+                None => str_type.const_null(),
+            })
+            .collect::<Vec<PointerValue>>();
+
+        let strings_ir = str_type.const_array(&strings);
+        gen_global(module, &strings_ir, name).as_pointer_value()
+    }
+}
+
 /// Generates a global array from the specified list of struct pointers
 pub(crate) fn gen_struct_ptr_array(
     module: &Module,
@@ -23,7 +23,7 @@ fn gen_prototype_from_function<D: IrDatabase>(
     types: &AbiTypes,
     function: hir::Function,
 ) -> StructValue {
-    let name = function.name(db).to_string();
+    let name = function.full_name(db);
 
     let name_ir = intern_string(&module, &name, &name);
     let _visibility = match function.visibility(db) {
@@ -168,7 +168,7 @@ fn get_function_definition_array<'a, D: IrDatabase>(
 ) -> GlobalValue {
     let function_infos: Vec<StructValue> = functions
         .map(|f| {
-            let name = f.name(db).to_string();
+            let name = f.full_name(db);
             // Get the function from the cloned module and modify the linkage of the function.
             let value = module
                 // If a wrapper function exists, use that (required for struct types)
@@ -192,6 +192,46 @@ fn get_function_definition_array<'a, D: IrDatabase>(
     gen_global(module, &function_infos, "fn.get_info.functions")
 }
 
+/// Construct a global that holds information about all global variables. e.g.:
+/// MunGlobalInfo[] globals = { ... }
+fn gen_global_info_array<D: IrDatabase>(
+    db: &D,
+    module: &Module,
+    types: &AbiTypes,
+    globals: &[hir::Static],
+) -> GlobalValue {
+    let context = module.get_context();
+    let global_infos: Vec<StructValue> = globals
+        .iter()
+        .map(|global| {
+            let name = global.full_name(db);
+            let name_ir = intern_string(&module, &name, &format!("global::<{}>::name", name));
+            let type_info_ir = TypeTable::get(module, &db.type_info(global.ty(db)))
+                .unwrap()
+                .as_pointer_value();
+
+            // Get the global from the linked module, it was defined in the IR of the package.
+            let address = module
+                .get_global(&name)
+                .unwrap()
+                .as_pointer_value()
+                .const_cast(context.i8_type().ptr_type(AddressSpace::Generic));
+
+            types.global_info_type.const_named_struct(&[
+                name_ir.into(),
+                type_info_ir.into(),
+                address.into(),
+                context
+                    .bool_type()
+                    .const_int(global.is_mut(db) as u64, false)
+                    .into(),
+            ])
+        })
+        .collect();
+    let global_infos = types.global_info_type.const_array(&global_infos);
+    gen_global(module, &global_infos, "module_info::globals")
+}
+
 /// Generate the dispatch table information. e.g.:
 /// ```c
 /// MunDispatchTable dispatchTable = { ... }
@@ -251,9 +291,11 @@ fn gen_dispatch_table(
 pub(super) fn gen_reflection_ir(
     db: &impl IrDatabase,
     module: &Module,
+    module_path: &str,
     api: &HashSet<hir::Function>,
     dispatch_table: &DispatchTable,
     type_table: &TypeTable,
+    globals: &[hir::Static],
 ) {
     // Get all the types
     let abi_types = gen_abi_types(&module.get_context());
@@ -267,9 +309,11 @@ pub(super) fn gen_reflection_ir(
         type_table.ty().ptr_type(AddressSpace::Const).const_null()
     };
 
+    let global_info = gen_global_info_array(db, module, &abi_types, globals);
+
     // Construct the module info struct
     let module_info = abi_types.module_info_type.const_named_struct(&[
-        intern_string(module, "", "module_info::path").into(),
+        intern_string(module, module_path, "module_info::path").into(),
         function_info.as_pointer_value().into(),
         module
             .get_context()
@@ -282,6 +326,12 @@ pub(super) fn gen_reflection_ir(
             .i32_type()
             .const_int(type_table.num_types() as u64, false)
             .into(),
+        global_info.as_pointer_value().into(),
+        module
+            .get_context()
+            .i32_type()
+            .const_int(globals.len() as u64, false)
+            .into(),
     ]);
 
     // Construct the dispatch table struct
@@ -10,7 +10,7 @@ use inkwell::{
     types::{AnyTypeEnum, StructType},
     OptimizationLevel,
 };
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 /// The `IrDatabase` enables caching of intermediate in the process of LLVM IR generation. It uses
 /// [salsa](https://github.com/salsa-rs/salsa) for this purpose.
@@ -24,6 +24,16 @@ pub trait IrDatabase: hir::HirDatabase {
     #[salsa::input]
     fn optimization_lvl(&self) -> OptimizationLevel;
 
+    /// Gets whether debug information is generated, which enables debuggers and profilers to map
+    /// machine code back to the source code.
+    #[salsa::input]
+    fn debug_info(&self) -> bool;
+
+    /// Gets the directory that contains the source files of the package. The debug information
+    /// refers to source files relative to this directory.
+    #[salsa::input]
+    fn source_dir(&self) -> PathBuf;
+
     /// Returns the target machine's data layout for code generation.
     #[salsa::invoke(crate::code_gen::target_data_query)]
     fn target_data(&self) -> Arc<TargetData>;
@@ -32,15 +42,18 @@ pub trait IrDatabase: hir::HirDatabase {
     #[salsa::invoke(crate::ir::ty::ir_query)]
     fn type_ir(&self, ty: hir::Ty, params: CodeGenParams) -> AnyTypeEnum;
 
-    /// Given a struct, return the corresponding IR type.
+    /// Given a struct and the type arguments of one of its instantiations, return the
+    /// corresponding IR type.
     #[salsa::invoke(crate::ir::ty::struct_ty_query)]
-    fn struct_ty(&self, s: hir::Struct) -> StructType;
+    fn struct_ty(&self, s: hir::Struct, substs: hir::Substs) -> StructType;
+
+    /// Given an enum, return the corresponding IR type.
+    #[salsa::invoke(crate::ir::ty::enum_ty_query)]
+    fn enum_ty(&self, e: hir::Enum) -> StructType;
 
-    /// Given a `hir::FileId` generate code that is shared among the group of files.
-    /// TODO: Currently, a group always consists of a single file. Need to add support for multiple
-    /// files using something like `FileGroupId`.
+    /// Given a `hir::Package` generate code that is shared among the files of the package.
     #[salsa::invoke(crate::ir::file_group::ir_query)]
-    fn group_ir(&self, file: hir::FileId) -> Arc<FileGroupIR>;
+    fn group_ir(&self, package: hir::Package) -> Arc<FileGroupIR>;
 
     /// Given a `hir::FileId` generate code for the module.
     #[salsa::invoke(crate::ir::file::ir_query)]
@@ -21,4 +21,37 @@ pub trait Intrinsic: Sync {
 intrinsics! {
     /// Allocates memory for the specified `type` in the allocator referred to by `alloc_handle`.
     pub fn new(type: *const TypeInfo, alloc_handle: *mut ffi::c_void) -> *const *mut ffi::c_void;
+
+    /// Allocates a dynamic array of the specified `type` with room for `capacity` elements in the
+    /// allocator referred to by `alloc_handle`.
+    pub fn new_array(type: *const TypeInfo, capacity: usize, alloc_handle: *mut ffi::c_void) -> *const *mut ffi::c_void;
+
+    /// Ensures that the dynamic array referred to by `handle` has room for at least `capacity`
+    /// elements.
+    pub fn array_reserve(handle: *const *mut ffi::c_void, capacity: usize, alloc_handle: *mut ffi::c_void) -> ();
+
+    /// Reports an out of bounds access at `index` into an array of length `length`, at `line` of
+    /// the function named by the `function_len` UTF-8 encoded bytes at `function`. Unwinds to the
+    /// caller of the Mun code.
+    pub fn index_out_of_bounds(index: usize, length: usize, function: *const u8, function_len: usize, line: u32) -> ();
+
+    /// Reports a runtime error with the `message_len` UTF-8 encoded bytes at `message` as its
+    /// message, at `line` of the function named by the `function_len` UTF-8 encoded bytes at
+    /// `function`. Unwinds to the caller of the Mun code.
+    pub fn trap(message: *const u8, message_len: usize, function: *const u8, function_len: usize, line: u32) -> ();
+
+    /// Allocates a string in the allocator referred to by `alloc_handle` that contains a copy of
+    /// the `length` UTF-8 encoded bytes at `bytes`.
+    pub fn new_string(bytes: *const u8, length: usize, alloc_handle: *mut ffi::c_void) -> *const *mut ffi::c_void;
+
+    /// Allocates a string in the allocator referred to by `alloc_handle` that contains the
+    /// concatenation of the strings `lhs` and `rhs`.
+    pub fn string_concat(lhs: *const *mut ffi::c_void, rhs: *const *mut ffi::c_void, alloc_handle: *mut ffi::c_void) -> *const *mut ffi::c_void;
+
+    /// Returns whether the strings `lhs` and `rhs` are equal.
+    pub fn string_eq(lhs: *const *mut ffi::c_void, rhs: *const *mut ffi::c_void) -> bool;
+
+    /// Notifies the allocator referred to by `alloc_handle` that a reference might have been
+    /// stored in the memory of the object referred to by `handle`.
+    pub fn write_barrier(handle: *const *mut ffi::c_void, alloc_handle: *mut ffi::c_void) -> ();
 }
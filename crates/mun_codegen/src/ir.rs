@@ -9,11 +9,13 @@ use inkwell::AddressSpace;
 pub(crate) mod abi_types;
 pub mod adt;
 pub mod body;
+pub(crate) mod debug_info;
 #[macro_use]
 pub(crate) mod dispatch_table;
 pub mod file;
 pub(crate) mod file_group;
 pub mod function;
+pub(crate) mod global;
 mod intrinsics;
 pub mod ty;
 pub(crate) mod type_table;
@@ -12,6 +12,9 @@ pub(crate) struct AbiTypes {
     pub function_prototype_type: StructType,
     pub function_definition_type: StructType,
     pub struct_info_type: StructType,
+    pub array_info_type: StructType,
+    pub enum_info_type: StructType,
+    pub global_info_type: StructType,
     pub module_info_type: StructType,
     pub dispatch_table_type: StructType,
     pub assembly_info_type: StructType,
@@ -89,6 +92,42 @@ pub(crate) fn gen_abi_types(context: &Context) -> AbiTypes {
             context.i16_type().ptr_type(AddressSpace::Const).into(), // field_offsets
             context.i16_type().into(),                     // num_fields
             context.i8_type().into(),                      // memory_kind
+            str_type.ptr_type(AddressSpace::Const).into(), // field_renamed_from
+        ],
+        false,
+    );
+
+    // Construct the `MunArrayInfo` struct
+    let array_info_type = context.opaque_struct_type("struct.MunArrayInfo");
+    array_info_type.set_body(
+        &[
+            type_info_ptr_type.into(),  // element_type
+            context.i32_type().into(),  // length
+            context.bool_type().into(), // is_dynamic
+        ],
+        false,
+    );
+
+    // Construct the `MunEnumInfo` struct
+    let enum_info_type = context.opaque_struct_type("struct.MunEnumInfo");
+    enum_info_type.set_body(
+        &[
+            str_type.ptr_type(AddressSpace::Const).into(), // variant_names
+            struct_info_type.ptr_type(AddressSpace::Const).into(), // variant_infos
+            context.i16_type().into(),                     // num_variants
+            context.i8_type().into(),                      // memory_kind
+        ],
+        false,
+    );
+
+    // Construct the `MunGlobalInfo` struct
+    let global_info_type = context.opaque_struct_type("struct.MunGlobalInfo");
+    global_info_type.set_body(
+        &[
+            str_type.into(),                                          // name
+            type_info_ptr_type.into(),                                // type_info
+            context.i8_type().ptr_type(AddressSpace::Generic).into(), // address
+            context.bool_type().into(),                               // is_mutable
         ],
         false,
     );
@@ -104,6 +143,8 @@ pub(crate) fn gen_abi_types(context: &Context) -> AbiTypes {
             context.i32_type().into(), // num_functions
             type_info_ptr_type.ptr_type(AddressSpace::Const).into(), // types
             context.i32_type().into(), // num_types
+            global_info_type.ptr_type(AddressSpace::Const).into(), // globals
+            context.i32_type().into(), // num_globals
         ],
         false,
     );
@@ -145,6 +186,9 @@ pub(crate) fn gen_abi_types(context: &Context) -> AbiTypes {
         function_prototype_type,
         function_definition_type,
         struct_info_type,
+        array_info_type,
+        enum_info_type,
+        global_info_type,
         module_info_type,
         dispatch_table_type,
         assembly_info_type,
@@ -1,18 +1,28 @@
 //use crate::ir::module::Types;
-use crate::ir::try_convert_any_to_basic;
+use crate::ir::{try_convert_any_to_basic, ty::enum_variant_ty};
 use crate::{CodeGenParams, IrDatabase};
 use inkwell::types::{BasicTypeEnum, StructType};
 
-pub(super) fn gen_struct_decl(db: &impl IrDatabase, s: hir::Struct) -> StructType {
-    let struct_type = db.struct_ty(s);
+/// Generates the body of the instantiation of the struct `s` with the type arguments `substs`.
+pub(super) fn gen_struct_decl(
+    db: &impl IrDatabase,
+    s: hir::Struct,
+    substs: &hir::Substs,
+) -> StructType {
+    let struct_type = db.struct_ty(s, substs.clone());
     if struct_type.is_opaque() {
-        let field_types: Vec<BasicTypeEnum> = s
+        let fields: Vec<hir::Ty> = s
             .fields(db)
             .iter()
-            .map(|field| {
-                let field_type = field.ty(db);
+            .map(|field| field.ty(db).subst(substs))
+            .collect();
+
+        let field_types: Vec<BasicTypeEnum> = fields
+            .iter()
+            .map(|field_type| {
+                gen_value_type_decl(db, field_type);
                 try_convert_any_to_basic(db.type_ir(
-                    field_type,
+                    field_type.clone(),
                     CodeGenParams {
                         make_marshallable: false,
                     },
@@ -22,6 +32,83 @@ pub(super) fn gen_struct_decl(db: &impl IrDatabase, s: hir::Struct) -> StructTyp
             .collect();
 
         struct_type.set_body(&field_types, false);
+
+        // Garbage collected instantiations of generic structs are only referred to by pointer,
+        // but their layout must be known to allocate them. This is done after setting the body
+        // to support recursive structs.
+        for field_type in fields.iter() {
+            gen_generic_struct_decls(db, field_type);
+        }
     }
     struct_type
 }
+
+/// Generates the bodies of all instantiations of generic structs that occur in `ty`, e.g.
+/// `Pair<f32, i64>` in `[Pair<f32, i64>]`.
+pub(super) fn gen_generic_struct_decls(db: &impl IrDatabase, ty: &hir::Ty) {
+    if let hir::Ty::Apply(hir::ApplicationTy { ctor, parameters }) = ty {
+        for ty in parameters.iter() {
+            gen_generic_struct_decls(db, ty);
+        }
+        if let hir::TypeCtor::Struct(s) = ctor {
+            if !parameters.is_empty() {
+                gen_struct_decl(db, *s, parameters);
+            }
+        }
+    }
+}
+
+/// Generates the body of an enum type. An enum is stored as a `u8` tag, which contains the index
+/// of the active variant, followed by a payload that is large and aligned enough to store the
+/// fields of each of its variants.
+pub(super) fn gen_enum_decl(db: &impl IrDatabase, e: hir::Enum) -> StructType {
+    let enum_type = db.enum_ty(e);
+    if enum_type.is_opaque() {
+        let context = db.context();
+        let target = db.target_data();
+
+        let (payload_size, payload_alignment) =
+            e.variants(db)
+                .into_iter()
+                .fold((0, 1), |(size, alignment), variant| {
+                    // The memory layout of the variant's fields must be known to compute its size
+                    for field in variant.fields(db).iter() {
+                        gen_value_type_decl(db, &field.ty(db));
+                    }
+
+                    let variant_type = enum_variant_ty(db, variant);
+                    (
+                        size.max(target.get_abi_size(&variant_type)),
+                        alignment.max(target.get_abi_alignment(&variant_type)),
+                    )
+                });
+
+        // Store the payload as an array of integers that have the largest alignment of all
+        // variants.
+        let payload_element_type = context.custom_width_int_type(payload_alignment * 8);
+        let payload_length =
+            (payload_size + u64::from(payload_alignment) - 1) / u64::from(payload_alignment);
+        let payload_type = payload_element_type.array_type(payload_length as u32);
+
+        enum_type.set_body(&[context.i8_type().into(), payload_type.into()], false);
+    }
+    enum_type
+}
+
+/// Generates the body of `ty`, if it is a value struct or enum whose memory is stored inline. The
+/// elements of a tuple are stored inline as well.
+fn gen_value_type_decl(db: &impl IrDatabase, ty: &hir::Ty) {
+    if let hir::ty_app!(hir::TypeCtor::Struct(s), substs) = ty {
+        if s.data(db).memory_kind == hir::StructMemoryKind::Value {
+            gen_struct_decl(db, *s, substs);
+        }
+    } else if let Some(e) = ty.as_enum() {
+        if e.data(db).memory_kind == hir::StructMemoryKind::Value {
+            gen_enum_decl(db, e);
+        }
+    } else if let Some(element_tys) = ty.as_tuple() {
+        for element_ty in element_tys.iter() {
+            gen_value_type_decl(db, element_ty);
+        }
+    }
+}
@@ -1,15 +1,37 @@
 use crate::intrinsics;
+use crate::ir::intrinsics::needs_write_barrier;
 use crate::{
-    ir::{dispatch_table::DispatchTable, try_convert_any_to_basic, type_table::TypeTable},
-    CodeGenParams, IrDatabase,
+    code_gen::gen_global,
+    ir::{
+        debug_info::DebugInfo,
+        dispatch_table::DispatchTable,
+        function::{self, FunctionInstance},
+        global::gen_const_value,
+        try_convert_any_to_basic,
+        ty::{
+            closure_fn_ty, closure_ty, closure_type_info, enum_variant_ty, fn_ptr_ty,
+            object_ptr_ty, string_ty,
+        },
+        type_table::TypeTable,
+        IsIrType,
+    },
+    type_info::TypeInfo,
+    CodeGenParams, IrDatabase, Module,
 };
 use hir::{
-    ArenaId, ArithOp, BinaryOp, Body, CmpOp, Expr, ExprId, HirDisplay, InferenceResult, Literal,
-    LogicOp, Name, Ordering, Pat, PatId, Path, Resolution, Resolver, Statement, TypeCtor, UnaryOp,
+    line_index::LineCol, ArenaId, ArithOp, BinaryOp, Body, BuiltinMethod, CmpOp, Expr, ExprId,
+    FnSig, HirDisplay, InferenceResult, Literal, LogicOp, MatchArm, MethodResolution, Name,
+    Ordering, Pat, PatId, Path, RangeOp, Resolution, Resolver, Statement, Substs, TypeCtor,
+    UnaryOp,
 };
 use inkwell::{
     builder::Builder,
-    values::{BasicValueEnum, CallSiteValue, FloatValue, FunctionValue, IntValue, StructValue},
+    debug_info::{AsDIScope, DILocation, DISubprogram},
+    module::Linkage,
+    types::{BasicType, IntType, StructType},
+    values::{
+        BasicValue, BasicValueEnum, CallSiteValue, FloatValue, FunctionValue, IntValue, StructValue,
+    },
     AddressSpace, FloatPredicate, IntPredicate,
 };
 use std::{collections::HashMap, sync::Arc};
@@ -31,10 +53,12 @@ pub(crate) struct ExternalGlobals {
     pub alloc_handle: Option<GlobalValue>,
     pub dispatch_table: Option<GlobalValue>,
     pub type_table: Option<GlobalValue>,
+    pub globals: HashMap<hir::Static, GlobalValue>,
 }
 
 pub(crate) struct BodyIrGenerator<'a, 'b, D: IrDatabase> {
     db: &'a D,
+    module: &'a Module,
     body: Arc<Body>,
     infer: Arc<InferenceResult>,
     builder: Builder,
@@ -42,30 +66,39 @@ pub(crate) struct BodyIrGenerator<'a, 'b, D: IrDatabase> {
     pat_to_param: HashMap<PatId, inkwell::values::BasicValueEnum>,
     pat_to_local: HashMap<PatId, inkwell::values::PointerValue>,
     pat_to_name: HashMap<PatId, String>,
-    function_map: &'a HashMap<hir::Function, FunctionValue>,
+    function_map: &'a HashMap<FunctionInstance, FunctionValue>,
     dispatch_table: &'b DispatchTable,
     type_table: &'b TypeTable,
     active_loop: Option<LoopInfo>,
-    hir_function: hir::Function,
+    instance: FunctionInstance,
     params: CodeGenParams,
     external_globals: ExternalGlobals,
+    debug_info: Option<&'b DebugInfo>,
+    /// The debug information of the function for which IR is generated, if any
+    debug_scope: Option<DISubprogram>,
+    /// The handle of the garbage collected object that contains the place that was last generated
+    /// by `gen_place_expr`, if any
+    place_owner: Option<PointerValue>,
 }
 
 impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         db: &'a D,
-        function: (hir::Function, FunctionValue),
-        function_map: &'a HashMap<hir::Function, FunctionValue>,
+        module: &'a Module,
+        function: (&FunctionInstance, FunctionValue),
+        function_map: &'a HashMap<FunctionInstance, FunctionValue>,
         dispatch_table: &'b DispatchTable,
         type_table: &'b TypeTable,
         params: CodeGenParams,
         external_globals: ExternalGlobals,
+        debug_info: Option<&'b DebugInfo>,
     ) -> Self {
-        let (hir_function, ir_function) = function;
+        let (instance, ir_function) = function;
 
-        // Get the type information from the `hir::Function`
-        let body = hir_function.body(db);
-        let infer = hir_function.infer(db);
+        // Get the type information from the `FunctionInstance`
+        let body = instance.function.body(db);
+        let infer = instance.infer(db);
 
         // Construct a builder for the IR function
         let context = db.context();
@@ -75,6 +108,7 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
 
         BodyIrGenerator {
             db,
+            module,
             body,
             infer,
             builder,
@@ -86,39 +120,26 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
             dispatch_table,
             type_table,
             active_loop: None,
-            hir_function,
+            instance: instance.clone(),
             params,
             external_globals,
+            debug_info,
+            debug_scope: None,
+            place_owner: None,
         }
     }
 
     /// Generates IR for the body of the function.
     pub fn gen_fn_body(&mut self) {
+        let fn_sig = self.instance.ty(self.db).callable_sig(self.db).unwrap();
+        self.gen_debug_function(self.instance.function.line_col(self.db), &fn_sig);
+
         // Iterate over all parameters and their type and store them so we can reference them
         // later in code.
-        for (i, (pat, _ty)) in self.body.params().iter().enumerate() {
-            let body = self.body.clone(); // Avoid borrow issues
-
-            match &body[*pat] {
-                Pat::Bind { name } => {
-                    let name = name.to_string();
-                    let param = self.fn_value.get_nth_param(i as u32).unwrap();
-                    let builder = self.new_alloca_builder();
-                    let param_ptr = builder.build_alloca(param.get_type(), &name);
-                    builder.build_store(param_ptr, param);
-                    self.pat_to_local.insert(*pat, param_ptr);
-                    self.pat_to_name.insert(*pat, name);
-                }
-                Pat::Wild => {
-                    // Wildcard patterns cannot be referenced from code. So nothing to do.
-                }
-                Pat::Path(_) => unreachable!(
-                    "Path patterns are not supported as parameters, are we missing a diagnostic?"
-                ),
-                Pat::Missing => unreachable!(
-                    "found missing Pattern, should not be generating IR for incomplete code"
-                ),
-            }
+        let body = self.body.clone(); // Avoid borrow issues
+        for (i, (pat, _ty)) in body.params().iter().enumerate() {
+            let param = self.fn_value.get_nth_param(i as u32).unwrap();
+            self.gen_param_bind(*pat, param, (i + 1) as u32);
         }
 
         // Generate code for the body of the function
@@ -129,7 +150,7 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
         // generate a return statement.
         let block_ret_type = &self.infer[self.body.body_expr()];
         let fn_ret_type = self
-            .hir_function
+            .instance
             .ty(self.db)
             .callable_sig(self.db)
             .unwrap()
@@ -144,8 +165,119 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
         }
     }
 
+    /// Binds the value of the parameter at the one-based position `arg_no` to the pattern `pat`.
+    fn gen_param_bind(&mut self, pat: PatId, param: BasicValueEnum, arg_no: u32) {
+        let body = self.body.clone(); // Avoid borrow issues
+
+        match &body[pat] {
+            Pat::Bind { name } => {
+                let name = name.to_string();
+                let builder = self.new_alloca_builder();
+                let param_ptr = builder.build_alloca(param.get_type(), &name);
+                builder.build_store(param_ptr, param);
+                self.pat_to_local.insert(pat, param_ptr);
+                self.pat_to_name.insert(pat, name);
+                self.gen_debug_variable(pat, param_ptr, Some(arg_no));
+            }
+            Pat::Tuple(_) => {
+                let builder = self.new_alloca_builder();
+                let param_ptr = builder.build_alloca(param.get_type(), "tuple");
+                builder.build_store(param_ptr, param);
+                self.gen_pat_bind(pat, param_ptr);
+            }
+            Pat::Wild => {
+                // Wildcard patterns cannot be referenced from code. So nothing to do.
+            }
+            Pat::Path(_) | Pat::TupleStruct { .. } | Pat::Record { .. } => unreachable!(
+                "Variant patterns are not supported as parameters, are we missing a diagnostic?"
+            ),
+            Pat::Missing => unreachable!(
+                "found missing Pattern, should not be generating IR for incomplete code"
+            ),
+        }
+    }
+
+    /// Generates IR for the body of the closure `expr` with the signature `sig`, of which the heap
+    /// memory has the layout `closure_ir_ty`. The values that the closure captured are loaded from
+    /// its memory, which is passed as the first argument.
+    fn gen_closure_body(
+        &mut self,
+        expr: ExprId,
+        closure_ir_ty: StructType,
+        captures: &[PatId],
+        args: &[PatId],
+        body: ExprId,
+        sig: &FnSig,
+    ) {
+        if let Some(line_col) = self.expr_line_col(expr) {
+            self.gen_debug_function(line_col, sig);
+        }
+
+        let object_ptr = self.fn_value.get_nth_param(0).unwrap();
+        let closure_ptr_ptr = self
+            .builder
+            .build_bitcast(
+                object_ptr,
+                closure_ir_ty
+                    .ptr_type(AddressSpace::Generic)
+                    .ptr_type(AddressSpace::Const),
+                "closure_ptr_ptr",
+            )
+            .into_pointer_value();
+        let closure_ptr = self
+            .builder
+            .build_load(closure_ptr_ptr, "closure_ptr")
+            .into_pointer_value();
+
+        // Captured values are copied into locals, so they can be used like any other binding
+        let hir_body = self.body.clone(); // Avoid borrow issues
+        for (i, pat) in captures.iter().enumerate() {
+            let name = match &hir_body[*pat] {
+                Pat::Bind { name } => name.to_string(),
+                _ => unreachable!("only bindings can be captured"),
+            };
+            let value_ptr = unsafe {
+                self.builder
+                    .build_struct_gep(closure_ptr, (i + 1) as u32, &format!("{}_ptr", name))
+            };
+            let value = self.builder.build_load(value_ptr, &name);
+            let local = self
+                .new_alloca_builder()
+                .build_alloca(value.get_type(), &name);
+            self.builder.build_store(local, value);
+            self.pat_to_local.insert(*pat, local);
+            self.pat_to_name.insert(*pat, name);
+            self.gen_debug_variable(*pat, local, None);
+        }
+
+        for (i, pat) in args.iter().enumerate() {
+            let param = self.fn_value.get_nth_param((i + 1) as u32).unwrap();
+            self.gen_param_bind(*pat, param, (i + 1) as u32);
+        }
+
+        let ret_value = self.gen_expr(body);
+        if !self.infer[body].is_never() {
+            if sig.ret().is_empty() {
+                self.builder.build_return(None);
+            } else if let Some(value) = ret_value {
+                self.builder.build_return(Some(&value));
+            }
+        }
+    }
+
+    /// Generates IR for the body of a thunk through which `function` is called as a function
+    /// pointer. The thunk drops the function pointer's object that is passed as its first
+    /// argument.
+    fn gen_thunk_body(&mut self, function: &FunctionInstance) {
+        let args: Vec<BasicValueEnum> = self.fn_value.get_params().into_iter().skip(1).collect();
+        match self.gen_call(function, &args).try_as_basic_value().left() {
+            Some(value) => self.builder.build_return(Some(&value)),
+            None => self.builder.build_return(None),
+        };
+    }
+
     pub fn gen_fn_wrapper(&mut self) {
-        let fn_sig = self.hir_function.ty(self.db).callable_sig(self.db).unwrap();
+        let fn_sig = self.instance.ty(self.db).callable_sig(self.db).unwrap();
         let args: Vec<BasicValueEnum> = fn_sig
             .params()
             .iter()
@@ -158,21 +290,28 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
                     } else {
                         param
                     }
+                } else if let Some(e) = ty.as_enum() {
+                    if e.data(self.db).memory_kind == abi::StructMemoryKind::Value {
+                        deref_heap_value(&self.builder, param)
+                    } else {
+                        param
+                    }
+                } else if let hir::ty_app!(TypeCtor::Array(_)) | hir::ty_app!(TypeCtor::Tuple) = ty
+                {
+                    deref_heap_value(&self.builder, param)
                 } else {
                     param
                 }
             })
             .collect();
 
-        let ret_value = self
-            .gen_call(self.hir_function, &args)
-            .try_as_basic_value()
-            .left();
+        let instance = self.instance.clone();
+        let ret_value = self.gen_call(&instance, &args).try_as_basic_value().left();
 
         let call_return_type = &self.infer[self.body.body_expr()];
         if !call_return_type.is_never() {
             let fn_ret_type = self
-                .hir_function
+                .instance
                 .ty(self.db)
                 .callable_sig(self.db)
                 .unwrap()
@@ -184,10 +323,22 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
             } else if let Some(value) = ret_value {
                 let ret_value = if let Some(hir_struct) = fn_ret_type.as_struct() {
                     if hir_struct.data(self.db).memory_kind == hir::StructMemoryKind::Value {
-                        self.gen_struct_alloc_on_heap(hir_struct, value.into_struct_value())
+                        let name = hir_struct.name(self.db).to_string();
+                        self.gen_alloc_on_heap(fn_ret_type, value, &name)
+                    } else {
+                        value
+                    }
+                } else if let Some(hir_enum) = fn_ret_type.as_enum() {
+                    if hir_enum.data(self.db).memory_kind == hir::StructMemoryKind::Value {
+                        let name = hir_enum.name(self.db).to_string();
+                        self.gen_alloc_on_heap(fn_ret_type, value, &name)
                     } else {
                         value
                     }
+                } else if let hir::ty_app!(TypeCtor::Array(_)) = fn_ret_type {
+                    self.gen_alloc_on_heap(fn_ret_type, value, "array")
+                } else if let hir::ty_app!(TypeCtor::Tuple) = fn_ret_type {
+                    self.gen_alloc_on_heap(fn_ret_type, value, "tuple")
                 } else {
                     value
                 };
@@ -199,6 +350,19 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
     /// Generates IR for the specified expression. Dependending on the type of expression an IR
     /// value is returned.
     fn gen_expr(&mut self, expr: ExprId) -> Option<inkwell::values::BasicValueEnum> {
+        // Instructions are attributed to the innermost expression that generates them
+        let parent_location = self.set_debug_location(expr);
+        let value = self.gen_expr_value(expr);
+        if let Some(location) = parent_location {
+            self.builder
+                .set_current_debug_location(&self.db.context(), location);
+        }
+        value
+    }
+
+    /// Generates IR for the specified expression, without changing the source location to which
+    /// the generated instructions are attributed.
+    fn gen_expr_value(&mut self, expr: ExprId) -> Option<inkwell::values::BasicValueEnum> {
         let body = self.body.clone();
         match &body[expr] {
             Expr::Block {
@@ -221,30 +385,43 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
             } => {
                 // Get the callable definition from the map
                 match self.infer[*callee].as_callable_def() {
-                    Some(hir::CallableDef::Function(def)) => {
+                    Some(hir::CallableDef::Function(_)) => {
+                        let instance = FunctionInstance::from_callee(self.db, &self.infer[*callee])
+                            .expect("expected a function");
+
                         // Get all the arguments
                         let args: Vec<BasicValueEnum> = args
                             .iter()
                             .map(|expr| self.gen_expr(*expr).expect("expected a value"))
                             .collect();
 
-                        self.gen_call(def, &args)
-                            .try_as_basic_value()
-                            .left()
-                            // If the called function is a void function it doesn't return anything.
-                            // If this method (`gen_expr`) returns None we assume the return value
-                            // is `never`. We return a const unit struct here to ensure that at
-                            // least something is returned. This matches with the hir where a
-                            // `nothing` is returned instead of a `never`.
-                            //
-                            // This unit value will also be optimized out.
-                            .or_else(|| match self.infer[expr] {
-                                hir::ty_app!(hir::TypeCtor::Never) => None,
-                                _ => Some(self.db.context().const_struct(&[], false).into()),
-                            })
+                        self.gen_fn_call(expr, &instance, &args)
+                    }
+                    Some(hir::CallableDef::BuiltinFunction(hir::BuiltinFunction::Panic)) => {
+                        // Strings are stored as dynamic arrays of bytes
+                        let string_ptr_ptr = self.gen_expr(args[0])?.into_pointer_value();
+                        let mem_ptr = self
+                            .builder
+                            .build_load(string_ptr_ptr, "string_mem_ptr")
+                            .into_pointer_value();
+                        let length = self.gen_dyn_array_length(mem_ptr);
+                        let bytes_ptr = self.builder.build_bitcast(
+                            unsafe { self.builder.build_struct_gep(mem_ptr, 2, "bytes_ptr") },
+                            self.db.context().i8_type().ptr_type(AddressSpace::Const),
+                            "bytes_ptr",
+                        );
+                        self.gen_trap(expr, bytes_ptr, length);
+                        None
                     }
                     Some(hir::CallableDef::Struct(_)) => Some(self.gen_named_tuple_lit(expr, args)),
-                    None => panic!("expected a callable expression"),
+                    Some(hir::CallableDef::EnumVariant(variant)) => {
+                        let args: Vec<BasicValueEnum> = args
+                            .iter()
+                            .map(|expr| self.gen_expr(*expr).expect("expected a field value"))
+                            .collect();
+                        Some(self.gen_enum_alloc(variant, args))
+                    }
+                    None => self.gen_fn_ptr_call(expr, *callee, args),
                 }
             }
             Expr::If {
@@ -255,7 +432,16 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
             Expr::Return { expr: ret_expr } => self.gen_return(expr, *ret_expr),
             Expr::Loop { body } => self.gen_loop(expr, *body),
             Expr::While { condition, body } => self.gen_while(expr, *condition, *body),
+            Expr::For {
+                pat,
+                iterable,
+                body,
+            } => self.gen_for(expr, *pat, *iterable, *body),
             Expr::Break { expr: break_expr } => self.gen_break(expr, *break_expr),
+            Expr::Match {
+                expr: scrutinee,
+                ref arms,
+            } => self.gen_match(expr, *scrutinee, arms),
             Expr::Field {
                 expr: receiver_expr,
                 name,
@@ -264,6 +450,17 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
                 let value = self.builder.build_load(ptr, &name.to_string());
                 Some(value)
             }
+            Expr::Array(elements) => self.gen_array_lit(expr, elements),
+            Expr::Tuple(elements) => self.gen_tuple_lit(expr, elements),
+            Expr::Index { base, index } => {
+                let ptr = self.gen_index(expr, *base, *index)?;
+                Some(self.builder.build_load(ptr, "element"))
+            }
+            Expr::MethodCall { receiver, args, .. } => self.gen_method_call(expr, *receiver, args),
+            Expr::Cast {
+                expr: value_expr, ..
+            } => self.gen_cast(expr, *value_expr),
+            Expr::Lambda { args, body, .. } => Some(self.gen_lambda(expr, args, *body)),
             _ => unimplemented!("unimplemented expr type {:?}", &body[expr]),
         }
     }
@@ -326,10 +523,85 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
                 }
             }
 
-            Literal::String(_) => unimplemented!("string literals are not implemented yet"),
+            Literal::String(value) => self.gen_string_lit(value),
+
+            // `nil` is represented by a null reference
+            Literal::Nil => self
+                .db
+                .type_ir(
+                    self.infer[expr].clone(),
+                    CodeGenParams {
+                        make_marshallable: false,
+                    },
+                )
+                .into_pointer_type()
+                .const_null()
+                .into(),
         }
     }
 
+    /// Generates a constant global that contains the UTF-8 encoded bytes of `value`. Returns a
+    /// pointer to the bytes and their length.
+    fn gen_str_bytes(&self, value: &str) -> (BasicValueEnum, IntValue) {
+        let context = self.db.context();
+
+        // The bytes are stored without a null terminator, as the length is passed explicitly
+        let bytes = context.const_string(value, false);
+        let bytes_ptr = self.builder.build_bitcast(
+            gen_global(self.module, &bytes, "str").as_pointer_value(),
+            context.i8_type().ptr_type(AddressSpace::Const),
+            "bytes_ptr",
+        );
+        let length = self.usize_type().const_int(value.len() as u64, false);
+        (bytes_ptr, length)
+    }
+
+    /// Allocates a string on the heap that contains a copy of `value`.
+    fn gen_string_lit(&mut self, value: &str) -> BasicValueEnum {
+        let new_string_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+            self.external_globals.dispatch_table,
+            &self.builder,
+            &intrinsics::new_string,
+        );
+
+        let (bytes_ptr, length) = self.gen_str_bytes(value);
+        let allocator_handle = self.gen_allocator_handle();
+
+        let object_ptr = self
+            .builder
+            .build_call(
+                new_string_fn_ptr,
+                &[bytes_ptr, length.into(), allocator_handle],
+                "new_string",
+            )
+            .try_as_basic_value()
+            .left()
+            .unwrap();
+
+        self.builder
+            .build_bitcast(object_ptr, self.string_ptr_ptr_type(), "string_ptr_ptr")
+    }
+
+    /// Returns the IR type of a handle to a string.
+    fn string_ptr_ptr_type(&self) -> inkwell::types::PointerType {
+        string_ty(self.db)
+            .ptr_type(AddressSpace::Generic)
+            .ptr_type(AddressSpace::Const)
+    }
+
+    /// Casts a handle to a string to the handle type that is expected by intrinsics.
+    fn gen_object_handle(&self, string_ptr_ptr: BasicValueEnum) -> BasicValueEnum {
+        self.builder.build_bitcast(
+            string_ptr_ptr,
+            self.db
+                .context()
+                .i8_type()
+                .ptr_type(AddressSpace::Generic)
+                .ptr_type(AddressSpace::Const),
+            "object_ptr",
+        )
+    }
+
     /// Constructs an empty struct value e.g. `{}`
     fn gen_empty(&mut self) -> BasicValueEnum {
         self.db.context().const_struct(&[], false).into()
@@ -339,10 +611,11 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
     fn gen_struct_alloc(
         &mut self,
         hir_struct: hir::Struct,
+        substs: Substs,
         args: Vec<BasicValueEnum>,
     ) -> BasicValueEnum {
         // Construct the struct literal
-        let struct_ty = self.db.struct_ty(hir_struct);
+        let struct_ty = self.db.struct_ty(hir_struct, substs.clone());
         let mut value: AggregateValueEnum = struct_ty.get_undef().into();
         for (i, arg) in args.into_iter().enumerate() {
             value = self
@@ -356,79 +629,695 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
             hir::StructMemoryKind::Value => struct_lit.into(),
             hir::StructMemoryKind::GC => {
                 // TODO: Root memory in GC
-                self.gen_struct_alloc_on_heap(hir_struct, struct_lit)
+                self.gen_struct_alloc_on_heap(hir_struct, substs, struct_lit)
             }
         }
     }
 
+    /// Allocate an enum variant literal either on the stack or the heap based on the type of the
+    /// enum.
+    fn gen_enum_alloc(
+        &mut self,
+        variant: hir::EnumVariant,
+        args: Vec<BasicValueEnum>,
+    ) -> BasicValueEnum {
+        let hir_enum = variant.parent_enum();
+        let enum_ty = self.db.enum_ty(hir_enum);
+
+        // The payload of the variant is written through a pointer, so the enum literal is
+        // constructed in a temporary
+        let enum_ptr = self.new_alloca_builder().build_alloca(enum_ty, "enum");
+        let tag_ptr = unsafe { self.builder.build_struct_gep(enum_ptr, 0, "tag_ptr") };
+        self.builder.build_store(
+            tag_ptr,
+            self.db
+                .context()
+                .i8_type()
+                .const_int(variant.index(self.db) as u64, false),
+        );
+        let variant_ptr = self.gen_variant_ptr(enum_ptr, variant);
+        for (i, arg) in args.into_iter().enumerate() {
+            let field_ptr = unsafe {
+                self.builder
+                    .build_struct_gep(variant_ptr, i as u32, "field_ptr")
+            };
+            self.builder.build_store(field_ptr, arg);
+        }
+        let enum_lit = self.builder.build_load(enum_ptr, "enum_lit");
+
+        match hir_enum.data(self.db).memory_kind {
+            hir::StructMemoryKind::Value => enum_lit,
+            hir::StructMemoryKind::GC => {
+                // TODO: Root memory in GC
+                self.gen_alloc_on_heap(
+                    hir_enum.ty(self.db),
+                    enum_lit,
+                    &hir_enum.name(self.db).to_string(),
+                )
+            }
+        }
+    }
+
+    /// Generates IR that casts the payload of the enum at `enum_ptr` to the fields of `variant`.
+    fn gen_variant_ptr(
+        &mut self,
+        enum_ptr: PointerValue,
+        variant: hir::EnumVariant,
+    ) -> PointerValue {
+        let payload_ptr = unsafe { self.builder.build_struct_gep(enum_ptr, 1, "payload_ptr") };
+        self.builder
+            .build_bitcast(
+                payload_ptr,
+                enum_variant_ty(self.db, variant).ptr_type(AddressSpace::Generic),
+                &format!("{}_ptr", variant.name(self.db)),
+            )
+            .into_pointer_value()
+    }
+
     fn gen_struct_alloc_on_heap(
         &mut self,
         hir_struct: hir::Struct,
+        substs: Substs,
         struct_lit: StructValue,
     ) -> BasicValueEnum {
-        let struct_ir_ty = self.db.struct_ty(hir_struct);
+        self.gen_alloc_on_heap(
+            hir::Ty::Apply(hir::ApplicationTy {
+                ctor: TypeCtor::Struct(hir_struct),
+                parameters: substs,
+            }),
+            struct_lit.into(),
+            &hir_struct.name(self.db).to_string(),
+        )
+    }
+
+    /// Allocates memory for a value of type `ty` on the heap and stores `value` in it. Returns the
+    /// object pointer.
+    fn gen_alloc_on_heap(
+        &mut self,
+        ty: hir::Ty,
+        value: BasicValueEnum,
+        name: &str,
+    ) -> BasicValueEnum {
+        let type_info = self.db.type_info(ty);
+        self.gen_alloc_object(&type_info, value, name)
+    }
+
+    /// Allocates a garbage collected object of the type described by `type_info` and stores
+    /// `value` in its memory, returning the handle of the object.
+    fn gen_alloc_object(
+        &mut self,
+        type_info: &TypeInfo,
+        value: BasicValueEnum,
+        name: &str,
+    ) -> BasicValueEnum {
         let new_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
             self.external_globals.dispatch_table,
             &self.builder,
             &intrinsics::new,
         );
 
+        let type_info_ptr = self.gen_type_info_ptr(type_info);
+        let allocator_handle = self.gen_allocator_handle();
+
+        // An object pointer adds an extra layer of indirection to allow for hot reloading. To
+        // make it type agnostic, it is stored in a `*const *mut std::ffi::c_void`.
+        let object_ptr = self
+            .builder
+            .build_call(new_fn_ptr, &[type_info_ptr, allocator_handle], "new")
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_pointer_value();
+
+        // Cast the object pointer to the value's type
+        let value_ptr_ptr = self
+            .builder
+            .build_bitcast(
+                object_ptr,
+                value
+                    .get_type()
+                    .ptr_type(AddressSpace::Generic)
+                    .ptr_type(AddressSpace::Const),
+                &format!("{}_ptr_ptr", name),
+            )
+            .into_pointer_value();
+
+        // Load the actual memory location of the value
+        let mem_ptr = self
+            .builder
+            .build_load(value_ptr_ptr, &format!("{}_mem_ptr", name))
+            .into_pointer_value();
+
+        // Store the value
+        self.builder.build_store(mem_ptr, value);
+
+        value_ptr_ptr.into()
+    }
+
+    /// Generates IR that looks up the `TypeInfo` of `ty` in the type table.
+    fn gen_type_info_ptr(&mut self, type_info: &TypeInfo) -> BasicValueEnum {
         let type_info_ptr = self.type_table.gen_type_info_lookup(
             &self.builder,
-            &self.db.type_info(hir_struct.ty(self.db)),
+            type_info,
             self.external_globals.type_table,
         );
 
         // HACK: We should be able to use pointers for built-in struct types like `TypeInfo` in intrinsics
-        let type_info_ptr = self.builder.build_bitcast(
+        self.builder.build_bitcast(
             type_info_ptr,
             self.db.context().i8_type().ptr_type(AddressSpace::Const),
             "type_info_ptr_to_i8_ptr",
-        );
+        )
+    }
 
-        let allocator_handle = self.builder.build_load(
+    /// Generates IR that loads the handle of the allocator.
+    fn gen_allocator_handle(&mut self) -> BasicValueEnum {
+        self.builder.build_load(
             self.external_globals
                 .alloc_handle
-                .expect("no allocator handle was specified, this is required for structs")
+                .expect("no allocator handle was specified, this is required for heap allocations")
                 .as_pointer_value(),
             "allocator_handle",
+        )
+    }
+
+    /// Returns the IR type of a `usize`.
+    fn usize_type(&self) -> IntType {
+        <usize as IsIrType>::ir_type(&self.db.context(), &self.db.target_data())
+    }
+
+    /// Generates IR for a tuple literal, e.g. `(1, 2.0)`. The empty tuple `()` has the empty type.
+    fn gen_tuple_lit(&mut self, expr: ExprId, elements: &[ExprId]) -> Option<BasicValueEnum> {
+        if elements.is_empty() {
+            return Some(self.gen_empty());
+        }
+
+        let mut values = Vec::with_capacity(elements.len());
+        for element in elements.iter() {
+            values.push(self.gen_expr(*element)?);
+        }
+
+        let tuple_ir_ty = self
+            .db
+            .type_ir(
+                self.infer[expr].clone(),
+                CodeGenParams {
+                    make_marshallable: false,
+                },
+            )
+            .into_struct_type();
+        let mut value: AggregateValueEnum = tuple_ir_ty.get_undef().into();
+        for (i, element) in values.into_iter().enumerate() {
+            value = self
+                .builder
+                .build_insert_value(value, element, i as u32, "init")
+                .expect("Failed to initialize tuple element.");
+        }
+        Some(value.into_struct_value().into())
+    }
+
+    /// Generates IR for an array literal, e.g. `[1, 2, 3]`
+    fn gen_array_lit(&mut self, expr: ExprId, elements: &[ExprId]) -> Option<BasicValueEnum> {
+        let mut values = Vec::with_capacity(elements.len());
+        for element in elements.iter() {
+            values.push(self.gen_expr(*element)?);
+        }
+
+        let array_ty = self.infer[expr].clone();
+        match array_ty {
+            hir::ty_app!(TypeCtor::Array(_)) => {
+                let array_ir_ty = self
+                    .db
+                    .type_ir(
+                        array_ty,
+                        CodeGenParams {
+                            make_marshallable: false,
+                        },
+                    )
+                    .into_array_type();
+                let mut value: AggregateValueEnum = array_ir_ty.get_undef().into();
+                for (i, element) in values.into_iter().enumerate() {
+                    value = self
+                        .builder
+                        .build_insert_value(value, element, i as u32, "init")
+                        .expect("Failed to initialize array element.");
+                }
+                Some(value.into_array_value().into())
+            }
+            hir::ty_app!(TypeCtor::DynArray) => Some(self.gen_dyn_array_alloc(array_ty, values)),
+            _ => unreachable!("array literals must have an array type"),
+        }
+    }
+
+    /// Allocates a dynamic array on the heap that contains the specified `elements`. Returns the
+    /// object pointer.
+    fn gen_dyn_array_alloc(
+        &mut self,
+        array_ty: hir::Ty,
+        elements: Vec<BasicValueEnum>,
+    ) -> BasicValueEnum {
+        let new_array_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+            self.external_globals.dispatch_table,
+            &self.builder,
+            &intrinsics::new_array,
         );
 
-        // An object pointer adds an extra layer of indirection to allow for hot reloading. To
-        // make it struct type agnostic, it is stored in a `*const *mut std::ffi::c_void`.
+        let array_ir_ty = self.db.type_ir(
+            array_ty.clone(),
+            CodeGenParams {
+                make_marshallable: false,
+            },
+        );
+        let type_info_ptr = self.gen_type_info_ptr(&self.db.type_info(array_ty));
+        let length = self.usize_type().const_int(elements.len() as u64, false);
+        let allocator_handle = self.gen_allocator_handle();
+
         let object_ptr = self
             .builder
-            .build_call(new_fn_ptr, &[type_info_ptr, allocator_handle], "new")
+            .build_call(
+                new_array_fn_ptr,
+                &[type_info_ptr, length.into(), allocator_handle],
+                "new_array",
+            )
             .try_as_basic_value()
             .left()
             .unwrap()
             .into_pointer_value();
 
-        // Cast the object pointer to the struct type
-        let struct_ptr_ptr = self
+        // Cast the object pointer to the array type
+        let array_ptr_ptr = self
             .builder
-            .build_bitcast(
-                object_ptr,
-                struct_ir_ty
-                    .ptr_type(AddressSpace::Generic)
-                    .ptr_type(AddressSpace::Const),
-                &format!("{}_ptr_ptr", hir_struct.name(self.db).to_string()),
+            .build_bitcast(object_ptr, array_ir_ty.into_pointer_type(), "array_ptr_ptr")
+            .into_pointer_value();
+
+        let mem_ptr = self
+            .builder
+            .build_load(array_ptr_ptr, "array_mem_ptr")
+            .into_pointer_value();
+        let elements_ptr = unsafe { self.builder.build_struct_gep(mem_ptr, 2, "elements_ptr") };
+        for (i, element) in elements.into_iter().enumerate() {
+            let element_ptr = unsafe {
+                self.builder.build_in_bounds_gep(
+                    elements_ptr,
+                    &[
+                        self.usize_type().const_zero(),
+                        self.usize_type().const_int(i as u64, false),
+                    ],
+                    "element_ptr",
+                )
+            };
+            self.builder.build_store(element_ptr, element);
+        }
+
+        let length_ptr = unsafe { self.builder.build_struct_gep(mem_ptr, 0, "length_ptr") };
+        self.builder.build_store(length_ptr, length);
+
+        array_ptr_ptr.into()
+    }
+
+    /// Generates IR that loads the length of the dynamic array that is stored at `mem_ptr`.
+    fn gen_dyn_array_length(&mut self, mem_ptr: PointerValue) -> IntValue {
+        let length_ptr = unsafe { self.builder.build_struct_gep(mem_ptr, 0, "length_ptr") };
+        self.builder
+            .build_load(length_ptr, "length")
+            .into_int_value()
+    }
+
+    /// Generates IR that results in a pointer to the value of the fixed-size array `expr`. If
+    /// `expr` is not a place expression, its value is stored in a temporary.
+    fn gen_array_place(&mut self, expr: ExprId) -> Option<PointerValue> {
+        match &self.body[expr] {
+            Expr::Path(_) | Expr::Field { .. } | Expr::Index { .. } => {
+                Some(self.gen_place_expr(expr))
+            }
+            _ => {
+                let value = self.gen_expr(expr)?;
+                let ptr = self
+                    .new_alloca_builder()
+                    .build_alloca(value.get_type(), "array");
+                self.builder.build_store(ptr, value);
+                self.place_owner = None;
+                Some(ptr)
+            }
+        }
+    }
+
+    /// Generates IR that results in a pointer to the element at `index` of the array `base`. An
+    /// out of bounds access results in a runtime error.
+    fn gen_index(&mut self, expr: ExprId, base: ExprId, index: ExprId) -> Option<PointerValue> {
+        let usize_type = self.usize_type();
+        let (elements_ptr, length, owner) = match self.infer[base] {
+            hir::ty_app!(TypeCtor::Array(length)) => {
+                let array_ptr = self.gen_array_place(base)?;
+                (
+                    array_ptr,
+                    usize_type.const_int(u64::from(length), false),
+                    self.place_owner,
+                )
+            }
+            hir::ty_app!(TypeCtor::DynArray) => {
+                let array_ptr_ptr = self.gen_expr(base)?.into_pointer_value();
+                let mem_ptr = self
+                    .builder
+                    .build_load(array_ptr_ptr, "array_mem_ptr")
+                    .into_pointer_value();
+                let length = self.gen_dyn_array_length(mem_ptr);
+                let elements_ptr =
+                    unsafe { self.builder.build_struct_gep(mem_ptr, 2, "elements_ptr") };
+                (elements_ptr, length, Some(array_ptr_ptr))
+            }
+            _ => unreachable!("can only index into arrays"),
+        };
+
+        let index = self
+            .gen_expr(index)
+            .map(|value| self.opt_deref_value(self.infer[index].clone(), value))?
+            .into_int_value();
+        self.gen_bounds_check(expr, index, length);
+
+        // The index might contain a place expression itself, so the owner is set afterwards
+        self.place_owner = owner;
+        Some(unsafe {
+            self.builder.build_in_bounds_gep(
+                elements_ptr,
+                &[usize_type.const_zero(), index],
+                "element_ptr",
             )
+        })
+    }
+
+    /// Generates IR that reports a runtime error at the location of `expr` if `index` is not
+    /// smaller than `length`.
+    fn gen_bounds_check(&mut self, expr: ExprId, index: IntValue, length: IntValue) {
+        let context = self.db.context();
+        let out_of_bounds_block = context.append_basic_block(&self.fn_value, "out_of_bounds");
+        let in_bounds_block = context.append_basic_block(&self.fn_value, "in_bounds");
+
+        let in_bounds =
+            self.builder
+                .build_int_compare(IntPredicate::ULT, index, length, "in_bounds");
+        self.builder
+            .build_conditional_branch(in_bounds, &in_bounds_block, &out_of_bounds_block);
+
+        // The runtime never returns from reporting the error
+        self.builder.position_at_end(&out_of_bounds_block);
+        let index_out_of_bounds_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+            self.external_globals.dispatch_table,
+            &self.builder,
+            &intrinsics::index_out_of_bounds,
+        );
+        let (function, function_len, line) = self.gen_trap_location(expr);
+        self.builder.build_call(
+            index_out_of_bounds_fn_ptr,
+            &[index.into(), length.into(), function, function_len, line],
+            "",
+        );
+        self.builder.build_unreachable();
+
+        self.builder.position_at_end(&in_bounds_block);
+    }
+
+    /// Returns the arguments that describe the location of `expr` in a runtime error: a pointer to
+    /// the name of the function, the length of that name, and the line at which `expr` starts.
+    fn gen_trap_location(&self, expr: ExprId) -> (BasicValueEnum, BasicValueEnum, BasicValueEnum) {
+        let (function, function_len) = self.gen_str_bytes(&self.instance.name(self.db));
+        // Lines are reported one-based, like in an editor
+        let line = self
+            .expr_line_col(expr)
+            .map_or(0, |line_col| line_col.line + 1);
+        let line = self
+            .db
+            .context()
+            .i32_type()
+            .const_int(u64::from(line), false);
+        (function, function_len.into(), line.into())
+    }
+
+    /// Generates IR that reports a runtime error at the location of `expr`, with the `message_len`
+    /// UTF-8 encoded bytes at `message` as its message. The runtime never returns from reporting
+    /// the error.
+    fn gen_trap(&mut self, expr: ExprId, message: BasicValueEnum, message_len: IntValue) {
+        let trap_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+            self.external_globals.dispatch_table,
+            &self.builder,
+            &intrinsics::trap,
+        );
+        let (function, function_len, line) = self.gen_trap_location(expr);
+        self.builder.build_call(
+            trap_fn_ptr,
+            &[message, message_len.into(), function, function_len, line],
+            "",
+        );
+        self.builder.build_unreachable();
+    }
+
+    /// Generates IR that reports a runtime error with `message` at the location of `expr` if
+    /// `condition` is true.
+    fn gen_trap_if(&mut self, expr: ExprId, condition: IntValue, message: &str) {
+        let context = self.db.context();
+        let trap_block = context.append_basic_block(&self.fn_value, "trap");
+        let continue_block = context.append_basic_block(&self.fn_value, "no_trap");
+        self.builder
+            .build_conditional_branch(condition, &trap_block, &continue_block);
+
+        self.builder.position_at_end(&trap_block);
+        let (message, message_len) = self.gen_str_bytes(message);
+        self.gen_trap(expr, message, message_len);
+
+        self.builder.position_at_end(&continue_block);
+    }
+
+    /// Returns the (zero-based) line and column in its source file at which `expr` starts.
+    fn expr_line_col(&self, expr: ExprId) -> Option<LineCol> {
+        self.instance
+            .function
+            .body_source_map(self.db)
+            .expr_line_col(self.db, expr)
+    }
+
+    /// Generates the debug information of the function for which IR is generated, if debug
+    /// information is generated at all. The function is declared at `line_col` and has the
+    /// signature `sig`. Instructions are attributed to the declaration until an expression is
+    /// generated.
+    fn gen_debug_function(&mut self, line_col: LineCol, sig: &FnSig) {
+        if let Some(debug_info) = self.debug_info {
+            let subprogram = debug_info.gen_function(self.db, self.fn_value, line_col, sig);
+            let context = self.db.context();
+            let location =
+                debug_info.location(&context, line_col, subprogram.as_debug_info_scope());
+            self.builder.set_current_debug_location(&context, location);
+            self.debug_scope = Some(subprogram);
+        }
+    }
+
+    /// Attributes subsequently generated instructions to `expr` in the debug information, if any.
+    /// Returns the location to which instructions were attributed before.
+    fn set_debug_location(&self, expr: ExprId) -> Option<DILocation> {
+        let debug_info = self.debug_info?;
+        let scope = self.debug_scope?;
+        let line_col = self.expr_line_col(expr)?;
+        let parent_location = self.builder.get_current_debug_location();
+        let context = self.db.context();
+        let location = debug_info.location(&context, line_col, scope.as_debug_info_scope());
+        self.builder.set_current_debug_location(&context, location);
+        parent_location
+    }
+
+    /// Declares the binding `pat`, which is stored at `ptr`, in the debug information of the
+    /// function, if any. Parameters are declared with their one-based position `arg_no`.
+    fn gen_debug_variable(&self, pat: PatId, ptr: PointerValue, arg_no: Option<u32>) {
+        if let (Some(debug_info), Some(scope)) = (self.debug_info, self.debug_scope) {
+            let line_col = self
+                .instance
+                .function
+                .body_source_map(self.db)
+                .pat_line_col(self.db, pat);
+            let block = self.builder.get_insert_block();
+            if let (Some(line_col), Some(block)) = (line_col, block) {
+                debug_info.gen_variable(
+                    self.db,
+                    scope,
+                    &self.pat_to_name[&pat],
+                    &self.infer[pat],
+                    arg_no,
+                    line_col,
+                    ptr,
+                    &block,
+                );
+            }
+        }
+    }
+
+    /// Generates IR for a method call, e.g. `array.len()` or `body.step(dt)`
+    fn gen_method_call(
+        &mut self,
+        expr: ExprId,
+        receiver: ExprId,
+        args: &[ExprId],
+    ) -> Option<BasicValueEnum> {
+        let method = match self
+            .infer
+            .method_resolution(expr)
+            .expect("unresolved method call")
+        {
+            MethodResolution::Builtin(method) => method,
+            MethodResolution::Function(_) => {
+                let instance = FunctionInstance::from_method_call(&self.infer, expr)
+                    .expect("expected a function");
+
+                // The receiver is passed as the `self` argument
+                let args: Vec<BasicValueEnum> = std::iter::once(receiver)
+                    .chain(args.iter().copied())
+                    .map(|expr| self.gen_expr(expr).expect("expected a value"))
+                    .collect();
+                return self.gen_fn_call(expr, &instance, &args);
+            }
+        };
+
+        match method {
+            BuiltinMethod::ArrayLen => {
+                if let hir::ty_app!(TypeCtor::Array(length)) = self.infer[receiver] {
+                    // The receiver is still evaluated for its side effects
+                    self.gen_expr(receiver)?;
+                    Some(self.usize_type().const_int(u64::from(length), false).into())
+                } else {
+                    let array_ptr_ptr = self.gen_expr(receiver)?.into_pointer_value();
+                    let mem_ptr = self
+                        .builder
+                        .build_load(array_ptr_ptr, "array_mem_ptr")
+                        .into_pointer_value();
+                    Some(self.gen_dyn_array_length(mem_ptr).into())
+                }
+            }
+            BuiltinMethod::ArrayPush => {
+                let array_ptr_ptr = self.gen_expr(receiver)?.into_pointer_value();
+                let value = self.gen_expr(args[0])?;
+                self.gen_array_push(array_ptr_ptr, value);
+                if needs_write_barrier(&self.infer[args[0]]) {
+                    self.gen_write_barrier(array_ptr_ptr);
+                }
+                Some(self.gen_empty())
+            }
+            BuiltinMethod::ArrayPop => {
+                let array_ptr_ptr = self.gen_expr(receiver)?.into_pointer_value();
+                Some(self.gen_array_pop(expr, array_ptr_ptr))
+            }
+            BuiltinMethod::StringLen => {
+                // Strings are stored as dynamic arrays of bytes
+                let string_ptr_ptr = self.gen_expr(receiver)?.into_pointer_value();
+                let mem_ptr = self
+                    .builder
+                    .build_load(string_ptr_ptr, "string_mem_ptr")
+                    .into_pointer_value();
+                Some(self.gen_dyn_array_length(mem_ptr).into())
+            }
+        }
+    }
+
+    /// Generates IR that appends `value` to the dynamic array referred to by `array_ptr_ptr`,
+    /// growing its memory if required.
+    fn gen_array_push(&mut self, array_ptr_ptr: PointerValue, value: BasicValueEnum) {
+        let context = self.db.context();
+        let grow_block = context.append_basic_block(&self.fn_value, "array_grow");
+        let push_block = context.append_basic_block(&self.fn_value, "array_push");
+
+        let mem_ptr = self
+            .builder
+            .build_load(array_ptr_ptr, "array_mem_ptr")
             .into_pointer_value();
+        let length = self.gen_dyn_array_length(mem_ptr);
+        let capacity_ptr = unsafe { self.builder.build_struct_gep(mem_ptr, 1, "capacity_ptr") };
+        let capacity = self
+            .builder
+            .build_load(capacity_ptr, "capacity")
+            .into_int_value();
+        let new_length =
+            self.builder
+                .build_int_add(length, length.get_type().const_int(1, false), "new_length");
+
+        let is_full = self
+            .builder
+            .build_int_compare(IntPredicate::EQ, length, capacity, "is_full");
+        self.builder
+            .build_conditional_branch(is_full, &grow_block, &push_block);
 
-        // Load the actual memory location of the struct
+        // Reserve memory for the new element
+        self.builder.position_at_end(&grow_block);
+        let array_reserve_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+            self.external_globals.dispatch_table,
+            &self.builder,
+            &intrinsics::array_reserve,
+        );
+        let object_ptr = self.builder.build_bitcast(
+            array_ptr_ptr,
+            context
+                .i8_type()
+                .ptr_type(AddressSpace::Generic)
+                .ptr_type(AddressSpace::Const),
+            "object_ptr",
+        );
+        let allocator_handle = self.gen_allocator_handle();
+        self.builder.build_call(
+            array_reserve_fn_ptr,
+            &[object_ptr, new_length.into(), allocator_handle],
+            "",
+        );
+        self.builder.build_unconditional_branch(&push_block);
+
+        // Reserving memory might have moved the array, so its memory location is reloaded
+        self.builder.position_at_end(&push_block);
         let mem_ptr = self
             .builder
-            .build_load(
-                struct_ptr_ptr,
-                &format!("{}_mem_ptr", hir_struct.name(self.db).to_string()),
+            .build_load(array_ptr_ptr, "array_mem_ptr")
+            .into_pointer_value();
+        let element_ptr = unsafe {
+            self.builder.build_in_bounds_gep(
+                mem_ptr,
+                &[
+                    context.i32_type().const_zero(),
+                    context.i32_type().const_int(2, false),
+                    length,
+                ],
+                "element_ptr",
             )
+        };
+        self.builder.build_store(element_ptr, value);
+        let length_ptr = unsafe { self.builder.build_struct_gep(mem_ptr, 0, "length_ptr") };
+        self.builder.build_store(length_ptr, new_length);
+    }
+
+    /// Generates IR that removes the last element of the dynamic array referred to by
+    /// `array_ptr_ptr` and returns it. Popping from an empty array results in a runtime error.
+    fn gen_array_pop(&mut self, expr: ExprId, array_ptr_ptr: PointerValue) -> BasicValueEnum {
+        let context = self.db.context();
+        let mem_ptr = self
+            .builder
+            .build_load(array_ptr_ptr, "array_mem_ptr")
             .into_pointer_value();
+        let length = self.gen_dyn_array_length(mem_ptr);
 
-        // Store the struct value
-        self.builder.build_store(mem_ptr, struct_lit);
+        // Popping is an access of the last element, so an empty array is reported as an out of
+        // bounds access at index zero.
+        self.gen_bounds_check(expr, length.get_type().const_zero(), length);
 
-        struct_ptr_ptr.into()
+        let new_length =
+            self.builder
+                .build_int_sub(length, length.get_type().const_int(1, false), "new_length");
+        let element_ptr = unsafe {
+            self.builder.build_in_bounds_gep(
+                mem_ptr,
+                &[
+                    context.i32_type().const_zero(),
+                    context.i32_type().const_int(2, false),
+                    new_length,
+                ],
+                "element_ptr",
+            )
+        };
+        let value = self.builder.build_load(element_ptr, "element");
+        let length_ptr = unsafe { self.builder.build_struct_gep(mem_ptr, 0, "length_ptr") };
+        self.builder.build_store(length_ptr, new_length);
+        value
     }
 
     /// Generates IR for a record literal, e.g. `Foo { a: 1.23, b: 4 }`
@@ -437,14 +1326,18 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
         type_expr: ExprId,
         fields: &[hir::RecordLitField],
     ) -> BasicValueEnum {
-        let struct_ty = self.infer[type_expr].clone();
-        let hir_struct = struct_ty.as_struct().unwrap(); // Can only really get here if the type is a struct
         let fields: Vec<BasicValueEnum> = fields
             .iter()
             .map(|field| self.gen_expr(field.expr).expect("expected a field value"))
             .collect();
 
-        self.gen_struct_alloc(hir_struct, fields)
+        if let Some(variant) = self.infer.variant_resolution_for_expr(type_expr) {
+            return self.gen_enum_alloc(variant, fields);
+        }
+
+        let struct_ty = self.infer[type_expr].clone();
+        let hir_struct = struct_ty.as_struct().unwrap(); // Can only really get here if the type is a struct
+        self.gen_struct_alloc(hir_struct, struct_ty.substs().unwrap(), fields)
     }
 
     /// Generates IR for a named tuple literal, e.g. `Foo(1.23, 4)`
@@ -456,14 +1349,14 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
             .map(|expr| self.gen_expr(*expr).expect("expected a field value"))
             .collect();
 
-        self.gen_struct_alloc(hir_struct, args)
+        self.gen_struct_alloc(hir_struct, struct_ty.substs().unwrap(), args)
     }
 
     /// Generates IR for a unit struct literal, e.g `Foo`
     fn gen_unit_struct_lit(&mut self, type_expr: ExprId) -> BasicValueEnum {
         let struct_ty = self.infer[type_expr].clone();
         let hir_struct = struct_ty.as_struct().unwrap(); // Can only really get here if the type is a struct
-        self.gen_struct_alloc(hir_struct, Vec::new())
+        self.gen_struct_alloc(hir_struct, struct_ty.substs().unwrap(), Vec::new())
     }
 
     /// Generates IR for the specified block expression.
@@ -524,14 +1417,58 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
                 let ptr = builder.build_alloca(ty, &name.to_string());
                 self.pat_to_local.insert(pat, ptr);
                 self.pat_to_name.insert(pat, name.to_string());
+                self.gen_debug_variable(pat, ptr, None);
                 if !(pat_ty.is_empty() || pat_ty.is_never()) {
                     if let Some(value) = initializer {
                         self.builder.build_store(ptr, value);
                     };
                 }
             }
+            Pat::Tuple(_) => {
+                let pat_ty = self.infer[pat].clone();
+                let ty = try_convert_any_to_basic(self.db.type_ir(
+                    pat_ty,
+                    CodeGenParams {
+                        make_marshallable: false,
+                    },
+                ))
+                .expect("expected basic type");
+                let ptr = self.new_alloca_builder().build_alloca(ty, "tuple");
+                if let Some(value) = initializer {
+                    self.builder.build_store(ptr, value);
+                }
+                self.gen_pat_bind(pat, ptr);
+            }
+            Pat::Wild => {}
+            Pat::Missing | Pat::Path(_) | Pat::TupleStruct { .. } | Pat::Record { .. } => {
+                unreachable!()
+            }
+        }
+    }
+
+    /// Generates IR that binds the irrefutable pattern `pat` to the value stored at `place`. The
+    /// bindings of the pattern refer directly to the memory of their sub-values.
+    fn gen_pat_bind(&mut self, pat: PatId, place: PointerValue) {
+        let body = self.body.clone();
+        match &body[pat] {
+            Pat::Bind { name } => {
+                self.pat_to_local.insert(pat, place);
+                self.pat_to_name.insert(pat, name.to_string());
+                self.gen_debug_variable(pat, place, None);
+            }
+            Pat::Tuple(args) => {
+                for (idx, arg) in args.iter().enumerate() {
+                    let element_ptr = unsafe {
+                        self.builder
+                            .build_struct_gep(place, idx as u32, "element_ptr")
+                    };
+                    self.gen_pat_bind(*arg, element_ptr);
+                }
+            }
             Pat::Wild => {}
-            Pat::Missing | Pat::Path(_) => unreachable!(),
+            Pat::Missing | Pat::Path(_) | Pat::TupleStruct { .. } | Pat::Record { .. } => {
+                unreachable!()
+            }
         }
     }
 
@@ -548,21 +1485,144 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
             .expect("unknown path");
 
         match resolution {
-            Resolution::LocalBinding(pat) => {
-                if let Some(param) = self.pat_to_param.get(&pat) {
-                    *param
-                } else if let Some(ptr) = self.pat_to_local.get(&pat) {
-                    let name = self.pat_to_name.get(&pat).expect("could not find pat name");
-                    self.builder.build_load(*ptr, &name)
-                } else {
-                    unreachable!("could not find the pattern..");
-                }
-            }
+            Resolution::LocalBinding(pat) => self.gen_local_binding(pat),
             Resolution::Def(hir::ModuleDef::Struct(_)) => self.gen_unit_struct_lit(expr),
-            Resolution::Def(_) => panic!("no support for module definitions"),
+            Resolution::Def(hir::ModuleDef::EnumVariant(variant)) => {
+                self.gen_enum_alloc(variant, Vec::new())
+            }
+            Resolution::Def(hir::ModuleDef::Function(_)) => self.gen_fn_ptr_lit(expr),
+            Resolution::Def(hir::ModuleDef::Const(c)) => {
+                let value = c
+                    .value(self.db)
+                    .expect("the value of a constant must be known at compile time");
+                gen_const_value(self.db, value, &c.ty(self.db))
+            }
+            Resolution::Def(hir::ModuleDef::Static(s)) => {
+                let global = self.external_globals.globals[&s];
+                self.builder
+                    .build_load(global.as_pointer_value(), &s.name(self.db).to_string())
+            }
+            Resolution::Def(_) | Resolution::SelfType(_) => {
+                panic!("no support for module definitions")
+            }
+        }
+    }
+
+    /// Generates IR that loads the value of the binding `pat`.
+    fn gen_local_binding(&mut self, pat: PatId) -> BasicValueEnum {
+        if let Some(param) = self.pat_to_param.get(&pat) {
+            *param
+        } else if let Some(ptr) = self.pat_to_local.get(&pat) {
+            let name = self.pat_to_name.get(&pat).expect("could not find pat name");
+            self.builder.build_load(*ptr, &name)
+        } else {
+            unreachable!("could not find the pattern..");
         }
     }
 
+    /// Generates IR for a function that is used as a value. The function is called through a
+    /// thunk, of which a pointer is stored in a function pointer object.
+    fn gen_fn_ptr_lit(&mut self, expr: ExprId) -> BasicValueEnum {
+        let ty = self.infer[expr].clone();
+        let function = FunctionInstance::from_callee(self.db, &ty).expect("expected a function");
+        let sig = ty.callable_sig(self.db).expect("expected a function");
+
+        let name = format!("{}::{{fn_ptr}}", function.name(self.db));
+        let thunk = self.module.get_function(&name).unwrap_or_else(|| {
+            let thunk = function::add_function(
+                self.module,
+                &name,
+                closure_fn_ty(self.db, &sig),
+                Some(Linkage::Private),
+            );
+            let mut code_gen = BodyIrGenerator::new(
+                self.db,
+                self.module,
+                (&self.instance, thunk),
+                self.function_map,
+                self.dispatch_table,
+                self.type_table,
+                self.params.clone(),
+                self.external_globals.clone(),
+                self.debug_info,
+            );
+            code_gen.gen_thunk_body(&function);
+            thunk
+        });
+
+        let value = fn_ptr_ty(self.db, &sig)
+            .const_named_struct(&[thunk.as_global_value().as_pointer_value().into()]);
+        let type_info = self.db.type_info(hir::Ty::fn_ptr(sig));
+        self.gen_alloc_object(&type_info, value.into(), "fn_ptr")
+    }
+
+    /// Generates IR for a closure. The body of the closure is generated as a separate function, a
+    /// pointer to which is stored in a garbage collected object together with the values that the
+    /// closure captured.
+    fn gen_lambda(&mut self, expr: ExprId, args: &[PatId], body: ExprId) -> BasicValueEnum {
+        let sig = self.infer[expr]
+            .callable_sig(self.db)
+            .expect("expected a function pointer");
+        let captures = self.infer.closure_captures(expr).to_vec();
+        let capture_tys: Vec<hir::Ty> = captures
+            .iter()
+            .map(|pat| self.infer[*pat].clone())
+            .collect();
+
+        let fn_ty = closure_fn_ty(self.db, &sig);
+        let closure_ir_ty = closure_ty(
+            self.db,
+            fn_ty.ptr_type(AddressSpace::Generic).into(),
+            &capture_tys,
+        );
+
+        let name = format!("{}::{{closure}}", self.instance.name(self.db));
+        let closure_fn = function::add_function(self.module, &name, fn_ty, Some(Linkage::Private));
+        let mut code_gen = BodyIrGenerator::new(
+            self.db,
+            self.module,
+            (&self.instance, closure_fn),
+            self.function_map,
+            self.dispatch_table,
+            self.type_table,
+            self.params.clone(),
+            self.external_globals.clone(),
+            self.debug_info,
+        );
+        code_gen.gen_closure_body(expr, closure_ir_ty, &captures, args, body, &sig);
+
+        // Closures capture values by copying them into their memory
+        let mut value: AggregateValueEnum = closure_ir_ty.get_undef().into();
+        value = self
+            .builder
+            .build_insert_value(
+                value,
+                closure_fn.as_global_value().as_pointer_value(),
+                0,
+                "init",
+            )
+            .expect("Failed to initialize closure.");
+        for (i, pat) in captures.iter().enumerate() {
+            let capture = self.gen_local_binding(*pat);
+            value = self
+                .builder
+                .build_insert_value(value, capture, (i + 1) as u32, "init")
+                .expect("Failed to initialize closure.");
+        }
+
+        let type_info = closure_type_info(self.db, &self.infer[expr], capture_tys);
+        let object = self.gen_alloc_object(&type_info, value.into_struct_value().into(), "closure");
+
+        // A closure is used through a function pointer, which hides its captured values
+        self.builder.build_bitcast(
+            object,
+            fn_ptr_ty(self.db, &sig)
+                .ptr_type(AddressSpace::Generic)
+                .ptr_type(AddressSpace::Const),
+            "fn_ptr",
+        )
+    }
+
     /// Given an expression and the type of the expression, optionally dereference the value.
     fn opt_deref_value(&mut self, ty: hir::Ty, value: BasicValueEnum) -> BasicValueEnum {
         match ty {
@@ -594,30 +1654,56 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
                 .pat_to_local
                 .get(&pat)
                 .expect("unresolved local binding"),
-            Resolution::Def(_) => panic!("no support for module definitions"),
+            Resolution::Def(hir::ModuleDef::Static(s)) => {
+                self.external_globals.globals[&s].as_pointer_value()
+            }
+            Resolution::Def(_) | Resolution::SelfType(_) => {
+                panic!("no support for module definitions")
+            }
         }
     }
 
     /// Generates IR to calculate a binary operation between two expressions.
     fn gen_binary_op(
         &mut self,
-        _tgt_expr: ExprId,
+        tgt_expr: ExprId,
         lhs: ExprId,
         rhs: ExprId,
         op: BinaryOp,
     ) -> Option<BasicValueEnum> {
         let lhs_type = self.infer[lhs].clone();
-        match lhs_type.as_simple() {
-            Some(TypeCtor::Bool) => self.gen_binary_op_bool(lhs, rhs, op),
-            Some(TypeCtor::Float(_ty)) => self.gen_binary_op_float(lhs, rhs, op),
-            Some(TypeCtor::Int(ty)) => self.gen_binary_op_int(lhs, rhs, op, ty.signedness),
-            Some(TypeCtor::Struct(s)) => {
+        if lhs_type.as_array().is_some() {
+            return self.gen_binary_op_array(lhs, rhs, op);
+        }
+        if lhs_type.as_optional().is_some() {
+            return self.gen_binary_op_optional(lhs, rhs, op);
+        }
+        // The type arguments of an instantiated generic struct or enum (e.g. `Pair<f32, i64>`) do
+        // not affect how its values are stored
+        match lhs_type {
+            hir::ty_app!(TypeCtor::Bool) => self.gen_binary_op_bool(lhs, rhs, op),
+            hir::ty_app!(TypeCtor::Float(_ty)) => self.gen_binary_op_float(lhs, rhs, op),
+            hir::ty_app!(TypeCtor::Int(ty)) => {
+                self.gen_binary_op_int(tgt_expr, lhs, rhs, op, ty.signedness)
+            }
+            hir::ty_app!(TypeCtor::String) => self.gen_binary_op_string(lhs, rhs, op),
+            hir::ty_app!(TypeCtor::Struct(s)) => {
                 if s.data(self.db).memory_kind == hir::StructMemoryKind::Value {
                     self.gen_binary_op_value_struct(lhs, rhs, op)
                 } else {
                     self.gen_binary_op_heap_struct(lhs, rhs, op)
                 }
             }
+            hir::ty_app!(TypeCtor::Enum(e)) => {
+                // Enums are stored like structs
+                if e.data(self.db).memory_kind == hir::StructMemoryKind::Value {
+                    self.gen_binary_op_value_struct(lhs, rhs, op)
+                } else {
+                    self.gen_binary_op_heap_struct(lhs, rhs, op)
+                }
+            }
+            hir::ty_app!(TypeCtor::Tuple) => self.gen_binary_op_tuple(lhs, rhs, op),
+            hir::ty_app!(TypeCtor::FnPtr { .. }) => self.gen_binary_op_fn_ptr(lhs, rhs, op),
             _ => {
                 let rhs_type = self.infer[rhs].clone();
                 unimplemented!(
@@ -629,6 +1715,121 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
         }
     }
 
+    /// Generates IR to convert the value of `value_expr` to the type of the cast expression
+    /// `expr`, e.g. `a as f64`.
+    fn gen_cast(&mut self, expr: ExprId, value_expr: ExprId) -> Option<BasicValueEnum> {
+        let from_ty = self.infer[value_expr].clone();
+        let to_ty = self.infer[expr].clone();
+        let value = self.gen_expr(value_expr)?;
+        if from_ty == to_ty {
+            return Some(value);
+        }
+
+        let value = self.opt_deref_value(from_ty.clone(), value);
+        let to_ir_ty = self.db.type_ir(
+            to_ty.clone(),
+            CodeGenParams {
+                make_marshallable: false,
+            },
+        );
+        let value = match (from_ty.as_simple(), to_ty.as_simple()) {
+            (Some(TypeCtor::Int(from)), Some(TypeCtor::Int(_))) => self
+                .gen_int_cast(
+                    value.into_int_value(),
+                    to_ir_ty.into_int_type(),
+                    from.signedness,
+                )
+                .into(),
+            (Some(TypeCtor::Bool), Some(TypeCtor::Int(_))) => self
+                .gen_int_cast(
+                    value.into_int_value(),
+                    to_ir_ty.into_int_type(),
+                    hir::Signedness::Unsigned,
+                )
+                .into(),
+            (Some(TypeCtor::Int(from)), Some(TypeCtor::Float(_))) => match from.signedness {
+                hir::Signedness::Signed => self.builder.build_signed_int_to_float(
+                    value.into_int_value(),
+                    to_ir_ty.into_float_type(),
+                    "cast",
+                ),
+                hir::Signedness::Unsigned => self.builder.build_unsigned_int_to_float(
+                    value.into_int_value(),
+                    to_ir_ty.into_float_type(),
+                    "cast",
+                ),
+            }
+            .into(),
+            (Some(TypeCtor::Float(_)), Some(TypeCtor::Int(to))) => match to.signedness {
+                hir::Signedness::Signed => self.builder.build_float_to_signed_int(
+                    value.into_float_value(),
+                    to_ir_ty.into_int_type(),
+                    "cast",
+                ),
+                hir::Signedness::Unsigned => self.builder.build_float_to_unsigned_int(
+                    value.into_float_value(),
+                    to_ir_ty.into_int_type(),
+                    "cast",
+                ),
+            }
+            .into(),
+            (Some(TypeCtor::Float(from)), Some(TypeCtor::Float(to))) => {
+                let data_layout = self.db.target_data_layout();
+                match (
+                    from.bitness.resolve(&data_layout),
+                    to.bitness.resolve(&data_layout),
+                ) {
+                    (hir::FloatBitness::X32, hir::FloatBitness::X64) => self
+                        .builder
+                        .build_float_ext(
+                            value.into_float_value(),
+                            to_ir_ty.into_float_type(),
+                            "cast",
+                        )
+                        .into(),
+                    (hir::FloatBitness::X64, hir::FloatBitness::X32) => self
+                        .builder
+                        .build_float_trunc(
+                            value.into_float_value(),
+                            to_ir_ty.into_float_type(),
+                            "cast",
+                        )
+                        .into(),
+                    _ => value,
+                }
+            }
+            _ => unreachable!(
+                "invalid cast from `{}` to `{}` should have been caught during type inference",
+                from_ty.display(self.db),
+                to_ty.display(self.db)
+            ),
+        };
+        Some(value)
+    }
+
+    /// Generates IR to convert an integer `value` to the integer type `to_ty` by truncating or
+    /// extending it. Whether the value is sign- or zero-extended depends on the `signedness` of
+    /// the source type.
+    fn gen_int_cast(
+        &mut self,
+        value: IntValue,
+        to_ty: IntType,
+        signedness: hir::Signedness,
+    ) -> IntValue {
+        let from_bit_width = value.get_type().get_bit_width();
+        let to_bit_width = to_ty.get_bit_width();
+        if from_bit_width > to_bit_width {
+            self.builder.build_int_truncate(value, to_ty, "cast")
+        } else if from_bit_width < to_bit_width {
+            match signedness {
+                hir::Signedness::Signed => self.builder.build_int_s_extend(value, to_ty, "cast"),
+                hir::Signedness::Unsigned => self.builder.build_int_z_extend(value, to_ty, "cast"),
+            }
+        } else {
+            value
+        }
+    }
+
     /// Generates IR to calculate a unary operation on an expression.
     fn gen_unary_op(&mut self, expr: ExprId, op: UnaryOp) -> Option<BasicValueEnum> {
         let ty = self.infer[expr].clone();
@@ -715,8 +1916,7 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
                     Some(op) => self.gen_arith_bin_op_bool(lhs, rhs, op),
                     None => rhs,
                 };
-                let place = self.gen_place_expr(lhs_expr);
-                self.builder.build_store(place, rhs);
+                self.gen_place_store(lhs_expr, rhs);
                 Some(self.gen_empty())
             }
             BinaryOp::LogicOp(op) => Some(self.gen_logic_bin_op(lhs, rhs, op).into()),
@@ -775,111 +1975,291 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
             }
             BinaryOp::Assignment { op } => {
                 let rhs = match op {
-                    Some(op) => self.gen_arith_bin_op_float(lhs, rhs, op),
+                    Some(op) => self.gen_arith_bin_op_float(lhs, rhs, op),
+                    None => rhs,
+                };
+                self.gen_place_store(lhs_expr, rhs);
+                Some(self.gen_empty())
+            }
+            _ => unimplemented!("Operator {:?} is not implemented for float", op),
+        }
+    }
+
+    /// Generates IR to calculate a binary operation between two integer values.
+    fn gen_binary_op_int(
+        &mut self,
+        expr: ExprId,
+        lhs_expr: ExprId,
+        rhs_expr: ExprId,
+        op: BinaryOp,
+        signedness: hir::Signedness,
+    ) -> Option<BasicValueEnum> {
+        let lhs = self
+            .gen_expr(lhs_expr)
+            .map(|value| self.opt_deref_value(self.infer[lhs_expr].clone(), value))
+            .expect("no lhs value")
+            .into_int_value();
+        let rhs = self
+            .gen_expr(rhs_expr)
+            .map(|value| self.opt_deref_value(self.infer[lhs_expr].clone(), value))
+            .expect("no rhs value")
+            .into_int_value();
+        match op {
+            BinaryOp::ArithOp(op) => Some(
+                self.gen_arith_bin_op_int(expr, lhs, rhs, op, signedness)
+                    .into(),
+            ),
+            BinaryOp::CmpOp(op) => Some(self.gen_cmp_bin_op_int(lhs, rhs, op, signedness).into()),
+            BinaryOp::Assignment { op } => {
+                let rhs = match op {
+                    Some(op) => self.gen_arith_bin_op_int(expr, lhs, rhs, op, signedness),
+                    None => rhs,
+                };
+                self.gen_place_store(lhs_expr, rhs);
+                Some(self.gen_empty())
+            }
+            _ => unreachable!(format!("Operator {:?} is not implemented for integer", op)),
+        }
+    }
+
+    /// Generates IR to calculate a binary operation between two heap struct values (e.g. a Mun
+    /// `struct(gc)`).
+    fn gen_binary_op_heap_struct(
+        &mut self,
+        lhs_expr: ExprId,
+        rhs_expr: ExprId,
+        op: BinaryOp,
+    ) -> Option<BasicValueEnum> {
+        let rhs = self
+            .gen_expr(rhs_expr)
+            .expect("no rhs value")
+            .into_pointer_value();
+        match op {
+            BinaryOp::Assignment { op } => {
+                let rhs = match op {
+                    Some(op) => unimplemented!(
+                        "Assignment with {:?} operator is not implemented for struct",
+                        op
+                    ),
+                    None => rhs,
+                };
+                self.gen_place_store(lhs_expr, rhs);
+                Some(self.gen_empty())
+            }
+            _ => unimplemented!("Operator {:?} is not implemented for struct", op),
+        }
+    }
+
+    /// Generates IR to calculate a binary operation between two value struct values, denoted in
+    /// Mun as `struct(value)`.
+    fn gen_binary_op_value_struct(
+        &mut self,
+        lhs_expr: ExprId,
+        rhs_expr: ExprId,
+        op: BinaryOp,
+    ) -> Option<BasicValueEnum> {
+        let rhs = self
+            .gen_expr(rhs_expr)
+            .expect("no rhs value")
+            .into_struct_value();
+        match op {
+            BinaryOp::Assignment { op } => {
+                let rhs = match op {
+                    Some(op) => unimplemented!(
+                        "Assignment with {:?} operator is not implemented for struct",
+                        op
+                    ),
+                    None => rhs,
+                };
+                self.gen_place_store(lhs_expr, rhs);
+                Some(self.gen_empty())
+            }
+            _ => unimplemented!("Operator {:?} is not implemented for struct", op),
+        }
+    }
+
+    /// Generates IR to calculate a binary operation between two arrays, denoted in Mun as `[T; N]`
+    /// or `[T]`.
+    fn gen_binary_op_array(
+        &mut self,
+        lhs_expr: ExprId,
+        rhs_expr: ExprId,
+        op: BinaryOp,
+    ) -> Option<BasicValueEnum> {
+        let rhs = self.gen_expr(rhs_expr).expect("no rhs value");
+        match op {
+            BinaryOp::Assignment { op } => {
+                let rhs = match op {
+                    Some(op) => unimplemented!(
+                        "Assignment with {:?} operator is not implemented for arrays",
+                        op
+                    ),
                     None => rhs,
                 };
-                let place = self.gen_place_expr(lhs_expr);
-                self.builder.build_store(place, rhs);
+                self.gen_place_store(lhs_expr, rhs);
                 Some(self.gen_empty())
             }
-            _ => unimplemented!("Operator {:?} is not implemented for float", op),
+            _ => unimplemented!("Operator {:?} is not implemented for arrays", op),
         }
     }
 
-    /// Generates IR to calculate a binary operation between two integer values.
-    fn gen_binary_op_int(
+    /// Generates IR to calculate a binary operation between two optional references, denoted in
+    /// Mun as `T?`. Optional references are compared by address.
+    fn gen_binary_op_optional(
         &mut self,
         lhs_expr: ExprId,
         rhs_expr: ExprId,
         op: BinaryOp,
-        signedness: hir::Signedness,
     ) -> Option<BasicValueEnum> {
-        let lhs = self
-            .gen_expr(lhs_expr)
-            .map(|value| self.opt_deref_value(self.infer[lhs_expr].clone(), value))
-            .expect("no lhs value")
-            .into_int_value();
-        let rhs = self
-            .gen_expr(rhs_expr)
-            .map(|value| self.opt_deref_value(self.infer[lhs_expr].clone(), value))
-            .expect("no rhs value")
-            .into_int_value();
         match op {
-            BinaryOp::ArithOp(op) => {
-                Some(self.gen_arith_bin_op_int(lhs, rhs, op, signedness).into())
+            BinaryOp::Assignment { op: None } => {
+                let rhs = self.gen_expr(rhs_expr).expect("no rhs value");
+                self.gen_place_store(lhs_expr, rhs);
+                Some(self.gen_empty())
             }
-            BinaryOp::CmpOp(op) => Some(self.gen_cmp_bin_op_int(lhs, rhs, op, signedness).into()),
-            BinaryOp::Assignment { op } => {
-                let rhs = match op {
-                    Some(op) => self.gen_arith_bin_op_int(lhs, rhs, op, signedness),
-                    None => rhs,
-                };
-                let place = self.gen_place_expr(lhs_expr);
-                self.builder.build_store(place, rhs);
+            BinaryOp::CmpOp(op @ CmpOp::Eq { .. }) => {
+                let lhs = self
+                    .gen_expr(lhs_expr)
+                    .expect("no lhs value")
+                    .into_pointer_value();
+                let rhs = self
+                    .gen_expr(rhs_expr)
+                    .expect("no rhs value")
+                    .into_pointer_value();
+                let lhs = self
+                    .builder
+                    .build_ptr_to_int(lhs, self.usize_type(), "lhs_addr");
+                let rhs = self
+                    .builder
+                    .build_ptr_to_int(rhs, self.usize_type(), "rhs_addr");
+                Some(
+                    self.gen_cmp_bin_op_int(lhs, rhs, op, hir::Signedness::Unsigned)
+                        .into(),
+                )
+            }
+            _ => unimplemented!("Operator {:?} is not implemented for optionals", op),
+        }
+    }
+
+    /// Generates IR to calculate a binary operation between two tuples, denoted in Mun as
+    /// `(A, B)`.
+    fn gen_binary_op_tuple(
+        &mut self,
+        lhs_expr: ExprId,
+        rhs_expr: ExprId,
+        op: BinaryOp,
+    ) -> Option<BasicValueEnum> {
+        match op {
+            BinaryOp::Assignment { op: None } => {
+                let rhs = self.gen_expr(rhs_expr).expect("no rhs value");
+                self.gen_place_store(lhs_expr, rhs);
                 Some(self.gen_empty())
             }
-            _ => unreachable!(format!("Operator {:?} is not implemented for integer", op)),
+            _ => unimplemented!("Operator {:?} is not implemented for tuples", op),
         }
     }
 
-    /// Generates IR to calculate a binary operation between two heap struct values (e.g. a Mun
-    /// `struct(gc)`).
-    fn gen_binary_op_heap_struct(
+    /// Generates IR to calculate a binary operation between two function pointers, denoted in Mun
+    /// as `fn(A) -> B`. Function pointers refer to garbage collected objects, so only the
+    /// reference is stored.
+    fn gen_binary_op_fn_ptr(
         &mut self,
         lhs_expr: ExprId,
         rhs_expr: ExprId,
         op: BinaryOp,
     ) -> Option<BasicValueEnum> {
-        let rhs = self
-            .gen_expr(rhs_expr)
-            .expect("no rhs value")
-            .into_pointer_value();
         match op {
-            BinaryOp::Assignment { op } => {
-                let rhs = match op {
-                    Some(op) => unimplemented!(
-                        "Assignment with {:?} operator is not implemented for struct",
-                        op
-                    ),
-                    None => rhs,
-                };
-                let place = self.gen_place_expr(lhs_expr);
-                self.builder.build_store(place, rhs);
+            BinaryOp::Assignment { op: None } => {
+                let rhs = self.gen_expr(rhs_expr).expect("no rhs value");
+                self.gen_place_store(lhs_expr, rhs);
                 Some(self.gen_empty())
             }
-            _ => unimplemented!("Operator {:?} is not implemented for struct", op),
+            _ => unimplemented!("Operator {:?} is not implemented for function pointers", op),
         }
     }
 
-    /// Generates IR to calculate a binary operation between two value struct values, denoted in
-    /// Mun as `struct(value)`.
-    fn gen_binary_op_value_struct(
+    /// Generates IR to calculate a binary operation between two strings.
+    fn gen_binary_op_string(
         &mut self,
         lhs_expr: ExprId,
         rhs_expr: ExprId,
         op: BinaryOp,
     ) -> Option<BasicValueEnum> {
-        let rhs = self
-            .gen_expr(rhs_expr)
-            .expect("no rhs value")
-            .into_struct_value();
         match op {
+            BinaryOp::ArithOp(ArithOp::Add) => {
+                let lhs = self.gen_expr(lhs_expr).expect("no lhs value");
+                let rhs = self.gen_expr(rhs_expr).expect("no rhs value");
+                Some(self.gen_string_concat(lhs, rhs))
+            }
+            BinaryOp::CmpOp(CmpOp::Eq { negated }) => {
+                let lhs = self.gen_expr(lhs_expr).expect("no lhs value");
+                let rhs = self.gen_expr(rhs_expr).expect("no rhs value");
+                let string_eq_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+                    self.external_globals.dispatch_table,
+                    &self.builder,
+                    &intrinsics::string_eq,
+                );
+                let lhs = self.gen_object_handle(lhs);
+                let rhs = self.gen_object_handle(rhs);
+                let eq = self
+                    .builder
+                    .build_call(string_eq_fn_ptr, &[lhs, rhs], "eq")
+                    .try_as_basic_value()
+                    .left()
+                    .unwrap()
+                    .into_int_value();
+                if negated {
+                    Some(self.builder.build_not(eq, "neq").into())
+                } else {
+                    Some(eq.into())
+                }
+            }
             BinaryOp::Assignment { op } => {
                 let rhs = match op {
+                    Some(ArithOp::Add) => {
+                        let lhs = self.gen_expr(lhs_expr).expect("no lhs value");
+                        let rhs = self.gen_expr(rhs_expr).expect("no rhs value");
+                        self.gen_string_concat(lhs, rhs)
+                    }
                     Some(op) => unimplemented!(
-                        "Assignment with {:?} operator is not implemented for struct",
+                        "Assignment with {:?} operator is not implemented for strings",
                         op
                     ),
-                    None => rhs,
+                    None => self.gen_expr(rhs_expr).expect("no rhs value"),
                 };
-                let place = self.gen_place_expr(lhs_expr);
-                self.builder.build_store(place, rhs);
+                self.gen_place_store(lhs_expr, rhs);
                 Some(self.gen_empty())
             }
-            _ => unimplemented!("Operator {:?} is not implemented for struct", op),
+            _ => unimplemented!("Operator {:?} is not implemented for strings", op),
         }
     }
 
+    /// Generates IR that allocates a new string that contains the concatenation of the strings
+    /// `lhs` and `rhs`.
+    fn gen_string_concat(&mut self, lhs: BasicValueEnum, rhs: BasicValueEnum) -> BasicValueEnum {
+        let string_concat_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+            self.external_globals.dispatch_table,
+            &self.builder,
+            &intrinsics::string_concat,
+        );
+        let lhs = self.gen_object_handle(lhs);
+        let rhs = self.gen_object_handle(rhs);
+        let allocator_handle = self.gen_allocator_handle();
+        let object_ptr = self
+            .builder
+            .build_call(
+                string_concat_fn_ptr,
+                &[lhs, rhs, allocator_handle],
+                "concat",
+            )
+            .try_as_basic_value()
+            .left()
+            .unwrap();
+
+        self.builder
+            .build_bitcast(object_ptr, self.string_ptr_ptr_type(), "string_ptr_ptr")
+    }
+
     fn gen_arith_bin_op_bool(&mut self, lhs: IntValue, rhs: IntValue, op: ArithOp) -> IntValue {
         match op {
             ArithOp::BitAnd => self.builder.build_and(lhs, rhs, "bit_and"),
@@ -949,11 +2329,16 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
 
     fn gen_arith_bin_op_int(
         &mut self,
+        expr: ExprId,
         lhs: IntValue,
         rhs: IntValue,
         op: ArithOp,
         signedness: hir::Signedness,
     ) -> IntValue {
+        if let ArithOp::Divide | ArithOp::Remainder = op {
+            self.gen_int_division_check(expr, lhs, rhs, op, signedness);
+        }
+
         match op {
             ArithOp::Add => self.builder.build_int_add(lhs, rhs, "add"),
             ArithOp::Subtract => self.builder.build_int_sub(lhs, rhs, "sub"),
@@ -977,6 +2362,56 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
         }
     }
 
+    /// Generates IR that reports a runtime error if the integer division (or remainder) of `lhs` by
+    /// `rhs` is undefined, i.e. if `rhs` is zero or if the result does not fit in its type.
+    fn gen_int_division_check(
+        &mut self,
+        expr: ExprId,
+        lhs: IntValue,
+        rhs: IntValue,
+        op: ArithOp,
+        signedness: hir::Signedness,
+    ) {
+        let int_type = rhs.get_type();
+        let is_zero =
+            self.builder
+                .build_int_compare(IntPredicate::EQ, rhs, int_type.const_zero(), "is_zero");
+        let message = match op {
+            ArithOp::Remainder => "attempt to calculate the remainder with a divisor of zero",
+            _ => "attempt to divide by zero",
+        };
+        self.gen_trap_if(expr, is_zero, message);
+
+        // Dividing the minimum value of a signed integer by `-1` overflows
+        if signedness.is_signed() {
+            let bit_width = int_type.get_bit_width();
+            let min_value = i128::MIN >> (128 - bit_width);
+            let min_value = if bit_width == 128 {
+                int_type.const_int_arbitrary_precision(&unsafe {
+                    std::mem::transmute::<i128, [u64; 2]>(min_value)
+                })
+            } else {
+                // Only the lower bits are used, which is the two's complement of negative values
+                int_type.const_int(min_value as u64, false)
+            };
+            let is_min = self
+                .builder
+                .build_int_compare(IntPredicate::EQ, lhs, min_value, "is_min");
+            let is_minus_one = self.builder.build_int_compare(
+                IntPredicate::EQ,
+                rhs,
+                int_type.const_all_ones(),
+                "is_minus_one",
+            );
+            let overflows = self.builder.build_and(is_min, is_minus_one, "overflows");
+            let message = match op {
+                ArithOp::Remainder => "attempt to calculate the remainder with overflow",
+                _ => "attempt to divide with overflow",
+            };
+            self.gen_trap_if(expr, overflows, message);
+        }
+    }
+
     fn gen_arith_bin_op_float(
         &mut self,
         lhs: FloatValue,
@@ -1013,23 +2448,119 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
         match &body[expr] {
             Expr::Path(ref p) => {
                 let resolver = hir::resolver_for_expr(self.body.clone(), self.db, expr);
+                self.place_owner = None;
                 self.gen_path_place_expr(p, expr, &resolver)
             }
             Expr::Field {
                 expr: receiver_expr,
                 name,
             } => self.gen_field(expr, *receiver_expr, name),
+            Expr::Index { base, index } => self
+                .gen_index(expr, *base, *index)
+                .expect("index expression has no value"),
             _ => unreachable!("invalid place expression"),
         }
     }
 
+    /// Generates IR that stores `value` in the place `lhs_expr`. If the place is located in the
+    /// memory of a garbage collected object, the garbage collector is notified of the store.
+    fn gen_place_store(&mut self, lhs_expr: ExprId, value: impl BasicValue) {
+        let place = self.gen_place_expr(lhs_expr);
+        self.builder.build_store(place, value);
+        if let Some(owner) = self.place_owner {
+            if needs_write_barrier(&self.infer[lhs_expr]) {
+                self.gen_write_barrier(owner);
+            }
+        }
+    }
+
+    /// Generates IR that notifies the garbage collector that a reference might have been stored in
+    /// the memory of the object referred to by `handle`.
+    fn gen_write_barrier(&mut self, handle: PointerValue) {
+        let write_barrier_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+            self.external_globals.dispatch_table,
+            &self.builder,
+            &intrinsics::write_barrier,
+        );
+        let handle = self.gen_object_handle(handle.into());
+        let allocator_handle = self.gen_allocator_handle();
+        self.builder
+            .build_call(write_barrier_fn_ptr, &[handle, allocator_handle], "");
+    }
+
     fn should_use_dispatch_table(&self) -> bool {
         // FIXME: When we use the dispatch table, generated wrappers have infinite recursion
         !self.params.make_marshallable
     }
 
-    /// Generates IR for a function call.
-    fn gen_call(&mut self, function: hir::Function, args: &[BasicValueEnum]) -> CallSiteValue {
+    /// Generates IR for the call expression `expr` that calls `function`, returning the value of
+    /// the call.
+    fn gen_fn_call(
+        &mut self,
+        expr: ExprId,
+        function: &FunctionInstance,
+        args: &[BasicValueEnum],
+    ) -> Option<BasicValueEnum> {
+        self.gen_call(function, args)
+            .try_as_basic_value()
+            .left()
+            // If the called function is a void function it doesn't return anything. If this method
+            // (`gen_expr`) returns None we assume the return value is `never`. We return a const
+            // unit struct here to ensure that at least something is returned. This matches with
+            // the hir where a `nothing` is returned instead of a `never`.
+            //
+            // This unit value will also be optimized out.
+            .or_else(|| match self.infer[expr] {
+                hir::ty_app!(hir::TypeCtor::Never) => None,
+                _ => Some(self.db.context().const_struct(&[], false).into()),
+            })
+    }
+
+    /// Generates IR for the call expression `expr` that calls the function pointer `callee`,
+    /// returning the value of the call. The handle of the function pointer's object is passed as
+    /// the first argument of the called function.
+    fn gen_fn_ptr_call(
+        &mut self,
+        expr: ExprId,
+        callee: ExprId,
+        args: &[ExprId],
+    ) -> Option<BasicValueEnum> {
+        let object = self
+            .gen_expr(callee)
+            .expect("expected a function pointer")
+            .into_pointer_value();
+
+        let mut call_args =
+            vec![self
+                .builder
+                .build_bitcast(object, object_ptr_ty(self.db), "object_ptr")];
+        for arg in args.iter() {
+            call_args.push(self.gen_expr(*arg).expect("expected a value"));
+        }
+
+        let mem_ptr = self
+            .builder
+            .build_load(object, "fn_ptr_mem_ptr")
+            .into_pointer_value();
+        let fn_ptr_ptr = unsafe { self.builder.build_struct_gep(mem_ptr, 0, "fn_ptr_ptr") };
+        let fn_ptr = self
+            .builder
+            .build_load(fn_ptr_ptr, "fn_ptr")
+            .into_pointer_value();
+
+        self.builder
+            .build_call(fn_ptr, &call_args, "fn_ptr")
+            .try_as_basic_value()
+            .left()
+            // Similar to `gen_fn_call`, a call that doesn't return anything returns a unit value.
+            .or_else(|| match self.infer[expr] {
+                hir::ty_app!(hir::TypeCtor::Never) => None,
+                _ => Some(self.db.context().const_struct(&[], false).into()),
+            })
+    }
+
+    fn gen_call(&mut self, function: &FunctionInstance, args: &[BasicValueEnum]) -> CallSiteValue {
+        let name = function.function.name(self.db).to_string();
         if self.dispatch_table.contains(function) && self.should_use_dispatch_table() {
             let ptr_value = self.dispatch_table.gen_function_lookup(
                 self.db,
@@ -1037,17 +2568,13 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
                 &self.builder,
                 function,
             );
-            self.builder
-                .build_call(ptr_value, &args, &function.name(self.db).to_string())
+            self.builder.build_call(ptr_value, &args, &name)
         } else {
-            let llvm_function = self.function_map.get(&function).unwrap_or_else(|| {
-                panic!(
-                    "missing function value for hir function: '{}'",
-                    function.name(self.db),
-                )
-            });
-            self.builder
-                .build_call(*llvm_function, &args, &function.name(self.db).to_string())
+            let llvm_function = self
+                .function_map
+                .get(function)
+                .unwrap_or_else(|| panic!("missing function value for hir function: '{}'", name));
+            self.builder.build_call(*llvm_function, &args, &name)
         }
     }
 
@@ -1127,6 +2654,160 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
         }
     }
 
+    /// Generates IR for a match expression. The arms are tested in order and the value of the first
+    /// arm whose pattern matches is returned.
+    fn gen_match(
+        &mut self,
+        expr: ExprId,
+        scrutinee: ExprId,
+        arms: &[MatchArm],
+    ) -> Option<BasicValueEnum> {
+        // Store the scrutinee in a temporary, so its fields can be accessed by the patterns
+        let value = self.gen_expr(scrutinee)?;
+        let place = self
+            .new_alloca_builder()
+            .build_alloca(value.get_type(), "match_value");
+        self.builder.build_store(place, value);
+
+        let context = self.db.context();
+        let exit_block = context.append_basic_block(&self.fn_value, "match_exit");
+
+        let mut arm_values = Vec::with_capacity(arms.len());
+        for arm in arms.iter() {
+            let next_block = context.append_basic_block(&self.fn_value, "match_next");
+            self.gen_pat_match(arm.pat, place, &next_block);
+
+            let arm_value = self.gen_expr(arm.expr);
+            if !self.infer[arm.expr].is_never() {
+                if let Some(arm_value) = arm_value {
+                    arm_values.push((arm_value, self.builder.get_insert_block().unwrap()));
+                }
+                self.builder.build_unconditional_branch(&exit_block);
+            }
+
+            let current_block = self.builder.get_insert_block().unwrap();
+            next_block.move_after(&current_block).unwrap();
+            self.builder.position_at_end(&next_block);
+        }
+
+        // The exhaustiveness check guarantees that one of the arms matches
+        self.builder.build_unreachable();
+
+        let current_block = self.builder.get_insert_block().unwrap();
+        exit_block.move_after(&current_block).unwrap();
+        self.builder.position_at_end(&exit_block);
+
+        if self.infer[expr].is_never() || arm_values.is_empty() {
+            self.builder.build_unreachable();
+            return None;
+        }
+
+        let phi = self
+            .builder
+            .build_phi(arm_values[0].0.get_type(), "match_result");
+        for (arm_value, arm_block) in arm_values.iter() {
+            phi.add_incoming(&[(arm_value, arm_block)]);
+        }
+        Some(phi.as_basic_value())
+    }
+
+    /// Generates IR that tests whether the value stored at `place` matches `pat`, binding its
+    /// sub-values to locals. If the value does not match, control flow continues at `no_match`.
+    fn gen_pat_match(&mut self, pat: PatId, place: PointerValue, no_match: &BasicBlock) {
+        let body = self.body.clone();
+        match &body[pat] {
+            Pat::Bind { name } => {
+                let value = self.builder.build_load(place, &name.to_string());
+                let ptr = self
+                    .new_alloca_builder()
+                    .build_alloca(value.get_type(), &name.to_string());
+                self.builder.build_store(ptr, value);
+                self.pat_to_local.insert(pat, ptr);
+                self.pat_to_name.insert(pat, name.to_string());
+                self.gen_debug_variable(pat, ptr, None);
+            }
+            Pat::Wild | Pat::Missing => {}
+            Pat::Tuple(args) => {
+                for (idx, arg) in args.iter().enumerate() {
+                    let element_ptr = unsafe {
+                        self.builder
+                            .build_struct_gep(place, idx as u32, "element_ptr")
+                    };
+                    self.gen_pat_match(*arg, element_ptr, no_match);
+                }
+            }
+            Pat::Path(_) | Pat::TupleStruct { .. } | Pat::Record { .. } => {
+                let variant = self
+                    .infer
+                    .variant_resolution_for_pat(pat)
+                    .expect("unresolved enum variant pattern");
+                let hir_enum = variant.parent_enum();
+
+                // Garbage collected enums are stored behind a layer of indirection
+                let enum_ptr = match hir_enum.data(self.db).memory_kind {
+                    hir::StructMemoryKind::Value => place,
+                    hir::StructMemoryKind::GC => {
+                        let enum_ptr_ptr = self
+                            .builder
+                            .build_load(place, "enum_ptr_ptr")
+                            .into_pointer_value();
+                        self.builder
+                            .build_load(enum_ptr_ptr, "enum_ptr")
+                            .into_pointer_value()
+                    }
+                };
+
+                let context = self.db.context();
+                let tag_ptr = unsafe { self.builder.build_struct_gep(enum_ptr, 0, "tag_ptr") };
+                let tag = self.builder.build_load(tag_ptr, "tag").into_int_value();
+                let is_match = self.builder.build_int_compare(
+                    IntPredicate::EQ,
+                    tag,
+                    context
+                        .i8_type()
+                        .const_int(variant.index(self.db) as u64, false),
+                    "is_match",
+                );
+                let match_block = context.append_basic_block(&self.fn_value, "match_arm");
+                self.builder
+                    .build_conditional_branch(is_match, &match_block, no_match);
+                self.builder.position_at_end(&match_block);
+
+                let field_pats: Vec<(u32, PatId)> = match &body[pat] {
+                    Pat::TupleStruct { args, .. } => args
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, arg)| (idx as u32, *arg))
+                        .collect(),
+                    Pat::Record { args, .. } => args
+                        .iter()
+                        .map(|arg| {
+                            let field_idx = variant
+                                .field(self.db, &arg.name)
+                                .expect("expected a variant field")
+                                .id()
+                                .into_raw()
+                                .into();
+                            (field_idx, arg.pat)
+                        })
+                        .collect(),
+                    _ => Vec::new(),
+                };
+
+                if !field_pats.is_empty() {
+                    let variant_ptr = self.gen_variant_ptr(enum_ptr, variant);
+                    for (field_idx, field_pat) in field_pats {
+                        let field_ptr = unsafe {
+                            self.builder
+                                .build_struct_gep(variant_ptr, field_idx, "field_ptr")
+                        };
+                        self.gen_pat_match(field_pat, field_ptr, no_match);
+                    }
+                }
+            }
+        }
+    }
+
     fn gen_return(&mut self, _expr: ExprId, ret_expr: Option<ExprId>) -> Option<BasicValueEnum> {
         let ret_value = ret_expr.and_then(|expr| self.gen_expr(expr));
 
@@ -1226,6 +2907,108 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
         Some(self.gen_empty())
     }
 
+    fn gen_for(
+        &mut self,
+        _expr: ExprId,
+        pat: PatId,
+        iterable: ExprId,
+        body_expr: ExprId,
+    ) -> Option<BasicValueEnum> {
+        let (start_expr, end_expr, op) = match &self.body[iterable] {
+            Expr::Range { start, end, op } => (*start, *end, *op),
+            _ => unreachable!("`for` loops can only iterate over ranges"),
+        };
+        let signedness = match self.infer[start_expr].as_simple() {
+            Some(TypeCtor::Int(ty)) => ty.signedness,
+            _ => unreachable!("ranges can only have integer bounds"),
+        };
+
+        // The bounds of the range are only evaluated once, before entering the loop
+        let start = self
+            .gen_expr(start_expr)
+            .map(|value| self.opt_deref_value(self.infer[start_expr].clone(), value))?
+            .into_int_value();
+        let end = self
+            .gen_expr(end_expr)
+            .map(|value| self.opt_deref_value(self.infer[end_expr].clone(), value))?
+            .into_int_value();
+
+        // The counter is kept separate from the binding of the pattern, so that assigning to the
+        // binding in the body of the loop does not affect the iteration.
+        let builder = self.new_alloca_builder();
+        let counter_ptr = builder.build_alloca(start.get_type(), "counter");
+        if let Pat::Bind { name } = &self.body[pat] {
+            let ptr = builder.build_alloca(start.get_type(), &name.to_string());
+            self.pat_to_local.insert(pat, ptr);
+            self.pat_to_name.insert(pat, name.to_string());
+            self.gen_debug_variable(pat, ptr, None);
+        }
+        self.builder.build_store(counter_ptr, start);
+
+        let context = self.db.context();
+        let cond_block = context.append_basic_block(&self.fn_value, "forcond");
+        let loop_block = context.append_basic_block(&self.fn_value, "for");
+        let step_block = context.append_basic_block(&self.fn_value, "forstep");
+        let exit_block = context.append_basic_block(&self.fn_value, "afterfor");
+
+        // Insert an explicit fall through from the current block to the condition check
+        self.builder.build_unconditional_branch(&cond_block);
+
+        // Generate condition block
+        self.builder.position_at_end(&cond_block);
+        let counter = self
+            .builder
+            .build_load(counter_ptr, "counter")
+            .into_int_value();
+        let in_range = self.gen_cmp_bin_op_int(
+            counter,
+            end,
+            CmpOp::Ord {
+                ordering: Ordering::Less,
+                strict: op == RangeOp::Exclusive,
+            },
+            signedness,
+        );
+        self.builder
+            .build_conditional_branch(in_range, &loop_block, &exit_block);
+
+        // Generate loop block
+        self.builder.position_at_end(&loop_block);
+        if let Some(ptr) = self.pat_to_local.get(&pat) {
+            self.builder.build_store(*ptr, counter);
+        }
+        let (exit_block, _, value) = self.gen_loop_block_expr(body_expr, exit_block);
+        if value.is_some() {
+            self.builder.build_unconditional_branch(&step_block);
+        }
+
+        // Generate step block. An inclusive range exits when the end is reached, before the counter
+        // is incremented, so the counter never overflows.
+        self.builder.position_at_end(&step_block);
+        let counter = self
+            .builder
+            .build_load(counter_ptr, "counter")
+            .into_int_value();
+        if op == RangeOp::Inclusive {
+            let increment_block = context.append_basic_block(&self.fn_value, "forinc");
+            let is_end =
+                self.gen_cmp_bin_op_int(counter, end, CmpOp::Eq { negated: false }, signedness);
+            self.builder
+                .build_conditional_branch(is_end, &exit_block, &increment_block);
+            self.builder.position_at_end(&increment_block);
+        }
+        let next =
+            self.builder
+                .build_int_add(counter, counter.get_type().const_int(1, false), "next");
+        self.builder.build_store(counter_ptr, next);
+        self.builder.build_unconditional_branch(&cond_block);
+
+        // Generate exit block
+        self.builder.position_at_end(&exit_block);
+
+        Some(self.gen_empty())
+    }
+
     fn gen_loop(&mut self, _expr: ExprId, body_expr: ExprId) -> Option<BasicValueEnum> {
         let context = self.db.context();
         let loop_block = context.append_basic_block(&self.fn_value, "loop");
@@ -1257,6 +3040,10 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
     }
 
     fn gen_field(&mut self, _expr: ExprId, receiver_expr: ExprId, name: &Name) -> PointerValue {
+        if self.infer[receiver_expr].as_tuple().is_some() {
+            return self.gen_tuple_field(receiver_expr, name);
+        }
+
         let hir_struct = self.infer[receiver_expr]
             .as_struct()
             .expect("expected a struct");
@@ -1269,9 +3056,20 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
             .into();
 
         let receiver_ptr = self.gen_place_expr(receiver_expr);
-        let receiver_ptr = self
-            .opt_deref_value(self.infer[receiver_expr].clone(), receiver_ptr.into())
-            .into_pointer_value();
+        let receiver_ptr = match hir_struct.data(self.db).memory_kind {
+            hir::StructMemoryKind::GC => {
+                let handle = self
+                    .builder
+                    .build_load(receiver_ptr, "mem_ptr")
+                    .into_pointer_value();
+                self.place_owner = Some(handle);
+                self.builder
+                    .build_load(handle, "deref")
+                    .into_pointer_value()
+            }
+            // The fields of a value struct are located in the memory of its own place
+            hir::StructMemoryKind::Value => receiver_ptr,
+        };
         unsafe {
             self.builder.build_struct_gep(
                 receiver_ptr,
@@ -1280,6 +3078,39 @@ impl<'a, 'b, D: IrDatabase> BodyIrGenerator<'a, 'b, D> {
             )
         }
     }
+
+    /// Generates IR that returns a pointer to the element `name` of a tuple, e.g. `pair.0`.
+    fn gen_tuple_field(&mut self, receiver_expr: ExprId, name: &Name) -> PointerValue {
+        let element_idx = name.as_tuple_index().expect("expected a tuple index");
+
+        // A tuple that is not stored in a place, e.g. the result of a call, is stored in a
+        // temporary first
+        let body = self.body.clone();
+        let receiver_ptr = match &body[receiver_expr] {
+            Expr::Path(_) | Expr::Field { .. } | Expr::Index { .. } => {
+                self.gen_place_expr(receiver_expr)
+            }
+            _ => {
+                let value = self
+                    .gen_expr(receiver_expr)
+                    .expect("expected a tuple value");
+                let ptr = self
+                    .new_alloca_builder()
+                    .build_alloca(value.get_type(), "tuple");
+                self.builder.build_store(ptr, value);
+                self.place_owner = None;
+                ptr
+            }
+        };
+
+        unsafe {
+            self.builder.build_struct_gep(
+                receiver_ptr,
+                element_idx as u32,
+                &format!("tuple.{}", element_idx),
+            )
+        }
+    }
 }
 
 /// Derefs a heap-allocated value. As we introduce a layer of indirection for hot
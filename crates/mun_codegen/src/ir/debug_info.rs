@@ -0,0 +1,360 @@
+use crate::ir::{try_convert_any_to_basic, ty::struct_name};
+use crate::{CodeGenParams, IrDatabase, Module};
+use hir::{line_index::LineCol, FileId, FnSig, HirDisplay, Substs, Ty, TypeCtor};
+use inkwell::{
+    basic_block::BasicBlock,
+    context::Context,
+    debug_info::{
+        AsDIScope, DICompileUnit, DIFile, DIFlags, DIFlagsConstants, DILocation, DIScope,
+        DISubprogram, DIType, DWARFEmissionKind, DWARFSourceLanguage, DebugInfoBuilder,
+    },
+    module::{FlagBehavior, Linkage},
+    types::BasicTypeEnum,
+    values::{FunctionValue, PointerValue},
+    AddressSpace, OptimizationLevel,
+};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+};
+
+/// The version of the debug information metadata that is generated, see
+/// `llvm::DEBUG_METADATA_VERSION`.
+const DEBUG_METADATA_VERSION: u64 = 3;
+
+// The DWARF encodings of basic types
+const DW_ATE_BOOLEAN: u32 = 0x02;
+const DW_ATE_FLOAT: u32 = 0x04;
+const DW_ATE_SIGNED: u32 = 0x05;
+const DW_ATE_UNSIGNED: u32 = 0x07;
+
+/// The debug information of a single source file. It describes the functions of the file, their
+/// local variables, and the source locations of their instructions, which enables native debuggers
+/// and profilers to map machine code back to Mun code.
+pub(crate) struct DebugInfo {
+    builder: DebugInfoBuilder,
+    compile_unit: DICompileUnit,
+    file: DIFile,
+    is_optimized: bool,
+    /// The debug information of all types that have been described so far
+    types: RefCell<HashMap<Ty, DIType>>,
+    /// The structs of which the debug information is being generated, used to break cycles
+    pending_structs: RefCell<HashSet<hir::Struct>>,
+}
+
+impl DebugInfo {
+    /// Constructs the debug information for the file `file_id`, of which the IR is generated in
+    /// `module`.
+    pub fn new(db: &impl IrDatabase, module: &Module, file_id: FileId) -> Self {
+        module.add_basic_value_flag(
+            "Debug Info Version",
+            FlagBehavior::Warning,
+            db.context()
+                .i32_type()
+                .const_int(DEBUG_METADATA_VERSION, false),
+        );
+
+        let is_optimized = db.optimization_lvl() != OptimizationLevel::None;
+        let (builder, compile_unit) = module.create_debug_info_builder(
+            true,
+            // DWARF does not define a language code for Mun. Debuggers understand C best, which is
+            // close enough for the types and expressions that Mun supports.
+            DWARFSourceLanguage::C,
+            db.file_relative_path(file_id).as_str(),
+            &db.source_dir().to_string_lossy(),
+            concat!("mun ", env!("CARGO_PKG_VERSION")),
+            is_optimized,
+            "",
+            0,
+            "",
+            DWARFEmissionKind::Full,
+            0,
+            false,
+            false,
+        );
+        let file = compile_unit.get_file();
+
+        DebugInfo {
+            builder,
+            compile_unit,
+            file,
+            is_optimized,
+            types: RefCell::new(HashMap::new()),
+            pending_structs: RefCell::new(HashSet::new()),
+        }
+    }
+
+    /// Generates the debug information of `function`, which is declared at `line_col` and has
+    /// the signature `sig`, and attaches it to the function.
+    pub fn gen_function(
+        &self,
+        db: &impl IrDatabase,
+        function: FunctionValue,
+        line_col: LineCol,
+        sig: &FnSig,
+    ) -> DISubprogram {
+        let ret_ty = if sig.ret().is_empty() || sig.ret().is_never() {
+            None
+        } else {
+            Some(self.ty(db, sig.ret()))
+        };
+        let param_tys: Vec<DIType> = sig.params().iter().map(|ty| self.ty(db, ty)).collect();
+        let subroutine_ty =
+            self.builder
+                .create_subroutine_type(self.file, ret_ty, &param_tys, DIFlags::PUBLIC);
+
+        let name = function.get_name().to_string_lossy().into_owned();
+        let line = line_col.line + 1;
+        let subprogram = self.builder.create_function(
+            self.compile_unit.as_debug_info_scope(),
+            &name,
+            Some(&name),
+            self.file,
+            line,
+            subroutine_ty,
+            function.get_linkage() == Linkage::Private,
+            true,
+            line,
+            DIFlags::PUBLIC,
+            self.is_optimized,
+        );
+        function.set_subprogram(subprogram);
+        subprogram
+    }
+
+    /// Returns the debug location of `line_col` within `scope`.
+    pub fn location(&self, context: &Context, line_col: LineCol, scope: DIScope) -> DILocation {
+        self.builder.create_debug_location(
+            context,
+            line_col.line + 1,
+            line_col.col + 1,
+            scope,
+            None,
+        )
+    }
+
+    /// Declares the variable `name` of type `ty` in `scope`, which is stored at `ptr`. The
+    /// declaration is inserted at the end of `block`. Parameters are declared with their one-based
+    /// position `arg_no`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn gen_variable(
+        &self,
+        db: &impl IrDatabase,
+        scope: DISubprogram,
+        name: &str,
+        ty: &Ty,
+        arg_no: Option<u32>,
+        line_col: LineCol,
+        ptr: PointerValue,
+        block: &BasicBlock,
+    ) {
+        let scope = scope.as_debug_info_scope();
+        let ty = self.ty(db, ty);
+        let line = line_col.line + 1;
+        let variable = match arg_no {
+            Some(arg_no) => self.builder.create_parameter_variable(
+                scope,
+                name,
+                arg_no,
+                self.file,
+                line,
+                ty,
+                true,
+                DIFlags::ZERO,
+            ),
+            None => self.builder.create_auto_variable(
+                scope,
+                name,
+                self.file,
+                line,
+                ty,
+                true,
+                DIFlags::ZERO,
+                0,
+            ),
+        };
+        let location = self.location(&db.context(), line_col, scope);
+        self.builder
+            .insert_declare_at_end(ptr, Some(variable), None, location, *block);
+    }
+
+    /// Resolves all debug information. This must be called after all functions of the file have
+    /// been generated.
+    pub fn finalize(&self) {
+        self.builder.finalize();
+    }
+
+    /// Returns the debug information of the type `ty`.
+    fn ty(&self, db: &impl IrDatabase, ty: &Ty) -> DIType {
+        if let Some(di_ty) = self.types.borrow().get(ty) {
+            return *di_ty;
+        }
+
+        let di_ty = match ty {
+            hir::ty_app!(TypeCtor::Bool) => self.basic_ty(db, ty, DW_ATE_BOOLEAN),
+            hir::ty_app!(TypeCtor::Int(int_ty)) => {
+                let encoding = if int_ty.signedness == hir::Signedness::Signed {
+                    DW_ATE_SIGNED
+                } else {
+                    DW_ATE_UNSIGNED
+                };
+                self.basic_ty(db, ty, encoding)
+            }
+            hir::ty_app!(TypeCtor::Float(_)) => self.basic_ty(db, ty, DW_ATE_FLOAT),
+            // An optional reference is stored as the reference itself
+            hir::ty_app!(TypeCtor::Optional, parameters) => self.ty(
+                db,
+                parameters
+                    .as_single()
+                    .expect("an optional type must have an inner type"),
+            ),
+            hir::ty_app!(TypeCtor::Struct(s), parameters) => {
+                let struct_ty = self.struct_ty(db, *s, parameters);
+                match s.data(db).memory_kind {
+                    // A garbage collected struct is referred to through a pointer to its memory
+                    hir::StructMemoryKind::GC => {
+                        let ptr_ty =
+                            self.pointer_ty(db, &format!("*mut {}", ty.display(db)), struct_ty);
+                        self.pointer_ty(db, &format!("*const *mut {}", ty.display(db)), ptr_ty)
+                    }
+                    hir::StructMemoryKind::Value => struct_ty,
+                }
+            }
+            // Other types are described by their name and size, without their internals
+            _ => self.opaque_ty(db, &ty.display(db).to_string(), self.ir_ty(db, ty)),
+        };
+
+        self.types.borrow_mut().insert(ty.clone(), di_ty);
+        di_ty
+    }
+
+    /// Returns the debug information of the primitive type `ty`, with the DWARF `encoding`.
+    fn basic_ty(&self, db: &impl IrDatabase, ty: &Ty, encoding: u32) -> DIType {
+        let size_in_bits = self.size_in_bits(db, self.ir_ty(db, ty));
+        self.builder
+            .create_basic_type(
+                &ty.display(db).to_string(),
+                size_in_bits,
+                encoding,
+                DIFlags::PUBLIC,
+            )
+            .expect("invalid basic type")
+            .as_type()
+    }
+
+    /// Returns a pointer type named `name` that points to `pointee`.
+    fn pointer_ty(&self, db: &impl IrDatabase, name: &str, pointee: DIType) -> DIType {
+        let target_data = db.target_data();
+        let ptr_ty = db
+            .context()
+            .i8_type()
+            .ptr_type(AddressSpace::Generic)
+            .into();
+        self.builder
+            .create_pointer_type(
+                name,
+                pointee,
+                self.size_in_bits(db, ptr_ty),
+                target_data.get_abi_alignment(&ptr_ty) * 8,
+                AddressSpace::Generic,
+            )
+            .as_type()
+    }
+
+    /// Returns the debug information of the instantiation of the struct `s` with the type
+    /// arguments `substs`, including its fields.
+    fn struct_ty(&self, db: &impl IrDatabase, s: hir::Struct, substs: &Substs) -> DIType {
+        let name = struct_name(db, s, substs);
+        let ir_ty = db.struct_ty(s, substs.clone());
+
+        // A struct can refer to itself through an optional field, in which case the inner
+        // reference is described without fields.
+        if !self.pending_structs.borrow_mut().insert(s) {
+            return self.opaque_ty(db, &name, ir_ty.into());
+        }
+
+        let target_data = db.target_data();
+        let scope = self.compile_unit.as_debug_info_scope();
+        let fields: Vec<DIType> = s
+            .fields(db)
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| {
+                let field_ty = field.ty(db).subst(substs);
+                let field_ir_ty = self.ir_ty(db, &field_ty);
+                let offset = target_data
+                    .offset_of_element(&ir_ty, idx as u32)
+                    .expect("field index out of bounds");
+                self.builder
+                    .create_member_type(
+                        scope,
+                        &field.name(db).to_string(),
+                        self.file,
+                        0,
+                        self.size_in_bits(db, field_ir_ty),
+                        target_data.get_abi_alignment(&field_ir_ty) * 8,
+                        offset * 8,
+                        DIFlags::PUBLIC,
+                        self.ty(db, &field_ty),
+                    )
+                    .as_type()
+            })
+            .collect();
+
+        self.pending_structs.borrow_mut().remove(&s);
+
+        self.builder
+            .create_struct_type(
+                scope,
+                &name,
+                self.file,
+                0,
+                self.size_in_bits(db, ir_ty.into()),
+                target_data.get_abi_alignment(&ir_ty) * 8,
+                DIFlags::PUBLIC,
+                None,
+                &fields,
+                0,
+                None,
+                &name,
+            )
+            .as_type()
+    }
+
+    /// Returns the debug information of a type named `name` that only describes the size of the
+    /// IR type `ir_ty`.
+    fn opaque_ty(&self, db: &impl IrDatabase, name: &str, ir_ty: BasicTypeEnum) -> DIType {
+        self.builder
+            .create_struct_type(
+                self.compile_unit.as_debug_info_scope(),
+                name,
+                self.file,
+                0,
+                self.size_in_bits(db, ir_ty),
+                db.target_data().get_abi_alignment(&ir_ty) * 8,
+                DIFlags::PUBLIC,
+                None,
+                &[],
+                0,
+                None,
+                name,
+            )
+            .as_type()
+    }
+
+    /// Returns the IR type in which a value of type `ty` is stored.
+    fn ir_ty(&self, db: &impl IrDatabase, ty: &Ty) -> BasicTypeEnum {
+        try_convert_any_to_basic(db.type_ir(
+            ty.clone(),
+            CodeGenParams {
+                make_marshallable: false,
+            },
+        ))
+        .expect("expected basic type")
+    }
+
+    /// Returns the number of bits that a value of the IR type `ty` occupies in memory.
+    fn size_in_bits(&self, db: &impl IrDatabase, ty: BasicTypeEnum) -> u64 {
+        db.target_data().get_store_size(&ty) * 8
+    }
+}
@@ -1,5 +1,5 @@
 use crate::intrinsics::Intrinsic;
-use crate::ir::function;
+use crate::ir::function::{self, FunctionInstance};
 use crate::type_info::TypeInfo;
 use crate::{CodeGenParams, IrDatabase};
 use hir::{Body, Expr, ExprId, InferenceResult};
@@ -32,7 +32,7 @@ pub struct DispatchTable {
     // The target for which to create the dispatch table
     target: Arc<TargetData>,
     // This contains the function that map to the DispatchTable struct fields
-    function_to_idx: HashMap<hir::Function, usize>,
+    function_to_idx: HashMap<FunctionInstance, usize>,
     // Prototype to function index
     prototype_to_idx: HashMap<FunctionPrototype, usize>,
     // This contains an ordered list of all the function in the dispatch table
@@ -56,13 +56,13 @@ pub struct FunctionPrototype {
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct DispatchableFunction {
     pub prototype: FunctionPrototype,
-    pub hir: Option<hir::Function>,
+    pub hir: Option<FunctionInstance>,
 }
 
 impl DispatchTable {
     /// Returns whether the `DispatchTable` contains the specified `function`.
-    pub fn contains(&self, function: hir::Function) -> bool {
-        self.function_to_idx.contains_key(&function)
+    pub fn contains(&self, function: &FunctionInstance) -> bool {
+        self.function_to_idx.contains_key(function)
     }
 
     /// Returns a slice containing all the functions in the dispatch table.
@@ -78,14 +78,14 @@ impl DispatchTable {
         db: &D,
         table_ref: Option<inkwell::values::GlobalValue>,
         builder: &inkwell::builder::Builder,
-        function: hir::Function,
+        function: &FunctionInstance,
     ) -> PointerValue {
-        let function_name = function.name(db).to_string();
+        let function_name = function.name(db);
 
         // Get the index of the function
         let index = *self
             .function_to_idx
-            .get(&function)
+            .get(function)
             .expect("unknown function");
 
         self.gen_function_lookup_by_index(table_ref, builder, &function_name, index)
@@ -158,7 +158,7 @@ pub(crate) struct DispatchTableBuilder<'a, D: IrDatabase> {
     // The target for which to create the dispatch table
     target: Arc<TargetData>,
     // This contains the functions that map to the DispatchTable struct fields
-    function_to_idx: HashMap<hir::Function, usize>,
+    function_to_idx: HashMap<FunctionInstance, usize>,
     // Prototype to function index
     prototype_to_idx: HashMap<FunctionPrototype, usize>,
     // These are *all* called functions in the modules
@@ -230,9 +230,33 @@ impl<'a, D: IrDatabase> DispatchTableBuilder<'a, D> {
         // If this expression is a call, store it in the dispatch table
         if let Expr::Call { callee, .. } = expr {
             match infer[*callee].as_callable_def() {
-                Some(hir::CallableDef::Function(def)) => self.collect_fn_def(def),
-                Some(hir::CallableDef::Struct(_)) => (),
-                None => panic!("expected a callable expression"),
+                Some(hir::CallableDef::Function(_)) => {
+                    let instance = FunctionInstance::from_callee(self.db, &infer[*callee])
+                        .expect("expected a function");
+                    self.collect_fn_def(instance)
+                }
+                Some(hir::CallableDef::BuiltinFunction(_))
+                | Some(hir::CallableDef::Struct(_))
+                | Some(hir::CallableDef::EnumVariant(_)) => (),
+                // Function pointers and closures are called through their object
+                None => (),
+            }
+        }
+
+        // Methods that are defined in `impl` blocks are called like any other function
+        if let Expr::MethodCall { .. } = expr {
+            if let Some(instance) = FunctionInstance::from_method_call(infer, expr_id) {
+                self.collect_fn_def(instance);
+            }
+        }
+
+        // Functions that are used as values are called through a thunk
+        if let Expr::Path(_) = expr {
+            let ty = &infer[expr_id];
+            if let hir::ty_app!(hir::TypeCtor::FnDef(hir::CallableDef::Function(_))) = ty {
+                let instance =
+                    FunctionInstance::from_callee(self.db, ty).expect("expected a function");
+                self.collect_fn_def(instance);
             }
         }
 
@@ -242,12 +266,12 @@ impl<'a, D: IrDatabase> DispatchTableBuilder<'a, D> {
 
     /// Collects function call expression from the given expression.
     #[allow(clippy::map_entry)]
-    fn collect_fn_def(&mut self, function: hir::Function) {
+    fn collect_fn_def(&mut self, function: FunctionInstance) {
         self.ensure_table_ref();
 
         // If the function is not yet contained in the table, add it
         if !self.function_to_idx.contains_key(&function) {
-            let name = function.name(self.db).to_string();
+            let name = function.name(self.db);
             let hir_type = function.ty(self.db);
             let sig = hir_type.callable_sig(self.db).unwrap();
             let ir_type = self
@@ -279,7 +303,7 @@ impl<'a, D: IrDatabase> DispatchTableBuilder<'a, D> {
             self.entries.push(TypedDispatchableFunction {
                 function: DispatchableFunction {
                     prototype: prototype.clone(),
-                    hir: Some(function),
+                    hir: Some(function.clone()),
                 },
                 ir_type,
             });
@@ -318,10 +342,10 @@ impl<'a, D: IrDatabase> DispatchTableBuilder<'a, D> {
                 .map(|(i, entry)| {
                     let function_type = table_body[i].into_pointer_type();
                     // Find the associated IR function if it exists
-                    match entry.function.hir {
+                    match &entry.function.hir {
                         // Case external function: Convert to typed null for the given function
                         None => function_type.const_null(),
-                        Some(f) if f.is_extern(self.db) => function_type.const_null(),
+                        Some(f) if f.function.is_extern(self.db) => function_type.const_null(),
                         // Case mun function: Get the function location as the initializer
                         Some(f) => function::gen_signature(
                             self.db,
@@ -1,7 +1,12 @@
 use super::body::ExternalGlobals;
-use crate::ir::{function, type_table::TypeTable};
+use crate::ir::{
+    debug_info::DebugInfo,
+    function::{self, FunctionInstance},
+    global,
+    type_table::TypeTable,
+};
 use crate::{CodeGenParams, IrDatabase};
-use hir::{FileId, ModuleDef};
+use hir::FileId;
 use inkwell::module::Module;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
@@ -23,38 +28,43 @@ pub(crate) fn ir_query(db: &impl IrDatabase, file_id: FileId) -> Arc<FileIR> {
         .context()
         .create_module(db.file_relative_path(file_id).as_str());
 
-    let group_ir = db.group_ir(file_id);
+    let module = hir::Module::from(file_id);
+    let group_ir = db.group_ir(module.package(db));
 
-    // Generate all exposed function and wrapper function signatures.
-    // Use a `BTreeMap` to guarantee deterministically ordered output.ures
+    // Generate all function instance and wrapper function signatures. Functions of other modules
+    // in the package are only declared, they are defined in the IR of their own file.
+    // Use a `BTreeMap` to guarantee deterministically ordered output.
     let mut functions = HashMap::new();
     let mut wrapper_functions = BTreeMap::new();
-    for def in db.module_data(file_id).definitions() {
-        if let ModuleDef::Function(f) = def {
-            if !f.is_extern(db) {
-                let fun = function::gen_signature(
-                    db,
-                    *f,
-                    &llvm_module,
-                    CodeGenParams {
-                        make_marshallable: false,
-                    },
-                );
-                functions.insert(*f, fun);
+    for instance in group_ir.function_instances.iter() {
+        let f = instance.function;
+        let fun = function::gen_signature(
+            db,
+            instance,
+            &llvm_module,
+            CodeGenParams {
+                make_marshallable: false,
+            },
+        );
+        functions.insert(instance.clone(), fun);
 
-                let fn_sig = f.ty(db).callable_sig(db).unwrap();
-                if !f.data(db).visibility().is_private() && !fn_sig.marshallable(db) {
-                    let wrapper_fun = function::gen_signature(
-                        db,
-                        *f,
-                        &llvm_module,
-                        CodeGenParams {
-                            make_marshallable: true,
-                        },
-                    );
-                    wrapper_functions.insert(*f, wrapper_fun);
-                }
-            }
+        // Instantiations of generic functions are not part of the API, so they don't need a
+        // wrapper
+        let fn_sig = instance.ty(db).callable_sig(db).unwrap();
+        if f.module(db) == module
+            && instance.substs.is_empty()
+            && !f.visibility(db).is_private()
+            && !fn_sig.marshallable(db)
+        {
+            let wrapper_fun = function::gen_signature(
+                db,
+                instance,
+                &llvm_module,
+                CodeGenParams {
+                    make_marshallable: true,
+                },
+            );
+            wrapper_functions.insert(f, wrapper_fun);
         }
     }
 
@@ -71,25 +81,43 @@ pub(crate) fn ir_query(db: &impl IrDatabase, file_id: FileId) -> Arc<FileIR> {
         } else {
             Some(llvm_module.add_global(group_ir.type_table.ty(), None, TypeTable::NAME))
         };
+        let globals = group_ir
+            .globals
+            .iter()
+            .map(|s| (*s, global::gen_global_decl(db, &llvm_module, *s)))
+            .collect();
         ExternalGlobals {
             alloc_handle,
             dispatch_table,
             type_table,
+            globals,
         }
     };
 
     // Construct requirements for generating the bodies
     let fn_pass_manager = function::create_pass_manager(&llvm_module, db.optimization_lvl());
+    let debug_info = if db.debug_info() {
+        Some(DebugInfo::new(db, &llvm_module, file_id))
+    } else {
+        None
+    };
 
-    // Generate the function bodies
-    for (hir_function, llvm_function) in functions.iter() {
+    // Generate the function bodies. Bodies define globals, e.g. for string literals, so they are
+    // generated in a deterministic order to guarantee deterministically named globals.
+    for instance in group_ir.function_instances.iter() {
+        if instance.function.module(db) != module {
+            continue;
+        }
+        let llvm_function = &functions[instance];
         function::gen_body(
             db,
-            (*hir_function, *llvm_function),
+            &llvm_module,
+            (instance, *llvm_function),
             &functions,
             &group_ir.dispatch_table,
             &group_ir.type_table,
             external_globals.clone(),
+            debug_info.as_ref(),
         );
         fn_pass_manager.run_on(llvm_function);
     }
@@ -97,7 +125,8 @@ pub(crate) fn ir_query(db: &impl IrDatabase, file_id: FileId) -> Arc<FileIR> {
     for (hir_function, llvm_function) in wrapper_functions.iter() {
         function::gen_wrapper_body(
             db,
-            (*hir_function, *llvm_function),
+            &llvm_module,
+            (&FunctionInstance::from(*hir_function), *llvm_function),
             &functions,
             &group_ir.dispatch_table,
             &group_ir.type_table,
@@ -106,11 +135,16 @@ pub(crate) fn ir_query(db: &impl IrDatabase, file_id: FileId) -> Arc<FileIR> {
         fn_pass_manager.run_on(llvm_function);
     }
 
-    // Filter private methods
+    if let Some(debug_info) = debug_info {
+        debug_info.finalize();
+    }
+
+    // Filter private methods and instantiations of generic functions
     let api: HashSet<hir::Function> = functions
         .keys()
-        .filter(|f| f.visibility(db) != hir::Visibility::Private)
-        .cloned()
+        .filter(|f| f.substs.is_empty())
+        .map(|f| f.function)
+        .filter(|f| f.module(db) == module && f.visibility(db) != hir::Visibility::Private)
         .collect();
 
     Arc::new(FileIR {
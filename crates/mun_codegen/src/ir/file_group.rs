@@ -2,11 +2,12 @@ use super::{
     abi_types::{gen_abi_types, AbiTypes},
     adt,
     dispatch_table::{DispatchTable, DispatchTableBuilder},
-    intrinsics,
+    function::{self, FunctionInstance},
+    global, intrinsics,
     type_table::{TypeTable, TypeTableBuilder},
 };
 use crate::IrDatabase;
-use hir::ModuleDef;
+use hir::{ModuleDef, Substs};
 use inkwell::{module::Module, types::PointerType, values::UnnamedAddress, AddressSpace};
 use std::{collections::BTreeMap, sync::Arc};
 
@@ -24,52 +25,87 @@ pub struct FileGroupIR {
     pub(crate) type_table: TypeTable,
     /// The allocator handle, if it exists
     pub(crate) allocator_handle_type: Option<PointerType>,
+    /// The instances of all functions in the package for which IR is generated
+    pub(crate) function_instances: Vec<FunctionInstance>,
+    /// The global variables of the package, which are defined in the IR
+    pub(crate) globals: Vec<hir::Static>,
 }
 
-/// Generates IR that is shared among the group's files.
-/// TODO: Currently, a group always consists of a single file. Need to add support for multiple
-/// files using something like `FileGroupId`.
-pub(crate) fn ir_query(db: &impl IrDatabase, file_id: hir::FileId) -> Arc<FileGroupIR> {
+/// Generates IR that is shared among the files of a package.
+pub(crate) fn ir_query(db: &impl IrDatabase, package: hir::Package) -> Arc<FileGroupIR> {
     let llvm_module = db.context().create_module("group_name");
 
+    // Collect the definitions of all the modules in the package
+    let definitions: Vec<ModuleDef> = package
+        .modules(db)
+        .into_iter()
+        .flat_map(|module| module.declarations(db))
+        .collect();
+
+    // Collect the instances of all functions, including the instantiations of generic functions
+    let function_instances = function::collect_instances(
+        db,
+        definitions.iter().filter_map(|def| match def {
+            ModuleDef::Function(f) => Some(*f),
+            _ => None,
+        }),
+    );
+
     // Use a `BTreeMap` to guarantee deterministically ordered output.
     let mut intrinsics_map = BTreeMap::new();
     let mut needs_alloc = false;
 
     // Collect all intrinsic functions, wrapper function, and generate struct declarations.
-    for def in db.module_data(file_id).definitions() {
+    for instance in function_instances.iter() {
+        let infer = instance.infer(db);
+        intrinsics::collect_fn_body(
+            db,
+            &mut intrinsics_map,
+            &mut needs_alloc,
+            &instance.function.body(db),
+            &infer,
+        );
+
+        // Instantiations of generic structs are declared where they are used
+        for ty in infer.types() {
+            adt::gen_generic_struct_decls(db, ty);
+        }
+
+        let fn_sig = instance.ty(db).callable_sig(db).unwrap();
+        if instance.substs.is_empty()
+            && !instance.function.visibility(db).is_private()
+            && !fn_sig.marshallable(db)
+        {
+            intrinsics::collect_wrapper_body(db, &mut intrinsics_map, &mut needs_alloc);
+        }
+    }
+    for def in definitions.iter() {
         match def {
-            ModuleDef::Function(f) if !f.is_extern(db) => {
-                intrinsics::collect_fn_body(
-                    db,
-                    &mut intrinsics_map,
-                    &mut needs_alloc,
-                    &f.body(db),
-                    &f.infer(db),
-                );
-
-                let fn_sig = f.ty(db).callable_sig(db).unwrap();
-                if !f.data(db).visibility().is_private() && !fn_sig.marshallable(db) {
-                    intrinsics::collect_wrapper_body(db, &mut intrinsics_map, &mut needs_alloc);
-                }
-            }
             ModuleDef::Function(_) => (), // TODO: Extern types?
-            ModuleDef::Struct(s) => {
-                adt::gen_struct_decl(db, *s);
+            ModuleDef::Struct(s) if s.generic_params(db).is_empty() => {
+                adt::gen_struct_decl(db, *s, &Substs::empty());
+            }
+            ModuleDef::Struct(_) => (),
+            ModuleDef::Enum(e) => {
+                adt::gen_enum_decl(db, *e);
             }
-            ModuleDef::BuiltinType(_) => (),
+            ModuleDef::EnumVariant(_)
+            | ModuleDef::BuiltinFunction(_)
+            | ModuleDef::BuiltinType(_)
+            | ModuleDef::Module(_)
+            | ModuleDef::Trait(_)
+            | ModuleDef::Const(_)
+            | ModuleDef::Static(_) => (),
         }
     }
 
     // Collect all exposed functions' bodies.
     let mut dispatch_table_builder = DispatchTableBuilder::new(db, &llvm_module, &intrinsics_map);
-    for def in db.module_data(file_id).definitions() {
-        if let ModuleDef::Function(f) = def {
-            if !f.data(db).visibility().is_private() && !f.is_extern(db) {
-                let body = f.body(db);
-                let infer = f.infer(db);
-                dispatch_table_builder.collect_body(&body, &infer);
-            }
+    for instance in function_instances.iter() {
+        if !instance.function.visibility(db).is_private() {
+            let body = instance.function.body(db);
+            let infer = instance.infer(db);
+            dispatch_table_builder.collect_body(&body, &infer);
         }
     }
 
@@ -85,17 +121,33 @@ pub(crate) fn ir_query(db: &impl IrDatabase, file_id: hir::FileId) -> Arc<FileGr
     );
 
     // Collect all used types
-    for def in db.module_data(file_id).definitions() {
+    for def in definitions.iter() {
         match def {
-            ModuleDef::Struct(s) => {
-                type_table_builder.collect_struct(*s);
+            ModuleDef::Struct(s) if s.generic_params(db).is_empty() => {
+                type_table_builder.collect_struct(*s, Substs::empty());
+            }
+            ModuleDef::Enum(e) => {
+                type_table_builder.collect_enum(*e);
             }
-            ModuleDef::Function(f) => {
-                type_table_builder.collect_fn(*f);
+            ModuleDef::Function(f) if f.is_extern(db) => {
+                type_table_builder.collect_fn(&FunctionInstance::from(*f));
             }
-            ModuleDef::BuiltinType(_) => (),
+            ModuleDef::Static(s) => {
+                type_table_builder.collect_global(*s);
+            }
+            ModuleDef::Struct(_)
+            | ModuleDef::Function(_)
+            | ModuleDef::EnumVariant(_)
+            | ModuleDef::BuiltinFunction(_)
+            | ModuleDef::BuiltinType(_)
+            | ModuleDef::Module(_)
+            | ModuleDef::Trait(_)
+            | ModuleDef::Const(_) => (),
         }
     }
+    for instance in function_instances.iter() {
+        type_table_builder.collect_fn(instance);
+    }
 
     let type_table = type_table_builder.build();
 
@@ -110,11 +162,31 @@ pub(crate) fn ir_query(db: &impl IrDatabase, file_id: hir::FileId) -> Arc<FileGr
         None
     };
 
+    // Define the global variables, which are initialized with values that are evaluated at
+    // compile time
+    let globals: Vec<hir::Static> = definitions
+        .iter()
+        .filter_map(|def| match def {
+            ModuleDef::Static(s) => Some(*s),
+            _ => None,
+        })
+        .collect();
+    for s in globals.iter() {
+        let initial_value = s
+            .initial_value(db)
+            .expect("the initial value of a global variable must be known at compile time");
+        let global = global::gen_global_decl(db, &llvm_module, *s);
+        global.set_initializer(&global::gen_const_value(db, initial_value, &s.ty(db)));
+        global.set_constant(!s.is_mut(db));
+    }
+
     Arc::new(FileGroupIR {
         llvm_module,
         abi_types,
         dispatch_table,
         type_table,
         allocator_handle_type,
+        function_instances,
+        globals,
     })
 }
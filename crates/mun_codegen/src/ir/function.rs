@@ -1,11 +1,157 @@
-use crate::ir::{body::BodyIrGenerator, dispatch_table::DispatchTable, type_table::TypeTable};
+use crate::ir::{
+    body::BodyIrGenerator, debug_info::DebugInfo, dispatch_table::DispatchTable, ty::type_name,
+    type_table::TypeTable,
+};
 use crate::values::FunctionValue;
 use crate::{CodeGenParams, IrDatabase, Module, OptimizationLevel};
+use hir::{Body, CallableDef, Expr, ExprId, InferenceResult, MethodResolution, Substs, TypeCtor};
+use inkwell::attributes::{Attribute, AttributeLoc};
+use inkwell::module::Linkage;
 use inkwell::passes::{PassManager, PassManagerBuilder};
-use inkwell::types::AnyTypeEnum;
+use inkwell::types::{AnyTypeEnum, FunctionType};
 
 use super::body::ExternalGlobals;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// A function of which all type parameters are replaced by concrete types. IR is generated for
+/// every instantiation of a generic function that is used in the package. A function without type
+/// parameters has a single instance with empty `substs`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FunctionInstance {
+    pub function: hir::Function,
+    pub substs: Substs,
+}
+
+impl From<hir::Function> for FunctionInstance {
+    fn from(function: hir::Function) -> Self {
+        FunctionInstance {
+            function,
+            substs: Substs::empty(),
+        }
+    }
+}
+
+impl FunctionInstance {
+    /// Returns the instance that is called through a callee of type `ty`, if the callee is a
+    /// function. A function of a trait is statically resolved to the function that implements it
+    /// for the `Self` type of the call.
+    pub fn from_callee(db: &impl IrDatabase, ty: &hir::Ty) -> Option<FunctionInstance> {
+        match ty {
+            hir::ty_app!(TypeCtor::FnDef(CallableDef::Function(function)), substs) => {
+                if function.trait_def(db).is_some() {
+                    let (self_ty, substs) = substs.split_first()?;
+                    Some(FunctionInstance {
+                        function: function.trait_impl_function(db, self_ty)?,
+                        substs: substs.to_vec().into(),
+                    })
+                } else {
+                    Some(FunctionInstance {
+                        function: *function,
+                        substs: substs.clone(),
+                    })
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the instance that is called by the method call expression `expr`, if the method is
+    /// a function.
+    pub fn from_method_call(infer: &InferenceResult, expr: ExprId) -> Option<FunctionInstance> {
+        match infer.method_resolution(expr)? {
+            MethodResolution::Function(function) => Some(FunctionInstance {
+                function,
+                substs: infer
+                    .method_substs(expr)
+                    .cloned()
+                    .unwrap_or_else(Substs::empty),
+            }),
+            MethodResolution::Builtin(_) => None,
+        }
+    }
+
+    /// Returns the name of the instance, e.g. `max<core::f32>`.
+    pub fn name(&self, db: &impl IrDatabase) -> String {
+        let name = self.function.full_name(db);
+        if self.substs.is_empty() {
+            name
+        } else {
+            let type_args: Vec<String> = self.substs.iter().map(|ty| type_name(db, ty)).collect();
+            format!("{}<{}>", name, type_args.join(", "))
+        }
+    }
+
+    /// Returns the type of the instance.
+    pub fn ty(&self, db: &impl IrDatabase) -> hir::Ty {
+        self.function.ty(db).subst(&self.substs)
+    }
+
+    /// Returns the result of type inference of the function's body with the type parameters
+    /// replaced by the type arguments of the instance.
+    pub fn infer(&self, db: &impl IrDatabase) -> Arc<InferenceResult> {
+        let infer = self.function.infer(db);
+        if self.substs.is_empty() {
+            infer
+        } else {
+            Arc::new(infer.subst(&self.substs))
+        }
+    }
+}
+
+/// Collects the instances of all non-extern functions that are used by the package, starting
+/// from its non-generic `functions`. Generic functions are instantiated for every combination of
+/// type arguments with which they are called. The instances are returned in the order in which
+/// they are discovered.
+pub(crate) fn collect_instances(
+    db: &impl IrDatabase,
+    functions: impl Iterator<Item = hir::Function>,
+) -> Vec<FunctionInstance> {
+    let mut instances: Vec<FunctionInstance> = functions
+        .filter(|f| !f.is_extern(db) && f.generic_params(db).is_empty())
+        .map(FunctionInstance::from)
+        .collect();
+    let mut visited: HashSet<FunctionInstance> = instances.iter().cloned().collect();
+
+    let mut idx = 0;
+    while idx < instances.len() {
+        let instance = instances[idx].clone();
+        let body = instance.function.body(db);
+        let infer = instance.infer(db);
+        let mut callees = Vec::new();
+        collect_callees(db, &mut callees, body.body_expr(), &body, &infer);
+        for callee in callees {
+            if !callee.function.is_extern(db) && visited.insert(callee.clone()) {
+                instances.push(callee);
+            }
+        }
+        idx += 1;
+    }
+
+    instances
+}
+
+/// Collects the instances of all functions that are called from the specified expression and its
+/// sub-expressions.
+fn collect_callees(
+    db: &impl IrDatabase,
+    callees: &mut Vec<FunctionInstance>,
+    expr_id: ExprId,
+    body: &Arc<Body>,
+    infer: &InferenceResult,
+) {
+    let expr = &body[expr_id];
+    let callee = match expr {
+        Expr::Call { callee, .. } => FunctionInstance::from_callee(db, &infer[*callee]),
+        Expr::MethodCall { .. } => FunctionInstance::from_method_call(infer, expr_id),
+        // A function that is used as a value is called through a function pointer
+        Expr::Path(_) => FunctionInstance::from_callee(db, &infer[expr_id]),
+        _ => None,
+    };
+    callees.extend(callee);
+
+    expr.walk_child_exprs(|expr_id| collect_callees(db, callees, expr_id, body, infer))
+}
 
 /// Constructs a PassManager to optimize functions for the given optimization level.
 pub(crate) fn create_pass_manager(
@@ -22,18 +168,36 @@ pub(crate) fn create_pass_manager(
     function_pass_manager
 }
 
-/// Generates a `FunctionValue` for a `hir::Function`. This function does not generate a body for
-/// the `hir::Function`. That task is left to the `gen_body` function. The reason this is split
-/// between two functions is that first all signatures are generated and then all bodies. This
-/// allows bodies to reference `FunctionValue` wherever they are declared in the file.
+/// Adds a function that is generated from Mun code to the `module`. A trap unwinds the stack
+/// through the frames of Mun functions, which requires every function to have an unwind table.
+pub(crate) fn add_function(
+    module: &Module,
+    name: &str,
+    ty: FunctionType,
+    linkage: Option<Linkage>,
+) -> FunctionValue {
+    let function = module.add_function(name, ty, linkage);
+    function.add_attribute(
+        AttributeLoc::Function,
+        module
+            .get_context()
+            .create_enum_attribute(Attribute::get_named_enum_kind_id("uwtable"), 0),
+    );
+    function
+}
+
+/// Generates a `FunctionValue` for a `FunctionInstance`. This function does not generate a body
+/// for the `FunctionInstance`. That task is left to the `gen_body` function. The reason this is
+/// split between two functions is that first all signatures are generated and then all bodies.
+/// This allows bodies to reference `FunctionValue` wherever they are declared in the file.
 pub(crate) fn gen_signature(
     db: &impl IrDatabase,
-    f: hir::Function,
+    f: &FunctionInstance,
     module: &Module,
     params: CodeGenParams,
 ) -> FunctionValue {
     let name = {
-        let name = f.name(db).to_string();
+        let name = f.name(db);
         if params.make_marshallable {
             format!("{}_wrapper", name)
         } else {
@@ -42,23 +206,28 @@ pub(crate) fn gen_signature(
     };
 
     if let AnyTypeEnum::FunctionType(ty) = db.type_ir(f.ty(db), params) {
-        module.add_function(&name, ty, None)
+        add_function(module, &name, ty, None)
     } else {
         panic!("not a function type")
     }
 }
 
-/// Generates the body of a `hir::Function` for an associated `FunctionValue`.
+/// Generates the body of a `FunctionInstance` for an associated `FunctionValue`. If `debug_info`
+/// is specified, the function is described in it.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn gen_body<'a, 'b, D: IrDatabase>(
     db: &'a D,
-    function: (hir::Function, FunctionValue),
-    llvm_functions: &'a HashMap<hir::Function, FunctionValue>,
+    module: &'a Module,
+    function: (&FunctionInstance, FunctionValue),
+    llvm_functions: &'a HashMap<FunctionInstance, FunctionValue>,
     dispatch_table: &'b DispatchTable,
     type_table: &'b TypeTable,
     external_globals: ExternalGlobals,
+    debug_info: Option<&'b DebugInfo>,
 ) {
     let mut code_gen = BodyIrGenerator::new(
         db,
+        module,
         function,
         llvm_functions,
         dispatch_table,
@@ -67,23 +236,26 @@ pub(crate) fn gen_body<'a, 'b, D: IrDatabase>(
             make_marshallable: false,
         },
         external_globals,
+        debug_info,
     );
 
     code_gen.gen_fn_body();
 }
 
-/// Generates the body of a wrapper around `hir::Function` for its associated
+/// Generates the body of a wrapper around `FunctionInstance` for its associated
 /// `FunctionValue`
 pub(crate) fn gen_wrapper_body<'a, 'b, D: IrDatabase>(
     db: &'a D,
-    function: (hir::Function, FunctionValue),
-    llvm_functions: &'a HashMap<hir::Function, FunctionValue>,
+    module: &'a Module,
+    function: (&FunctionInstance, FunctionValue),
+    llvm_functions: &'a HashMap<FunctionInstance, FunctionValue>,
     dispatch_table: &'b DispatchTable,
     type_table: &'b TypeTable,
     external_globals: ExternalGlobals,
 ) {
     let mut code_gen = BodyIrGenerator::new(
         db,
+        module,
         function,
         llvm_functions,
         dispatch_table,
@@ -92,6 +264,7 @@ pub(crate) fn gen_wrapper_body<'a, 'b, D: IrDatabase>(
             make_marshallable: true,
         },
         external_globals,
+        None,
     );
 
     code_gen.gen_fn_wrapper();
@@ -0,0 +1,59 @@
+use crate::ir::try_convert_any_to_basic;
+use crate::{CodeGenParams, IrDatabase};
+use hir::ConstValue;
+use inkwell::{
+    module::Module,
+    types::BasicTypeEnum,
+    values::{BasicValueEnum, GlobalValue},
+};
+
+/// Generates a constant IR value of type `ty` for a value that was evaluated at compile time.
+pub(crate) fn gen_const_value(
+    db: &impl IrDatabase,
+    value: ConstValue,
+    ty: &hir::Ty,
+) -> BasicValueEnum {
+    let ir_type = try_convert_any_to_basic(db.type_ir(
+        ty.clone(),
+        CodeGenParams {
+            make_marshallable: false,
+        },
+    ))
+    .expect("could not convert the type of a constant value");
+
+    match (value, ir_type) {
+        (ConstValue::Bool(value), BasicTypeEnum::IntType(ty)) => {
+            ty.const_int(value as u64, false).into()
+        }
+        (ConstValue::Int(value), BasicTypeEnum::IntType(ty)) => {
+            if ty.get_bit_width() == 128 {
+                ty.const_int_arbitrary_precision(&unsafe {
+                    std::mem::transmute::<i128, [u64; 2]>(value)
+                })
+                .into()
+            } else {
+                // Only the lower bits are used, which is the two's complement of negative values
+                ty.const_int(value as u64, false).into()
+            }
+        }
+        (ConstValue::Float(value), BasicTypeEnum::FloatType(ty)) => ty.const_float(value).into(),
+        _ => unreachable!("a constant value must have a boolean, integer or floating-point type"),
+    }
+}
+
+/// Declares the global variable `global` in the specified module. The global variable is defined
+/// in the IR of its package, all other modules only declare it.
+pub(crate) fn gen_global_decl(
+    db: &impl IrDatabase,
+    module: &Module,
+    global: hir::Static,
+) -> GlobalValue {
+    let ir_type = try_convert_any_to_basic(db.type_ir(
+        global.ty(db),
+        CodeGenParams {
+            make_marshallable: false,
+        },
+    ))
+    .expect("could not convert the type of a global variable");
+    module.add_global(ir_type, None, &global.full_name(db))
+}
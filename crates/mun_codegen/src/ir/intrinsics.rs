@@ -1,7 +1,7 @@
 use crate::intrinsics::{self, Intrinsic};
 use crate::ir::dispatch_table::FunctionPrototype;
 use crate::IrDatabase;
-use hir::{Body, Expr, ExprId, InferenceResult};
+use hir::{ArithOp, BinaryOp, Body, CmpOp, Expr, ExprId, InferenceResult, Literal};
 use inkwell::types::FunctionType;
 use std::collections::BTreeMap;
 use std::sync::Arc;
@@ -22,6 +22,17 @@ fn collect_intrinsic<D: IrDatabase>(
         .or_insert_with(|| intrinsic.ir_type(context.as_ref(), target.as_ref()));
 }
 
+/// Returns whether storing a value of type `ty` in the memory of a garbage collected object
+/// requires a write barrier, i.e. whether the value might contain a reference to another object.
+pub(crate) fn needs_write_barrier(ty: &hir::Ty) -> bool {
+    match ty.as_simple() {
+        Some(hir::TypeCtor::Bool) | Some(hir::TypeCtor::Float(_)) | Some(hir::TypeCtor::Int(_)) => {
+            false
+        }
+        _ => true,
+    }
+}
+
 fn collect_expr<D: IrDatabase>(
     db: &D,
     entries: &mut IntrinsicsMap,
@@ -35,13 +46,17 @@ fn collect_expr<D: IrDatabase>(
     // If this expression is a call, store it in the dispatch table
     if let Expr::Call { callee, .. } = expr {
         match infer[*callee].as_callable_def() {
-            Some(hir::CallableDef::Struct(_)) => {
+            Some(hir::CallableDef::Struct(_)) | Some(hir::CallableDef::EnumVariant(_)) => {
                 collect_intrinsic(db, entries, &intrinsics::new);
                 // self.collect_intrinsic(module, entries, &intrinsics::drop);
                 *needs_alloc = true;
             }
+            Some(hir::CallableDef::BuiltinFunction(hir::BuiltinFunction::Panic)) => {
+                collect_intrinsic(db, entries, &intrinsics::trap);
+            }
             Some(hir::CallableDef::Function(_)) => (),
-            None => panic!("expected a callable expression"),
+            // Function pointers and closures are called through their object
+            None => (),
         }
     }
 
@@ -51,6 +66,88 @@ fn collect_expr<D: IrDatabase>(
         *needs_alloc = true;
     }
 
+    if let Expr::Array(_) = expr {
+        if let hir::ty_app!(hir::TypeCtor::DynArray) = infer[expr_id] {
+            collect_intrinsic(db, entries, &intrinsics::new_array);
+            *needs_alloc = true;
+        }
+    }
+
+    if let Expr::Index { .. } = expr {
+        collect_intrinsic(db, entries, &intrinsics::index_out_of_bounds);
+    }
+
+    if let Expr::MethodCall { args, .. } = expr {
+        match infer.method_resolution(expr_id) {
+            Some(hir::MethodResolution::Builtin(hir::BuiltinMethod::ArrayPush)) => {
+                collect_intrinsic(db, entries, &intrinsics::array_reserve);
+                if needs_write_barrier(&infer[args[0]]) {
+                    collect_intrinsic(db, entries, &intrinsics::write_barrier);
+                }
+                *needs_alloc = true;
+            }
+            Some(hir::MethodResolution::Builtin(hir::BuiltinMethod::ArrayPop)) => {
+                collect_intrinsic(db, entries, &intrinsics::index_out_of_bounds);
+            }
+            Some(hir::MethodResolution::Builtin(hir::BuiltinMethod::ArrayLen))
+            | Some(hir::MethodResolution::Builtin(hir::BuiltinMethod::StringLen))
+            | Some(hir::MethodResolution::Function(_))
+            | None => (),
+        }
+    }
+
+    if let Expr::Literal(Literal::String(_)) = expr {
+        collect_intrinsic(db, entries, &intrinsics::new_string);
+        *needs_alloc = true;
+    }
+
+    if let Expr::BinaryOp {
+        lhs, op: Some(op), ..
+    } = expr
+    {
+        // Integer divisions trap on a division by zero or an overflow
+        if let hir::ty_app!(hir::TypeCtor::Int(_)) = infer[*lhs] {
+            if let BinaryOp::ArithOp(ArithOp::Divide)
+            | BinaryOp::ArithOp(ArithOp::Remainder)
+            | BinaryOp::Assignment {
+                op: Some(ArithOp::Divide),
+            }
+            | BinaryOp::Assignment {
+                op: Some(ArithOp::Remainder),
+            } = op
+            {
+                collect_intrinsic(db, entries, &intrinsics::trap);
+            }
+        }
+
+        // Storing a reference in the memory of an object, e.g. `a.b = c`, has to be reported to
+        // the garbage collector
+        if let BinaryOp::Assignment { .. } = op {
+            if let Expr::Field { .. } | Expr::Index { .. } = body[*lhs] {
+                if needs_write_barrier(&infer[*lhs]) {
+                    collect_intrinsic(db, entries, &intrinsics::write_barrier);
+                    *needs_alloc = true;
+                }
+            }
+        }
+
+        if let hir::ty_app!(hir::TypeCtor::String) = infer[*lhs] {
+            match op {
+                BinaryOp::ArithOp(ArithOp::Add)
+                | BinaryOp::Assignment {
+                    op: Some(ArithOp::Add),
+                } => {
+                    collect_intrinsic(db, entries, &intrinsics::string_concat);
+                    *needs_alloc = true;
+                }
+                BinaryOp::CmpOp(CmpOp::Eq { .. }) => {
+                    collect_intrinsic(db, entries, &intrinsics::string_eq);
+                }
+                _ => (),
+            }
+        }
+    }
+
     if let Expr::Path(path) = expr {
         let resolver = hir::resolver_for_expr(body.clone(), db, expr_id);
         let resolution = resolver
@@ -58,7 +155,9 @@ fn collect_expr<D: IrDatabase>(
             .take_values()
             .expect("unknown path");
 
-        if let hir::Resolution::Def(hir::ModuleDef::Struct(_)) = resolution {
+        if let hir::Resolution::Def(hir::ModuleDef::Struct(_))
+        | hir::Resolution::Def(hir::ModuleDef::EnumVariant(_)) = resolution
+        {
             collect_intrinsic(db, entries, &intrinsics::new);
             // self.collect_intrinsic( module, entries, &intrinsics::drop);
             *needs_alloc = true;
@@ -1,14 +1,17 @@
-use super::try_convert_any_to_basic;
+use super::{try_convert_any_to_basic, IsIrType};
 use crate::{
     type_info::{TypeInfo, TypeSize},
     CodeGenParams, IrDatabase,
 };
 use hir::{
-    ApplicationTy, CallableDef, FloatBitness, FloatTy, IntBitness, IntTy, ResolveBitness, Ty,
-    TypeCtor,
+    ApplicationTy, CallableDef, FloatBitness, FloatTy, FnSig, IntBitness, IntTy, ResolveBitness,
+    Substs, Ty, TypeCtor,
 };
 use inkwell::{
-    types::{AnyTypeEnum, BasicType, BasicTypeEnum, FloatType, IntType, StructType},
+    types::{
+        AnyTypeEnum, BasicType, BasicTypeEnum, FloatType, FunctionType, IntType, PointerType,
+        StructType,
+    },
     AddressSpace,
 };
 
@@ -18,13 +21,14 @@ pub(crate) fn ir_query(db: &impl IrDatabase, ty: Ty, params: CodeGenParams) -> A
     let context = db.context();
     match ty {
         Ty::Empty => AnyTypeEnum::StructType(context.struct_type(&[], false)),
-        Ty::Apply(ApplicationTy { ctor, .. }) => match ctor {
+        Ty::Apply(ApplicationTy { ctor, parameters }) => match ctor {
             TypeCtor::Float(fty) => float_ty_query(db, fty).into(),
             TypeCtor::Int(ity) => int_ty_query(db, ity).into(),
             TypeCtor::Bool => AnyTypeEnum::IntType(context.bool_type()),
+            TypeCtor::String => string_ty(db).ptr_type(AddressSpace::Generic).ptr_type(AddressSpace::Const).into(),
 
             TypeCtor::FnDef(def @ CallableDef::Function(_)) => {
-                let ty = db.callable_sig(def);
+                let ty = db.callable_sig(def).subst(&parameters);
                 let param_tys: Vec<BasicTypeEnum> = ty
                     .params()
                     .iter()
@@ -42,8 +46,12 @@ pub(crate) fn ir_query(db: &impl IrDatabase, ty: Ty, params: CodeGenParams) -> A
 
                 AnyTypeEnum::FunctionType(fn_type)
             }
+            TypeCtor::FnPtr { .. } => {
+                let sig = FnSig::from_fn_ptr_substs(&parameters);
+                fn_ptr_ty(db, &sig).ptr_type(AddressSpace::Generic).ptr_type(AddressSpace::Const).into()
+            }
             TypeCtor::Struct(s) => {
-                let struct_ty = db.struct_ty(s);
+                let struct_ty = db.struct_ty(s, parameters);
                 match s.data(db).memory_kind {
                     hir::StructMemoryKind::GC => struct_ty.ptr_type(AddressSpace::Generic).ptr_type(AddressSpace::Const).into(),
                     hir::StructMemoryKind::Value if params.make_marshallable =>
@@ -51,12 +59,177 @@ pub(crate) fn ir_query(db: &impl IrDatabase, ty: Ty, params: CodeGenParams) -> A
                     hir::StructMemoryKind::Value => struct_ty.into(),
                 }
             }
+            TypeCtor::Enum(e) => {
+                let enum_ty = db.enum_ty(e);
+                match e.data(db).memory_kind {
+                    hir::StructMemoryKind::GC => enum_ty.ptr_type(AddressSpace::Generic).ptr_type(AddressSpace::Const).into(),
+                    hir::StructMemoryKind::Value if params.make_marshallable =>
+                            enum_ty.ptr_type(AddressSpace::Generic).ptr_type(AddressSpace::Const).into(),
+                    hir::StructMemoryKind::Value => enum_ty.into(),
+                }
+            }
+            TypeCtor::Array(length) => {
+                let array_ty = array_element_ty(db, parameters.as_single()).array_type(length);
+                if params.make_marshallable {
+                    array_ty.ptr_type(AddressSpace::Generic).ptr_type(AddressSpace::Const).into()
+                } else {
+                    array_ty.into()
+                }
+            }
+            TypeCtor::DynArray => {
+                let element_ty = array_element_ty(db, parameters.as_single());
+                dyn_array_ty(db, element_ty).ptr_type(AddressSpace::Generic).ptr_type(AddressSpace::Const).into()
+            }
+            // An optional reference is stored as the reference itself, which is null for `nil`
+            TypeCtor::Optional => db.type_ir(parameters.as_single().expect("an optional type must have an inner type").clone(), params),
+            TypeCtor::Tuple => {
+                let tuple_ty = tuple_ty(db, &parameters);
+                if params.make_marshallable {
+                    tuple_ty.ptr_type(AddressSpace::Generic).ptr_type(AddressSpace::Const).into()
+                } else {
+                    tuple_ty.into()
+                }
+            }
             _ => unreachable!(),
         },
         _ => unreachable!("unknown type can not be converted"),
     }
 }
 
+/// Returns the LLVM IR type of the elements of an array type
+fn array_element_ty(db: &impl IrDatabase, element_ty: Option<&Ty>) -> BasicTypeEnum {
+    let element_ty = element_ty
+        .expect("an array type must have an element type")
+        .clone();
+    try_convert_any_to_basic(db.type_ir(
+        element_ty,
+        CodeGenParams {
+            make_marshallable: false,
+        },
+    ))
+    .expect("could not convert array element type")
+}
+
+/// Returns the LLVM IR type of a tuple with the specified element types. A tuple is stored as an
+/// anonymous value struct.
+pub(crate) fn tuple_ty(db: &impl IrDatabase, element_tys: &Substs) -> StructType {
+    let element_tys: Vec<BasicTypeEnum> = element_tys
+        .iter()
+        .map(|ty| {
+            try_convert_any_to_basic(db.type_ir(
+                ty.clone(),
+                CodeGenParams {
+                    make_marshallable: false,
+                },
+            ))
+            .expect("could not convert tuple element type")
+        })
+        .collect();
+
+    db.context().struct_type(&element_tys, false)
+}
+
+/// Returns the LLVM IR type of the heap memory of a dynamic array with the specified element type.
+/// This matches the layout of a `mun_memory::gc::ArrayHeader` followed by the elements.
+pub(crate) fn dyn_array_ty(db: &impl IrDatabase, element_ty: BasicTypeEnum) -> StructType {
+    let context = db.context();
+    let target = db.target_data();
+    let usize_ty = <usize as IsIrType>::ir_type(&context, &target);
+    context.struct_type(
+        &[
+            usize_ty.into(),                 // length
+            usize_ty.into(),                 // capacity
+            element_ty.array_type(0).into(), // elements
+        ],
+        false,
+    )
+}
+
+/// Returns the LLVM IR type of the heap memory of a string. A string is stored as a dynamic array
+/// of UTF-8 encoded bytes.
+pub(crate) fn string_ty(db: &impl IrDatabase) -> StructType {
+    dyn_array_ty(db, db.context().i8_type().into())
+}
+
+/// Returns the type agnostic LLVM IR type of a handle to a garbage collected object, which is
+/// stored in a `*const *mut std::ffi::c_void`.
+pub(crate) fn object_ptr_ty(db: &impl IrDatabase) -> PointerType {
+    db.context()
+        .i8_type()
+        .ptr_type(AddressSpace::Generic)
+        .ptr_type(AddressSpace::Const)
+}
+
+/// Returns the LLVM IR type of a function that is called through a function pointer with the
+/// specified signature. The function receives the handle of the function pointer's object as its
+/// first argument, from which a closure loads the values that it captured.
+pub(crate) fn closure_fn_ty(db: &impl IrDatabase, sig: &FnSig) -> FunctionType {
+    let params = CodeGenParams {
+        make_marshallable: false,
+    };
+
+    let mut param_tys: Vec<BasicTypeEnum> = vec![object_ptr_ty(db).into()];
+    param_tys.extend(sig.params().iter().map(|ty| {
+        try_convert_any_to_basic(db.type_ir(ty.clone(), params.clone()))
+            .expect("could not convert parameter type")
+    }));
+
+    match sig.ret() {
+        Ty::Empty => db.context().void_type().fn_type(&param_tys, false),
+        ty => try_convert_any_to_basic(db.type_ir(ty.clone(), params))
+            .expect("could not convert return value")
+            .fn_type(&param_tys, false),
+    }
+}
+
+/// Returns the LLVM IR type of the heap memory of a closure: a pointer to its function, of type
+/// `fn_ptr_ty`, followed by the values that it captured.
+pub(crate) fn closure_ty(
+    db: &impl IrDatabase,
+    fn_ptr_ty: BasicTypeEnum,
+    captures: &[Ty],
+) -> StructType {
+    let mut field_tys = vec![fn_ptr_ty];
+    field_tys.extend(captures.iter().map(|ty| {
+        try_convert_any_to_basic(db.type_ir(
+            ty.clone(),
+            CodeGenParams {
+                make_marshallable: false,
+            },
+        ))
+        .expect("could not convert captured value type")
+    }));
+
+    db.context().struct_type(&field_tys, false)
+}
+
+/// Returns the LLVM IR type of the heap memory of a closure with an untyped function pointer. Its
+/// layout is identical to that of the closure's typed memory.
+pub(crate) fn closure_layout_ty(db: &impl IrDatabase, captures: &[Ty]) -> StructType {
+    let fn_ptr_ty = db.context().i8_type().ptr_type(AddressSpace::Generic);
+    closure_ty(db, fn_ptr_ty.into(), captures)
+}
+
+/// Returns the LLVM IR type of the heap memory of a function pointer with the specified signature.
+/// Only the pointer to the function is accessible through a function pointer, the values that a
+/// closure captured are not.
+pub(crate) fn fn_ptr_ty(db: &impl IrDatabase, sig: &FnSig) -> StructType {
+    let fn_ptr_ty = closure_fn_ty(db, sig).ptr_type(AddressSpace::Generic);
+    closure_ty(db, fn_ptr_ty.into(), &[])
+}
+
+/// Constructs the `TypeInfo` of the heap memory of a closure that is called through the function
+/// pointer type `fn_ptr_ty` and that captures values of the specified types.
+pub(crate) fn closure_type_info(
+    db: &impl IrDatabase,
+    fn_ptr_ty: &Ty,
+    captures: Vec<Ty>,
+) -> TypeInfo {
+    let ir_ty = closure_layout_ty(db, &captures);
+    let type_size = TypeSize::from_ir_type(&ir_ty, db.target_data().as_ref());
+    TypeInfo::new_closure(db, fn_ptr_ty, captures, type_size)
+}
+
 /// Returns the LLVM IR type of the specified float type
 fn float_ty_query(db: &impl IrDatabase, fty: FloatTy) -> FloatType {
     let context = db.context();
@@ -79,13 +252,22 @@ fn int_ty_query(db: &impl IrDatabase, ity: IntTy) -> IntType {
     }
 }
 
-/// Returns the LLVM IR type of the specified struct
-pub fn struct_ty_query(db: &impl IrDatabase, s: hir::Struct) -> StructType {
-    let name = s.name(db).to_string();
+/// Returns the LLVM IR type of the instantiation of the specified struct with the type arguments
+/// `substs`. A struct without type parameters has a single instantiation with empty `substs`.
+pub fn struct_ty_query(db: &impl IrDatabase, s: hir::Struct, substs: Substs) -> StructType {
+    let name = struct_name(db, s, &substs);
     for field in s.fields(db).iter() {
+        let field_ty = field.ty(db).subst(&substs);
+
+        // An optional field only refers to its struct by name, which allows a struct to refer to
+        // itself (e.g. `struct(gc) Node { next: Node? }`) without a cyclic query.
+        if field_ty.as_optional().is_some() {
+            continue;
+        }
+
         // Ensure that salsa's cached value incorporates the struct fields
         let _field_type_ir = db.type_ir(
-            field.ty(db),
+            field_ty,
             CodeGenParams {
                 make_marshallable: false,
             },
@@ -95,6 +277,109 @@ pub fn struct_ty_query(db: &impl IrDatabase, s: hir::Struct) -> StructType {
     db.context().opaque_struct_type(&name)
 }
 
+/// Returns the name of the instantiation of the struct `s` with the type arguments `substs`, e.g.
+/// `Pair<core::f32, core::i64>`.
+pub(crate) fn struct_name(db: &impl IrDatabase, s: hir::Struct, substs: &Substs) -> String {
+    let name = s.full_name(db);
+    if substs.is_empty() {
+        name
+    } else {
+        let type_args: Vec<String> = substs.iter().map(|ty| type_name(db, ty)).collect();
+        format!("{}<{}>", name, type_args.join(", "))
+    }
+}
+
+/// Returns the name of a concrete type as it is used in the names of instantiations of generic
+/// structs and functions, e.g. `core::f32` or `[Pair<core::f32, core::i64>]`.
+pub(crate) fn type_name(db: &impl IrDatabase, ty: &Ty) -> String {
+    match ty {
+        Ty::Apply(ApplicationTy { ctor, parameters }) => match ctor {
+            TypeCtor::Struct(s) => struct_name(db, *s, parameters),
+            TypeCtor::Enum(e) => e.full_name(db),
+            TypeCtor::Array(length) => format!("[{}; {}]", element_name(db, parameters), length),
+            TypeCtor::DynArray => format!("[{}]", element_name(db, parameters)),
+            TypeCtor::Optional => format!(
+                "{}?",
+                type_name(
+                    db,
+                    parameters
+                        .as_single()
+                        .expect("an optional type must have an inner type")
+                )
+            ),
+            TypeCtor::Tuple => {
+                let element_names: Vec<String> =
+                    parameters.iter().map(|ty| type_name(db, ty)).collect();
+                hir::tuple_name(&element_names)
+            }
+            TypeCtor::FnPtr { .. } => {
+                let sig = FnSig::from_fn_ptr_substs(parameters);
+                let param_names: Vec<String> =
+                    sig.params().iter().map(|ty| type_name(db, ty)).collect();
+                let ret_name = if sig.ret().is_empty() {
+                    None
+                } else {
+                    Some(type_name(db, sig.ret()))
+                };
+                hir::fn_ptr_name(&param_names, ret_name.as_deref())
+            }
+            _ => ty
+                .guid_string(db)
+                .expect("type should be convertible to a string"),
+        },
+        _ => unreachable!("{:?} is not a concrete type", ty),
+    }
+}
+
+/// Returns the name of the element type of an array type with the type arguments `substs`.
+fn element_name(db: &impl IrDatabase, substs: &Substs) -> String {
+    type_name(
+        db,
+        substs
+            .as_single()
+            .expect("an array type must have an element type"),
+    )
+}
+
+/// Returns the LLVM IR type of the specified enum. The body of the type is a tag followed by a
+/// payload that is large enough to hold any of the enum's variants.
+pub fn enum_ty_query(db: &impl IrDatabase, e: hir::Enum) -> StructType {
+    let name = e.full_name(db);
+    for variant in e.variants(db).iter() {
+        for field in variant.fields(db).iter() {
+            // Ensure that salsa's cached value incorporates the variant fields
+            let _field_type_ir = db.type_ir(
+                field.ty(db),
+                CodeGenParams {
+                    make_marshallable: false,
+                },
+            );
+        }
+    }
+
+    db.context().opaque_struct_type(&name)
+}
+
+/// Returns the LLVM IR type of the payload of the specified enum variant. The payload of a variant
+/// is stored in the memory of the enum's payload.
+pub(crate) fn enum_variant_ty(db: &impl IrDatabase, variant: hir::EnumVariant) -> StructType {
+    let field_types: Vec<BasicTypeEnum> = variant
+        .fields(db)
+        .iter()
+        .map(|field| {
+            try_convert_any_to_basic(db.type_ir(
+                field.ty(db),
+                CodeGenParams {
+                    make_marshallable: false,
+                },
+            ))
+            .expect("could not convert field type")
+        })
+        .collect();
+
+    db.context().struct_type(&field_types, false)
+}
+
 /// Constructs the `TypeInfo` for the specified HIR type
 pub fn type_info_query(db: &impl IrDatabase, ty: Ty) -> TypeInfo {
     let target = db.target_data();
@@ -121,10 +406,53 @@ pub fn type_info_query(db: &impl IrDatabase, ty: Ty) -> TypeInfo {
                 let type_size = TypeSize::from_ir_type(&ir_ty, target.as_ref());
                 TypeInfo::new_fundamental("core::bool", type_size)
             }
+            TypeCtor::String => {
+                let ir_ty = db.type_ir(
+                    Ty::simple(TypeCtor::String),
+                    CodeGenParams {
+                        make_marshallable: false,
+                    },
+                );
+                let type_size = TypeSize::from_ir_type(&ir_ty, target.as_ref());
+                TypeInfo::new_fundamental("core::string", type_size)
+            }
             TypeCtor::Struct(s) => {
-                let ir_ty = db.struct_ty(s);
+                let ir_ty = db.struct_ty(s, ctor.parameters.clone());
+                let type_size = TypeSize::from_ir_type(&ir_ty, target.as_ref());
+                TypeInfo::new_struct(db, s, ctor.parameters, type_size)
+            }
+            TypeCtor::Enum(e) => {
+                let ir_ty = db.enum_ty(e);
+                let type_size = TypeSize::from_ir_type(&ir_ty, target.as_ref());
+                TypeInfo::new_enum(db, e, type_size)
+            }
+            TypeCtor::Array(_) | TypeCtor::DynArray => {
+                let ty = Ty::Apply(ctor);
+                let ir_ty = db.type_ir(
+                    ty.clone(),
+                    CodeGenParams {
+                        make_marshallable: false,
+                    },
+                );
+                let type_size = TypeSize::from_ir_type(&ir_ty, target.as_ref());
+                TypeInfo::new_array(db, &ty, type_size)
+            }
+            // An optional reference has the same type information as the reference itself
+            TypeCtor::Optional => db.type_info(
+                ctor.parameters
+                    .as_single()
+                    .expect("an optional type must have an inner type")
+                    .clone(),
+            ),
+            TypeCtor::Tuple => {
+                let ir_ty = tuple_ty(db, &ctor.parameters);
+                let type_size = TypeSize::from_ir_type(&ir_ty, target.as_ref());
+                TypeInfo::new_tuple(db, ctor.parameters.to_vec(), type_size)
+            }
+            TypeCtor::FnPtr { .. } => {
+                let ir_ty = fn_ptr_ty(db, &FnSig::from_fn_ptr_substs(&ctor.parameters));
                 let type_size = TypeSize::from_ir_type(&ir_ty, target.as_ref());
-                TypeInfo::new_struct(db, s, type_size)
+                TypeInfo::new_fn_ptr(db, &Ty::Apply(ctor), type_size)
             }
             _ => unreachable!("{:?} unhandled", ctor),
         },
@@ -1,13 +1,16 @@
 use crate::code_gen::{
-    gen_global, gen_string_array, gen_struct_ptr_array, gen_u16_array, intern_string,
+    gen_global, gen_optional_string_array, gen_string_array, gen_struct_ptr_array, gen_u16_array,
+    intern_string,
 };
 use crate::ir::{
     abi_types::AbiTypes,
     dispatch_table::{DispatchTable, FunctionPrototype},
+    function::FunctionInstance,
+    ty::{closure_layout_ty, closure_type_info, enum_variant_ty, struct_name, tuple_ty},
 };
 use crate::type_info::{TypeGroup, TypeInfo};
 use crate::IrDatabase;
-use hir::{Body, ExprId, InferenceResult};
+use hir::{Body, Expr, ExprId, InferenceResult};
 use inkwell::{
     module::Module,
     targets::TargetData,
@@ -16,7 +19,7 @@ use inkwell::{
     AddressSpace,
 };
 use std::collections::{BTreeSet, HashMap};
-use std::{convert::TryInto, mem, sync::Arc};
+use std::{convert::TryInto, iter, mem, sync::Arc};
 
 /// A type table in IR is a list of pointers to unique type information that are used to generate
 /// function and struct information.
@@ -122,10 +125,18 @@ impl<'a, D: IrDatabase> TypeTableBuilder<'a, D> {
 
     /// Collects unique `TypeInfo` from the given `Ty`.
     fn collect_type(&mut self, type_info: TypeInfo) {
-        if let TypeGroup::StructTypes(hir_struct) = type_info.group {
-            self.collect_struct(hir_struct);
-        } else {
-            self.entries.insert(type_info);
+        match type_info.group {
+            TypeGroup::StructTypes(hir_struct, substs) => self.collect_struct(hir_struct, substs),
+            TypeGroup::EnumTypes(hir_enum) => self.collect_enum(hir_enum),
+            TypeGroup::ClosureTypes(ref captures) => {
+                for capture in captures.clone() {
+                    self.collect_type(self.db.type_info(capture));
+                }
+                self.entries.insert(type_info);
+            }
+            _ => {
+                self.entries.insert(type_info);
+            }
         }
     }
 
@@ -133,17 +144,69 @@ impl<'a, D: IrDatabase> TypeTableBuilder<'a, D> {
     fn collect_expr(&mut self, expr_id: ExprId, body: &Arc<Body>, infer: &InferenceResult) {
         let expr = &body[expr_id];
 
+        // Dynamic arrays are allocated at runtime using their `TypeInfo`
+        if let Expr::Array(_) = expr {
+            let ty = &infer[expr_id];
+            if let hir::ty_app!(hir::TypeCtor::DynArray) = ty {
+                self.collect_type(self.db.type_info(ty.clone()));
+            }
+        }
+
+        // Instantiations of generic structs are not declared in the package, so they are collected
+        // where they are used
+        if let hir::ty_app!(hir::TypeCtor::Struct(_), substs) = &infer[expr_id] {
+            if !substs.is_empty() {
+                self.collect_type(self.db.type_info(infer[expr_id].clone()));
+            }
+        }
+
+        // Garbage collected enums are allocated at runtime using their `TypeInfo`
+        if let Some(hir_enum) = infer[expr_id].as_enum() {
+            if hir_enum.data(self.db).memory_kind == hir::StructMemoryKind::GC {
+                self.collect_enum(hir_enum);
+            }
+        }
+
+        // Functions that are used as values are allocated at runtime as function pointers
+        if let Expr::Path(_) = expr {
+            let ty = &infer[expr_id];
+            if let hir::ty_app!(hir::TypeCtor::FnDef(hir::CallableDef::Function(_))) = ty {
+                let sig = ty.callable_sig(self.db).expect("expected a function");
+                self.collect_type(self.db.type_info(hir::Ty::fn_ptr(sig)));
+            }
+        }
+
+        // Closures are allocated at runtime together with the values that they capture
+        if let Expr::Lambda { .. } = expr {
+            let captures = infer
+                .closure_captures(expr_id)
+                .iter()
+                .map(|pat| infer[*pat].clone())
+                .collect();
+            self.collect_type(closure_type_info(self.db, &infer[expr_id], captures));
+        }
+
         // TODO: Collect used external `TypeInfo` for the type dispatch table
 
-        // Recurse further
-        expr.walk_child_exprs(|expr_id| self.collect_expr(expr_id, body, infer))
+        // Recurse further. A function that is called is not used as a value, so its callee is
+        // skipped.
+        match expr {
+            Expr::Call { callee, args } if infer[*callee].as_callable_def().is_some() => {
+                for arg in args.iter() {
+                    self.collect_expr(*arg, body, infer)
+                }
+            }
+            _ => expr.walk_child_exprs(|expr_id| self.collect_expr(expr_id, body, infer)),
+        }
     }
 
     /// Collects unique `TypeInfo` from the specified function signature and body.
-    pub fn collect_fn(&mut self, hir_fn: hir::Function) {
+    pub fn collect_fn(&mut self, instance: &FunctionInstance) {
         // Collect type info for exposed function
-        if !hir_fn.data(self.db).visibility().is_private() || self.dispatch_table.contains(hir_fn) {
-            let fn_sig = hir_fn.ty(self.db).callable_sig(self.db).unwrap();
+        if !instance.function.visibility(self.db).is_private()
+            || self.dispatch_table.contains(instance)
+        {
+            let fn_sig = instance.ty(self.db).callable_sig(self.db).unwrap();
 
             // Collect argument types
             for ty in fn_sig.params().iter() {
@@ -158,19 +221,44 @@ impl<'a, D: IrDatabase> TypeTableBuilder<'a, D> {
         }
 
         // Collect used types from body
-        let body = hir_fn.body(self.db);
-        let infer = hir_fn.infer(self.db);
+        let body = instance.function.body(self.db);
+        let infer = instance.infer(self.db);
         self.collect_expr(body.body_expr(), &body, &infer);
     }
 
-    /// Collects unique `TypeInfo` from the specified struct type.
-    pub fn collect_struct(&mut self, hir_struct: hir::Struct) {
-        let type_info = self.db.type_info(hir_struct.ty(self.db));
-        self.entries.insert(type_info);
+    /// Collects unique `TypeInfo` from the type of the specified global variable.
+    pub fn collect_global(&mut self, global: hir::Static) {
+        self.collect_type(self.db.type_info(global.ty(self.db)));
+    }
+
+    /// Collects unique `TypeInfo` from the instantiation of the specified struct type with the
+    /// type arguments `substs`.
+    pub fn collect_struct(&mut self, hir_struct: hir::Struct, substs: hir::Substs) {
+        let type_info = self.db.type_info(hir::Ty::Apply(hir::ApplicationTy {
+            ctor: hir::TypeCtor::Struct(hir_struct),
+            parameters: substs.clone(),
+        }));
+        if !self.entries.insert(type_info) {
+            return;
+        }
 
         let fields = hir_struct.fields(self.db);
         for field in fields.into_iter() {
-            self.collect_type(self.db.type_info(field.ty(self.db)));
+            self.collect_type(self.db.type_info(field.ty(self.db).subst(&substs)));
+        }
+    }
+
+    /// Collects unique `TypeInfo` from the specified enum type.
+    pub fn collect_enum(&mut self, hir_enum: hir::Enum) {
+        let type_info = self.db.type_info(hir_enum.ty(self.db));
+        if !self.entries.insert(type_info) {
+            return;
+        }
+
+        for variant in hir_enum.variants(self.db).into_iter() {
+            for field in variant.fields(self.db).into_iter() {
+                self.collect_type(self.db.type_info(field.ty(self.db)));
+            }
         }
     }
 
@@ -208,10 +296,30 @@ impl<'a, D: IrDatabase> TypeTableBuilder<'a, D> {
         ]);
         let type_info_ir = match type_info.group {
             TypeGroup::FundamentalTypes => type_info_ir,
-            TypeGroup::StructTypes(s) => {
-                let struct_info_ir = self.gen_struct_info(type_info_to_ir, s);
+            TypeGroup::StructTypes(s, ref substs) => {
+                let struct_info_ir = self.gen_struct_info(type_info_to_ir, s, substs);
                 context.const_struct(&[type_info_ir.into(), struct_info_ir.into()], false)
             }
+            TypeGroup::ArrayTypes {
+                ref element,
+                length,
+            } => {
+                let array_info_ir = self.gen_array_info(type_info_to_ir, element, length);
+                context.const_struct(&[type_info_ir.into(), array_info_ir.into()], false)
+            }
+            TypeGroup::EnumTypes(e) => {
+                let enum_info_ir = self.gen_enum_info(type_info_to_ir, e);
+                context.const_struct(&[type_info_ir.into(), enum_info_ir.into()], false)
+            }
+            TypeGroup::TupleTypes(ref elements) => {
+                let tuple_info_ir = self.gen_tuple_info(type_info_to_ir, &type_info.name, elements);
+                context.const_struct(&[type_info_ir.into(), tuple_info_ir.into()], false)
+            }
+            TypeGroup::ClosureTypes(ref captures) => {
+                let closure_info_ir =
+                    self.gen_closure_info(type_info_to_ir, &type_info.name, captures);
+                context.const_struct(&[type_info_ir.into(), closure_info_ir.into()], false)
+            }
         };
         gen_global(
             self.module,
@@ -224,20 +332,116 @@ impl<'a, D: IrDatabase> TypeTableBuilder<'a, D> {
         &self,
         type_info_to_ir: &mut HashMap<TypeInfo, GlobalValue>,
         hir_struct: hir::Struct,
+        substs: &hir::Substs,
     ) -> StructValue {
-        let struct_ir = self.db.struct_ty(hir_struct);
-        let name = hir_struct.name(self.db).to_string();
+        let struct_ir = self.db.struct_ty(hir_struct, substs.clone());
+        let name = struct_name(self.db, hir_struct, substs);
         let fields = hir_struct.fields(self.db);
 
+        self.gen_fields_info(
+            type_info_to_ir,
+            &format!("struct_info::<{}>", name),
+            fields.iter().map(|field| {
+                (
+                    field.name(self.db).to_string(),
+                    field.ty(self.db).subst(substs),
+                )
+            }),
+            (0..fields.len()).map(|idx| {
+                self.target_data
+                    .offset_of_element(&struct_ir, idx as u32)
+                    .unwrap()
+            }),
+            fields
+                .iter()
+                .map(|field| field.renamed_from(self.db))
+                .collect(),
+            hir_struct.data(self.db).memory_kind.clone(),
+        )
+    }
+
+    /// Generates a `StructInfo` for a tuple with the specified element types. A tuple is exposed as
+    /// a value struct of which the fields are named after the indices of the elements.
+    fn gen_tuple_info(
+        &self,
+        type_info_to_ir: &mut HashMap<TypeInfo, GlobalValue>,
+        name: &str,
+        elements: &[hir::Ty],
+    ) -> StructValue {
+        let tuple_ir = tuple_ty(self.db, &elements.to_vec().into());
+
+        self.gen_fields_info(
+            type_info_to_ir,
+            &format!("struct_info::<{}>", name),
+            elements
+                .iter()
+                .enumerate()
+                .map(|(idx, element)| (idx.to_string(), element.clone())),
+            (0..elements.len()).map(|idx| {
+                self.target_data
+                    .offset_of_element(&tuple_ir, idx as u32)
+                    .unwrap()
+            }),
+            Vec::new(),
+            hir::StructMemoryKind::Value,
+        )
+    }
+
+    /// Generates a `StructInfo` for a closure that captures values of the specified types. A closure
+    /// is exposed as a garbage collected struct of which the first field, `fn`, is the pointer to its
+    /// function and the remaining fields are named after the indices of the captured values.
+    fn gen_closure_info(
+        &self,
+        type_info_to_ir: &mut HashMap<TypeInfo, GlobalValue>,
+        name: &str,
+        captures: &[hir::Ty],
+    ) -> StructValue {
+        let closure_ir = closure_layout_ty(self.db, captures);
+        let fn_ptr_ty = hir::Ty::simple(hir::TypeCtor::Int(hir::IntTy::usize()));
+
+        self.gen_fields_info(
+            type_info_to_ir,
+            &format!("struct_info::<{}>", name),
+            iter::once(("fn".to_owned(), fn_ptr_ty)).chain(
+                captures
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, capture)| (idx.to_string(), capture.clone())),
+            ),
+            (0..=captures.len()).map(|idx| {
+                self.target_data
+                    .offset_of_element(&closure_ir, idx as u32)
+                    .unwrap()
+            }),
+            Vec::new(),
+            hir::StructMemoryKind::GC,
+        )
+    }
+
+    /// Generates a `StructInfo` for the specified fields, their offsets, and their previous names.
+    /// Fields without a previous name may be omitted from `field_renamed_from`. The names of the
+    /// generated globals are prefixed with `prefix`.
+    fn gen_fields_info(
+        &self,
+        type_info_to_ir: &mut HashMap<TypeInfo, GlobalValue>,
+        prefix: &str,
+        fields: impl Iterator<Item = (String, hir::Ty)>,
+        field_offsets: impl Iterator<Item = u64>,
+        field_renamed_from: Vec<Option<String>>,
+        memory_kind: hir::StructMemoryKind,
+    ) -> StructValue {
+        let (field_names, field_types): (Vec<String>, Vec<hir::Ty>) = fields.unzip();
+        let num_fields = field_names.len();
+
         let field_names = gen_string_array(
             self.module,
-            fields.iter().map(|field| field.name(self.db).to_string()),
-            &format!("struct_info::<{}>::field_names", name),
+            field_names.into_iter(),
+            &format!("{}::field_names", prefix),
         );
-        let field_types: Vec<PointerValue> = fields
-            .iter()
-            .map(|field| {
-                let field_type_info = self.db.type_info(field.ty(self.db));
+        let field_types: Vec<PointerValue> = field_types
+            .into_iter()
+            .map(|field_ty| {
+                let field_type_info = self.db.type_info(field_ty);
                 if let Some(ir_value) = type_info_to_ir.get(&field_type_info) {
                     *ir_value
                 } else {
@@ -253,17 +457,19 @@ impl<'a, D: IrDatabase> TypeTableBuilder<'a, D> {
             self.module,
             self.abi_types.type_info_type,
             &field_types,
-            &format!("struct_info::<{}>::field_types", name),
+            &format!("{}::field_types", prefix),
         );
 
         let field_offsets = gen_u16_array(
             self.module,
-            (0..fields.len()).map(|idx| {
-                self.target_data
-                    .offset_of_element(&struct_ir, idx as u32)
-                    .unwrap()
-            }),
-            &format!("struct_info::<{}>::field_offsets", name),
+            field_offsets,
+            &format!("{}::field_offsets", prefix),
+        );
+
+        let field_renamed_from = gen_optional_string_array(
+            self.module,
+            field_renamed_from,
+            &format!("{}::field_renamed_from", prefix),
         );
 
         self.abi_types.struct_info_type.const_named_struct(&[
@@ -273,12 +479,119 @@ impl<'a, D: IrDatabase> TypeTableBuilder<'a, D> {
             self.module
                 .get_context()
                 .i16_type()
-                .const_int(fields.len() as u64, false)
+                .const_int(num_fields as u64, false)
                 .into(),
             self.module
                 .get_context()
                 .i8_type()
-                .const_int(hir_struct.data(self.db).memory_kind.clone().into(), false)
+                .const_int(memory_kind.into(), false)
+                .into(),
+            field_renamed_from.into(),
+        ])
+    }
+
+    fn gen_enum_info(
+        &self,
+        type_info_to_ir: &mut HashMap<TypeInfo, GlobalValue>,
+        hir_enum: hir::Enum,
+    ) -> StructValue {
+        let context = self.module.get_context();
+        let enum_ir = self.db.enum_ty(hir_enum);
+        let name = hir_enum.full_name(self.db);
+        let variants = hir_enum.variants(self.db);
+        let memory_kind = hir_enum.data(self.db).memory_kind.clone();
+
+        // The offsets of a variant's fields are relative to the start of the enum
+        let payload_offset = self.target_data.offset_of_element(&enum_ir, 1).unwrap();
+
+        let variant_names = gen_string_array(
+            self.module,
+            variants
+                .iter()
+                .map(|variant| variant.name(self.db).to_string()),
+            &format!("enum_info::<{}>::variant_names", name),
+        );
+
+        let variant_infos: Vec<StructValue> = variants
+            .iter()
+            .map(|variant| {
+                let variant_ir = enum_variant_ty(self.db, *variant);
+                let fields = variant.fields(self.db);
+                self.gen_fields_info(
+                    type_info_to_ir,
+                    &format!("enum_info::<{}>::{}", name, variant.name(self.db)),
+                    fields
+                        .iter()
+                        .map(|field| (field.name(self.db).to_string(), field.ty(self.db))),
+                    (0..fields.len()).map(|idx| {
+                        payload_offset
+                            + self
+                                .target_data
+                                .offset_of_element(&variant_ir, idx as u32)
+                                .unwrap()
+                    }),
+                    Vec::new(),
+                    memory_kind.clone(),
+                )
+            })
+            .collect();
+
+        let variant_infos = if variant_infos.is_empty() {
+            self.abi_types
+                .struct_info_type
+                .ptr_type(AddressSpace::Const)
+                .const_null()
+        } else {
+            gen_global(
+                self.module,
+                &self.abi_types.struct_info_type.const_array(&variant_infos),
+                &format!("enum_info::<{}>::variant_infos", name),
+            )
+            .as_pointer_value()
+        };
+
+        self.abi_types.enum_info_type.const_named_struct(&[
+            variant_names.into(),
+            variant_infos.into(),
+            context
+                .i16_type()
+                .const_int(variants.len() as u64, false)
+                .into(),
+            context
+                .i8_type()
+                .const_int(memory_kind.into(), false)
+                .into(),
+        ])
+    }
+
+    fn gen_array_info(
+        &self,
+        type_info_to_ir: &mut HashMap<TypeInfo, GlobalValue>,
+        element: &hir::Ty,
+        length: Option<u32>,
+    ) -> StructValue {
+        let context = self.module.get_context();
+
+        let element_type_info = self.db.type_info(element.clone());
+        let element_type = if let Some(ir_value) = type_info_to_ir.get(&element_type_info) {
+            *ir_value
+        } else {
+            let ir_value = self.gen_type_info(type_info_to_ir, &element_type_info);
+            type_info_to_ir.insert(element_type_info, ir_value);
+            ir_value
+        }
+        .as_pointer_value()
+        .const_cast(self.abi_types.type_info_type.ptr_type(AddressSpace::Const));
+
+        self.abi_types.array_info_type.const_named_struct(&[
+            element_type.into(),
+            context
+                .i32_type()
+                .const_int(u64::from(length.unwrap_or(0)), false)
+                .into(),
+            context
+                .bool_type()
+                .const_int(length.is_none() as u64, false)
                 .into(),
         ])
     }
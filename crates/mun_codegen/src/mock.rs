@@ -1,7 +1,7 @@
 use crate::{IrDatabase, OptimizationLevel};
 use hir::{FileId, RelativePathBuf, SourceDatabase, SourceRoot, SourceRootId};
 use parking_lot::Mutex;
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 /// A mock implementation of the IR database. It can be used to set up a simple test case.
 #[salsa::database(
@@ -46,7 +46,10 @@ impl MockDatabase {
         source_root.insert_file(rel_path, file_id);
 
         db.set_source_root(source_root_id, Arc::new(source_root));
+        db.set_package_root(source_root_id, file_id);
         db.set_optimization_lvl(OptimizationLevel::None);
+        db.set_debug_info(false);
+        db.set_source_dir(PathBuf::new());
 
         let context = crate::Context::create();
         db.set_context(Arc::new(context));
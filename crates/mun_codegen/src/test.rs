@@ -5,6 +5,7 @@ use hir::{
 use inkwell::OptimizationLevel;
 use mun_target::spec::Target;
 use std::cell::RefCell;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 #[test]
@@ -364,6 +365,57 @@ fn invalid_binary_ops() {
     );
 }
 
+#[test]
+fn cast_expr() {
+    test_snapshot(
+        r#"
+    pub fn int_to_float(a: i32) -> f64 { a as f64 }
+    pub fn uint_to_float(a: u32) -> f32 { a as f32 }
+    pub fn float_to_int(a: f64) -> i64 { a as i64 }
+    pub fn float_to_uint(a: f32) -> u8 { a as u8 }
+    pub fn int_extend(a: i8) -> i64 { a as i64 }
+    pub fn uint_extend(a: u8) -> i32 { a as i32 }
+    pub fn int_truncate(a: i64) -> u8 { a as u8 }
+    pub fn int_same_width(a: i32) -> u32 { a as u32 }
+    pub fn float_extend(a: f32) -> f64 { a as f64 }
+    pub fn float_truncate(a: f64) -> f32 { a as f32 }
+    pub fn bool_to_int(a: bool) -> i32 { a as i32 }
+    "#,
+    );
+}
+
+#[test]
+fn invalid_cast() {
+    test_snapshot(
+        r#"
+    struct Foo;
+
+    pub fn main(a: i32) {
+        let b = Foo as i32;
+        let c = a as bool;
+    }
+    "#,
+    );
+}
+
+#[test]
+fn invalid_globals() {
+    test_snapshot(
+        r#"
+    struct Foo;
+
+    fn foo() -> i32 { 1 }
+
+    const A: i32 = 1 / 0;
+    const B: i32 = C + 1;
+    const C: i32 = B;
+    static D: Foo = Foo;
+    static E: i32 = foo();
+    const F: u8 = 256;
+    "#,
+    );
+}
+
 #[test]
 fn update_operators() {
     test_snapshot(
@@ -773,7 +825,71 @@ fn incremental_compilation() {
     }
 
     // TODO: Try to disconnect `group_ir` and `file_ir`
-    // TODO: Add support for multiple files in a group
+}
+
+#[test]
+fn debug_info() {
+    let (mut db, file_id) = MockDatabase::with_single_file(
+        r#"
+    struct Foo { a: i32, b: bool }
+
+    pub fn add(foo: Foo, b: i32) -> i32 {
+        let c = foo.a + b;
+        c
+    }
+    "#,
+    );
+    db.set_target(Target::host_target().unwrap());
+
+    // No debug information is generated unless it is enabled
+    let ir = db
+        .file_ir(file_id)
+        .llvm_module
+        .print_to_string()
+        .to_string();
+    assert!(!ir.contains("!DICompileUnit"), "{}", ir);
+
+    db.set_debug_info(true);
+    db.set_source_dir(PathBuf::from("/mun/src"));
+    let ir = db
+        .file_ir(file_id)
+        .llvm_module
+        .print_to_string()
+        .to_string();
+    assert!(
+        ir.contains(r#"!DIFile(filename: "main.mun", directory: "/mun/src")"#),
+        "{}",
+        ir
+    );
+    assert!(ir.contains(r#"!DISubprogram(name: "add""#), "{}", ir);
+    assert!(
+        ir.contains(r#"!DILocalVariable(name: "foo", arg: 1"#),
+        "{}",
+        ir
+    );
+    assert!(
+        ir.contains(r#"!DILocalVariable(name: "b", arg: 2"#),
+        "{}",
+        ir
+    );
+    assert!(ir.contains(r#"!DILocalVariable(name: "c""#), "{}", ir);
+    assert!(
+        ir.contains(r#"!DIDerivedType(tag: DW_TAG_member, name: "a""#),
+        "{}",
+        ir
+    );
+    assert!(
+        ir.contains(r#"!DIBasicType(name: "i32", size: 32, encoding: DW_ATE_signed)"#),
+        "{}",
+        ir
+    );
+    // `let c = foo.a + b;` is on the fifth line
+    assert!(ir.contains("!DILocation(line: 5"), "{}", ir);
+
+    // The debug information must survive code generation
+    let module_builder =
+        ModuleBuilder::new(&db, file_id).expect("Failed to initialize module builder");
+    module_builder.build().expect("Failed to build object file");
 }
 
 #[test]
@@ -868,7 +984,7 @@ fn test_snapshot_with_optimization(text: &str, opt: OptimizationLevel) {
     } else {
         format!(
             "{}",
-            db.group_ir(file_id)
+            db.group_ir(hir::Module::from(file_id).package(&db))
                 .llvm_module
                 .print_to_string()
                 .to_string()
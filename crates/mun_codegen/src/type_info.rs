@@ -1,4 +1,7 @@
-use super::ir::IsIrType;
+use super::ir::{
+    ty::{struct_name, type_name},
+    IsIrType,
+};
 use crate::IrDatabase;
 use abi::Guid;
 use inkwell::context::Context;
@@ -9,14 +12,32 @@ use std::hash::{Hash, Hasher};
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum TypeGroup {
     FundamentalTypes,
-    StructTypes(hir::Struct),
+    /// The instantiation of a struct with the specified type arguments
+    StructTypes(hir::Struct, hir::Substs),
+    ArrayTypes {
+        element: hir::Ty,
+        /// The number of elements of a fixed-size array, or `None` for a dynamic array
+        length: Option<u32>,
+    },
+    EnumTypes(hir::Enum),
+    /// A tuple with the specified element types
+    TupleTypes(Vec<hir::Ty>),
+    /// The heap memory of a closure that captures values of the specified types. A function
+    /// pointer is stored as a closure that captures no values.
+    ClosureTypes(Vec<hir::Ty>),
 }
 
 impl From<TypeGroup> for u64 {
     fn from(group: TypeGroup) -> Self {
         match group {
             TypeGroup::FundamentalTypes => 0,
-            TypeGroup::StructTypes(_) => 1,
+            TypeGroup::StructTypes(..) => 1,
+            TypeGroup::ArrayTypes { .. } => 2,
+            TypeGroup::EnumTypes(_) => 3,
+            // Tuples are exposed as anonymous value structs
+            TypeGroup::TupleTypes(_) => 1,
+            // Closures are exposed as anonymous garbage collected structs
+            TypeGroup::ClosureTypes(_) => 1,
         }
     }
 }
@@ -91,8 +112,13 @@ impl TypeInfo {
         }
     }
 
-    pub fn new_struct<D: IrDatabase>(db: &D, s: hir::Struct, type_size: TypeSize) -> TypeInfo {
-        let name = s.name(db).to_string();
+    pub fn new_struct<D: IrDatabase>(
+        db: &D,
+        s: hir::Struct,
+        substs: hir::Substs,
+        type_size: TypeSize,
+    ) -> TypeInfo {
+        let name = struct_name(db, s, &substs);
         let guid_string = {
             let fields: Vec<String> = s
                 .fields(db)
@@ -100,6 +126,7 @@ impl TypeInfo {
                 .map(|f| {
                     let ty_string = f
                         .ty(db)
+                        .subst(&substs)
                         .guid_string(db)
                         .expect("type should be convertible to a string");
                     format!("{}: {}", f.name(db).to_string(), ty_string)
@@ -117,7 +144,156 @@ impl TypeInfo {
                 b: md5::compute(&guid_string).0,
             },
             name,
-            group: TypeGroup::StructTypes(s),
+            group: TypeGroup::StructTypes(s, substs),
+            size: type_size,
+        }
+    }
+
+    pub fn new_enum<D: IrDatabase>(db: &D, e: hir::Enum, type_size: TypeSize) -> TypeInfo {
+        let name = e.full_name(db);
+        let guid_string = {
+            let variants: Vec<String> = e
+                .variants(db)
+                .into_iter()
+                .map(|v| {
+                    let fields: Vec<String> = v
+                        .fields(db)
+                        .into_iter()
+                        .map(|f| {
+                            let ty_string = f
+                                .ty(db)
+                                .guid_string(db)
+                                .expect("type should be convertible to a string");
+                            format!("{}: {}", f.name(db).to_string(), ty_string)
+                        })
+                        .collect();
+                    format!("{}{{{}}}", v.name(db), fields.join(","))
+                })
+                .collect();
+
+            format!(
+                "enum {name}{{{variants}}}",
+                name = &name,
+                variants = variants.join(",")
+            )
+        };
+        Self {
+            guid: Guid {
+                b: md5::compute(&guid_string).0,
+            },
+            name,
+            group: TypeGroup::EnumTypes(e),
+            size: type_size,
+        }
+    }
+
+    pub fn new_array<D: IrDatabase>(db: &D, ty: &hir::Ty, type_size: TypeSize) -> TypeInfo {
+        let (element, length) = match ty {
+            hir::Ty::Apply(hir::ApplicationTy {
+                ctor: hir::TypeCtor::Array(length),
+                parameters,
+            }) => (parameters.as_single(), Some(*length)),
+            hir::Ty::Apply(hir::ApplicationTy {
+                ctor: hir::TypeCtor::DynArray,
+                parameters,
+            }) => (parameters.as_single(), None),
+            _ => (None, None),
+        };
+        let element = element.expect("expected an array type").clone();
+
+        let element_name = db.type_info(element.clone()).name;
+        let name = match length {
+            Some(length) => format!("[{}; {}]", element_name, length),
+            None => format!("[{}]", element_name),
+        };
+        let guid_string = ty
+            .guid_string(db)
+            .expect("type should be convertible to a string");
+
+        Self {
+            guid: Guid {
+                b: md5::compute(&guid_string).0,
+            },
+            name,
+            group: TypeGroup::ArrayTypes { element, length },
+            size: type_size,
+        }
+    }
+
+    pub fn new_tuple<D: IrDatabase>(
+        db: &D,
+        elements: Vec<hir::Ty>,
+        type_size: TypeSize,
+    ) -> TypeInfo {
+        let element_names: Vec<String> = elements
+            .iter()
+            .map(|element| db.type_info(element.clone()).name)
+            .collect();
+        let name = hir::tuple_name(&element_names);
+        let guid_string = hir::Ty::tuple(elements.clone())
+            .guid_string(db)
+            .expect("type should be convertible to a string");
+
+        Self {
+            guid: Guid {
+                b: md5::compute(&guid_string).0,
+            },
+            name,
+            group: TypeGroup::TupleTypes(elements),
+            size: type_size,
+        }
+    }
+
+    /// The layout of a function pointer does not depend on its signature, so its `Guid` is derived
+    /// from its name, e.g. `fn(core::f32) -> core::f32`.
+    pub fn new_fn_ptr<D: IrDatabase>(db: &D, ty: &hir::Ty, type_size: TypeSize) -> TypeInfo {
+        let name = type_name(db, ty);
+
+        Self {
+            guid: Guid {
+                b: md5::compute(&name).0,
+            },
+            name,
+            group: TypeGroup::ClosureTypes(Vec::new()),
+            size: type_size,
+        }
+    }
+
+    /// The name of a closure starts with the name of the function pointer type `fn_ptr_ty` through
+    /// which it is called, e.g. `fn(core::f32) -> core::f32 {closure}(core::f32,)`.
+    pub fn new_closure<D: IrDatabase>(
+        db: &D,
+        fn_ptr_ty: &hir::Ty,
+        captures: Vec<hir::Ty>,
+        type_size: TypeSize,
+    ) -> TypeInfo {
+        let capture_names: Vec<String> = captures
+            .iter()
+            .map(|capture| db.type_info(capture.clone()).name)
+            .collect();
+        let name = format!(
+            "{} {{closure}}{}",
+            type_name(db, fn_ptr_ty),
+            hir::tuple_name(&capture_names)
+        );
+        let guid_string = {
+            let capture_tys: Vec<String> = captures
+                .iter()
+                .map(|capture| {
+                    capture
+                        .guid_string(db)
+                        .expect("type should be convertible to a string")
+                })
+                .collect();
+            format!("{} closure{}", name, hir::tuple_name(&capture_tys))
+        };
+
+        Self {
+            guid: Guid {
+                b: md5::compute(&guid_string).0,
+            },
+            name,
+            group: TypeGroup::ClosureTypes(captures),
             size: type_size,
         }
     }
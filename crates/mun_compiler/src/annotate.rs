@@ -4,11 +4,33 @@
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
 use mun_hir::line_index::LineIndex;
+use mun_syntax::TextRange;
 
 use unicode_segmentation::UnicodeSegmentation;
 
+/// A snippet together with the annotated locations in the source text. Building a `Slice`
+/// converts the ranges of its annotations to graphemes in the cropped source of the slice, so the
+/// locations are retained for machine-readable output.
+#[derive(Debug)]
+pub(crate) struct SourceSnippet {
+    pub snippet: Snippet,
+    pub spans: Vec<SourceSpan>,
+}
+
+/// An annotation of a range of the source text
+#[derive(Clone, Debug)]
+pub(crate) struct SourceSpan {
+    pub range: TextRange,
+    pub label: String,
+    pub annotation_type: AnnotationType,
+    /// Whether this is the primary location of the diagnostic, as opposed to a secondary
+    /// annotation like the previous definition of a duplicate name
+    pub is_primary: bool,
+}
+
 pub struct SnippetBuilder {
     snippet: Snippet,
+    spans: Vec<SourceSpan>,
 }
 
 impl Default for SnippetBuilder {
@@ -19,6 +41,7 @@ impl Default for SnippetBuilder {
                 footer: vec![],
                 slices: vec![],
             },
+            spans: vec![],
         }
     }
 }
@@ -39,13 +62,35 @@ impl SnippetBuilder {
         self.snippet.slices.push(slice);
         self
     }
+    /// Builds the `slice` and adds it, retaining the locations of its annotations in the
+    /// `source_text`.
+    pub(crate) fn source_slice(
+        mut self,
+        slice: SliceBuilder,
+        source_text: &str,
+        line_index: &LineIndex,
+    ) -> SnippetBuilder {
+        self.spans.extend(slice.spans.iter().cloned());
+        self.snippet
+            .slices
+            .push(slice.build(source_text, line_index));
+        self
+    }
     pub fn build(self) -> Snippet {
         self.snippet
     }
+    /// Builds the snippet together with the locations of the annotations of its source slices.
+    pub(crate) fn build_source_snippet(self) -> SourceSnippet {
+        SourceSnippet {
+            snippet: self.snippet,
+            spans: self.spans,
+        }
+    }
 }
 
 pub struct SliceBuilder {
     slice: Slice,
+    spans: Vec<SourceSpan>,
 }
 
 impl SliceBuilder {
@@ -58,6 +103,7 @@ impl SliceBuilder {
                 annotations: Vec::new(),
                 fold,
             },
+            spans: Vec::new(),
         }
     }
 
@@ -67,11 +113,37 @@ impl SliceBuilder {
     }
 
     pub fn source_annotation(
+        self,
+        range: (usize, usize),
+        label: &str,
+        source_annotation_type: AnnotationType,
+    ) -> SliceBuilder {
+        self.annotation(range, label, source_annotation_type, false)
+    }
+
+    /// Adds the annotation of the primary location of the diagnostic.
+    pub fn primary_source_annotation(
+        self,
+        range: (usize, usize),
+        label: &str,
+        source_annotation_type: AnnotationType,
+    ) -> SliceBuilder {
+        self.annotation(range, label, source_annotation_type, true)
+    }
+
+    fn annotation(
         mut self,
         range: (usize, usize),
         label: &str,
         source_annotation_type: AnnotationType,
+        is_primary: bool,
     ) -> SliceBuilder {
+        self.spans.push(SourceSpan {
+            range: TextRange::from_to((range.0 as u32).into(), (range.1 as u32).into()),
+            label: label.to_string(),
+            annotation_type: source_annotation_type.clone(),
+            is_primary,
+        });
         self.slice.annotations.push(SourceAnnotation {
             range,
             label: label.to_string(),
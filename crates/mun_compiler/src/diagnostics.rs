@@ -1,14 +1,22 @@
 use mun_hir::diagnostics::DiagnosticSink;
-use mun_hir::{FileId, HirDatabase, Module};
+use mun_hir::lint::LintLevels;
+use mun_hir::{FileId, HirDatabase, Module, RelativePathBuf};
 
 use std::cell::RefCell;
+use std::collections::HashMap;
 
-use annotate_snippets::snippet::Snippet;
-
+use crate::annotate::SourceSnippet;
 use crate::diagnostics_snippets;
 
-/// Constructs diagnostic messages for the given file.
-pub fn diagnostics(db: &impl HirDatabase, file_id: FileId) -> Vec<Snippet> {
+/// Constructs diagnostic messages for the given file, including the warnings of the lints that are
+/// enabled by `lint_levels`. Modules whose file is in `unreadable_files` are reported with the
+/// reason why their file could not be read.
+pub fn diagnostics(
+    db: &impl HirDatabase,
+    file_id: FileId,
+    lint_levels: &LintLevels,
+    unreadable_files: &HashMap<RelativePathBuf, String>,
+) -> Vec<SourceSnippet> {
     let parse = db.parse(file_id);
 
     let mut result = Vec::new();
@@ -129,9 +137,49 @@ pub fn diagnostics(db: &impl HirDatabase, file_id: FileId) -> Vec<Snippet> {
                 &source_code,
                 &line_index,
             ));
+    })
+    .on::<mun_hir::diagnostics::UnresolvedModule, _>(|d| {
+        let snippet = match unreadable_files.get(&d.candidate) {
+            Some(error) => diagnostics_snippets::unreadable_module_error(
+                d,
+                db,
+                &parse,
+                &relative_file_path,
+                &source_code,
+                &line_index,
+                error,
+            ),
+            None => diagnostics_snippets::generic_error(
+                d,
+                db,
+                &parse,
+                &relative_file_path,
+                &source_code,
+                &line_index,
+            ),
+        };
+        result.borrow_mut().push(snippet);
+    })
+    .on::<mun_hir::diagnostics::LintDiagnostic, _>(|d| {
+        result
+            .borrow_mut()
+            .push(diagnostics_snippets::lint_diagnostic(
+                d,
+                db,
+                &parse,
+                &relative_file_path,
+                &source_code,
+                &line_index,
+            ));
     });
 
-    Module::from(file_id).diagnostics(db, &mut sink);
+    let module = Module::from(file_id);
+    module.diagnostics(db, &mut sink);
+
+    // Lints are only run on a file without errors, because their results would be unreliable
+    if result.borrow().is_empty() {
+        module.lints(db, lint_levels, &mut sink);
+    }
 
     drop(sink);
 
@@ -140,7 +188,11 @@ pub fn diagnostics(db: &impl HirDatabase, file_id: FileId) -> Vec<Snippet> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Config, DisplayColor, Driver, PathOrInline, RelativePathBuf};
+    use crate::{
+        Config, DisplayColor, Driver, Lint, LintLevel, LintLevels, MessageFormat, PathOrInline,
+        RelativePathBuf,
+    };
+    use serde_json::{json, Value};
 
     /// Compile passed source code and return all compilation errors
     fn compilation_errors(source_code: &str) -> String {
@@ -163,6 +215,31 @@ mod tests {
         String::from_utf8(compilation_errors).unwrap()
     }
 
+    /// Compile passed source code and return all JSON messages that are emitted
+    fn json_messages(source_code: &str) -> Vec<Value> {
+        let config = Config {
+            message_format: MessageFormat::Json,
+            ..Config::default()
+        };
+
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: source_code.to_owned(),
+        };
+
+        let (driver, _) = Driver::with_file(config, input).unwrap();
+
+        let mut messages = Vec::<u8>::new();
+
+        let _ = driver.emit_diagnostics(&mut messages).unwrap();
+
+        String::from_utf8(messages)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
     #[test]
     fn test_syntax_error() {
         insta::assert_display_snapshot!(compilation_errors("\n\nfn main(\n struct Foo\n"));
@@ -216,4 +293,171 @@ mod tests {
             "\n\nstruct Foo {\ni: bool\n}\n\nfn main() {\nlet a = Foo { i: false };\nlet b = a.t;\n}"
         ));
     }
+
+    #[test]
+    fn test_json_unresolved_value_error() {
+        assert_eq!(
+            json_messages("fn main() {\n    let a = b;\n}"),
+            vec![
+                json!({
+                    "type": "diagnostic",
+                    "severity": "error",
+                    "message": "cannot find value `b` in this scope",
+                    "file": "main.mun",
+                    "spans": [{
+                        "byte_start": 24,
+                        "byte_end": 25,
+                        "line_start": 2,
+                        "column_start": 13,
+                        "line_end": 2,
+                        "column_end": 14,
+                        "severity": "error",
+                        "label": "not found in this scope",
+                        "is_primary": true,
+                    }],
+                    "notes": [],
+                }),
+                json!({ "type": "summary", "errors": 1, "warnings": 0 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_multibyte_characters() {
+        assert_eq!(
+            json_messages("// héllo wörld\nfn main() {\n    let a = b;\n}"),
+            vec![
+                json!({
+                    "type": "diagnostic",
+                    "severity": "error",
+                    "message": "cannot find value `b` in this scope",
+                    "file": "main.mun",
+                    "spans": [{
+                        "byte_start": 41,
+                        "byte_end": 42,
+                        "line_start": 3,
+                        "column_start": 13,
+                        "line_end": 3,
+                        "column_end": 14,
+                        "severity": "error",
+                        "label": "not found in this scope",
+                        "is_primary": true,
+                    }],
+                    "notes": [],
+                }),
+                json!({ "type": "summary", "errors": 1, "warnings": 0 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_duplicate_definition_error() {
+        assert_eq!(
+            json_messages("fn foo() {}\nfn foo() {}"),
+            vec![
+                json!({
+                    "type": "diagnostic",
+                    "severity": "error",
+                    "message": "the name `foo` is defined multiple times",
+                    "file": "main.mun",
+                    "spans": [
+                        {
+                            "byte_start": 0,
+                            "byte_end": 8,
+                            "line_start": 1,
+                            "column_start": 1,
+                            "line_end": 1,
+                            "column_end": 9,
+                            "severity": "warning",
+                            "label": "previous definition of the value `foo` here",
+                            "is_primary": false,
+                        },
+                        {
+                            "byte_start": 12,
+                            "byte_end": 20,
+                            "line_start": 2,
+                            "column_start": 1,
+                            "line_end": 2,
+                            "column_end": 9,
+                            "severity": "error",
+                            "label": "`foo` redefined here",
+                            "is_primary": true,
+                        }
+                    ],
+                    "notes": [{
+                        "severity": "note",
+                        "message": "`foo` must be defined only once in the value namespace of this module",
+                    }],
+                }),
+                json!({ "type": "summary", "errors": 1, "warnings": 0 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_no_errors() {
+        assert_eq!(
+            json_messages("pub fn main() {}"),
+            vec![json!({ "type": "summary", "errors": 0, "warnings": 0 })]
+        );
+    }
+
+    #[test]
+    fn test_json_lint_warning() {
+        assert_eq!(
+            json_messages("pub fn main() {\n    let a = 1;\n}"),
+            vec![
+                json!({
+                    "type": "diagnostic",
+                    "severity": "warning",
+                    "message": "unused variable: `a`",
+                    "file": "main.mun",
+                    "spans": [{
+                        "byte_start": 24,
+                        "byte_end": 25,
+                        "line_start": 2,
+                        "column_start": 9,
+                        "line_end": 2,
+                        "column_end": 10,
+                        "severity": "warning",
+                        "label": "unused variable: `a`",
+                        "is_primary": true,
+                    }],
+                    "notes": [{
+                        "severity": "note",
+                        "message": "the `unused_variables` lint can be suppressed with `#[allow(unused_variables)]`",
+                    }],
+                }),
+                json!({ "type": "summary", "errors": 0, "warnings": 1 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_levels() {
+        let has_errors = |lint_levels: LintLevels| {
+            let config = Config {
+                lint_levels,
+                ..Config::default()
+            };
+            let input = PathOrInline::Inline {
+                rel_path: RelativePathBuf::from("main.mun"),
+                contents: "fn foo() {}".to_owned(),
+            };
+            let (driver, _) = Driver::with_file(config, input).unwrap();
+            driver.emit_diagnostics(&mut Vec::new()).unwrap()
+        };
+
+        // Warnings do not prevent an assembly from being written
+        assert!(!has_errors(LintLevels::default()));
+
+        let mut lint_levels = LintLevels::default();
+        lint_levels.set(Lint::UnusedFunctions, LintLevel::Allow);
+        lint_levels.set_warnings(LintLevel::Deny);
+        assert!(!has_errors(lint_levels));
+
+        let mut lint_levels = LintLevels::default();
+        lint_levels.set_warnings(LintLevel::Deny);
+        assert!(has_errors(lint_levels));
+    }
 }
@@ -0,0 +1,156 @@
+//! Machine-readable representations of the diagnostics in `diagnostics_snippets.rs`, which are
+//! emitted as one JSON object per line when the `MessageFormat::Json` is used.
+
+use crate::annotate::SourceSnippet;
+use annotate_snippets::snippet::{Annotation, AnnotationType};
+use mun_hir::line_index::LineIndex;
+use serde::Serialize;
+
+/// A single line of JSON output
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum Message {
+    Diagnostic(Diagnostic),
+    Summary(BuildSummary),
+}
+
+/// A diagnostic message about the source code of a single file
+#[derive(Debug, Serialize)]
+pub(crate) struct Diagnostic {
+    /// One of `error`, `warning`, `info`, `note`, or `help`
+    pub severity: &'static str,
+    pub message: String,
+    /// The path of the file, relative to the root of the package
+    pub file: Option<String>,
+    /// The annotated regions of the source code
+    pub spans: Vec<Span>,
+    /// Additional notes that do not refer to the source code
+    pub notes: Vec<Note>,
+}
+
+/// An annotated region of source code
+#[derive(Debug, Serialize)]
+pub(crate) struct Span {
+    /// The offset in bytes of the start of the span
+    pub byte_start: usize,
+    /// The offset in bytes of the (exclusive) end of the span
+    pub byte_end: usize,
+    /// The one-based line of the start of the span
+    pub line_start: usize,
+    /// The one-based column, in characters, of the start of the span
+    pub column_start: usize,
+    /// The one-based line of the end of the span
+    pub line_end: usize,
+    /// The one-based column, in characters, of the end of the span
+    pub column_end: usize,
+    pub severity: &'static str,
+    pub label: String,
+    /// Whether this span is the primary location of the diagnostic, as opposed to a secondary
+    /// annotation like the previous definition of a duplicate name
+    pub is_primary: bool,
+}
+
+/// A note that is attached to a diagnostic
+#[derive(Debug, Serialize)]
+pub(crate) struct Note {
+    pub severity: &'static str,
+    pub message: String,
+}
+
+/// The summary that is emitted after all diagnostics of a build
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct BuildSummary {
+    /// The number of emitted errors
+    pub errors: usize,
+    /// The number of emitted warnings
+    pub warnings: usize,
+}
+
+impl Diagnostic {
+    /// Constructs the machine-readable representation of `source_snippet`, which annotates
+    /// `source_text`.
+    pub fn from_snippet(
+        source_snippet: &SourceSnippet,
+        source_text: &str,
+        line_index: &LineIndex,
+    ) -> Self {
+        let snippet = &source_snippet.snippet;
+        let (severity, message) = snippet
+            .title
+            .as_ref()
+            .map(annotation_parts)
+            .unwrap_or_else(|| (severity_name(&AnnotationType::Error), String::new()));
+
+        let spans = source_snippet
+            .spans
+            .iter()
+            .map(|span| {
+                let byte_start = span.range.start().to_usize();
+                let byte_end = span.range.end().to_usize();
+                let (line_start, column_start) = line_column(byte_start, source_text, line_index);
+                let (line_end, column_end) = line_column(byte_end, source_text, line_index);
+                Span {
+                    byte_start,
+                    byte_end,
+                    line_start,
+                    column_start,
+                    line_end,
+                    column_end,
+                    severity: severity_name(&span.annotation_type),
+                    label: span.label.clone(),
+                    is_primary: span.is_primary,
+                }
+            })
+            .collect();
+
+        let notes = snippet
+            .footer
+            .iter()
+            .map(|footer| {
+                let (severity, message) = annotation_parts(footer);
+                Note { severity, message }
+            })
+            .collect();
+
+        Diagnostic {
+            severity,
+            message,
+            file: snippet
+                .slices
+                .first()
+                .and_then(|slice| slice.origin.clone()),
+            spans,
+            notes,
+        }
+    }
+}
+
+/// Returns the severity and label of `annotation`.
+fn annotation_parts(annotation: &Annotation) -> (&'static str, String) {
+    (
+        severity_name(&annotation.annotation_type),
+        annotation.label.clone().unwrap_or_default(),
+    )
+}
+
+/// Returns the name of the severity of `annotation_type`.
+fn severity_name(annotation_type: &AnnotationType) -> &'static str {
+    match annotation_type {
+        AnnotationType::Error => "error",
+        AnnotationType::Warning => "warning",
+        AnnotationType::Info => "info",
+        AnnotationType::Note => "note",
+        AnnotationType::Help => "help",
+    }
+}
+
+/// Returns the one-based line and column, in characters, of the byte `offset` in `source_text`.
+fn line_column(offset: usize, source_text: &str, line_index: &LineIndex) -> (usize, usize) {
+    let offset = offset.min(source_text.len());
+    let line = line_index.line_col((offset as u32).into()).line;
+    let line_offset = line_index.line_offset(line);
+    let column = source_text
+        .get(line_offset..offset)
+        .map_or(0, |text| text.chars().count());
+    (line as usize + 1, column + 1)
+}
@@ -1,4 +1,4 @@
-use mun_hir::diagnostics::Diagnostic as HirDiagnostic;
+use mun_hir::diagnostics::{Diagnostic as HirDiagnostic, Severity};
 use mun_hir::{HirDatabase, HirDisplay};
 use mun_syntax::{
     ast, AstNode, Parse, SourceFile, SyntaxError, SyntaxKind, SyntaxNodePtr, TextRange,
@@ -8,9 +8,9 @@ use std::sync::Arc;
 
 use mun_hir::line_index::LineIndex;
 
-use crate::annotate::{AnnotationBuilder, SliceBuilder, SnippetBuilder};
+use crate::annotate::{AnnotationBuilder, SliceBuilder, SnippetBuilder, SourceSnippet};
 
-use annotate_snippets::snippet::{AnnotationType, Snippet};
+use annotate_snippets::snippet::AnnotationType;
 
 fn text_range_to_tuple(text_range: TextRange) -> (usize, usize) {
     (text_range.start().to_usize(), text_range.end().to_usize())
@@ -42,29 +42,30 @@ pub(crate) fn syntax_error(
     relative_file_path: &str,
     source_code: &str,
     line_index: &Arc<LineIndex>,
-) -> Snippet {
+) -> SourceSnippet {
     let mut snippet = SnippetBuilder::new()
         .title(
             AnnotationBuilder::new(AnnotationType::Error)
                 .label("syntax error")
                 .build(),
         )
-        .slice(
+        .source_slice(
             SliceBuilder::new(true)
                 .origin(relative_file_path)
-                .source_annotation(
+                .primary_source_annotation(
                     (
                         syntax_error.location().offset().to_usize(),
                         syntax_error.location().end_offset().to_usize(),
                     ),
                     &syntax_error.to_string(),
                     AnnotationType::Error,
-                )
-                .build(&source_code, &line_index),
+                ),
+            &source_code,
+            &line_index,
         )
-        .build();
+        .build_source_snippet();
     // Add one to right range to make highlighting range here visible on output
-    snippet.slices[0].annotations[0].range.1 += 1;
+    snippet.snippet.slices[0].annotations[0].range.1 += 1;
 
     snippet
 }
@@ -76,24 +77,25 @@ pub(crate) fn generic_error(
     relative_file_path: &str,
     source_code: &str,
     line_index: &Arc<LineIndex>,
-) -> Snippet {
+) -> SourceSnippet {
     SnippetBuilder::new()
         .title(
             AnnotationBuilder::new(AnnotationType::Error)
                 .label(&diagnostic.message())
                 .build(),
         )
-        .slice(
+        .source_slice(
             SliceBuilder::new(true)
                 .origin(relative_file_path)
-                .source_annotation(
+                .primary_source_annotation(
                     text_range_to_tuple(diagnostic.highlight_range()),
                     &diagnostic.message(),
                     AnnotationType::Error,
-                )
-                .build(&source_code, &line_index),
+                ),
+            &source_code,
+            &line_index,
         )
-        .build()
+        .build_source_snippet()
 }
 
 pub(crate) fn unresolved_value_error(
@@ -103,7 +105,7 @@ pub(crate) fn unresolved_value_error(
     relative_file_path: &str,
     source_code: &str,
     line_index: &Arc<LineIndex>,
-) -> Snippet {
+) -> SourceSnippet {
     let unresolved_value = diagnostic
         .expr
         .to_node(&parse.tree().syntax())
@@ -119,17 +121,18 @@ pub(crate) fn unresolved_value_error(
                 ))
                 .build(),
         )
-        .slice(
+        .source_slice(
             SliceBuilder::new(true)
                 .origin(relative_file_path)
-                .source_annotation(
+                .primary_source_annotation(
                     text_range_to_tuple(diagnostic.highlight_range()),
                     "not found in this scope",
                     AnnotationType::Error,
-                )
-                .build(&source_code, &line_index),
+                ),
+            &source_code,
+            &line_index,
         )
-        .build()
+        .build_source_snippet()
 }
 
 pub(crate) fn unresolved_type_error(
@@ -139,7 +142,7 @@ pub(crate) fn unresolved_type_error(
     relative_file_path: &str,
     source_code: &str,
     line_index: &Arc<LineIndex>,
-) -> Snippet {
+) -> SourceSnippet {
     let unresolved_type = diagnostic
         .type_ref
         .to_node(&parse.syntax_node())
@@ -156,17 +159,18 @@ pub(crate) fn unresolved_type_error(
                 ))
                 .build(),
         )
-        .slice(
+        .source_slice(
             SliceBuilder::new(true)
                 .origin(relative_file_path)
-                .source_annotation(
+                .primary_source_annotation(
                     text_range_to_tuple(diagnostic.highlight_range()),
                     "not found in this scope",
                     AnnotationType::Error,
-                )
-                .build(&source_code, &line_index),
+                ),
+            &source_code,
+            &line_index,
         )
-        .build()
+        .build_source_snippet()
 }
 
 pub(crate) fn expected_function_error(
@@ -176,27 +180,28 @@ pub(crate) fn expected_function_error(
     relative_file_path: &str,
     source_code: &str,
     line_index: &Arc<LineIndex>,
-) -> Snippet {
+) -> SourceSnippet {
     SnippetBuilder::new()
         .title(
             AnnotationBuilder::new(AnnotationType::Error)
                 .label(&diagnostic.message())
                 .build(),
         )
-        .slice(
+        .source_slice(
             SliceBuilder::new(true)
                 .origin(relative_file_path)
-                .source_annotation(
+                .primary_source_annotation(
                     text_range_to_tuple(diagnostic.highlight_range()),
                     &format!(
                         "expected function, found `{}`",
                         diagnostic.found.display(hir_database)
                     ),
                     AnnotationType::Error,
-                )
-                .build(&source_code, &line_index),
+                ),
+            &source_code,
+            &line_index,
         )
-        .build()
+        .build_source_snippet()
 }
 
 pub(crate) fn mismatched_type_error(
@@ -206,17 +211,17 @@ pub(crate) fn mismatched_type_error(
     relative_file_path: &str,
     source_code: &str,
     line_index: &Arc<LineIndex>,
-) -> Snippet {
+) -> SourceSnippet {
     SnippetBuilder::new()
         .title(
             AnnotationBuilder::new(AnnotationType::Error)
                 .label(&diagnostic.message())
                 .build(),
         )
-        .slice(
+        .source_slice(
             SliceBuilder::new(true)
                 .origin(relative_file_path)
-                .source_annotation(
+                .primary_source_annotation(
                     text_range_to_tuple(diagnostic.highlight_range()),
                     &format!(
                         "expected `{}`, found `{}`",
@@ -224,10 +229,11 @@ pub(crate) fn mismatched_type_error(
                         diagnostic.found.display(hir_database)
                     ),
                     AnnotationType::Error,
-                )
-                .build(&source_code, &line_index),
+                ),
+            &source_code,
+            &line_index,
         )
-        .build()
+        .build_source_snippet()
 }
 
 pub(crate) fn duplicate_definition_error(
@@ -237,7 +243,7 @@ pub(crate) fn duplicate_definition_error(
     relative_file_path: &str,
     source_code: &str,
     line_index: &Arc<LineIndex>,
-) -> Snippet {
+) -> SourceSnippet {
     let first_definition_location = syntax_node_ptr_location(diagnostic.first_definition, &parse);
     let definition_location = syntax_node_ptr_location(diagnostic.definition, &parse);
 
@@ -256,7 +262,7 @@ pub(crate) fn duplicate_definition_error(
                 .label(&diagnostic.message())
                 .build(),
         )
-        .slice(
+        .source_slice(
             SliceBuilder::new(true)
                 .origin(relative_file_path)
                 // First definition
@@ -269,12 +275,13 @@ pub(crate) fn duplicate_definition_error(
                     AnnotationType::Warning,
                 )
                 // Second definition
-                .source_annotation(
+                .primary_source_annotation(
                     text_range_to_tuple(definition_location),
                     &format!("`{}` redefined here", diagnostic.name),
                     AnnotationType::Error,
-                )
-                .build(&source_code, &line_index),
+                ),
+            &source_code,
+            &line_index,
         )
         .footer(
             AnnotationBuilder::new(AnnotationType::Note)
@@ -284,7 +291,7 @@ pub(crate) fn duplicate_definition_error(
                 ))
                 .build(),
         )
-        .build()
+        .build_source_snippet()
 }
 
 pub(crate) fn possibly_uninitialized_variable_error(
@@ -294,7 +301,7 @@ pub(crate) fn possibly_uninitialized_variable_error(
     relative_file_path: &str,
     source_code: &str,
     line_index: &Arc<LineIndex>,
-) -> Snippet {
+) -> SourceSnippet {
     let variable_name = diagnostic.pat.to_node(&parse.syntax_node()).text();
 
     SnippetBuilder::new()
@@ -303,17 +310,18 @@ pub(crate) fn possibly_uninitialized_variable_error(
                 .label(&format!("{}: `{}`", diagnostic.message(), variable_name))
                 .build(),
         )
-        .slice(
+        .source_slice(
             SliceBuilder::new(true)
                 .origin(relative_file_path)
-                .source_annotation(
+                .primary_source_annotation(
                     text_range_to_tuple(diagnostic.highlight_range()),
                     &format!("use of possibly-uninitialized `{}`", variable_name),
                     AnnotationType::Error,
-                )
-                .build(&source_code, &line_index),
+                ),
+            &source_code,
+            &line_index,
         )
-        .build()
+        .build_source_snippet()
 }
 
 pub(crate) fn access_unknown_field_error(
@@ -323,7 +331,7 @@ pub(crate) fn access_unknown_field_error(
     relative_file_path: &str,
     source_code: &str,
     line_index: &Arc<LineIndex>,
-) -> Snippet {
+) -> SourceSnippet {
     let location = ast::FieldExpr::cast(diagnostic.expr.to_node(&parse.syntax_node()))
         .map(|f| f.field_range())
         .unwrap_or_else(|| diagnostic.highlight_range());
@@ -338,17 +346,95 @@ pub(crate) fn access_unknown_field_error(
                 ))
                 .build(),
         )
-        .slice(
+        .source_slice(
             SliceBuilder::new(true)
                 .origin(relative_file_path)
-                .source_annotation(
+                .primary_source_annotation(
                     text_range_to_tuple(location),
                     "unknown field",
                     AnnotationType::Error,
-                )
-                .build(&source_code, &line_index),
+                ),
+            &source_code,
+            &line_index,
+        )
+        .build_source_snippet()
+}
+
+pub(crate) fn unreadable_module_error(
+    diagnostic: &mun_hir::diagnostics::UnresolvedModule,
+    _: &impl HirDatabase,
+    _: &Parse<SourceFile>,
+    relative_file_path: &str,
+    source_code: &str,
+    line_index: &Arc<LineIndex>,
+    read_error: &str,
+) -> SourceSnippet {
+    SnippetBuilder::new()
+        .title(
+            AnnotationBuilder::new(AnnotationType::Error)
+                .label(&format!(
+                    "could not read `{}`: {}",
+                    diagnostic.candidate, read_error
+                ))
+                .build(),
         )
-        .build()
+        .source_slice(
+            SliceBuilder::new(true)
+                .origin(relative_file_path)
+                .primary_source_annotation(
+                    text_range_to_tuple(diagnostic.highlight_range()),
+                    "the file of this module could not be read",
+                    AnnotationType::Error,
+                ),
+            &source_code,
+            &line_index,
+        )
+        .build_source_snippet()
+}
+
+pub(crate) fn lint_diagnostic(
+    diagnostic: &mun_hir::diagnostics::LintDiagnostic,
+    _: &impl HirDatabase,
+    _: &Parse<SourceFile>,
+    relative_file_path: &str,
+    source_code: &str,
+    line_index: &Arc<LineIndex>,
+) -> SourceSnippet {
+    SnippetBuilder::new()
+        .title(
+            AnnotationBuilder::new(lint_annotation_type(diagnostic))
+                .label(&diagnostic.message())
+                .build(),
+        )
+        .source_slice(
+            SliceBuilder::new(true)
+                .origin(relative_file_path)
+                .primary_source_annotation(
+                    text_range_to_tuple(diagnostic.highlight_range()),
+                    &diagnostic.message(),
+                    lint_annotation_type(diagnostic),
+                ),
+            &source_code,
+            &line_index,
+        )
+        .footer(
+            AnnotationBuilder::new(AnnotationType::Note)
+                .label(&format!(
+                    "the `{}` lint can be suppressed with `#[allow({})]`",
+                    diagnostic.lint, diagnostic.lint
+                ))
+                .build(),
+        )
+        .build_source_snippet()
+}
+
+/// Returns the annotation type that corresponds to the level of the lint that emitted
+/// `diagnostic`.
+fn lint_annotation_type(diagnostic: &mun_hir::diagnostics::LintDiagnostic) -> AnnotationType {
+    match diagnostic.severity() {
+        Severity::Error => AnnotationType::Error,
+        Severity::Warning => AnnotationType::Warning,
+    }
 }
 
 #[cfg(test)]
@@ -1,17 +1,31 @@
 //! `Driver` is a stateful compiler frontend that enables incremental compilation by retaining state
 //! from previous compilation.
 
-use crate::{db::CompilerDatabase, diagnostics::diagnostics, PathOrInline};
+use crate::{
+    db::CompilerDatabase,
+    diagnostics::diagnostics,
+    diagnostics_json::{self, BuildSummary},
+    PathOrInline,
+};
 use mun_codegen::{IrDatabase, ModuleBuilder};
-use mun_hir::{FileId, HirDatabase, RelativePathBuf, SourceDatabase, SourceRoot, SourceRootId};
+use mun_hir::{
+    lint::LintLevels, FileId, HirDatabase, Package, RelativePath, RelativePathBuf, SourceDatabase,
+    SourceRoot, SourceRootId,
+};
 
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 mod config;
 mod display_color;
+mod message_format;
 
 pub use self::config::Config;
 pub use self::display_color::DisplayColor;
+pub use self::message_format::MessageFormat;
 
 use annotate_snippets::{
     display_list::DisplayList,
@@ -26,6 +40,14 @@ pub struct Driver {
     db: CompilerDatabase,
     out_dir: Option<PathBuf>,
     display_color: DisplayColor,
+    message_format: MessageFormat,
+    lint_levels: LintLevels,
+    /// The directory that contains the source files of the package, if it is loaded from disk
+    source_dir: Option<PathBuf>,
+    /// The files of declared submodules that could not be read from disk, together with the
+    /// reason
+    unreadable_files: HashMap<RelativePathBuf, String>,
+    next_file_id: u32,
 }
 
 impl Driver {
@@ -35,6 +57,11 @@ impl Driver {
             db: CompilerDatabase::new(),
             out_dir: None,
             display_color: config.display_color,
+            message_format: config.message_format,
+            lint_levels: config.lint_levels,
+            source_dir: None,
+            unreadable_files: HashMap::new(),
+            next_file_id: 0,
         };
 
         // Move relevant configuration into the database
@@ -43,22 +70,25 @@ impl Driver {
             .db
             .set_context(Arc::new(mun_codegen::Context::create()));
         driver.db.set_optimization_lvl(config.optimization_lvl);
+        driver.db.set_debug_info(config.debug_info);
+        driver.db.set_source_dir(PathBuf::new());
+        driver
+            .db
+            .set_source_root(WORKSPACE, Arc::new(SourceRoot::default()));
 
         driver.out_dir = config.out_dir;
 
         driver
     }
 
-    /// Constructs a driver with a configuration and a single file.
+    /// Constructs a driver with a configuration and the root file of a package. If the file is
+    /// loaded from disk, the files of all declared submodules are loaded as well.
     pub fn with_file(
         config: Config,
         path: PathOrInline,
     ) -> Result<(Driver, FileId), failure::Error> {
         let mut driver = Driver::with_config(config);
 
-        // Construct a SourceRoot
-        let mut source_root = SourceRoot::default();
-
         // Get the path and contents of the path
         let (rel_path, text) = match path {
             PathOrInline::Path(p) => {
@@ -68,21 +98,25 @@ impl Driver {
                         "Input path is missing a filename.",
                     )
                 })?;
+                driver.source_dir = p.parent().map(Path::to_path_buf);
+                if let Some(source_dir) = &driver.source_dir {
+                    // Debuggers expect the source directory to be an absolute path
+                    driver
+                        .db
+                        .set_source_dir(std::env::current_dir()?.join(source_dir));
+                }
                 (
                     RelativePathBuf::from_path(filename).unwrap(),
-                    std::fs::read_to_string(p)?,
+                    std::fs::read_to_string(&p)?,
                 )
             }
             PathOrInline::Inline { rel_path, contents } => (rel_path, contents),
         };
 
         // Store the file information in the database together with the source root
-        let file_id = FileId(0);
-        driver.db.set_file_relative_path(file_id, rel_path.clone());
-        driver.db.set_file_text(file_id, Arc::new(text));
-        driver.db.set_file_source_root(file_id, WORKSPACE);
-        source_root.insert_file(rel_path, file_id);
-        driver.db.set_source_root(WORKSPACE, Arc::new(source_root));
+        let file_id = driver.add_file(rel_path, text);
+        driver.db.set_package_root(WORKSPACE, file_id);
+        driver.load_submodules();
 
         Ok((driver, file_id))
     }
@@ -94,6 +128,126 @@ impl Driver {
         self.db
             .set_file_text(file_id, Arc::new(text.as_ref().to_owned()));
     }
+
+    /// Adds a file with the specified path, relative to the root of the package, to the package.
+    pub fn add_file<T: AsRef<str>>(&mut self, rel_path: RelativePathBuf, text: T) -> FileId {
+        let mut source_root = (*self.db.source_root(WORKSPACE)).clone();
+        let file_id = self.insert_file(&mut source_root, rel_path, text);
+        self.db.set_source_root(WORKSPACE, Arc::new(source_root));
+        file_id
+    }
+
+    /// Stores a new file in the database and inserts it into `source_root`. The source root of the
+    /// package is not updated, which allows multiple files to be added at the cost of invalidating
+    /// the queries that depend on it only once.
+    fn insert_file<T: AsRef<str>>(
+        &mut self,
+        source_root: &mut SourceRoot,
+        rel_path: RelativePathBuf,
+        text: T,
+    ) -> FileId {
+        let file_id = FileId(self.next_file_id);
+        self.next_file_id += 1;
+
+        self.db.set_file_relative_path(file_id, rel_path.clone());
+        self.db
+            .set_file_text(file_id, Arc::new(text.as_ref().to_owned()));
+        self.db.set_file_source_root(file_id, WORKSPACE);
+        source_root.insert_file(rel_path, file_id);
+
+        file_id
+    }
+
+    /// Returns the file with the specified path, relative to the root of the package, if it is
+    /// part of the package.
+    pub fn file_id(&self, rel_path: &RelativePath) -> Option<FileId> {
+        self.db
+            .source_root(WORKSPACE)
+            .file_by_relative_path(rel_path)
+    }
+
+    /// Returns the paths, relative to the root of the package, of all files in the package.
+    pub fn file_paths(&self) -> Vec<RelativePathBuf> {
+        self.db
+            .source_root(WORKSPACE)
+            .files()
+            .map(|file_id| self.db.file_relative_path(file_id))
+            .collect()
+    }
+
+    /// Removes the file at `rel_path`, or all files in the directory at `rel_path`, from the
+    /// package. Returns whether any file was removed.
+    pub fn remove_path(&mut self, rel_path: &RelativePath) -> bool {
+        let files = self.files_at(rel_path);
+        if files.is_empty() {
+            return false;
+        }
+
+        let mut source_root = (*self.db.source_root(WORKSPACE)).clone();
+        for (_, file_path) in files {
+            source_root.remove_file(&file_path);
+        }
+        self.db.set_source_root(WORKSPACE, Arc::new(source_root));
+        true
+    }
+
+    /// Returns the files of the package that are located at `rel_path`, which is either the path
+    /// of a file or of a directory, together with their paths.
+    fn files_at(&self, rel_path: &RelativePath) -> Vec<(FileId, RelativePathBuf)> {
+        let dir_prefix = format!("{}/", rel_path.as_str());
+        self.db
+            .source_root(WORKSPACE)
+            .files()
+            .map(|file_id| (file_id, self.db.file_relative_path(file_id)))
+            .filter(|(_, file_path)| {
+                file_path.as_str() == rel_path.as_str()
+                    || file_path.as_str().starts_with(&dir_prefix)
+            })
+            .collect()
+    }
+
+    /// Returns the directory that contains the source files of the package, if it was loaded from
+    /// disk.
+    pub fn source_dir(&self) -> Option<&Path> {
+        self.source_dir.as_deref()
+    }
+
+    /// Loads the files of all declared submodules that are not yet part of the package from disk.
+    /// Files that do not exist or cannot be read are reported as diagnostics. This does nothing if
+    /// the package was not loaded from disk.
+    pub fn load_submodules(&mut self) {
+        let source_dir = match &self.source_dir {
+            Some(source_dir) => source_dir.clone(),
+            None => return,
+        };
+
+        // Loading a module can declare new submodules, so repeat until nothing new is found
+        self.unreadable_files.clear();
+        let package = Package::from(WORKSPACE);
+        loop {
+            let mut source_root = (*self.db.source_root(WORKSPACE)).clone();
+            let mut loaded_file = false;
+            for rel_path in package.unresolved_module_paths(&self.db) {
+                let path = rel_path.to_path(&source_dir);
+                if !path.is_file() || self.unreadable_files.contains_key(&rel_path) {
+                    continue;
+                }
+                match std::fs::read_to_string(&path) {
+                    Ok(text) => {
+                        self.insert_file(&mut source_root, rel_path, text);
+                        loaded_file = true;
+                    }
+                    Err(e) => {
+                        self.unreadable_files.insert(rel_path, e.to_string());
+                    }
+                }
+            }
+            if !loaded_file {
+                return;
+            }
+            self.db.set_source_root(WORKSPACE, Arc::new(source_root));
+        }
+    }
 }
 
 impl Driver {
@@ -102,22 +256,35 @@ impl Driver {
         self.db
             .source_root(WORKSPACE)
             .files()
-            .map(|f| diagnostics(&self.db, f))
+            .map(|f| diagnostics(&self.db, f, &self.lint_levels, &self.unreadable_files))
             .flatten()
+            .map(|diagnostic| diagnostic.snippet)
             .collect()
     }
 
-    /// Emits all diagnostic messages currently in the database; returns true if errors were
-    /// emitted.
+    /// Emits all diagnostic messages currently in the database in the configured `MessageFormat`;
+    /// returns true if errors were emitted.
     pub fn emit_diagnostics(
         &self,
         writer: &mut dyn std::io::Write,
+    ) -> Result<bool, failure::Error> {
+        match self.message_format {
+            MessageFormat::Human => self.emit_human_diagnostics(writer),
+            MessageFormat::Json => self.emit_json_diagnostics(writer),
+        }
+    }
+
+    /// Emits all diagnostic messages as annotated source code; returns true if errors were
+    /// emitted.
+    fn emit_human_diagnostics(
+        &self,
+        writer: &mut dyn std::io::Write,
     ) -> Result<bool, failure::Error> {
         let mut has_errors = false;
         let dlf = DisplayListFormatter::new(self.display_color.should_enable(), false);
         for file_id in self.db.source_root(WORKSPACE).files() {
-            let diags = diagnostics(&self.db, file_id);
-            for diagnostic in diags {
+            let diags = diagnostics(&self.db, file_id, &self.lint_levels, &self.unreadable_files);
+            for diagnostic in diags.into_iter().map(|diagnostic| diagnostic.snippet) {
                 let dl = DisplayList::from(diagnostic.clone());
                 writeln!(writer, "{}", dlf.format(&dl)).unwrap();
                 if let Some(annotation) = diagnostic.title {
@@ -129,6 +296,43 @@ impl Driver {
         }
         Ok(has_errors)
     }
+
+    /// Emits every diagnostic message as a JSON object on a separate line, followed by a summary
+    /// of the build; returns true if errors were emitted.
+    fn emit_json_diagnostics(
+        &self,
+        writer: &mut dyn std::io::Write,
+    ) -> Result<bool, failure::Error> {
+        let mut summary = BuildSummary::default();
+        for file_id in self.db.source_root(WORKSPACE).files() {
+            let source_text = self.db.file_text(file_id);
+            let line_index = self.db.line_index(file_id);
+            for diagnostic in
+                diagnostics(&self.db, file_id, &self.lint_levels, &self.unreadable_files)
+            {
+                if let Some(title) = &diagnostic.snippet.title {
+                    match title.annotation_type {
+                        AnnotationType::Error => summary.errors += 1,
+                        AnnotationType::Warning => summary.warnings += 1,
+                        _ => {}
+                    }
+                }
+                let message = diagnostics_json::Message::Diagnostic(
+                    diagnostics_json::Diagnostic::from_snippet(
+                        &diagnostic,
+                        &source_text,
+                        &line_index,
+                    ),
+                );
+                writeln!(writer, "{}", serde_json::to_string(&message)?)?;
+            }
+        }
+
+        let has_errors = summary.errors > 0;
+        let message = diagnostics_json::Message::Summary(summary);
+        writeln!(writer, "{}", serde_json::to_string(&message)?)?;
+        Ok(has_errors)
+    }
 }
 
 impl Driver {
@@ -139,3 +343,58 @@ impl Driver {
         obj_file.into_shared_object(self.out_dir.as_deref())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Config, DisplayColor, Driver, PathOrInline, RelativePathBuf};
+
+    #[test]
+    fn test_remove_module_file() {
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: "mod foo;\npub fn main() -> i32 { foo::value() }".to_owned(),
+        };
+        let (mut driver, _) = Driver::with_file(Config::default(), input).unwrap();
+        assert!(driver.emit_diagnostics(&mut Vec::new()).unwrap());
+
+        let foo_path = RelativePathBuf::from("foo.mun");
+        let foo = driver.add_file(foo_path.clone(), "pub fn value() -> i32 { 3 }");
+        assert_eq!(driver.file_id(&foo_path), Some(foo));
+        assert!(!driver.emit_diagnostics(&mut Vec::new()).unwrap());
+
+        assert!(driver.remove_path(&foo_path));
+        assert_eq!(driver.file_id(&foo_path), None);
+        assert!(!driver.remove_path(&foo_path));
+        assert!(driver.emit_diagnostics(&mut Vec::new()).unwrap());
+    }
+    #[test]
+    fn test_unreadable_module_file() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let main_path = source_dir.path().join("main.mun");
+        std::fs::write(
+            &main_path,
+            "mod foo;\nmod bar;\npub fn main() -> i32 { foo::value() + bar::value() }",
+        )
+        .unwrap();
+        std::fs::write(
+            source_dir.path().join("foo.mun"),
+            "pub fn value() -> i32 { 3 }",
+        )
+        .unwrap();
+        // Invalid UTF-8 cannot be read as source text
+        std::fs::write(source_dir.path().join("bar.mun"), b"\xff\xfe").unwrap();
+
+        let config = Config {
+            display_color: DisplayColor::Disable,
+            ..Config::default()
+        };
+        let (driver, _) = Driver::with_file(config, PathOrInline::Path(main_path)).unwrap();
+        assert!(driver.file_id(&RelativePathBuf::from("foo.mun")).is_some());
+        assert_eq!(driver.file_id(&RelativePathBuf::from("bar.mun")), None);
+
+        let mut output = Vec::new();
+        assert!(driver.emit_diagnostics(&mut output).unwrap());
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("could not read `bar.mun`"));
+    }
+}
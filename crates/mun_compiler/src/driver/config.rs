@@ -1,5 +1,6 @@
-use crate::DisplayColor;
+use crate::{DisplayColor, MessageFormat};
 pub use mun_codegen::OptimizationLevel;
+use mun_hir::lint::LintLevels;
 use mun_target::spec::Target;
 use std::path::PathBuf;
 
@@ -12,12 +13,22 @@ pub struct Config {
     /// The optimization level to use for the IR generation.
     pub optimization_lvl: OptimizationLevel,
 
+    /// Whether or not to generate debug information, which enables stepping through Mun code
+    /// with a native debugger.
+    pub debug_info: bool,
+
     /// The optional output directory to store all outputs. If no directory is specified all output
     /// is stored in a temporary directory.
     pub out_dir: Option<PathBuf>,
 
     /// Whether or not to use colors in terminal output
     pub display_color: DisplayColor,
+
+    /// The format in which diagnostics are emitted
+    pub message_format: MessageFormat,
+
+    /// The levels of the lints that warn about code that is probably wrong
+    pub lint_levels: LintLevels,
 }
 
 impl Default for Config {
@@ -28,8 +39,11 @@ impl Default for Config {
             // triple.
             target: target.unwrap(),
             optimization_lvl: OptimizationLevel::Default,
+            debug_info: false,
             out_dir: None,
             display_color: DisplayColor::Auto,
+            message_format: MessageFormat::Human,
+            lint_levels: LintLevels::default(),
         }
     }
 }
@@ -0,0 +1,8 @@
+/// The format in which diagnostics are emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageFormat {
+    /// Human-readable messages that annotate the relevant source code
+    Human,
+    /// One JSON object per line for every diagnostic, followed by a summary of the build
+    Json,
+}
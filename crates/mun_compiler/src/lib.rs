@@ -3,15 +3,17 @@ mod annotate;
 mod db;
 ///! This library contains the code required to go from source code to binaries.
 mod diagnostics;
+mod diagnostics_json;
 mod diagnostics_snippets;
 mod driver;
 
+pub use mun_hir::lint::{Lint, LintLevel, LintLevels, WARNINGS};
 pub use mun_hir::{FileId, RelativePath, RelativePathBuf};
 pub use mun_target::spec::Target;
 use std::path::{Path, PathBuf};
 
-pub use crate::driver::DisplayColor;
 pub use crate::driver::{Config, Driver};
+pub use crate::driver::{DisplayColor, MessageFormat};
 pub use annotate::{AnnotationBuilder, SliceBuilder, SnippetBuilder};
 pub use mun_codegen::OptimizationLevel;
 
@@ -1,26 +1,33 @@
+use std::path::Path;
 use std::sync::mpsc::channel;
 use std::time::Duration;
 
-use failure::Error;
-use mun_compiler::{CompilerOptions, Driver, PathOrInline};
-use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use failure::{format_err, Error};
+use mun_compiler::{CompilerOptions, Driver, PathOrInline, RelativePathBuf};
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
 
 use std::io::stderr;
 
 pub fn main(options: CompilerOptions) -> Result<(), Error> {
+    if let PathOrInline::Inline { .. } = &options.input {
+        return Err(format_err!("cannot watch an inline input file"));
+    }
+
+    let (mut driver, file_id) = Driver::with_file(options.config, options.input)?;
+
     // Need to canonicalize path to do comparisons
-    let input_path = match &options.input {
-        PathOrInline::Path(path) => path.canonicalize()?,
-        PathOrInline::Inline { .. } => panic!("cannot run compiler with inline path"),
-    };
+    let source_dir = driver
+        .source_dir()
+        .map(|dir| std::env::current_dir().map(|cwd| cwd.join(dir)))
+        .transpose()?
+        .ok_or_else(|| format_err!("the input file is not located in a directory"))?
+        .canonicalize()?;
 
     let (tx, rx) = channel();
 
     let mut watcher: RecommendedWatcher = Watcher::new(tx, Duration::from_millis(10))?;
-    watcher.watch(&input_path, RecursiveMode::NonRecursive)?;
-    println!("Watching: {}", input_path.display());
-
-    let (mut driver, file_id) = Driver::with_file(options.config, options.input)?;
+    watcher.watch(&source_dir, RecursiveMode::Recursive)?;
+    println!("Watching: {}", source_dir.display());
 
     // Compile at least once
     if !driver.emit_diagnostics(&mut stderr())? {
@@ -28,18 +35,171 @@ pub fn main(options: CompilerOptions) -> Result<(), Error> {
     }
 
     loop {
-        use notify::DebouncedEvent::*;
-        match rx.recv() {
-            Ok(Write(ref path)) | Ok(Create(ref path)) if path == &input_path => {
-                let contents = std::fs::read_to_string(path)?;
-                driver.set_file_text(file_id, &contents);
-                if !driver.emit_diagnostics(&mut stderr())? {
-                    driver.write_assembly(file_id)?;
-                    println!("Successfully compiled: {}", path.display())
+        // Saving a file can result in multiple events, so handle all pending events before
+        // recompiling
+        let mut events = match rx.recv() {
+            Ok(event) => vec![event],
+            Err(e) => return Err(e.into()),
+        };
+        events.extend(rx.try_iter());
+
+        let mut changed = false;
+        for event in events {
+            changed |= handle_event(&mut driver, &source_dir, event)?;
+        }
+
+        // Only the files that were changed are invalidated, so the intermediate representation of
+        // all other files is reused
+        if changed {
+            driver.load_submodules();
+            if !driver.emit_diagnostics(&mut stderr())? {
+                let assembly_path = driver.write_assembly(file_id)?;
+                println!("Successfully compiled: {}", assembly_path.display())
+            }
+        }
+    }
+}
+
+/// Applies a file system `event` in the `source_dir` to the files of the `driver`. Returns whether
+/// the package needs to be recompiled.
+fn handle_event(
+    driver: &mut Driver,
+    source_dir: &Path,
+    event: DebouncedEvent,
+) -> Result<bool, Error> {
+    match event {
+        DebouncedEvent::Write(path) => {
+            match mun_file_path(source_dir, &path).and_then(|rel_path| driver.file_id(&rel_path)) {
+                Some(file_id) => match read_file(&path) {
+                    Some(contents) => {
+                        driver.set_file_text(file_id, &contents);
+                        Ok(true)
+                    }
+                    None => Ok(false),
+                },
+                None => Ok(false),
+            }
+        }
+        // A new file is only added to the package if it is declared as a module, which is checked
+        // by `Driver::load_submodules`
+        DebouncedEvent::Create(path) => Ok(mun_file_path(source_dir, &path).is_some()),
+        DebouncedEvent::Remove(path) => Ok(relative_path(source_dir, &path)
+            .map_or(false, |rel_path| driver.remove_path(&rel_path))),
+        DebouncedEvent::Rename(from, to) => {
+            // The module path of a renamed file changes, so it is removed from the package and
+            // loaded again if its new path is declared as a module
+            let removed = relative_path(source_dir, &from)
+                .map_or(false, |rel_path| driver.remove_path(&rel_path));
+            // Editors that save atomically write to a temporary file that is then renamed to the
+            // saved file, which replaces the contents of a file that is already in the package
+            match mun_file_path(source_dir, &to).map(|rel_path| driver.file_id(&rel_path)) {
+                Some(Some(file_id)) => match read_file(&to) {
+                    Some(contents) => {
+                        driver.set_file_text(file_id, &contents);
+                        Ok(true)
+                    }
+                    None => Ok(removed),
+                },
+                Some(None) => Ok(true),
+                None => Ok(removed),
+            }
+        }
+        DebouncedEvent::Rescan => {
+            for rel_path in driver.file_paths() {
+                let path = rel_path.to_path(source_dir);
+                if path.is_file() {
+                    if let (Some(contents), Some(file_id)) =
+                        (read_file(&path), driver.file_id(&rel_path))
+                    {
+                        driver.set_file_text(file_id, &contents);
+                    }
+                } else {
+                    driver.remove_path(&rel_path);
                 }
             }
-            Ok(_) => {}
-            Err(e) => eprintln!("Watcher error: {:?}", e),
+            Ok(true)
         }
+        DebouncedEvent::Error(e, path) => {
+            match path {
+                Some(path) => eprintln!("Watcher error: {}: {}", path.display(), e),
+                None => eprintln!("Watcher error: {}", e),
+            }
+            Ok(false)
+        }
+        DebouncedEvent::NoticeWrite(_)
+        | DebouncedEvent::NoticeRemove(_)
+        | DebouncedEvent::Chmod(_) => Ok(false),
+    }
+}
+
+/// Returns the contents of the file at `path`. A file can be removed or replaced before its event
+/// is handled, in which case the error is printed and `None` is returned, so the file is skipped.
+fn read_file(path: &Path) -> Option<String> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Some(contents),
+        Err(e) => {
+            eprintln!("Could not read {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Returns the path of `path` relative to the `source_dir`, if it is located in the `source_dir`.
+fn relative_path(source_dir: &Path, path: &Path) -> Option<RelativePathBuf> {
+    path.strip_prefix(source_dir)
+        .ok()
+        .and_then(|path| RelativePathBuf::from_path(path).ok())
+}
+
+/// Returns the path of `path` relative to the `source_dir`, if it is a Mun source file in the
+/// `source_dir`.
+fn mun_file_path(source_dir: &Path, path: &Path) -> Option<RelativePathBuf> {
+    if path.extension().map_or(false, |ext| ext == "mun") {
+        relative_path(source_dir, path)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::handle_event;
+    use mun_compiler::{Config, Driver, PathOrInline, RelativePathBuf};
+    use notify::DebouncedEvent;
+
+    #[test]
+    fn test_file_removed_before_read() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source_dir = temp_dir.path().canonicalize().unwrap();
+        let main_path = source_dir.join("main.mun");
+        std::fs::write(
+            &main_path,
+            "mod foo;\npub fn main() -> i32 { foo::value() }",
+        )
+        .unwrap();
+        let foo_path = source_dir.join("foo.mun");
+        std::fs::write(&foo_path, "pub fn value() -> i32 { 3 }").unwrap();
+
+        let (mut driver, _) =
+            Driver::with_file(Config::default(), PathOrInline::Path(main_path)).unwrap();
+        assert!(driver.file_id(&RelativePathBuf::from("foo.mun")).is_some());
+
+        // The file disappears before its events are handled
+        std::fs::remove_file(&foo_path).unwrap();
+        assert!(!handle_event(
+            &mut driver,
+            &source_dir,
+            DebouncedEvent::Write(foo_path.clone())
+        )
+        .unwrap());
+        assert!(!handle_event(
+            &mut driver,
+            &source_dir,
+            DebouncedEvent::Rename(source_dir.join("foo.tmp"), foo_path)
+        )
+        .unwrap());
+
+        // The last contents that were read are kept until the removal of the file is handled
+        assert!(!driver.emit_diagnostics(&mut Vec::new()).unwrap());
     }
 }
@@ -1,12 +1,19 @@
-use std::{fmt, sync::Arc};
+use std::{collections::HashSet, fmt, sync::Arc};
 
+use crate::diagnostics::{
+    DiagnosticSink, DuplicateRenamedFrom, InvalidRenamedFrom, MultipleRenamedFrom,
+    RenamedFromExistingField,
+};
+use crate::in_file::InFile;
 use crate::type_ref::{TypeRefBuilder, TypeRefId, TypeRefMap, TypeRefSourceMap};
 use crate::{
     arena::{Arena, RawId},
-    ids::{AstItemDef, StructId},
-    AsName, DefDatabase, Name,
+    expr::{string_lit, Literal},
+    ids::{AstItemDef, EnumId, StructId},
+    AsName, DefDatabase, FileId, Name, Visibility,
 };
-use mun_syntax::ast::{self, NameOwner, TypeAscriptionOwner};
+use mun_syntax::ast::{self, AttrsOwner, NameOwner, TypeAscriptionOwner, VisibilityOwner};
+use mun_syntax::{AstNode, SyntaxNodePtr};
 
 pub use mun_syntax::ast::StructMemoryKind;
 
@@ -26,6 +33,8 @@ pub use mun_syntax::ast::StructMemoryKind;
 pub struct StructFieldData {
     pub name: Name,
     pub type_ref: TypeRefId,
+    /// The previous name of the field, if it was declared with `#[renamed_from("..")]`
+    pub renamed_from: Option<String>,
 }
 
 /// An identifier for a struct's or tuple's field
@@ -54,6 +63,7 @@ impl fmt::Display for StructKind {
 #[derive(Debug, PartialEq, Eq)]
 pub struct StructData {
     pub name: Name,
+    pub visibility: Visibility,
     pub fields: Arena<StructFieldId, StructFieldData>,
     pub kind: StructKind,
     pub memory_kind: StructMemoryKind,
@@ -70,6 +80,12 @@ impl StructData {
             .map(|n| n.as_name())
             .unwrap_or_else(Name::missing);
 
+        let visibility = src
+            .value
+            .visibility()
+            .map(|_v| Visibility::Public)
+            .unwrap_or(Visibility::Private);
+
         let memory_kind = src
             .value
             .memory_type_specifier()
@@ -77,34 +93,12 @@ impl StructData {
             .unwrap_or_default();
 
         let mut type_ref_builder = TypeRefBuilder::default();
-        let (fields, kind) = match src.value.kind() {
-            ast::StructKind::Record(r) => {
-                let fields = r
-                    .fields()
-                    .map(|fd| StructFieldData {
-                        name: fd.name().map(|n| n.as_name()).unwrap_or_else(Name::missing),
-                        type_ref: type_ref_builder.alloc_from_node_opt(fd.ascribed_type().as_ref()),
-                    })
-                    .collect();
-                (fields, StructKind::Record)
-            }
-            ast::StructKind::Tuple(t) => {
-                let fields = t
-                    .fields()
-                    .enumerate()
-                    .map(|(index, fd)| StructFieldData {
-                        name: Name::new_tuple_field(index),
-                        type_ref: type_ref_builder.alloc_from_node_opt(fd.type_ref().as_ref()),
-                    })
-                    .collect();
-                (fields, StructKind::Tuple)
-            }
-            ast::StructKind::Unit => (Arena::default(), StructKind::Unit),
-        };
+        let (fields, kind) = lower_fields(&mut type_ref_builder, src.value.kind());
 
         let (type_ref_map, type_ref_source_map) = type_ref_builder.finish();
         Arc::new(StructData {
             name,
+            visibility,
             fields,
             kind,
             memory_kind,
@@ -121,3 +115,196 @@ impl StructData {
         &self.type_ref_map
     }
 }
+
+/// Lowers the fields of a struct or enum variant.
+fn lower_fields(
+    type_ref_builder: &mut TypeRefBuilder,
+    kind: ast::StructKind,
+) -> (Arena<StructFieldId, StructFieldData>, StructKind) {
+    match kind {
+        ast::StructKind::Record(r) => {
+            let fields = r
+                .fields()
+                .map(|fd| StructFieldData {
+                    name: fd.name().map(|n| n.as_name()).unwrap_or_else(Name::missing),
+                    type_ref: type_ref_builder.alloc_from_node_opt(fd.ascribed_type().as_ref()),
+                    renamed_from: renamed_from(&fd),
+                })
+                .collect();
+            (fields, StructKind::Record)
+        }
+        ast::StructKind::Tuple(t) => {
+            let fields = t
+                .fields()
+                .enumerate()
+                .map(|(index, fd)| StructFieldData {
+                    name: Name::new_tuple_field(index),
+                    type_ref: type_ref_builder.alloc_from_node_opt(fd.type_ref().as_ref()),
+                    renamed_from: None,
+                })
+                .collect();
+            (fields, StructKind::Tuple)
+        }
+        ast::StructKind::Unit => (Arena::default(), StructKind::Unit),
+    }
+}
+
+/// Returns the previous name of a field that is declared with a `#[renamed_from("..")]` attribute.
+fn renamed_from(field: &ast::RecordFieldDef) -> Option<String> {
+    renamed_from_attrs(field)
+        .next()
+        .and_then(|attr| renamed_from_arg(&attr))
+}
+
+/// Returns the `renamed_from` attributes of a field.
+fn renamed_from_attrs(field: &ast::RecordFieldDef) -> impl Iterator<Item = ast::Attr> {
+    field.attrs().filter(|attr| {
+        attr.name_ref()
+            .map_or(false, |name| name.text() == "renamed_from")
+    })
+}
+
+/// Returns the previous name declared by a `renamed_from` attribute, or `None` if its argument is
+/// not a single, valid string literal.
+fn renamed_from_arg(attr: &ast::Attr) -> Option<String> {
+    let args = attr.attr_arg_list()?;
+    if args.syntax().children().count() != 1 {
+        return None;
+    }
+    let literal = args.literals().next()?;
+    match string_lit(literal.token().text()) {
+        (Literal::String(name), ref errors) if errors.is_empty() => Some(name),
+        _ => None,
+    }
+}
+
+/// Validates the `renamed_from` attributes of the fields of a struct. Each field can be renamed
+/// from a single name that is not used by another field, and every name can only be renamed once.
+pub(crate) fn validate_renamed_from(
+    file_id: FileId,
+    fields: &[ast::RecordFieldDef],
+    sink: &mut DiagnosticSink,
+) {
+    let mut renamed = HashSet::new();
+    for field in fields {
+        for (idx, attr) in renamed_from_attrs(field).enumerate() {
+            let attr_ptr = InFile::new(file_id, SyntaxNodePtr::new(attr.syntax()));
+            if idx > 0 {
+                sink.push(MultipleRenamedFrom { attr: attr_ptr });
+                continue;
+            }
+
+            let name = match renamed_from_arg(&attr) {
+                Some(name) => name,
+                None => {
+                    sink.push(InvalidRenamedFrom { attr: attr_ptr });
+                    continue;
+                }
+            };
+            let is_existing_field = fields.iter().any(|field| {
+                field
+                    .name()
+                    .map_or(false, |field_name| field_name.text() == name.as_str())
+            });
+            if is_existing_field {
+                sink.push(RenamedFromExistingField {
+                    attr: attr_ptr,
+                    name,
+                });
+            } else if !renamed.insert(name.clone()) {
+                sink.push(DuplicateRenamedFrom {
+                    attr: attr_ptr,
+                    name,
+                });
+            }
+        }
+    }
+}
+
+/// A single variant of an enum
+/// ```mun
+/// enum Shape {
+///     Circle(f32), // <- this
+/// }
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct EnumVariantData {
+    pub name: Name,
+    pub fields: Arena<StructFieldId, StructFieldData>,
+    pub kind: StructKind,
+}
+
+/// An identifier for an enum's variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EnumVariantId(RawId);
+impl_arena_id!(EnumVariantId);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct EnumData {
+    pub name: Name,
+    pub visibility: Visibility,
+    pub variants: Arena<EnumVariantId, EnumVariantData>,
+    pub memory_kind: StructMemoryKind,
+    type_ref_map: TypeRefMap,
+    type_ref_source_map: TypeRefSourceMap,
+}
+
+impl EnumData {
+    pub(crate) fn enum_data_query(db: &impl DefDatabase, id: EnumId) -> Arc<EnumData> {
+        let src = id.source(db);
+        let name = src
+            .value
+            .name()
+            .map(|n| n.as_name())
+            .unwrap_or_else(Name::missing);
+
+        let visibility = src
+            .value
+            .visibility()
+            .map(|_v| Visibility::Public)
+            .unwrap_or(Visibility::Private);
+
+        let memory_kind = src
+            .value
+            .memory_type_specifier()
+            .map(|s| s.kind())
+            .unwrap_or_default();
+
+        let mut type_ref_builder = TypeRefBuilder::default();
+        let variants = src
+            .value
+            .enum_variant_list()
+            .into_iter()
+            .flat_map(|list| list.variants())
+            .map(|variant| {
+                let (fields, kind) = lower_fields(&mut type_ref_builder, variant.kind());
+                EnumVariantData {
+                    name: variant
+                        .name()
+                        .map(|n| n.as_name())
+                        .unwrap_or_else(Name::missing),
+                    fields,
+                    kind,
+                }
+            })
+            .collect();
+
+        let (type_ref_map, type_ref_source_map) = type_ref_builder.finish();
+        Arc::new(EnumData {
+            name,
+            visibility,
+            variants,
+            memory_kind,
+            type_ref_map,
+            type_ref_source_map,
+        })
+    }
+
+    pub fn type_ref_source_map(&self) -> &TypeRefSourceMap {
+        &self.type_ref_source_map
+    }
+
+    pub fn type_ref_map(&self) -> &TypeRefMap {
+        &self.type_ref_map
+    }
+}
@@ -0,0 +1,26 @@
+use crate::name::{name, Name};
+use std::fmt;
+
+/// A function that is built into the language, e.g. `panic`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuiltinFunction {
+    /// Aborts the execution of Mun code with the specified message, e.g. `panic("oops")`.
+    Panic,
+}
+
+impl BuiltinFunction {
+    pub const ALL: &'static [(Name, BuiltinFunction)] = &[(name![panic], BuiltinFunction::Panic)];
+
+    /// Returns the name of the builtin function.
+    pub fn name(self) -> Name {
+        match self {
+            BuiltinFunction::Panic => name![panic],
+        }
+    }
+}
+
+impl fmt::Display for BuiltinFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.name(), f)
+    }
+}
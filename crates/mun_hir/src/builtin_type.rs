@@ -45,6 +45,7 @@ pub enum BuiltinType {
     Float(BuiltinFloat),
     Int(BuiltinInt),
     Bool,
+    String,
 }
 
 impl BuiltinType {
@@ -68,6 +69,8 @@ impl BuiltinType {
 
         (name![f32],   BuiltinType::Float(BuiltinFloat::F32)),
         (name![f64],   BuiltinType::Float(BuiltinFloat::F64)),
+
+        (name![string], BuiltinType::String),
     ];
 }
 
@@ -75,6 +78,7 @@ impl fmt::Display for BuiltinType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let type_name = match self {
             BuiltinType::Bool => "bool",
+            BuiltinType::String => "string",
             BuiltinType::Int(BuiltinInt {
                 signedness,
                 bitness,
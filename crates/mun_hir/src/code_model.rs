@@ -1,24 +1,34 @@
 pub(crate) mod src;
 
 use self::src::HasSource;
-use crate::adt::{StructData, StructFieldId};
+use crate::adt::{self, EnumData, EnumVariantId, StructData, StructFieldId, StructKind};
+use crate::builtin_function::BuiltinFunction;
 use crate::builtin_type::BuiltinType;
 use crate::code_model::diagnostics::ModuleDefinitionDiagnostic;
-use crate::diagnostics::DiagnosticSink;
+use crate::diagnostics::{DiagnosticSink, DuplicateDefinition, InvalidGlobalType};
+use crate::expr::const_eval::{eval_body, ConstValue};
 use crate::expr::validator::ExprValidator;
 use crate::expr::{Body, BodySourceMap};
+use crate::generics::GenericParams;
+use crate::global::{ConstData, StaticData};
 use crate::ids::AstItemDef;
 use crate::ids::LocationCtx;
+use crate::impl_block::ImplData;
+use crate::input::SourceRootId;
+use crate::line_index::LineCol;
+use crate::module_tree::{ModuleTreeDiagnostic, ModuleTreeNode};
 use crate::name_resolution::Namespace;
 use crate::raw::{DefKind, RawFileItem};
 use crate::resolve::{Resolution, Resolver};
+use crate::traits::TraitData;
 use crate::ty::{lower::LowerBatchResult, InferenceResult};
 use crate::type_ref::{TypeRefBuilder, TypeRefId, TypeRefMap, TypeRefSourceMap};
 use crate::{
-    ids::{FunctionId, StructId},
-    AsName, DefDatabase, FileId, HirDatabase, Name, Ty,
+    ids::{ConstId, EnumId, FunctionId, ImplId, StaticId, StructId, TraitId},
+    ty_app, AsName, DefDatabase, FileId, HirDatabase, Name, RelativePathBuf, Ty, TypeCtor,
 };
 use mun_syntax::ast::{ExternOwner, NameOwner, TypeAscriptionOwner, VisibilityOwner};
+use mun_syntax::{ast, AstNode, AstPtr, SyntaxNodePtr};
 use rustc_hash::FxHashMap;
 use std::sync::Arc;
 
@@ -38,33 +48,195 @@ impl Module {
         self.file_id
     }
 
-    /// Returns all the definitions declared in this module.
+    /// Returns the package this module is part of.
+    pub fn package(self, db: &impl DefDatabase) -> Package {
+        Package {
+            source_root: db.file_source_root(self.file_id),
+        }
+    }
+
+    /// Returns the name of this module or `None` if this is the root module of a package.
+    pub fn name(self, db: &impl DefDatabase) -> Option<Name> {
+        self.tree_node(db, |node| node.name.clone())
+    }
+
+    /// Returns the module that declares this module or `None` if this is the root module of a
+    /// package.
+    pub fn parent(self, db: &impl DefDatabase) -> Option<Module> {
+        self.tree_node(db, |node| node.parent.map(Module::from))
+    }
+
+    /// Returns all the submodules of this module in declaration order.
+    pub fn children(self, db: &impl DefDatabase) -> Vec<Module> {
+        self.tree_node(db, |node| {
+            Some(
+                node.children
+                    .iter()
+                    .map(|(_, file_id)| Module::from(*file_id))
+                    .collect(),
+            )
+        })
+        .unwrap_or_default()
+    }
+
+    /// Returns the names of all the modules from the root of the package to this module, e.g.
+    /// `["physics", "body"]` for the module `package::physics::body`.
+    pub fn path(self, db: &impl DefDatabase) -> Vec<Name> {
+        let mut path = Vec::new();
+        let mut module = Some(self);
+        while let Some(m) = module {
+            if let Some(name) = m.name(db) {
+                path.push(name);
+            }
+            module = m.parent(db);
+        }
+        path.reverse();
+        path
+    }
+
+    /// Returns the visibility of the `mod` declaration of this module.
+    pub fn visibility(self, db: &impl DefDatabase) -> Visibility {
+        let tree = db.module_tree(db.file_source_root(self.file_id));
+        let declaration = tree
+            .get(self.file_id)
+            .and_then(|node| Some((node.parent?, node.declaration?)));
+        match declaration {
+            Some((parent, declaration)) => declaration
+                .with_file_id(parent)
+                .to_node(db)
+                .visibility()
+                .map(|_v| Visibility::Public)
+                .unwrap_or(Visibility::Private),
+            None => Visibility::Public,
+        }
+    }
+
+    /// Returns the fully qualified name of an item with the given `name` declared in this module,
+    /// e.g. `physics::step`. Items of the root module of a package are not qualified.
+    pub(crate) fn qualified_name(self, db: &impl DefDatabase, name: &Name) -> String {
+        let mut qualified_name = String::new();
+        for module_name in self.path(db) {
+            qualified_name.push_str(&module_name.to_string());
+            qualified_name.push_str("::");
+        }
+        qualified_name.push_str(&name.to_string());
+        qualified_name
+    }
+
+    /// Returns all the definitions declared in this module, including the functions that are
+    /// defined in its `impl` blocks.
     pub fn declarations(self, db: &impl HirDatabase) -> Vec<ModuleDef> {
-        db.module_data(self.file_id).definitions.clone()
+        let data = db.module_data(self.file_id);
+        let mut declarations = data.definitions.clone();
+        for impl_block in data.impls.iter() {
+            declarations.extend(impl_block.items(db).into_iter().map(ModuleDef::Function));
+        }
+        declarations
+    }
+
+    /// Returns all the `impl` blocks declared in this module.
+    pub fn impl_blocks(self, db: &impl HirDatabase) -> Vec<ImplBlock> {
+        db.module_data(self.file_id).impls.clone()
     }
 
     fn resolver(self, _db: &impl DefDatabase) -> Resolver {
         Resolver::default().push_module_scope(self.file_id)
     }
 
+    /// Calls `f` with the node of this module in the module tree of its package, if the module is
+    /// part of the tree.
+    fn tree_node<T>(
+        self,
+        db: &impl DefDatabase,
+        f: impl FnOnce(&ModuleTreeNode) -> Option<T>,
+    ) -> Option<T> {
+        let tree = db.module_tree(db.file_source_root(self.file_id));
+        tree.get(self.file_id).and_then(f)
+    }
+
     pub fn diagnostics(self, db: &impl HirDatabase, sink: &mut DiagnosticSink) {
         for diag in db.module_data(self.file_id).diagnostics.iter() {
             diag.add_to(db, self, sink);
         }
+        let package = self.package(db);
+        for diag in db.module_tree(package.source_root).diagnostics() {
+            diag.add_to(db, self, sink);
+        }
+        for diag in db.package_defs(package.source_root).diagnostics() {
+            diag.add_to(db, self, sink);
+        }
+        for diag in db.package_impls(package.source_root).diagnostics() {
+            diag.add_to(db, self, sink);
+        }
+        for impl_block in self.impl_blocks(db) {
+            impl_block.diagnostics(db, sink);
+        }
         for decl in self.declarations(db) {
             #[allow(clippy::single_match)]
             match decl {
                 ModuleDef::Function(f) => f.diagnostics(db, sink),
                 ModuleDef::Struct(s) => s.diagnostics(db, sink),
+                ModuleDef::Enum(e) => e.diagnostics(db, sink),
+                ModuleDef::Trait(t) => t.diagnostics(db, sink),
+                ModuleDef::Const(c) => c.diagnostics(db, sink),
+                ModuleDef::Static(s) => s.diagnostics(db, sink),
                 _ => (),
             }
         }
     }
 }
 
+/// A package is a tree of modules that is compiled into a single assembly. A package consists of
+/// all the modules in a source root that are reachable from the root module of the source root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Package {
+    pub(crate) source_root: SourceRootId,
+}
+
+impl From<SourceRootId> for Package {
+    fn from(source_root: SourceRootId) -> Self {
+        Package { source_root }
+    }
+}
+
+impl Package {
+    pub fn source_root(self) -> SourceRootId {
+        self.source_root
+    }
+
+    /// Returns the root module of the package.
+    pub fn root_module(self, db: &impl DefDatabase) -> Module {
+        Module::from(db.module_tree(self.source_root).root())
+    }
+
+    /// Returns all the modules of the package, ordered by `FileId`.
+    pub fn modules(self, db: &impl DefDatabase) -> Vec<Module> {
+        let mut modules: Vec<Module> = db
+            .module_tree(self.source_root)
+            .files()
+            .map(Module::from)
+            .collect();
+        modules.sort_by_key(|m| m.file_id);
+        modules
+    }
+
+    /// Returns the relative paths of the files of all declared modules that could not be found in
+    /// the source root of the package.
+    pub fn unresolved_module_paths(self, db: &impl DefDatabase) -> Vec<RelativePathBuf> {
+        db.module_tree(self.source_root)
+            .diagnostics()
+            .iter()
+            .map(|diag| match diag {
+                ModuleTreeDiagnostic::UnresolvedModule { candidate, .. } => candidate.clone(),
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Default)]
 pub struct ModuleData {
     definitions: Vec<ModuleDef>,
+    impls: Vec<ImplBlock>,
     diagnostics: Vec<ModuleDefinitionDiagnostic>,
 }
 
@@ -94,6 +266,8 @@ impl ModuleData {
                         definition_by_name.insert(items[*def].name.clone(), *def);
                     }
                     match items[*def].kind {
+                        // Modules are resolved by the module tree
+                        DefKind::Module(_) => (),
                         DefKind::Function(ast_id) => {
                             data.definitions.push(ModuleDef::Function(Function {
                                 id: FunctionId::from_ast_id(loc_ctx, ast_id),
@@ -104,8 +278,26 @@ impl ModuleData {
                                 id: StructId::from_ast_id(loc_ctx, ast_id),
                             }))
                         }
+                        DefKind::Enum(ast_id) => data.definitions.push(ModuleDef::Enum(Enum {
+                            id: EnumId::from_ast_id(loc_ctx, ast_id),
+                        })),
+                        DefKind::Trait(ast_id) => data.definitions.push(ModuleDef::Trait(Trait {
+                            id: TraitId::from_ast_id(loc_ctx, ast_id),
+                        })),
+                        DefKind::Const(ast_id) => data.definitions.push(ModuleDef::Const(Const {
+                            id: ConstId::from_ast_id(loc_ctx, ast_id),
+                        })),
+                        DefKind::Static(ast_id) => {
+                            data.definitions.push(ModuleDef::Static(Static {
+                                id: StaticId::from_ast_id(loc_ctx, ast_id),
+                            }))
+                        }
                     }
                 }
+                RawFileItem::Impl(ast_id) => data.impls.push(ImplBlock {
+                    id: ImplId::from_ast_id(loc_ctx, *ast_id),
+                }),
+                RawFileItem::Import(_) => (),
             };
         }
         Arc::new(data)
@@ -118,9 +310,22 @@ impl ModuleData {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ModuleDef {
+    Module(Module),
     Function(Function),
+    BuiltinFunction(BuiltinFunction),
     BuiltinType(BuiltinType),
     Struct(Struct),
+    Enum(Enum),
+    EnumVariant(EnumVariant),
+    Trait(Trait),
+    Const(Const),
+    Static(Static),
+}
+
+impl From<Module> for ModuleDef {
+    fn from(t: Module) -> Self {
+        ModuleDef::Module(t)
+    }
 }
 
 impl From<Function> for ModuleDef {
@@ -129,6 +334,12 @@ impl From<Function> for ModuleDef {
     }
 }
 
+impl From<BuiltinFunction> for ModuleDef {
+    fn from(t: BuiltinFunction) -> Self {
+        ModuleDef::BuiltinFunction(t)
+    }
+}
+
 impl From<BuiltinType> for ModuleDef {
     fn from(t: BuiltinType) -> Self {
         ModuleDef::BuiltinType(t)
@@ -141,12 +352,44 @@ impl From<Struct> for ModuleDef {
     }
 }
 
+impl From<Enum> for ModuleDef {
+    fn from(t: Enum) -> Self {
+        ModuleDef::Enum(t)
+    }
+}
+
+impl From<EnumVariant> for ModuleDef {
+    fn from(t: EnumVariant) -> Self {
+        ModuleDef::EnumVariant(t)
+    }
+}
+
+impl From<Trait> for ModuleDef {
+    fn from(t: Trait) -> Self {
+        ModuleDef::Trait(t)
+    }
+}
+
+impl From<Const> for ModuleDef {
+    fn from(t: Const) -> Self {
+        ModuleDef::Const(t)
+    }
+}
+
+impl From<Static> for ModuleDef {
+    fn from(t: Static) -> Self {
+        ModuleDef::Static(t)
+    }
+}
+
 /// The definitions that have a body.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DefWithBody {
     Function(Function),
+    Const(Const),
+    Static(Static),
 }
-impl_froms!(DefWithBody: Function);
+impl_froms!(DefWithBody: Function, Const, Static);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Visibility {
@@ -167,10 +410,53 @@ impl DefWithBody {
         db.body_with_source_map(self).1
     }
 
+    pub fn module(self, db: &impl DefDatabase) -> Module {
+        match self {
+            DefWithBody::Function(f) => f.module(db),
+            DefWithBody::Const(c) => c.module(db),
+            DefWithBody::Static(s) => s.module(db),
+        }
+    }
+
     /// Builds a `Resolver` for code inside this item. A `Resolver` enables name resolution.
     pub(crate) fn resolver(self, db: &impl HirDatabase) -> Resolver {
         match self {
             DefWithBody::Function(f) => f.resolver(db),
+            DefWithBody::Const(c) => c.resolver(db),
+            DefWithBody::Static(s) => s.resolver(db),
+        }
+    }
+
+    /// Adds the diagnostics of the initializer of a constant or a global variable to the `sink`.
+    /// The initializer has to be evaluated at compile time, which is only supported for boolean,
+    /// integer and floating-point types.
+    fn global_diagnostics(
+        self,
+        db: &impl HirDatabase,
+        ty: Ty,
+        type_ref: Option<AstPtr<ast::TypeRef>>,
+        sink: &mut DiagnosticSink,
+    ) {
+        let body = self.body(db);
+        body.add_diagnostics(db, self, sink);
+        let infer = self.infer(db);
+        infer.add_diagnostics(db, self, sink);
+
+        match ty {
+            ty_app!(TypeCtor::Bool) | ty_app!(TypeCtor::Int(_)) | ty_app!(TypeCtor::Float(_)) => {
+                if let Err(err) = eval_body(db, self) {
+                    err.add_to(db, self, sink);
+                }
+            }
+            Ty::Unknown => {}
+            _ => {
+                if let Some(type_ref) = type_ref {
+                    sink.push(InvalidGlobalType {
+                        file: self.module(db).file_id,
+                        type_ref,
+                    })
+                }
+            }
         }
     }
 }
@@ -232,6 +518,9 @@ pub struct FnData {
     type_ref_map: TypeRefMap,
     type_ref_source_map: TypeRefSourceMap,
     is_extern: bool,
+    impl_block: Option<ImplBlock>,
+    trait_def: Option<Trait>,
+    has_self_param: bool,
 }
 
 impl FnData {
@@ -250,8 +539,27 @@ impl FnData {
             .map(|_v| Visibility::Public)
             .unwrap_or(Visibility::Private);
 
+        let impl_block = src.value.impl_block().map(|impl_block| ImplBlock {
+            id: ImplId::from_ast(LocationCtx::new(db, src.file_id), &impl_block),
+        });
+        let trait_def = src.value.trait_def().map(|trait_def| Trait {
+            id: TraitId::from_ast(LocationCtx::new(db, src.file_id), &trait_def),
+        });
+
         let mut params = Vec::new();
+        let mut has_self_param = false;
         if let Some(param_list) = src.value.param_list() {
+            if param_list.self_param().is_some() {
+                // A `self` parameter outside of an `impl` block or a trait is reported by the
+                // `ExprValidator`
+                let type_ref = if impl_block.is_some() || trait_def.is_some() {
+                    type_ref_builder.self_type()
+                } else {
+                    type_ref_builder.error()
+                };
+                params.push(type_ref);
+                has_self_param = true;
+            }
             for param in param_list.params() {
                 let type_ref = type_ref_builder.alloc_from_node_opt(param.ascribed_type().as_ref());
                 params.push(type_ref);
@@ -276,6 +584,9 @@ impl FnData {
             type_ref_map,
             type_ref_source_map,
             is_extern,
+            impl_block,
+            trait_def,
+            has_self_param,
         })
     }
 
@@ -283,10 +594,17 @@ impl FnData {
         &self.name
     }
 
+    /// Returns the types of the parameters. If the function has a `self` parameter, its type is
+    /// the first one.
     pub fn params(&self) -> &[TypeRefId] {
         &self.params
     }
 
+    /// Returns true if the function has a `self` parameter, which makes it a method.
+    pub fn has_self_param(&self) -> bool {
+        self.has_self_param
+    }
+
     pub fn visibility(&self) -> Visibility {
         self.visibility
     }
@@ -315,8 +633,55 @@ impl Function {
         self.data(db).name.clone()
     }
 
+    /// Returns the name of the function, qualified with the path of its module, e.g.
+    /// `physics::step`. Functions of `impl` blocks are also qualified with the name of their type,
+    /// e.g. `physics::Body::step`, and functions of traits with the name of their trait, e.g.
+    /// `physics::Update::update`.
+    pub fn full_name(self, db: &impl HirDatabase) -> String {
+        let target_name = self
+            .impl_block(db)
+            .and_then(|impl_block| {
+                let ty = impl_block.target_ty(db);
+                ty.as_struct()
+                    .map(|s| s.full_name(db))
+                    .or_else(|| ty.as_enum().map(|e| e.full_name(db)))
+            })
+            .or_else(|| self.trait_def(db).map(|t| t.full_name(db)));
+        match target_name {
+            Some(target_name) => format!("{}::{}", target_name, self.name(db)),
+            None => self.module(db).qualified_name(db, &self.name(db)),
+        }
+    }
+
+    /// Returns the `impl` block in which the function is defined, if any.
+    pub fn impl_block(self, db: &impl HirDatabase) -> Option<ImplBlock> {
+        self.data(db).impl_block
+    }
+
+    /// Returns the trait in which the function is declared, if any.
+    pub fn trait_def(self, db: &impl HirDatabase) -> Option<Trait> {
+        self.data(db).trait_def
+    }
+
+    /// Returns the visibility of the function. The functions of a trait and of the `impl` blocks
+    /// that implement it share the visibility of the trait.
     pub fn visibility(self, db: &impl HirDatabase) -> Visibility {
-        self.data(db).visibility()
+        let trait_def = self.trait_def(db).or_else(|| {
+            self.impl_block(db)
+                .and_then(|impl_block| impl_block.target_trait(db))
+        });
+        match trait_def {
+            Some(trait_def) => trait_def.visibility(db),
+            None => self.data(db).visibility(),
+        }
+    }
+
+    /// Returns the function that implements this trait function for the type `self_ty`, if this
+    /// function is declared in a trait and the trait is implemented for `self_ty`.
+    pub fn trait_impl_function(self, db: &impl HirDatabase, self_ty: &Ty) -> Option<Function> {
+        let package = self.module(db).package(db);
+        db.package_impls(package.source_root)
+            .lookup_trait_function(db, self, self_ty)
     }
 
     pub fn data(self, db: &impl HirDatabase) -> Arc<FnData> {
@@ -327,6 +692,18 @@ impl Function {
         db.body(self.into())
     }
 
+    pub fn body_source_map(self, db: &impl HirDatabase) -> Arc<BodySourceMap> {
+        db.body_with_source_map(self.into()).1
+    }
+
+    /// Returns the (zero-based) line and column in its source file at which the function is
+    /// declared.
+    pub fn line_col(self, db: &impl HirDatabase) -> LineCol {
+        let src = self.source(db);
+        let range = SyntaxNodePtr::new(src.value.syntax()).range();
+        db.line_index(src.file_id).line_col(range.start())
+    }
+
     pub fn ty(self, db: &impl HirDatabase) -> Ty {
         db.type_for_def(self.into(), Namespace::Values)
     }
@@ -343,16 +720,26 @@ impl Function {
         db.body_with_source_map(self.into()).1
     }
 
+    /// Returns the type parameters of the function, e.g. `T` in `fn max<T>(a: T, b: T) -> T`.
+    pub fn generic_params(self, db: &impl DefDatabase) -> Arc<GenericParams> {
+        db.generic_params(self.into())
+    }
+
     pub(crate) fn resolver(self, db: &impl HirDatabase) -> Resolver {
         // take the outer scope...
-        self.module(db).resolver(db)
+        let resolver = self.module(db).resolver(db);
+        let resolver = match self.impl_block(db) {
+            Some(impl_block) => resolver.push_impl_block_scope(impl_block),
+            None => resolver,
+        };
+        resolver.push_generic_params_scope(self.generic_params(db))
     }
 
     pub fn diagnostics(self, db: &impl HirDatabase, sink: &mut DiagnosticSink) {
         let body = self.body(db);
         body.add_diagnostics(db, self.into(), sink);
         let infer = self.infer(db);
-        infer.add_diagnostics(db, self, sink);
+        infer.add_diagnostics(db, self.into(), sink);
         let validator = ExprValidator::new(self, db);
         validator.validate_body(sink);
     }
@@ -381,6 +768,11 @@ impl StructField {
         self.parent.data(db).fields[self.id].name.clone()
     }
 
+    /// Returns the previous name of the field, if it was declared with `#[renamed_from("..")]`.
+    pub fn renamed_from(self, db: &impl HirDatabase) -> Option<String> {
+        self.parent.data(db).fields[self.id].renamed_from.clone()
+    }
+
     pub fn id(self) -> StructFieldId {
         self.id
     }
@@ -401,6 +793,16 @@ impl Struct {
         self.data(db).name.clone()
     }
 
+    /// Returns the name of the struct, qualified with the path of its module, e.g.
+    /// `physics::Body`.
+    pub fn full_name(self, db: &impl DefDatabase) -> String {
+        self.module(db).qualified_name(db, &self.name(db))
+    }
+
+    pub fn visibility(self, db: &impl DefDatabase) -> Visibility {
+        self.data(db).visibility
+    }
+
     pub fn fields(self, db: &impl HirDatabase) -> Vec<StructField> {
         self.data(db)
             .fields
@@ -425,6 +827,229 @@ impl Struct {
         db.lower_struct(self)
     }
 
+    /// Returns the type parameters of the struct, e.g. `A` and `B` in `struct Pair<A, B>`.
+    pub fn generic_params(self, db: &impl DefDatabase) -> Arc<GenericParams> {
+        db.generic_params(self.into())
+    }
+
+    pub(crate) fn resolver(self, db: &impl HirDatabase) -> Resolver {
+        // take the outer scope...
+        self.module(db)
+            .resolver(db)
+            .push_generic_params_scope(self.generic_params(db))
+    }
+
+    pub fn diagnostics(self, db: &impl HirDatabase, sink: &mut DiagnosticSink) {
+        let data = self.data(db);
+        let lower = self.lower(db);
+        lower.add_diagnostics(
+            db,
+            self.module(db).file_id,
+            data.type_ref_source_map(),
+            sink,
+        );
+
+        let src = self.source(db);
+        if let ast::StructKind::Record(record) = src.value.kind() {
+            let fields: Vec<_> = record.fields().collect();
+            adt::validate_renamed_from(src.file_id, &fields, sink);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Enum {
+    pub(crate) id: EnumId,
+}
+
+/// A single variant of an enum, e.g. `Shape::Circle`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EnumVariant {
+    pub(crate) parent: Enum,
+    pub(crate) id: EnumVariantId,
+}
+
+/// A field of an enum variant, e.g. `w` in `Shape::Rect { w: f32, h: f32 }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EnumVariantField {
+    pub(crate) parent: EnumVariant,
+    pub(crate) id: StructFieldId,
+}
+
+impl Enum {
+    pub fn module(self, db: &impl DefDatabase) -> Module {
+        Module {
+            file_id: self.id.file_id(db),
+        }
+    }
+
+    pub fn data(self, db: &impl DefDatabase) -> Arc<EnumData> {
+        db.enum_data(self.id)
+    }
+
+    pub fn name(self, db: &impl DefDatabase) -> Name {
+        self.data(db).name.clone()
+    }
+
+    /// Returns the name of the enum, qualified with the path of its module, e.g.
+    /// `shapes::Shape`.
+    pub fn full_name(self, db: &impl DefDatabase) -> String {
+        self.module(db).qualified_name(db, &self.name(db))
+    }
+
+    pub fn visibility(self, db: &impl DefDatabase) -> Visibility {
+        self.data(db).visibility
+    }
+
+    /// Returns all the variants of the enum in declaration order.
+    pub fn variants(self, db: &impl DefDatabase) -> Vec<EnumVariant> {
+        self.data(db)
+            .variants
+            .iter()
+            .map(|(id, _)| EnumVariant { parent: self, id })
+            .collect()
+    }
+
+    pub fn variant(self, db: &impl DefDatabase, name: &Name) -> Option<EnumVariant> {
+        self.data(db)
+            .variants
+            .iter()
+            .find(|(_, data)| data.name == *name)
+            .map(|(id, _)| EnumVariant { parent: self, id })
+    }
+
+    pub fn ty(self, db: &impl HirDatabase) -> Ty {
+        db.type_for_def(self.into(), Namespace::Types)
+    }
+
+    pub fn lower(self, db: &impl HirDatabase) -> Arc<LowerBatchResult> {
+        db.lower_enum(self)
+    }
+
+    pub(crate) fn resolver(self, db: &impl HirDatabase) -> Resolver {
+        // take the outer scope...
+        self.module(db).resolver(db)
+    }
+
+    pub fn diagnostics(self, db: &impl HirDatabase, sink: &mut DiagnosticSink) {
+        let data = self.data(db);
+        let lower = self.lower(db);
+        lower.add_diagnostics(
+            db,
+            self.module(db).file_id,
+            data.type_ref_source_map(),
+            sink,
+        );
+    }
+}
+
+impl EnumVariant {
+    pub fn parent_enum(self) -> Enum {
+        self.parent
+    }
+
+    pub fn name(self, db: &impl DefDatabase) -> Name {
+        self.parent.data(db).variants[self.id].name.clone()
+    }
+
+    pub fn kind(self, db: &impl DefDatabase) -> StructKind {
+        self.parent.data(db).variants[self.id].kind
+    }
+
+    /// Returns the index of the variant in the declaration order of the enum.
+    pub fn index(self, db: &impl DefDatabase) -> usize {
+        self.parent
+            .data(db)
+            .variants
+            .iter()
+            .position(|(id, _)| id == self.id)
+            .unwrap()
+    }
+
+    pub fn fields(self, db: &impl DefDatabase) -> Vec<EnumVariantField> {
+        self.parent.data(db).variants[self.id]
+            .fields
+            .iter()
+            .map(|(id, _)| EnumVariantField { parent: self, id })
+            .collect()
+    }
+
+    pub fn field(self, db: &impl DefDatabase, name: &Name) -> Option<EnumVariantField> {
+        self.parent.data(db).variants[self.id]
+            .fields
+            .iter()
+            .find(|(_, data)| data.name == *name)
+            .map(|(id, _)| EnumVariantField { parent: self, id })
+    }
+}
+
+impl EnumVariantField {
+    pub fn ty(self, db: &impl HirDatabase) -> Ty {
+        let data = self.parent.parent.data(db);
+        let type_ref_id = data.variants[self.parent.id].fields[self.id].type_ref;
+        let lower = self.parent.parent.lower(db);
+        lower[type_ref_id].clone()
+    }
+
+    pub fn name(self, db: &impl DefDatabase) -> Name {
+        self.parent.parent.data(db).variants[self.parent.id].fields[self.id]
+            .name
+            .clone()
+    }
+
+    pub fn id(self) -> StructFieldId {
+        self.id
+    }
+}
+
+/// An `impl` block that defines methods and associated functions for a struct or an enum, e.g.
+/// `impl Foo { fn new() -> Self { .. } }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImplBlock {
+    pub(crate) id: ImplId,
+}
+
+impl ImplBlock {
+    pub fn module(self, db: &impl DefDatabase) -> Module {
+        Module {
+            file_id: self.id.file_id(db),
+        }
+    }
+
+    pub fn data(self, db: &impl DefDatabase) -> Arc<ImplData> {
+        db.impl_data(self.id)
+    }
+
+    /// Returns the type for which the `impl` block is defined.
+    pub fn target_ty(self, db: &impl HirDatabase) -> Ty {
+        let data = self.data(db);
+        self.lower(db)[data.target_type].clone()
+    }
+
+    /// Returns the trait that is implemented by the `impl` block, if any, e.g. `Update` in
+    /// `impl Update for Enemy { .. }`.
+    pub fn target_trait(self, db: &impl HirDatabase) -> Option<Trait> {
+        let data = self.data(db);
+        let path = data.target_trait.as_ref()?;
+        match self
+            .resolver(db)
+            .resolve_path_without_assoc_items(db, path)
+            .take_types()
+        {
+            Some(Resolution::Def(ModuleDef::Trait(t))) => Some(t),
+            _ => None,
+        }
+    }
+
+    /// Returns all the functions of the `impl` block in declaration order.
+    pub fn items(self, db: &impl DefDatabase) -> Vec<Function> {
+        self.data(db).items.clone()
+    }
+
+    pub fn lower(self, db: &impl HirDatabase) -> Arc<LowerBatchResult> {
+        db.lower_impl(self)
+    }
+
     pub(crate) fn resolver(self, db: &impl HirDatabase) -> Resolver {
         // take the outer scope...
         self.module(db).resolver(db)
@@ -442,6 +1067,196 @@ impl Struct {
     }
 }
 
+/// A trait declares functions that types have to implement to share behavior, e.g.
+/// `trait Update { fn update(self, dt: f32); }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Trait {
+    pub(crate) id: TraitId,
+}
+
+impl Trait {
+    pub fn module(self, db: &impl DefDatabase) -> Module {
+        Module {
+            file_id: self.id.file_id(db),
+        }
+    }
+
+    pub fn data(self, db: &impl DefDatabase) -> Arc<TraitData> {
+        db.trait_data(self.id)
+    }
+
+    pub fn name(self, db: &impl DefDatabase) -> Name {
+        self.data(db).name.clone()
+    }
+
+    /// Returns the name of the trait, qualified with the path of its module, e.g.
+    /// `physics::Update`.
+    pub fn full_name(self, db: &impl DefDatabase) -> String {
+        self.module(db).qualified_name(db, &self.name(db))
+    }
+
+    pub fn visibility(self, db: &impl DefDatabase) -> Visibility {
+        self.data(db).visibility
+    }
+
+    /// Returns all the functions declared in the trait in declaration order.
+    pub fn items(self, db: &impl DefDatabase) -> Vec<Function> {
+        self.data(db).items.clone()
+    }
+
+    /// Returns the function with the specified `name` that is declared in the trait, if any.
+    pub fn item(self, db: &impl HirDatabase, name: &Name) -> Option<Function> {
+        self.items(db).into_iter().find(|f| f.name(db) == *name)
+    }
+
+    pub fn diagnostics(self, db: &impl HirDatabase, sink: &mut DiagnosticSink) {
+        let mut function_by_name = FxHashMap::default();
+        for function in self.items(db) {
+            let name = function.name(db);
+            if let Some(first_definition) = function_by_name.get(&name) {
+                sink.push(DuplicateDefinition {
+                    file: self.module(db).file_id,
+                    name: name.to_string(),
+                    definition: SyntaxNodePtr::new(function.source(db).value.syntax()),
+                    first_definition: SyntaxNodePtr::new(
+                        first_definition.source(db).value.syntax(),
+                    ),
+                });
+            } else {
+                function_by_name.insert(name, function);
+            }
+            function.diagnostics(db, sink);
+        }
+    }
+}
+
+/// A constant whose value is evaluated at compile time and inlined wherever it is used, e.g.
+/// `const GRAVITY: f32 = 9.81;`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Const {
+    pub(crate) id: ConstId,
+}
+
+impl Const {
+    pub fn module(self, db: &impl DefDatabase) -> Module {
+        Module {
+            file_id: self.id.file_id(db),
+        }
+    }
+
+    pub fn data(self, db: &impl DefDatabase) -> Arc<ConstData> {
+        db.const_data(self.id)
+    }
+
+    pub fn name(self, db: &impl DefDatabase) -> Name {
+        self.data(db).name.clone()
+    }
+
+    /// Returns the name of the constant, qualified with the path of its module, e.g.
+    /// `physics::GRAVITY`.
+    pub fn full_name(self, db: &impl DefDatabase) -> String {
+        self.module(db).qualified_name(db, &self.name(db))
+    }
+
+    pub fn visibility(self, db: &impl DefDatabase) -> Visibility {
+        self.data(db).visibility
+    }
+
+    pub fn ty(self, db: &impl HirDatabase) -> Ty {
+        db.type_for_def(self.into(), Namespace::Values)
+    }
+
+    pub fn body(self, db: &impl HirDatabase) -> Arc<Body> {
+        db.body(self.into())
+    }
+
+    pub fn infer(self, db: &impl HirDatabase) -> Arc<InferenceResult> {
+        db.infer(self.into())
+    }
+
+    /// Returns the value of the constant, or `None` if it cannot be evaluated at compile time.
+    pub fn value(self, db: &impl HirDatabase) -> Option<ConstValue> {
+        eval_body(db, self.into()).ok()
+    }
+
+    pub(crate) fn resolver(self, db: &impl HirDatabase) -> Resolver {
+        // take the outer scope...
+        self.module(db).resolver(db)
+    }
+
+    pub fn diagnostics(self, db: &impl HirDatabase, sink: &mut DiagnosticSink) {
+        let data = self.data(db);
+        let type_ref = data.type_ref_source_map().type_ref_syntax(data.type_ref);
+        DefWithBody::from(self).global_diagnostics(db, self.ty(db), type_ref, sink);
+    }
+}
+
+/// A global variable whose storage lives in the assembly, e.g. `static mut SCORE: i32 = 0;`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Static {
+    pub(crate) id: StaticId,
+}
+
+impl Static {
+    pub fn module(self, db: &impl DefDatabase) -> Module {
+        Module {
+            file_id: self.id.file_id(db),
+        }
+    }
+
+    pub fn data(self, db: &impl DefDatabase) -> Arc<StaticData> {
+        db.static_data(self.id)
+    }
+
+    pub fn name(self, db: &impl DefDatabase) -> Name {
+        self.data(db).name.clone()
+    }
+
+    /// Returns the name of the global variable, qualified with the path of its module, e.g.
+    /// `game::SCORE`.
+    pub fn full_name(self, db: &impl DefDatabase) -> String {
+        self.module(db).qualified_name(db, &self.name(db))
+    }
+
+    pub fn visibility(self, db: &impl DefDatabase) -> Visibility {
+        self.data(db).visibility
+    }
+
+    /// Returns true if the global variable can be assigned to, e.g. `static mut SCORE: i32 = 0;`.
+    pub fn is_mut(self, db: &impl DefDatabase) -> bool {
+        self.data(db).is_mut
+    }
+
+    pub fn ty(self, db: &impl HirDatabase) -> Ty {
+        db.type_for_def(self.into(), Namespace::Values)
+    }
+
+    pub fn body(self, db: &impl HirDatabase) -> Arc<Body> {
+        db.body(self.into())
+    }
+
+    pub fn infer(self, db: &impl HirDatabase) -> Arc<InferenceResult> {
+        db.infer(self.into())
+    }
+
+    /// Returns the value with which the global variable is initialized, or `None` if it cannot be
+    /// evaluated at compile time.
+    pub fn initial_value(self, db: &impl HirDatabase) -> Option<ConstValue> {
+        eval_body(db, self.into()).ok()
+    }
+
+    pub(crate) fn resolver(self, db: &impl HirDatabase) -> Resolver {
+        // take the outer scope...
+        self.module(db).resolver(db)
+    }
+
+    pub fn diagnostics(self, db: &impl HirDatabase, sink: &mut DiagnosticSink) {
+        let data = self.data(db);
+        let type_ref = data.type_ref_source_map().type_ref_syntax(data.type_ref);
+        DefWithBody::from(self).global_diagnostics(db, self.ty(db), type_ref, sink);
+    }
+}
+
 mod diagnostics {
     use super::Module;
     use crate::diagnostics::{DiagnosticSink, DuplicateDefinition};
@@ -466,6 +1281,21 @@ mod diagnostics {
             DefKind::Struct(id) => {
                 SyntaxNodePtr::new(id.with_file_id(owner.file_id).to_node(db).syntax())
             }
+            DefKind::Enum(id) => {
+                SyntaxNodePtr::new(id.with_file_id(owner.file_id).to_node(db).syntax())
+            }
+            DefKind::Trait(id) => {
+                SyntaxNodePtr::new(id.with_file_id(owner.file_id).to_node(db).syntax())
+            }
+            DefKind::Const(id) => {
+                SyntaxNodePtr::new(id.with_file_id(owner.file_id).to_node(db).syntax())
+            }
+            DefKind::Static(id) => {
+                SyntaxNodePtr::new(id.with_file_id(owner.file_id).to_node(db).syntax())
+            }
+            DefKind::Module(id) => {
+                SyntaxNodePtr::new(id.with_file_id(owner.file_id).to_node(db).syntax())
+            }
         }
     }
 
@@ -1,4 +1,4 @@
-use crate::code_model::{Function, Struct, StructField};
+use crate::code_model::{Const, Enum, Function, ImplBlock, Static, Struct, StructField, Trait};
 use crate::ids::AstItemDef;
 use crate::in_file::InFile;
 use crate::DefDatabase;
@@ -23,6 +23,41 @@ impl HasSource for Struct {
     }
 }
 
+impl HasSource for Enum {
+    type Ast = ast::EnumDef;
+    fn source(self, db: &impl DefDatabase) -> InFile<ast::EnumDef> {
+        self.id.source(db)
+    }
+}
+
+impl HasSource for ImplBlock {
+    type Ast = ast::ImplBlock;
+    fn source(self, db: &impl DefDatabase) -> InFile<ast::ImplBlock> {
+        self.id.source(db)
+    }
+}
+
+impl HasSource for Trait {
+    type Ast = ast::TraitDef;
+    fn source(self, db: &impl DefDatabase) -> InFile<ast::TraitDef> {
+        self.id.source(db)
+    }
+}
+
+impl HasSource for Const {
+    type Ast = ast::ConstDef;
+    fn source(self, db: &impl DefDatabase) -> InFile<ast::ConstDef> {
+        self.id.source(db)
+    }
+}
+
+impl HasSource for Static {
+    type Ast = ast::StaticDef;
+    fn source(self, db: &impl DefDatabase) -> InFile<ast::StaticDef> {
+        self.id.source(db)
+    }
+}
+
 impl HasSource for StructField {
     type Ast = ast::RecordFieldDef;
 
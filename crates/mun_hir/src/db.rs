@@ -5,14 +5,20 @@ use crate::name_resolution::Namespace;
 use crate::ty::lower::LowerBatchResult;
 use crate::ty::{CallableDef, FnSig, Ty, TypableDef};
 use crate::{
-    adt::StructData,
+    adt::{EnumData, StructData},
     code_model::{DefWithBody, FnData, Function, ModuleData},
+    generics::{GenericDef, GenericParams},
+    global::{ConstData, StaticData},
     ids,
+    impl_block::{ImplData, PackageImpls},
     line_index::LineIndex,
-    name_resolution::ModuleScope,
+    lint::PackageUsages,
+    module_tree::ModuleTree,
+    name_resolution::{ModuleScope, PackageDefs},
     source_id::ErasedFileAstId,
+    traits::TraitData,
     ty::InferenceResult,
-    AstIdMap, ExprScopes, FileId, RawItems, Struct,
+    AstIdMap, Enum, ExprScopes, FileId, ImplBlock, Package, RawItems, Struct,
 };
 use mun_syntax::{ast, Parse, SourceFile, SyntaxNode};
 use mun_target::abi;
@@ -44,6 +50,10 @@ pub trait SourceDatabase: std::fmt::Debug {
     #[salsa::input]
     fn source_root(&self, id: SourceRootId) -> Arc<SourceRoot>;
 
+    /// The root module of the package of a source root
+    #[salsa::input]
+    fn package_root(&self, id: SourceRootId) -> FileId;
+
     /// Returns the line index of a file
     #[salsa::invoke(line_index_query)]
     fn line_index(&self, file_id: FileId) -> Arc<LineIndex>;
@@ -63,9 +73,32 @@ pub trait DefDatabase: SourceDatabase {
     #[salsa::invoke(RawItems::raw_file_items_query)]
     fn raw_items(&self, file_id: FileId) -> Arc<RawItems>;
 
+    /// Returns the hierarchy of modules of the package in a source root
+    #[salsa::invoke(crate::module_tree::ModuleTree::module_tree_query)]
+    fn module_tree(&self, id: SourceRootId) -> Arc<ModuleTree>;
+
     #[salsa::invoke(StructData::struct_data_query)]
     fn struct_data(&self, id: ids::StructId) -> Arc<StructData>;
 
+    #[salsa::invoke(EnumData::enum_data_query)]
+    fn enum_data(&self, id: ids::EnumId) -> Arc<EnumData>;
+
+    #[salsa::invoke(ImplData::impl_data_query)]
+    fn impl_data(&self, id: ids::ImplId) -> Arc<ImplData>;
+
+    #[salsa::invoke(TraitData::trait_data_query)]
+    fn trait_data(&self, id: ids::TraitId) -> Arc<TraitData>;
+
+    #[salsa::invoke(ConstData::const_data_query)]
+    fn const_data(&self, id: ids::ConstId) -> Arc<ConstData>;
+
+    #[salsa::invoke(StaticData::static_data_query)]
+    fn static_data(&self, id: ids::StaticId) -> Arc<StaticData>;
+
+    /// Returns the type parameters of a function or struct
+    #[salsa::invoke(GenericParams::generic_params_query)]
+    fn generic_params(&self, def: GenericDef) -> Arc<GenericParams>;
+
     /// Interns a function definition
     #[salsa::interned]
     fn intern_function(&self, loc: ids::ItemLoc<ast::FunctionDef>) -> ids::FunctionId;
@@ -73,6 +106,26 @@ pub trait DefDatabase: SourceDatabase {
     /// Interns a struct definition
     #[salsa::interned]
     fn intern_struct(&self, loc: ids::ItemLoc<ast::StructDef>) -> ids::StructId;
+
+    /// Interns an enum definition
+    #[salsa::interned]
+    fn intern_enum(&self, loc: ids::ItemLoc<ast::EnumDef>) -> ids::EnumId;
+
+    /// Interns an impl block
+    #[salsa::interned]
+    fn intern_impl(&self, loc: ids::ItemLoc<ast::ImplBlock>) -> ids::ImplId;
+
+    /// Interns a trait definition
+    #[salsa::interned]
+    fn intern_trait(&self, loc: ids::ItemLoc<ast::TraitDef>) -> ids::TraitId;
+
+    /// Interns a const definition
+    #[salsa::interned]
+    fn intern_const(&self, loc: ids::ItemLoc<ast::ConstDef>) -> ids::ConstId;
+
+    /// Interns a static definition
+    #[salsa::interned]
+    fn intern_static(&self, loc: ids::ItemLoc<ast::StaticDef>) -> ids::StaticId;
 }
 
 #[salsa::query_group(HirDatabaseStorage)]
@@ -88,15 +141,34 @@ pub trait HirDatabase: DefDatabase {
     #[salsa::invoke(ExprScopes::expr_scopes_query)]
     fn expr_scopes(&self, def: DefWithBody) -> Arc<ExprScopes>;
 
+    /// Returns the scopes of all modules in the package of a source root
+    #[salsa::invoke(crate::name_resolution::PackageDefs::package_defs_query)]
+    fn package_defs(&self, id: SourceRootId) -> Arc<PackageDefs>;
+
     #[salsa::invoke(crate::name_resolution::module_scope_query)]
     fn module_scope(&self, file_id: FileId) -> Arc<ModuleScope>;
 
+    /// Returns the `impl` blocks of the package in a source root, indexed by their type. This also
+    /// checks the coherence of the implementations of traits.
+    #[salsa::invoke(PackageImpls::package_impls_query)]
+    fn package_impls(&self, id: SourceRootId) -> Arc<PackageImpls>;
+
+    /// Returns the functions and global variables that are used by the bodies in a package
+    #[salsa::invoke(crate::lint::PackageUsages::package_usages_query)]
+    fn package_usages(&self, package: Package) -> Arc<PackageUsages>;
+
     #[salsa::invoke(crate::ty::infer_query)]
     fn infer(&self, def: DefWithBody) -> Arc<InferenceResult>;
 
     #[salsa::invoke(crate::ty::lower::lower_struct_query)]
     fn lower_struct(&self, def: Struct) -> Arc<LowerBatchResult>;
 
+    #[salsa::invoke(crate::ty::lower::lower_enum_query)]
+    fn lower_enum(&self, def: Enum) -> Arc<LowerBatchResult>;
+
+    #[salsa::invoke(crate::ty::lower::lower_impl_query)]
+    fn lower_impl(&self, def: ImplBlock) -> Arc<LowerBatchResult>;
+
     #[salsa::invoke(crate::FnData::fn_data_query)]
     fn fn_data(&self, func: Function) -> Arc<FnData>;
 
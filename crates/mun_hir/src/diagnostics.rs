@@ -1,6 +1,7 @@
 use crate::adt::StructKind;
 use crate::in_file::InFile;
-use crate::{FileId, HirDatabase, IntTy, Name, Ty};
+use crate::lint::{Lint, LintLevel};
+use crate::{FileId, HirDatabase, IntTy, Name, RelativePathBuf, Ty};
 use mun_syntax::{ast, AstPtr, SmolStr, SyntaxNode, SyntaxNodePtr, TextRange};
 use std::{any::Any, fmt};
 
@@ -20,6 +21,16 @@ pub trait Diagnostic: Any + Send + Sync + fmt::Debug + 'static {
         self.source().value.range()
     }
     fn as_any(&self) -> &(dyn Any + Send + 'static);
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+}
+
+/// The severity of a `Diagnostic`. Only errors prevent an assembly from being generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
 }
 
 pub trait AstDiagnostic {
@@ -117,6 +128,71 @@ impl Diagnostic for UnresolvedType {
     }
 }
 
+#[derive(Debug)]
+pub struct InvalidArrayLength {
+    pub file: FileId,
+    pub type_ref: AstPtr<ast::TypeRef>,
+}
+
+impl Diagnostic for InvalidArrayLength {
+    fn message(&self) -> String {
+        "array length must be an integer literal".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidOptionalType {
+    pub file: FileId,
+    pub type_ref: AstPtr<ast::TypeRef>,
+}
+
+impl Diagnostic for InvalidOptionalType {
+    fn message(&self) -> String {
+        "only garbage collected structs can be optional".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct WrongNumberOfTypeArguments {
+    pub file: FileId,
+    pub type_ref: AstPtr<ast::TypeRef>,
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl Diagnostic for WrongNumberOfTypeArguments {
+    fn message(&self) -> String {
+        format!(
+            "wrong number of type arguments: expected {}, found {}",
+            self.expected, self.found
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct ExpectedFunction {
     pub file: FileId,
@@ -379,6 +455,220 @@ impl Diagnostic for BreakWithValueOutsideLoop {
     }
 }
 
+#[derive(Debug)]
+pub struct InvalidForIterable {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub found: Ty,
+}
+
+impl Diagnostic for InvalidForIterable {
+    fn message(&self) -> String {
+        "`for` loops can only iterate over integer ranges".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct RangeOutsideForLoop {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+}
+
+impl Diagnostic for RangeOutsideForLoop {
+    fn message(&self) -> String {
+        "range expressions can only appear as the iterable of a `for` loop".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct CannotIndex {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub found: Ty,
+}
+
+impl Diagnostic for CannotIndex {
+    fn message(&self) -> String {
+        "cannot index into a value that is not an array".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when an optional value is dereferenced without first checking that it
+/// is not `nil` (e.g. `foo.a` where `foo: Foo?`)
+#[derive(Debug)]
+pub struct PossiblyNil {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub found: Ty,
+}
+
+impl Diagnostic for PossiblyNil {
+    fn message(&self) -> String {
+        "value may be `nil`; compare it to `nil` before accessing it".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidCast {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub from: Ty,
+    pub to: Ty,
+}
+
+impl Diagnostic for InvalidCast {
+    fn message(&self) -> String {
+        "non-primitive cast: `as` can only be used to convert between numeric types".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct UnresolvedMethod {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub receiver_ty: Ty,
+    pub name: Name,
+}
+
+impl Diagnostic for UnresolvedMethod {
+    fn message(&self) -> String {
+        format!("no method named `{}` found for this type", self.name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct TypeArgumentsNeeded {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+}
+
+impl Diagnostic for TypeArgumentsNeeded {
+    fn message(&self) -> String {
+        "type annotations needed: cannot infer the type arguments of a generic function or struct"
+            .to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct EmptyArrayNeedsType {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+}
+
+impl Diagnostic for EmptyArrayNeedsType {
+    fn message(&self) -> String {
+        "type annotations needed: cannot infer the element type of an empty array".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct NilNeedsType {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+}
+
+impl Diagnostic for NilNeedsType {
+    fn message(&self) -> String {
+        "type annotations needed: cannot infer the type of `nil`".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct BuiltinFunctionNotCalled {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub name: Name,
+}
+
+impl Diagnostic for BuiltinFunctionNotCalled {
+    fn message(&self) -> String {
+        format!(
+            "builtin function `{}` can only be called, not used as a value",
+            self.name
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct AccessUnknownField {
     pub file: FileId,
@@ -540,6 +830,32 @@ impl Diagnostic for PossiblyUninitializedVariable {
     }
 }
 
+#[derive(Debug)]
+pub struct MissingMatchArms {
+    pub file: FileId,
+    pub match_expr: SyntaxNodePtr,
+    pub missing_patterns: Vec<String>,
+}
+
+impl Diagnostic for MissingMatchArms {
+    fn message(&self) -> String {
+        use std::fmt::Write;
+        let mut message = "non-exhaustive patterns, missing match arms:\n".to_string();
+        for pattern in &self.missing_patterns {
+            writeln!(message, "- {}", pattern).unwrap();
+        }
+        message
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.match_expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct ExternCannotHaveBody {
     pub func: InFile<SyntaxNodePtr>,
@@ -686,3 +1002,495 @@ impl Diagnostic for InvalidLiteral {
         self
     }
 }
+
+/// An error that is emitted for a `mod` declaration for which no source file could be found
+#[derive(Debug)]
+pub struct UnresolvedModule {
+    pub file: FileId,
+    pub decl: AstPtr<ast::Module>,
+    pub candidate: RelativePathBuf,
+}
+
+impl Diagnostic for UnresolvedModule {
+    fn message(&self) -> String {
+        format!("unresolved module, could not find `{}`", self.candidate)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.decl.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for a `use` path that could not be resolved
+#[derive(Debug)]
+pub struct UnresolvedImport {
+    pub file: FileId,
+    pub use_tree: AstPtr<ast::UseTree>,
+}
+
+impl Diagnostic for UnresolvedImport {
+    fn message(&self) -> String {
+        "unresolved import".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.use_tree.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when a `use` path refers to a private item of another module
+#[derive(Debug)]
+pub struct PrivateImport {
+    pub file: FileId,
+    pub use_tree: AstPtr<ast::UseTree>,
+    pub name: Name,
+}
+
+impl Diagnostic for PrivateImport {
+    fn message(&self) -> String {
+        format!("`{}` is private", self.name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.use_tree.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when an `impl` block is defined for a type that is not a struct or an
+/// enum
+#[derive(Debug)]
+pub struct InvalidImplType {
+    pub file: FileId,
+    pub type_ref: AstPtr<ast::TypeRef>,
+}
+
+impl Diagnostic for InvalidImplType {
+    fn message(&self) -> String {
+        "`impl` blocks can only be defined for structs and enums".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when a function that is not defined in an `impl` block or a trait has
+/// a `self` parameter
+#[derive(Debug)]
+pub struct SelfParamOutsideImpl {
+    pub self_param: InFile<SyntaxNodePtr>,
+}
+
+impl Diagnostic for SelfParamOutsideImpl {
+    fn message(&self) -> String {
+        "`self` parameter is only allowed in functions of `impl` blocks and traits".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.self_param
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when the trait of an `impl` block does not refer to a trait
+#[derive(Debug)]
+pub struct NotATrait {
+    pub file: FileId,
+    pub type_ref: AstPtr<ast::TypeRef>,
+}
+
+impl Diagnostic for NotATrait {
+    fn message(&self) -> String {
+        "expected a trait".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when a trait is implemented more than once for the same type
+#[derive(Debug)]
+pub struct ConflictingImpls {
+    pub file: FileId,
+    pub type_ref: AstPtr<ast::TypeRef>,
+    pub trait_name: String,
+}
+
+impl Diagnostic for ConflictingImpls {
+    fn message(&self) -> String {
+        format!(
+            "conflicting implementations of trait `{}` for the same type",
+            self.trait_name
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when an `impl` block does not implement all the functions of its trait
+#[derive(Debug)]
+pub struct MissingTraitMethods {
+    pub file: FileId,
+    pub type_ref: AstPtr<ast::TypeRef>,
+    pub trait_name: String,
+    pub names: Vec<Name>,
+}
+
+impl Diagnostic for MissingTraitMethods {
+    fn message(&self) -> String {
+        use std::fmt::Write;
+        let mut message = format!("missing methods of trait `{}`:\n", self.trait_name);
+        for name in &self.names {
+            writeln!(message, "- {}", name).unwrap();
+        }
+        message
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when an `impl` block of a trait defines a function that is not
+/// declared by the trait
+#[derive(Debug)]
+pub struct NotATraitMethod {
+    pub file: FileId,
+    pub function: SyntaxNodePtr,
+    pub name: String,
+    pub trait_name: String,
+}
+
+impl Diagnostic for NotATraitMethod {
+    fn message(&self) -> String {
+        format!(
+            "method `{}` is not a member of trait `{}`",
+            self.name, self.trait_name
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.function)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when the signature of a function of an `impl` block differs from the
+/// signature that is declared by its trait
+#[derive(Debug)]
+pub struct IncompatibleTraitMethod {
+    pub file: FileId,
+    pub function: SyntaxNodePtr,
+    pub name: String,
+    pub trait_name: String,
+}
+
+impl Diagnostic for IncompatibleTraitMethod {
+    fn message(&self) -> String {
+        format!(
+            "method `{}` has an incompatible signature for trait `{}`",
+            self.name, self.trait_name
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.function)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when a function of a trait is called for a type that does not
+/// implement the trait
+#[derive(Debug)]
+pub struct TraitNotImplemented {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub trait_name: String,
+}
+
+impl Diagnostic for TraitNotImplemented {
+    fn message(&self) -> String {
+        format!(
+            "the trait `{}` is not implemented for this type",
+            self.trait_name
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when a function that is declared in a trait has a body
+#[derive(Debug)]
+pub struct TraitFunctionCannotHaveBody {
+    pub func: InFile<SyntaxNodePtr>,
+}
+
+impl Diagnostic for TraitFunctionCannotHaveBody {
+    fn message(&self) -> String {
+        "functions of traits cannot have bodies".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.func
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when the value of a constant or the initial value of a global
+/// variable cannot be evaluated at compile time
+#[derive(Debug)]
+pub struct NotConstant {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+}
+
+impl Diagnostic for NotConstant {
+    fn message(&self) -> String {
+        "expression cannot be evaluated at compile time".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when a division by zero is evaluated at compile time
+#[derive(Debug)]
+pub struct DivisionByZero {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+}
+
+impl Diagnostic for DivisionByZero {
+    fn message(&self) -> String {
+        "attempt to divide by zero".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when the value of a constant depends on itself, e.g.
+/// `const A: i32 = A + 1;`
+#[derive(Debug)]
+pub struct CyclicConstant {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+}
+
+impl Diagnostic for CyclicConstant {
+    fn message(&self) -> String {
+        "cycle detected when evaluating the value of a constant".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when a constant or a global variable does not have a boolean,
+/// integer or floating-point type
+#[derive(Debug)]
+pub struct InvalidGlobalType {
+    pub file: FileId,
+    pub type_ref: AstPtr<ast::TypeRef>,
+}
+
+impl Diagnostic for InvalidGlobalType {
+    fn message(&self) -> String {
+        "constants and global variables must have a boolean, integer or floating-point type"
+            .to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when the argument of a `renamed_from` attribute is not a single string
+/// literal, e.g. `#[renamed_from(hp)]`
+#[derive(Debug)]
+pub struct InvalidRenamedFrom {
+    pub attr: InFile<SyntaxNodePtr>,
+}
+
+impl Diagnostic for InvalidRenamedFrom {
+    fn message(&self) -> String {
+        "expected the previous name of the field as a string literal".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.attr
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when a field has more than one `renamed_from` attribute
+#[derive(Debug)]
+pub struct MultipleRenamedFrom {
+    pub attr: InFile<SyntaxNodePtr>,
+}
+
+impl Diagnostic for MultipleRenamedFrom {
+    fn message(&self) -> String {
+        "a field can only be renamed from a single name".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.attr
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when multiple fields of a struct are renamed from the same name
+#[derive(Debug)]
+pub struct DuplicateRenamedFrom {
+    pub attr: InFile<SyntaxNodePtr>,
+    pub name: String,
+}
+
+impl Diagnostic for DuplicateRenamedFrom {
+    fn message(&self) -> String {
+        format!(
+            "the field `{}` is renamed by multiple fields of the struct",
+            self.name
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.attr
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when a field is renamed from the name of another field of the same
+/// struct, as that field still exists
+#[derive(Debug)]
+pub struct RenamedFromExistingField {
+    pub attr: InFile<SyntaxNodePtr>,
+    pub name: String,
+}
+
+impl Diagnostic for RenamedFromExistingField {
+    fn message(&self) -> String {
+        format!(
+            "cannot rename from `{}`, as the struct still has a field with that name",
+            self.name
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.attr
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A diagnostic that is emitted by one of the lints in `crate::lint`. Its severity depends on the
+/// level of the lint.
+#[derive(Debug)]
+pub struct LintDiagnostic {
+    pub file: FileId,
+    pub node: SyntaxNodePtr,
+    pub lint: Lint,
+    pub level: LintLevel,
+    pub message: String,
+}
+
+impl Diagnostic for LintDiagnostic {
+    fn message(&self) -> String {
+        self.message.clone()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.node)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+
+    fn severity(&self) -> Severity {
+        match self.level {
+            LintLevel::Deny => Severity::Error,
+            LintLevel::Allow | LintLevel::Warn => Severity::Warning,
+        }
+    }
+}
@@ -7,10 +7,11 @@ use crate::{
 
 //pub use mun_syntax::ast::PrefixOp as UnaryOp;
 use crate::code_model::src::HasSource;
-use crate::name::AsName;
+use crate::name::{name, AsName};
 use crate::type_ref::{TypeRef, TypeRefBuilder, TypeRefId, TypeRefMap, TypeRefSourceMap};
 use either::Either;
 pub use mun_syntax::ast::PrefixOp as UnaryOp;
+pub use mun_syntax::ast::RangeOp;
 use mun_syntax::ast::{ArgListOwner, BinOp, LoopBodyOwner, NameOwner, TypeAscriptionOwner};
 use mun_syntax::{ast, AstNode, AstPtr, SmolStr, T};
 use rustc_hash::FxHashMap;
@@ -21,10 +22,12 @@ pub use self::scope::ExprScopes;
 use crate::builtin_type::{BuiltinFloat, BuiltinInt};
 use crate::diagnostics::DiagnosticSink;
 use crate::in_file::InFile;
+use crate::line_index::LineCol;
 use crate::resolve::Resolver;
 use std::borrow::Cow;
 use std::str::FromStr;
 
+pub(crate) mod const_eval;
 pub(crate) mod scope;
 pub(crate) mod validator;
 
@@ -41,7 +44,7 @@ pub enum ExprDiagnostic {
     LiteralError { expr: ExprId, err: LiteralError },
 }
 
-/// The body of an item (function, const etc.).
+/// The body of an item (function, const or static).
 #[derive(Debug, Eq, PartialEq)]
 pub struct Body {
     owner: DefWithBody,
@@ -155,6 +158,25 @@ impl BodySourceMap {
         self.expr_map_back.get(expr).cloned()
     }
 
+    /// Returns the (zero-based) line and column in its source file at which the expression `expr`
+    /// starts.
+    pub fn expr_line_col(&self, db: &impl HirDatabase, expr: ExprId) -> Option<LineCol> {
+        let source = self.expr_syntax(expr)?;
+        let range = source
+            .value
+            .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())
+            .range();
+        Some(db.line_index(source.file_id).line_col(range.start()))
+    }
+
+    /// Returns the (zero-based) line and column in its source file at which the pattern `pat`
+    /// starts.
+    pub fn pat_line_col(&self, db: &impl HirDatabase, pat: PatId) -> Option<LineCol> {
+        let source = self.pat_syntax(pat)?;
+        let range = source.value.syntax_node_ptr().range();
+        Some(db.line_index(source.file_id).line_col(range.start()))
+    }
+
     pub fn type_ref_syntax(&self, type_ref: TypeRefId) -> Option<AstPtr<ast::TypeRef>> {
         self.type_refs.type_ref_syntax(type_ref)
     }
@@ -190,6 +212,13 @@ pub struct RecordLitField {
     pub expr: ExprId,
 }
 
+/// A single arm of a `match` expression, e.g. `Shape::Circle(r) => r * r`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MatchArm {
+    pub pat: PatId,
+    pub expr: ExprId,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Statement {
     Let {
@@ -206,6 +235,8 @@ pub enum Literal {
     Bool(bool),
     Int(LiteralInt),
     Float(LiteralFloat),
+    /// The `nil` literal, the absent value of an optional type
+    Nil,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -292,6 +323,20 @@ pub enum Expr {
         condition: ExprId,
         body: ExprId,
     },
+    For {
+        pat: PatId,
+        iterable: ExprId,
+        body: ExprId,
+    },
+    Range {
+        start: ExprId,
+        end: ExprId,
+        op: RangeOp,
+    },
+    Match {
+        expr: ExprId,
+        arms: Vec<MatchArm>,
+    },
     RecordLit {
         type_id: TypeRefId,
         fields: Vec<RecordLitField>,
@@ -301,6 +346,29 @@ pub enum Expr {
         expr: ExprId,
         name: Name,
     },
+    MethodCall {
+        receiver: ExprId,
+        method_name: Name,
+        args: Vec<ExprId>,
+    },
+    Index {
+        base: ExprId,
+        index: ExprId,
+    },
+    Array(Vec<ExprId>),
+    Tuple(Vec<ExprId>),
+    Cast {
+        expr: ExprId,
+        type_ref: TypeRefId,
+    },
+    /// A closure, e.g. `|x| x * k`. The types of the parameters and the return type are inferred
+    /// if they are omitted.
+    Lambda {
+        args: Vec<PatId>,
+        arg_types: Vec<Option<TypeRefId>>,
+        ret_type: Option<TypeRefId>,
+        body: ExprId,
+    },
     Literal(Literal),
 }
 
@@ -370,14 +438,32 @@ impl Expr {
                     f(*arg);
                 }
             }
+            Expr::MethodCall { receiver, args, .. } => {
+                f(*receiver);
+                for arg in args {
+                    f(*arg);
+                }
+            }
             Expr::BinaryOp { lhs, rhs, .. } => {
                 f(*lhs);
                 f(*rhs);
             }
-            Expr::Field { expr, .. } | Expr::UnaryOp { expr, .. } => {
+            Expr::Index { base, index } => {
+                f(*base);
+                f(*index);
+            }
+            Expr::Array(exprs) | Expr::Tuple(exprs) => {
+                for expr in exprs {
+                    f(*expr);
+                }
+            }
+            Expr::Field { expr, .. } | Expr::UnaryOp { expr, .. } | Expr::Cast { expr, .. } => {
                 f(*expr);
             }
             Expr::Literal(_) => {}
+            Expr::Lambda { body, .. } => {
+                f(*body);
+            }
             Expr::If {
                 condition,
                 then_branch,
@@ -406,6 +492,20 @@ impl Expr {
                 f(*condition);
                 f(*body);
             }
+            Expr::For { iterable, body, .. } => {
+                f(*iterable);
+                f(*body);
+            }
+            Expr::Range { start, end, .. } => {
+                f(*start);
+                f(*end);
+            }
+            Expr::Match { expr, arms } => {
+                f(*expr);
+                for arm in arms {
+                    f(arm.expr);
+                }
+            }
             Expr::RecordLit { fields, spread, .. } => {
                 for field in fields {
                     f(field.expr);
@@ -418,17 +518,46 @@ impl Expr {
     }
 }
 
+/// A field of a record pattern, e.g. `h: height` in `Shape::Rect { w, h: height }`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RecordFieldPat {
+    pub name: Name,
+    pub pat: PatId,
+}
+
 /// Similar to `ast::PatKind`
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Pat {
-    Missing,             // Indicates an error
-    Wild,                // `_`
-    Path(Path),          // E.g. `foo::bar`
-    Bind { name: Name }, // E.g. `a`
+    /// Indicates an error
+    Missing,
+    /// `_`
+    Wild,
+    /// E.g. `foo::bar`
+    Path(Path),
+    /// E.g. `a`
+    Bind { name: Name },
+    /// E.g. `(x, y)`
+    Tuple(Vec<PatId>),
+    /// E.g. `Shape::Circle(r)`
+    TupleStruct {
+        path: Option<Path>,
+        args: Vec<PatId>,
+    },
+    /// E.g. `Shape::Rect { w, h: height }`
+    Record {
+        path: Option<Path>,
+        args: Vec<RecordFieldPat>,
+    },
 }
 
 impl Pat {
-    pub fn walk_child_pats(&self, mut _f: impl FnMut(PatId)) {}
+    pub fn walk_child_pats(&self, mut f: impl FnMut(PatId)) {
+        match self {
+            Pat::Missing | Pat::Wild | Pat::Path(_) | Pat::Bind { .. } => {}
+            Pat::Tuple(args) | Pat::TupleStruct { args, .. } => args.iter().copied().for_each(f),
+            Pat::Record { args, .. } => args.iter().for_each(|field| f(field.pat)),
+        }
+    }
 }
 
 // Queries
@@ -502,6 +631,16 @@ where
 
     fn collect_fn_body(&mut self, node: &ast::FunctionDef) {
         if let Some(param_list) = node.param_list() {
+            if param_list.self_param().is_some() {
+                let self_pat = self.pats.alloc(Pat::Bind { name: name![self] });
+                // A `self` parameter outside of an `impl` block is reported by the `ExprValidator`
+                let self_type = if node.impl_block().is_some() {
+                    self.type_ref_builder.self_type()
+                } else {
+                    self.type_ref_builder.error()
+                };
+                self.params.push((self_pat, self_type));
+            }
             for param in param_list.params() {
                 let pat = if let Some(pat) = param.pat() {
                     pat
@@ -527,6 +666,14 @@ where
         self.ret_type = Some(ret_type);
     }
 
+    /// Collects the initializer of a `const` or `static` item. The declared type of the item is
+    /// used as the type of the body.
+    fn collect_global_body(&mut self, initializer: Option<ast::Expr>, ty: Option<ast::TypeRef>) {
+        let body = self.collect_expr_opt(initializer);
+        self.body_expr = Some(body);
+        self.ret_type = Some(self.type_ref_builder.alloc_from_node_opt(ty.as_ref()));
+    }
+
     fn collect_block_opt(&mut self, block: Option<ast::BlockExpr>) -> ExprId {
         if let Some(block) = block {
             self.collect_block(block)
@@ -582,6 +729,8 @@ where
         match expr.kind() {
             ast::ExprKind::LoopExpr(expr) => self.collect_loop(expr),
             ast::ExprKind::WhileExpr(expr) => self.collect_while(expr),
+            ast::ExprKind::ForExpr(expr) => self.collect_for(expr),
+            ast::ExprKind::MatchExpr(expr) => self.collect_match(expr),
             ast::ExprKind::ReturnExpr(r) => self.collect_return(r),
             ast::ExprKind::BreakExpr(r) => self.collect_break(r),
             ast::ExprKind::BlockExpr(b) => self.collect_block(b),
@@ -590,6 +739,7 @@ where
                     let lit = Literal::Bool(e.token().kind() == T![true]);
                     self.alloc_expr(Expr::Literal(lit), syntax_ptr)
                 }
+                ast::LiteralKind::Nil => self.alloc_expr(Expr::Literal(Literal::Nil), syntax_ptr),
                 ast::LiteralKind::IntNumber => {
                     let (text, suffix) = e.text_and_suffix();
                     let (lit, errors) = integer_lit(&text, suffix.as_ref().map(SmolStr::as_str));
@@ -615,8 +765,15 @@ where
                     expr_id
                 }
                 ast::LiteralKind::String => {
-                    let lit = Literal::String(Default::default());
-                    self.alloc_expr(Expr::Literal(lit), syntax_ptr)
+                    let (lit, errors) = string_lit(e.token().text());
+                    let expr_id = self.alloc_expr(Expr::Literal(lit), syntax_ptr);
+
+                    for err in errors {
+                        self.diagnostics
+                            .push(ExprDiagnostic::LiteralError { expr: expr_id, err })
+                    }
+
+                    expr_id
                 }
             },
             ast::ExprKind::PrefixExpr(e) => {
@@ -738,6 +895,15 @@ where
                     self.alloc_expr(Expr::BinaryOp { lhs, rhs, op: None }, syntax_ptr)
                 }
             }
+            ast::ExprKind::RangeExpr(e) => {
+                let start = self.collect_expr_opt(e.start());
+                let end = self.collect_expr_opt(e.end());
+                if let Some(op) = e.op_kind() {
+                    self.alloc_expr(Expr::Range { start, end, op }, syntax_ptr)
+                } else {
+                    self.alloc_expr(Expr::Missing, syntax_ptr)
+                }
+            }
             ast::ExprKind::PathExpr(e) => {
                 let path = e
                     .path()
@@ -838,6 +1004,74 @@ where
                 };
                 self.alloc_expr(Expr::Call { callee, args }, syntax_ptr)
             }
+            ast::ExprKind::MethodCallExpr(e) => {
+                let receiver = self.collect_expr_opt(e.expr());
+                let args = if let Some(arg_list) = e.arg_list() {
+                    arg_list.args().map(|e| self.collect_expr(e)).collect()
+                } else {
+                    Vec::new()
+                };
+                let method_name = e
+                    .name_ref()
+                    .map(|nr| nr.as_name())
+                    .unwrap_or_else(Name::missing);
+                self.alloc_expr(
+                    Expr::MethodCall {
+                        receiver,
+                        method_name,
+                        args,
+                    },
+                    syntax_ptr,
+                )
+            }
+            ast::ExprKind::IndexExpr(e) => {
+                let base = self.collect_expr_opt(e.base());
+                let index = self.collect_expr_opt(e.index());
+                self.alloc_expr(Expr::Index { base, index }, syntax_ptr)
+            }
+            ast::ExprKind::ArrayExpr(e) => {
+                let exprs = e.exprs().map(|e| self.collect_expr(e)).collect();
+                self.alloc_expr(Expr::Array(exprs), syntax_ptr)
+            }
+            ast::ExprKind::TupleExpr(e) => {
+                let exprs = e.exprs().map(|e| self.collect_expr(e)).collect();
+                self.alloc_expr(Expr::Tuple(exprs), syntax_ptr)
+            }
+            ast::ExprKind::CastExpr(e) => {
+                let expr = self.collect_expr_opt(e.expr());
+                let type_ref = self
+                    .type_ref_builder
+                    .alloc_from_node_opt(e.type_ref().as_ref());
+                self.alloc_expr(Expr::Cast { expr, type_ref }, syntax_ptr)
+            }
+            ast::ExprKind::ClosureExpr(e) => {
+                let mut args = Vec::new();
+                let mut arg_types = Vec::new();
+                if let Some(param_list) = e.param_list() {
+                    for param in param_list.params() {
+                        args.push(self.collect_pat_opt(param.pat()));
+                        arg_types.push(
+                            param
+                                .ascribed_type()
+                                .map(|t| self.type_ref_builder.alloc_from_node(&t)),
+                        );
+                    }
+                }
+                let ret_type = e
+                    .ret_type()
+                    .and_then(|rt| rt.type_ref())
+                    .map(|t| self.type_ref_builder.alloc_from_node(&t));
+                let body = self.collect_expr_opt(e.body());
+                self.alloc_expr(
+                    Expr::Lambda {
+                        args,
+                        arg_types,
+                        ret_type,
+                        body,
+                    },
+                    syntax_ptr,
+                )
+            }
         }
     }
 
@@ -866,6 +1100,38 @@ where
                 Pat::Bind { name }
             }
             ast::PatKind::PlaceholderPat(_) => Pat::Wild,
+            ast::PatKind::PathPat(p) => match p.path().and_then(Path::from_ast) {
+                Some(path) => Pat::Path(path),
+                None => Pat::Missing,
+            },
+            ast::PatKind::TuplePat(p) => {
+                Pat::Tuple(p.args().map(|p| self.collect_pat(p)).collect())
+            }
+            ast::PatKind::TupleStructPat(p) => {
+                let path = p.path().and_then(Path::from_ast);
+                let args = p.args().map(|p| self.collect_pat(p)).collect();
+                Pat::TupleStruct { path, args }
+            }
+            ast::PatKind::RecordPat(p) => {
+                let path = p.path().and_then(Path::from_ast);
+                let args = p
+                    .record_field_pat_list()
+                    .into_iter()
+                    .flat_map(|list| list.fields())
+                    .filter_map(|field| {
+                        // Either an explicit field name (`h: height`) or the shorthand binding
+                        // (`w`) names the field
+                        let name = match (field.name_ref(), field.pat().map(|p| p.kind())) {
+                            (Some(name_ref), _) => name_ref.as_name(),
+                            (None, Some(ast::PatKind::BindPat(bp))) => bp.name()?.as_name(),
+                            _ => return None,
+                        };
+                        let pat = self.collect_pat_opt(field.pat());
+                        Some(RecordFieldPat { name, pat })
+                    })
+                    .collect();
+                Pat::Record { path, args }
+            }
         };
         let ptr = AstPtr::new(&pat);
         self.alloc_pat(pattern, ptr)
@@ -896,6 +1162,42 @@ where
         self.alloc_expr(Expr::While { condition, body }, syntax_node_ptr)
     }
 
+    fn collect_for(&mut self, expr: ast::ForExpr) -> ExprId {
+        let syntax_node_ptr = AstPtr::new(&expr.clone().into());
+        let pat = self.collect_pat_opt(expr.pat());
+        let iterable = self.collect_expr_opt(expr.iterable());
+        let body = self.collect_block_opt(expr.loop_body());
+        self.alloc_expr(
+            Expr::For {
+                pat,
+                iterable,
+                body,
+            },
+            syntax_node_ptr,
+        )
+    }
+
+    fn collect_match(&mut self, expr: ast::MatchExpr) -> ExprId {
+        let syntax_node_ptr = AstPtr::new(&expr.clone().into());
+        let scrutinee = self.collect_expr_opt(expr.expr());
+        let arms = expr
+            .match_arm_list()
+            .into_iter()
+            .flat_map(|list| list.arms())
+            .map(|arm| MatchArm {
+                pat: self.collect_pat_opt(arm.pat()),
+                expr: self.collect_expr_opt(arm.expr()),
+            })
+            .collect();
+        self.alloc_expr(
+            Expr::Match {
+                expr: scrutinee,
+                arms,
+            },
+            syntax_node_ptr,
+        )
+    }
+
     fn finish(mut self) -> (Body, BodySourceMap) {
         let (type_refs, type_ref_source_map) = self.type_ref_builder.finish();
         let body = Body {
@@ -927,6 +1229,16 @@ pub(crate) fn body_with_source_map_query(
             collector = ExprCollector::new(def, src.file_id, db);
             collector.collect_fn_body(&src.value)
         }
+        DefWithBody::Const(ref c) => {
+            let src = c.source(db);
+            collector = ExprCollector::new(def, src.file_id, db);
+            collector.collect_global_body(src.value.body(), src.value.ascribed_type())
+        }
+        DefWithBody::Static(ref s) => {
+            let src = s.source(db);
+            collector = ExprCollector::new(def, src.file_id, db);
+            collector.collect_global_body(src.value.body(), src.value.ascribed_type())
+        }
     }
 
     let (body, source_map) = collector.finish();
@@ -1055,11 +1367,50 @@ fn integer_lit(str: &str, suffix: Option<&str>) -> (Literal, Vec<LiteralError>)
     (Literal::Int(LiteralInt { kind, value }), errors)
 }
 
+/// Parses the given quoted string into a string literal, replacing escape sequences with the
+/// characters they represent
+pub(crate) fn string_lit(str: &str) -> (Literal, Vec<LiteralError>) {
+    // The lexer does not require a string to be terminated
+    let quote = str.chars().next();
+    let terminated = str.len() >= 2 && quote.map_or(false, |quote| str.ends_with(quote));
+    if !terminated {
+        return (
+            Literal::String(String::new()),
+            vec![LiteralError::LexerError],
+        );
+    }
+
+    let mut errors = Vec::new();
+    let mut value = String::with_capacity(str.len() - 2);
+    let mut chars = str[1..str.len() - 1].chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some('t') => value.push('\t'),
+            Some('0') => value.push('\0'),
+            Some(c @ '\\') | Some(c @ '"') | Some(c @ '\'') => value.push(c),
+            Some(c) => {
+                errors.push(LiteralError::LexerError);
+                value.push(c);
+            }
+            None => errors.push(LiteralError::LexerError),
+        }
+    }
+
+    (Literal::String(value), errors)
+}
+
 #[cfg(test)]
 mod test {
     use crate::builtin_type::{BuiltinFloat, BuiltinInt};
     use crate::expr::{float_lit, LiteralError, LiteralFloat, LiteralFloatKind};
-    use crate::expr::{integer_lit, LiteralInt, LiteralIntKind};
+    use crate::expr::{integer_lit, string_lit, LiteralInt, LiteralIntKind};
     use crate::Literal;
     use mun_syntax::SmolStr;
 
@@ -1371,6 +1722,39 @@ mod test {
             )
         );
     }
+
+    #[test]
+    fn test_string_literals() {
+        assert_eq!(
+            string_lit(r#""Hello, world!""#),
+            (Literal::String("Hello, world!".to_owned()), vec![])
+        );
+        assert_eq!(
+            string_lit(r#"'single quoted'"#),
+            (Literal::String("single quoted".to_owned()), vec![])
+        );
+        assert_eq!(
+            string_lit(r#""tab\tnewline\nquote\"backslash\\""#),
+            (
+                Literal::String("tab\tnewline\nquote\"backslash\\".to_owned()),
+                vec![]
+            )
+        );
+        assert_eq!(
+            string_lit(r#""unknown \escape""#),
+            (
+                Literal::String("unknown escape".to_owned()),
+                vec![LiteralError::LexerError]
+            )
+        );
+        assert_eq!(
+            string_lit(r#""unterminated"#),
+            (
+                Literal::String(String::new()),
+                vec![LiteralError::LexerError]
+            )
+        );
+    }
 }
 
 mod diagnostics {
@@ -0,0 +1,394 @@
+//! Evaluation of the initializers of constants and global variables at compile time.
+
+use super::{ArithOp, BinaryOp, CmpOp, Expr, ExprId, Literal, LogicOp, Ordering, UnaryOp};
+use crate::code_model::{Const, DefWithBody};
+use crate::resolve::Resolution;
+use crate::ty::ResolveBitness;
+use crate::{
+    resolver_for_expr, ty_app, Body, FloatBitness, FloatTy, HirDatabase, InferenceResult,
+    IntBitness, IntTy, ModuleDef, Path, Signedness, Ty, TypeCtor,
+};
+use std::sync::Arc;
+
+/// A value that is computed at compile time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConstValue {
+    Bool(bool),
+    /// An integer value. Unsigned 128-bit integers are stored with the same bit pattern.
+    Int(i128),
+    Float(f64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ConstEvalError {
+    /// The expression cannot be evaluated at compile time
+    NotConstant(ExprId),
+    /// The expression divides by zero
+    DivisionByZero(ExprId),
+    /// The expression refers to a constant whose value depends on itself
+    Cycle(ExprId),
+    /// The literal does not fit in its type
+    LiteralOutOfRange(ExprId, IntTy),
+    /// The body contains an error that has already been reported, e.g. by type inference.
+    Invalid,
+}
+
+/// Evaluates the body of a constant or the initializer of a global variable.
+pub(crate) fn eval_body(
+    db: &impl HirDatabase,
+    def: DefWithBody,
+) -> Result<ConstValue, ConstEvalError> {
+    ConstEvaluator::new(db, def, Vec::new()).eval()
+}
+
+struct ConstEvaluator<'a, DB: HirDatabase> {
+    db: &'a DB,
+    body: Arc<Body>,
+    infer: Arc<InferenceResult>,
+    /// The constants that are being evaluated, used to detect cycles.
+    stack: Vec<Const>,
+}
+
+impl<'a, DB: HirDatabase> ConstEvaluator<'a, DB> {
+    fn new(db: &'a DB, def: DefWithBody, mut stack: Vec<Const>) -> Self {
+        if let DefWithBody::Const(c) = def {
+            stack.push(c);
+        }
+        ConstEvaluator {
+            db,
+            body: def.body(db),
+            infer: def.infer(db),
+            stack,
+        }
+    }
+
+    fn eval(&self) -> Result<ConstValue, ConstEvalError> {
+        self.eval_expr(self.body.body_expr())
+    }
+
+    fn eval_expr(&self, expr: ExprId) -> Result<ConstValue, ConstEvalError> {
+        let value = match &self.body[expr] {
+            Expr::Literal(literal) => self.eval_literal(expr, literal)?,
+            Expr::Path(path) => self.eval_path(expr, path)?,
+            Expr::UnaryOp { expr: operand, op } => match (op, self.eval_expr(*operand)?) {
+                (UnaryOp::Neg, ConstValue::Int(value)) => ConstValue::Int(value.wrapping_neg()),
+                (UnaryOp::Neg, ConstValue::Float(value)) => ConstValue::Float(-value),
+                (UnaryOp::Not, ConstValue::Int(value)) => ConstValue::Int(!value),
+                (UnaryOp::Not, ConstValue::Bool(value)) => ConstValue::Bool(!value),
+                _ => return Err(ConstEvalError::Invalid),
+            },
+            Expr::BinaryOp {
+                lhs,
+                rhs,
+                op: Some(op),
+            } => self.eval_binary_op(expr, *lhs, *rhs, *op)?,
+            Expr::Cast { expr: operand, .. } => {
+                let value = self.eval_expr(*operand)?;
+                self.eval_cast(value, &self.infer[*operand], &self.infer[expr])?
+            }
+            Expr::Block { statements, tail } if statements.is_empty() => match tail {
+                Some(tail) => self.eval_expr(*tail)?,
+                None => return Err(ConstEvalError::NotConstant(expr)),
+            },
+            Expr::Missing => return Err(ConstEvalError::Invalid),
+            _ => return Err(ConstEvalError::NotConstant(expr)),
+        };
+
+        // Truncate the value to its type
+        match (&self.infer[expr], value) {
+            (ty_app!(TypeCtor::Int(int_ty)), ConstValue::Int(value)) => {
+                Ok(ConstValue::Int(wrap_int(value, self.resolve_int(*int_ty))))
+            }
+            (ty_app!(TypeCtor::Float(float_ty)), ConstValue::Float(value)) => {
+                Ok(ConstValue::Float(round_float(value, *float_ty)))
+            }
+            (ty_app!(TypeCtor::Bool), ConstValue::Bool(_)) => Ok(value),
+            (Ty::Unknown, _) => Err(ConstEvalError::Invalid),
+            _ => Err(ConstEvalError::NotConstant(expr)),
+        }
+    }
+
+    fn eval_literal(&self, expr: ExprId, literal: &Literal) -> Result<ConstValue, ConstEvalError> {
+        match literal {
+            Literal::Bool(value) => Ok(ConstValue::Bool(*value)),
+            Literal::Int(literal) => match &self.infer[expr] {
+                ty_app!(TypeCtor::Int(int_ty)) => {
+                    let int_ty = self.resolve_int(*int_ty);
+                    if literal.value > int_ty.max() {
+                        Err(ConstEvalError::LiteralOutOfRange(expr, int_ty))
+                    } else {
+                        Ok(ConstValue::Int(literal.value as i128))
+                    }
+                }
+                _ => Err(ConstEvalError::Invalid),
+            },
+            Literal::Float(literal) => Ok(ConstValue::Float(literal.value)),
+            Literal::String(_) | Literal::Nil => Err(ConstEvalError::NotConstant(expr)),
+        }
+    }
+
+    /// Evaluates a path to another constant by evaluating the body of that constant.
+    fn eval_path(&self, expr: ExprId, path: &Path) -> Result<ConstValue, ConstEvalError> {
+        let resolver = resolver_for_expr(self.body.clone(), self.db, expr);
+        let c = match resolver
+            .resolve_path_without_assoc_items(self.db, path)
+            .take_values()
+        {
+            Some(Resolution::Def(ModuleDef::Const(c))) => c,
+            Some(_) => return Err(ConstEvalError::NotConstant(expr)),
+            None => return Err(ConstEvalError::Invalid),
+        };
+
+        if self.stack.contains(&c) {
+            return Err(ConstEvalError::Cycle(expr));
+        }
+
+        // Errors in the body of the other constant are reported for that constant
+        ConstEvaluator::new(self.db, c.into(), self.stack.clone())
+            .eval()
+            .map_err(|err| match err {
+                ConstEvalError::Cycle(_) => ConstEvalError::Cycle(expr),
+                _ => ConstEvalError::Invalid,
+            })
+    }
+
+    fn eval_binary_op(
+        &self,
+        expr: ExprId,
+        lhs: ExprId,
+        rhs: ExprId,
+        op: BinaryOp,
+    ) -> Result<ConstValue, ConstEvalError> {
+        let lhs_value = self.eval_expr(lhs)?;
+        let rhs_value = self.eval_expr(rhs)?;
+        match (lhs_value, rhs_value) {
+            (ConstValue::Bool(a), ConstValue::Bool(b)) => match op {
+                BinaryOp::LogicOp(LogicOp::And) => Ok(ConstValue::Bool(a && b)),
+                BinaryOp::LogicOp(LogicOp::Or) => Ok(ConstValue::Bool(a || b)),
+                BinaryOp::CmpOp(CmpOp::Eq { negated }) => Ok(ConstValue::Bool((a == b) != negated)),
+                BinaryOp::Assignment { .. } => Err(ConstEvalError::NotConstant(expr)),
+                _ => Err(ConstEvalError::Invalid),
+            },
+            (ConstValue::Int(a), ConstValue::Int(b)) => {
+                let signed = match &self.infer[lhs] {
+                    ty_app!(TypeCtor::Int(int_ty)) => int_ty.signedness.is_signed(),
+                    _ => return Err(ConstEvalError::Invalid),
+                };
+                match op {
+                    BinaryOp::ArithOp(op) => eval_int_arith_op(expr, a, b, op, signed),
+                    BinaryOp::CmpOp(op) => Ok(ConstValue::Bool(if signed {
+                        compare(a, b, op)
+                    } else {
+                        compare(a as u128, b as u128, op)
+                    })),
+                    BinaryOp::Assignment { .. } => Err(ConstEvalError::NotConstant(expr)),
+                    BinaryOp::LogicOp(_) => Err(ConstEvalError::Invalid),
+                }
+            }
+            (ConstValue::Float(a), ConstValue::Float(b)) => match op {
+                BinaryOp::ArithOp(ArithOp::Add) => Ok(ConstValue::Float(a + b)),
+                BinaryOp::ArithOp(ArithOp::Subtract) => Ok(ConstValue::Float(a - b)),
+                BinaryOp::ArithOp(ArithOp::Multiply) => Ok(ConstValue::Float(a * b)),
+                BinaryOp::ArithOp(ArithOp::Divide) => Ok(ConstValue::Float(a / b)),
+                BinaryOp::ArithOp(ArithOp::Remainder) => Ok(ConstValue::Float(a % b)),
+                BinaryOp::CmpOp(op) => Ok(ConstValue::Bool(compare(a, b, op))),
+                BinaryOp::Assignment { .. } => Err(ConstEvalError::NotConstant(expr)),
+                _ => Err(ConstEvalError::Invalid),
+            },
+            _ => Err(ConstEvalError::Invalid),
+        }
+    }
+
+    /// Evaluates a numeric cast. Like in Rust, casting a floating-point value to an integer
+    /// saturates.
+    fn eval_cast(
+        &self,
+        value: ConstValue,
+        from_ty: &Ty,
+        to_ty: &Ty,
+    ) -> Result<ConstValue, ConstEvalError> {
+        let result = match (value, to_ty) {
+            (ConstValue::Bool(value), ty_app!(TypeCtor::Int(_))) => ConstValue::Int(value as i128),
+            (ConstValue::Int(value), ty_app!(TypeCtor::Int(_))) => ConstValue::Int(value),
+            (ConstValue::Int(value), ty_app!(TypeCtor::Float(_))) => match from_ty {
+                ty_app!(TypeCtor::Int(int_ty)) if !int_ty.signedness.is_signed() => {
+                    ConstValue::Float(value as u128 as f64)
+                }
+                _ => ConstValue::Float(value as f64),
+            },
+            (ConstValue::Float(value), ty_app!(TypeCtor::Int(int_ty))) => {
+                let int_ty = self.resolve_int(*int_ty);
+                ConstValue::Int(if int_ty.signedness.is_signed() {
+                    let (min, max) = signed_bounds(int_ty.bitness);
+                    (value as i128).max(min).min(max)
+                } else {
+                    (value as u128).min(int_ty.max()) as i128
+                })
+            }
+            (ConstValue::Float(value), ty_app!(TypeCtor::Float(_))) => ConstValue::Float(value),
+            (ConstValue::Bool(_), ty_app!(TypeCtor::Bool)) => value,
+            _ => return Err(ConstEvalError::Invalid),
+        };
+        Ok(result)
+    }
+
+    fn resolve_int(&self, int_ty: IntTy) -> IntTy {
+        int_ty.resolve(&self.db.target_data_layout())
+    }
+}
+
+fn eval_int_arith_op(
+    expr: ExprId,
+    a: i128,
+    b: i128,
+    op: ArithOp,
+    signed: bool,
+) -> Result<ConstValue, ConstEvalError> {
+    let result = match op {
+        ArithOp::Add => a.wrapping_add(b),
+        ArithOp::Subtract => a.wrapping_sub(b),
+        ArithOp::Multiply => a.wrapping_mul(b),
+        ArithOp::Divide | ArithOp::Remainder if b == 0 => {
+            return Err(ConstEvalError::DivisionByZero(expr))
+        }
+        ArithOp::Divide if signed => a.wrapping_div(b),
+        ArithOp::Divide => ((a as u128) / (b as u128)) as i128,
+        ArithOp::Remainder if signed => a.wrapping_rem(b),
+        ArithOp::Remainder => ((a as u128) % (b as u128)) as i128,
+        ArithOp::LeftShift => a.wrapping_shl(b as u32),
+        ArithOp::RightShift if signed => a.wrapping_shr(b as u32),
+        ArithOp::RightShift => (a as u128).wrapping_shr(b as u32) as i128,
+        ArithOp::BitAnd => a & b,
+        ArithOp::BitOr => a | b,
+        ArithOp::BitXor => a ^ b,
+    };
+    Ok(ConstValue::Int(result))
+}
+
+fn compare<T: PartialOrd>(a: T, b: T, op: CmpOp) -> bool {
+    match op {
+        CmpOp::Eq { negated } => (a == b) != negated,
+        CmpOp::Ord {
+            ordering: Ordering::Less,
+            strict,
+        } => {
+            if strict {
+                a < b
+            } else {
+                a <= b
+            }
+        }
+        CmpOp::Ord {
+            ordering: Ordering::Greater,
+            strict,
+        } => {
+            if strict {
+                a > b
+            } else {
+                a >= b
+            }
+        }
+    }
+}
+
+/// Returns the number of bits of a resolved integer bitness.
+fn bits(bitness: IntBitness) -> u32 {
+    match bitness {
+        IntBitness::X8 => 8,
+        IntBitness::X16 => 16,
+        IntBitness::X32 => 32,
+        IntBitness::X64 => 64,
+        IntBitness::X128 => 128,
+        IntBitness::Xsize => unreachable!("cannot determine the size of variable bitness"),
+    }
+}
+
+/// Returns the minimum and maximum value of a signed integer type.
+fn signed_bounds(bitness: IntBitness) -> (i128, i128) {
+    let bits = bits(bitness);
+    let max = (((1u128) << (bits - 1)) - 1) as i128;
+    (-max - 1, max)
+}
+
+/// Wraps an integer value around the bounds of its type.
+fn wrap_int(value: i128, int_ty: IntTy) -> i128 {
+    let bits = bits(int_ty.bitness);
+    if bits == 128 {
+        return value;
+    }
+
+    let value = value & ((1i128 << bits) - 1);
+    if int_ty.signedness == Signedness::Signed && value >= 1i128 << (bits - 1) {
+        value - (1i128 << bits)
+    } else {
+        value
+    }
+}
+
+/// Rounds a floating-point value to the precision of its type.
+fn round_float(value: f64, float_ty: FloatTy) -> f64 {
+    match float_ty.bitness {
+        FloatBitness::X32 => value as f32 as f64,
+        FloatBitness::X64 => value,
+    }
+}
+
+mod diagnostics {
+    use super::ConstEvalError;
+    use crate::code_model::DefWithBody;
+    use crate::diagnostics::{
+        CyclicConstant, DiagnosticSink, DivisionByZero, LiteralOutOfRange, NotConstant,
+    };
+    use crate::HirDatabase;
+
+    impl ConstEvalError {
+        pub(crate) fn add_to(
+            &self,
+            db: &impl HirDatabase,
+            owner: DefWithBody,
+            sink: &mut DiagnosticSink,
+        ) {
+            let file = owner.module(db).file_id();
+            let source_map = owner.body_source_map(db);
+            let expr_syntax = |expr| {
+                source_map
+                    .expr_syntax(expr)
+                    .expect("could not retrieve expr from source map")
+                    .value
+                    .either(|ptr| ptr.syntax_node_ptr(), |ptr| ptr.syntax_node_ptr())
+            };
+
+            match self {
+                ConstEvalError::NotConstant(expr) => sink.push(NotConstant {
+                    file,
+                    expr: expr_syntax(*expr),
+                }),
+                ConstEvalError::DivisionByZero(expr) => sink.push(DivisionByZero {
+                    file,
+                    expr: expr_syntax(*expr),
+                }),
+                ConstEvalError::Cycle(expr) => sink.push(CyclicConstant {
+                    file,
+                    expr: expr_syntax(*expr),
+                }),
+                ConstEvalError::LiteralOutOfRange(expr, int_ty) => {
+                    let literal = source_map
+                        .expr_syntax(*expr)
+                        .expect("could not retrieve expr from source map")
+                        .map(|expr_src| {
+                            expr_src
+                                .left()
+                                .expect("could not retrieve expr from ExprSource")
+                                .cast()
+                                .expect("could not cast expression to literal")
+                        });
+                    sink.push(LiteralOutOfRange {
+                        literal,
+                        int_ty: *int_ty,
+                    })
+                }
+                ConstEvalError::Invalid => {}
+            }
+        }
+    }
+}
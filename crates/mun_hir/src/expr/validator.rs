@@ -1,5 +1,8 @@
 use crate::code_model::src::HasSource;
-use crate::diagnostics::{ExternCannotHaveBody, ExternNonPrimitiveParam};
+use crate::diagnostics::{
+    ExternCannotHaveBody, ExternNonPrimitiveParam, SelfParamOutsideImpl,
+    TraitFunctionCannotHaveBody,
+};
 use crate::expr::BodySourceMap;
 use crate::in_file::InFile;
 use crate::{diagnostics::DiagnosticSink, Body, Expr, Function, HirDatabase, InferenceResult};
@@ -7,6 +10,7 @@ use mun_syntax::{AstNode, SyntaxNodePtr};
 use std::sync::Arc;
 
 mod literal_out_of_range;
+mod match_exhaustiveness;
 mod uninitialized_access;
 
 #[cfg(test)]
@@ -35,7 +39,41 @@ impl<'d, DB: HirDatabase> ExprValidator<'d, DB> {
     pub fn validate_body(&self, sink: &mut DiagnosticSink) {
         self.validate_literal_ranges(sink);
         self.validate_uninitialized_access(sink);
+        self.validate_match_exhaustiveness(sink);
         self.validate_extern(sink);
+        self.validate_self_param(sink);
+        self.validate_trait_function(sink);
+    }
+
+    /// Validates that a `self` parameter is only used by functions of `impl` blocks and traits.
+    pub fn validate_self_param(&self, sink: &mut DiagnosticSink) {
+        if self.func.impl_block(self.db).is_some() || self.func.trait_def(self.db).is_some() {
+            return;
+        }
+
+        let src = self.func.source(self.db);
+        if let Some(self_param) = src.value.param_list().and_then(|p| p.self_param()) {
+            sink.push(SelfParamOutsideImpl {
+                self_param: InFile::new(src.file_id, SyntaxNodePtr::new(self_param.syntax())),
+            })
+        }
+    }
+
+    /// Validates that a function that is declared in a trait has no body.
+    pub fn validate_trait_function(&self, sink: &mut DiagnosticSink) {
+        if self.func.trait_def(self.db).is_none() {
+            return;
+        }
+
+        match self.body[self.func.body(self.db).body_expr] {
+            Expr::Missing => {}
+            _ => sink.push(TraitFunctionCannotHaveBody {
+                func: self
+                    .func
+                    .source(self.db)
+                    .map(|f| SyntaxNodePtr::new(f.syntax())),
+            }),
+        }
     }
 
     pub fn validate_extern(&self, sink: &mut DiagnosticSink) {
@@ -57,12 +95,20 @@ impl<'d, DB: HirDatabase> ExprValidator<'d, DB> {
         if let Some(sig) = self.func.ty(self.db).callable_sig(self.db) {
             let fn_data = self.func.data(self.db);
             for (arg_ty, ty_ref) in sig.params().iter().zip(fn_data.params()) {
-                if arg_ty.as_struct().is_some() {
-                    let arg_ptr = fn_data
-                        .type_ref_source_map()
-                        .type_ref_syntax(*ty_ref)
-                        .map(|ptr| ptr.syntax_node_ptr())
-                        .unwrap();
+                if arg_ty.as_struct().is_some() || arg_ty.as_enum().is_some() {
+                    // The type of a `self` parameter has no syntax of its own
+                    let arg_ptr = match fn_data.type_ref_source_map().type_ref_syntax(*ty_ref) {
+                        Some(ptr) => ptr.syntax_node_ptr(),
+                        None => SyntaxNodePtr::new(
+                            self.func
+                                .source(self.db)
+                                .value
+                                .param_list()
+                                .and_then(|p| p.self_param())
+                                .unwrap()
+                                .syntax(),
+                        ),
+                    };
                     sink.push(ExternNonPrimitiveParam {
                         param: InFile::new(self.func.source(self.db).file_id, arg_ptr),
                     })
@@ -70,7 +116,7 @@ impl<'d, DB: HirDatabase> ExprValidator<'d, DB> {
             }
 
             let return_ty = sig.ret();
-            if return_ty.as_struct().is_some() {
+            if return_ty.as_struct().is_some() || return_ty.as_enum().is_some() {
                 let arg_ptr = fn_data
                     .type_ref_source_map()
                     .type_ref_syntax(*fn_data.ret_type())
@@ -0,0 +1,180 @@
+use super::ExprValidator;
+use crate::diagnostics::{DiagnosticSink, MissingMatchArms};
+use crate::expr::{ExprId, MatchArm, Pat, PatId};
+use crate::{EnumVariant, Expr, HirDatabase, Ty};
+
+/// A row of patterns that is matched against a row of values. A `None` entry is a wildcard that
+/// was introduced by expanding the fields of an enum variant that were not matched explicitly.
+type PatRow = Vec<Option<PatId>>;
+
+impl<'d, D: HirDatabase> ExprValidator<'d, D> {
+    /// Iterates over all match expressions to determine if all possible values of the matched
+    /// expression are covered by at least one of the arms.
+    pub fn validate_match_exhaustiveness(&self, sink: &mut DiagnosticSink) {
+        self.validate_match_exhaustiveness_expr(self.body.body_expr, sink);
+    }
+
+    fn validate_match_exhaustiveness_expr(&self, expr: ExprId, sink: &mut DiagnosticSink) {
+        if let Expr::Match {
+            expr: scrutinee,
+            arms,
+        } = &self.body[expr]
+        {
+            let missing_patterns = self.missing_match_arms(*scrutinee, arms);
+            if !missing_patterns.is_empty() {
+                sink.push(MissingMatchArms {
+                    file: self.func.module(self.db).file_id(),
+                    match_expr: self
+                        .body_source_map
+                        .expr_syntax(expr)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr()),
+                    missing_patterns,
+                });
+            }
+        }
+
+        self.body[expr]
+            .walk_child_exprs(|expr| self.validate_match_exhaustiveness_expr(expr, sink));
+    }
+
+    /// Returns a textual representation of the patterns that are not covered by the arms of a
+    /// match expression.
+    fn missing_match_arms(&self, scrutinee: ExprId, arms: &[MatchArm]) -> Vec<String> {
+        let ty = &self.infer[scrutinee];
+        if *ty == Ty::Unknown {
+            // An error has already been reported for the scrutinee
+            return Vec::new();
+        }
+
+        let rows: Vec<PatRow> = arms.iter().map(|arm| vec![Some(arm.pat)]).collect();
+        match ty.as_enum() {
+            Some(e) => e
+                .variants(self.db)
+                .into_iter()
+                .filter(|variant| !self.is_variant_covered(&rows, *variant, &[]))
+                .map(|variant| format!("{}::{}", e.name(self.db), variant.name(self.db)))
+                .collect(),
+            _ if self.is_covered(&rows, &[ty.clone()]) => Vec::new(),
+            _ => vec!["_".to_string()],
+        }
+    }
+
+    /// Returns whether the rows of patterns cover all possible values of the specified types.
+    fn is_covered(&self, rows: &[PatRow], tys: &[Ty]) -> bool {
+        let (ty, rest_tys) = match tys.split_first() {
+            Some(it) => it,
+            None => return !rows.is_empty(),
+        };
+
+        // A tuple is covered if the patterns of its elements, followed by the remaining patterns,
+        // cover the values of its elements and the remaining types.
+        if let Some(element_tys) = ty.as_tuple() {
+            let rows: Vec<PatRow> = rows
+                .iter()
+                .map(|row| {
+                    let mut expanded = self.expand_tuple(row[0], element_tys.len());
+                    expanded.extend_from_slice(&row[1..]);
+                    expanded
+                })
+                .collect();
+            let tys: Vec<Ty> = element_tys.iter().chain(rest_tys.iter()).cloned().collect();
+            return self.is_covered(&rows, &tys);
+        }
+
+        match ty.as_enum() {
+            Some(e) if !rows.iter().all(|row| self.is_wildcard(row[0])) => e
+                .variants(self.db)
+                .into_iter()
+                .all(|variant| self.is_variant_covered(rows, variant, rest_tys)),
+            _ => {
+                let rows: Vec<PatRow> = rows
+                    .iter()
+                    .filter(|row| self.is_wildcard(row[0]))
+                    .map(|row| row[1..].to_vec())
+                    .collect();
+                self.is_covered(&rows, rest_tys)
+            }
+        }
+    }
+
+    /// Returns whether all values of the specified enum variant, followed by values of the
+    /// remaining types, are covered by the rows of patterns.
+    fn is_variant_covered(&self, rows: &[PatRow], variant: EnumVariant, rest_tys: &[Ty]) -> bool {
+        let fields = variant.fields(self.db);
+        let rows: Vec<PatRow> = rows
+            .iter()
+            .filter_map(|row| {
+                let mut specialized = self.specialize(row[0], variant)?;
+                specialized.extend_from_slice(&row[1..]);
+                Some(specialized)
+            })
+            .collect();
+        let tys: Vec<Ty> = fields
+            .iter()
+            .map(|field| field.ty(self.db))
+            .chain(rest_tys.iter().cloned())
+            .collect();
+        self.is_covered(&rows, &tys)
+    }
+
+    /// Returns the patterns of the fields of the specified variant if `pat` matches the variant;
+    /// otherwise returns `None`.
+    fn specialize(&self, pat: Option<PatId>, variant: EnumVariant) -> Option<PatRow> {
+        let fields = variant.fields(self.db);
+        if self.is_wildcard(pat) {
+            return Some(vec![None; fields.len()]);
+        }
+
+        let pat = pat?;
+        if self.infer.variant_resolution_for_pat(pat) != Some(variant) {
+            return None;
+        }
+
+        match &self.body[pat] {
+            Pat::TupleStruct { args, .. } => Some(
+                (0..fields.len())
+                    .map(|idx| args.get(idx).copied())
+                    .collect(),
+            ),
+            Pat::Record { args, .. } => Some(
+                fields
+                    .iter()
+                    .map(|field| {
+                        let name = field.name(self.db);
+                        args.iter()
+                            .find(|field_pat| field_pat.name == name)
+                            .map(|field_pat| field_pat.pat)
+                    })
+                    .collect(),
+            ),
+            _ => Some(vec![None; fields.len()]),
+        }
+    }
+
+    /// Returns the patterns of the elements of a tuple with `len` elements. Patterns other than
+    /// tuple patterns match any tuple.
+    fn expand_tuple(&self, pat: Option<PatId>, len: usize) -> PatRow {
+        match pat.map(|pat| &self.body[pat]) {
+            Some(Pat::Tuple(args)) => (0..len).map(|idx| args.get(idx).copied()).collect(),
+            _ => vec![None; len],
+        }
+    }
+
+    /// Returns whether the pattern matches any value. Patterns that could not be resolved are
+    /// treated as wildcards because an error has already been reported for them.
+    fn is_wildcard(&self, pat: Option<PatId>) -> bool {
+        let pat = match pat {
+            Some(pat) => pat,
+            None => return true,
+        };
+        match &self.body[pat] {
+            Pat::Missing | Pat::Wild | Pat::Bind { .. } => true,
+            Pat::Tuple(_) => false,
+            Pat::Path(_) | Pat::TupleStruct { .. } | Pat::Record { .. } => {
+                self.infer.variant_resolution_for_pat(pat).is_none()
+            }
+        }
+    }
+}
@@ -66,6 +66,43 @@ fn test_uninitialized_access_while() {
     )
 }
 
+#[test]
+fn test_match_exhaustiveness() {
+    diagnostics_snapshot(
+        r#"
+    enum Shape {
+        Circle(f32),
+        Rect { w: f32, h: f32 },
+        None,
+    }
+
+    fn foo(s: Shape) {
+        match s {      // `Shape::Rect` is not covered
+            Shape::Circle(r) => {},
+            Shape::None => {},
+        }
+    }
+
+    fn bar(s: Shape) {
+        match s {
+            Shape::Circle(r) => {},
+            _ => {},
+        }
+    }
+
+    fn baz(a: int) -> int {
+        match a {
+            b => b,
+        }
+    }
+
+    fn foz(a: int) {
+        match a {}     // `_` is not covered
+    }
+    "#,
+    )
+}
+
 fn diagnostics(content: &str) -> String {
     let (db, file_id) = MockDatabase::with_single_file(content);
     let source_file = db.parse(file_id).ok().unwrap();
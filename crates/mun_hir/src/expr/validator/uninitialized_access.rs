@@ -18,7 +18,7 @@ impl<'d, D: HirDatabase> ExprValidator<'d, D> {
         // Add all parameter patterns to the set of initialized patterns (they must have been
         // initialized)
         for (pat, _) in self.body.params.iter() {
-            initialized_patterns.insert(*pat);
+            self.add_pattern_bindings(&mut initialized_patterns, *pat);
         }
 
         self.validate_expr_access(
@@ -121,7 +121,7 @@ impl<'d, D: HirDatabase> ExprValidator<'d, D> {
                                     *initializer,
                                     ExprKind::Normal,
                                 );
-                                initialized_patterns.insert(*pat);
+                                self.add_pattern_bindings(initialized_patterns, *pat);
                             }
                         }
                         Statement::Expr(expr) => {
@@ -163,6 +163,38 @@ impl<'d, D: HirDatabase> ExprValidator<'d, D> {
                     ExprKind::Normal,
                 );
             }
+            Expr::For {
+                pat,
+                iterable,
+                body,
+            } => {
+                self.validate_expr_access(sink, initialized_patterns, *iterable, ExprKind::Normal);
+                let mut body_initialized_patterns = initialized_patterns.clone();
+                body_initialized_patterns.insert(*pat);
+                self.validate_expr_access(
+                    sink,
+                    &mut body_initialized_patterns,
+                    *body,
+                    ExprKind::Normal,
+                );
+            }
+            Expr::Match { expr, arms } => {
+                self.validate_expr_access(sink, initialized_patterns, *expr, ExprKind::Normal);
+                for arm in arms.iter() {
+                    let mut arm_initialized_patterns = initialized_patterns.clone();
+                    self.add_pattern_bindings(&mut arm_initialized_patterns, arm.pat);
+                    self.validate_expr_access(
+                        sink,
+                        &mut arm_initialized_patterns,
+                        arm.expr,
+                        ExprKind::Normal,
+                    );
+                }
+            }
+            Expr::Range { start, end, .. } => {
+                self.validate_expr_access(sink, initialized_patterns, *start, ExprKind::Normal);
+                self.validate_expr_access(sink, initialized_patterns, *end, ExprKind::Normal);
+            }
             Expr::RecordLit { fields, spread, .. } => {
                 for field in fields.iter() {
                     self.validate_expr_access(
@@ -176,14 +208,48 @@ impl<'d, D: HirDatabase> ExprValidator<'d, D> {
                     self.validate_expr_access(sink, initialized_patterns, *expr, ExprKind::Normal);
                 }
             }
-            Expr::Field { expr, .. } => {
+            Expr::Field { expr, .. } | Expr::Cast { expr, .. } => {
                 self.validate_expr_access(sink, initialized_patterns, *expr, ExprKind::Normal);
             }
+            Expr::MethodCall { receiver, args, .. } => {
+                self.validate_expr_access(sink, initialized_patterns, *receiver, ExprKind::Normal);
+                for arg in args.iter() {
+                    self.validate_expr_access(sink, initialized_patterns, *arg, ExprKind::Normal);
+                }
+            }
+            Expr::Index { base, index } => {
+                self.validate_expr_access(sink, initialized_patterns, *base, ExprKind::Normal);
+                self.validate_expr_access(sink, initialized_patterns, *index, ExprKind::Normal);
+            }
+            Expr::Array(exprs) | Expr::Tuple(exprs) => {
+                for expr in exprs.iter() {
+                    self.validate_expr_access(sink, initialized_patterns, *expr, ExprKind::Normal);
+                }
+            }
+            Expr::Lambda { args, body, .. } => {
+                // The captured bindings must be initialized when the closure is created
+                let mut body_initialized_patterns = initialized_patterns.clone();
+                for arg in args.iter() {
+                    self.add_pattern_bindings(&mut body_initialized_patterns, *arg);
+                }
+                self.validate_expr_access(
+                    sink,
+                    &mut body_initialized_patterns,
+                    *body,
+                    ExprKind::Normal,
+                );
+            }
             Expr::Literal(_) => {}
             Expr::Missing => {}
         }
     }
 
+    /// Adds the specified pattern and all of its sub-patterns to the set of initialized patterns.
+    fn add_pattern_bindings(&self, initialized_patterns: &mut HashSet<PatId>, pat: PatId) {
+        initialized_patterns.insert(pat);
+        self.body[pat].walk_child_pats(|pat| self.add_pattern_bindings(initialized_patterns, pat));
+    }
+
     fn validate_path_access(
         &self,
         sink: &mut DiagnosticSink,
@@ -206,7 +272,7 @@ impl<'d, D: HirDatabase> ExprValidator<'d, D> {
 
         let pat = match resolution {
             Resolution::LocalBinding(pat) => pat,
-            Resolution::Def(_def) => return,
+            Resolution::Def(_) | Resolution::SelfType(_) | Resolution::GenericParam(_) => return,
         };
 
         if expr_side == ExprKind::Normal || expr_side == ExprKind::Both {
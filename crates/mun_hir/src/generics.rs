@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use crate::code_model::src::HasSource;
+use crate::{name::name, AsName, DefDatabase, Function, Name, Struct};
+use mun_syntax::ast::{self, NameOwner, TypeParamsOwner};
+
+/// A single type parameter of a generic definition, e.g. `T` in `fn max<T>(a: T, b: T) -> T`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GenericParam {
+    pub idx: u32,
+    pub name: Name,
+}
+
+/// The type parameters of a function or struct. The functions of a trait have an implicit `Self`
+/// type parameter that precedes their own type parameters.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct GenericParams {
+    pub params: Vec<GenericParam>,
+}
+
+/// A definition that can have type parameters
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum GenericDef {
+    Function(Function),
+    Struct(Struct),
+}
+impl_froms!(GenericDef: Function, Struct);
+
+impl GenericParams {
+    pub(crate) fn generic_params_query(
+        db: &impl DefDatabase,
+        def: GenericDef,
+    ) -> Arc<GenericParams> {
+        let mut generics = GenericParams::default();
+        let type_param_list = match def {
+            GenericDef::Function(f) => {
+                let src = f.source(db).value;
+                if src.trait_def().is_some() {
+                    generics.params.push(GenericParam {
+                        idx: 0,
+                        name: name![Self],
+                    });
+                }
+                src.type_param_list()
+            }
+            GenericDef::Struct(s) => s.source(db).value.type_param_list(),
+        };
+
+        if let Some(type_param_list) = type_param_list {
+            generics.fill_params(type_param_list);
+        }
+        Arc::new(generics)
+    }
+
+    fn fill_params(&mut self, params: ast::TypeParamList) {
+        let start = self.params.len();
+        for (idx, type_param) in params.type_params().enumerate() {
+            let name = type_param
+                .name()
+                .map(|n| n.as_name())
+                .unwrap_or_else(Name::missing);
+            self.params.push(GenericParam {
+                idx: (start + idx) as u32,
+                name,
+            });
+        }
+    }
+
+    /// Returns the number of type parameters
+    pub fn len(&self) -> usize {
+        self.params.len()
+    }
+
+    /// Returns true if there are no type parameters, i.e. the definition is not generic.
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+
+    /// Returns the type parameter with the specified `name`, if any.
+    pub fn find_by_name(&self, name: &Name) -> Option<&GenericParam> {
+        self.params.iter().find(|p| &p.name == name)
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use crate::ids::{AstItemDef, ConstId, StaticId};
+use crate::type_ref::{TypeRefBuilder, TypeRefId, TypeRefMap, TypeRefSourceMap};
+use crate::{AsName, DefDatabase, Name, Visibility};
+use mun_syntax::ast::{NameOwner, TypeAscriptionOwner, VisibilityOwner};
+
+/// The data of a constant, e.g. `const GRAVITY: f32 = 9.81;`. The value of a constant is
+/// evaluated at compile time and inlined wherever the constant is used.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ConstData {
+    pub name: Name,
+    pub visibility: Visibility,
+    pub type_ref: TypeRefId,
+    type_ref_map: TypeRefMap,
+    type_ref_source_map: TypeRefSourceMap,
+}
+
+/// The data of a global variable, e.g. `static mut SCORE: i32 = 0;`. The storage of a global
+/// variable lives in the assembly.
+#[derive(Debug, PartialEq, Eq)]
+pub struct StaticData {
+    pub name: Name,
+    pub visibility: Visibility,
+    pub type_ref: TypeRefId,
+    pub is_mut: bool,
+    type_ref_map: TypeRefMap,
+    type_ref_source_map: TypeRefSourceMap,
+}
+
+impl ConstData {
+    pub(crate) fn const_data_query(db: &impl DefDatabase, id: ConstId) -> Arc<ConstData> {
+        let src = id.source(db);
+        let mut type_ref_builder = TypeRefBuilder::default();
+        let name = src
+            .value
+            .name()
+            .map(|n| n.as_name())
+            .unwrap_or_else(Name::missing);
+
+        let visibility = src
+            .value
+            .visibility()
+            .map(|_v| Visibility::Public)
+            .unwrap_or(Visibility::Private);
+
+        let type_ref = type_ref_builder.alloc_from_node_opt(src.value.ascribed_type().as_ref());
+        let (type_ref_map, type_ref_source_map) = type_ref_builder.finish();
+
+        Arc::new(ConstData {
+            name,
+            visibility,
+            type_ref,
+            type_ref_map,
+            type_ref_source_map,
+        })
+    }
+
+    pub fn type_ref_source_map(&self) -> &TypeRefSourceMap {
+        &self.type_ref_source_map
+    }
+
+    pub fn type_ref_map(&self) -> &TypeRefMap {
+        &self.type_ref_map
+    }
+}
+
+impl StaticData {
+    pub(crate) fn static_data_query(db: &impl DefDatabase, id: StaticId) -> Arc<StaticData> {
+        let src = id.source(db);
+        let mut type_ref_builder = TypeRefBuilder::default();
+        let name = src
+            .value
+            .name()
+            .map(|n| n.as_name())
+            .unwrap_or_else(Name::missing);
+
+        let visibility = src
+            .value
+            .visibility()
+            .map(|_v| Visibility::Public)
+            .unwrap_or(Visibility::Private);
+
+        let type_ref = type_ref_builder.alloc_from_node_opt(src.value.ascribed_type().as_ref());
+        let (type_ref_map, type_ref_source_map) = type_ref_builder.finish();
+
+        Arc::new(StaticData {
+            name,
+            visibility,
+            type_ref,
+            is_mut: src.value.is_mut(),
+            type_ref_map,
+            type_ref_source_map,
+        })
+    }
+
+    pub fn type_ref_source_map(&self) -> &TypeRefSourceMap {
+        &self.type_ref_source_map
+    }
+
+    pub fn type_ref_map(&self) -> &TypeRefMap {
+        &self.type_ref_map
+    }
+}
@@ -116,3 +116,73 @@ impl AstItemDef<ast::StructDef> for StructId {
         db.lookup_intern_struct(self)
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EnumId(salsa::InternId);
+impl_intern_key!(EnumId);
+
+impl AstItemDef<ast::EnumDef> for EnumId {
+    fn intern(db: &impl DefDatabase, loc: ItemLoc<ast::EnumDef>) -> Self {
+        db.intern_enum(loc)
+    }
+
+    fn lookup_intern(self, db: &impl DefDatabase) -> ItemLoc<ast::EnumDef> {
+        db.lookup_intern_enum(self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImplId(salsa::InternId);
+impl_intern_key!(ImplId);
+
+impl AstItemDef<ast::ImplBlock> for ImplId {
+    fn intern(db: &impl DefDatabase, loc: ItemLoc<ast::ImplBlock>) -> Self {
+        db.intern_impl(loc)
+    }
+
+    fn lookup_intern(self, db: &impl DefDatabase) -> ItemLoc<ast::ImplBlock> {
+        db.lookup_intern_impl(self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraitId(salsa::InternId);
+impl_intern_key!(TraitId);
+
+impl AstItemDef<ast::TraitDef> for TraitId {
+    fn intern(db: &impl DefDatabase, loc: ItemLoc<ast::TraitDef>) -> Self {
+        db.intern_trait(loc)
+    }
+
+    fn lookup_intern(self, db: &impl DefDatabase) -> ItemLoc<ast::TraitDef> {
+        db.lookup_intern_trait(self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConstId(salsa::InternId);
+impl_intern_key!(ConstId);
+
+impl AstItemDef<ast::ConstDef> for ConstId {
+    fn intern(db: &impl DefDatabase, loc: ItemLoc<ast::ConstDef>) -> Self {
+        db.intern_const(loc)
+    }
+
+    fn lookup_intern(self, db: &impl DefDatabase) -> ItemLoc<ast::ConstDef> {
+        db.lookup_intern_const(self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StaticId(salsa::InternId);
+impl_intern_key!(StaticId);
+
+impl AstItemDef<ast::StaticDef> for StaticId {
+    fn intern(db: &impl DefDatabase, loc: ItemLoc<ast::StaticDef>) -> Self {
+        db.intern_static(loc)
+    }
+
+    fn lookup_intern(self, db: &impl DefDatabase) -> ItemLoc<ast::StaticDef> {
+        db.lookup_intern_static(self)
+    }
+}
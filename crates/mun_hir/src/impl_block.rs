@@ -0,0 +1,394 @@
+use std::sync::Arc;
+
+use crate::code_model::src::HasSource;
+use crate::diagnostics::{
+    ConflictingImpls, DiagnosticSink, DuplicateDefinition, IncompatibleTraitMethod,
+    InvalidImplType, MissingTraitMethods, NotATrait, NotATraitMethod,
+};
+use crate::ids::{AstItemDef, FunctionId, ImplId, LocationCtx};
+use crate::input::SourceRootId;
+use crate::type_ref::{TypeRefBuilder, TypeRefId, TypeRefMap, TypeRefSourceMap};
+use crate::{
+    ApplicationTy, DefDatabase, Function, HirDatabase, ImplBlock, Module, Name, Package, Path,
+    Substs, Trait, Ty, TypeCtor,
+};
+use mun_syntax::{ast, ast::FunctionDefOwner, AstNode, AstPtr, SyntaxNodePtr};
+use rustc_hash::FxHashMap;
+use std::iter;
+
+/// The data of an `impl` block: the type it is defined for, the trait it implements and the
+/// functions it contains.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ImplData {
+    pub target_type: TypeRefId,
+    /// The path to the implemented trait, e.g. `Update` in `impl Update for Enemy`. This is `None`
+    /// if the `impl` block does not implement a trait or if the trait is not referred to by a path.
+    pub target_trait: Option<Path>,
+    target_trait_ptr: Option<AstPtr<ast::TypeRef>>,
+    pub items: Vec<Function>,
+    type_ref_map: TypeRefMap,
+    type_ref_source_map: TypeRefSourceMap,
+}
+
+impl ImplData {
+    pub(crate) fn impl_data_query(db: &impl DefDatabase, id: ImplId) -> Arc<ImplData> {
+        let src = id.source(db);
+        let loc_ctx = LocationCtx::new(db, src.file_id);
+
+        let mut type_ref_builder = TypeRefBuilder::default();
+        let target_type = type_ref_builder.alloc_from_node_opt(src.value.target_type().as_ref());
+
+        // The trait is not a type, so it is not lowered with the other type references
+        let target_trait_ref = src.value.target_trait();
+        let target_trait = target_trait_ref
+            .as_ref()
+            .and_then(|type_ref| match type_ref.kind() {
+                ast::TypeRefKind::PathType(path_type) => path_type.path(),
+                _ => None,
+            })
+            .and_then(Path::from_ast);
+        let target_trait_ptr = target_trait_ref.as_ref().map(AstPtr::new);
+
+        let items = src
+            .value
+            .item_list()
+            .map(|item_list| {
+                item_list
+                    .functions()
+                    .map(|f| Function {
+                        id: FunctionId::from_ast(loc_ctx, &f),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let (type_ref_map, type_ref_source_map) = type_ref_builder.finish();
+        Arc::new(ImplData {
+            target_type,
+            target_trait,
+            target_trait_ptr,
+            items,
+            type_ref_map,
+            type_ref_source_map,
+        })
+    }
+
+    /// Returns true if the `impl` block implements a trait, even if the trait could not be
+    /// resolved.
+    pub fn is_trait_impl(&self) -> bool {
+        self.target_trait_ptr.is_some()
+    }
+
+    pub fn type_ref_source_map(&self) -> &TypeRefSourceMap {
+        &self.type_ref_source_map
+    }
+
+    pub fn type_ref_map(&self) -> &TypeRefMap {
+        &self.type_ref_map
+    }
+}
+
+/// All the `impl` blocks of a package, indexed by the type they are defined for. The `impl` blocks
+/// that implement a trait are also indexed by their trait.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PackageImpls {
+    impls: FxHashMap<TypeCtor, Vec<ImplBlock>>,
+    trait_impls: FxHashMap<(Trait, TypeCtor), ImplBlock>,
+    diagnostics: Vec<ImplDiagnostic>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) enum ImplDiagnostic {
+    /// The type of an `impl` block is not a struct or an enum
+    InvalidImplType { impl_block: ImplBlock },
+    /// Multiple functions with the same name are defined for a single type
+    DuplicateName {
+        name: Name,
+        definition: Function,
+        first_definition: Function,
+    },
+    /// The trait of an `impl` block does not refer to a trait
+    NotATrait { impl_block: ImplBlock },
+    /// A trait is implemented more than once for a single type
+    ConflictingImpls {
+        impl_block: ImplBlock,
+        trait_: Trait,
+    },
+    /// An `impl` block does not implement all functions of its trait
+    MissingTraitMethods {
+        impl_block: ImplBlock,
+        trait_: Trait,
+        names: Vec<Name>,
+    },
+    /// A function of an `impl` block is not declared by its trait
+    NotATraitMethod { function: Function, trait_: Trait },
+    /// The signature of a function of an `impl` block differs from the one declared by its trait
+    IncompatibleTraitMethod { function: Function, trait_: Trait },
+}
+
+impl PackageImpls {
+    pub(crate) fn package_impls_query(
+        db: &impl HirDatabase,
+        source_root: SourceRootId,
+    ) -> Arc<PackageImpls> {
+        let mut impls = PackageImpls::default();
+        let mut function_by_name = FxHashMap::default();
+        for module in Package::from(source_root).modules(db) {
+            for impl_block in module.impl_blocks(db) {
+                let target_ty = impl_block.target_ty(db);
+                let ctor = match target_ty_ctor(&target_ty) {
+                    Some(ctor) => ctor,
+                    // An unresolved type has already been reported
+                    None if target_ty == Ty::Unknown => continue,
+                    None => {
+                        impls
+                            .diagnostics
+                            .push(ImplDiagnostic::InvalidImplType { impl_block });
+                        continue;
+                    }
+                };
+
+                if impl_block.data(db).is_trait_impl() {
+                    let trait_ = match impl_block.target_trait(db) {
+                        Some(trait_) => trait_,
+                        None => {
+                            impls
+                                .diagnostics
+                                .push(ImplDiagnostic::NotATrait { impl_block });
+                            continue;
+                        }
+                    };
+                    if impls.trait_impls.contains_key(&(trait_, ctor)) {
+                        impls
+                            .diagnostics
+                            .push(ImplDiagnostic::ConflictingImpls { impl_block, trait_ });
+                        continue;
+                    }
+                    impls.trait_impls.insert((trait_, ctor), impl_block);
+                    check_trait_impl(db, impl_block, trait_, &target_ty, &mut impls.diagnostics);
+                }
+
+                for function in impl_block.items(db) {
+                    let name = function.name(db);
+                    if let Some(first_definition) = function_by_name.get(&(ctor, name.clone())) {
+                        impls.diagnostics.push(ImplDiagnostic::DuplicateName {
+                            name,
+                            definition: function,
+                            first_definition: *first_definition,
+                        });
+                    } else {
+                        function_by_name.insert((ctor, name), function);
+                    }
+                }
+
+                impls.impls.entry(ctor).or_default().push(impl_block);
+            }
+        }
+        Arc::new(impls)
+    }
+
+    /// Returns all the `impl` blocks that are defined for the type `ty`.
+    pub fn for_ty(&self, ty: &Ty) -> &[ImplBlock] {
+        target_ty_ctor(ty)
+            .and_then(|ctor| self.impls.get(&ctor))
+            .map(|impls| impls.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Returns the function with the specified `name` that is defined for the type `ty`, if any.
+    pub fn lookup_function(&self, db: &impl HirDatabase, ty: &Ty, name: &Name) -> Option<Function> {
+        self.for_ty(ty)
+            .iter()
+            .flat_map(|impl_block| impl_block.items(db))
+            .find(|function| function.name(db) == *name)
+    }
+
+    /// Returns the function that implements the trait function `function` for the type `ty`, if
+    /// `function` is declared in a trait that is implemented for `ty`.
+    pub fn lookup_trait_function(
+        &self,
+        db: &impl HirDatabase,
+        function: Function,
+        ty: &Ty,
+    ) -> Option<Function> {
+        let trait_ = function.trait_def(db)?;
+        let impl_block = self.trait_impls.get(&(trait_, target_ty_ctor(ty)?))?;
+        let name = function.name(db);
+        impl_block
+            .items(db)
+            .into_iter()
+            .find(|function| function.name(db) == name)
+    }
+
+    pub(crate) fn diagnostics(&self) -> &[ImplDiagnostic] {
+        &self.diagnostics
+    }
+}
+
+/// Checks that the `impl` block implements exactly the functions of its trait, with matching
+/// signatures.
+fn check_trait_impl(
+    db: &impl HirDatabase,
+    impl_block: ImplBlock,
+    trait_: Trait,
+    target_ty: &Ty,
+    diagnostics: &mut Vec<ImplDiagnostic>,
+) {
+    let trait_items = trait_.items(db);
+    let items = impl_block.items(db);
+    for function in items.iter().copied() {
+        let name = function.name(db);
+        match trait_items.iter().find(|f| f.name(db) == name) {
+            Some(trait_function) => {
+                if !is_compatible_trait_function(db, *trait_function, function, target_ty) {
+                    diagnostics.push(ImplDiagnostic::IncompatibleTraitMethod { function, trait_ });
+                }
+            }
+            None => diagnostics.push(ImplDiagnostic::NotATraitMethod { function, trait_ }),
+        }
+    }
+
+    let names: Vec<Name> = trait_items
+        .iter()
+        .map(|f| f.name(db))
+        .filter(|name| !items.iter().any(|f| f.name(db) == *name))
+        .collect();
+    if !names.is_empty() {
+        diagnostics.push(ImplDiagnostic::MissingTraitMethods {
+            impl_block,
+            trait_,
+            names,
+        });
+    }
+}
+
+/// Returns true if `function` has the signature of the trait function `trait_function`, of which
+/// the `Self` type is replaced by `self_ty`.
+fn is_compatible_trait_function(
+    db: &impl HirDatabase,
+    trait_function: Function,
+    function: Function,
+    self_ty: &Ty,
+) -> bool {
+    let generic_params = function.generic_params(db);
+    if trait_function.data(db).has_self_param() != function.data(db).has_self_param()
+        || trait_function.generic_params(db).len() != generic_params.len() + 1
+    {
+        return false;
+    }
+
+    // The type parameters of the trait function follow its `Self` type
+    let substs: Substs = iter::once(self_ty.clone())
+        .chain(Substs::identity(&generic_params).iter().cloned())
+        .collect::<Vec<_>>()
+        .into();
+    db.callable_sig(trait_function.into()).subst(&substs) == db.callable_sig(function.into())
+}
+
+/// Returns the type constructor of `ty` if `impl` blocks can be defined for it.
+fn target_ty_ctor(ty: &Ty) -> Option<TypeCtor> {
+    match ty {
+        Ty::Apply(ApplicationTy {
+            ctor: ctor @ TypeCtor::Struct(_),
+            ..
+        })
+        | Ty::Apply(ApplicationTy {
+            ctor: ctor @ TypeCtor::Enum(_),
+            ..
+        }) => Some(*ctor),
+        _ => None,
+    }
+}
+
+impl ImplDiagnostic {
+    pub(crate) fn add_to(&self, db: &impl HirDatabase, owner: Module, sink: &mut DiagnosticSink) {
+        match self {
+            ImplDiagnostic::InvalidImplType { impl_block } => {
+                if impl_block.module(db) != owner {
+                    return;
+                }
+                let data = impl_block.data(db);
+                sink.push(InvalidImplType {
+                    file: owner.file_id,
+                    type_ref: data
+                        .type_ref_source_map()
+                        .type_ref_syntax(data.target_type)
+                        .unwrap(),
+                })
+            }
+            ImplDiagnostic::DuplicateName {
+                name,
+                definition,
+                first_definition,
+            } => {
+                if definition.module(db) != owner {
+                    return;
+                }
+                sink.push(DuplicateDefinition {
+                    file: owner.file_id,
+                    name: name.to_string(),
+                    definition: SyntaxNodePtr::new(definition.source(db).value.syntax()),
+                    first_definition: SyntaxNodePtr::new(
+                        first_definition.source(db).value.syntax(),
+                    ),
+                })
+            }
+            ImplDiagnostic::NotATrait { impl_block } => {
+                if impl_block.module(db) != owner {
+                    return;
+                }
+                sink.push(NotATrait {
+                    file: owner.file_id,
+                    type_ref: impl_block.data(db).target_trait_ptr.unwrap(),
+                })
+            }
+            ImplDiagnostic::ConflictingImpls { impl_block, trait_ } => {
+                if impl_block.module(db) != owner {
+                    return;
+                }
+                sink.push(ConflictingImpls {
+                    file: owner.file_id,
+                    type_ref: impl_block.data(db).target_trait_ptr.unwrap(),
+                    trait_name: trait_.name(db).to_string(),
+                })
+            }
+            ImplDiagnostic::MissingTraitMethods {
+                impl_block,
+                trait_,
+                names,
+            } => {
+                if impl_block.module(db) != owner {
+                    return;
+                }
+                sink.push(MissingTraitMethods {
+                    file: owner.file_id,
+                    type_ref: impl_block.data(db).target_trait_ptr.unwrap(),
+                    trait_name: trait_.name(db).to_string(),
+                    names: names.clone(),
+                })
+            }
+            ImplDiagnostic::NotATraitMethod { function, trait_ } => {
+                if function.module(db) != owner {
+                    return;
+                }
+                sink.push(NotATraitMethod {
+                    file: owner.file_id,
+                    function: SyntaxNodePtr::new(function.source(db).value.syntax()),
+                    name: function.name(db).to_string(),
+                    trait_name: trait_.name(db).to_string(),
+                })
+            }
+            ImplDiagnostic::IncompatibleTraitMethod { function, trait_ } => {
+                if function.module(db) != owner {
+                    return;
+                }
+                sink.push(IncompatibleTraitMethod {
+                    file: owner.file_id,
+                    function: SyntaxNodePtr::new(function.source(db).value.syntax()),
+                    name: function.name(db).to_string(),
+                    trait_name: trait_.name(db).to_string(),
+                })
+            }
+        }
+    }
+}
@@ -10,23 +10,31 @@ mod macros;
 #[macro_use]
 mod arena;
 mod adt;
+mod builtin_function;
 mod builtin_type;
 mod code_model;
 mod db;
 pub mod diagnostics;
 mod display;
 mod expr;
+mod generics;
+mod global;
 mod ids;
+mod impl_block;
 mod in_file;
 mod input;
 pub mod line_index;
+pub mod lint;
 mod model;
+mod module_tree;
 mod name;
 mod name_resolution;
 mod path;
 mod raw;
 mod resolve;
+mod source_analyzer;
 mod source_id;
+mod traits;
 mod ty;
 mod type_ref;
 mod utils;
@@ -42,6 +50,7 @@ pub use relative_path::{RelativePath, RelativePathBuf};
 
 pub use crate::{
     arena::{ArenaId, RawId},
+    builtin_function::BuiltinFunction,
     builtin_type::{FloatBitness, IntBitness, Signedness},
     db::{
         DefDatabase, DefDatabaseStorage, HirDatabase, HirDatabaseStorage, SourceDatabase,
@@ -49,19 +58,23 @@ pub use crate::{
     },
     display::HirDisplay,
     expr::{
-        resolver_for_expr, ArithOp, BinaryOp, Body, CmpOp, Expr, ExprId, ExprScopes, Literal,
-        LogicOp, Ordering, Pat, PatId, RecordLitField, Statement, UnaryOp,
+        const_eval::ConstValue, resolver_for_expr, ArithOp, BinaryOp, Body, BodySourceMap, CmpOp,
+        Expr, ExprId, ExprScopes, Literal, LogicOp, MatchArm, Ordering, Pat, PatId, RangeOp,
+        RecordFieldPat, RecordLitField, Statement, UnaryOp,
     },
+    generics::{GenericDef, GenericParam, GenericParams},
     ids::ItemLoc,
+    in_file::InFile,
     input::{FileId, SourceRoot, SourceRootId},
     name::Name,
     name_resolution::PerNs,
     path::{Path, PathKind},
     raw::RawItems,
     resolve::{Resolution, Resolver},
+    source_analyzer::SourceAnalyzer,
     ty::{
-        lower::CallableDef, ApplicationTy, FloatTy, InferenceResult, IntTy, ResolveBitness, Ty,
-        TypeCtor,
+        fn_ptr_name, lower::CallableDef, tuple_name, ApplicationTy, BuiltinMethod, FloatTy, FnSig,
+        InferenceResult, IntTy, MethodResolution, ResolveBitness, Substs, Ty, TypeCtor,
     },
 };
 
@@ -72,4 +85,7 @@ use crate::{
 };
 
 pub use self::adt::StructMemoryKind;
-pub use self::code_model::{FnData, Function, Module, ModuleDef, Struct, Visibility};
+pub use self::code_model::{
+    Const, DefWithBody, Enum, EnumVariant, EnumVariantField, FnData, Function, ImplBlock, Module,
+    ModuleDef, Package, Static, Struct, StructField, Trait, Visibility,
+};
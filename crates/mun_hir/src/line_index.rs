@@ -60,6 +60,12 @@ impl LineIndex {
     pub fn line_offset(&self, line_index: u32) -> usize {
         self.newlines[line_index as usize].to_usize()
     }
+
+    /// Retrieves the offset of the (zero-based) line and column `line_col`, if the line exists.
+    pub fn offset(&self, line_col: LineCol) -> Option<TextUnit> {
+        let line_start_offset = self.newlines.get(line_col.line as usize)?;
+        Some(*line_start_offset + TextUnit::from(line_col.col))
+    }
 }
 #[cfg(test)]
 mod tests {
@@ -96,4 +102,12 @@ mod tests {
         assert_eq!(index.line_offset(1), 4);
         assert_eq!(index.line_offset(2), 9);
     }
+    #[test]
+    fn test_offset() {
+        let text = "hello\nworld";
+        let index = LineIndex::new(text);
+        assert_eq!(index.offset(LineCol { line: 0, col: 3 }), Some(3.into()));
+        assert_eq!(index.offset(LineCol { line: 1, col: 1 }), Some(7.into()));
+        assert_eq!(index.offset(LineCol { line: 2, col: 0 }), None);
+    }
 }
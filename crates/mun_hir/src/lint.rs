@@ -0,0 +1,327 @@
+//! Lints are checks for code that is valid but is probably not what the author intended, like an
+//! unused variable or unreachable code. Unlike the errors in `crate::diagnostics`, lints do not
+//! prevent an assembly from being generated unless their level is raised to `LintLevel::Deny`.
+//!
+//! The level of a lint can be changed for a whole package with `LintLevels`, and lints can be
+//! suppressed for a single declaration with an attribute, e.g. `#[allow(unused_variables)]`.
+
+use crate::code_model::src::HasSource;
+use crate::diagnostics::{DiagnosticSink, LintDiagnostic};
+use crate::expr::{BodySourceMap, ExprScopes};
+use crate::{
+    BinaryOp, Body, DefWithBody, Expr, ExprId, FileId, Function, HirDatabase, InferenceResult,
+    Module, ModuleDef, Package, PatId, Resolution, Static,
+};
+use mun_syntax::{ast, ast::AttrsOwner, SyntaxNodePtr};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{fmt, sync::Arc};
+
+mod shadowed_bindings;
+mod unreachable_code;
+mod unused_functions;
+mod unused_mut;
+mod unused_variables;
+
+#[cfg(test)]
+mod tests;
+
+/// The name that refers to all lints in an attribute and to all lints that emit warnings on the
+/// command line, e.g. `#[allow(warnings)]` or `-D warnings`.
+pub const WARNINGS: &str = "warnings";
+
+/// A check for code that is valid but probably contains a mistake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lint {
+    /// A local variable or parameter that is never read
+    UnusedVariables,
+    /// A private function that is never called
+    UnusedFunctions,
+    /// A statement or expression that follows an expression that never returns, like `return` or
+    /// `break`
+    UnreachableCode,
+    /// A `static mut` that is never assigned to
+    UnusedMut,
+    /// A local variable with the same name as a variable in an enclosing scope
+    ShadowedBindings,
+}
+
+impl Lint {
+    pub const ALL: [Lint; 5] = [
+        Lint::UnusedVariables,
+        Lint::UnusedFunctions,
+        Lint::UnreachableCode,
+        Lint::UnusedMut,
+        Lint::ShadowedBindings,
+    ];
+
+    /// Returns the name by which the lint is referred to in attributes and on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Lint::UnusedVariables => "unused_variables",
+            Lint::UnusedFunctions => "unused_functions",
+            Lint::UnreachableCode => "unreachable_code",
+            Lint::UnusedMut => "unused_mut",
+            Lint::ShadowedBindings => "shadowed_bindings",
+        }
+    }
+
+    /// Returns the lint with the specified `name`, if any.
+    pub fn from_name(name: &str) -> Option<Lint> {
+        Lint::ALL.iter().copied().find(|lint| lint.name() == name)
+    }
+
+    /// Returns the level of the lint if it is not changed by the user.
+    pub fn default_level(self) -> LintLevel {
+        match self {
+            Lint::UnusedVariables
+            | Lint::UnusedFunctions
+            | Lint::UnreachableCode
+            | Lint::UnusedMut => LintLevel::Warn,
+            // Shadowing is idiomatic, e.g. to convert a value to another type
+            Lint::ShadowedBindings => LintLevel::Allow,
+        }
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Determines what happens when a lint finds a problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LintLevel {
+    /// The lint is not checked
+    Allow,
+    /// The lint emits a warning
+    Warn,
+    /// The lint emits an error
+    Deny,
+}
+
+/// The levels of all lints, which default to `Lint::default_level`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintLevels {
+    levels: FxHashMap<Lint, LintLevel>,
+    /// The level of all lints that would otherwise emit a warning
+    warnings: Option<LintLevel>,
+}
+
+impl LintLevels {
+    /// Sets the level of `lint`.
+    pub fn set(&mut self, lint: Lint, level: LintLevel) {
+        self.levels.insert(lint, level);
+    }
+
+    /// Sets the level of all lints that would otherwise emit a warning, e.g. `-D warnings` turns
+    /// all warnings into errors.
+    pub fn set_warnings(&mut self, level: LintLevel) {
+        self.warnings = Some(level);
+    }
+
+    /// Returns the level of `lint`.
+    pub fn level(&self, lint: Lint) -> LintLevel {
+        let level = self
+            .levels
+            .get(&lint)
+            .copied()
+            .unwrap_or_else(|| lint.default_level());
+        match (level, self.warnings) {
+            (LintLevel::Warn, Some(warnings)) => warnings,
+            (level, _) => level,
+        }
+    }
+
+    /// Returns the levels that apply to a declaration with the specified attributes. Only the
+    /// `allow` attribute is taken into account; unknown attributes and lint names are ignored.
+    fn with_attrs(&self, attrs: impl Iterator<Item = ast::Attr>) -> LintLevels {
+        let mut levels = self.clone();
+        let allowed = attrs
+            .filter(|attr| attr.name_ref().map_or(false, |name| name.text() == "allow"))
+            .filter_map(|attr| attr.attr_arg_list())
+            .flat_map(|args| args.args());
+        for name in allowed {
+            if name.text() == WARNINGS {
+                for lint in Lint::ALL.iter() {
+                    levels.set(*lint, LintLevel::Allow);
+                }
+            } else if let Some(lint) = Lint::from_name(name.text()) {
+                levels.set(lint, LintLevel::Allow);
+            }
+        }
+        levels
+    }
+}
+
+impl Module {
+    /// Runs all lints that are not allowed by `levels` on the declarations in this module and adds
+    /// their diagnostics to the `sink`.
+    pub fn lints(self, db: &impl HirDatabase, levels: &LintLevels, sink: &mut DiagnosticSink) {
+        let usages = db.package_usages(self.package(db));
+        for decl in self.declarations(db) {
+            match decl {
+                ModuleDef::Function(func) => {
+                    let mut levels = levels.with_attrs(func.source(db).value.attrs());
+                    if let Some(impl_block) = func.impl_block(db) {
+                        levels = levels.with_attrs(impl_block.source(db).value.attrs());
+                    }
+                    let linter = Linter::new(func.into(), levels, db);
+                    linter.lint_unused_functions(&usages, sink);
+                    if !func.is_extern(db) && func.trait_def(db).is_none() {
+                        linter.lint_unused_variables(sink);
+                        linter.lint_unreachable_code(sink);
+                        linter.lint_shadowed_bindings(sink);
+                    }
+                }
+                ModuleDef::Static(s) => {
+                    let levels = levels.with_attrs(s.source(db).value.attrs());
+                    let linter = Linter::new(s.into(), levels, db);
+                    linter.lint_unused_mut(&usages, sink);
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// The functions and global variables that are used by the bodies in a package.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PackageUsages {
+    /// The functions that are referred to from outside of their own body
+    functions: FxHashSet<Function>,
+    /// The global variables that are assigned to
+    assigned_statics: FxHashSet<Static>,
+}
+
+impl PackageUsages {
+    pub(crate) fn package_usages_query(db: &impl HirDatabase, package: Package) -> Arc<Self> {
+        let mut usages = PackageUsages {
+            functions: FxHashSet::default(),
+            assigned_statics: FxHashSet::default(),
+        };
+        for module in package.modules(db) {
+            for decl in module.declarations(db) {
+                let owner: DefWithBody = match decl {
+                    ModuleDef::Function(func) => func.into(),
+                    ModuleDef::Const(c) => c.into(),
+                    ModuleDef::Static(s) => s.into(),
+                    _ => continue,
+                };
+                usages.add_body(db, owner);
+            }
+        }
+        Arc::new(usages)
+    }
+
+    fn add_body(&mut self, db: &impl HirDatabase, owner: DefWithBody) {
+        let body = owner.body(db);
+        for (expr, data) in body.exprs() {
+            match data {
+                Expr::Path(_) => match resolve_path_expr(db, &body, expr) {
+                    Some(Resolution::Def(ModuleDef::Function(func)))
+                        if DefWithBody::Function(func) != owner =>
+                    {
+                        self.functions.insert(func);
+                    }
+                    _ => (),
+                },
+                Expr::BinaryOp {
+                    lhs,
+                    op: Some(BinaryOp::Assignment { .. }),
+                    ..
+                } => {
+                    // The assigned place can be a field or an element of the global variable
+                    let mut place = *lhs;
+                    loop {
+                        match &body[place] {
+                            Expr::Field { expr, .. } => place = *expr,
+                            Expr::Index { base, .. } => place = *base,
+                            _ => break,
+                        }
+                    }
+                    if let Some(Resolution::Def(ModuleDef::Static(s))) =
+                        resolve_path_expr(db, &body, place)
+                    {
+                        self.assigned_statics.insert(s);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Returns what the path expression `expr` refers to, if it is a path that can be resolved.
+fn resolve_path_expr(db: &impl HirDatabase, body: &Arc<Body>, expr: ExprId) -> Option<Resolution> {
+    let path = match &body[expr] {
+        Expr::Path(path) => path,
+        _ => return None,
+    };
+    crate::expr::resolver_for_expr(body.clone(), db, expr)
+        .resolve_path_without_assoc_items(db, path)
+        .take_values()
+}
+
+/// Runs the lints on a single declaration.
+struct Linter<'d, DB: HirDatabase> {
+    owner: DefWithBody,
+    levels: LintLevels,
+    file: FileId,
+    body: Arc<Body>,
+    body_source_map: Arc<BodySourceMap>,
+    infer: Arc<InferenceResult>,
+    scopes: Arc<ExprScopes>,
+    db: &'d DB,
+}
+
+impl<'d, DB: HirDatabase> Linter<'d, DB> {
+    fn new(owner: DefWithBody, levels: LintLevels, db: &'d DB) -> Self {
+        let (body, body_source_map) = db.body_with_source_map(owner);
+        Linter {
+            owner,
+            levels,
+            file: owner.module(db).file_id(),
+            body,
+            body_source_map,
+            infer: db.infer(owner),
+            scopes: db.expr_scopes(owner),
+            db,
+        }
+    }
+
+    /// Returns whether `lint` is checked for this declaration.
+    fn is_enabled(&self, lint: Lint) -> bool {
+        self.levels.level(lint) != LintLevel::Allow
+    }
+
+    /// Returns a pointer to the syntax node of the expression `expr`.
+    fn expr_ptr(&self, expr: ExprId) -> Option<SyntaxNodePtr> {
+        self.body_source_map.expr_syntax(expr).map(|source| {
+            source
+                .value
+                .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())
+        })
+    }
+
+    /// Returns a pointer to the syntax node of the pattern `pat`.
+    fn pat_ptr(&self, pat: PatId) -> Option<SyntaxNodePtr> {
+        self.body_source_map
+            .pat_syntax(pat)
+            .map(|source| source.value.syntax_node_ptr())
+    }
+
+    /// Adds a diagnostic for `lint` to the `sink`, unless the lint is allowed.
+    fn report(&self, sink: &mut DiagnosticSink, lint: Lint, node: SyntaxNodePtr, message: String) {
+        let level = self.levels.level(lint);
+        if level != LintLevel::Allow {
+            sink.push(LintDiagnostic {
+                file: self.file,
+                node,
+                lint,
+                level,
+                message,
+            });
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use super::{Lint, Linter};
+use crate::diagnostics::DiagnosticSink;
+use crate::HirDatabase;
+
+impl<'d, DB: HirDatabase> Linter<'d, DB> {
+    /// Warns about local bindings that have the same name as a binding in an enclosing scope,
+    /// including an earlier `let` statement of the same block.
+    pub(super) fn lint_shadowed_bindings(&self, sink: &mut DiagnosticSink) {
+        if !self.is_enabled(Lint::ShadowedBindings) {
+            return;
+        }
+
+        for scope in self.scopes.scopes() {
+            for entry in self.scopes.entries(scope) {
+                let is_shadowing = self
+                    .scopes
+                    .scope_chain(Some(scope))
+                    .skip(1)
+                    .flat_map(|parent| self.scopes.entries(parent))
+                    .any(|parent_entry| parent_entry.name() == entry.name());
+                if !is_shadowing {
+                    continue;
+                }
+                if let Some(node) = self.pat_ptr(entry.pat()) {
+                    self.report(
+                        sink,
+                        Lint::ShadowedBindings,
+                        node,
+                        format!("`{}` shadows an earlier binding", entry.name()),
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use super::{Lint, LintLevel, LintLevels};
+use crate::diagnostics::DiagnosticSink;
+use crate::mock::MockDatabase;
+use crate::Module;
+
+/// Runs the lints on `text` and returns the text of the highlighted node, the severity, and the
+/// message of every diagnostic.
+fn lints_with_levels(text: &str, levels: &LintLevels) -> Vec<String> {
+    let (db, file_id) = MockDatabase::with_single_file(text);
+    let module = Module::from(file_id);
+
+    let mut errors = Vec::new();
+    let mut sink = DiagnosticSink::new(|diag| errors.push(diag.message()));
+    module.diagnostics(&db, &mut sink);
+    drop(sink);
+    assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+
+    let mut lints = Vec::new();
+    let mut sink = DiagnosticSink::new(|diag| {
+        lints.push(format!(
+            "{}: {:?}: {}",
+            diag.syntax_node(&db).text(),
+            diag.severity(),
+            diag.message()
+        ))
+    });
+    module.lints(&db, levels, &mut sink);
+    drop(sink);
+    lints
+}
+
+fn lints(text: &str) -> Vec<String> {
+    lints_with_levels(text, &LintLevels::default())
+}
+
+#[test]
+fn unused_variables() {
+    assert_eq!(
+        lints(
+            r#"
+    pub fn foo(a: i32, _b: i32, c: i32) -> i32 {
+        let d = 1;
+        let _e = 2;
+        let f = c;
+        f
+    }
+    "#
+        ),
+        vec![
+            "a: Warning: unused variable: `a`",
+            "d: Warning: unused variable: `d`"
+        ]
+    );
+}
+
+#[test]
+fn unused_functions() {
+    assert_eq!(
+        lints(
+            r#"
+    fn unused() {}
+    fn used() {}
+    fn recursive() { recursive() }
+    pub fn main() { used() }
+    "#
+        ),
+        vec![
+            "unused: Warning: function `unused` is never used",
+            "recursive: Warning: function `recursive` is never used"
+        ]
+    );
+}
+
+#[test]
+fn unreachable_code() {
+    assert_eq!(
+        lints(
+            r#"
+    pub fn foo() -> i32 {
+        return 1;
+        let a = 2;
+        a
+    }
+
+    pub fn bar() {
+        loop {
+            break;
+            bar()
+        }
+    }
+    "#
+        ),
+        vec![
+            "let a = 2;: Warning: unreachable statement",
+            "bar(): Warning: unreachable expression"
+        ]
+    );
+}
+
+#[test]
+fn unused_mut() {
+    assert_eq!(
+        lints(
+            r#"
+    static mut UNUSED: i32 = 0;
+    static mut ASSIGNED: i32 = 0;
+    static mut ADDED: i32 = 0;
+
+    pub fn foo() {
+        ASSIGNED = 1;
+        ADDED += 1;
+    }
+    "#
+        ),
+        vec!["UNUSED: Warning: global variable `UNUSED` does not need to be mutable"]
+    );
+}
+
+#[test]
+fn shadowed_bindings() {
+    let text = r#"
+    pub fn foo(a: i32) -> i32 {
+        let b = a;
+        let a = b + 1;
+        let f = |b: i32| b * a;
+        f(a)
+    }
+    "#;
+    assert!(lints(text).is_empty());
+
+    let mut levels = LintLevels::default();
+    levels.set(Lint::ShadowedBindings, LintLevel::Warn);
+    assert_eq!(
+        lints_with_levels(text, &levels),
+        vec![
+            "a: Warning: `a` shadows an earlier binding",
+            "b: Warning: `b` shadows an earlier binding"
+        ]
+    );
+}
+
+#[test]
+fn allow_attribute() {
+    assert_eq!(
+        lints(
+            r#"
+    #[allow(unused_variables)]
+    fn foo(a: i32) {}
+
+    #[allow(warnings)]
+    static mut BAR: i32 = 0;
+    "#
+        ),
+        vec!["foo: Warning: function `foo` is never used"]
+    );
+}
+
+#[test]
+fn deny_warnings() {
+    let mut levels = LintLevels::default();
+    levels.set_warnings(LintLevel::Deny);
+    assert_eq!(levels.level(Lint::UnusedVariables), LintLevel::Deny);
+    assert_eq!(levels.level(Lint::ShadowedBindings), LintLevel::Allow);
+
+    assert_eq!(
+        lints_with_levels("pub fn foo(a: i32) {}", &levels),
+        vec!["a: Error: unused variable: `a`"]
+    );
+}
+
+#[test]
+fn lint_names() {
+    for lint in Lint::ALL.iter() {
+        assert_eq!(Lint::from_name(lint.name()), Some(*lint));
+    }
+    assert_eq!(Lint::from_name("unknown_lint"), None);
+}
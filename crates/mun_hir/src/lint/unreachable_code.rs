@@ -0,0 +1,53 @@
+use super::{Lint, Linter};
+use crate::diagnostics::DiagnosticSink;
+use crate::{Expr, ExprId, HirDatabase, PatId, Statement};
+use mun_syntax::{SyntaxKind, SyntaxNodePtr};
+
+impl<'d, DB: HirDatabase> Linter<'d, DB> {
+    /// Warns about the first statement or tail expression of a block that follows a statement that
+    /// never returns, like `return` or `break`.
+    pub(super) fn lint_unreachable_code(&self, sink: &mut DiagnosticSink) {
+        if !self.is_enabled(Lint::UnreachableCode) {
+            return;
+        }
+
+        let diverges = |expr: ExprId| self.infer[expr].is_never();
+        for (_, expr) in self.body.exprs() {
+            let (statements, tail) = match expr {
+                Expr::Block { statements, tail } => (statements, tail),
+                _ => continue,
+            };
+
+            let first_diverging = statements.iter().position(|statement| match statement {
+                Statement::Let { initializer, .. } => initializer.map_or(false, diverges),
+                Statement::Expr(expr) => diverges(*expr),
+            });
+            let (node, message) = match first_diverging.map(|index| statements.get(index + 1)) {
+                None => continue,
+                Some(Some(Statement::Let { pat, .. })) => {
+                    (self.let_stmt_ptr(*pat), "unreachable statement")
+                }
+                Some(Some(Statement::Expr(expr))) => {
+                    (self.expr_ptr(*expr), "unreachable statement")
+                }
+                Some(None) => (
+                    tail.and_then(|expr| self.expr_ptr(expr)),
+                    "unreachable expression",
+                ),
+            };
+            if let Some(node) = node {
+                self.report(sink, Lint::UnreachableCode, node, message.to_owned());
+            }
+        }
+    }
+
+    /// Returns a pointer to the `let` statement that declares the pattern `pat`.
+    fn let_stmt_ptr(&self, pat: PatId) -> Option<SyntaxNodePtr> {
+        let root = self.db.parse(self.file).syntax_node();
+        self.pat_ptr(pat)?
+            .to_node(&root)
+            .ancestors()
+            .find(|node| node.kind() == SyntaxKind::LET_STMT)
+            .map(|node| SyntaxNodePtr::new(&node))
+    }
+}
@@ -0,0 +1,36 @@
+use super::{Lint, Linter, PackageUsages};
+use crate::code_model::src::HasSource;
+use crate::diagnostics::DiagnosticSink;
+use crate::{DefWithBody, HirDatabase, Visibility};
+use mun_syntax::{ast::NameOwner, AstNode, SyntaxNodePtr};
+
+impl<'d, DB: HirDatabase> Linter<'d, DB> {
+    /// Warns about private functions that are not called from anywhere in the package, apart from
+    /// their own body. Functions of `impl` blocks and traits are not checked.
+    pub(super) fn lint_unused_functions(&self, usages: &PackageUsages, sink: &mut DiagnosticSink) {
+        let func = match self.owner {
+            DefWithBody::Function(func) => func,
+            _ => return,
+        };
+        if func.visibility(self.db) == Visibility::Public
+            || func.is_extern(self.db)
+            || func.impl_block(self.db).is_some()
+            || func.trait_def(self.db).is_some()
+            || usages.functions.contains(&func)
+        {
+            return;
+        }
+
+        let src = func.source(self.db).value;
+        let node = match src.name() {
+            Some(name) => SyntaxNodePtr::new(name.syntax()),
+            None => SyntaxNodePtr::new(src.syntax()),
+        };
+        self.report(
+            sink,
+            Lint::UnusedFunctions,
+            node,
+            format!("function `{}` is never used", func.name(self.db)),
+        );
+    }
+}
@@ -0,0 +1,33 @@
+use super::{Lint, Linter, PackageUsages};
+use crate::code_model::src::HasSource;
+use crate::diagnostics::DiagnosticSink;
+use crate::{DefWithBody, HirDatabase};
+use mun_syntax::{ast::NameOwner, AstNode, SyntaxNodePtr};
+
+impl<'d, DB: HirDatabase> Linter<'d, DB> {
+    /// Warns about a `static mut` that is never assigned to.
+    pub(super) fn lint_unused_mut(&self, usages: &PackageUsages, sink: &mut DiagnosticSink) {
+        let global = match self.owner {
+            DefWithBody::Static(global) => global,
+            _ => return,
+        };
+        if !global.is_mut(self.db) || usages.assigned_statics.contains(&global) {
+            return;
+        }
+
+        let src = global.source(self.db).value;
+        let node = match src.name() {
+            Some(name) => SyntaxNodePtr::new(name.syntax()),
+            None => SyntaxNodePtr::new(src.syntax()),
+        };
+        self.report(
+            sink,
+            Lint::UnusedMut,
+            node,
+            format!(
+                "global variable `{}` does not need to be mutable",
+                global.name(self.db)
+            ),
+        );
+    }
+}
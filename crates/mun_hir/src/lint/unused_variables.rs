@@ -0,0 +1,47 @@
+use super::{resolve_path_expr, Lint, Linter};
+use crate::diagnostics::DiagnosticSink;
+use crate::{Expr, HirDatabase, Pat, Resolution};
+use rustc_hash::FxHashSet;
+
+impl<'d, DB: HirDatabase> Linter<'d, DB> {
+    /// Warns about local bindings that are never read. Bindings whose name starts with an
+    /// underscore are exempt.
+    pub(super) fn lint_unused_variables(&self, sink: &mut DiagnosticSink) {
+        if !self.is_enabled(Lint::UnusedVariables) {
+            return;
+        }
+
+        let used_pats: FxHashSet<_> = self
+            .body
+            .exprs()
+            .filter(|(_, expr)| match expr {
+                Expr::Path(_) => true,
+                _ => false,
+            })
+            .filter_map(
+                |(expr, _)| match resolve_path_expr(self.db, &self.body, expr) {
+                    Some(Resolution::LocalBinding(pat)) => Some(pat),
+                    _ => None,
+                },
+            )
+            .collect();
+
+        for (pat, data) in self.body.pats() {
+            let name = match data {
+                Pat::Bind { name } => name.to_string(),
+                _ => continue,
+            };
+            if used_pats.contains(&pat) || name.starts_with('_') || name == "self" {
+                continue;
+            }
+            if let Some(node) = self.pat_ptr(pat) {
+                self.report(
+                    sink,
+                    Lint::UnusedVariables,
+                    node,
+                    format!("unused variable: `{}`", name),
+                );
+            }
+        }
+    }
+}
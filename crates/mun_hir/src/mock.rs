@@ -49,8 +49,44 @@ impl MockDatabase {
         source_root.insert_file(rel_path, file_id);
 
         db.set_source_root(source_root_id, Arc::new(source_root));
+        db.set_package_root(source_root_id, file_id);
         (db, file_id)
     }
+
+    /// Creates a database from a fixture that contains multiple files. Every file starts with a
+    /// line `//- <relative path>`, the first file is the root module of the package.
+    pub fn with_files(fixture: &str) -> (MockDatabase, Vec<FileId>) {
+        let mut db: MockDatabase = Default::default();
+        db.set_target(Target::host_target().unwrap());
+
+        let mut source_root = SourceRoot::default();
+        let source_root_id = SourceRootId(0);
+
+        let mut files: Vec<(RelativePathBuf, String)> = Vec::new();
+        for line in fixture.lines() {
+            if line.trim().starts_with("//- ") {
+                let path = line.trim()["//- ".len()..].trim();
+                files.push((RelativePathBuf::from(path), String::new()));
+            } else if let Some((_, text)) = files.last_mut() {
+                text.push_str(line);
+                text.push('\n');
+            }
+        }
+
+        let mut file_ids = Vec::new();
+        for (idx, (rel_path, text)) in files.into_iter().enumerate() {
+            let file_id = FileId(idx as u32);
+            db.set_file_relative_path(file_id, rel_path.clone());
+            db.set_file_text(file_id, Arc::new(text));
+            db.set_file_source_root(file_id, source_root_id);
+            source_root.insert_file(rel_path, file_id);
+            file_ids.push(file_id);
+        }
+
+        db.set_source_root(source_root_id, Arc::new(source_root));
+        db.set_package_root(source_root_id, file_ids[0]);
+        (db, file_ids)
+    }
 }
 
 impl MockDatabase {
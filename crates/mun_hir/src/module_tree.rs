@@ -0,0 +1,173 @@
+use crate::diagnostics::{DiagnosticSink, UnresolvedModule};
+use crate::raw::{DefKind, RawFileItem};
+use crate::{
+    DefDatabase, FileAstId, FileId, Module, Name, RelativePath, RelativePathBuf, SourceRootId,
+};
+use mun_syntax::{ast, AstPtr};
+use rustc_hash::FxHashMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// The hierarchy of modules in a package. Every module corresponds to a single file. The root of
+/// the tree is the `package_root` of a source root, all other modules are declared by their parent
+/// with a `mod` declaration.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ModuleTree {
+    root: FileId,
+    modules: FxHashMap<FileId, ModuleTreeNode>,
+    diagnostics: Vec<ModuleTreeDiagnostic>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ModuleTreeNode {
+    /// The module that declared this module, `None` for the root module
+    pub(crate) parent: Option<FileId>,
+    /// The name by which this module was declared, `None` for the root module
+    pub(crate) name: Option<Name>,
+    /// The `mod` declaration of this module in the file of its parent
+    pub(crate) declaration: Option<FileAstId<ast::Module>>,
+    /// All submodules of this module in declaration order
+    pub(crate) children: Vec<(Name, FileId)>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) enum ModuleTreeDiagnostic {
+    /// A `mod` declaration for which no file could be found
+    UnresolvedModule {
+        file_id: FileId,
+        declaration: FileAstId<ast::Module>,
+        candidate: RelativePathBuf,
+    },
+}
+
+impl ModuleTree {
+    pub(crate) fn module_tree_query(
+        db: &impl DefDatabase,
+        source_root_id: SourceRootId,
+    ) -> Arc<ModuleTree> {
+        let source_root = db.source_root(source_root_id);
+        let root = db.package_root(source_root_id);
+
+        let mut tree = ModuleTree {
+            root,
+            modules: FxHashMap::default(),
+            diagnostics: Vec::new(),
+        };
+        tree.modules.insert(
+            root,
+            ModuleTreeNode {
+                parent: None,
+                name: None,
+                declaration: None,
+                children: Vec::new(),
+            },
+        );
+
+        // Walk all modules in bfs order, starting at the root
+        let mut queue = VecDeque::new();
+        queue.push_back(root);
+        while let Some(file_id) = queue.pop_front() {
+            let raw_items = db.raw_items(file_id);
+            let dir = submodule_dir(&db.file_relative_path(file_id), file_id == root);
+            for item in raw_items.items() {
+                let def = match item {
+                    RawFileItem::Definition(def) => &raw_items[*def],
+                    RawFileItem::Import(_) | RawFileItem::Impl(_) => continue,
+                };
+                let declaration = match def.kind {
+                    DefKind::Module(ast_id) => ast_id,
+                    _ => continue,
+                };
+
+                let candidate = dir.join(format!("{}.mun", def.name));
+                match source_root.file_by_relative_path(&candidate) {
+                    Some(child) if !tree.modules.contains_key(&child) => {
+                        tree.modules.insert(
+                            child,
+                            ModuleTreeNode {
+                                parent: Some(file_id),
+                                name: Some(def.name.clone()),
+                                declaration: Some(declaration),
+                                children: Vec::new(),
+                            },
+                        );
+                        tree.modules
+                            .get_mut(&file_id)
+                            .unwrap()
+                            .children
+                            .push((def.name.clone(), child));
+                        queue.push_back(child);
+                    }
+                    // The module is already part of the tree; duplicate declarations are reported
+                    // as duplicate definitions.
+                    Some(_) => (),
+                    None => tree
+                        .diagnostics
+                        .push(ModuleTreeDiagnostic::UnresolvedModule {
+                            file_id,
+                            declaration,
+                            candidate,
+                        }),
+                }
+            }
+        }
+
+        Arc::new(tree)
+    }
+
+    /// Returns the root module of the package
+    pub(crate) fn root(&self) -> FileId {
+        self.root
+    }
+
+    /// Returns the node of the specified file, if the file is part of the tree.
+    pub(crate) fn get(&self, file_id: FileId) -> Option<&ModuleTreeNode> {
+        self.modules.get(&file_id)
+    }
+
+    /// Returns all the files that are part of the tree.
+    pub(crate) fn files(&self) -> impl Iterator<Item = FileId> + '_ {
+        self.modules.keys().copied()
+    }
+
+    pub(crate) fn diagnostics(&self) -> &[ModuleTreeDiagnostic] {
+        &self.diagnostics
+    }
+}
+
+/// Returns the directory that contains the files of the submodules of the module at `path`. The
+/// submodules of the root module `main.mun` are stored next to it (e.g. `foo.mun`), the submodules
+/// of any other module `foo.mun` are stored in a directory with the same name (e.g. `foo/bar.mun`).
+fn submodule_dir(path: &RelativePath, is_root: bool) -> RelativePathBuf {
+    let parent = path
+        .parent()
+        .map(RelativePath::to_relative_path_buf)
+        .unwrap_or_else(RelativePathBuf::new);
+    if is_root {
+        parent
+    } else {
+        parent.join(path.file_stem().unwrap_or(""))
+    }
+}
+
+impl ModuleTreeDiagnostic {
+    pub(crate) fn add_to(&self, db: &impl DefDatabase, owner: Module, sink: &mut DiagnosticSink) {
+        match self {
+            ModuleTreeDiagnostic::UnresolvedModule {
+                file_id,
+                declaration,
+                candidate,
+            } => {
+                if *file_id != owner.file_id {
+                    return;
+                }
+                let decl = declaration.with_file_id(*file_id).to_node(db);
+                sink.push(UnresolvedModule {
+                    file: *file_id,
+                    decl: AstPtr::new(&decl),
+                    candidate: candidate.clone(),
+                })
+            }
+        }
+    }
+}
@@ -52,7 +52,7 @@ impl Name {
         Name::new_text("[missing name]".into())
     }
 
-    pub(crate) fn as_tuple_index(&self) -> Option<usize> {
+    pub fn as_tuple_index(&self) -> Option<usize> {
         match self.0 {
             Repr::TupleField(idx) => Some(idx),
             _ => None,
@@ -102,11 +102,31 @@ pub mod known {
     known_names!(
         // Primitives
         int, isize, i8, i16, i32, i64, i128, uint, usize, u8, u16, u32, u64, u128, float, f32, f64,
-        bool,
+        bool, string,
     );
 
+    known_names!(
+        // Builtin methods
+        len, push, pop,
+    );
+
+    known_names!(
+        // Builtin functions
+        panic,
+    );
+
+    // `self` and `Self` are keywords, so they cannot be passed to `known_names!`
+    pub const SELF_PARAM: super::Name = super::Name::new_inline_ascii(b"self");
+    pub const SELF_TYPE: super::Name = super::Name::new_inline_ascii(b"Self");
+
     #[macro_export]
     macro_rules! name {
+        (self) => {
+            $crate::name::known::SELF_PARAM
+        };
+        (Self) => {
+            $crate::name::known::SELF_TYPE
+        };
         ($ident:ident) => {
             $crate::name::known::$ident
         };
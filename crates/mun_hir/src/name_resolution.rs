@@ -1,17 +1,25 @@
 mod per_ns;
 
 pub use self::per_ns::{Namespace, PerNs};
-use crate::{builtin_type::BuiltinType, FileId, HirDatabase, ModuleDef, Name};
+use crate::diagnostics::{DiagnosticSink, PrivateImport, UnresolvedImport};
+use crate::module_tree::ModuleTree;
+use crate::raw::{ImportData, ImportId, RawFileItem};
+use crate::{
+    builtin_function::BuiltinFunction, builtin_type::BuiltinType, FileId, HirDatabase, Module,
+    ModuleDef, Name, Path, PathKind, PathSegment, SourceRootId, Visibility,
+};
+use mun_syntax::AstPtr;
 use once_cell::sync::Lazy;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::sync::Arc;
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Resolution {
     /// None for unresolved
     pub def: PerNs<ModuleDef>,
-    //    /// ident by which this is imported into local scope.
-    //    pub import: Option<ImportId>,
+    /// The visibility of the name in the scope it is defined in. A private name is only visible
+    /// in its module and the submodules of that module.
+    pub(crate) visibility: Visibility,
 }
 
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
@@ -20,13 +28,20 @@ pub struct ModuleScope {
 }
 
 static BUILTIN_SCOPE: Lazy<FxHashMap<Name, Resolution>> = Lazy::new(|| {
-    BuiltinType::ALL
+    let types = BuiltinType::ALL
         .iter()
-        .map(|(name, ty)| {
+        .map(|(name, ty)| (name, PerNs::types(ModuleDef::from(*ty))));
+    let functions = BuiltinFunction::ALL
+        .iter()
+        .map(|(name, f)| (name, PerNs::values(ModuleDef::from(*f))));
+    types
+        .chain(functions)
+        .map(|(name, def)| {
             (
                 name.clone(),
                 Resolution {
-                    def: PerNs::types(ty.clone().into()),
+                    def,
+                    visibility: Visibility::Public,
                 },
             )
         })
@@ -41,31 +56,391 @@ impl ModuleScope {
     pub fn get(&self, name: &Name) -> Option<&Resolution> {
         self.items.get(name).or_else(|| BUILTIN_SCOPE.get(name))
     }
+
+    /// Constructs the scope of a module that only contains the definitions and submodules of the
+    /// module; imports are not included.
+    fn with_local_items(db: &impl HirDatabase, file_id: FileId, tree: &ModuleTree) -> ModuleScope {
+        let mut scope = ModuleScope::default();
+        let defs = db.module_data(file_id);
+        for def in defs.definitions() {
+            match def {
+                ModuleDef::Function(f) => {
+                    scope.items.insert(
+                        f.name(db),
+                        Resolution {
+                            def: PerNs::values(*def),
+                            visibility: f.visibility(db),
+                        },
+                    );
+                }
+                ModuleDef::Struct(s) => {
+                    scope.items.insert(
+                        s.name(db),
+                        Resolution {
+                            def: PerNs::both(*def, *def),
+                            visibility: s.visibility(db),
+                        },
+                    );
+                }
+                ModuleDef::Enum(e) => {
+                    scope.items.insert(
+                        e.name(db),
+                        Resolution {
+                            def: PerNs::types(*def),
+                            visibility: e.visibility(db),
+                        },
+                    );
+                }
+                ModuleDef::Const(c) => {
+                    scope.items.insert(
+                        c.name(db),
+                        Resolution {
+                            def: PerNs::values(*def),
+                            visibility: c.visibility(db),
+                        },
+                    );
+                }
+                ModuleDef::Static(s) => {
+                    scope.items.insert(
+                        s.name(db),
+                        Resolution {
+                            def: PerNs::values(*def),
+                            visibility: s.visibility(db),
+                        },
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(node) = tree.get(file_id) {
+            for (name, child) in node.children.iter() {
+                let module = Module::from(*child);
+                scope
+                    .items
+                    .entry(name.clone())
+                    .or_insert_with(|| Resolution {
+                        def: PerNs::types(module.into()),
+                        visibility: module.visibility(db),
+                    });
+            }
+        }
+        scope
+    }
 }
 
 pub(crate) fn module_scope_query(db: &impl HirDatabase, file_id: FileId) -> Arc<ModuleScope> {
-    let mut scope = ModuleScope::default();
-    let defs = db.module_data(file_id);
-    for def in defs.definitions() {
-        match def {
-            ModuleDef::Function(f) => {
-                scope.items.insert(
-                    f.name(db),
-                    Resolution {
-                        def: PerNs::values(*def),
-                    },
-                );
+    let package = Module::from(file_id).package(db);
+    let package_defs = db.package_defs(package.source_root());
+    let scope = match package_defs.modules.get(&file_id) {
+        Some(scope) => scope.clone(),
+        // The file is not part of the module tree of its package
+        None => ModuleScope::with_local_items(db, file_id, &package_defs.tree),
+    };
+    Arc::new(scope)
+}
+
+/// The scopes of all modules of a package. Imports are resolved iteratively until a fixed point is
+/// reached, which allows importing names that are themselves imported by another module.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PackageDefs {
+    tree: Arc<ModuleTree>,
+    modules: FxHashMap<FileId, ModuleScope>,
+    diagnostics: Vec<ImportDiagnostic>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) enum ImportDiagnostic {
+    /// The path of an import could not be resolved
+    UnresolvedImport { file_id: FileId, import: ImportId },
+    /// The path of an import refers to an item that is not visible from the importing module
+    PrivateImport {
+        file_id: FileId,
+        import: ImportId,
+        name: Name,
+    },
+}
+
+/// The result of resolving a single import
+enum ImportResolution {
+    /// The import (partially) resolved
+    Resolved,
+    /// The import could not be resolved (yet)
+    Unresolved,
+    /// The import resolved to an item that is not visible from the importing module
+    Private(Name),
+}
+
+impl PackageDefs {
+    pub(crate) fn package_defs_query(
+        db: &impl HirDatabase,
+        source_root: SourceRootId,
+    ) -> Arc<PackageDefs> {
+        let tree = db.module_tree(source_root);
+
+        // Sort the files to guarantee deterministic import resolution
+        let mut files: Vec<FileId> = tree.files().collect();
+        files.sort();
+
+        let mut package_defs = PackageDefs {
+            tree: tree.clone(),
+            modules: FxHashMap::default(),
+            diagnostics: Vec::new(),
+        };
+
+        let mut imports = Vec::new();
+        for file_id in files {
+            package_defs
+                .modules
+                .insert(file_id, ModuleScope::with_local_items(db, file_id, &tree));
+            let raw_items = db.raw_items(file_id);
+            for item in raw_items.items() {
+                if let RawFileItem::Import(import) = item {
+                    imports.push((file_id, *import));
+                }
+            }
+        }
+
+        // Resolve imports until no more progress is made. Glob imports are re-applied every
+        // iteration, because the scope they import from might have grown.
+        let mut resolved = FxHashSet::default();
+        let mut private = FxHashMap::default();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (file_id, import_id) in imports.iter() {
+                let raw_items = db.raw_items(*file_id);
+                let import = &raw_items[*import_id];
+                if !import.is_glob && resolved.contains(&(*file_id, *import_id)) {
+                    continue;
+                }
+                match package_defs.resolve_import(*file_id, import, &mut changed) {
+                    ImportResolution::Resolved => {
+                        resolved.insert((*file_id, *import_id));
+                    }
+                    ImportResolution::Private(name) => {
+                        private.insert((*file_id, *import_id), name);
+                    }
+                    ImportResolution::Unresolved => (),
+                }
             }
-            ModuleDef::Struct(s) => {
-                scope.items.insert(
-                    s.name(db),
-                    Resolution {
-                        def: PerNs::both(*def, *def),
+        }
+
+        for (file_id, import) in imports {
+            if resolved.contains(&(file_id, import)) {
+                continue;
+            }
+            package_defs
+                .diagnostics
+                .push(match private.remove(&(file_id, import)) {
+                    Some(name) => ImportDiagnostic::PrivateImport {
+                        file_id,
+                        import,
+                        name,
                     },
-                );
+                    None => ImportDiagnostic::UnresolvedImport { file_id, import },
+                });
+        }
+
+        Arc::new(package_defs)
+    }
+
+    /// Tries to resolve the specified import of the module `file_id` and adds the imported names
+    /// to the scope of the module. `changed` is set to true if any name was added.
+    fn resolve_import(
+        &mut self,
+        file_id: FileId,
+        import: &ImportData,
+        changed: &mut bool,
+    ) -> ImportResolution {
+        if import.is_glob {
+            let module =
+                match self.resolve_module(file_id, &import.path.kind, &import.path.segments) {
+                    Some(module) => module,
+                    None => return ImportResolution::Unresolved,
+                };
+            let entries: Vec<(Name, Resolution)> = match self.modules.get(&module) {
+                Some(scope) => scope
+                    .items
+                    .iter()
+                    .filter(|(_, res)| self.is_visible(file_id, module, res.visibility))
+                    .map(|(name, res)| {
+                        (
+                            name.clone(),
+                            Resolution {
+                                def: res.def,
+                                visibility: import.visibility,
+                            },
+                        )
+                    })
+                    .collect(),
+                None => return ImportResolution::Unresolved,
+            };
+            for (name, res) in entries {
+                *changed |= self.insert(file_id, name, res);
+            }
+            ImportResolution::Resolved
+        } else {
+            let (last, init) = match import.path.segments.split_last() {
+                Some(it) => it,
+                None => return ImportResolution::Unresolved,
+            };
+            let module = match self.resolve_module(file_id, &import.path.kind, init) {
+                Some(module) => module,
+                None => return ImportResolution::Unresolved,
+            };
+            let res = match self
+                .modules
+                .get(&module)
+                .and_then(|s| s.items.get(&last.name))
+            {
+                Some(res) => res.clone(),
+                None => return ImportResolution::Unresolved,
+            };
+            if !self.is_visible(file_id, module, res.visibility) {
+                return ImportResolution::Private(last.name.clone());
             }
-            _ => {}
+            let name = import.alias.clone().unwrap_or_else(|| last.name.clone());
+            *changed |= self.insert(
+                file_id,
+                name,
+                Resolution {
+                    def: res.def,
+                    visibility: import.visibility,
+                },
+            );
+            ImportResolution::Resolved
+        }
+    }
+
+    /// Adds a name to the scope of a module, if the name is not yet defined in that scope.
+    /// Returns true if the name was added.
+    fn insert(&mut self, file_id: FileId, name: Name, res: Resolution) -> bool {
+        let scope = self.modules.get_mut(&file_id).unwrap();
+        if scope.items.contains_key(&name) {
+            false
+        } else {
+            scope.items.insert(name, res);
+            true
+        }
+    }
+
+    /// Resolves the module that is referred to by a path with the specified `kind` and `segments`
+    /// from within the module `from`.
+    fn resolve_module(
+        &self,
+        from: FileId,
+        kind: &PathKind,
+        segments: &[PathSegment],
+    ) -> Option<FileId> {
+        let (mut module, rest) = match kind {
+            PathKind::Package | PathKind::Abs => (self.tree.root(), segments),
+            PathKind::Self_ => (from, segments),
+            PathKind::Super => (self.tree.get(from)?.parent?, segments),
+            PathKind::Plain => {
+                let (first, rest) = segments.split_first()?;
+                match self.modules.get(&from)?.items.get(&first.name)?.def.types {
+                    Some(ModuleDef::Module(m)) => (m.file_id, rest),
+                    _ => return None,
+                }
+            }
+        };
+
+        for segment in rest {
+            let res = self.modules.get(&module)?.items.get(&segment.name)?;
+            if !self.is_visible(from, module, res.visibility) {
+                return None;
+            }
+            module = match res.def.types {
+                Some(ModuleDef::Module(m)) => m.file_id,
+                _ => return None,
+            };
+        }
+
+        Some(module)
+    }
+
+    /// Resolves a path, that consists of more than a single identifier, from within the module
+    /// `from`. Only items that are visible from `from` are returned.
+    pub(crate) fn resolve_path(&self, from: FileId, path: &Path) -> PerNs<ModuleDef> {
+        let (last, init) = match path.segments.split_last() {
+            Some(it) => it,
+            None => return PerNs::none(),
+        };
+        let module = match self.resolve_module(from, &path.kind, init) {
+            Some(module) => module,
+            None => return PerNs::none(),
+        };
+        match self
+            .modules
+            .get(&module)
+            .and_then(|s| s.items.get(&last.name))
+        {
+            Some(res) if self.is_visible(from, module, res.visibility) => res.def,
+            _ => PerNs::none(),
+        }
+    }
+
+    /// Returns true if a name with the given `visibility` in the scope of module `owner` is visible
+    /// from within the module `from`.
+    pub(crate) fn is_visible(&self, from: FileId, owner: FileId, visibility: Visibility) -> bool {
+        if visibility.is_public() {
+            return true;
+        }
+        let mut current = Some(from);
+        while let Some(file_id) = current {
+            if file_id == owner {
+                return true;
+            }
+            current = self.tree.get(file_id).and_then(|node| node.parent);
+        }
+        false
+    }
+
+    pub(crate) fn diagnostics(&self) -> &[ImportDiagnostic] {
+        &self.diagnostics
+    }
+}
+
+impl ImportDiagnostic {
+    pub(crate) fn add_to(&self, db: &impl HirDatabase, owner: Module, sink: &mut DiagnosticSink) {
+        let (file_id, import) = match self {
+            ImportDiagnostic::UnresolvedImport { file_id, import }
+            | ImportDiagnostic::PrivateImport {
+                file_id, import, ..
+            } => (*file_id, *import),
+        };
+        if file_id != owner.file_id {
+            return;
+        }
+
+        // Find the `UseTree` that corresponds with the import
+        let raw_items = db.raw_items(file_id);
+        let import_data = &raw_items[import];
+        let use_item = import_data.ast_id.with_file_id(file_id).to_node(db);
+        let mut use_tree = None;
+        let mut index = 0;
+        Path::expand_use_item(&use_item, |_, tree, _, _| {
+            if index == import_data.index {
+                use_tree = Some(AstPtr::new(tree));
+            }
+            index += 1;
+        });
+        let use_tree = match use_tree {
+            Some(use_tree) => use_tree,
+            None => return,
+        };
+
+        match self {
+            ImportDiagnostic::UnresolvedImport { .. } => sink.push(UnresolvedImport {
+                file: file_id,
+                use_tree,
+            }),
+            ImportDiagnostic::PrivateImport { name, .. } => sink.push(PrivateImport {
+                file: file_id,
+                use_tree,
+                name: name.clone(),
+            }),
         }
     }
-    Arc::new(scope)
 }
@@ -1,5 +1,5 @@
 use crate::{AsName, Name};
-use mun_syntax::ast;
+use mun_syntax::ast::{self, NameOwner};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Path {
@@ -18,42 +18,66 @@ pub enum PathKind {
     Self_,
     Super,
     Abs,
+    /// A path relative to the root module of the package, e.g. `package::foo`
+    Package,
 }
 
 impl Path {
     /// Converts an `ast::Path` to `Path`.
-    pub fn from_ast(path: ast::Path) -> Option<Path> {
+    pub fn from_ast(mut path: ast::Path) -> Option<Path> {
         let mut kind = PathKind::Plain;
         let mut segments = Vec::new();
-        //        loop {
-        let segment = path.segment()?;
+        loop {
+            let segment = path.segment()?;
 
-        if segment.has_colon_colon() {
-            kind = PathKind::Abs;
-        }
-
-        match segment.kind()? {
-            ast::PathSegmentKind::Name(name) => {
-                let segment = PathSegment {
-                    name: name.as_name(),
-                };
-                segments.push(segment);
+            if segment.has_colon_colon() {
+                kind = PathKind::Abs;
             }
-            ast::PathSegmentKind::SelfKw => {
-                kind = PathKind::Self_;
-                //                    break;
-            }
-            ast::PathSegmentKind::SuperKw => {
-                kind = PathKind::Super;
-                //                    break;
+
+            match segment.kind()? {
+                ast::PathSegmentKind::Name(name) => {
+                    let segment = PathSegment {
+                        name: name.as_name(),
+                    };
+                    segments.push(segment);
+                }
+                ast::PathSegmentKind::SelfKw
+                | ast::PathSegmentKind::SuperKw
+                | ast::PathSegmentKind::PackageKw => {
+                    // Keywords are only allowed as the first segment of a path
+                    if path.qualifier().is_some() {
+                        return None;
+                    }
+                    kind = match segment.kind()? {
+                        ast::PathSegmentKind::SelfKw => PathKind::Self_,
+                        ast::PathSegmentKind::SuperKw => PathKind::Super,
+                        _ => PathKind::Package,
+                    };
+                    break;
+                }
             }
+
+            path = match path.qualifier() {
+                Some(it) => it,
+                None => break,
+            };
         }
-        //            break;
-        //        }
         segments.reverse();
         Some(Path { kind, segments })
     }
 
+    /// Calls `cb` with all paths, represented by this use item. The callback also receives the
+    /// `UseTree` the path originates from, whether the import is a glob import and the optional
+    /// alias of the import.
+    pub(crate) fn expand_use_item(
+        item_src: &ast::Use,
+        mut cb: impl FnMut(Path, &ast::UseTree, bool, Option<Name>),
+    ) {
+        if let Some(tree) = item_src.use_tree() {
+            lower_use_tree(None, &tree, &mut cb);
+        }
+    }
+
     /// Converts an `ast::NameRef` into a single-identifier `Path`.
     pub fn from_name_ref(name_ref: &ast::NameRef) -> Path {
         name_ref.as_name().into()
@@ -81,3 +105,101 @@ impl From<Name> for Path {
         }
     }
 }
+
+/// Recursively lowers a `UseTree` into `Path`s, prefixing every path with `prefix`.
+fn lower_use_tree(
+    prefix: Option<Path>,
+    tree: &ast::UseTree,
+    cb: &mut impl FnMut(Path, &ast::UseTree, bool, Option<Name>),
+) {
+    if let Some(use_tree_list) = tree.use_tree_list() {
+        let prefix = match tree.path() {
+            None => prefix,
+            Some(path) => match convert_path(prefix, &path) {
+                Some(it) => Some(it),
+                None => return, // TODO: report errors somewhere
+            },
+        };
+        for child_tree in use_tree_list.use_trees() {
+            lower_use_tree(prefix.clone(), &child_tree, cb);
+        }
+    } else {
+        let alias = tree.rename().and_then(|a| a.name()).map(|a| a.as_name());
+        let is_glob = tree.has_star_token();
+        if let Some(ast_path) = tree.path() {
+            // Handle `self` in a use tree list, e.g. `use foo::{self, bar}`
+            if let Some(prefix) = prefix.clone() {
+                if ast_path.qualifier().is_none() {
+                    if let Some(ast::PathSegmentKind::SelfKw) =
+                        ast_path.segment().and_then(|s| s.kind())
+                    {
+                        cb(prefix, tree, false, alias);
+                        return;
+                    }
+                }
+            }
+            if let Some(path) = convert_path(prefix, &ast_path) {
+                cb(path, tree, is_glob, alias)
+            }
+        } else if is_glob {
+            if let Some(prefix) = prefix {
+                cb(prefix, tree, is_glob, None)
+            }
+        }
+    }
+}
+
+/// Converts an `ast::Path` in a `UseTree` into a `Path`, prefixing it with `prefix`.
+fn convert_path(prefix: Option<Path>, path: &ast::Path) -> Option<Path> {
+    let prefix = if let Some(qualifier) = path.qualifier() {
+        Some(convert_path(prefix, &qualifier)?)
+    } else {
+        prefix
+    };
+
+    let segment = path.segment()?;
+    let res = match segment.kind()? {
+        ast::PathSegmentKind::Name(name) => {
+            let mut res = prefix.unwrap_or_else(|| Path {
+                kind: if segment.has_colon_colon() {
+                    PathKind::Abs
+                } else {
+                    PathKind::Plain
+                },
+                segments: Vec::with_capacity(1),
+            });
+            res.segments.push(PathSegment {
+                name: name.as_name(),
+            });
+            res
+        }
+        ast::PathSegmentKind::SelfKw => {
+            if prefix.is_some() {
+                return None;
+            }
+            Path {
+                kind: PathKind::Self_,
+                segments: Vec::new(),
+            }
+        }
+        ast::PathSegmentKind::SuperKw => {
+            if prefix.is_some() {
+                return None;
+            }
+            Path {
+                kind: PathKind::Super,
+                segments: Vec::new(),
+            }
+        }
+        ast::PathSegmentKind::PackageKw => {
+            if prefix.is_some() {
+                return None;
+            }
+            Path {
+                kind: PathKind::Package,
+                segments: Vec::new(),
+            }
+        }
+    };
+    Some(res)
+}
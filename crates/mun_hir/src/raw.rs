@@ -1,7 +1,7 @@
-use mun_syntax::ast::{self, ModuleItemOwner, NameOwner};
+use mun_syntax::ast::{self, ModuleItemOwner, NameOwner, VisibilityOwner};
 
 use crate::name::AsName;
-use crate::{Arena, DefDatabase, FileAstId, FileId, Name, RawId};
+use crate::{Arena, DefDatabase, FileAstId, FileId, Name, Path, RawId, Visibility};
 use std::ops::Index;
 use std::sync::Arc;
 
@@ -9,6 +9,7 @@ use std::sync::Arc;
 #[derive(Debug, Default, PartialEq, Eq)]
 pub struct RawItems {
     definitions: Arena<DefId, DefData>,
+    imports: Arena<ImportId, ImportData>,
     items: Vec<RawFileItem>,
 }
 
@@ -27,11 +28,35 @@ pub(super) struct DefData {
 pub(super) enum DefKind {
     Function(FileAstId<ast::FunctionDef>),
     Struct(FileAstId<ast::StructDef>),
+    Enum(FileAstId<ast::EnumDef>),
+    Trait(FileAstId<ast::TraitDef>),
+    Const(FileAstId<ast::ConstDef>),
+    Static(FileAstId<ast::StaticDef>),
+    Module(FileAstId<ast::Module>),
+}
+
+/// Id for an import (a single path of a `use` item)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct ImportId(RawId);
+impl_arena_id!(ImportId);
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct ImportData {
+    pub(super) path: Path,
+    pub(super) alias: Option<Name>,
+    pub(super) is_glob: bool,
+    pub(super) visibility: Visibility,
+    /// The `use` item that contains this import
+    pub(super) ast_id: FileAstId<ast::Use>,
+    /// The index of the import within the expanded `use` item
+    pub(super) index: usize,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub(super) enum RawFileItem {
     Definition(DefId),
+    Import(ImportId),
+    Impl(FileAstId<ast::ImplBlock>),
 }
 
 impl Index<DefId> for RawItems {
@@ -42,6 +67,14 @@ impl Index<DefId> for RawItems {
     }
 }
 
+impl Index<ImportId> for RawItems {
+    type Output = ImportData;
+
+    fn index(&self, index: ImportId) -> &Self::Output {
+        &self.imports[index]
+    }
+}
+
 impl RawItems {
     pub(crate) fn raw_file_items_query(db: &impl DefDatabase, file_id: FileId) -> Arc<RawItems> {
         let mut items = RawItems::default();
@@ -58,6 +91,48 @@ impl RawItems {
                 ast::ModuleItemKind::StructDef(it) => {
                     (DefKind::Struct((*ast_id_map).ast_id(&it)), it.name())
                 }
+                ast::ModuleItemKind::EnumDef(it) => {
+                    (DefKind::Enum((*ast_id_map).ast_id(&it)), it.name())
+                }
+                ast::ModuleItemKind::TraitDef(it) => {
+                    (DefKind::Trait((*ast_id_map).ast_id(&it)), it.name())
+                }
+                ast::ModuleItemKind::ConstDef(it) => {
+                    (DefKind::Const((*ast_id_map).ast_id(&it)), it.name())
+                }
+                ast::ModuleItemKind::StaticDef(it) => {
+                    (DefKind::Static((*ast_id_map).ast_id(&it)), it.name())
+                }
+                ast::ModuleItemKind::Module(it) => {
+                    (DefKind::Module((*ast_id_map).ast_id(&it)), it.name())
+                }
+                ast::ModuleItemKind::Use(it) => {
+                    let ast_id = (*ast_id_map).ast_id(&it);
+                    let visibility = it
+                        .visibility()
+                        .map(|_v| Visibility::Public)
+                        .unwrap_or(Visibility::Private);
+                    let mut index = 0;
+                    Path::expand_use_item(&it, |path, _tree, is_glob, alias| {
+                        let id = items.imports.alloc(ImportData {
+                            path,
+                            alias,
+                            is_glob,
+                            visibility,
+                            ast_id,
+                            index,
+                        });
+                        items.items.push(RawFileItem::Import(id));
+                        index += 1;
+                    });
+                    continue;
+                }
+                ast::ModuleItemKind::ImplBlock(it) => {
+                    items
+                        .items
+                        .push(RawFileItem::Impl((*ast_id_map).ast_id(&it)));
+                    continue;
+                }
             };
 
             // If no name is provided an error is already emitted
@@ -1,6 +1,6 @@
 use crate::{
-    expr::scope::ScopeId, expr::PatId, ExprScopes, FileId, HirDatabase, ModuleDef, Name, Path,
-    PerNs,
+    expr::scope::ScopeId, expr::PatId, name::name, ExprScopes, FileId, Function, GenericParams,
+    HirDatabase, ImplBlock, Module, ModuleDef, Name, Path, PathKind, PerNs, Ty,
 };
 use std::sync::Arc;
 
@@ -14,6 +14,12 @@ pub(crate) enum Scope {
     /// All the items and imported names of a module
     ModuleScope(ModuleItemMap),
 
+    /// Brings the `Self` type of an `impl` block into scope
+    ImplBlockScope(ImplBlock),
+
+    /// Brings the type parameters of a generic function or struct into scope
+    GenericParams(Arc<GenericParams>),
+
     /// Local bindings
     ExprScope(ExprScope),
 }
@@ -39,6 +45,18 @@ impl Resolver {
         self.push_scope(Scope::ModuleScope(ModuleItemMap { file_id }))
     }
 
+    pub(crate) fn push_impl_block_scope(self, impl_block: ImplBlock) -> Resolver {
+        self.push_scope(Scope::ImplBlockScope(impl_block))
+    }
+
+    pub(crate) fn push_generic_params_scope(self, params: Arc<GenericParams>) -> Resolver {
+        if params.is_empty() {
+            self
+        } else {
+            self.push_scope(Scope::GenericParams(params))
+        }
+    }
+
     pub(crate) fn push_expr_scope(
         self,
         expr_scopes: Arc<ExprScopes>,
@@ -57,6 +75,10 @@ pub enum Resolution {
     Def(ModuleDef),
     /// A local binding (only value namespace)
     LocalBinding(PatId),
+    /// The `Self` type of an `impl` block (only types namespace)
+    SelfType(ImplBlock),
+    /// A type parameter of a generic function or struct (only types namespace)
+    GenericParam(u32),
 }
 
 impl Resolver {
@@ -80,10 +102,117 @@ impl Resolver {
     ) -> PerNs<Resolution> {
         if let Some(name) = path.as_ident() {
             self.resolve_name(db, name)
+        } else if path.kind == PathKind::Self_ && path.segments.is_empty() {
+            // The `self` parameter of a method
+            self.resolve_name(db, &name![self])
+        } else if let Some(file_id) = self.module() {
+            let package = Module::from(file_id).package(db);
+            let resolution = db
+                .package_defs(package.source_root())
+                .resolve_path(file_id, path)
+                .map(Resolution::Def);
+            if resolution.is_none() {
+                self.resolve_type_item(db, path)
+            } else {
+                resolution
+            }
         } else {
             PerNs::none()
         }
     }
+
+    /// Resolves a path of which the last segment refers to an item of the type or trait that is
+    /// referred to by the other segments. This is either a variant of an enum, e.g.
+    /// `Shape::Circle`, a function of an `impl` block, e.g. `Shape::new`, or a function of a trait,
+    /// e.g. `Update::update`. A variant lives in both the types namespace (for record literals) and
+    /// the values namespace.
+    fn resolve_type_item(&self, db: &impl HirDatabase, path: &Path) -> PerNs<Resolution> {
+        let (last, init) = match path.segments.split_last() {
+            Some(it) => it,
+            None => return PerNs::none(),
+        };
+        let type_path = Path {
+            kind: path.kind.clone(),
+            segments: init.to_vec(),
+        };
+        let ty = match self
+            .resolve_path_without_assoc_items(db, &type_path)
+            .take_types()
+        {
+            Some(Resolution::Def(ModuleDef::Struct(s))) => s.ty(db),
+            Some(Resolution::Def(ModuleDef::Enum(e))) => e.ty(db),
+            Some(Resolution::SelfType(impl_block)) => impl_block.target_ty(db),
+            Some(Resolution::Def(ModuleDef::Trait(t))) => {
+                return match t.item(db, &last.name) {
+                    Some(function) => PerNs::values(Resolution::Def(function.into())),
+                    None => PerNs::none(),
+                };
+            }
+            _ => return PerNs::none(),
+        };
+
+        if let Some(variant) = ty.as_enum().and_then(|e| e.variant(db, &last.name)) {
+            let def = Resolution::Def(variant.into());
+            return PerNs::both(def.clone(), def);
+        }
+
+        match self.resolve_assoc_function(db, &ty, &last.name) {
+            Some(function) => PerNs::values(Resolution::Def(function.into())),
+            None => PerNs::none(),
+        }
+    }
+
+    /// Resolves the function with the specified `name` that is defined for the type `ty` in an
+    /// `impl` block. Only functions that are visible from the innermost module scope are returned.
+    pub(crate) fn resolve_assoc_function(
+        &self,
+        db: &impl HirDatabase,
+        ty: &Ty,
+        name: &Name,
+    ) -> Option<Function> {
+        let file_id = self.module()?;
+        let package = Module::from(file_id).package(db);
+        let function = db
+            .package_impls(package.source_root())
+            .lookup_function(db, ty, name)?;
+        if db.package_defs(package.source_root()).is_visible(
+            file_id,
+            function.module(db).file_id(),
+            function.visibility(db),
+        ) {
+            Some(function)
+        } else {
+            None
+        }
+    }
+
+    /// Calls `f` for every name that is visible from the resolver, starting with the innermost
+    /// scope. A name can be passed more than once if an inner scope shadows an outer scope.
+    pub fn process_all_names(
+        &self,
+        db: &impl HirDatabase,
+        f: &mut dyn FnMut(Name, PerNs<Resolution>),
+    ) {
+        for scope in self.scopes.iter().rev() {
+            scope.process_names(db, f);
+        }
+    }
+
+    /// Returns the type parameters of the innermost generic function or struct
+    pub(crate) fn generic_params(&self) -> Option<&Arc<GenericParams>> {
+        self.scopes.iter().rev().find_map(|scope| match scope {
+            Scope::GenericParams(params) => Some(params),
+            _ => None,
+        })
+    }
+
+    /// Returns the file of the innermost module scope
+    fn module(&self) -> Option<FileId> {
+        self.scopes.iter().rev().find_map(|scope| match scope {
+            Scope::ModuleScope(m) => Some(m.file_id),
+            _ => None,
+        })
+    }
 }
 
 impl Scope {
@@ -106,45 +235,53 @@ impl Scope {
                     None => PerNs::none(),
                 }
             }
+            Scope::ImplBlockScope(i) => {
+                if *name != name![Self] {
+                    return PerNs::none();
+                }
+                // In the values namespace, `Self` refers to the constructor of a struct
+                let values = i
+                    .target_ty(db)
+                    .as_struct()
+                    .map(|s| Resolution::Def(s.into()));
+                PerNs {
+                    types: Some(Resolution::SelfType(*i)),
+                    values,
+                }
+            }
+            Scope::GenericParams(params) => match params.find_by_name(name) {
+                Some(param) => PerNs::types(Resolution::GenericParam(param.idx)),
+                None => PerNs::none(),
+            },
         }
     }
 
-    //    fn collect_names(&self, db: &impl HirDatabase, f: &mut dyn FnMut(Name, PerNs<Resolution>)) {
-    //        match self {
-    //            Scope::ModuleScope(m) => {
-    //                // FIXME: should we provide `self` here?
-    //                // f(
-    //                //     Name::self_param(),
-    //                //     PerNs::types(Resolution::Def {
-    //                //         def: m.module.into(),
-    //                //     }),
-    //                // );
-    //                m.crate_def_map[m.module_id].scope.entries().for_each(|(name, res)| {
-    //                    f(name.clone(), res.def.map(Resolution::Def));
-    //                });
-    //                m.crate_def_map.extern_prelude().iter().for_each(|(name, def)| {
-    //                    f(name.clone(), PerNs::types(Resolution::Def(*def)));
-    //                });
-    //                if let Some(prelude) = m.crate_def_map.prelude() {
-    //                    let prelude_def_map = db.crate_def_map(prelude.krate);
-    //                    prelude_def_map[prelude.module_id].scope.entries().for_each(|(name, res)| {
-    //                        f(name.clone(), res.def.map(Resolution::Def));
-    //                    });
-    //                }
-    //            }
-    //            Scope::GenericParams(gp) => {
-    //                for param in &gp.params {
-    //                    f(param.name.clone(), PerNs::types(Resolution::GenericParam(param.idx)))
-    //                }
-    //            }
-    //            Scope::ImplBlockScope(i) => {
-    //                f(SELF_TYPE, PerNs::types(Resolution::SelfType(*i)));
-    //            }
-    //            Scope::ExprScope(e) => {
-    //                e.expr_scopes.entries(e.scope_id).iter().for_each(|e| {
-    //                    f(e.name().clone(), PerNs::values(Resolution::LocalBinding(e.pat())));
-    //                });
-    //            }
-    //        }
-    //    }
+    fn process_names(&self, db: &impl HirDatabase, f: &mut dyn FnMut(Name, PerNs<Resolution>)) {
+        match self {
+            Scope::ModuleScope(m) => {
+                db.module_scope(m.file_id)
+                    .entries()
+                    .for_each(|(name, res)| f(name.clone(), res.def.map(Resolution::Def)));
+            }
+            Scope::ExprScope(e) => {
+                e.expr_scopes.entries(e.scope_id).iter().for_each(|e| {
+                    f(
+                        e.name().clone(),
+                        PerNs::values(Resolution::LocalBinding(e.pat())),
+                    );
+                });
+            }
+            Scope::ImplBlockScope(i) => {
+                f(name![Self], PerNs::types(Resolution::SelfType(*i)));
+            }
+            Scope::GenericParams(params) => {
+                for param in params.params.iter() {
+                    f(
+                        param.name.clone(),
+                        PerNs::types(Resolution::GenericParam(param.idx)),
+                    );
+                }
+            }
+        }
+    }
 }
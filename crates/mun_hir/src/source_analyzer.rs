@@ -0,0 +1,279 @@
+//! `SourceAnalyzer` answers questions about a specific location in a source file, such as the type
+//! of the expression under the cursor or the definition that a name refers to. It is the entry
+//! point of the language server into the semantic model.
+
+use crate::adt::StructKind;
+use crate::code_model::{src::HasSource, DefWithBody, StructField};
+use crate::expr::{resolver_for_scope, scope::ScopeId, BodySourceMap, ExprScopes};
+use crate::in_file::InFile;
+use crate::resolve::{Resolution, Resolver};
+use crate::ty::InferenceResult;
+use crate::{AsName, FileId, HirDatabase, Module, ModuleDef, Name, PatId, Path, PerNs, Ty};
+use mun_syntax::{
+    ast::{self, NameOwner},
+    AstNode, SyntaxNode, SyntaxNodePtr, TextRange, TextUnit,
+};
+use std::sync::Arc;
+
+/// Provides semantic information about the syntax nodes of a single item of a file.
+#[derive(Debug)]
+pub struct SourceAnalyzer {
+    file_id: FileId,
+    resolver: Resolver,
+    body: Option<BodyInfo>,
+}
+
+#[derive(Debug)]
+struct BodyInfo {
+    owner: DefWithBody,
+    source_map: Arc<BodySourceMap>,
+    infer: Arc<InferenceResult>,
+}
+
+impl SourceAnalyzer {
+    /// Constructs a `SourceAnalyzer` for the item that contains `node`. If `offset` is specified,
+    /// the names that are visible at that offset are in scope, otherwise the names that are visible
+    /// at `node`.
+    pub fn new(
+        db: &impl HirDatabase,
+        file_id: FileId,
+        node: &SyntaxNode,
+        offset: Option<TextUnit>,
+    ) -> SourceAnalyzer {
+        let owner = match find_def_with_body(db, file_id, node) {
+            Some(owner) => owner,
+            None => {
+                return SourceAnalyzer {
+                    file_id,
+                    resolver: Resolver::default().push_module_scope(file_id),
+                    body: None,
+                }
+            }
+        };
+
+        let (body, source_map) = db.body_with_source_map(owner);
+        let scopes = db.expr_scopes(owner);
+        let scope = match offset {
+            Some(offset) => scope_for_offset(&scopes, &source_map, offset),
+            None => scope_for(&scopes, &source_map, node),
+        };
+        SourceAnalyzer {
+            file_id,
+            resolver: resolver_for_scope(body, db, scope),
+            body: Some(BodyInfo {
+                owner,
+                source_map,
+                infer: db.infer(owner),
+            }),
+        }
+    }
+
+    /// Returns the item that contains the analyzed location, if it is a function, a constant, or a
+    /// global variable.
+    pub fn owner(&self) -> Option<DefWithBody> {
+        self.body.as_ref().map(|body| body.owner)
+    }
+
+    /// Returns the inferred type of the expression `expr`.
+    pub fn type_of(&self, expr: &ast::Expr) -> Option<Ty> {
+        let body = self.body.as_ref()?;
+        let expr_id = body.source_map.node_expr(expr)?;
+        Some(body.infer[expr_id].clone())
+    }
+
+    /// Returns the inferred type of the pattern `pat`.
+    pub fn type_of_pat(&self, pat: &ast::Pat) -> Option<Ty> {
+        let body = self.body.as_ref()?;
+        let pat_id = body.source_map.node_pat(pat)?;
+        Some(body.infer[pat_id].clone())
+    }
+
+    /// Returns the type of the local binding `pat`.
+    pub fn type_of_binding(&self, pat: PatId) -> Option<Ty> {
+        let body = self.body.as_ref()?;
+        Some(body.infer[pat].clone())
+    }
+
+    /// Resolves the item, local binding, or type parameter that `path` refers to. A path of an
+    /// expression prefers the values namespace, all other paths prefer the types namespace.
+    pub fn resolve_path(&self, db: &impl HirDatabase, path: &ast::Path) -> Option<Resolution> {
+        let hir_path = Path::from_ast(path.clone())?;
+        let resolution = self
+            .resolver
+            .resolve_path_without_assoc_items(db, &hir_path);
+        let is_expr = path
+            .syntax()
+            .parent()
+            .and_then(ast::PathExpr::cast)
+            .is_some();
+        if is_expr {
+            let PerNs { types, values } = resolution;
+            values.or(types)
+        } else {
+            let PerNs { types, values } = resolution;
+            types.or(values)
+        }
+    }
+
+    /// Resolves the struct field that is accessed by the field expression `field`.
+    pub fn resolve_field(
+        &self,
+        db: &impl HirDatabase,
+        field: &ast::FieldExpr,
+    ) -> Option<StructField> {
+        let receiver = field.expr()?;
+        let name = field.field_access()?.as_name();
+        self.type_of(&receiver)?.as_struct()?.field(db, &name)
+    }
+
+    /// Calls `f` for every name that is visible at the analyzed location, starting with the names
+    /// of the innermost scope.
+    pub fn process_all_names(
+        &self,
+        db: &impl HirDatabase,
+        f: &mut dyn FnMut(Name, PerNs<Resolution>),
+    ) {
+        self.resolver.process_all_names(db, f)
+    }
+
+    /// Returns the range of the name of the declaration that `resolution` refers to.
+    pub fn declaration_range(
+        &self,
+        db: &impl HirDatabase,
+        resolution: &Resolution,
+    ) -> Option<InFile<TextRange>> {
+        match resolution {
+            Resolution::Def(def) => def_declaration_range(db, *def),
+            Resolution::LocalBinding(pat) => {
+                let body = self.body.as_ref()?;
+                let source = body.source_map.pat_syntax(*pat)?;
+                Some(source.map(|ptr| ptr.syntax_node_ptr().range()))
+            }
+            Resolution::SelfType(impl_block) => {
+                let source = impl_block.source(db);
+                Some(source.map(|it| it.syntax().text_range()))
+            }
+            Resolution::GenericParam(_) => None,
+        }
+    }
+
+    /// Returns the range of the name of the declaration of the struct field `field`.
+    pub fn field_declaration_range(
+        &self,
+        db: &impl HirDatabase,
+        field: StructField,
+    ) -> Option<InFile<TextRange>> {
+        // The fields of tuple structs have no name
+        if field.parent.data(db).kind != StructKind::Record {
+            return None;
+        }
+        name_range(field.source(db))
+    }
+
+    /// Returns the file that contains the analyzed location.
+    pub fn file_id(&self) -> FileId {
+        self.file_id
+    }
+}
+
+/// Returns the range of the name of the definition `def`. A module refers to the start of its
+/// file.
+fn def_declaration_range(db: &impl HirDatabase, def: ModuleDef) -> Option<InFile<TextRange>> {
+    match def {
+        ModuleDef::Module(module) => Some(InFile::new(
+            module.file_id(),
+            TextRange::offset_len(0.into(), 0.into()),
+        )),
+        ModuleDef::Function(f) => name_range(f.source(db)),
+        ModuleDef::Struct(s) => name_range(s.source(db)),
+        ModuleDef::Enum(e) => name_range(e.source(db)),
+        ModuleDef::EnumVariant(v) => {
+            let source = v.parent_enum().source(db);
+            let variant = source
+                .value
+                .enum_variant_list()?
+                .variants()
+                .nth(v.index(db))?;
+            name_range(source.with_value(variant))
+        }
+        ModuleDef::Trait(t) => name_range(t.source(db)),
+        ModuleDef::Const(c) => name_range(c.source(db)),
+        ModuleDef::Static(s) => name_range(s.source(db)),
+        ModuleDef::BuiltinFunction(_) | ModuleDef::BuiltinType(_) => None,
+    }
+}
+
+/// Returns the range of the name of `node`, or the range of the whole node if it has no name.
+fn name_range<N: NameOwner>(node: InFile<N>) -> Option<InFile<TextRange>> {
+    let range = match node.value.name() {
+        Some(name) => name.syntax().text_range(),
+        None => node.value.syntax().text_range(),
+    };
+    Some(node.with_value(range))
+}
+
+/// Finds the function, constant, or global variable of the file `file_id` that contains `node`.
+fn find_def_with_body(
+    db: &impl HirDatabase,
+    file_id: FileId,
+    node: &SyntaxNode,
+) -> Option<DefWithBody> {
+    let item = node.ancestors().find(|it| {
+        ast::FunctionDef::can_cast(it.kind())
+            || ast::ConstDef::can_cast(it.kind())
+            || ast::StaticDef::can_cast(it.kind())
+    })?;
+    let item_ptr = SyntaxNodePtr::new(&item);
+    Module::from(file_id)
+        .declarations(db)
+        .into_iter()
+        .find_map(|def| {
+            let (def, ptr): (DefWithBody, _) = match def {
+                ModuleDef::Function(f) => {
+                    (f.into(), SyntaxNodePtr::new(f.source(db).value.syntax()))
+                }
+                ModuleDef::Const(c) => (c.into(), SyntaxNodePtr::new(c.source(db).value.syntax())),
+                ModuleDef::Static(s) => (s.into(), SyntaxNodePtr::new(s.source(db).value.syntax())),
+                _ => return None,
+            };
+            if ptr == item_ptr {
+                Some(def)
+            } else {
+                None
+            }
+        })
+}
+
+/// Returns the innermost scope of the expressions that contain `node`.
+fn scope_for(
+    scopes: &ExprScopes,
+    source_map: &BodySourceMap,
+    node: &SyntaxNode,
+) -> Option<ScopeId> {
+    node.ancestors()
+        .filter_map(ast::Expr::cast)
+        .filter_map(|it| source_map.node_expr(&it))
+        .find_map(|it| scopes.scope_for(it))
+}
+
+/// Returns the scope of the smallest expression that contains `offset`.
+fn scope_for_offset(
+    scopes: &ExprScopes,
+    source_map: &BodySourceMap,
+    offset: TextUnit,
+) -> Option<ScopeId> {
+    scopes
+        .scope_by_expr()
+        .iter()
+        .filter_map(|(id, scope)| {
+            let source = source_map.expr_syntax(*id)?;
+            let range = source
+                .value
+                .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())
+                .range();
+            Some((range, *scope))
+        })
+        .filter(|(range, _)| range.start() <= offset && offset <= range.end())
+        .min_by_key(|(range, _)| range.len())
+        .map(|(_, scope)| scope)
+}
@@ -1,6 +1,8 @@
 use crate::db::HirDatabase;
 use crate::db::SourceDatabase;
+use crate::diagnostics::DiagnosticSink;
 use crate::mock::MockDatabase;
+use crate::{FileId, Module, ModuleDef};
 use std::sync::Arc;
 
 /// This function tests that the ModuleData of a module does not change if the contents of a function
@@ -47,3 +49,345 @@ fn check_module_data_does_not_change() {
         )
     }
 }
+
+/// Returns the messages of all diagnostics of the module in the specified file.
+fn module_diagnostics(db: &MockDatabase, file_id: FileId) -> Vec<String> {
+    let mut diags = Vec::new();
+    let mut sink = DiagnosticSink::new(|diag| diags.push(diag.message()));
+    Module::from(file_id).diagnostics(db, &mut sink);
+    drop(sink);
+    diags
+}
+
+#[test]
+fn module_tree() {
+    let (db, files) = MockDatabase::with_files(
+        r#"
+    //- main.mun
+    mod physics;
+    mod missing;
+    //- physics.mun
+    pub mod body;
+    //- physics/body.mun
+    pub struct Body;
+    //- body.mun
+    pub struct NotAModule;
+    "#,
+    );
+
+    let root = Module::from(files[0]);
+    let physics = Module::from(files[1]);
+    let body = Module::from(files[2]);
+
+    let package = root.package(&db);
+    assert_eq!(package.root_module(&db), root);
+    assert_eq!(package.modules(&db), vec![root, physics, body]);
+    assert_eq!(
+        package.unresolved_module_paths(&db),
+        vec![crate::RelativePathBuf::from("missing.mun")]
+    );
+
+    assert_eq!(root.name(&db), None);
+    assert_eq!(root.children(&db), vec![physics]);
+    assert_eq!(physics.parent(&db), Some(root));
+    assert_eq!(physics.children(&db), vec![body]);
+    assert_eq!(body.parent(&db), Some(physics));
+    assert_eq!(
+        body.path(&db)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec!["physics", "body"]
+    );
+
+    match body.declarations(&db)[0] {
+        ModuleDef::Struct(s) => assert_eq!(s.full_name(&db), "physics::body::Body"),
+        _ => panic!("expected a struct"),
+    }
+
+    assert_eq!(
+        module_diagnostics(&db, files[0]),
+        vec!["unresolved module, could not find `missing.mun`"]
+    );
+}
+
+#[test]
+fn use_across_files() {
+    let (db, files) = MockDatabase::with_files(
+        r#"
+    //- main.mun
+    mod physics;
+    use package::physics::{step, Body as PhysicsBody};
+
+    fn main() -> f32 {
+        let body = PhysicsBody { mass: 1.0 };
+        step(body) + physics::mass(body)
+    }
+    //- physics.mun
+    use super::physics::*;
+
+    pub struct Body { mass: f32 }
+
+    pub fn step(body: Body) -> f32 {
+        mass(body)
+    }
+
+    pub fn mass(body: Body) -> f32 {
+        body.mass
+    }
+    "#,
+    );
+
+    for file_id in files {
+        assert_eq!(module_diagnostics(&db, file_id), Vec::<String>::new());
+    }
+}
+
+#[test]
+fn unresolved_and_private_imports() {
+    let (db, files) = MockDatabase::with_files(
+        r#"
+    //- main.mun
+    mod physics;
+    use package::physics::secret;
+    use physics::missing;
+    use package::physics::{self, step};
+
+    fn main() {
+        physics::secret();
+        step()
+    }
+    //- physics.mun
+    fn secret() {}
+    pub fn step() {
+        secret()
+    }
+    "#,
+    );
+
+    assert_eq!(
+        module_diagnostics(&db, files[0]),
+        vec![
+            "`secret` is private",
+            "unresolved import",
+            "undefined value"
+        ]
+    );
+    assert_eq!(module_diagnostics(&db, files[1]), Vec::<String>::new());
+}
+
+#[test]
+fn impl_blocks_across_files() {
+    let (db, files) = MockDatabase::with_files(
+        r#"
+    //- main.mun
+    mod physics;
+    use physics::Body;
+
+    impl i32 {}
+
+    fn main() -> f32 {
+        let body = Body::new(1.0);
+        body.secret();
+        Body::secret(body);
+        body.mass()
+    }
+    //- physics.mun
+    pub struct Body { mass: f32 }
+
+    impl Body {
+        pub fn new(mass: f32) -> Self {
+            Self { mass }
+        }
+
+        pub fn mass(self) -> f32 {
+            self.mass
+        }
+
+        fn secret(self) -> f32 {
+            self.mass
+        }
+    }
+
+    impl Body {
+        fn mass(self) -> f32 {
+            0.0
+        }
+    }
+    "#,
+    );
+
+    let physics = Module::from(files[1]);
+    let function_names: Vec<String> = physics
+        .declarations(&db)
+        .into_iter()
+        .filter_map(|def| match def {
+            ModuleDef::Function(f) => Some(f.full_name(&db)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        function_names,
+        vec![
+            "physics::Body::new",
+            "physics::Body::mass",
+            "physics::Body::secret",
+            "physics::Body::mass"
+        ]
+    );
+
+    assert_eq!(
+        module_diagnostics(&db, files[0]),
+        vec![
+            "`impl` blocks can only be defined for structs and enums",
+            "no method named `secret` found for this type",
+            "undefined value"
+        ]
+    );
+    assert_eq!(
+        module_diagnostics(&db, files[1]),
+        vec!["the name `mass` is defined multiple times"]
+    );
+}
+
+#[test]
+fn traits_across_files() {
+    let (db, files) = MockDatabase::with_files(
+        r#"
+    //- main.mun
+    mod physics;
+    use physics::{Body, Update};
+
+    struct Enemy { hp: f32 }
+
+    impl Update for Enemy {
+        fn update(self, dt: f32) {}
+        fn draw(self) {}
+    }
+
+    impl Update for Enemy {
+        fn update(self, dt: f32) {}
+    }
+
+    impl Body for Enemy {}
+
+    impl physics::Render for Enemy {
+        fn render(self) -> i32 {
+            0
+        }
+    }
+
+    fn main() {
+        let body = Body { mass: 1.0 };
+        Update::update(body, 1.0);
+        body.update(1.0);
+        Update::update(1, 1.0);
+    }
+    //- physics.mun
+    pub struct Body { mass: f32 }
+
+    pub trait Update {
+        fn update(self, dt: f32);
+        fn reset(self);
+    }
+
+    pub trait Render {
+        fn render(self) -> f32;
+    }
+
+    impl Update for Body {
+        fn update(self, dt: f32) {}
+        fn reset(self) {}
+    }
+    "#,
+    );
+
+    let physics = Module::from(files[1]);
+    let function_names: Vec<String> = physics
+        .declarations(&db)
+        .into_iter()
+        .filter_map(|def| match def {
+            ModuleDef::Trait(t) => Some(t.items(&db)),
+            _ => None,
+        })
+        .flatten()
+        .map(|f| f.full_name(&db))
+        .collect();
+    assert_eq!(
+        function_names,
+        vec![
+            "physics::Update::update",
+            "physics::Update::reset",
+            "physics::Render::render"
+        ]
+    );
+
+    assert_eq!(
+        module_diagnostics(&db, files[0]),
+        vec![
+            "method `draw` is not a member of trait `Update`",
+            "missing methods of trait `Update`:\n- reset\n",
+            "conflicting implementations of trait `Update` for the same type",
+            "expected a trait",
+            "method `render` has an incompatible signature for trait `Render`",
+            "the trait `Update` is not implemented for this type"
+        ]
+    );
+    assert_eq!(module_diagnostics(&db, files[1]), Vec::<String>::new());
+}
+
+#[test]
+fn renamed_from_attributes() {
+    let (db, file_id) = MockDatabase::with_single_file(
+        r#"
+    struct Foo {
+        #[renamed_from("hp")]
+        health: f32,
+        mana: f32,
+    }
+
+    struct Bar {
+        #[renamed_from(hp)]
+        a: i32,
+        #[renamed_from("x", "y")]
+        b: i32,
+        #[renamed_from]
+        c: i32,
+        #[renamed_from("d")]
+        #[renamed_from("e")]
+        f: i32,
+        #[renamed_from("a")]
+        g: i32,
+        #[renamed_from("d")]
+        h: i32,
+    }
+    "#,
+    );
+
+    let foo = Module::from(file_id)
+        .declarations(&db)
+        .into_iter()
+        .find_map(|def| match def {
+            ModuleDef::Struct(s) if s.name(&db).to_string() == "Foo" => Some(s),
+            _ => None,
+        })
+        .unwrap();
+    let renamed_from: Vec<Option<String>> = foo
+        .fields(&db)
+        .into_iter()
+        .map(|field| field.renamed_from(&db))
+        .collect();
+    assert_eq!(renamed_from, vec![Some("hp".to_owned()), None]);
+
+    assert_eq!(
+        module_diagnostics(&db, file_id),
+        vec![
+            "expected the previous name of the field as a string literal",
+            "expected the previous name of the field as a string literal",
+            "expected the previous name of the field as a string literal",
+            "a field can only be renamed from a single name",
+            "cannot rename from `a`, as the struct still has a field with that name",
+            "the field `d` is renamed by multiple fields of the struct"
+        ]
+    );
+}
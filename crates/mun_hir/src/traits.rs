@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use crate::ids::{AstItemDef, FunctionId, LocationCtx, TraitId};
+use crate::{AsName, DefDatabase, Function, Name, Visibility};
+use mun_syntax::ast::{FunctionDefOwner, NameOwner, VisibilityOwner};
+
+/// The data of a trait: its name and the functions it declares, e.g.
+/// `trait Update { fn update(self, dt: f32); }`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TraitData {
+    pub name: Name,
+    pub visibility: Visibility,
+    pub items: Vec<Function>,
+}
+
+impl TraitData {
+    pub(crate) fn trait_data_query(db: &impl DefDatabase, id: TraitId) -> Arc<TraitData> {
+        let src = id.source(db);
+        let loc_ctx = LocationCtx::new(db, src.file_id);
+        let name = src
+            .value
+            .name()
+            .map(|n| n.as_name())
+            .unwrap_or_else(Name::missing);
+
+        let visibility = src
+            .value
+            .visibility()
+            .map(|_v| Visibility::Public)
+            .unwrap_or(Visibility::Private);
+
+        let items = src
+            .value
+            .item_list()
+            .map(|item_list| {
+                item_list
+                    .functions()
+                    .map(|f| Function {
+                        id: FunctionId::from_ast(loc_ctx, &f),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Arc::new(TraitData {
+            name,
+            visibility,
+            items,
+        })
+    }
+}
@@ -4,13 +4,16 @@ mod op;
 mod primitives;
 mod resolve;
 
+use crate::adt::StructKind;
 use crate::display::{HirDisplay, HirFormatter};
 use crate::ty::infer::InferTy;
-use crate::ty::lower::fn_sig_for_struct_constructor;
+use crate::ty::lower::{
+    fn_sig_for_builtin_fn, fn_sig_for_enum_variant_constructor, fn_sig_for_struct_constructor,
+};
 use crate::utils::make_mut_slice;
-use crate::{HirDatabase, Struct, StructMemoryKind};
+use crate::{Enum, GenericParams, HirDatabase, Name, Struct, StructMemoryKind};
 pub(crate) use infer::infer_query;
-pub use infer::InferenceResult;
+pub use infer::{BuiltinMethod, InferenceResult, MethodResolution};
 pub(crate) use lower::{callable_item_sig, fn_sig_for_fn, type_for_def, CallableDef, TypableDef};
 pub use primitives::{FloatTy, IntTy};
 pub use resolve::ResolveBitness;
@@ -28,6 +31,13 @@ pub enum Ty {
 
     Apply(ApplicationTy),
 
+    /// A type parameter of a generic function or struct, e.g. `T` in `fn max<T>(a: T, b: T) -> T`.
+    /// The index refers to the position of the parameter in the `GenericParams` of its definition.
+    Param {
+        idx: u32,
+        name: Name,
+    },
+
     /// A type variable used during type checking. Not to be confused with a type parameter.
     Infer(InferTy),
 
@@ -61,10 +71,31 @@ pub enum TypeCtor {
     /// The primitive boolean type. Written as `bool`.
     Bool,
 
+    /// The immutable, garbage collected string type. Written as `string`.
+    String,
+
     /// An abstract datatype (structures, tuples, or enumerations)
-    /// TODO: Add tuples and enumerations
     Struct(Struct),
 
+    /// A sum type of which a value is exactly one of its variants, e.g. `enum Shape { .. }`.
+    Enum(Enum),
+
+    /// A fixed-size array of elements, written as `[T; N]`. The element type is stored as the
+    /// single type parameter.
+    Array(u32),
+
+    /// A growable array of elements that is allocated on the heap, written as `[T]`. The element
+    /// type is stored as the single type parameter.
+    DynArray,
+
+    /// A garbage collected reference that may be `nil`, written as `T?`. The referenced type is
+    /// stored as the single type parameter.
+    Optional,
+
+    /// An anonymous tuple of values, e.g. `(f32, i64)`. The types of the elements are stored as
+    /// the type parameters.
+    Tuple,
+
     /// The never type `never`.
     Never,
 
@@ -81,6 +112,17 @@ pub enum TypeCtor {
     /// let bar = foo; // bar: function() -> number {foo}
     /// ```
     FnDef(CallableDef),
+
+    /// A pointer to a function or closure, written as `fn(A, B) -> R`. The types of the
+    /// parameters followed by the return type are stored as the type parameters.
+    ///
+    /// For example the type of `bar` here:
+    ///
+    /// ```mun
+    /// fn foo(a: f32) -> f32 { a }
+    /// let bar: fn(f32) -> f32 = foo;
+    /// ```
+    FnPtr { num_args: u16 },
 }
 
 impl Ty {
@@ -91,6 +133,48 @@ impl Ty {
         })
     }
 
+    /// Constructs a fixed-size array type `[T; N]`
+    pub fn array(element_ty: Ty, length: u32) -> Ty {
+        Ty::Apply(ApplicationTy {
+            ctor: TypeCtor::Array(length),
+            parameters: Substs::single(element_ty),
+        })
+    }
+
+    /// Constructs a growable array type `[T]`
+    pub fn dyn_array(element_ty: Ty) -> Ty {
+        Ty::Apply(ApplicationTy {
+            ctor: TypeCtor::DynArray,
+            parameters: Substs::single(element_ty),
+        })
+    }
+
+    /// Constructs an optional type `T?`
+    pub fn optional(inner_ty: Ty) -> Ty {
+        Ty::Apply(ApplicationTy {
+            ctor: TypeCtor::Optional,
+            parameters: Substs::single(inner_ty),
+        })
+    }
+
+    /// Constructs a tuple type with the specified element types, e.g. `(f32, i64)`
+    pub fn tuple(element_tys: Vec<Ty>) -> Ty {
+        Ty::Apply(ApplicationTy {
+            ctor: TypeCtor::Tuple,
+            parameters: element_tys.into(),
+        })
+    }
+
+    /// Constructs a function pointer type with the specified signature, e.g. `fn(f32) -> f32`
+    pub fn fn_ptr(sig: FnSig) -> Ty {
+        Ty::Apply(ApplicationTy {
+            ctor: TypeCtor::FnPtr {
+                num_args: sig.params().len() as u16,
+            },
+            parameters: Substs(sig.params_and_return),
+        })
+    }
+
     pub fn as_simple(&self) -> Option<TypeCtor> {
         match self {
             Ty::Apply(ApplicationTy { ctor, parameters }) if parameters.0.is_empty() => Some(*ctor),
@@ -131,47 +215,186 @@ impl Ty {
         }
     }
 
+    pub fn as_enum(&self) -> Option<Enum> {
+        match self {
+            Ty::Apply(a_ty) => match a_ty.ctor {
+                TypeCtor::FnDef(CallableDef::EnumVariant(v)) => Some(v.parent_enum()),
+                TypeCtor::Enum(e) => Some(e),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the element type of an array type, if this is an array type.
+    pub fn as_array(&self) -> Option<&Ty> {
+        match self {
+            Ty::Apply(ApplicationTy {
+                ctor: TypeCtor::Array(_),
+                parameters,
+            })
+            | Ty::Apply(ApplicationTy {
+                ctor: TypeCtor::DynArray,
+                parameters,
+            }) => parameters.as_single(),
+            _ => None,
+        }
+    }
+
+    /// Returns the referenced type of an optional type, if this is an optional type.
+    pub fn as_optional(&self) -> Option<&Ty> {
+        match self {
+            Ty::Apply(ApplicationTy {
+                ctor: TypeCtor::Optional,
+                parameters,
+            }) => parameters.as_single(),
+            _ => None,
+        }
+    }
+
+    /// Returns the types of the elements of a tuple type, if this is a tuple type.
+    pub fn as_tuple(&self) -> Option<&Substs> {
+        match self {
+            Ty::Apply(ApplicationTy {
+                ctor: TypeCtor::Tuple,
+                parameters,
+            }) => Some(parameters),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the type contains a type parameter, i.e. it is not a concrete type.
+    pub fn has_params(&self) -> bool {
+        match self {
+            Ty::Param { .. } => true,
+            Ty::Apply(a_ty) => a_ty.parameters.iter().any(Ty::has_params),
+            Ty::Empty | Ty::Infer(_) | Ty::Unknown => false,
+        }
+    }
+
+    /// Returns the signature of a callable with its type parameters substituted by the type
+    /// arguments of this type.
     pub fn callable_sig(&self, db: &impl HirDatabase) -> Option<FnSig> {
         match self {
             Ty::Apply(a_ty) => match a_ty.ctor {
-                TypeCtor::FnDef(def) => Some(db.callable_sig(def)),
+                TypeCtor::FnDef(def) => Some(db.callable_sig(def).subst(&a_ty.parameters)),
+                TypeCtor::FnPtr { .. } => Some(FnSig::from_fn_ptr_substs(&a_ty.parameters)),
                 _ => None,
             },
             _ => None,
         }
     }
 
+    /// Returns the type arguments of a generic struct or callable, e.g. `f32` and `i64` for
+    /// `Pair<f32, i64>`.
+    pub fn substs(&self) -> Option<Substs> {
+        match self {
+            Ty::Apply(ApplicationTy { parameters, .. }) => Some(parameters.clone()),
+            _ => None,
+        }
+    }
+
+    /// Replaces all type parameters in this type by the corresponding types in `substs`.
+    pub fn subst(self, substs: &Substs) -> Ty {
+        self.fold(&mut |ty| match ty {
+            Ty::Param { idx, name } => substs
+                .get(idx as usize)
+                .cloned()
+                .unwrap_or(Ty::Param { idx, name }),
+            ty => ty,
+        })
+    }
+
     /// Returns the type's name as a string, if one exists.
     ///
     /// This name needs to be unique as it is used to generate a type's `Guid`.
     pub fn guid_string(&self, db: &impl HirDatabase) -> Option<String> {
+        if let Ty::Apply(ApplicationTy { ctor, parameters }) = self {
+            match ctor {
+                TypeCtor::Array(length) => {
+                    let element_ty = parameters.as_single()?.guid_string(db)?;
+                    return Some(format!("[{}; {}]", element_ty, length));
+                }
+                TypeCtor::DynArray => {
+                    let element_ty = parameters.as_single()?.guid_string(db)?;
+                    return Some(format!("[{}]", element_ty));
+                }
+                // An optional reference has the same memory layout as the reference itself
+                TypeCtor::Optional => return parameters.as_single()?.guid_string(db),
+                TypeCtor::Struct(s) => return Some(struct_guid_string(db, s, parameters)),
+                TypeCtor::Tuple => {
+                    let element_tys = parameters
+                        .iter()
+                        .map(|ty| ty.guid_string(db))
+                        .collect::<Option<Vec<_>>>()?;
+                    return Some(tuple_name(&element_tys));
+                }
+                TypeCtor::FnPtr { .. } => {
+                    let sig = FnSig::from_fn_ptr_substs(parameters);
+                    let param_tys = sig
+                        .params()
+                        .iter()
+                        .map(|ty| ty.guid_string(db))
+                        .collect::<Option<Vec<_>>>()?;
+                    let ret_ty = if sig.ret().is_empty() {
+                        None
+                    } else {
+                        Some(sig.ret().guid_string(db)?)
+                    };
+                    return Some(fn_ptr_name(&param_tys, ret_ty.as_deref()));
+                }
+                _ => {}
+            }
+        }
+
         self.as_simple().and_then(|ty_ctor| match ty_ctor {
-            TypeCtor::Struct(s) => {
-                let name = s.name(db).to_string();
+            TypeCtor::Enum(e) => {
+                let name = e.name(db).to_string();
 
-                Some(if s.data(db).memory_kind == StructMemoryKind::GC {
-                    format!("struct {}", name)
+                Some(if e.data(db).memory_kind == StructMemoryKind::GC {
+                    format!("enum {}", name)
                 } else {
-                    let fields: Vec<String> = s
-                        .fields(db)
+                    let variants: Vec<String> = e
+                        .variants(db)
                         .into_iter()
-                        .map(|f| {
-                            let ty_string = f
-                                .ty(db)
-                                .guid_string(db)
-                                .expect("type should be convertible to a string");
-                            format!("{}: {}", f.name(db).to_string(), ty_string)
+                        .map(|v| {
+                            let fields: Vec<String> = v
+                                .fields(db)
+                                .into_iter()
+                                .map(|f| {
+                                    let ty_string = f
+                                        .ty(db)
+                                        .guid_string(db)
+                                        .expect("type should be convertible to a string");
+                                    match v.kind(db) {
+                                        StructKind::Record => {
+                                            format!("{}: {}", f.name(db).to_string(), ty_string)
+                                        }
+                                        _ => ty_string,
+                                    }
+                                })
+                                .collect();
+                            match v.kind(db) {
+                                StructKind::Record => {
+                                    format!("{}{{{}}}", v.name(db), fields.join(","))
+                                }
+                                StructKind::Tuple => {
+                                    format!("{}({})", v.name(db), fields.join(","))
+                                }
+                                StructKind::Unit => v.name(db).to_string(),
+                            }
                         })
                         .collect();
 
                     format!(
-                        "struct {name}{{{fields}}}",
+                        "enum {name}{{{variants}}}",
                         name = name,
-                        fields = fields.join(",")
+                        variants = variants.join(",")
                     )
                 })
             }
             TypeCtor::Bool => Some("core::bool".to_string()),
+            TypeCtor::String => Some("core::string".to_string()),
             TypeCtor::Float(ty) => Some(format!("core::{}", ty.as_str())),
             TypeCtor::Int(ty) => Some(format!("core::{}", ty.as_str())),
             _ => None,
@@ -179,6 +402,66 @@ impl Ty {
     }
 }
 
+/// Returns the name of a tuple type with elements of which the types have the specified names, e.g.
+/// `(f32, i64)`. A tuple with a single element is written with a trailing comma: `(f32,)`.
+pub fn tuple_name(element_names: &[String]) -> String {
+    if element_names.len() == 1 {
+        format!("({},)", element_names[0])
+    } else {
+        format!("({})", element_names.join(", "))
+    }
+}
+
+/// Returns the name of a function pointer type with parameters and a return type of which the
+/// types have the specified names, e.g. `fn(f32, i64) -> f32`. The return type is omitted if the
+/// function does not return a value.
+pub fn fn_ptr_name(param_names: &[String], ret_name: Option<&str>) -> String {
+    match ret_name {
+        Some(ret_name) => format!("fn({}) -> {}", param_names.join(", "), ret_name),
+        None => format!("fn({})", param_names.join(", ")),
+    }
+}
+
+/// Returns the unique name of an instantiation of the struct `s`, e.g. `struct Pair<core::f32,
+/// core::i64>`. The fields of a value struct are part of the name as they determine its memory
+/// layout.
+fn struct_guid_string(db: &impl HirDatabase, s: Struct, substs: &Substs) -> String {
+    let mut name = s.name(db).to_string();
+    if !substs.is_empty() {
+        let type_args: Vec<String> = substs
+            .iter()
+            .map(|ty| {
+                ty.guid_string(db)
+                    .expect("type should be convertible to a string")
+            })
+            .collect();
+        name = format!("{}<{}>", name, type_args.join(", "));
+    }
+
+    if s.data(db).memory_kind == StructMemoryKind::GC {
+        format!("struct {}", name)
+    } else {
+        let fields: Vec<String> = s
+            .fields(db)
+            .into_iter()
+            .map(|f| {
+                let ty_string = f
+                    .ty(db)
+                    .subst(substs)
+                    .guid_string(db)
+                    .expect("type should be convertible to a string");
+                format!("{}: {}", f.name(db).to_string(), ty_string)
+            })
+            .collect();
+
+        format!(
+            "struct {name}{{{fields}}}",
+            name = name,
+            fields = fields.join(",")
+        )
+    }
+}
+
 /// A list of substitutions for generic parameters.
 #[derive(Clone, PartialEq, Eq, Debug, Hash)]
 pub struct Substs(Arc<[Ty]>);
@@ -191,6 +474,35 @@ impl Substs {
     pub fn single(ty: Ty) -> Substs {
         Substs(Arc::new([ty]))
     }
+
+    /// Returns a substitution that maps each type parameter to itself, e.g. `<A, B>` for
+    /// `struct Pair<A, B>`.
+    pub fn identity(generic_params: &GenericParams) -> Substs {
+        generic_params
+            .params
+            .iter()
+            .map(|p| Ty::Param {
+                idx: p.idx,
+                name: p.name.clone(),
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// Returns the type if this substitution contains exactly one type.
+    pub fn as_single(&self) -> Option<&Ty> {
+        if self.0.len() == 1 {
+            Some(&self.0[0])
+        } else {
+            None
+        }
+    }
+}
+
+impl From<Vec<Ty>> for Substs {
+    fn from(v: Vec<Ty>) -> Self {
+        Substs(v.into())
+    }
 }
 
 impl Deref for Substs {
@@ -222,6 +534,13 @@ impl FnSig {
         }
     }
 
+    /// Returns the signature of a function pointer type with the type parameters `substs`.
+    pub fn from_fn_ptr_substs(substs: &Substs) -> FnSig {
+        FnSig {
+            params_and_return: substs.0.clone(),
+        }
+    }
+
     pub fn params(&self) -> &[Ty] {
         &self.params_and_return[0..self.params_and_return.len() - 1]
     }
@@ -230,12 +549,38 @@ impl FnSig {
         &self.params_and_return[self.params_and_return.len() - 1]
     }
 
+    /// Replaces all type parameters in the signature by the corresponding types in `substs`.
+    pub fn subst(&self, substs: &Substs) -> FnSig {
+        FnSig {
+            params_and_return: self
+                .params_and_return
+                .iter()
+                .map(|ty| ty.clone().subst(substs))
+                .collect::<Vec<_>>()
+                .into(),
+        }
+    }
+
     pub fn marshallable(&self, db: &impl HirDatabase) -> bool {
         for ty in self.params_and_return.iter() {
             if let Some(s) = ty.as_struct() {
                 if s.data(db).memory_kind == StructMemoryKind::Value {
                     return false;
                 }
+            } else if let Some(e) = ty.as_enum() {
+                if e.data(db).memory_kind == StructMemoryKind::Value {
+                    return false;
+                }
+            } else if let Ty::Apply(ApplicationTy {
+                ctor: TypeCtor::Array(_),
+                ..
+            })
+            | Ty::Apply(ApplicationTy {
+                ctor: TypeCtor::Tuple,
+                ..
+            }) = ty
+            {
+                return false;
             }
         }
         true
@@ -248,6 +593,7 @@ impl HirDisplay for Ty {
             Ty::Apply(a_ty) => a_ty.hir_fmt(f),
             Ty::Unknown => write!(f, "{{unknown}}"),
             Ty::Empty => write!(f, "nothing"),
+            Ty::Param { name, .. } => write!(f, "{}", name),
             Ty::Infer(tv) => match tv {
                 InferTy::TypeVar(tv) => write!(f, "'{}", tv.0),
                 InferTy::IntVar(_) => write!(f, "{{integer}}"),
@@ -263,24 +609,76 @@ impl HirDisplay for ApplicationTy {
             TypeCtor::Float(ty) => write!(f, "{}", ty),
             TypeCtor::Int(ty) => write!(f, "{}", ty),
             TypeCtor::Bool => write!(f, "bool"),
-            TypeCtor::Struct(def) => write!(f, "{}", def.name(f.db)),
+            TypeCtor::String => write!(f, "string"),
+            TypeCtor::Struct(def) => {
+                write!(f, "{}", def.name(f.db))?;
+                if !self.parameters.is_empty() {
+                    write!(f, "<")?;
+                    f.write_joined(&*self.parameters, ", ")?;
+                    write!(f, ">")?;
+                }
+                Ok(())
+            }
+            TypeCtor::Enum(def) => write!(f, "{}", def.name(f.db)),
+            TypeCtor::Array(length) => {
+                write!(f, "[{}; {}]", self.parameters[0].display(f.db), length)
+            }
+            TypeCtor::DynArray => write!(f, "[{}]", self.parameters[0].display(f.db)),
+            TypeCtor::Optional => write!(f, "{}?", self.parameters[0].display(f.db)),
+            TypeCtor::Tuple => {
+                let element_names: Vec<String> = self
+                    .parameters
+                    .iter()
+                    .map(|ty| ty.display(f.db).to_string())
+                    .collect();
+                write!(f, "{}", tuple_name(&element_names))
+            }
             TypeCtor::Never => write!(f, "never"),
+            TypeCtor::FnPtr { .. } => {
+                let sig = FnSig::from_fn_ptr_substs(&self.parameters);
+                write!(f, "fn(")?;
+                f.write_joined(sig.params(), ", ")?;
+                write!(f, ")")?;
+                if !sig.ret().is_empty() {
+                    write!(f, " -> {}", sig.ret().display(f.db))?;
+                }
+                Ok(())
+            }
             TypeCtor::FnDef(CallableDef::Function(def)) => {
-                let sig = fn_sig_for_fn(f.db, def);
+                let sig = fn_sig_for_fn(f.db, def).subst(&self.parameters);
                 let name = def.name(f.db);
                 write!(f, "function {}", name)?;
                 write!(f, "(")?;
                 f.write_joined(sig.params(), ", ")?;
                 write!(f, ") -> {}", sig.ret().display(f.db))
             }
+            TypeCtor::FnDef(CallableDef::BuiltinFunction(def)) => {
+                let sig = fn_sig_for_builtin_fn(def);
+                write!(f, "function {}", def.name())?;
+                write!(f, "(")?;
+                f.write_joined(sig.params(), ", ")?;
+                write!(f, ") -> {}", sig.ret().display(f.db))
+            }
             TypeCtor::FnDef(CallableDef::Struct(def)) => {
-                let sig = fn_sig_for_struct_constructor(f.db, def);
+                let sig = fn_sig_for_struct_constructor(f.db, def).subst(&self.parameters);
                 let name = def.name(f.db);
                 write!(f, "ctor {}", name)?;
                 write!(f, "(")?;
                 f.write_joined(sig.params(), ", ")?;
                 write!(f, ") -> {}", sig.ret().display(f.db))
             }
+            TypeCtor::FnDef(CallableDef::EnumVariant(def)) => {
+                let sig = fn_sig_for_enum_variant_constructor(f.db, def);
+                write!(
+                    f,
+                    "ctor {}::{}",
+                    def.parent_enum().name(f.db),
+                    def.name(f.db)
+                )?;
+                write!(f, "(")?;
+                f.write_joined(sig.params(), ", ")?;
+                write!(f, ") -> {}", sig.ret().display(f.db))
+            }
         }
     }
 }
@@ -299,7 +697,7 @@ impl Ty {
                     t.walk_mut(f);
                 }
             }
-            Ty::Empty | Ty::Infer(_) | Ty::Unknown => {}
+            Ty::Empty | Ty::Param { .. } | Ty::Infer(_) | Ty::Unknown => {}
         }
         f(self)
     }
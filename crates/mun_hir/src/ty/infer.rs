@@ -1,24 +1,29 @@
 use crate::{
     adt::StructKind,
     arena::map::ArenaMap,
-    code_model::{DefWithBody, DefWithStruct, Struct},
+    code_model::{DefWithBody, DefWithStruct, EnumVariant},
     diagnostics::DiagnosticSink,
     expr,
-    expr::{Body, Expr, ExprId, Literal, Pat, PatId, RecordLitField, Statement, UnaryOp},
+    expr::{
+        Body, Expr, ExprId, Literal, LogicOp, MatchArm, Pat, PatId, RecordLitField, Statement,
+        UnaryOp,
+    },
+    name::name,
     name_resolution::Namespace,
     resolve::{Resolution, Resolver},
     ty::infer::diagnostics::InferenceDiagnostic,
     ty::infer::type_variable::TypeVariableTable,
     ty::lower::LowerDiagnostic,
     ty::op,
-    ty::{Ty, TypableDef},
-    type_ref::TypeRefId,
-    ApplicationTy, BinaryOp, Function, HirDatabase, Name, Path, TypeCtor,
+    ty::{CallableDef, FnSig, Substs, Ty, TypableDef},
+    type_ref::{TypeRef, TypeRefId},
+    ApplicationTy, BinaryOp, Function, HirDatabase, ModuleDef, Name, Path, TypeCtor,
 };
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::ops::Index;
 use std::sync::Arc;
 
+mod narrowing;
 mod place_expr;
 mod type_variable;
 mod unify;
@@ -46,11 +51,37 @@ macro_rules! ty_app {
 
 mod coerce;
 
+/// A method that is built into the language.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum BuiltinMethod {
+    /// `len()` on `[T; N]` and `[T]`; returns the number of elements as a `usize`.
+    ArrayLen,
+    /// `push(value)` on `[T]`; appends an element to the end of the array.
+    ArrayPush,
+    /// `pop()` on `[T]`; removes the last element of the array and returns it.
+    ArrayPop,
+    /// `len()` on `string`; returns the number of bytes as a `usize`.
+    StringLen,
+}
+
+/// The method that is called by a method call expression.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MethodResolution {
+    /// A method that is built into the language, e.g. `len()` on arrays.
+    Builtin(BuiltinMethod),
+    /// A function with a `self` parameter that is defined in an `impl` block.
+    Function(Function),
+}
+
 /// The result of type inference: A mapping from expressions and patterns to types.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct InferenceResult {
     pub(crate) type_of_expr: ArenaMap<ExprId, Ty>,
     pub(crate) type_of_pat: ArenaMap<PatId, Ty>,
+    pub(crate) method_resolutions: FxHashMap<ExprId, MethodResolution>,
+    pub(crate) method_substs: FxHashMap<ExprId, Substs>,
+    pub(crate) variant_resolutions: FxHashMap<ExprOrPatId, EnumVariant>,
+    pub(crate) closure_captures: FxHashMap<ExprId, Vec<PatId>>,
     pub(crate) diagnostics: Vec<diagnostics::InferenceDiagnostic>,
 }
 
@@ -69,11 +100,69 @@ impl Index<PatId> for InferenceResult {
 }
 
 impl InferenceResult {
+    /// Returns the method that is called by the specified method call expression.
+    pub fn method_resolution(&self, expr: ExprId) -> Option<MethodResolution> {
+        self.method_resolutions.get(&expr).copied()
+    }
+
+    /// Returns the type arguments of the generic method that is called by the specified method
+    /// call expression, e.g. `f32` for `pair.with_first(1.0)` if `with_first` is defined as
+    /// `fn with_first<T>(self, value: T)`.
+    pub fn method_substs(&self, expr: ExprId) -> Option<&Substs> {
+        self.method_substs.get(&expr)
+    }
+
+    /// Returns the enum variant that is constructed by the specified expression, e.g.
+    /// `Shape::Circle` or `Shape::Rect { w: 1.0, h: 2.0 }`.
+    pub fn variant_resolution_for_expr(&self, expr: ExprId) -> Option<EnumVariant> {
+        self.variant_resolutions.get(&expr.into()).copied()
+    }
+
+    /// Returns the enum variant that is matched by the specified pattern.
+    pub fn variant_resolution_for_pat(&self, pat: PatId) -> Option<EnumVariant> {
+        self.variant_resolutions.get(&pat.into()).copied()
+    }
+
+    /// Returns the local bindings of an enclosing scope that are used by the specified closure
+    /// expression, in the order in which they are first used. Closures capture these bindings by
+    /// value.
+    pub fn closure_captures(&self, expr: ExprId) -> &[PatId] {
+        self.closure_captures
+            .get(&expr)
+            .map_or(&[], |captures| captures.as_slice())
+    }
+
+    /// Returns the types of all expressions and patterns.
+    pub fn types(&self) -> impl Iterator<Item = &Ty> {
+        self.type_of_expr.values().chain(self.type_of_pat.values())
+    }
+
+    /// Returns a copy of the result in which all type parameters are replaced by the types in
+    /// `substs`. This is used to generate code for an instantiation of a generic function.
+    pub fn subst(&self, substs: &Substs) -> InferenceResult {
+        let mut result = self.clone();
+        for ty in result
+            .type_of_expr
+            .values_mut()
+            .chain(result.type_of_pat.values_mut())
+        {
+            *ty = ty.clone().subst(substs);
+        }
+        for method_substs in result.method_substs.values_mut() {
+            *method_substs = method_substs
+                .iter()
+                .map(|ty| ty.clone().subst(substs))
+                .collect::<Vec<_>>()
+                .into();
+        }
+        result
+    }
+
     /// Adds all the `InferenceDiagnostic`s of the result to the `DiagnosticSink`.
     pub(crate) fn add_diagnostics(
         &self,
         db: &impl HirDatabase,
-        owner: Function,
+        owner: DefWithBody,
         sink: &mut DiagnosticSink,
     ) {
         self.diagnostics
@@ -90,7 +179,9 @@ pub fn infer_query(db: &impl HirDatabase, def: DefWithBody) -> Arc<InferenceResu
     let mut ctx = InferenceResultBuilder::new(db, body, resolver);
 
     match def {
-        DefWithBody::Function(_) => ctx.infer_signature(),
+        DefWithBody::Function(_) | DefWithBody::Const(_) | DefWithBody::Static(_) => {
+            ctx.infer_signature()
+        }
     }
 
     ctx.infer_body();
@@ -130,6 +221,14 @@ enum ActiveLoop {
     For,
 }
 
+/// A closure of which the body is being inferred.
+struct ActiveClosure {
+    /// The local bindings that are introduced by the closure's parameters and body
+    bindings: FxHashSet<PatId>,
+    /// The local bindings of enclosing scopes that are used by the closure
+    captures: Vec<PatId>,
+}
+
 /// The inference context contains all information needed during type inference.
 struct InferenceResultBuilder<'a, D: HirDatabase> {
     db: &'a D,
@@ -138,6 +237,10 @@ struct InferenceResultBuilder<'a, D: HirDatabase> {
 
     type_of_expr: ArenaMap<ExprId, Ty>,
     type_of_pat: ArenaMap<PatId, Ty>,
+    method_resolutions: FxHashMap<ExprId, MethodResolution>,
+    method_substs: FxHashMap<ExprId, Substs>,
+    variant_resolutions: FxHashMap<ExprOrPatId, EnumVariant>,
+    closure_captures: FxHashMap<ExprId, Vec<PatId>>,
     diagnostics: Vec<InferenceDiagnostic>,
 
     type_variables: TypeVariableTable,
@@ -148,8 +251,16 @@ struct InferenceResultBuilder<'a, D: HirDatabase> {
     /// encountered.
     active_loop: Option<ActiveLoop>,
 
-    /// The return type of the function being inferred.
+    /// The closures that enclose the expression that is being inferred, from outermost to
+    /// innermost.
+    active_closures: Vec<ActiveClosure>,
+
+    /// The return type of the function or closure being inferred.
     return_ty: Ty,
+
+    /// The optional local bindings that are known not to be `nil` at the expression that is being
+    /// inferred, because they have been compared to `nil` by an enclosing condition.
+    narrowed_bindings: FxHashSet<PatId>,
 }
 
 impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
@@ -158,13 +269,19 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
         InferenceResultBuilder {
             type_of_expr: ArenaMap::default(),
             type_of_pat: ArenaMap::default(),
+            method_resolutions: FxHashMap::default(),
+            method_substs: FxHashMap::default(),
+            variant_resolutions: FxHashMap::default(),
+            closure_captures: FxHashMap::default(),
             diagnostics: Vec::default(),
             active_loop: None,
+            active_closures: Vec::new(),
             type_variables: TypeVariableTable::default(),
             db,
             body,
             resolver,
             return_ty: Ty::Unknown, // set in collect_fn_signature
+            narrowed_bindings: FxHashSet::default(),
         }
     }
 
@@ -189,18 +306,78 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
             &self.body.type_refs(),
             type_ref,
         );
+        self.push_lower_diagnostics(result.diagnostics);
+        result.ty
+    }
 
-        // Convert the diagnostics from resolving the type reference
-        for diag in result.diagnostics {
+    /// Resolves the type of a record literal. Unlike other type references, the type arguments of
+    /// a generic struct can be omitted, e.g. `Pair { a: 1, b: 2.0 }`, in which case they are
+    /// inferred.
+    fn resolve_record_lit_type(&mut self, type_ref: TypeRefId) -> Ty {
+        let type_args_omitted = match &self.body.type_refs()[type_ref] {
+            TypeRef::Path(_, type_args) => type_args.is_empty(),
+            _ => false,
+        };
+        if !type_args_omitted {
+            return self.resolve_type(type_ref);
+        }
+
+        let result = Ty::from_hir(self.db, &self.resolver, &self.body.type_refs(), type_ref);
+        let diagnostics = result
+            .diagnostics
+            .into_iter()
+            .filter(|diag| match diag {
+                LowerDiagnostic::WrongNumberOfTypeArguments { id, found: 0, .. } => *id != type_ref,
+                _ => true,
+            })
+            .collect();
+        self.push_lower_diagnostics(diagnostics);
+        self.instantiate_generics(result.ty)
+    }
+
+    /// Converts the diagnostics from resolving a type reference
+    fn push_lower_diagnostics(&mut self, diagnostics: Vec<LowerDiagnostic>) {
+        for diag in diagnostics {
             let diag = match diag {
                 LowerDiagnostic::UnresolvedType { id } => {
                     InferenceDiagnostic::UnresolvedType { id }
                 }
+                LowerDiagnostic::InvalidArrayLength { id } => {
+                    InferenceDiagnostic::InvalidArrayLength { id }
+                }
+                LowerDiagnostic::InvalidOptionalType { id } => {
+                    InferenceDiagnostic::InvalidOptionalType { id }
+                }
+                LowerDiagnostic::WrongNumberOfTypeArguments {
+                    id,
+                    expected,
+                    found,
+                } => InferenceDiagnostic::WrongNumberOfTypeArguments {
+                    id,
+                    expected,
+                    found,
+                },
             };
             self.diagnostics.push(diag);
         }
+    }
 
-        result.ty
+    /// Replaces the type arguments of a generic function or struct by new type variables, e.g.
+    /// `Pair<A, B>` becomes `Pair<'0, '1>`. The type arguments are then inferred from their usage.
+    fn instantiate_generics(&mut self, ty: Ty) -> Ty {
+        match ty {
+            Ty::Apply(ApplicationTy { ctor, parameters }) if !parameters.is_empty() => {
+                let parameters: Vec<Ty> = parameters
+                    .iter()
+                    .map(|_| self.type_variables.new_type_var())
+                    .collect();
+                Ty::Apply(ApplicationTy {
+                    ctor,
+                    parameters: parameters.into(),
+                })
+            }
+            ty => ty,
+        }
     }
 }
 
@@ -224,12 +401,145 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
     /// Record the type of the specified pattern and all sub-patterns.
     fn infer_pat(&mut self, pat: PatId, ty: Ty) {
         let body = Arc::clone(&self.body); // avoid borrow checker problem
-        #[allow(clippy::single_match)]
         match &body[pat] {
             Pat::Bind { .. } => {
+                if let Some(closure) = self.active_closures.last_mut() {
+                    closure.bindings.insert(pat);
+                }
                 self.set_pat_type(pat, ty);
             }
-            _ => {}
+            Pat::Path(path) => {
+                self.infer_variant_pat(pat, Some(path), StructKind::Unit, &ty);
+            }
+            Pat::Tuple(args) => {
+                let element_tys: Vec<Ty> = args
+                    .iter()
+                    .map(|_| self.type_variables.new_type_var())
+                    .collect();
+                let tuple_ty = Ty::tuple(element_tys.clone());
+                if self.unify(&tuple_ty, &ty) {
+                    for (arg, element_ty) in args.iter().zip(element_tys) {
+                        self.infer_pat(*arg, element_ty);
+                    }
+                    self.set_pat_type(pat, tuple_ty);
+                } else {
+                    self.diagnostics.push(InferenceDiagnostic::MismatchedTypes {
+                        id: pat.into(),
+                        expected: ty.clone(),
+                        found: tuple_ty,
+                    });
+                    for arg in args.iter() {
+                        self.infer_pat(*arg, Ty::Unknown);
+                    }
+                    self.set_pat_type(pat, Ty::Unknown);
+                }
+            }
+            Pat::TupleStruct { path, args } => {
+                let field_tys = self
+                    .infer_variant_pat(pat, path.as_ref(), StructKind::Tuple, &ty)
+                    .map(|variant| {
+                        variant
+                            .fields(self.db)
+                            .into_iter()
+                            .map(|field| field.ty(self.db))
+                            .collect::<Vec<_>>()
+                    });
+                if let Some(field_tys) = &field_tys {
+                    if field_tys.len() != args.len() {
+                        self.diagnostics
+                            .push(InferenceDiagnostic::FieldCountMismatch {
+                                id: pat.into(),
+                                found: args.len(),
+                                expected: field_tys.len(),
+                            });
+                    }
+                }
+                for (idx, arg) in args.iter().enumerate() {
+                    let field_ty = field_tys
+                        .as_ref()
+                        .and_then(|tys| tys.get(idx).cloned())
+                        .unwrap_or(Ty::Unknown);
+                    self.infer_pat(*arg, field_ty);
+                }
+            }
+            Pat::Record { path, args } => {
+                let variant = self.infer_variant_pat(pat, path.as_ref(), StructKind::Record, &ty);
+                for field in args.iter() {
+                    let field_ty = match variant {
+                        Some(variant) => match variant.field(self.db, &field.name) {
+                            Some(variant_field) => variant_field.ty(self.db),
+                            None => {
+                                self.diagnostics
+                                    .push(InferenceDiagnostic::AccessUnknownField {
+                                        id: field.pat.into(),
+                                        receiver_ty: ty.clone(),
+                                        name: field.name.clone(),
+                                    });
+                                Ty::Unknown
+                            }
+                        },
+                        None => Ty::Unknown,
+                    };
+                    self.infer_pat(field.pat, field_ty);
+                }
+            }
+            Pat::Wild | Pat::Missing => {}
+        }
+    }
+
+    /// Resolves the enum variant that is matched by a pattern and checks that it is a variant of
+    /// the expected type. Returns `None` if the variant could not be resolved or if it is of a
+    /// different kind than the pattern.
+    fn infer_variant_pat(
+        &mut self,
+        pat: PatId,
+        path: Option<&Path>,
+        kind: StructKind,
+        expected: &Ty,
+    ) -> Option<EnumVariant> {
+        let variant = match path.and_then(|path| self.resolve_variant(path)) {
+            Some(variant) => variant,
+            None => {
+                self.diagnostics
+                    .push(InferenceDiagnostic::UnresolvedValue { id: pat.into() });
+                return None;
+            }
+        };
+        self.variant_resolutions.insert(pat.into(), variant);
+
+        let ty = variant.parent_enum().ty(self.db);
+        if !self.unify(&ty, expected) {
+            self.diagnostics.push(InferenceDiagnostic::MismatchedTypes {
+                id: pat.into(),
+                expected: expected.clone(),
+                found: ty.clone(),
+            });
+        }
+        self.set_pat_type(pat, ty);
+
+        let variant_kind = variant.kind(self.db);
+        if variant_kind != kind {
+            self.diagnostics
+                .push(InferenceDiagnostic::MismatchedStructLit {
+                    id: pat.into(),
+                    expected: variant_kind,
+                    found: kind,
+                });
+            return None;
+        }
+
+        Some(variant)
+    }
+
+    /// Returns the enum variant that is referred to by the specified path, e.g. `Shape::Circle`.
+    fn resolve_variant(&self, path: &Path) -> Option<EnumVariant> {
+        match self
+            .resolver
+            .resolve_path_without_assoc_items(self.db, path)
+            .take_types()?
+        {
+            Resolution::Def(ModuleDef::EnumVariant(variant)) => Some(variant),
+            _ => None,
         }
     }
 
@@ -248,7 +558,7 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
             self.diagnostics.push(InferenceDiagnostic::MismatchedTypes {
                 expected: expected.ty.clone(),
                 found: ty.clone(),
-                id: tgt_expr,
+                id: tgt_expr.into(),
             });
         };
 
@@ -269,7 +579,7 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
             self.diagnostics.push(InferenceDiagnostic::MismatchedTypes {
                 expected: expected.ty.clone(),
                 found: ty.clone(),
-                id: expr,
+                id: expr.into(),
             });
             ty
         } else if expected.ty == Ty::Unknown {
@@ -294,8 +604,32 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
             Expr::Path(p) => {
                 // FIXME this could be more efficient...
                 let resolver = expr::resolver_for_expr(self.body.clone(), self.db, tgt_expr);
-                self.infer_path_expr(&resolver, p, tgt_expr, check_params)
-                    .unwrap_or(Ty::Unknown)
+                let ty = self
+                    .infer_path_expr(&resolver, p, tgt_expr, check_params)
+                    .unwrap_or(Ty::Unknown);
+                // A function that is not called directly is used as a function pointer. The
+                // expression retains the type of the function definition, which identifies the
+                // function that is pointed to.
+                if let ty_app!(TypeCtor::FnDef(CallableDef::Function(_))) = &ty {
+                    if !check_params.is_callee {
+                        let fn_ptr_ty = Ty::fn_ptr(ty.callable_sig(self.db).unwrap());
+                        let ty = self.resolve_ty_as_far_as_possible(ty);
+                        self.set_expr_type(tgt_expr, ty);
+                        return fn_ptr_ty;
+                    }
+                }
+                // Builtin functions have no address, so they cannot be used as a function pointer
+                if let ty_app!(TypeCtor::FnDef(CallableDef::BuiltinFunction(f))) = &ty {
+                    if !check_params.is_callee {
+                        self.diagnostics
+                            .push(InferenceDiagnostic::BuiltinFunctionNotCalled {
+                                id: tgt_expr,
+                                name: f.name(),
+                            });
+                        return Ty::Unknown;
+                    }
+                }
+                ty
             }
             Expr::If {
                 condition,
@@ -308,7 +642,17 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
                         BinaryOp::LogicOp(..) => Expectation::has_type(Ty::simple(TypeCtor::Bool)),
                         _ => Expectation::none(),
                     };
+                    // The assigned local binding is not narrowed, as it may be assigned `nil`
+                    let assigned = match op {
+                        BinaryOp::Assignment { .. } => self.local_binding(*lhs),
+                        _ => None,
+                    };
+                    let was_narrowed =
+                        assigned.map_or(false, |pat| self.narrowed_bindings.remove(&pat));
                     let lhs_ty = self.infer_expr(*lhs, &lhs_expected);
+                    if was_narrowed {
+                        self.narrowed_bindings.insert(assigned.unwrap());
+                    }
                     if let BinaryOp::Assignment { op: _op } = op {
                         let resolver =
                             expr::resolver_for_expr(self.body.clone(), self.db, tgt_expr);
@@ -328,7 +672,29 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
                                 rhs: rhs_expected.clone(),
                             })
                     }
-                    let rhs_ty = self.infer_expr(*rhs, &Expectation::has_type(rhs_expected));
+                    let rhs_expected = Expectation::has_type(rhs_expected);
+                    let rhs_ty = match op {
+                        // The right hand side of a logic operation is only evaluated if the left
+                        // hand side did not already determine the result
+                        BinaryOp::LogicOp(logic_op) => {
+                            let checks = self.nil_checks(*lhs);
+                            let narrowed = match logic_op {
+                                LogicOp::And => checks.when_true,
+                                LogicOp::Or => checks.when_false,
+                            };
+                            self.infer_narrowed(*rhs, &narrowed, |this| {
+                                this.infer_expr(*rhs, &rhs_expected)
+                            })
+                        }
+                        // A reference can be assigned to, or compared with, an optional reference
+                        _ if rhs_expected.ty.as_optional().is_some() => {
+                            self.infer_expr_coerce(*rhs, &rhs_expected)
+                        }
+                        _ => self.infer_expr(*rhs, &rhs_expected),
+                    };
+                    if let Some(pat) = assigned {
+                        self.narrowed_bindings.remove(&pat);
+                    }
                     op::binary_op_return_ty(*op, rhs_ty)
                 }
                 _ => Ty::Unknown,
@@ -336,8 +702,9 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
             Expr::Block { statements, tail } => self.infer_block(statements, *tail, expected),
             Expr::Call { callee: call, args } => self.infer_call(tgt_expr, *call, args, expected),
             Expr::Literal(lit) => match lit {
-                Literal::String(_) => Ty::Unknown,
+                Literal::String(_) => Ty::simple(TypeCtor::String),
                 Literal::Bool(_) => Ty::simple(TypeCtor::Bool),
+                Literal::Nil => Ty::optional(self.type_variables.new_type_var()),
                 Literal::Int(LiteralInt {
                     kind: LiteralIntKind::Suffixed(suffix),
                     ..
@@ -375,50 +742,105 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
             Expr::While { condition, body } => {
                 self.infer_while_expr(tgt_expr, *condition, *body, expected)
             }
+            Expr::For {
+                pat,
+                iterable,
+                body,
+            } => self.infer_for_expr(tgt_expr, *pat, *iterable, *body, expected),
+            Expr::Match { expr, arms } => self.infer_match(*expr, arms, expected),
+            Expr::Range { start, end, .. } => {
+                self.infer_range_bounds(tgt_expr, *start, *end);
+                self.diagnostics
+                    .push(InferenceDiagnostic::RangeOutsideForLoop { id: tgt_expr });
+                Ty::Unknown
+            }
             Expr::RecordLit {
                 type_id,
                 fields,
                 spread,
             } => {
-                let ty = self.resolve_type(*type_id);
+                let ty = self.resolve_record_lit_type(*type_id);
+                let substs = ty.substs().unwrap_or_else(Substs::empty);
+                let variant = match &body.type_refs()[*type_id] {
+                    TypeRef::Path(path, _) => self.resolve_variant(path),
+                    _ => None,
+                };
                 let def_id = ty.as_struct();
                 self.unify(&ty, &expected.ty);
 
                 for (idx, field) in fields.iter().enumerate() {
-                    let field_ty = def_id
-                        .as_ref()
-                        .and_then(|it| match it.field(self.db, &field.name) {
-                            Some(field) => Some(field),
-                            None => {
-                                self.diagnostics.push(InferenceDiagnostic::NoSuchField {
-                                    id: tgt_expr,
-                                    field: idx,
-                                });
-                                None
-                            }
-                        })
-                        .map_or(Ty::Unknown, |field| field.ty(self.db));
+                    let field_ty = match (variant, def_id) {
+                        (Some(variant), _) => variant
+                            .field(self.db, &field.name)
+                            .map(|field| field.ty(self.db)),
+                        (None, Some(s)) => s
+                            .field(self.db, &field.name)
+                            .map(|field| field.ty(self.db).subst(&substs)),
+                        (None, None) => Some(Ty::Unknown),
+                    };
+                    let field_ty = field_ty.unwrap_or_else(|| {
+                        self.diagnostics.push(InferenceDiagnostic::NoSuchField {
+                            id: tgt_expr,
+                            field: idx,
+                        });
+                        Ty::Unknown
+                    });
                     self.infer_expr_coerce(field.expr, &Expectation::has_type(field_ty));
                 }
                 if let Some(expr) = spread {
                     self.infer_expr(*expr, &Expectation::has_type(ty.clone()));
                 }
-                if let Some(s) = ty.as_struct() {
-                    self.check_record_lit(tgt_expr, s, &fields);
+                if let Some(variant) = variant {
+                    self.variant_resolutions.insert(tgt_expr.into(), variant);
+                    let field_names: Vec<Name> = variant
+                        .fields(self.db)
+                        .into_iter()
+                        .map(|field| field.name(self.db))
+                        .collect();
+                    self.check_record_lit(tgt_expr, variant.kind(self.db), &field_names, &fields);
+                } else if let Some(s) = def_id {
+                    let struct_data = s.data(self.db);
+                    let field_names: Vec<Name> = struct_data
+                        .fields
+                        .iter()
+                        .map(|(_, field)| field.name.clone())
+                        .collect();
+                    self.check_record_lit(tgt_expr, struct_data.kind, &field_names, &fields);
                 }
                 ty
             }
             Expr::Field { expr, name } => {
                 let receiver_ty = self.infer_expr(*expr, &Expectation::none());
-                match receiver_ty {
-                    ty_app!(TypeCtor::Struct(s)) => {
-                        match s.field(self.db, name).map(|field| field.ty(self.db)) {
+                match &receiver_ty {
+                    ty_app!(TypeCtor::Struct(s), substs) => {
+                        match s
+                            .field(self.db, name)
+                            .map(|field| field.ty(self.db).subst(substs))
+                        {
                             Some(field_ty) => field_ty,
                             None => {
                                 self.diagnostics
                                     .push(InferenceDiagnostic::AccessUnknownField {
-                                        id: tgt_expr,
-                                        receiver_ty,
+                                        id: tgt_expr.into(),
+                                        receiver_ty: receiver_ty.clone(),
+                                        name: name.clone(),
+                                    });
+
+                                Ty::Unknown
+                            }
+                        }
+                    }
+                    ty_app!(TypeCtor::Tuple, element_tys) => {
+                        match name
+                            .as_tuple_index()
+                            .and_then(|idx| element_tys.get(idx).cloned())
+                        {
+                            Some(element_ty) => element_ty,
+                            None => {
+                                self.diagnostics
+                                    .push(InferenceDiagnostic::AccessUnknownField {
+                                        id: tgt_expr.into(),
+                                        receiver_ty: receiver_ty.clone(),
                                         name: name.clone(),
                                     });
 
@@ -426,15 +848,55 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
                             }
                         }
                     }
+                    ty_app!(TypeCtor::Optional) => {
+                        self.diagnostics.push(InferenceDiagnostic::PossiblyNil {
+                            id: *expr,
+                            found: receiver_ty.clone(),
+                        });
+                        Ty::Unknown
+                    }
                     _ => {
                         self.diagnostics.push(InferenceDiagnostic::NoFields {
                             id: *expr,
-                            found: receiver_ty,
+                            found: receiver_ty.clone(),
                         });
                         Ty::Unknown
                     }
                 }
             }
+            Expr::MethodCall {
+                receiver,
+                method_name,
+                args,
+            } => self.infer_method_call(tgt_expr, *receiver, method_name, args),
+            Expr::Index { base, index } => {
+                let base_ty = self.infer_expr(*base, &Expectation::none());
+                self.infer_expr_coerce(
+                    *index,
+                    &Expectation::has_type(Ty::simple(TypeCtor::Int(IntTy::usize()))),
+                );
+                match base_ty.as_array() {
+                    Some(element_ty) => element_ty.clone(),
+                    None => {
+                        if base_ty != Ty::Unknown {
+                            self.diagnostics.push(InferenceDiagnostic::CannotIndex {
+                                id: *base,
+                                found: base_ty,
+                            });
+                        }
+                        Ty::Unknown
+                    }
+                }
+            }
+            Expr::Array(exprs) => self.infer_array_lit(exprs, expected),
+            Expr::Tuple(exprs) => self.infer_tuple_lit(exprs, expected),
+            Expr::Cast { expr, type_ref } => self.infer_cast(tgt_expr, *expr, *type_ref),
+            Expr::Lambda {
+                args,
+                arg_types,
+                ret_type,
+                body,
+            } => self.infer_lambda(tgt_expr, args, arg_types, *ret_type, *body, expected),
             Expr::UnaryOp { expr, op } => {
                 let inner_ty =
                     self.infer_expr_inner(*expr, &Expectation::none(), &CheckParams::default());
@@ -500,10 +962,26 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
             condition,
             &Expectation::has_type(Ty::simple(TypeCtor::Bool)),
         );
-        let then_ty = self.infer_expr_coerce(then_branch, expected);
+        let checks = self.nil_checks(condition);
+        let then_ty = self.infer_narrowed(then_branch, &checks.when_true, |this| {
+            this.infer_expr_coerce(then_branch, expected)
+        });
+        // If one of the branches diverges, the code after the `if` expression is only reached
+        // through the other branch
+        if then_ty.is_never() {
+            self.narrowed_bindings
+                .extend(checks.when_false.iter().copied());
+        }
         match else_branch {
             Some(else_branch) => {
-                let else_ty = self.infer_expr_coerce(else_branch, expected);
+                let else_ty = self.infer_narrowed(else_branch, &checks.when_false, |this| {
+                    this.infer_expr_coerce(else_branch, expected)
+                });
+                if else_ty.is_never() && !then_ty.is_never() {
+                    self.narrowed_bindings
+                        .extend(checks.when_true.iter().copied());
+                    self.forget_assigned_bindings(then_branch);
+                }
                 match self.coerce_merge_branch(&then_ty, &else_ty) {
                     Some(ty) => ty,
                     None => {
@@ -530,6 +1008,31 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
         }
     }
 
+    /// Inferences the type of a match expression. The type of the expression is the common type of
+    /// all its arms, or `never` if there are no arms.
+    fn infer_match(&mut self, expr: ExprId, arms: &[MatchArm], expected: &Expectation) -> Ty {
+        let input_ty = self.infer_expr(expr, &Expectation::none());
+        let mut result_ty = Ty::simple(TypeCtor::Never);
+        for arm in arms.iter() {
+            self.infer_pat(arm.pat, input_ty.clone());
+            let arm_ty = self.infer_narrowed(arm.expr, &[], |this| {
+                this.infer_expr_coerce(arm.expr, expected)
+            });
+            result_ty = match self.coerce_merge_branch(&result_ty, &arm_ty) {
+                Some(ty) => ty,
+                None => {
+                    self.diagnostics.push(InferenceDiagnostic::MismatchedTypes {
+                        id: arm.expr.into(),
+                        expected: result_ty.clone(),
+                        found: arm_ty,
+                    });
+                    result_ty
+                }
+            };
+        }
+        result_ty
+    }
+
     /// Inferences the type of a call expression.
     fn infer_call(
         &mut self,
@@ -543,8 +1046,10 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
             &Expectation::none(),
             &CheckParams {
                 is_unit_struct: false,
+                is_callee: true,
             },
         );
+        let callee_variant = self.variant_resolutions.get(&callee.into()).copied();
 
         match callee_ty {
             ty_app!(TypeCtor::Struct(s)) => {
@@ -552,7 +1057,7 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
                 let struct_data = s.data(self.db);
                 self.diagnostics
                     .push(InferenceDiagnostic::MismatchedStructLit {
-                        id: tgt_expr,
+                        id: tgt_expr.into(),
                         expected: struct_data.kind,
                         found: StructKind::Tuple,
                     });
@@ -564,13 +1069,29 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
 
                 callee_ty
             }
+            ty_app!(TypeCtor::Enum(_)) if callee_variant.is_some() => {
+                // Erroneously found either a unit or record enum variant literal
+                self.diagnostics
+                    .push(InferenceDiagnostic::MismatchedStructLit {
+                        id: tgt_expr.into(),
+                        expected: callee_variant.unwrap().kind(self.db),
+                        found: StructKind::Tuple,
+                    });
+
+                // Still derive subtypes
+                for arg in args.iter() {
+                    self.infer_expr(*arg, &Expectation::none());
+                }
+
+                callee_ty
+            }
             ty_app!(TypeCtor::FnDef(def)) => {
                 // Found either a tuple struct literal or function
                 let sig = callee_ty.callable_sig(self.db).unwrap();
                 let (param_tys, ret_ty) = (sig.params().to_vec(), sig.ret().clone());
                 self.check_call_argument_count(
                     tgt_expr,
-                    def.is_struct(),
+                    !def.is_function(),
                     args.len(),
                     param_tys.len(),
                 );
@@ -580,6 +1101,17 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
 
                 ret_ty
             }
+            ty_app!(TypeCtor::FnPtr { .. }) => {
+                // Found a function pointer or closure
+                let sig = callee_ty.callable_sig(self.db).unwrap();
+                let (param_tys, ret_ty) = (sig.params().to_vec(), sig.ret().clone());
+                self.check_call_argument_count(tgt_expr, false, args.len(), param_tys.len());
+                for (&arg, param_ty) in args.iter().zip(param_tys.iter()) {
+                    self.infer_expr_coerce(arg, &Expectation::has_type(param_ty.clone()));
+                }
+
+                ret_ty
+            }
             _ => {
                 self.diagnostics
                     .push(InferenceDiagnostic::ExpectedFunction {
@@ -591,14 +1123,276 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
         }
     }
 
-    /// Checks whether the specified struct type is a unit struct.
-    fn check_unit_struct_lit(&mut self, tgt_expr: ExprId, expected: Struct) {
-        let struct_data = expected.data(self.db);
-        if struct_data.kind != StructKind::Unit {
+    /// Inferences the type of a method call. Besides the builtin methods of arrays and strings,
+    /// the methods that are defined in `impl` blocks of structs and enums are supported.
+    fn infer_method_call(
+        &mut self,
+        tgt_expr: ExprId,
+        receiver: ExprId,
+        method_name: &Name,
+        args: &[ExprId],
+    ) -> Ty {
+        let receiver_ty = self.infer_expr(receiver, &Expectation::none());
+        let receiver_ty = self.replace_if_possible(&receiver_ty).into_owned();
+        if let ty_app!(TypeCtor::Optional) = receiver_ty {
+            self.diagnostics.push(InferenceDiagnostic::PossiblyNil {
+                id: receiver,
+                found: receiver_ty,
+            });
+            for arg in args.iter() {
+                self.infer_expr(*arg, &Expectation::none());
+            }
+            return Ty::Unknown;
+        }
+
+        // A function of an `impl` block can only be called as a method if it has a `self`
+        // parameter
+        let function = self
+            .resolver
+            .resolve_assoc_function(self.db, &receiver_ty, method_name)
+            .filter(|f| f.data(self.db).has_self_param());
+        if let Some(function) = function {
+            let function_ty = self.instantiate_generics(function.ty(self.db));
+            let sig = function_ty.callable_sig(self.db).unwrap();
+            let (param_tys, ret_ty) = (sig.params()[1..].to_vec(), sig.ret().clone());
+            self.method_resolutions
+                .insert(tgt_expr, MethodResolution::Function(function));
+            if let Some(substs) = function_ty.substs() {
+                self.method_substs.insert(tgt_expr, substs);
+            }
+            self.check_call_argument_count(tgt_expr, false, args.len(), param_tys.len());
+            for (&arg, param_ty) in args.iter().zip(param_tys.iter()) {
+                self.infer_expr_coerce(arg, &Expectation::has_type(param_ty.clone()));
+            }
+            return ret_ty;
+        }
+
+        let element_ty = receiver_ty.as_array().cloned();
+        let is_dyn_array = match receiver_ty {
+            ty_app!(TypeCtor::DynArray) => true,
+            _ => false,
+        };
+        let is_string = match receiver_ty {
+            ty_app!(TypeCtor::String) => true,
+            _ => false,
+        };
+
+        let (method, param_tys, ret_ty) = match element_ty {
+            None if is_string && *method_name == name![len] => (
+                BuiltinMethod::StringLen,
+                vec![],
+                Ty::simple(TypeCtor::Int(IntTy::usize())),
+            ),
+            Some(_) if *method_name == name![len] => (
+                BuiltinMethod::ArrayLen,
+                vec![],
+                Ty::simple(TypeCtor::Int(IntTy::usize())),
+            ),
+            Some(element_ty) if is_dyn_array && *method_name == name![push] => {
+                (BuiltinMethod::ArrayPush, vec![element_ty], Ty::Empty)
+            }
+            Some(element_ty) if is_dyn_array && *method_name == name![pop] => {
+                (BuiltinMethod::ArrayPop, vec![], element_ty)
+            }
+            _ => {
+                if receiver_ty != Ty::Unknown {
+                    self.diagnostics
+                        .push(InferenceDiagnostic::UnresolvedMethod {
+                            id: tgt_expr,
+                            receiver_ty,
+                            name: method_name.clone(),
+                        });
+                }
+
+                // Still derive subtypes
+                for arg in args.iter() {
+                    self.infer_expr(*arg, &Expectation::none());
+                }
+
+                return Ty::Unknown;
+            }
+        };
+
+        self.method_resolutions
+            .insert(tgt_expr, MethodResolution::Builtin(method));
+        self.check_call_argument_count(tgt_expr, false, args.len(), param_tys.len());
+        for (&arg, param_ty) in args.iter().zip(param_tys.iter()) {
+            self.infer_expr_coerce(arg, &Expectation::has_type(param_ty.clone()));
+        }
+
+        ret_ty
+    }
+
+    /// Inferences the type of an array literal. The literal is a growable array `[T]` if that is
+    /// what is expected; otherwise it is a fixed-size array `[T; N]`.
+    fn infer_array_lit(&mut self, exprs: &[ExprId], expected: &Expectation) -> Ty {
+        let expected_ty = self.replace_if_possible(&expected.ty);
+        let is_dyn_array = match &*expected_ty {
+            ty_app!(TypeCtor::DynArray) => true,
+            _ => false,
+        };
+        let mut element_ty = match expected_ty.as_array() {
+            Some(element_ty) => element_ty.clone(),
+            None => self.type_variables.new_type_var(),
+        };
+
+        for expr in exprs.iter() {
+            element_ty = self.infer_expr_coerce(*expr, &Expectation::has_type(element_ty));
+        }
+
+        if is_dyn_array {
+            Ty::dyn_array(element_ty)
+        } else {
+            Ty::array(element_ty, exprs.len() as u32)
+        }
+    }
+
+    /// Infers the type of a tuple literal, e.g. `(1, 2.0)`. The empty tuple `()` has the empty
+    /// type.
+    fn infer_tuple_lit(&mut self, exprs: &[ExprId], expected: &Expectation) -> Ty {
+        if exprs.is_empty() {
+            return Ty::Empty;
+        }
+
+        let expected_ty = self.replace_if_possible(&expected.ty);
+        let expected_element_tys = expected_ty
+            .as_tuple()
+            .filter(|element_tys| element_tys.len() == exprs.len())
+            .cloned();
+
+        let element_tys = exprs
+            .iter()
+            .enumerate()
+            .map(|(idx, expr)| {
+                let expectation = match &expected_element_tys {
+                    Some(element_tys) => Expectation::has_type(element_tys[idx].clone()),
+                    None => Expectation::none(),
+                };
+                self.infer_expr_coerce(*expr, &expectation)
+            })
+            .collect();
+
+        Ty::tuple(element_tys)
+    }
+
+    /// Infers the type of a cast expression, e.g. `a as f64`. Only numeric values and booleans
+    /// can be cast, and only to a numeric type.
+    fn infer_cast(&mut self, tgt_expr: ExprId, expr: ExprId, type_ref: TypeRefId) -> Ty {
+        let cast_ty = self.resolve_type(type_ref);
+        let expr_ty = self.infer_expr(expr, &Expectation::none());
+        let expr_ty = self.replace_if_possible(&expr_ty).into_owned();
+
+        let is_numeric = |ty: &Ty| {
+            matches!(
+                ty,
+                ty_app!(TypeCtor::Int(_))
+                    | ty_app!(TypeCtor::Float(_))
+                    | Ty::Infer(InferTy::IntVar(_))
+                    | Ty::Infer(InferTy::FloatVar(_))
+            )
+        };
+        let is_valid = match (&expr_ty, &cast_ty) {
+            (Ty::Unknown, _) | (_, Ty::Unknown) | (ty_app!(TypeCtor::Never), _) => true,
+            (from, to) if from == to => true,
+            (ty_app!(TypeCtor::Bool), ty_app!(TypeCtor::Int(_))) => true,
+            (from, to) => is_numeric(from) && is_numeric(to),
+        };
+
+        if !is_valid {
+            self.diagnostics.push(InferenceDiagnostic::InvalidCast {
+                id: tgt_expr,
+                from: expr_ty,
+                to: cast_ty.clone(),
+            });
+        }
+
+        cast_ty
+    }
+
+    /// Infers the type of a closure, e.g. `|x: f32| x * k`. The types of parameters and the return
+    /// type that are omitted are taken from the expected function pointer type, if any, or are
+    /// otherwise inferred from the body of the closure.
+    fn infer_lambda(
+        &mut self,
+        tgt_expr: ExprId,
+        args: &[PatId],
+        arg_types: &[Option<TypeRefId>],
+        ret_type: Option<TypeRefId>,
+        body: ExprId,
+        expected: &Expectation,
+    ) -> Ty {
+        let expected_ty = self.replace_if_possible(&expected.ty).into_owned();
+        let expected_sig = match &expected_ty {
+            ty_app!(TypeCtor::FnPtr { num_args }) if *num_args as usize == args.len() => {
+                expected_ty.callable_sig(self.db)
+            }
+            _ => None,
+        };
+
+        self.active_closures.push(ActiveClosure {
+            bindings: FxHashSet::default(),
+            captures: Vec::new(),
+        });
+
+        let mut param_tys = Vec::with_capacity(args.len());
+        for (idx, (arg, arg_type)) in args.iter().zip(arg_types.iter()).enumerate() {
+            let expected_param_ty = expected_sig.as_ref().map(|sig| sig.params()[idx].clone());
+            let param_ty = match (arg_type, expected_param_ty) {
+                (Some(type_ref), _) => self.resolve_type(*type_ref),
+                (None, Some(expected_param_ty)) => expected_param_ty,
+                (None, None) => self.type_variables.new_type_var(),
+            };
+            self.infer_pat(*arg, param_ty.clone());
+            param_tys.push(param_ty);
+        }
+        let ret_ty = match (ret_type, &expected_sig) {
+            (Some(type_ref), _) => self.resolve_type(type_ref),
+            (None, Some(expected_sig)) => expected_sig.ret().clone(),
+            (None, None) => self.type_variables.new_type_var(),
+        };
+
+        // The body of a closure returns from the closure and cannot break out of an enclosing loop
+        let outer_return_ty = mem::replace(&mut self.return_ty, ret_ty.clone());
+        let outer_loop = self.active_loop.take();
+        let ret_ty = self.infer_narrowed(body, &[], |this| {
+            this.infer_expr_coerce(body, &Expectation::has_type(ret_ty))
+        });
+        self.return_ty = outer_return_ty;
+        self.active_loop = outer_loop;
+
+        let closure = self.active_closures.pop().unwrap();
+        self.closure_captures.insert(tgt_expr, closure.captures);
+
+        Ty::fn_ptr(FnSig::from_params_and_return(param_tys, ret_ty))
+    }
+
+    /// Records that the specified local binding is used by the active closures that do not
+    /// introduce the binding themselves.
+    fn capture_binding(&mut self, pat: PatId) {
+        for closure in self.active_closures.iter_mut().rev() {
+            if closure.bindings.contains(&pat) {
+                break;
+            }
+            if !closure.captures.contains(&pat) {
+                closure.captures.push(pat);
+            }
+        }
+    }
+
+    /// Returns true if the specified local binding is captured by the innermost active closure.
+    fn is_captured(&self, pat: PatId) -> bool {
+        self.active_closures
+            .last()
+            .map_or(false, |closure| !closure.bindings.contains(&pat))
+    }
+
+    /// Checks whether the kind of the specified struct or enum variant is a unit kind.
+    fn check_unit_struct_lit(&mut self, tgt_expr: ExprId, expected_kind: StructKind) {
+        if expected_kind != StructKind::Unit {
             self.diagnostics
                 .push(InferenceDiagnostic::MismatchedStructLit {
-                    id: tgt_expr,
-                    expected: struct_data.kind,
+                    id: tgt_expr.into(),
+                    expected: expected_kind,
                     found: StructKind::Unit,
                 });
         }
@@ -616,7 +1410,7 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
         if num_args != num_params {
             self.diagnostics.push(if is_tuple_lit {
                 InferenceDiagnostic::FieldCountMismatch {
-                    id: tgt_expr,
+                    id: tgt_expr.into(),
                     found: num_args,
                     expected: num_params,
                 }
@@ -630,31 +1424,29 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
         }
     }
 
-    // Checks whether the passed fields match the fields of a struct definition.
-    fn check_record_lit(&mut self, tgt_expr: ExprId, expected: Struct, fields: &[RecordLitField]) {
-        let struct_data = expected.data(self.db);
-        if struct_data.kind != StructKind::Record {
+    // Checks whether the passed fields match the fields of a struct or enum variant definition.
+    fn check_record_lit(
+        &mut self,
+        tgt_expr: ExprId,
+        expected_kind: StructKind,
+        expected_fields: &[Name],
+        fields: &[RecordLitField],
+    ) {
+        if expected_kind != StructKind::Record {
             self.diagnostics
                 .push(InferenceDiagnostic::MismatchedStructLit {
-                    id: tgt_expr,
-                    expected: struct_data.kind,
+                    id: tgt_expr.into(),
+                    expected: expected_kind,
                     found: StructKind::Record,
                 });
             return;
         }
 
         let lit_fields: FxHashSet<_> = fields.iter().map(|f| &f.name).collect();
-        let missed_fields: Vec<Name> = struct_data
-            .fields
+        let missed_fields: Vec<Name> = expected_fields
             .iter()
-            .filter_map(|(_f, d)| {
-                let name = d.name.clone();
-                if lit_fields.contains(&name) {
-                    None
-                } else {
-                    Some(name)
-                }
-            })
+            .filter(|name| !lit_fields.contains(name))
+            .cloned()
             .collect();
 
         if !missed_fields.is_empty() {
@@ -688,15 +1480,38 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
             Resolution::LocalBinding(pat) => {
                 let ty = self.type_of_pat.get(pat)?.clone();
                 //let ty = self.resolve_ty_as_possible(&mut vec![], ty);
-                Some(ty)
+                self.capture_binding(pat);
+                // An optional binding that is known not to be `nil` can be used as a reference
+                let ty = self.replace_if_possible(&ty).into_owned();
+                match ty.as_optional() {
+                    Some(inner_ty) if self.narrowed_bindings.contains(&pat) => {
+                        Some(inner_ty.clone())
+                    }
+                    _ => Some(ty),
+                }
+            }
+            Resolution::SelfType(_) | Resolution::GenericParam(_) => {
+                // this should never happen
+                panic!("path resolved to a type in value ns");
             }
             Resolution::Def(def) => {
                 let typable: Option<TypableDef> = def.into();
                 let typable = typable?;
                 let ty = self.db.type_for_def(typable, Namespace::Values);
-                if check_params.is_unit_struct {
+                let ty = match typable {
+                    TypableDef::Function(_) | TypableDef::Struct(_) => {
+                        self.instantiate_generics(ty)
+                    }
+                    _ => ty,
+                };
+                if let TypableDef::EnumVariant(variant) = typable {
+                    self.variant_resolutions.insert(id.into(), variant);
+                    if check_params.is_unit_struct {
+                        self.check_unit_struct_lit(id, variant.kind(self.db));
+                    }
+                } else if check_params.is_unit_struct {
                     if let Some(s) = ty.as_struct() {
-                        self.check_unit_struct_lit(id, s);
+                        self.check_unit_struct_lit(id, s.data(self.db).kind);
                     }
                 }
                 Some(ty)
@@ -716,8 +1531,57 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
             if !was_unknown && resolved == Ty::Unknown {
                 self.report_expr_inference_failure(expr);
             }
+            if let Expr::Array(exprs) = &self.body[expr] {
+                if exprs.is_empty() && resolved.as_array() == Some(&Ty::Unknown) {
+                    self.diagnostics
+                        .push(InferenceDiagnostic::EmptyArrayNeedsType { id: expr });
+                }
+            }
+            if let Expr::Literal(Literal::Nil) = &self.body[expr] {
+                if resolved.as_optional() == Some(&Ty::Unknown) {
+                    self.diagnostics
+                        .push(InferenceDiagnostic::NilNeedsType { id: expr });
+                }
+            }
+            // The type arguments of a generic function or struct must all be known
+            let type_args_unknown = match (&self.body[expr], &resolved) {
+                (Expr::Path(_), ty_app!(TypeCtor::FnDef(_), substs))
+                | (Expr::Path(_), ty_app!(TypeCtor::Struct(_), substs))
+                | (Expr::RecordLit { .. }, ty_app!(TypeCtor::Struct(_), substs)) => {
+                    substs.contains(&Ty::Unknown)
+                }
+                _ => false,
+            };
+            if type_args_unknown {
+                self.diagnostics
+                    .push(InferenceDiagnostic::TypeArgumentsNeeded { id: expr });
+            } else if let (
+                Expr::Path(_),
+                ty_app!(TypeCtor::FnDef(CallableDef::Function(function)), substs),
+            ) = (&self.body[expr], &resolved)
+            {
+                // A function of a trait can only be called for a type that implements the trait
+                if let Some(trait_) = function.trait_def(self.db) {
+                    if function.trait_impl_function(self.db, &substs[0]).is_none() {
+                        self.diagnostics
+                            .push(InferenceDiagnostic::TraitNotImplemented { id: expr, trait_ });
+                    }
+                }
+            }
             *ty = resolved;
         }
+        let mut method_substs = std::mem::take(&mut self.method_substs);
+        for (expr, substs) in method_substs.iter_mut() {
+            let resolved: Vec<Ty> = substs
+                .iter()
+                .map(|ty| self.type_variables.resolve_ty_completely(ty.clone()))
+                .collect();
+            if resolved.contains(&Ty::Unknown) {
+                self.diagnostics
+                    .push(InferenceDiagnostic::TypeArgumentsNeeded { id: *expr });
+            }
+            *substs = resolved.into();
+        }
         let mut pat_types = std::mem::take(&mut self.type_of_pat);
         for (pat, ty) in pat_types.iter_mut() {
             let was_unknown = ty == &mut Ty::Unknown;
@@ -732,10 +1596,13 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
         InferenceResult {
             //            method_resolutions: self.method_resolutions,
             //            field_resolutions: self.field_resolutions,
-            //            variant_resolutions: self.variant_resolutions,
             //            assoc_resolutions: self.assoc_resolutions,
             type_of_expr: expr_types,
             type_of_pat: pat_types,
+            method_resolutions: self.method_resolutions,
+            method_substs,
+            variant_resolutions: self.variant_resolutions,
+            closure_captures: self.closure_captures,
             diagnostics: self.diagnostics,
         }
     }
@@ -763,14 +1630,26 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
                 //let ty = self.resolve_ty_as_possible(&mut vec![], ty);
                 (ty, None)
             }
+            Resolution::SelfType(impl_block) => match impl_block.target_ty(self.db).as_struct() {
+                Some(s) => (s.ty(self.db), Some(s.into())),
+                None => (Ty::Unknown, None),
+            },
+            Resolution::GenericParam(_) => (Ty::Unknown, None),
             Resolution::Def(def) => {
                 if let Some(typable) = def.into() {
                     match typable {
                         TypableDef::Struct(s) => (s.ty(self.db), Some(s.into())),
-                        TypableDef::BuiltinType(_) | TypableDef::Function(_) => (Ty::Unknown, None),
+                        TypableDef::BuiltinType(_)
+                        | TypableDef::BuiltinFunction(_)
+                        | TypableDef::Function(_)
+                        | TypableDef::Enum(_)
+                        | TypableDef::EnumVariant(_)
+                        | TypableDef::Const(_)
+                        | TypableDef::Static(_) => (Ty::Unknown, None),
                     }
                 } else {
-                    unreachable!();
+                    // The path refers to a module
+                    (Ty::Unknown, None)
                 }
             }
         }
@@ -860,7 +1739,7 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
             self.diagnostics.push(InferenceDiagnostic::MismatchedTypes {
                 expected: expected.ty.clone(),
                 found: ty,
-                id: tgt_expr,
+                id: tgt_expr.into(),
             });
             expected.ty
         } else {
@@ -885,10 +1764,24 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
     }
 
     fn infer_loop_block(&mut self, body: ExprId, lp: ActiveLoop) -> ActiveLoop {
+        self.infer_loop_block_narrowed(body, lp, &[])
+    }
+
+    /// Infers the body of a loop, while the optional local bindings in `narrowed` are known not to
+    /// be `nil` at the start of each iteration.
+    fn infer_loop_block_narrowed(
+        &mut self,
+        body: ExprId,
+        lp: ActiveLoop,
+        narrowed: &[PatId],
+    ) -> ActiveLoop {
         let top_level_loop = std::mem::replace(&mut self.active_loop, Some(lp));
 
         // Infer the body of the loop
-        self.infer_expr_coerce(body, &Expectation::has_type(Ty::Empty));
+        self.forget_assigned_bindings(body);
+        self.infer_narrowed(body, narrowed, |this| {
+            this.infer_expr_coerce(body, &Expectation::has_type(Ty::Empty))
+        });
 
         // Take the result of the loop information and replace with top level loop
         std::mem::replace(&mut self.active_loop, top_level_loop).unwrap()
@@ -901,31 +1794,81 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
         body: ExprId,
         _expected: &Expectation,
     ) -> Ty {
+        // The condition is evaluated before each iteration of the loop
+        self.forget_assigned_bindings(body);
         self.infer_expr(
             condition,
             &Expectation::has_type(Ty::simple(TypeCtor::Bool)),
         );
 
-        self.infer_loop_block(body, ActiveLoop::While);
+        let checks = self.nil_checks(condition);
+        self.infer_loop_block_narrowed(body, ActiveLoop::While, &checks.when_true);
         Ty::Empty
     }
 
+    fn infer_for_expr(
+        &mut self,
+        _tgt_expr: ExprId,
+        pat: PatId,
+        iterable: ExprId,
+        body: ExprId,
+        _expected: &Expectation,
+    ) -> Ty {
+        // Only ranges can be iterated over. The range itself is not a value, so its type is not
+        // recorded; the pattern receives the type of the range's bounds instead.
+        let exprs = Arc::clone(&self.body); // avoid borrow checker problem
+        let item_ty = match &exprs[iterable] {
+            Expr::Range { start, end, .. } => self.infer_range_bounds(iterable, *start, *end),
+            _ => {
+                let found = self.infer_expr(iterable, &Expectation::none());
+                self.diagnostics
+                    .push(InferenceDiagnostic::InvalidForIterable {
+                        id: iterable,
+                        found,
+                    });
+                Ty::Unknown
+            }
+        };
+
+        self.infer_pat(pat, item_ty);
+        self.infer_loop_block(body, ActiveLoop::For);
+        Ty::Empty
+    }
+
+    /// Infers the type of the bounds of a range. Both bounds must be of the same integer type,
+    /// which is returned.
+    fn infer_range_bounds(&mut self, tgt_expr: ExprId, start: ExprId, end: ExprId) -> Ty {
+        let start_ty = self.infer_expr(start, &Expectation::none());
+        let ty = self.infer_expr_coerce(end, &Expectation::has_type(start_ty));
+        match ty {
+            ty_app!(TypeCtor::Int(_)) | Ty::Infer(InferTy::IntVar(..)) | Ty::Unknown => ty,
+            _ => {
+                self.diagnostics
+                    .push(InferenceDiagnostic::InvalidForIterable {
+                        id: tgt_expr,
+                        found: ty,
+                    });
+                Ty::Unknown
+            }
+        }
+    }
+
     pub fn report_pat_inference_failure(&mut self, _pat: PatId) {
         //        self.diagnostics.push(InferenceDiagnostic::PatInferenceFailed {
         //            pat
         //        });
-        // Currently this should never happen because we can only infer integer and floating-point
-        // types which always have a fallback value.
-        panic!("pattern failed inferencing");
+        // Integer and floating-point types always have a fallback value. General type variables
+        // are only introduced for the elements of empty array literals and for `nil` literals,
+        // which are already reported through `EmptyArrayNeedsType` and `NilNeedsType`.
     }
 
     pub fn report_expr_inference_failure(&mut self, _expr: ExprId) {
         //        self.diagnostics.push(InferenceDiagnostic::ExprInferenceFailed {
         //            expr
         //        });
-        // Currently this should never happen because we can only infer integer and floating-point
-        // types which always have a fallback value.
-        panic!("expression failed inferencing");
+        // Integer and floating-point types always have a fallback value. General type variables
+        // are only introduced for the elements of empty array literals and for `nil` literals,
+        // which are already reported through `EmptyArrayNeedsType` and `NilNeedsType`.
     }
 }
 
@@ -960,12 +1903,16 @@ impl Expectation {
 struct CheckParams {
     /// Checks whether a `Expr::Path` of type struct, is actually a unit struct
     is_unit_struct: bool,
+    /// Whether the expression is the callee of a call expression. A function that is not called
+    /// directly is used as a function pointer.
+    is_callee: bool,
 }
 
 impl Default for CheckParams {
     fn default() -> Self {
         Self {
             is_unit_struct: true,
+            is_callee: false,
         }
     }
 }
@@ -990,19 +1937,25 @@ impl From<PatId> for ExprOrPatId {
 
 mod diagnostics {
     use crate::diagnostics::{
-        AccessUnknownField, BreakOutsideLoop, BreakWithValueOutsideLoop, CannotApplyBinaryOp,
-        CannotApplyUnaryOp, ExpectedFunction, FieldCountMismatch, IncompatibleBranch, InvalidLHS,
-        LiteralOutOfRange, MismatchedStructLit, MismatchedType, MissingElseBranch, MissingFields,
-        NoFields, NoSuchField, ParameterCountMismatch, ReturnMissingExpression,
+        AccessUnknownField, BreakOutsideLoop, BreakWithValueOutsideLoop, BuiltinFunctionNotCalled,
+        CannotApplyBinaryOp, CannotApplyUnaryOp, CannotIndex, EmptyArrayNeedsType,
+        ExpectedFunction, FieldCountMismatch, IncompatibleBranch, InvalidArrayLength, InvalidCast,
+        InvalidForIterable, InvalidLHS, InvalidOptionalType, LiteralOutOfRange,
+        MismatchedStructLit, MismatchedType, MissingElseBranch, MissingFields, NilNeedsType,
+        NoFields, NoSuchField, ParameterCountMismatch, PossiblyNil, RangeOutsideForLoop,
+        ReturnMissingExpression, TraitNotImplemented, TypeArgumentsNeeded, UnresolvedMethod,
+        WrongNumberOfTypeArguments,
     };
     use crate::{
         adt::StructKind,
-        code_model::src::HasSource,
+        code_model::DefWithBody,
         diagnostics::{DiagnosticSink, UnresolvedType, UnresolvedValue},
+        expr::BodySourceMap,
         ty::infer::ExprOrPatId,
         type_ref::TypeRefId,
-        ExprId, Function, HirDatabase, IntTy, Name, Ty,
+        ExprId, HirDatabase, IntTy, Name, Trait, Ty,
     };
+    use mun_syntax::SyntaxNodePtr;
 
     #[derive(Debug, PartialEq, Eq, Clone)]
     pub(crate) enum InferenceDiagnostic {
@@ -1012,6 +1965,21 @@ mod diagnostics {
         UnresolvedType {
             id: TypeRefId,
         },
+        InvalidArrayLength {
+            id: TypeRefId,
+        },
+        InvalidOptionalType {
+            id: TypeRefId,
+        },
+        PossiblyNil {
+            id: ExprId,
+            found: Ty,
+        },
+        WrongNumberOfTypeArguments {
+            id: TypeRefId,
+            expected: usize,
+            found: usize,
+        },
         ExpectedFunction {
             id: ExprId,
             found: Ty,
@@ -1022,7 +1990,7 @@ mod diagnostics {
             expected: usize,
         },
         MismatchedTypes {
-            id: ExprId,
+            id: ExprOrPatId,
             expected: Ty,
             found: Ty,
         },
@@ -1057,13 +2025,51 @@ mod diagnostics {
         BreakWithValueOutsideLoop {
             id: ExprId,
         },
-        AccessUnknownField {
+        InvalidForIterable {
+            id: ExprId,
+            found: Ty,
+        },
+        RangeOutsideForLoop {
+            id: ExprId,
+        },
+        CannotIndex {
+            id: ExprId,
+            found: Ty,
+        },
+        InvalidCast {
+            id: ExprId,
+            from: Ty,
+            to: Ty,
+        },
+        UnresolvedMethod {
             id: ExprId,
             receiver_ty: Ty,
             name: Name,
         },
-        FieldCountMismatch {
+        EmptyArrayNeedsType {
+            id: ExprId,
+        },
+        NilNeedsType {
             id: ExprId,
+        },
+        BuiltinFunctionNotCalled {
+            id: ExprId,
+            name: Name,
+        },
+        TypeArgumentsNeeded {
+            id: ExprId,
+        },
+        TraitNotImplemented {
+            id: ExprId,
+            trait_: Trait,
+        },
+        AccessUnknownField {
+            id: ExprOrPatId,
+            receiver_ty: Ty,
+            name: Name,
+        },
+        FieldCountMismatch {
+            id: ExprOrPatId,
             found: usize,
             expected: usize,
         },
@@ -1072,7 +2078,7 @@ mod diagnostics {
             names: Vec<Name>,
         },
         MismatchedStructLit {
-            id: ExprId,
+            id: ExprOrPatId,
             expected: StructKind,
             found: StructKind,
         },
@@ -1094,30 +2100,41 @@ mod diagnostics {
         pub(crate) fn add_to(
             &self,
             db: &impl HirDatabase,
-            owner: Function,
+            owner: DefWithBody,
             sink: &mut DiagnosticSink,
         ) {
-            let file = owner.source(db).file_id;
+            let file = owner.module(db).file_id();
             let body = owner.body_source_map(db);
             match self {
                 InferenceDiagnostic::UnresolvedValue { id } => {
-                    let expr = match id {
-                        ExprOrPatId::ExprId(id) => body.expr_syntax(*id).map(|ptr| {
-                            ptr.value
-                                .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())
-                        }),
-                        ExprOrPatId::PatId(id) => {
-                            body.pat_syntax(*id).map(|ptr| ptr.value.syntax_node_ptr())
-                        }
-                    }
-                    .unwrap();
-
+                    let expr = expr_or_pat_syntax(&body, *id);
                     sink.push(UnresolvedValue { file, expr });
                 }
                 InferenceDiagnostic::UnresolvedType { id } => {
                     let type_ref = body.type_ref_syntax(*id).expect("If this is not found, it must be a type ref generated by the library which should never be unresolved.");
                     sink.push(UnresolvedType { file, type_ref });
                 }
+                InferenceDiagnostic::InvalidArrayLength { id } => {
+                    let type_ref = body.type_ref_syntax(*id).unwrap();
+                    sink.push(InvalidArrayLength { file, type_ref });
+                }
+                InferenceDiagnostic::InvalidOptionalType { id } => {
+                    let type_ref = body.type_ref_syntax(*id).unwrap();
+                    sink.push(InvalidOptionalType { file, type_ref });
+                }
+                InferenceDiagnostic::WrongNumberOfTypeArguments {
+                    id,
+                    expected,
+                    found,
+                } => {
+                    let type_ref = body.type_ref_syntax(*id).unwrap();
+                    sink.push(WrongNumberOfTypeArguments {
+                        file,
+                        type_ref,
+                        expected: *expected,
+                        found: *found,
+                    });
+                }
                 InferenceDiagnostic::ParameterCountMismatch {
                     id,
                     expected,
@@ -1152,11 +2169,7 @@ mod diagnostics {
                     found,
                     expected,
                 } => {
-                    let expr = body
-                        .expr_syntax(*id)
-                        .unwrap()
-                        .value
-                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    let expr = expr_or_pat_syntax(&body, *id);
                     sink.push(MismatchedType {
                         file,
                         expr,
@@ -1268,7 +2281,64 @@ mod diagnostics {
                         break_expr: id,
                     });
                 }
-                InferenceDiagnostic::AccessUnknownField {
+                InferenceDiagnostic::InvalidForIterable { id, found } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(InvalidForIterable {
+                        file,
+                        expr,
+                        found: found.clone(),
+                    });
+                }
+                InferenceDiagnostic::RangeOutsideForLoop { id } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(RangeOutsideForLoop { file, expr });
+                }
+                InferenceDiagnostic::CannotIndex { id, found } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(CannotIndex {
+                        file,
+                        expr,
+                        found: found.clone(),
+                    });
+                }
+                InferenceDiagnostic::PossiblyNil { id, found } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(PossiblyNil {
+                        file,
+                        expr,
+                        found: found.clone(),
+                    });
+                }
+                InferenceDiagnostic::InvalidCast { id, from, to } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(InvalidCast {
+                        file,
+                        expr,
+                        from: from.clone(),
+                        to: to.clone(),
+                    });
+                }
+                InferenceDiagnostic::UnresolvedMethod {
                     id,
                     receiver_ty,
                     name,
@@ -1278,6 +2348,67 @@ mod diagnostics {
                         .unwrap()
                         .value
                         .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(UnresolvedMethod {
+                        file,
+                        expr,
+                        receiver_ty: receiver_ty.clone(),
+                        name: name.clone(),
+                    });
+                }
+                InferenceDiagnostic::EmptyArrayNeedsType { id } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(EmptyArrayNeedsType { file, expr });
+                }
+                InferenceDiagnostic::NilNeedsType { id } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(NilNeedsType { file, expr });
+                }
+                InferenceDiagnostic::BuiltinFunctionNotCalled { id, name } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(BuiltinFunctionNotCalled {
+                        file,
+                        expr,
+                        name: name.clone(),
+                    });
+                }
+                InferenceDiagnostic::TypeArgumentsNeeded { id } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(TypeArgumentsNeeded { file, expr });
+                }
+                InferenceDiagnostic::TraitNotImplemented { id, trait_ } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(TraitNotImplemented {
+                        file,
+                        expr,
+                        trait_name: trait_.name(db).to_string(),
+                    });
+                }
+                InferenceDiagnostic::AccessUnknownField {
+                    id,
+                    receiver_ty,
+                    name,
+                } => {
+                    let expr = expr_or_pat_syntax(&body, *id);
                     sink.push(AccessUnknownField {
                         file,
                         expr,
@@ -1290,11 +2421,7 @@ mod diagnostics {
                     expected,
                     found,
                 } => {
-                    let expr = body
-                        .expr_syntax(*id)
-                        .unwrap()
-                        .value
-                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    let expr = expr_or_pat_syntax(&body, *id);
                     sink.push(FieldCountMismatch {
                         file,
                         expr,
@@ -1320,11 +2447,7 @@ mod diagnostics {
                     expected,
                     found,
                 } => {
-                    let expr = body
-                        .expr_syntax(*id)
-                        .unwrap()
-                        .value
-                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    let expr = expr_or_pat_syntax(&body, *id);
                     sink.push(MismatchedStructLit {
                         file,
                         expr,
@@ -1367,4 +2490,21 @@ mod diagnostics {
             }
         }
     }
+
+    /// Returns the syntax node of either an expression or a pattern.
+    fn expr_or_pat_syntax(body: &BodySourceMap, id: ExprOrPatId) -> SyntaxNodePtr {
+        match id {
+            ExprOrPatId::ExprId(id) => body
+                .expr_syntax(id)
+                .map(|ptr| {
+                    ptr.value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())
+                })
+                .unwrap(),
+            ExprOrPatId::PatId(id) => body
+                .pat_syntax(id)
+                .map(|ptr| ptr.value.syntax_node_ptr())
+                .unwrap(),
+        }
+    }
 }
@@ -24,6 +24,10 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
     fn coerce_inner(&mut self, from_ty: Ty, to_ty: &Ty) -> bool {
         match (&from_ty, to_ty) {
             (ty_app!(TypeCtor::Never), ..) => return true,
+            // A reference can implicitly be used where an optional reference is expected
+            (ty_app!(TypeCtor::Struct(_)), ty_app!(TypeCtor::Optional, to_params)) => {
+                return self.unify(&from_ty, &to_params[0]);
+            }
             _ => {
                 if self.type_variables.unify_inner_trivial(&from_ty, &to_ty) {
                     return true;
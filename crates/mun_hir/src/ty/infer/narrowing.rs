@@ -0,0 +1,144 @@
+use crate::{
+    expr::{self, BinaryOp, CmpOp, Literal, LogicOp, UnaryOp},
+    ty::infer::InferenceResultBuilder,
+    Expr, ExprId, HirDatabase, PatId, Resolution,
+};
+use std::sync::Arc;
+
+/// The local bindings that are known not to be `nil` when a condition evaluates to `true` or to
+/// `false`, e.g. for `a != nil && b != nil` both `a` and `b` are known not to be `nil` when the
+/// condition evaluates to `true`.
+#[derive(Default, Debug)]
+pub(super) struct NilChecks {
+    pub when_true: Vec<PatId>,
+    pub when_false: Vec<PatId>,
+}
+
+impl NilChecks {
+    fn negate(self) -> Self {
+        NilChecks {
+            when_true: self.when_false,
+            when_false: self.when_true,
+        }
+    }
+}
+
+impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
+    /// Infers the expression `scope` using `f`, while the optional local bindings in `narrowed` are
+    /// known not to be `nil`. Narrowings introduced within `scope` do not outlive it, and the local
+    /// bindings that are assigned to within `scope` are no longer narrowed after it.
+    pub(super) fn infer_narrowed<T>(
+        &mut self,
+        scope: ExprId,
+        narrowed: &[PatId],
+        f: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let outer = self.narrowed_bindings.clone();
+        self.narrowed_bindings.extend(narrowed.iter().copied());
+        let result = f(self);
+        self.narrowed_bindings = outer;
+        self.forget_assigned_bindings(scope);
+        result
+    }
+
+    /// Stops narrowing all local bindings that are assigned to within `expr`. Used before entering
+    /// a loop, as the assignments may be executed before the next iteration of the loop.
+    pub(super) fn forget_assigned_bindings(&mut self, expr: ExprId) {
+        if self.narrowed_bindings.is_empty() {
+            return;
+        }
+        let mut assigned = Vec::new();
+        self.collect_assigned_bindings(expr, &mut assigned);
+        for pat in assigned {
+            self.narrowed_bindings.remove(&pat);
+        }
+    }
+
+    fn collect_assigned_bindings(&self, expr: ExprId, assigned: &mut Vec<PatId>) {
+        let body = Arc::clone(&self.body); // avoid borrow checker problem
+        if let Expr::BinaryOp {
+            lhs,
+            op: Some(BinaryOp::Assignment { .. }),
+            ..
+        } = &body[expr]
+        {
+            if let Some(pat) = self.local_binding(*lhs) {
+                assigned.push(pat);
+            }
+        }
+        body[expr].walk_child_exprs(|child| self.collect_assigned_bindings(child, assigned));
+    }
+
+    /// Returns the local bindings that are compared to `nil` by the specified boolean condition.
+    pub(super) fn nil_checks(&self, condition: ExprId) -> NilChecks {
+        let body = Arc::clone(&self.body); // avoid borrow checker problem
+        match &body[condition] {
+            Expr::BinaryOp {
+                lhs,
+                rhs,
+                op: Some(BinaryOp::CmpOp(CmpOp::Eq { negated })),
+            } => {
+                let pat = match (&body[*lhs], &body[*rhs]) {
+                    (_, Expr::Literal(Literal::Nil)) => self.local_binding(*lhs),
+                    (Expr::Literal(Literal::Nil), _) => self.local_binding(*rhs),
+                    _ => None,
+                };
+                let checks = NilChecks {
+                    when_true: pat.into_iter().collect(),
+                    when_false: Vec::new(),
+                };
+                if *negated {
+                    checks
+                } else {
+                    checks.negate()
+                }
+            }
+            Expr::BinaryOp {
+                lhs,
+                rhs,
+                op: Some(BinaryOp::LogicOp(LogicOp::And)),
+            } => {
+                let mut when_true = self.nil_checks(*lhs).when_true;
+                when_true.extend(self.nil_checks(*rhs).when_true);
+                NilChecks {
+                    when_true,
+                    when_false: Vec::new(),
+                }
+            }
+            Expr::BinaryOp {
+                lhs,
+                rhs,
+                op: Some(BinaryOp::LogicOp(LogicOp::Or)),
+            } => {
+                let mut when_false = self.nil_checks(*lhs).when_false;
+                when_false.extend(self.nil_checks(*rhs).when_false);
+                NilChecks {
+                    when_true: Vec::new(),
+                    when_false,
+                }
+            }
+            Expr::UnaryOp {
+                expr,
+                op: UnaryOp::Not,
+            } => self.nil_checks(*expr).negate(),
+            _ => NilChecks::default(),
+        }
+    }
+
+    /// Returns the local binding that is referred to by the specified expression, if the
+    /// expression is a path to a local binding.
+    pub(super) fn local_binding(&self, expr: ExprId) -> Option<PatId> {
+        let path = match &self.body[expr] {
+            Expr::Path(path) => path,
+            _ => return None,
+        };
+        let resolver = expr::resolver_for_expr(self.body.clone(), self.db, expr);
+        match resolver
+            .resolve_path_without_assoc_items(self.db, path)
+            .take_values()
+        {
+            Some(Resolution::LocalBinding(pat)) => Some(pat),
+            _ => None,
+        }
+    }
+}
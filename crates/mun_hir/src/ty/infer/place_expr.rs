@@ -1,5 +1,6 @@
 use crate::{
-    ty::infer::InferenceResultBuilder, Expr, ExprId, HirDatabase, Path, Resolution, Resolver,
+    ty::infer::InferenceResultBuilder, Expr, ExprId, HirDatabase, ModuleDef, Path, Resolution,
+    Resolver,
 };
 use std::sync::Arc;
 
@@ -10,7 +11,7 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
         let body = Arc::clone(&self.body); // avoid borrow checker problem
         match &body[expr] {
             Expr::Path(p) => self.check_place_path(resolver, p),
-            Expr::Field { .. } => true,
+            Expr::Field { .. } | Expr::Index { .. } => true,
             _ => false,
         }
     }
@@ -26,8 +27,11 @@ impl<'a, D: HirDatabase> InferenceResultBuilder<'a, D> {
         };
 
         match resolution {
-            Resolution::LocalBinding(_) => true,
-            Resolution::Def(_) => false,
+            // Closures capture bindings by value, so a captured binding cannot be assigned to
+            Resolution::LocalBinding(pat) => !self.is_captured(pat),
+            // Only mutable global variables can be assigned to
+            Resolution::Def(ModuleDef::Static(s)) => s.is_mut(self.db),
+            Resolution::Def(_) | Resolution::SelfType(_) | Resolution::GenericParam(_) => false,
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::{ty::infer::InferTy, ty_app, Ty, TypeCtor};
+use crate::{ty::infer::InferTy, ty::Substs, ty_app, Ty, TypeCtor};
 use ena::unify::{InPlaceUnificationTable, NoError, UnifyKey, UnifyValue};
 use std::{borrow::Cow, fmt};
 
@@ -128,7 +128,21 @@ impl TypeVariableTable {
         let a = self.replace_if_possible(a);
         let b = self.replace_if_possible(b);
 
-        self.unify_inner_trivial(&a, &b)
+        match (&*a, &*b) {
+            // Types with the same constructor unify if all their type parameters unify
+            (Ty::Apply(a_ty), Ty::Apply(b_ty)) if a_ty.ctor == b_ty.ctor => {
+                self.unify_substs(&a_ty.parameters, &b_ty.parameters)
+            }
+            _ => self.unify_inner_trivial(&a, &b),
+        }
+    }
+
+    fn unify_substs(&mut self, substs1: &Substs, substs2: &Substs) -> bool {
+        substs1.len() == substs2.len()
+            && substs1
+                .iter()
+                .zip(substs2.iter())
+                .all(|(t1, t2)| self.unify_inner(t1, t2))
     }
 
     /// Handles unificiation of trivial cases.
@@ -1,13 +1,17 @@
 pub(crate) use self::diagnostics::LowerDiagnostic;
 use crate::adt::StructKind;
 use crate::arena::map::ArenaMap;
+use crate::builtin_function::BuiltinFunction;
 use crate::builtin_type::BuiltinType;
 use crate::diagnostics::DiagnosticSink;
 use crate::name_resolution::Namespace;
 use crate::resolve::{Resolution, Resolver};
-use crate::ty::{FnSig, Ty, TypeCtor};
+use crate::ty::{ApplicationTy, FnSig, Substs, Ty, TypeCtor};
 use crate::type_ref::{TypeRef, TypeRefId, TypeRefMap, TypeRefSourceMap};
-use crate::{FileId, Function, HirDatabase, ModuleDef, Path, Struct};
+use crate::{
+    Const, Enum, EnumVariant, FileId, Function, HirDatabase, ImplBlock, ModuleDef, Name, Path,
+    Static, Struct, StructMemoryKind,
+};
 use std::ops::Index;
 use std::sync::Arc;
 
@@ -66,10 +70,108 @@ impl Ty {
         type_ref: TypeRefId,
     ) -> Ty {
         let res = match &type_ref_map[type_ref] {
-            TypeRef::Path(path) => Ty::from_hir_path(db, resolver, path),
+            TypeRef::Path(path, type_args) => {
+                let type_args = type_args
+                    .iter()
+                    .map(|type_arg| {
+                        Ty::from_hir_with_diagnostics(
+                            db,
+                            resolver,
+                            type_ref_map,
+                            diagnostics,
+                            *type_arg,
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                Ty::from_hir_path(db, resolver, path, type_args)
+                    .map(|ty| ty.apply_type_args(type_ref, diagnostics))
+            }
             TypeRef::Error => Some(Ty::Unknown),
             TypeRef::Empty => Some(Ty::Empty),
             TypeRef::Never => Some(Ty::simple(TypeCtor::Never)),
+            TypeRef::Array(element_type, length) => {
+                let element_ty = Ty::from_hir_with_diagnostics(
+                    db,
+                    resolver,
+                    type_ref_map,
+                    diagnostics,
+                    *element_type,
+                );
+                match length {
+                    Some(length) => Some(Ty::array(element_ty, *length)),
+                    None => {
+                        diagnostics.push(LowerDiagnostic::InvalidArrayLength { id: type_ref });
+                        Some(Ty::Unknown)
+                    }
+                }
+            }
+            TypeRef::DynArray(element_type) => {
+                let element_ty = Ty::from_hir_with_diagnostics(
+                    db,
+                    resolver,
+                    type_ref_map,
+                    diagnostics,
+                    *element_type,
+                );
+                Some(Ty::dyn_array(element_ty))
+            }
+            TypeRef::Optional(inner_type) => {
+                let inner_ty = Ty::from_hir_with_diagnostics(
+                    db,
+                    resolver,
+                    type_ref_map,
+                    diagnostics,
+                    *inner_type,
+                );
+                // Only references to garbage collected structs can be `nil`
+                match inner_ty.as_struct() {
+                    _ if inner_ty == Ty::Unknown => Some(Ty::Unknown),
+                    Some(s) if s.data(db).memory_kind == StructMemoryKind::GC => {
+                        Some(Ty::optional(inner_ty))
+                    }
+                    _ => {
+                        diagnostics.push(LowerDiagnostic::InvalidOptionalType { id: type_ref });
+                        Some(Ty::Unknown)
+                    }
+                }
+            }
+            TypeRef::Tuple(element_types) => {
+                let element_tys = element_types
+                    .iter()
+                    .map(|element_type| {
+                        Ty::from_hir_with_diagnostics(
+                            db,
+                            resolver,
+                            type_ref_map,
+                            diagnostics,
+                            *element_type,
+                        )
+                    })
+                    .collect();
+                Some(Ty::tuple(element_tys))
+            }
+            TypeRef::Fn(param_types, ret_type) => {
+                let param_tys = param_types
+                    .iter()
+                    .map(|param_type| {
+                        Ty::from_hir_with_diagnostics(
+                            db,
+                            resolver,
+                            type_ref_map,
+                            diagnostics,
+                            *param_type,
+                        )
+                    })
+                    .collect();
+                let ret_ty = Ty::from_hir_with_diagnostics(
+                    db,
+                    resolver,
+                    type_ref_map,
+                    diagnostics,
+                    *ret_type,
+                );
+                Some(Ty::fn_ptr(FnSig::from_params_and_return(param_tys, ret_ty)))
+            }
         };
         if let Some(ty) = res {
             ty
@@ -79,11 +181,14 @@ impl Ty {
         }
     }
 
-    pub(crate) fn from_hir_path(
+    /// Resolves the type that is referred to by `path`. The type arguments are only applied by
+    /// `TypeArgs::apply_type_args`, which also checks their number.
+    fn from_hir_path(
         db: &impl HirDatabase,
         resolver: &Resolver,
         path: &Path,
-    ) -> Option<Self> {
+        type_args: Vec<Ty>,
+    ) -> Option<TypeArgs> {
         let resolution = resolver
             .resolve_path_without_assoc_items(db, path)
             .take_types();
@@ -94,6 +199,17 @@ impl Ty {
                 // this should never happen
                 panic!("path resolved to local binding in type ns");
             }
+            Some(Resolution::SelfType(impl_block)) => {
+                return Some(TypeArgs::non_generic(impl_block.target_ty(db), type_args))
+            }
+            Some(Resolution::GenericParam(idx)) => {
+                let name = resolver
+                    .generic_params()
+                    .and_then(|params| params.params.get(idx as usize))
+                    .map(|param| param.name.clone())
+                    .unwrap_or_else(Name::missing);
+                return Some(TypeArgs::non_generic(Ty::Param { idx, name }, type_args));
+            }
             None => return None,
         };
 
@@ -103,7 +219,55 @@ impl Ty {
         };
 
         let ty = db.type_for_def(typable, Namespace::Types);
-        Some(ty)
+        let expected = match typable {
+            TypableDef::Struct(s) => s.generic_params(db).len(),
+            _ => 0,
+        };
+        Some(TypeArgs {
+            ty,
+            type_args,
+            expected,
+        })
+    }
+}
+
+/// A resolved type together with the type arguments that were specified for it, e.g. `Pair` and
+/// `f32, i64` for `Pair<f32, i64>`.
+struct TypeArgs {
+    ty: Ty,
+    type_args: Vec<Ty>,
+    expected: usize,
+}
+
+impl TypeArgs {
+    fn non_generic(ty: Ty, type_args: Vec<Ty>) -> Self {
+        TypeArgs {
+            ty,
+            type_args,
+            expected: 0,
+        }
+    }
+
+    /// Substitutes the type parameters of the type by its type arguments. A missing type argument
+    /// is replaced by `Ty::Unknown`.
+    fn apply_type_args(self, id: TypeRefId, diagnostics: &mut Vec<LowerDiagnostic>) -> Ty {
+        let TypeArgs {
+            ty,
+            mut type_args,
+            expected,
+        } = self;
+        if type_args.len() != expected {
+            diagnostics.push(LowerDiagnostic::WrongNumberOfTypeArguments {
+                id,
+                expected,
+                found: type_args.len(),
+            });
+        }
+        if expected == 0 {
+            return ty;
+        }
+        type_args.resize(expected, Ty::Unknown);
+        ty.subst(&type_args.into())
     }
 }
 
@@ -116,7 +280,11 @@ pub fn types_from_hir(
     for (id, _) in type_ref_map.iter() {
         let LowerResult { ty, diagnostics } = Ty::from_hir(db, resolver, type_ref_map, id);
         for diagnostic in diagnostics {
-            result.diagnostics.push(diagnostic);
+            // Nested type references (e.g. the element type of an array) are also lowered by
+            // themselves, so only report each diagnostic once.
+            if !result.diagnostics.contains(&diagnostic) {
+                result.diagnostics.push(diagnostic);
+            }
         }
         // TODO: Add detection of cyclic types
         result.type_ref_to_type.insert(id, ty);
@@ -129,11 +297,26 @@ pub fn lower_struct_query(db: &impl HirDatabase, s: Struct) -> Arc<LowerBatchRes
     types_from_hir(db, &s.resolver(db), data.type_ref_map())
 }
 
+pub fn lower_enum_query(db: &impl HirDatabase, e: Enum) -> Arc<LowerBatchResult> {
+    let data = e.data(db);
+    types_from_hir(db, &e.resolver(db), data.type_ref_map())
+}
+
+pub fn lower_impl_query(db: &impl HirDatabase, i: ImplBlock) -> Arc<LowerBatchResult> {
+    let data = i.data(db);
+    types_from_hir(db, &i.resolver(db), data.type_ref_map())
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum TypableDef {
     Function(Function),
+    BuiltinFunction(BuiltinFunction),
     BuiltinType(BuiltinType),
     Struct(Struct),
+    Enum(Enum),
+    EnumVariant(EnumVariant),
+    Const(Const),
+    Static(Static),
 }
 
 impl From<Function> for TypableDef {
@@ -142,6 +325,12 @@ impl From<Function> for TypableDef {
     }
 }
 
+impl From<BuiltinFunction> for TypableDef {
+    fn from(f: BuiltinFunction) -> Self {
+        TypableDef::BuiltinFunction(f)
+    }
+}
+
 impl From<BuiltinType> for TypableDef {
     fn from(f: BuiltinType) -> Self {
         TypableDef::BuiltinType(f)
@@ -154,12 +343,42 @@ impl From<Struct> for TypableDef {
     }
 }
 
+impl From<Enum> for TypableDef {
+    fn from(f: Enum) -> Self {
+        TypableDef::Enum(f)
+    }
+}
+
+impl From<EnumVariant> for TypableDef {
+    fn from(f: EnumVariant) -> Self {
+        TypableDef::EnumVariant(f)
+    }
+}
+
+impl From<Const> for TypableDef {
+    fn from(f: Const) -> Self {
+        TypableDef::Const(f)
+    }
+}
+
+impl From<Static> for TypableDef {
+    fn from(f: Static) -> Self {
+        TypableDef::Static(f)
+    }
+}
+
 impl From<ModuleDef> for Option<TypableDef> {
     fn from(d: ModuleDef) -> Self {
         match d {
             ModuleDef::Function(f) => Some(TypableDef::Function(f)),
+            ModuleDef::BuiltinFunction(f) => Some(TypableDef::BuiltinFunction(f)),
             ModuleDef::BuiltinType(t) => Some(TypableDef::BuiltinType(t)),
             ModuleDef::Struct(t) => Some(TypableDef::Struct(t)),
+            ModuleDef::Enum(t) => Some(TypableDef::Enum(t)),
+            ModuleDef::EnumVariant(t) => Some(TypableDef::EnumVariant(t)),
+            ModuleDef::Const(t) => Some(TypableDef::Const(t)),
+            ModuleDef::Static(t) => Some(TypableDef::Static(t)),
+            ModuleDef::Module(_) | ModuleDef::Trait(_) => None,
         }
     }
 }
@@ -167,14 +386,16 @@ impl From<ModuleDef> for Option<TypableDef> {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum CallableDef {
     Function(Function),
+    BuiltinFunction(BuiltinFunction),
     Struct(Struct),
+    EnumVariant(EnumVariant),
 }
-impl_froms!(CallableDef: Function, Struct);
+impl_froms!(CallableDef: Function, BuiltinFunction, Struct, EnumVariant);
 
 impl CallableDef {
     pub fn is_function(self) -> bool {
         match self {
-            CallableDef::Function(_) => true,
+            CallableDef::Function(_) | CallableDef::BuiltinFunction(_) => true,
             _ => false,
         }
     }
@@ -185,6 +406,13 @@ impl CallableDef {
             _ => false,
         }
     }
+
+    pub fn is_enum_variant(self) -> bool {
+        match self {
+            CallableDef::EnumVariant(_) => true,
+            _ => false,
+        }
+    }
 }
 
 /// Build the declared type of an item. This depends on the namespace; e.g. for
@@ -194,35 +422,75 @@ impl CallableDef {
 pub(crate) fn type_for_def(db: &impl HirDatabase, def: TypableDef, ns: Namespace) -> Ty {
     match (def, ns) {
         (TypableDef::Function(f), Namespace::Values) => type_for_fn(db, f),
+        (TypableDef::BuiltinFunction(f), Namespace::Values) => {
+            Ty::simple(TypeCtor::FnDef(CallableDef::BuiltinFunction(f)))
+        }
         (TypableDef::BuiltinType(t), Namespace::Types) => type_for_builtin(t),
         (TypableDef::Struct(s), Namespace::Values) => type_for_struct_constructor(db, s),
         (TypableDef::Struct(s), Namespace::Types) => type_for_struct(db, s),
+        (TypableDef::Enum(e), Namespace::Types) => type_for_enum(db, e),
+        (TypableDef::EnumVariant(v), Namespace::Values) => type_for_enum_variant_constructor(db, v),
+        (TypableDef::EnumVariant(v), Namespace::Types) => type_for_enum(db, v.parent_enum()),
+        (TypableDef::Const(c), Namespace::Values) => type_for_const(db, c),
+        (TypableDef::Static(s), Namespace::Values) => type_for_static(db, s),
 
         // 'error' cases:
         (TypableDef::Function(_), Namespace::Types) => Ty::Unknown,
+        (TypableDef::BuiltinFunction(_), Namespace::Types) => Ty::Unknown,
         (TypableDef::BuiltinType(_), Namespace::Values) => Ty::Unknown,
+        (TypableDef::Enum(_), Namespace::Values) => Ty::Unknown,
+        (TypableDef::Const(_), Namespace::Types) => Ty::Unknown,
+        (TypableDef::Static(_), Namespace::Types) => Ty::Unknown,
     }
 }
 
+/// Build the declared type of a constant.
+fn type_for_const(db: &impl HirDatabase, def: Const) -> Ty {
+    let data = def.data(db);
+    Ty::from_hir(db, &def.resolver(db), data.type_ref_map(), data.type_ref).ty
+}
+
+/// Build the declared type of a global variable.
+fn type_for_static(db: &impl HirDatabase, def: Static) -> Ty {
+    let data = def.data(db);
+    Ty::from_hir(db, &def.resolver(db), data.type_ref_map(), data.type_ref).ty
+}
+
 /// Build the declared type of a static.
 fn type_for_builtin(def: BuiltinType) -> Ty {
     Ty::simple(match def {
         BuiltinType::Float(f) => TypeCtor::Float(f.into()),
         BuiltinType::Int(i) => TypeCtor::Int(i.into()),
         BuiltinType::Bool => TypeCtor::Bool,
+        BuiltinType::String => TypeCtor::String,
     })
 }
 
 /// Build the declared type of a function. This should not need to look at the
 /// function body.
-fn type_for_fn(_db: &impl HirDatabase, def: Function) -> Ty {
-    Ty::simple(TypeCtor::FnDef(def.into()))
+fn type_for_fn(db: &impl HirDatabase, def: Function) -> Ty {
+    Ty::Apply(ApplicationTy {
+        ctor: TypeCtor::FnDef(def.into()),
+        parameters: Substs::identity(&def.generic_params(db)),
+    })
 }
 
 pub(crate) fn callable_item_sig(db: &impl HirDatabase, def: CallableDef) -> FnSig {
     match def {
         CallableDef::Function(f) => fn_sig_for_fn(db, f),
+        CallableDef::BuiltinFunction(f) => fn_sig_for_builtin_fn(f),
         CallableDef::Struct(s) => fn_sig_for_struct_constructor(db, s),
+        CallableDef::EnumVariant(v) => fn_sig_for_enum_variant_constructor(db, v),
+    }
+}
+
+/// Build the signature of a builtin function.
+pub(crate) fn fn_sig_for_builtin_fn(def: BuiltinFunction) -> FnSig {
+    match def {
+        BuiltinFunction::Panic => FnSig::from_params_and_return(
+            vec![Ty::simple(TypeCtor::String)],
+            Ty::simple(TypeCtor::Never),
+        ),
     }
 }
 
@@ -254,18 +522,55 @@ pub(crate) fn fn_sig_for_struct_constructor(db: &impl HirDatabase, def: Struct)
 fn type_for_struct_constructor(db: &impl HirDatabase, def: Struct) -> Ty {
     let struct_data = db.struct_data(def.id);
     if struct_data.kind == StructKind::Tuple {
-        Ty::simple(TypeCtor::FnDef(def.into()))
+        Ty::Apply(ApplicationTy {
+            ctor: TypeCtor::FnDef(def.into()),
+            parameters: Substs::identity(&def.generic_params(db)),
+        })
     } else {
         type_for_struct(db, def)
     }
 }
 
-fn type_for_struct(_db: &impl HirDatabase, def: Struct) -> Ty {
-    Ty::simple(TypeCtor::Struct(def))
+/// Build the declared type of a struct. The type arguments of a generic struct are its own type
+/// parameters, e.g. `Pair<A, B>`.
+fn type_for_struct(db: &impl HirDatabase, def: Struct) -> Ty {
+    Ty::Apply(ApplicationTy {
+        ctor: TypeCtor::Struct(def),
+        parameters: Substs::identity(&def.generic_params(db)),
+    })
+}
+
+pub(crate) fn fn_sig_for_enum_variant_constructor(
+    db: &impl HirDatabase,
+    def: EnumVariant,
+) -> FnSig {
+    let params = def
+        .fields(db)
+        .into_iter()
+        .map(|field| field.ty(db))
+        .collect::<Vec<_>>();
+    let ret = type_for_enum(db, def.parent_enum());
+    FnSig::from_params_and_return(params, ret)
+}
+
+/// Build the type of an enum variant constructor. Only tuple variants have a constructor function;
+/// unit variants are values of the enum type.
+fn type_for_enum_variant_constructor(db: &impl HirDatabase, def: EnumVariant) -> Ty {
+    if def.kind(db) == StructKind::Tuple {
+        Ty::simple(TypeCtor::FnDef(def.into()))
+    } else {
+        type_for_enum(db, def.parent_enum())
+    }
+}
+
+fn type_for_enum(_db: &impl HirDatabase, def: Enum) -> Ty {
+    Ty::simple(TypeCtor::Enum(def))
 }
 
 pub mod diagnostics {
-    use crate::diagnostics::UnresolvedType;
+    use crate::diagnostics::{
+        InvalidArrayLength, InvalidOptionalType, UnresolvedType, WrongNumberOfTypeArguments,
+    };
     use crate::{
         diagnostics::DiagnosticSink,
         type_ref::{TypeRefId, TypeRefSourceMap},
@@ -274,7 +579,20 @@ pub mod diagnostics {
 
     #[derive(Debug, PartialEq, Eq, Clone)]
     pub(crate) enum LowerDiagnostic {
-        UnresolvedType { id: TypeRefId },
+        UnresolvedType {
+            id: TypeRefId,
+        },
+        InvalidArrayLength {
+            id: TypeRefId,
+        },
+        InvalidOptionalType {
+            id: TypeRefId,
+        },
+        WrongNumberOfTypeArguments {
+            id: TypeRefId,
+            expected: usize,
+            found: usize,
+        },
     }
 
     impl LowerDiagnostic {
@@ -290,6 +608,24 @@ pub mod diagnostics {
                     file: file_id,
                     type_ref: source_map.type_ref_syntax(*id).unwrap(),
                 }),
+                LowerDiagnostic::InvalidArrayLength { id } => sink.push(InvalidArrayLength {
+                    file: file_id,
+                    type_ref: source_map.type_ref_syntax(*id).unwrap(),
+                }),
+                LowerDiagnostic::InvalidOptionalType { id } => sink.push(InvalidOptionalType {
+                    file: file_id,
+                    type_ref: source_map.type_ref_syntax(*id).unwrap(),
+                }),
+                LowerDiagnostic::WrongNumberOfTypeArguments {
+                    id,
+                    expected,
+                    found,
+                } => sink.push(WrongNumberOfTypeArguments {
+                    file: file_id,
+                    type_ref: source_map.type_ref_syntax(*id).unwrap(),
+                    expected: *expected,
+                    found: *found,
+                }),
             }
         }
     }
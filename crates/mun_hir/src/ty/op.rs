@@ -1,5 +1,5 @@
 use crate::ty::infer::InferTy;
-use crate::{ApplicationTy, ArithOp, BinaryOp, Ty, TypeCtor};
+use crate::{ApplicationTy, ArithOp, BinaryOp, CmpOp, Ty, TypeCtor};
 
 /// Given a binary operation and the type on the left of that operation, returns the expected type
 /// for the right hand side of the operation or `Ty::Unknown` if such an operation is invalid.
@@ -8,9 +8,14 @@ pub(super) fn binary_op_rhs_expectation(op: BinaryOp, lhs_ty: Ty) -> Ty {
         BinaryOp::LogicOp(..) => Ty::simple(TypeCtor::Bool),
 
         // Compare operations are allowed for all scalar types
-        BinaryOp::CmpOp(..) => match lhs_ty {
+        BinaryOp::CmpOp(cmp_op) => match lhs_ty {
             Ty::Apply(ApplicationTy { ctor, .. }) => match ctor {
                 TypeCtor::Int(_) | TypeCtor::Float(_) | TypeCtor::Bool => lhs_ty,
+                // Strings and optional references can only be compared for equality
+                TypeCtor::String | TypeCtor::Optional => match cmp_op {
+                    CmpOp::Eq { .. } => lhs_ty,
+                    CmpOp::Ord { .. } => Ty::Unknown,
+                },
                 _ => Ty::Unknown,
             },
             Ty::Infer(InferTy::IntVar(..)) | Ty::Infer(InferTy::FloatVar(..)) => lhs_ty,
@@ -19,9 +24,17 @@ pub(super) fn binary_op_rhs_expectation(op: BinaryOp, lhs_ty: Ty) -> Ty {
 
         BinaryOp::Assignment { op: None } => match lhs_ty {
             Ty::Apply(ApplicationTy { ctor, .. }) => match ctor {
-                TypeCtor::Int(_) | TypeCtor::Float(_) | TypeCtor::Bool | TypeCtor::Struct(_) => {
-                    lhs_ty
-                }
+                TypeCtor::Int(_)
+                | TypeCtor::Float(_)
+                | TypeCtor::Bool
+                | TypeCtor::String
+                | TypeCtor::Struct(_)
+                | TypeCtor::Enum(_)
+                | TypeCtor::Array(_)
+                | TypeCtor::DynArray
+                | TypeCtor::Optional
+                | TypeCtor::Tuple
+                | TypeCtor::FnPtr { .. } => lhs_ty,
                 _ => Ty::Unknown,
             },
             Ty::Infer(InferTy::IntVar(..)) | Ty::Infer(InferTy::FloatVar(..)) => lhs_ty,
@@ -56,9 +69,11 @@ pub(super) fn binary_op_rhs_expectation(op: BinaryOp, lhs_ty: Ty) -> Ty {
         },
 
         // Arithmetic operations are supported only on number types
-        BinaryOp::Assignment { op: Some(_) } | BinaryOp::ArithOp(_) => match lhs_ty {
+        BinaryOp::Assignment { op: Some(arith_op) } | BinaryOp::ArithOp(arith_op) => match lhs_ty {
             Ty::Apply(ApplicationTy { ctor, .. }) => match ctor {
                 TypeCtor::Int(_) | TypeCtor::Float(_) => lhs_ty,
+                // Strings can be concatenated
+                TypeCtor::String if arith_op == ArithOp::Add => lhs_ty,
                 _ => Ty::Unknown,
             },
             Ty::Infer(InferTy::IntVar(..)) | Ty::Infer(InferTy::FloatVar(..)) => lhs_ty,
@@ -71,9 +86,10 @@ pub(super) fn binary_op_rhs_expectation(op: BinaryOp, lhs_ty: Ty) -> Ty {
 /// the return type of that operation.
 pub(super) fn binary_op_return_ty(op: BinaryOp, rhs_ty: Ty) -> Ty {
     match op {
-        BinaryOp::ArithOp(_) => match rhs_ty {
+        BinaryOp::ArithOp(arith_op) => match rhs_ty {
             Ty::Apply(ApplicationTy { ctor, .. }) => match ctor {
                 TypeCtor::Int(_) | TypeCtor::Float(_) => rhs_ty,
+                TypeCtor::String if arith_op == ArithOp::Add => rhs_ty,
                 _ => Ty::Unknown,
             },
             Ty::Infer(InferTy::IntVar(..)) | Ty::Infer(InferTy::FloatVar(..)) => rhs_ty,
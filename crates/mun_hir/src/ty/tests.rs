@@ -1,9 +1,10 @@
+use crate::code_model::DefWithBody;
 use crate::db::SourceDatabase;
 use crate::diagnostics::DiagnosticSink;
 use crate::expr::BodySourceMap;
 use crate::ids::LocationCtx;
 use crate::mock::MockDatabase;
-use crate::{Function, HirDisplay, InferenceResult};
+use crate::{Const, Function, HirDisplay, InferenceResult, Static};
 use mun_syntax::{ast, AstNode};
 use std::fmt::Write;
 use std::sync::Arc;
@@ -184,6 +185,34 @@ fn place_expressions() {
     )
 }
 
+#[test]
+fn infer_globals() {
+    infer_snapshot(
+        r#"
+    const GRAVITY: f32 = 9.81;
+    const TWO: i32 = 1 + 1;
+    static mut SCORE: i32 = TWO * TWO;
+    static MAX_SCORE: i32 = 100;
+
+    fn main() -> f32 {
+        SCORE += TWO;
+        GRAVITY
+    }
+
+    fn assign() {
+        TWO = 3;        // error: invalid left hand side of expression
+        MAX_SCORE = 3;  // error: invalid left hand side of expression
+    }
+
+    const CALL: i32 = main() as i32;    // error: expression cannot be evaluated at compile time
+    const DIV: i32 = 1 / (TWO - 2);     // error: attempt to divide by zero
+    const CYCLE: i32 = CYCLE + 1;       // error: cycle detected when evaluating the value of a constant
+    struct Foo;
+    static FOO: Foo = Foo;              // error: not a boolean, integer or floating-point type
+    "#,
+    )
+}
+
 #[test]
 fn update_operators() {
     infer_snapshot(
@@ -272,6 +301,60 @@ fn infer_while() {
     )
 }
 
+#[test]
+fn infer_for() {
+    infer_snapshot(
+        r#"
+    fn foo() {
+        let n = 0;
+        for i in 0..n { n += i; };
+        for _ in 0..=10u8 { break; };
+        for i in 1.0..3.0 {};   // error: `for` loops can only iterate over integer ranges
+        for i in n {};          // error: `for` loops can only iterate over integer ranges
+        let r = 0..3;           // error: range expressions can only appear in a `for` loop
+    }
+    "#,
+    )
+}
+
+#[test]
+fn infer_array() {
+    infer_snapshot(
+        r#"
+    fn foo(a: [i32], b: [f64; 2]) -> usize {
+        let c = [1, 2, 3];
+        let d: [u8] = [4, 5];
+        let e = [];             // error: type annotations needed
+        a.push(c[0]);
+        a[1] = a.pop();
+        b[0] += 1.0;
+        b.push(1.0);            // error: no method named `push` found for this type
+        d[true];                // error: mismatched type
+        let f = 3;
+        f[0];                   // error: cannot index into a value that is not an array
+        a.len() + b.len()
+    }
+    "#,
+    )
+}
+
+#[test]
+fn infer_string() {
+    infer_snapshot(
+        r#"
+    fn foo(a: string) -> usize {
+        let b = "hello";
+        let c = a + b;
+        c += "!";
+        let d = c == b;
+        let e = a < b;          // error: invalid binary operation
+        let f = a - b;          // error: invalid binary operation
+        c.len()
+    }
+    "#,
+    )
+}
+
 #[test]
 fn invalid_binary_ops() {
     infer_snapshot(
@@ -363,6 +446,214 @@ fn struct_field_index() {
     )
 }
 
+#[test]
+fn infer_enum() {
+    infer_snapshot(
+        r#"
+    enum Shape {
+        Circle(f32),
+        Rect { w: f32, h: f32 },
+        None,
+    }
+
+    fn area(s: Shape) -> f32 {
+        match s {
+            Shape::Circle(r) => r * r,
+            Shape::Rect { w, h: height } => w * height,
+            Shape::None => 0.0,
+        }
+    }
+
+    fn main() {
+        let a = Shape::Circle(1.0);
+        let b = Shape::Rect { w: 1.0, h: 2.0 };
+        let c = Shape::None;
+        let d = Shape::Circle;  // error: mismatched struct literal kind. expected `tuple`, found `unit struct`
+        let e = Shape::Rect;    // error: mismatched struct literal kind. expected `record`, found `unit struct`
+        let f = Shape::None(1); // error: mismatched struct literal kind. expected `unit struct`, found `tuple`
+    }
+    "#,
+    )
+}
+
+#[test]
+fn infer_impl_block() {
+    infer_snapshot(
+        r#"
+    struct Foo {
+        a: f32,
+    }
+
+    impl Foo {
+        fn new(a: f32) -> Self {
+            Self { a }
+        }
+
+        fn len(self) -> f32 {
+            self.a
+        }
+
+        fn scale(self, b: f32) -> Foo {
+            Foo::new(self.len() * b)
+        }
+    }
+
+    fn main() {
+        let foo = Foo::new(2.0);
+        let a = foo.len();
+        let b = foo.scale(3.0).len();
+        foo.new();              // error: no method named `new` found for this type
+        let c = Foo::missing;   // error: undefined value
+    }
+
+    fn bar(self) {}             // error: `self` parameter is only allowed in functions of `impl` blocks and traits
+    "#,
+    )
+}
+
+#[test]
+fn infer_generics() {
+    infer_snapshot(
+        r#"
+    struct Pair<A, B> {
+        first: A,
+        second: B,
+    }
+
+    fn max<T>(a: T, b: T, a_is_larger: bool) -> T {
+        if a_is_larger { a } else { b }
+    }
+
+    fn zero<T>() -> i32 {
+        0
+    }
+
+    fn main() {
+        let a = max(1, 2, true);
+        let b = max(1.0, 2.5, false);
+        let p = Pair { first: a, second: b };
+        let c: Pair<i32, f64> = p;
+        let d = p.second;
+        let e: Pair<i32> = p;   // error: wrong number of type arguments: expected 2, found 1
+        let f = zero();         // error: type annotations needed: cannot infer the type arguments of a generic function or struct
+    }
+    "#,
+    )
+}
+
+#[test]
+fn infer_tuples() {
+    infer_snapshot(
+        r#"
+    fn swap(p: (i32, f64)) -> (f64, i32) {
+        (p.1, p.0)
+    }
+
+    fn main() {
+        let a = (1, 2.0);
+        let (b, c) = swap(a);
+        let d: (i32,) = (5,);
+        let e = d.0;
+        let f = ();
+        let h = a.2;        // error: attempted to access a non-existent field in a struct.
+        let (i, j) = true;  // error: mismatched type
+    }
+    "#,
+    )
+}
+
+#[test]
+fn infer_cast() {
+    infer_snapshot(
+        r#"
+    struct Foo;
+
+    fn main(a: i32, b: f64, c: bool, d: Foo) {
+        let e = a as f64;
+        let f = b as u8;
+        let g = c as i64;
+        let h = 5 as f32;
+        let i = a as i32;
+        let j = d as i32;   // error: non-primitive cast
+        let k = a as bool;  // error: non-primitive cast
+    }
+    "#,
+    )
+}
+
+#[test]
+fn infer_closures() {
+    infer_snapshot(
+        r#"
+    fn add(a: i32, b: i32) -> i32 { a + b }
+
+    fn apply(f: fn(i32) -> i32, x: i32) -> i32 {
+        f(x)
+    }
+
+    fn main(k: i32) {
+        let a: fn(i32, i32) -> i32 = add;
+        let b = |x: i32| x * k;
+        let c = apply(|x| x + k, a(1, 2));
+        let d = b(2);
+        let e = || {};
+        let f = |x: i32| { k = x; };    // error: invalid left hand side of expression
+    }
+    "#,
+    )
+}
+
+#[test]
+fn infer_optional() {
+    infer_snapshot(
+        r#"
+    struct(gc) Foo { a: i32 }
+
+    fn main(a: Foo?, b: Foo) -> i32 {
+        let c: Foo? = nil;
+        c = b;
+        let d: i32 = a.a;   // error: value may be `nil`
+        if a != nil {
+            d = a.a;
+        }
+        let e = nil;        // error: cannot infer the type of `nil`
+        d
+    }
+
+    fn early_return(a: Foo?) -> i32 {
+        if a == nil {
+            return 0;
+        }
+        a.a
+    }
+
+    fn invalid(a: i32?) {}  // error: only garbage collected structs can be optional
+    "#,
+    )
+}
+
+#[test]
+fn infer_panic() {
+    infer_snapshot(
+        r#"
+    fn main(a: i32) -> i32 {
+        if a > 0 {
+            panic("a is positive");
+        }
+        a
+    }
+
+    fn tail() -> i32 {
+        panic("oops")
+    }
+
+    fn invalid() {
+        let f = panic;  // error: builtin function `panic` can only be called, not used as a value
+    }
+    "#,
+    )
+}
+
 #[test]
 fn primitives() {
     infer_snapshot(
@@ -455,17 +746,29 @@ fn infer(content: &str) -> String {
 
     let ctx = LocationCtx::new(&db, file_id);
     for node in source_file.syntax().descendants() {
-        if let Some(def) = ast::FunctionDef::cast(node.clone()) {
+        let def: DefWithBody = if let Some(def) = ast::FunctionDef::cast(node.clone()) {
             let fun = Function {
                 id: ctx.to_def(&def),
             };
-            let source_map = fun.body_source_map(&db);
-            let infer_result = fun.infer(&db);
-
             fun.diagnostics(&db, &mut diag_sink);
+            fun.into()
+        } else if let Some(def) = ast::ConstDef::cast(node.clone()) {
+            let c = Const {
+                id: ctx.to_def(&def),
+            };
+            c.diagnostics(&db, &mut diag_sink);
+            c.into()
+        } else if let Some(def) = ast::StaticDef::cast(node.clone()) {
+            let s = Static {
+                id: ctx.to_def(&def),
+            };
+            s.diagnostics(&db, &mut diag_sink);
+            s.into()
+        } else {
+            continue;
+        };
 
-            infer_def(infer_result, source_map);
-        }
+        infer_def(def.infer(&db), def.body_source_map(&db));
     }
 
     drop(diag_sink);
@@ -2,7 +2,7 @@ use crate::arena::map::ArenaMap;
 use crate::arena::{Arena, RawId};
 ///! HIR for references to types. These paths are not yet resolved. They can be directly created
 /// from an `ast::TypeRef`, without further queries.
-use crate::Path;
+use crate::{name::name, Path};
 use mun_syntax::ast;
 use mun_syntax::AstPtr;
 use rustc_hash::FxHashMap;
@@ -15,37 +15,25 @@ impl_arena_id!(TypeRefId);
 /// Compare ty::Ty
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum TypeRef {
-    Path(Path),
+    /// A path to a type, followed by the type arguments of a generic type, e.g. `Pair<f32, i64>`
+    Path(Path, Vec<TypeRefId>),
+    /// A fixed-size array `[T; N]`. The length is `None` if it is not a valid integer literal.
+    Array(TypeRefId, Option<u32>),
+    /// A growable array `[T]`
+    DynArray(TypeRefId),
+    /// A garbage collected reference that may be `nil`, e.g. `Foo?`
+    Optional(TypeRefId),
+    /// A tuple type with at least one element, e.g. `(f32, i64)`. The empty tuple `()` is
+    /// represented by `TypeRef::Empty`.
+    Tuple(Vec<TypeRefId>),
+    /// A function pointer type, e.g. `fn(f32) -> f32`, with the types of its parameters and its
+    /// return type. The return type is `TypeRef::Empty` if it is omitted.
+    Fn(Vec<TypeRefId>, TypeRefId),
     Never,
     Empty,
     Error,
 }
 
-impl TypeRef {
-    /// Converts an `ast::TypeRef` to a `hir::TypeRef`.
-    pub fn from_ast(node: ast::TypeRef) -> Self {
-        match node.kind() {
-            ast::TypeRefKind::NeverType(..) => TypeRef::Never,
-            ast::TypeRefKind::PathType(inner) => {
-                // FIXME: Use `Path::from_src`
-                inner
-                    .path()
-                    .and_then(Path::from_ast)
-                    .map(TypeRef::Path)
-                    .unwrap_or(TypeRef::Error)
-            }
-        }
-    }
-
-    pub fn from_ast_opt(node: Option<ast::TypeRef>) -> Self {
-        if let Some(node) = node {
-            TypeRef::from_ast(node)
-        } else {
-            TypeRef::Error
-        }
-    }
-}
-
 #[derive(Default, Debug, Eq, PartialEq)]
 pub struct TypeRefSourceMap {
     type_ref_map: FxHashMap<AstPtr<ast::TypeRef>, TypeRefId>,
@@ -108,12 +96,66 @@ impl TypeRefBuilder {
         use mun_syntax::ast::TypeRefKind::*;
         let ptr = AstPtr::new(node);
         let type_ref = match node.kind() {
-            PathType(path) => path
-                .path()
-                .and_then(Path::from_ast)
-                .map(TypeRef::Path)
-                .unwrap_or(TypeRef::Error),
+            PathType(path) => match path.path() {
+                Some(path) => {
+                    let type_args = path
+                        .segment()
+                        .and_then(|segment| segment.type_arg_list())
+                        .map(|type_arg_list| {
+                            type_arg_list
+                                .type_args()
+                                .map(|arg| self.alloc_from_node_opt(arg.type_ref().as_ref()))
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    Path::from_ast(path)
+                        .map(|path| TypeRef::Path(path, type_args))
+                        .unwrap_or(TypeRef::Error)
+                }
+                None => TypeRef::Error,
+            },
             NeverType(_) => TypeRef::Never,
+            ArrayType(array) => {
+                let element_type = self.alloc_from_node_opt(array.type_ref().as_ref());
+                match array.size() {
+                    Some(size) => TypeRef::Array(element_type, array_length(&size)),
+                    None => TypeRef::DynArray(element_type),
+                }
+            }
+            OptionType(option) => {
+                TypeRef::Optional(self.alloc_from_node_opt(option.type_ref().as_ref()))
+            }
+            ParenType(inner) => match inner.type_ref() {
+                Some(inner) => return self.alloc_from_node(&inner),
+                None => TypeRef::Error,
+            },
+            TupleType(tuple) => {
+                let fields: Vec<_> = tuple
+                    .fields()
+                    .map(|field| self.alloc_from_node(&field))
+                    .collect();
+                if fields.is_empty() {
+                    TypeRef::Empty
+                } else {
+                    TypeRef::Tuple(fields)
+                }
+            }
+            FnPointerType(fn_pointer) => {
+                let params = fn_pointer
+                    .param_list()
+                    .map(|param_list| {
+                        param_list
+                            .params()
+                            .map(|param| self.alloc_from_node_opt(param.ascribed_type().as_ref()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let ret = match fn_pointer.ret_type().and_then(|ret| ret.type_ref()) {
+                    Some(ret) => self.alloc_from_node(&ret),
+                    None => self.unit(),
+                };
+                TypeRef::Fn(params, ret)
+            }
         };
         self.alloc_type_ref(type_ref, ptr)
     }
@@ -126,7 +168,27 @@ impl TypeRefBuilder {
         self.map.type_refs.alloc(TypeRef::Error)
     }
 
+    /// Allocates the type of a `self` parameter, which is the `Self` type of its `impl` block.
+    pub fn self_type(&mut self) -> TypeRefId {
+        self.map
+            .type_refs
+            .alloc(TypeRef::Path(name![Self].into(), Vec::new()))
+    }
+
     pub fn finish(self) -> (TypeRefMap, TypeRefSourceMap) {
         (self.map, self.source_map)
     }
 }
+
+/// Evaluates the length of a fixed-size array type. Only unsuffixed integer literals are supported.
+fn array_length(size: &ast::Expr) -> Option<u32> {
+    match size.kind() {
+        ast::ExprKind::Literal(lit) if lit.kind() == ast::LiteralKind::IntNumber => {
+            match lit.text_and_suffix() {
+                (text, None) => text.replace('_', "").parse().ok(),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
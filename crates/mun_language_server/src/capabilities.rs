@@ -0,0 +1,23 @@
+use lsp_types::{
+    CompletionOptions, ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind,
+    WorkDoneProgressOptions,
+};
+
+/// Returns the capabilities of the language server, which are announced to the client when the
+/// connection is initialized.
+pub(crate) fn server_capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        // The client sends the entire text of a document when it changes
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::Full)),
+        hover_provider: Some(true),
+        completion_provider: Some(CompletionOptions {
+            resolve_provider: None,
+            trigger_characters: Some(vec![".".to_string()]),
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
+        definition_provider: Some(true),
+        ..Default::default()
+    }
+}
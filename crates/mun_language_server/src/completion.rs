@@ -0,0 +1,127 @@
+use crate::state::LanguageServerState;
+use lsp_types::{CompletionItem, CompletionItemKind, CompletionParams, CompletionResponse};
+use mun_hir::{
+    HirDatabase, HirDisplay, ModuleDef, PerNs, Resolution, SourceAnalyzer, SourceDatabase,
+};
+use mun_syntax::{ast, AstNode, SyntaxToken, T};
+use std::collections::HashSet;
+
+/// Returns the completions at the position of `params`. After the `.` of a field expression these
+/// are the fields of the receiver, otherwise these are the names that are in scope.
+pub(crate) fn completion(
+    state: &LanguageServerState,
+    params: CompletionParams,
+) -> Option<CompletionResponse> {
+    let (file_id, offset) = state.file_position(&params.text_document_position)?;
+    let db = &state.db;
+    let syntax = db.parse(file_id).syntax_node();
+    let token = syntax.token_at_offset(offset).left_biased()?;
+
+    let analyzer = SourceAnalyzer::new(db, file_id, &token.parent(), Some(offset));
+    let items = match field_expr_of_name(&token) {
+        Some(field_expr) => complete_fields(db, &analyzer, &field_expr)?,
+        None => complete_names(db, &analyzer),
+    };
+    Some(CompletionResponse::Array(items))
+}
+
+/// Returns the field expression of which `token` is the `.` or (part of) the field name.
+fn field_expr_of_name(token: &SyntaxToken) -> Option<ast::FieldExpr> {
+    let parent = token.parent();
+    if token.kind() == T![.] {
+        ast::FieldExpr::cast(parent)
+    } else {
+        ast::NameRef::cast(parent)?
+            .syntax()
+            .parent()
+            .and_then(ast::FieldExpr::cast)
+    }
+}
+
+/// Returns the fields of the struct type of the receiver of `field_expr`.
+fn complete_fields(
+    db: &impl HirDatabase,
+    analyzer: &SourceAnalyzer,
+    field_expr: &ast::FieldExpr,
+) -> Option<Vec<CompletionItem>> {
+    let ty = analyzer.type_of(&field_expr.expr()?)?;
+    let substs = ty.substs()?;
+    let items = ty
+        .as_struct()?
+        .fields(db)
+        .into_iter()
+        .map(|field| {
+            let field_ty = field.ty(db).subst(&substs);
+            completion_item(
+                field.name(db).to_string(),
+                CompletionItemKind::Field,
+                Some(field_ty.display(db).to_string()),
+            )
+        })
+        .collect();
+    Some(items)
+}
+
+/// Returns the local bindings, functions, and other items that are visible from the analyzed
+/// location. A name that is shadowed by an inner scope is only returned once.
+fn complete_names(db: &impl HirDatabase, analyzer: &SourceAnalyzer) -> Vec<CompletionItem> {
+    let mut seen = HashSet::new();
+    let mut items = Vec::new();
+    analyzer.process_all_names(db, &mut |name, resolution| {
+        let name = name.to_string();
+        if seen.contains(&name) {
+            return;
+        }
+        let PerNs { types, values } = resolution;
+        let (kind, detail) = match values.or(types) {
+            Some(Resolution::LocalBinding(pat)) => (
+                CompletionItemKind::Variable,
+                analyzer
+                    .type_of_binding(pat)
+                    .map(|ty| ty.display(db).to_string()),
+            ),
+            Some(Resolution::Def(def)) => match def {
+                ModuleDef::Function(f) => (
+                    CompletionItemKind::Function,
+                    Some(f.ty(db).display(db).to_string()),
+                ),
+                ModuleDef::BuiltinFunction(_) => (CompletionItemKind::Function, None),
+                ModuleDef::Const(c) => (
+                    CompletionItemKind::Constant,
+                    Some(c.ty(db).display(db).to_string()),
+                ),
+                ModuleDef::Static(s) => (
+                    CompletionItemKind::Variable,
+                    Some(s.ty(db).display(db).to_string()),
+                ),
+                ModuleDef::Struct(_) => (CompletionItemKind::Struct, None),
+                ModuleDef::Enum(_) => (CompletionItemKind::Enum, None),
+                ModuleDef::EnumVariant(_) => (CompletionItemKind::EnumMember, None),
+                ModuleDef::Trait(_) => (CompletionItemKind::Interface, None),
+                ModuleDef::Module(_) => (CompletionItemKind::Module, None),
+                ModuleDef::BuiltinType(_) => (CompletionItemKind::Struct, None),
+            },
+            Some(Resolution::SelfType(_)) | Some(Resolution::GenericParam(_)) => {
+                (CompletionItemKind::TypeParameter, None)
+            }
+            None => return,
+        };
+        items.push(completion_item(name.clone(), kind, detail));
+        seen.insert(name);
+    });
+    items
+}
+
+/// Constructs a completion item with the `label`, `kind`, and `detail`.
+fn completion_item(
+    label: String,
+    kind: CompletionItemKind,
+    detail: Option<String>,
+) -> CompletionItem {
+    CompletionItem {
+        label,
+        kind: Some(kind),
+        detail,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,30 @@
+//! Conversions between the positions of the Language Server Protocol and the offsets of the syntax
+//! tree. Columns are counted in bytes, which matches the UTF-16 columns of the protocol for ASCII
+//! text.
+
+use lsp_types::{Position, Range};
+use mun_hir::line_index::{LineCol, LineIndex};
+use mun_syntax::{TextRange, TextUnit};
+
+/// Converts the protocol position `position` to an offset in the file of `line_index`. Returns
+/// `None` if the position lies outside of the file.
+pub(crate) fn convert_position(line_index: &LineIndex, position: Position) -> Option<TextUnit> {
+    line_index.offset(LineCol {
+        line: position.line as u32,
+        col: position.character as u32,
+    })
+}
+
+/// Converts the offset `offset` in the file of `line_index` to a protocol position.
+pub(crate) fn convert_offset(line_index: &LineIndex, offset: TextUnit) -> Position {
+    let line_col = line_index.line_col(offset);
+    Position::new(line_col.line.into(), line_col.col.into())
+}
+
+/// Converts the range `range` in the file of `line_index` to a protocol range.
+pub(crate) fn convert_range(line_index: &LineIndex, range: TextRange) -> Range {
+    Range::new(
+        convert_offset(line_index, range.start()),
+        convert_offset(line_index, range.end()),
+    )
+}
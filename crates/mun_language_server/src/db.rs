@@ -0,0 +1,31 @@
+use mun_hir::{salsa, HirDatabase};
+use mun_target::spec::Target;
+
+/// The database that stores the semantic model of the source files of the workspace. Queries are
+/// recomputed incrementally whenever the text of a file changes.
+#[salsa::database(
+    mun_hir::SourceDatabaseStorage,
+    mun_hir::DefDatabaseStorage,
+    mun_hir::HirDatabaseStorage
+)]
+#[derive(Debug)]
+pub(crate) struct AnalysisDatabase {
+    runtime: salsa::Runtime<AnalysisDatabase>,
+}
+
+impl AnalysisDatabase {
+    pub fn new() -> Self {
+        let mut db = AnalysisDatabase {
+            runtime: salsa::Runtime::default(),
+        };
+        // The code is analyzed for the machine that the language server runs on
+        db.set_target(Target::host_target().expect("unable to load the host target"));
+        db
+    }
+}
+
+impl salsa::Database for AnalysisDatabase {
+    fn salsa_runtime(&self) -> &salsa::Runtime<AnalysisDatabase> {
+        &self.runtime
+    }
+}
@@ -0,0 +1,60 @@
+use crate::conversion::convert_range;
+use lsp_types::{Diagnostic, DiagnosticSeverity};
+use mun_hir::diagnostics::{Diagnostic as HirDiagnostic, DiagnosticSink};
+use mun_hir::{FileId, HirDatabase, Module};
+use mun_syntax::{ast, AstNode, SyntaxKind, TextRange};
+use std::cell::RefCell;
+
+/// Returns the syntax errors and semantic errors of the file `file_id`.
+pub(crate) fn diagnostics(db: &impl HirDatabase, file_id: FileId) -> Vec<Diagnostic> {
+    let parse = db.parse(file_id);
+    let line_index = db.line_index(file_id);
+
+    let mut result: Vec<Diagnostic> = parse
+        .errors()
+        .iter()
+        .map(|err| {
+            let range = TextRange::from_to(err.location().offset(), err.location().end_offset());
+            error(convert_range(&line_index, range), err.to_string())
+        })
+        .collect();
+
+    let hir_diagnostics = RefCell::new(Vec::new());
+    let mut sink = DiagnosticSink::new(|d| {
+        if d.source().file_id != file_id {
+            return;
+        }
+        let range = highlight_range(db, d);
+        hir_diagnostics
+            .borrow_mut()
+            .push(error(convert_range(&line_index, range), d.message()));
+    });
+    Module::from(file_id).diagnostics(db, &mut sink);
+    drop(sink);
+
+    result.append(&mut hir_diagnostics.into_inner());
+    result
+}
+
+/// Constructs an error diagnostic with the `message` at `range`.
+fn error(range: lsp_types::Range, message: String) -> Diagnostic {
+    let mut diagnostic = Diagnostic::new_simple(range, message);
+    diagnostic.severity = Some(DiagnosticSeverity::Error);
+    diagnostic.source = Some("mun".to_string());
+    diagnostic
+}
+
+/// Returns the range that is highlighted for the diagnostic `d`. Diagnostics of entire functions
+/// and structs only highlight their signatures.
+fn highlight_range(db: &impl HirDatabase, d: &dyn HirDiagnostic) -> TextRange {
+    let source = d.source();
+    match source.value.kind() {
+        SyntaxKind::FUNCTION_DEF => ast::FunctionDef::cast(d.syntax_node(db))
+            .map(|f| f.signature_range())
+            .unwrap_or_else(|| d.highlight_range()),
+        SyntaxKind::STRUCT_DEF => ast::StructDef::cast(d.syntax_node(db))
+            .map(|s| s.signature_range())
+            .unwrap_or_else(|| d.highlight_range()),
+        _ => d.highlight_range(),
+    }
+}
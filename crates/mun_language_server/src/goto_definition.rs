@@ -0,0 +1,34 @@
+use crate::state::LanguageServerState;
+use lsp_types::{GotoDefinitionParams, GotoDefinitionResponse};
+use mun_hir::{SourceAnalyzer, SourceDatabase};
+use mun_syntax::{algo::find_node_at_offset, ast, AstNode};
+
+/// Returns the location of the declaration of the name at the position of `params`. The name is
+/// either a path, which is resolved by the scopes that surround it, or the field of a field
+/// expression, which is resolved by the type of its receiver.
+pub(crate) fn goto_definition(
+    state: &LanguageServerState,
+    params: GotoDefinitionParams,
+) -> Option<GotoDefinitionResponse> {
+    let (file_id, offset) = state.file_position(&params.text_document_position_params)?;
+    let db = &state.db;
+    let syntax = db.parse(file_id).syntax_node();
+
+    let field_expr = find_node_at_offset::<ast::FieldExpr>(&syntax, offset).filter(|field_expr| {
+        field_expr.name_ref().map_or(false, |name_ref| {
+            name_ref.syntax().text_range().contains_inclusive(offset)
+        })
+    });
+    let range = if let Some(field_expr) = field_expr {
+        let analyzer = SourceAnalyzer::new(db, file_id, field_expr.syntax(), None);
+        let field = analyzer.resolve_field(db, &field_expr)?;
+        analyzer.field_declaration_range(db, field)?
+    } else {
+        let path = find_node_at_offset::<ast::Path>(&syntax, offset)?;
+        let analyzer = SourceAnalyzer::new(db, file_id, path.syntax(), None);
+        let resolution = analyzer.resolve_path(db, &path)?;
+        analyzer.declaration_range(db, &resolution)?
+    };
+
+    Some(GotoDefinitionResponse::Scalar(state.location(range)))
+}
@@ -0,0 +1,35 @@
+use crate::conversion::convert_range;
+use crate::state::LanguageServerState;
+use lsp_types::{Hover, HoverContents, HoverParams, MarkupContent, MarkupKind};
+use mun_hir::{HirDisplay, SourceAnalyzer, SourceDatabase, Ty};
+use mun_syntax::{algo::find_node_at_offset, ast, AstNode};
+
+/// Returns the inferred type of the pattern or expression at the position of `params`.
+pub(crate) fn hover(state: &LanguageServerState, params: HoverParams) -> Option<Hover> {
+    let (file_id, offset) = state.file_position(&params.text_document_position_params)?;
+    let db = &state.db;
+    let syntax = db.parse(file_id).syntax_node();
+
+    // A pattern is never part of an expression, so a pattern at the offset is always the innermost
+    // node of interest
+    let (node, ty) = if let Some(pat) = find_node_at_offset::<ast::Pat>(&syntax, offset) {
+        let analyzer = SourceAnalyzer::new(db, file_id, pat.syntax(), None);
+        (pat.syntax().clone(), analyzer.type_of_pat(&pat)?)
+    } else {
+        let expr = find_node_at_offset::<ast::Expr>(&syntax, offset)?;
+        let analyzer = SourceAnalyzer::new(db, file_id, expr.syntax(), None);
+        (expr.syntax().clone(), analyzer.type_of(&expr)?)
+    };
+
+    if let Ty::Unknown = ty {
+        return None;
+    }
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: format!("```mun\n{}\n```", ty.display(db)),
+        }),
+        range: Some(convert_range(&state.line_index(file_id), node.text_range())),
+    })
+}
@@ -0,0 +1,36 @@
+//! An implementation of the [Language Server Protocol](https://microsoft.github.io/language-server-protocol/)
+//! for Mun. The language server answers the queries of an editor, such as the diagnostics of a
+//! file, the definition of a name, the type of an expression, or the completions at the cursor,
+//! using the incremental semantic model of the `mun_hir` crate.
+
+mod capabilities;
+mod completion;
+mod conversion;
+mod db;
+mod diagnostics;
+mod goto_definition;
+mod hover;
+mod main_loop;
+mod state;
+
+pub use lsp_server::Connection;
+
+use crate::capabilities::server_capabilities;
+use lsp_types::InitializeParams;
+
+/// Runs the language server over stdin and stdout until the client shuts it down.
+pub fn run_server() -> Result<(), failure::Error> {
+    let (connection, io_threads) = Connection::stdio();
+    run(connection)?;
+    io_threads.join()?;
+    Ok(())
+}
+
+/// Runs the language server over `connection` until the client shuts it down. The connection is
+/// initialized by the first messages of the client.
+pub fn run(connection: Connection) -> Result<(), failure::Error> {
+    let capabilities = serde_json::to_value(server_capabilities())?;
+    let params = connection.initialize(capabilities)?;
+    let params: InitializeParams = serde_json::from_value(params)?;
+    main_loop::main_loop(connection, params)
+}
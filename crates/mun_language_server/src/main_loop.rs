@@ -0,0 +1,147 @@
+use crate::completion::completion;
+use crate::diagnostics::diagnostics;
+use crate::goto_definition::goto_definition;
+use crate::hover::hover;
+use crate::state::LanguageServerState;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, PublishDiagnostics,
+    },
+    request::{Completion, GotoDefinition, HoverRequest},
+    InitializeParams, PublishDiagnosticsParams,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Handles the messages of the client until it shuts down the language server.
+pub(crate) fn main_loop(
+    connection: Connection,
+    params: InitializeParams,
+) -> Result<(), failure::Error> {
+    let source_dir = params.root_uri.and_then(|url| url.to_file_path().ok());
+    let mut state = LanguageServerState::new(source_dir);
+
+    for msg in &connection.receiver {
+        match msg {
+            Message::Request(req) => {
+                if connection.handle_shutdown(&req)? {
+                    return Ok(());
+                }
+                let response = handle_request(&state, req);
+                connection.sender.send(Message::Response(response))?;
+            }
+            Message::Notification(not) => handle_notification(&connection, &mut state, not)?,
+            Message::Response(_) => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Computes the response to the request `req`.
+fn handle_request(state: &LanguageServerState, req: Request) -> Response {
+    let req = match dispatch::<GotoDefinition>(req, |params| goto_definition(state, params)) {
+        Ok(response) => return response,
+        Err(req) => req,
+    };
+    let req = match dispatch::<HoverRequest>(req, |params| hover(state, params)) {
+        Ok(response) => return response,
+        Err(req) => req,
+    };
+    let req = match dispatch::<Completion>(req, |params| completion(state, params)) {
+        Ok(response) => return response,
+        Err(req) => req,
+    };
+    Response::new_err(
+        req.id,
+        ErrorCode::MethodNotFound as i32,
+        format!("unknown request: {}", req.method),
+    )
+}
+
+/// Calls `f` with the parameters of `req` if it is a request of type `R`, otherwise the request is
+/// returned.
+fn dispatch<R>(req: Request, f: impl FnOnce(R::Params) -> R::Result) -> Result<Response, Request>
+where
+    R: lsp_types::request::Request,
+    R::Params: DeserializeOwned,
+    R::Result: Serialize,
+{
+    let (id, params) = req.extract::<R::Params>(R::METHOD)?;
+    Ok(Response::new_ok(id, f(params)))
+}
+
+/// Applies the changes of the notification `not` to `state`. When the contents of a document
+/// change, the diagnostics of all opened files are published again.
+fn handle_notification(
+    connection: &Connection,
+    state: &mut LanguageServerState,
+    not: Notification,
+) -> Result<(), failure::Error> {
+    let not = match extract::<DidOpenTextDocument>(not) {
+        Ok(params) => {
+            state.open_file(params.text_document.uri, params.text_document.text);
+            return publish_diagnostics(connection, state);
+        }
+        Err(not) => not,
+    };
+    let not = match extract::<DidChangeTextDocument>(not) {
+        Ok(params) => {
+            // The entire text of the document is sent on every change
+            if let Some(change) = params.content_changes.into_iter().last() {
+                state.set_file_text(params.text_document.uri, change.text);
+            }
+            return publish_diagnostics(connection, state);
+        }
+        Err(not) => not,
+    };
+    if let Ok(params) = extract::<DidCloseTextDocument>(not) {
+        if let Some(file_id) = state.close_file(&params.text_document.uri) {
+            // The client keeps showing the diagnostics of a closed file until they are cleared
+            let params = PublishDiagnosticsParams {
+                uri: state.url(file_id).clone(),
+                diagnostics: Vec::new(),
+                version: None,
+            };
+            send_notification::<PublishDiagnostics>(connection, params)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the parameters of `not` if it is a notification of type `N`, otherwise the notification
+/// is returned.
+fn extract<N>(not: Notification) -> Result<N::Params, Notification>
+where
+    N: lsp_types::notification::Notification,
+    N::Params: DeserializeOwned,
+{
+    not.extract(N::METHOD)
+}
+
+/// Publishes the diagnostics of all files that are opened in the client.
+fn publish_diagnostics(
+    connection: &Connection,
+    state: &LanguageServerState,
+) -> Result<(), failure::Error> {
+    for file_id in state.open_files() {
+        let params = PublishDiagnosticsParams {
+            uri: state.url(file_id).clone(),
+            diagnostics: diagnostics(&state.db, file_id),
+            version: None,
+        };
+        send_notification::<PublishDiagnostics>(connection, params)?;
+    }
+    Ok(())
+}
+
+/// Sends a notification of type `N` with `params` to the client.
+fn send_notification<N>(connection: &Connection, params: N::Params) -> Result<(), failure::Error>
+where
+    N: lsp_types::notification::Notification,
+    N::Params: Serialize,
+{
+    let not = Notification::new(N::METHOD.to_string(), params);
+    connection.sender.send(Message::Notification(not))?;
+    Ok(())
+}
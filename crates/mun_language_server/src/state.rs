@@ -0,0 +1,180 @@
+use crate::conversion::{convert_position, convert_range};
+use crate::db::AnalysisDatabase;
+use lsp_types::{Location, TextDocumentPositionParams, Url};
+use mun_hir::line_index::LineIndex;
+use mun_hir::{FileId, InFile, RelativePathBuf, SourceDatabase, SourceRoot, SourceRootId};
+use mun_syntax::{TextRange, TextUnit};
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The source root that contains all files of the workspace
+const WORKSPACE: SourceRootId = SourceRootId(0);
+
+/// The name of the file that is the root module of the package, if it exists
+const PACKAGE_ROOT_FILE: &str = "main.mun";
+
+/// The state of the language server: the semantic model of the source files and the documents that
+/// are opened by the client.
+#[derive(Debug)]
+pub(crate) struct LanguageServerState {
+    pub db: AnalysisDatabase,
+    /// The directory that contains the source files of the workspace, if the client opened one
+    source_dir: Option<PathBuf>,
+    file_ids: HashMap<Url, FileId>,
+    urls: Vec<Url>,
+    /// The files that are opened in the client, for which diagnostics are published
+    open_files: BTreeSet<FileId>,
+    package_root: Option<FileId>,
+}
+
+impl LanguageServerState {
+    /// Constructs the state of a workspace in `source_dir`. All Mun files in the directory and its
+    /// subdirectories are loaded from disk.
+    pub fn new(source_dir: Option<PathBuf>) -> Self {
+        let mut state = LanguageServerState {
+            db: AnalysisDatabase::new(),
+            source_dir: None,
+            file_ids: HashMap::new(),
+            urls: Vec::new(),
+            open_files: BTreeSet::new(),
+            package_root: None,
+        };
+        state
+            .db
+            .set_source_root(WORKSPACE, Arc::new(SourceRoot::default()));
+
+        if let Some(source_dir) = source_dir {
+            let mut paths = Vec::new();
+            find_source_files(&source_dir, &mut paths);
+            paths.sort();
+            state.source_dir = Some(source_dir);
+
+            for path in paths {
+                // Files that cannot be read are loaded once the client opens them
+                if let (Ok(url), Ok(text)) =
+                    (Url::from_file_path(&path), std::fs::read_to_string(&path))
+                {
+                    state.set_file_text(url, text);
+                }
+            }
+        }
+
+        state
+    }
+
+    /// Returns the id of the file at `url`, if it is part of the workspace.
+    pub fn file_id(&self, url: &Url) -> Option<FileId> {
+        self.file_ids.get(url).copied()
+    }
+
+    /// Returns the url of the file `file_id`.
+    pub fn url(&self, file_id: FileId) -> &Url {
+        &self.urls[file_id.0 as usize]
+    }
+
+    /// Returns the line index of the file `file_id`.
+    pub fn line_index(&self, file_id: FileId) -> Arc<LineIndex> {
+        self.db.line_index(file_id)
+    }
+
+    /// Returns the file and the offset in that file of the protocol position `position`.
+    pub fn file_position(
+        &self,
+        position: &TextDocumentPositionParams,
+    ) -> Option<(FileId, TextUnit)> {
+        let file_id = self.file_id(&position.text_document.uri)?;
+        let offset = convert_position(&self.line_index(file_id), position.position)?;
+        Some((file_id, offset))
+    }
+
+    /// Converts the range `range` to a protocol location.
+    pub fn location(&self, range: InFile<TextRange>) -> Location {
+        Location::new(
+            self.url(range.file_id).clone(),
+            convert_range(&self.line_index(range.file_id), range.value),
+        )
+    }
+
+    /// Returns the files that are opened in the client.
+    pub fn open_files(&self) -> impl Iterator<Item = FileId> + '_ {
+        self.open_files.iter().copied()
+    }
+
+    /// Marks the file at `url` as opened in the client with the contents `text`.
+    pub fn open_file(&mut self, url: Url, text: String) -> FileId {
+        let file_id = self.set_file_text(url, text);
+        self.open_files.insert(file_id);
+        file_id
+    }
+
+    /// Marks the file at `url` as closed in the client.
+    pub fn close_file(&mut self, url: &Url) -> Option<FileId> {
+        let file_id = self.file_id(url)?;
+        self.open_files.remove(&file_id);
+        Some(file_id)
+    }
+
+    /// Sets the contents of the file at `url`, which is added to the workspace if it is not yet
+    /// part of it.
+    pub fn set_file_text(&mut self, url: Url, text: String) -> FileId {
+        if let Some(file_id) = self.file_id(&url) {
+            self.db.set_file_text(file_id, Arc::new(text));
+            return file_id;
+        }
+
+        let file_id = FileId(self.urls.len() as u32);
+        let rel_path = self.relative_path(&url);
+        self.db.set_file_relative_path(file_id, rel_path.clone());
+        self.db.set_file_text(file_id, Arc::new(text));
+        self.db.set_file_source_root(file_id, WORKSPACE);
+
+        let mut source_root = (*self.db.source_root(WORKSPACE)).clone();
+        source_root.insert_file(rel_path.clone(), file_id);
+        self.db.set_source_root(WORKSPACE, Arc::new(source_root));
+
+        // The first file is the root of the package, unless the workspace contains a `main.mun`
+        if self.package_root.is_none() || rel_path.as_str() == PACKAGE_ROOT_FILE {
+            self.db.set_package_root(WORKSPACE, file_id);
+            self.package_root = Some(file_id);
+        }
+
+        self.file_ids.insert(url.clone(), file_id);
+        self.urls.push(url);
+        file_id
+    }
+
+    /// Returns the path of the file at `url`, relative to the source directory. A file outside of
+    /// the source directory is placed in the root of the package.
+    fn relative_path(&self, url: &Url) -> RelativePathBuf {
+        let path = url.to_file_path().ok();
+        path.as_ref()
+            .and_then(|path| path.strip_prefix(self.source_dir.as_ref()?).ok())
+            .and_then(|path| RelativePathBuf::from_path(path).ok())
+            .unwrap_or_else(|| {
+                let file_name = url
+                    .path_segments()
+                    .and_then(|mut segments| segments.next_back())
+                    .unwrap_or(PACKAGE_ROOT_FILE);
+                RelativePathBuf::from(file_name)
+            })
+    }
+}
+
+/// Adds the paths of all Mun files in `dir` and its subdirectories to `paths`.
+fn find_source_files(dir: &Path, paths: &mut Vec<PathBuf>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        if path.is_dir() {
+            find_source_files(&path, paths);
+        } else if path.extension().map_or(false, |ext| ext == "mun") {
+            paths.push(path);
+        }
+    }
+}
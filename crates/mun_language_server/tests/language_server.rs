@@ -0,0 +1,310 @@
+use lsp_types::{
+    CompletionItemKind, CompletionResponse, Diagnostic, GotoDefinitionResponse, Hover,
+    HoverContents, Location, MarkupContent, Position, PublishDiagnosticsParams, Range, Url,
+};
+use mun_language_server::Connection;
+use serde_json::{json, Value};
+use std::{collections::VecDeque, path::Path, thread::JoinHandle, time::Duration};
+
+/// The time to wait for a message of the language server before a test fails
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An in-process client of a language server that runs on a separate thread.
+struct Server {
+    client: Connection,
+    thread: Option<JoinHandle<()>>,
+    next_request_id: u64,
+    /// Notifications that were received while waiting for the response to a request
+    notifications: VecDeque<lsp_server::Notification>,
+}
+
+impl Server {
+    /// Starts a language server and initializes it with the workspace in `root`.
+    fn new(root: Option<&Path>) -> Server {
+        let (client, connection) = Connection::memory();
+        let thread = std::thread::spawn(move || mun_language_server::run(connection).unwrap());
+        let mut server = Server {
+            client,
+            thread: Some(thread),
+            next_request_id: 0,
+            notifications: VecDeque::new(),
+        };
+
+        let root_uri = root.map(|root| Url::from_directory_path(root).unwrap());
+        server.request(
+            "initialize",
+            json!({
+                "processId": null,
+                "rootUri": root_uri,
+                "capabilities": {},
+            }),
+        );
+        server.notify("initialized", json!({}));
+        server
+    }
+
+    /// Sends a request to the language server and returns its result.
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.next_request_id += 1;
+        let id: lsp_server::RequestId = self.next_request_id.into();
+        let request = lsp_server::Request::new(id.clone(), method.to_string(), params);
+        self.client
+            .sender
+            .send(lsp_server::Message::Request(request))
+            .unwrap();
+
+        loop {
+            match self.client.receiver.recv_timeout(TIMEOUT).unwrap() {
+                lsp_server::Message::Response(response) if response.id == id => {
+                    assert!(response.error.is_none(), "{:?}", response.error);
+                    return response.result.unwrap_or(Value::Null);
+                }
+                lsp_server::Message::Notification(not) => self.notifications.push_back(not),
+                msg => panic!("unexpected message: {:?}", msg),
+            }
+        }
+    }
+
+    /// Sends a notification to the language server.
+    fn notify(&self, method: &str, params: Value) {
+        let not = lsp_server::Notification::new(method.to_string(), params);
+        self.client
+            .sender
+            .send(lsp_server::Message::Notification(not))
+            .unwrap();
+    }
+
+    /// Opens the document at `url` with the contents `text`.
+    fn open(&self, url: &Url, text: &str) {
+        self.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": {
+                    "uri": url,
+                    "languageId": "mun",
+                    "version": 0,
+                    "text": text,
+                }
+            }),
+        );
+    }
+
+    /// Replaces the contents of the document at `url` by `text`.
+    fn change(&self, url: &Url, text: &str) {
+        self.notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": url, "version": 1 },
+                "contentChanges": [{ "text": text }],
+            }),
+        );
+    }
+
+    /// Waits for the next diagnostics that are published for the document at `url`.
+    fn diagnostics(&mut self, url: &Url) -> Vec<Diagnostic> {
+        loop {
+            let not = match self.notifications.pop_front() {
+                Some(not) => not,
+                None => match self.client.receiver.recv_timeout(TIMEOUT).unwrap() {
+                    lsp_server::Message::Notification(not) => not,
+                    msg => panic!("unexpected message: {:?}", msg),
+                },
+            };
+            if not.method == "textDocument/publishDiagnostics" {
+                let params: PublishDiagnosticsParams = serde_json::from_value(not.params).unwrap();
+                if params.uri == *url {
+                    return params.diagnostics;
+                }
+            }
+        }
+    }
+
+    /// Sends a request for `method` at `position` in the document at `url`.
+    fn position_request(&mut self, method: &str, url: &Url, position: Position) -> Value {
+        self.request(
+            method,
+            json!({
+                "textDocument": { "uri": url },
+                "position": position,
+            }),
+        )
+    }
+
+    fn goto_definition(&mut self, url: &Url, position: Position) -> Option<Location> {
+        let result = self.position_request("textDocument/definition", url, position);
+        match serde_json::from_value(result).unwrap() {
+            Some(GotoDefinitionResponse::Scalar(location)) => Some(location),
+            None => None,
+            Some(response) => panic!("unexpected response: {:?}", response),
+        }
+    }
+
+    fn hover(&mut self, url: &Url, position: Position) -> Option<String> {
+        let result = self.position_request("textDocument/hover", url, position);
+        let hover: Option<Hover> = serde_json::from_value(result).unwrap();
+        hover.map(|hover| match hover.contents {
+            HoverContents::Markup(MarkupContent { value, .. }) => value,
+            contents => panic!("unexpected contents: {:?}", contents),
+        })
+    }
+
+    fn completion(&mut self, url: &Url, position: Position) -> Vec<(String, CompletionItemKind)> {
+        let result = self.position_request("textDocument/completion", url, position);
+        match serde_json::from_value(result).unwrap() {
+            Some(CompletionResponse::Array(items)) => items
+                .into_iter()
+                .map(|item| (item.label, item.kind.unwrap()))
+                .collect(),
+            None => Vec::new(),
+            Some(response) => panic!("unexpected response: {:?}", response),
+        }
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        // Don't obscure the message of a failed test with a failing shutdown
+        if std::thread::panicking() {
+            return;
+        }
+        self.request("shutdown", Value::Null);
+        self.notify("exit", Value::Null);
+        self.thread.take().unwrap().join().unwrap();
+    }
+}
+
+fn range(start: (u64, u64), end: (u64, u64)) -> Range {
+    Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1))
+}
+
+const SOURCE: &str = r#"struct Foo {
+    a: i32,
+}
+fn bar(foo: Foo) -> i32 {
+    foo.a
+}
+fn main() {
+    let x = bar(Foo { a: 1 });
+}
+"#;
+
+fn main_url() -> Url {
+    Url::parse("untitled:main.mun").unwrap()
+}
+
+#[test]
+fn publish_diagnostics() {
+    let mut server = Server::new(None);
+    let url = main_url();
+
+    server.open(&url, "fn main() {\n    let a: i32 = b;\n}\n");
+    let diagnostics = server.diagnostics(&url);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message, "undefined value");
+    assert_eq!(diagnostics[0].range, range((1, 17), (1, 18)));
+
+    server.change(&url, "fn main() {\n    let a: i32 = 5;\n}\n");
+    assert_eq!(server.diagnostics(&url), Vec::new());
+
+    server.change(&url, "fn main() {\n    let a: i32 = 5\n");
+    assert!(!server.diagnostics(&url).is_empty());
+}
+
+#[test]
+fn goto_definition() {
+    let mut server = Server::new(None);
+    let url = main_url();
+    server.open(&url, SOURCE);
+
+    // A function
+    let location = server.goto_definition(&url, Position::new(7, 13)).unwrap();
+    assert_eq!(location.uri, url);
+    assert_eq!(location.range, range((3, 3), (3, 6)));
+
+    // A local binding
+    let location = server.goto_definition(&url, Position::new(4, 5)).unwrap();
+    assert_eq!(location.range, range((3, 7), (3, 10)));
+
+    // A struct field
+    let location = server.goto_definition(&url, Position::new(4, 8)).unwrap();
+    assert_eq!(location.range, range((1, 4), (1, 5)));
+
+    // A struct
+    let location = server.goto_definition(&url, Position::new(3, 13)).unwrap();
+    assert_eq!(location.range, range((0, 7), (0, 10)));
+
+    // A keyword
+    assert_eq!(server.goto_definition(&url, Position::new(6, 1)), None);
+}
+
+#[test]
+fn goto_definition_in_other_module() {
+    let dir = tempfile::tempdir().unwrap();
+    let main_text = "mod foo;\nfn main() -> i32 {\n    foo::value()\n}\n";
+    std::fs::write(dir.path().join("main.mun"), main_text).unwrap();
+    std::fs::write(
+        dir.path().join("foo.mun"),
+        "pub fn value() -> i32 {\n    3\n}\n",
+    )
+    .unwrap();
+
+    let mut server = Server::new(Some(dir.path()));
+    let main_url = Url::from_file_path(dir.path().join("main.mun")).unwrap();
+    server.open(&main_url, main_text);
+    assert_eq!(server.diagnostics(&main_url), Vec::new());
+
+    let location = server
+        .goto_definition(&main_url, Position::new(2, 10))
+        .unwrap();
+    assert_eq!(
+        location.uri,
+        Url::from_file_path(dir.path().join("foo.mun")).unwrap()
+    );
+    assert_eq!(location.range, range((0, 7), (0, 12)));
+}
+
+#[test]
+fn hover() {
+    let mut server = Server::new(None);
+    let url = main_url();
+    server.open(&url, SOURCE);
+
+    assert_eq!(
+        server.hover(&url, Position::new(7, 8)),
+        Some("```mun\ni32\n```".to_string())
+    );
+    assert_eq!(
+        server.hover(&url, Position::new(4, 5)),
+        Some("```mun\nFoo\n```".to_string())
+    );
+    assert_eq!(server.hover(&url, Position::new(0, 0)), None);
+}
+
+#[test]
+fn complete_fields() {
+    let mut server = Server::new(None);
+    let url = main_url();
+    server.open(&url, SOURCE);
+
+    assert_eq!(
+        server.completion(&url, Position::new(4, 9)),
+        vec![("a".to_string(), CompletionItemKind::Field)]
+    );
+}
+
+#[test]
+fn complete_names() {
+    let mut server = Server::new(None);
+    let url = main_url();
+    server.open(&url, SOURCE);
+
+    let items = server.completion(&url, Position::new(4, 4));
+    assert!(items.contains(&("foo".to_string(), CompletionItemKind::Variable)));
+    assert!(items.contains(&("bar".to_string(), CompletionItemKind::Function)));
+    assert!(items.contains(&("main".to_string(), CompletionItemKind::Function)));
+    assert!(items.contains(&("Foo".to_string(), CompletionItemKind::Struct)));
+
+    // A local binding is only in scope after its declaration
+    let items = server.completion(&url, Position::new(7, 4));
+    assert!(!items.iter().any(|(label, _)| label == "x"));
+}
@@ -6,6 +6,9 @@ use crate::{TypeDesc, TypeFields};
 pub enum FieldEditKind {
     ConvertType,
     Rename,
+    /// The field was renamed and its type was converted. This is only possible for fields with a
+    /// declared previous name.
+    RenameAndConvertType,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -80,27 +83,29 @@ where
     let mut mapping: Vec<Diff> = Vec::with_capacity(diff.len());
     let (deletions, insertions) = myers::split_diff(&diff);
 
-    // ASSUMPTION: `FundamentalTypes` can never be converted to `StructTypes`, hence they can be
-    // compared separately.
+    // ASSUMPTION: `FundamentalTypes` and `ArrayTypes` can never be converted to `StructTypes` or
+    // `EnumTypes`, hence they can be compared separately. Arrays are either identical or replaced,
+    // just like fundamental types.
+    let is_adt = |group: abi::TypeGroup| group.is_struct() || group.is_enum();
     let deleted_fundamentals = deletions
         .iter()
-        .filter(|idx| unsafe { old.get_unchecked(**idx) }.group().is_fundamental())
+        .filter(|idx| !is_adt(unsafe { old.get_unchecked(**idx) }.group()))
         .cloned()
         .collect();
     let deleted_structs = deletions
         .iter()
-        .filter(|idx| unsafe { old.get_unchecked(**idx) }.group().is_struct())
+        .filter(|idx| is_adt(unsafe { old.get_unchecked(**idx) }.group()))
         .cloned()
         .collect();
 
     let inserted_fundamentals = insertions
         .iter()
-        .filter(|idx| unsafe { new.get_unchecked(**idx) }.group().is_fundamental())
+        .filter(|idx| !is_adt(unsafe { new.get_unchecked(**idx) }.group()))
         .cloned()
         .collect();
     let inserted_structs = insertions
         .iter()
-        .filter(|idx| unsafe { new.get_unchecked(**idx) }.group().is_struct())
+        .filter(|idx| is_adt(unsafe { new.get_unchecked(**idx) }.group()))
         .cloned()
         .collect();
 
@@ -161,6 +166,29 @@ fn append_fundamental_mapping<T>(
     }
 }
 
+/// Calculates the number of changes required to convert the fields of type `old` to the fields of
+/// type `new`. For enums, the fields of all variants are compared.
+fn adt_diff_length<T>(old: &T, new: &T) -> usize
+where
+    T: Eq + TypeDesc + TypeFields<T>,
+{
+    if old.group().is_enum() {
+        let old_variants: Vec<_> = old
+            .variants()
+            .into_iter()
+            .map(|(name, fields, _)| (name, fields))
+            .collect();
+        let new_variants: Vec<_> = new
+            .variants()
+            .into_iter()
+            .map(|(name, fields, _)| (name, fields))
+            .collect();
+        myers::diff_length(&old_variants, &new_variants)
+    } else {
+        myers::diff_length(&old.fields(), &new.fields())
+    }
+}
+
 /// Given a set of indices for `deletions` from the `old` slice of types `T` and a set of indices
 /// for `insertions` into the `new` slice of types `T`, appends the corresponding `Diff` mapping
 /// for all
@@ -180,13 +208,17 @@ fn append_struct_mapping<T>(
         .iter()
         .flat_map(|new_idx| {
             let new_ty = unsafe { new.get_unchecked(*new_idx) };
-            let new_fields = new_ty.fields();
             deletions
                 .iter()
                 .map(|old_idx| {
                     let old_ty = unsafe { old.get_unchecked(*old_idx) };
-                    let old_fields = old_ty.fields();
-                    let length = myers::diff_length(&old_fields, &new_fields);
+
+                    // Structs and enums cannot be converted into one another
+                    if old_ty.group() != new_ty.group() {
+                        return std::usize::MAX;
+                    }
+
+                    let length = adt_diff_length(old_ty, new_ty);
 
                     // Given N old fields and M new fields, the smallest set capable of
                     // completely changing a struct is N + M.
@@ -242,7 +274,12 @@ fn append_struct_mapping<T>(
             // ASSUMPTION: Don't use recursion, because all types are individually checked for
             // differences.
             // TODO: Support value struct vs heap struct?
-            let diff = field_diff(&old_ty.fields(), &new_ty.fields());
+            // The fields of an enum's variants are diffed per variant, when mapping its memory.
+            let diff = if old_ty.group().is_enum() {
+                Vec::new()
+            } else {
+                field_diff(&old_ty.fields(), &new_ty.fields(), &new_ty.renamed_from())
+            };
 
             // Edit the struct, potentially moving it in the process.
             Diff::Edit {
@@ -285,17 +322,56 @@ fn append_struct_mapping<T>(
     }
 }
 
-/// Given an `old` and a `new` set of fields, calculates the difference.
-fn field_diff<T>(old: &[(&str, T)], new: &[(&str, T)]) -> Vec<FieldDiff>
+/// Given an `old` and a `new` set of fields, calculates the difference. The `renamed_from` names
+/// of the new fields are authoritative; the remaining renames are guessed.
+pub fn field_diff<T>(
+    old: &[(&str, T)],
+    new: &[(&str, T)],
+    renamed_from: &[Option<&str>],
+) -> Vec<FieldDiff>
 where
     T: Eq,
 {
     let diff = myers::diff(old, new);
-    let (deletions, insertions) = myers::split_diff(&diff);
+    let (mut deletions, insertions) = myers::split_diff(&diff);
     let mut insertions: Vec<Option<usize>> = insertions.into_iter().map(Some).collect();
 
     let mut mapping = Vec::with_capacity(diff.len());
-    // For all deletions,
+    // First, match all deletions that were explicitly renamed to an insertion.
+    deletions.retain(|old_idx| {
+        let old_field = unsafe { old.get_unchecked(*old_idx) };
+        let insertion = insertions.iter_mut().find(|insertion| {
+            insertion.map_or(false, |new_idx| {
+                renamed_from.get(new_idx) == Some(&Some(old_field.0))
+            })
+        });
+        if let Some(insertion) = insertion {
+            let new_idx = insertion.take().unwrap();
+            let new_field = unsafe { new.get_unchecked(new_idx) };
+            let kind = if old_field.1 == new_field.1 {
+                FieldEditKind::Rename
+            } else {
+                FieldEditKind::RenameAndConvertType
+            };
+            mapping.push(if *old_idx == new_idx {
+                FieldDiff::Edit {
+                    index: new_idx,
+                    kind,
+                }
+            } else {
+                FieldDiff::Move {
+                    old_index: *old_idx,
+                    new_index: new_idx,
+                    edit: Some(kind),
+                }
+            });
+            false
+        } else {
+            true
+        }
+    });
+
+    // For all remaining deletions,
     'outer: for old_idx in deletions {
         let old_ty = unsafe { old.get_unchecked(old_idx) };
         // is there an insertion with the same name and type `T`?
@@ -339,13 +415,15 @@ where
                 }
             }
         }
-        // Else, is there an insertion with a different name but same type `T`?
+        // Else, is there an insertion with a different name but same type `T`? Insertions that
+        // declare their previous name are never guessed to be renamed from another field.
         // As there can be multiple fields with the same type `T`, we want to find the closest one.
         let mut closest = None;
         for (insert_idx, insertion) in insertions.iter_mut().enumerate() {
             if let Some(new_idx) = insertion {
                 let new_ty = unsafe { new.get_unchecked(*new_idx) };
-                if old_ty.1 == new_ty.1 {
+                let is_renamed = renamed_from.get(*new_idx).map_or(false, Option::is_some);
+                if old_ty.1 == new_ty.1 && !is_renamed {
                     let diff = old_idx.max(*new_idx) - old_idx.min(*new_idx);
                     // If so, select the closest candidate.
                     if let Some((closest_idx, closest_diff)) = &mut closest {
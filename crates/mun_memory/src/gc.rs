@@ -1,18 +1,92 @@
+mod array;
+mod generational;
 mod mark_sweep;
 mod ptr;
 mod root_ptr;
+mod snapshot;
 
 use crate::TypeMemory;
-use std::marker::PhantomData;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::{marker::PhantomData, time::Duration};
 
+pub use array::{array_layout, ArrayHeader};
+pub use generational::{Generational, DEFAULT_NURSERY_SIZE};
 pub use mark_sweep::MarkSweep;
 pub use ptr::{GcPtr, HasIndirectionPtr, RawGcPtr};
 pub use root_ptr::GcRootPtr;
+pub use snapshot::{HeapSnapshot, ObjectSnapshot, TypeStats};
 
 /// Contains stats about the current state of a GC implementation
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct Stats {
+    /// The number of bytes that are allocated for objects
     pub allocated_memory: usize,
+    /// The number of allocated objects
+    pub allocated_objects: usize,
+    /// The number of collections that were performed
+    pub collections: usize,
+    /// The duration of the last collection
+    pub last_pause: Duration,
+    /// The duration of the longest collection
+    pub max_pause: Duration,
+    /// The combined duration of all collections
+    pub total_pause: Duration,
+    /// The number of bytes that were freed by the last collection
+    pub last_freed_memory: usize,
+    /// The number of bytes that were freed by all collections
+    pub total_freed_memory: usize,
+    /// The number of bytes that were allocated since the last collection
+    pub allocated_since_collection: usize,
+    /// The number of bytes that were still allocated after the last collection
+    pub memory_after_collection: usize,
+}
+
+impl Stats {
+    /// Records an allocation of `size` bytes, either for a new object or to grow an existing one.
+    fn log_alloc(&mut self, size: usize) {
+        self.allocated_memory += size;
+        self.allocated_since_collection += size;
+    }
+
+    /// Records a collection that took `pause` and freed `freed_memory` bytes.
+    fn log_collection(&mut self, pause: Duration, freed_memory: usize) {
+        self.collections += 1;
+        self.last_pause = pause;
+        self.max_pause = self.max_pause.max(pause);
+        self.total_pause += pause;
+        self.last_freed_memory = freed_memory;
+        self.total_freed_memory += freed_memory;
+        self.allocated_since_collection = 0;
+        self.memory_after_collection = self.allocated_memory;
+    }
+}
+
+/// Determines when a garbage collector requests a collection. A collector never collects on its own
+/// initiative, because objects that are only referenced from the stack of a Mun function are not
+/// rooted; the owner of the collector has to collect when it knows that no Mun code is running.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TriggerPolicy {
+    /// Requests a collection once this many bytes were allocated since the last collection
+    pub allocation_threshold: Option<usize>,
+    /// Requests a collection once the allocated memory has grown by this factor since the last
+    /// collection. Growth is not measured while no memory survived the last collection, e.g.
+    /// before the first collection.
+    pub growth_factor: Option<f64>,
+}
+
+impl TriggerPolicy {
+    /// Returns whether a collector with the specified `stats` should request a collection.
+    pub fn is_triggered(&self, stats: &Stats) -> bool {
+        let threshold_reached = self.allocation_threshold.map_or(false, |threshold| {
+            stats.allocated_since_collection >= threshold
+        });
+        let grown = self.growth_factor.map_or(false, |factor| {
+            stats.memory_after_collection > 0
+                && stats.allocated_memory as f64 >= stats.memory_after_collection as f64 * factor
+        });
+        threshold_reached || grown
+    }
 }
 
 /// A trait used to trace an object type.
@@ -28,6 +102,14 @@ pub trait GcRuntime<T: TypeMemory + TypeTrace>: Send + Sync {
     /// Allocates an object of the given type returning a GcPtr
     fn alloc(&self, ty: T) -> GcPtr;
 
+    /// Allocates a dynamic array of the given type with room for `capacity` elements, returning a
+    /// GcPtr. The length of the array is initialized to zero.
+    fn alloc_array(&self, ty: T, capacity: usize) -> GcPtr;
+
+    /// Ensures that the dynamic array `obj` has room for at least `capacity` elements. The memory
+    /// of the array might be reallocated, but `obj` remains valid.
+    fn reserve_array(&self, obj: GcPtr, capacity: usize);
+
     /// Returns the type of the specified `obj`.
     fn ptr_type(&self, obj: GcPtr) -> T;
 
@@ -42,6 +124,11 @@ pub trait GcRuntime<T: TypeMemory + TypeTrace>: Send + Sync {
     /// the same number of times as `root` was called before the object can be collected.
     fn unroot(&self, obj: GcPtr);
 
+    /// Notifies the runtime that a reference to another object might have been stored in the
+    /// memory of `obj`. This must be called after every such store, unless `obj` was allocated
+    /// after the last collection. Runtimes that do not need to track these stores ignore it.
+    fn write_barrier(&self, _obj: GcPtr) {}
+
     /// Returns stats about the current state of the runtime.
     fn stats(&self) -> Stats;
 }
@@ -84,3 +171,31 @@ impl<T: Send + Sync> Default for NoopObserver<T> {
         NoopObserver { data: PhantomData }
     }
 }
+
+/// An implementation of an `Observer` that stores all events, so they can be verified.
+pub struct EventAggregator<T: Send + Sync> {
+    events: Mutex<Vec<T>>,
+}
+
+impl<T: Send + Sync> Default for EventAggregator<T> {
+    fn default() -> Self {
+        EventAggregator {
+            events: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl<T: Send + Sync> EventAggregator<T> {
+    /// Removes and returns all events that were received so far.
+    pub fn take_all(&self) -> Vec<T> {
+        self.events.lock().drain(..).collect()
+    }
+}
+
+impl<T: Send + Sync> Observer for EventAggregator<T> {
+    type Event = T;
+
+    fn event(&self, event: T) {
+        self.events.lock().push(event)
+    }
+}
@@ -0,0 +1,30 @@
+use std::alloc::Layout;
+
+/// The header of a heap-allocated dynamic array. The elements of the array are stored directly
+/// after the header, at the offset returned by [`array_layout`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct ArrayHeader {
+    /// The number of initialized elements
+    pub length: usize,
+    /// The number of elements for which memory has been allocated
+    pub capacity: usize,
+}
+
+/// Returns the memory layout of a dynamic array that can hold `capacity` elements of the specified
+/// `element` layout, together with the offset of the first element relative to the start of the
+/// array's header.
+pub fn array_layout(element: Layout, capacity: usize) -> (Layout, usize) {
+    let element = element.pad_to_align();
+    let elements = element
+        .size()
+        .checked_mul(capacity)
+        .and_then(|size| Layout::from_size_align(size, element.align()).ok())
+        .expect("array capacity overflow");
+
+    let (layout, offset) = Layout::new::<ArrayHeader>()
+        .extend(elements)
+        .expect("array capacity overflow");
+
+    (layout.pad_to_align(), offset)
+}
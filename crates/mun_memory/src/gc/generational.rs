@@ -0,0 +1,676 @@
+use crate::{
+    gc::{
+        array_layout,
+        mark_sweep::{map_objects, object_layout, Color, ObjectInfo},
+        ArrayHeader, Event, GcPtr, GcRuntime, HeapSnapshot, Observer, RawGcPtr, Stats,
+        TriggerPolicy, TypeTrace,
+    },
+    mapping::{Mapping, MemoryMapper},
+    TypeDesc, TypeFields, TypeMemory,
+};
+use parking_lot::RwLock;
+use std::{
+    alloc::Layout,
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    ops::Deref,
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+/// The default size of the nursery of a `Generational` collector, in bytes
+pub const DEFAULT_NURSERY_SIZE: usize = 1024 * 1024;
+
+/// The alignment of the start of the nursery
+const NURSERY_ALIGN: usize = 16;
+
+/// The number of objects that are traced between two checks of the time budget of an incremental
+/// collection
+const MARK_BATCH_SIZE: usize = 64;
+
+/// Implements a generational garbage collector. New objects are bump-allocated in a nursery and
+/// objects that survive a minor collection are moved to an old generation, which is collected by
+/// an incremental mark-sweep collector.
+///
+/// Like `MarkSweep`, collections only happen when they are requested. Every store of a reference
+/// into a garbage collected object has to be followed by a call to `GcRuntime::write_barrier`,
+/// which allows the collector to find references from the old generation to young objects and
+/// references that were stored in objects that have already been marked.
+#[derive(Debug)]
+pub struct Generational<T, O>
+where
+    T: TypeMemory + TypeTrace + Clone,
+    O: Observer<Event = Event>,
+{
+    heap: RwLock<Heap<T>>,
+    observer: O,
+    stats: RwLock<Stats>,
+    trigger_policy: TriggerPolicy,
+    /// Whether the `trigger_policy` requested a collection since the last collection
+    collection_requested: AtomicBool,
+}
+
+/// The objects of a `Generational` collector and the state of its collections.
+#[derive(Debug)]
+struct Heap<T: TypeMemory + TypeTrace + Clone> {
+    /// All objects, both young and old
+    objects: HashMap<GcPtr, Pin<Box<ObjectInfo<T>>>>,
+    /// The memory in which new objects are allocated
+    nursery: Nursery,
+    /// The objects that were allocated since the last minor collection. Their memory is located in
+    /// the nursery, unless it did not fit.
+    young: HashSet<GcPtr>,
+    /// The old objects that were written to since the last minor collection and might reference
+    /// young objects
+    remembered: HashSet<GcPtr>,
+    /// Whether the old generation is being marked
+    marking: bool,
+    /// The old objects that have been reached by the mark phase, but whose references have not
+    /// been traced yet
+    gray: Vec<GcPtr>,
+}
+
+impl<T, O> Default for Generational<T, O>
+where
+    T: TypeMemory + TypeTrace + Clone,
+    O: Observer<Event = Event> + Default,
+{
+    fn default() -> Self {
+        Generational::new(DEFAULT_NURSERY_SIZE, O::default())
+    }
+}
+
+impl<T, O> Generational<T, O>
+where
+    T: TypeMemory + TypeTrace + Clone,
+    O: Observer<Event = Event>,
+{
+    /// Creates a `Generational` memory collector with a nursery of `nursery_size` bytes and the
+    /// specified `Observer`.
+    pub fn new(nursery_size: usize, observer: O) -> Self {
+        Self {
+            heap: RwLock::new(Heap {
+                objects: HashMap::new(),
+                nursery: Nursery::new(nursery_size),
+                young: HashSet::new(),
+                remembered: HashSet::new(),
+                marking: false,
+                gray: Vec::new(),
+            }),
+            observer,
+            stats: RwLock::new(Stats::default()),
+            trigger_policy: TriggerPolicy::default(),
+            collection_requested: AtomicBool::new(false),
+        }
+    }
+
+    /// Creates a `Generational` memory collector with a nursery of `DEFAULT_NURSERY_SIZE` bytes
+    /// and the specified `Observer`.
+    pub fn with_observer(observer: O) -> Self {
+        Self::new(DEFAULT_NURSERY_SIZE, observer)
+    }
+
+    /// Sets the policy that determines when the collector requests a collection.
+    pub fn with_trigger_policy(mut self, trigger_policy: TriggerPolicy) -> Self {
+        self.trigger_policy = trigger_policy;
+        self
+    }
+
+    /// Returns whether the trigger policy requested a collection since the last collection. The
+    /// collector never collects on its own, so the owner has to call one of the collection methods
+    /// when it is safe.
+    pub fn collection_requested(&self) -> bool {
+        self.collection_requested.load(Ordering::Relaxed)
+    }
+
+    /// Returns the observer
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    /// Logs an allocation of `size` bytes
+    fn log_alloc(&self, handle: GcPtr, size: usize) {
+        {
+            let mut stats = self.stats.write();
+            stats.log_alloc(size);
+            stats.allocated_objects += 1;
+            self.request_collection(&stats);
+        }
+
+        self.observer.event(Event::Allocation(handle));
+    }
+
+    /// Requests a collection if the trigger policy is triggered by the `stats`.
+    fn request_collection(&self, stats: &Stats) {
+        if self.trigger_policy.is_triggered(stats) {
+            self.collection_requested.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Logs a deallocation of `size` bytes
+    fn log_dealloc(&self, handle: GcPtr, size: usize) {
+        {
+            let mut stats = self.stats.write();
+            stats.allocated_memory -= size;
+            stats.allocated_objects -= 1;
+        }
+
+        self.observer.event(Event::Deallocation(handle));
+    }
+
+    /// Logs a collection that started at `start`, when `allocated_memory` bytes were allocated.
+    /// Must be called while holding the lock of the heap, so no memory was allocated since.
+    fn log_collection(&self, start: Instant, allocated_memory: usize) {
+        let mut stats = self.stats.write();
+        let freed_memory = allocated_memory - stats.allocated_memory;
+        stats.log_collection(start.elapsed(), freed_memory);
+        self.collection_requested.store(false, Ordering::Relaxed);
+    }
+
+    /// Stores a newly allocated object, whose memory is located at `ptr`, in the young generation
+    /// and returns its handle.
+    fn insert(&self, heap: &mut Heap<T>, ptr: *mut u8, ty: T) -> GcPtr {
+        let object = Box::pin(ObjectInfo {
+            ptr,
+            ty,
+            roots: 0,
+            color: Color::White,
+        });
+
+        // We want to return a pointer to the `ObjectInfo`, to be used as handle.
+        let handle = (object.as_ref().deref() as *const _ as RawGcPtr).into();
+        let size = object_layout(&object.ty, object.ptr).size();
+
+        heap.objects.insert(handle, object);
+        heap.young.insert(handle);
+
+        self.log_alloc(handle, size);
+        handle
+    }
+
+    /// Collects the young objects that are no longer referenced and moves the remaining young
+    /// objects to the old generation. Returns `true` if memory was reclaimed, `false` otherwise.
+    pub fn collect_minor(&self) -> bool {
+        let start = Instant::now();
+        self.observer.event(Event::Start);
+
+        let mut heap = self.heap.write();
+        let allocated_memory = self.stats.read().allocated_memory;
+        let reclaimed = self.minor_collection(&mut heap);
+
+        self.log_collection(start, allocated_memory);
+        self.observer.event(Event::End);
+
+        reclaimed
+    }
+
+    /// Performs a minor collection and marks the old generation for at most `budget`. Marking
+    /// continues where the previous call left off; once the old generation has been marked
+    /// completely, its unreachable objects are collected. Returns `true` if memory was reclaimed,
+    /// `false` otherwise.
+    ///
+    /// Every call makes some progress, so a major collection finishes eventually, even if the
+    /// budget is exceeded by the minor collection.
+    pub fn collect_incremental(&self, budget: Duration) -> bool {
+        let start = Instant::now();
+        self.observer.event(Event::Start);
+
+        let mut heap = self.heap.write();
+        let allocated_memory = self.stats.read().allocated_memory;
+        let mut reclaimed = self.minor_collection(&mut heap);
+        if heap.mark(Some(start + budget)) {
+            reclaimed |= self.sweep(&mut heap);
+        }
+
+        self.log_collection(start, allocated_memory);
+        self.observer.event(Event::End);
+
+        reclaimed
+    }
+
+    /// Collects all memory that is no longer referenced by rooted objects, finishing the marking of
+    /// the old generation if it is in progress. Returns `true` if memory was reclaimed, `false`
+    /// otherwise.
+    pub fn collect(&self) -> bool {
+        let start = Instant::now();
+        self.observer.event(Event::Start);
+
+        let mut heap = self.heap.write();
+        let allocated_memory = self.stats.read().allocated_memory;
+        let mut reclaimed = self.minor_collection(&mut heap);
+        heap.mark(None);
+        reclaimed |= self.sweep(&mut heap);
+
+        self.log_collection(start, allocated_memory);
+        self.observer.event(Event::End);
+
+        reclaimed
+    }
+
+    /// Deallocates the young objects that are not reachable from rooted young objects or from
+    /// remembered old objects, and moves the others to the old generation.
+    fn minor_collection(&self, heap: &mut Heap<T>) -> bool {
+        let mut survivors = HashSet::new();
+        {
+            let Heap {
+                young, remembered, ..
+            } = &mut *heap;
+
+            let mut queue: Vec<GcPtr> = young
+                .iter()
+                .filter(|handle| {
+                    let object_info: *const ObjectInfo<T> = (**handle).into();
+                    unsafe { (*object_info).roots > 0 }
+                })
+                .cloned()
+                .collect();
+
+            for handle in remembered.drain() {
+                let object_info: *const ObjectInfo<T> = handle.into();
+                let references = unsafe { (*object_info).ty.trace(handle) };
+                queue.extend(references.filter(|reference| young.contains(reference)));
+            }
+
+            while let Some(handle) = queue.pop() {
+                if !survivors.insert(handle) {
+                    continue;
+                }
+
+                let object_info: *const ObjectInfo<T> = handle.into();
+                let references = unsafe { (*object_info).ty.trace(handle) };
+                queue.extend(references.filter(|reference| young.contains(reference)));
+            }
+        }
+
+        let young = std::mem::take(&mut heap.young);
+        let mut reclaimed = false;
+        for handle in young {
+            if survivors.contains(&handle) {
+                heap.promote(handle);
+
+                // An object that is promoted while the old generation is being marked can
+                // reference old objects that are otherwise unreachable
+                heap.shade(handle);
+            } else {
+                let object_info: *const ObjectInfo<T> = handle.into();
+                let (ptr, layout) = unsafe {
+                    (
+                        (*object_info).ptr,
+                        object_layout(&(*object_info).ty, (*object_info).ptr),
+                    )
+                };
+                if !heap.nursery.contains(ptr) {
+                    unsafe { std::alloc::dealloc(ptr, layout) };
+                }
+                heap.objects.remove(&handle);
+                self.log_dealloc(handle, layout.size());
+                reclaimed = true;
+            }
+        }
+        heap.nursery.reset();
+
+        reclaimed
+    }
+
+    /// Deallocates all old objects that were not reached by the mark phase, which must be
+    /// finished. Returns `true` if memory was reclaimed, `false` otherwise.
+    fn sweep(&self, heap: &mut Heap<T>) -> bool {
+        debug_assert!(heap.gray.is_empty());
+        heap.marking = false;
+
+        let Heap { objects, young, .. } = &mut *heap;
+        let size_before = objects.len();
+        objects.retain(|handle, obj| {
+            if obj.color == Color::Black || young.contains(handle) {
+                unsafe {
+                    obj.as_mut().get_unchecked_mut().color = Color::White;
+                }
+                true
+            } else {
+                let layout = object_layout(&obj.ty, obj.ptr);
+                unsafe { std::alloc::dealloc(obj.ptr, layout) };
+                self.log_dealloc(*handle, layout.size());
+                false
+            }
+        });
+
+        size_before != objects.len()
+    }
+}
+
+impl<T: TypeMemory + TypeTrace + Clone> Heap<T> {
+    /// Returns memory for an object with the specified `layout`, which is allocated in the nursery
+    /// if it fits.
+    fn alloc_memory(&mut self, layout: Layout) -> *mut u8 {
+        match self.nursery.alloc(layout) {
+            Some(ptr) => ptr,
+            None => system_alloc(layout),
+        }
+    }
+
+    /// Moves the memory of the young object `handle` out of the nursery.
+    fn promote(&mut self, handle: GcPtr) {
+        let object_info: *mut ObjectInfo<T> = handle.into();
+        let object_info = unsafe { &mut *object_info };
+        if self.nursery.contains(object_info.ptr) {
+            let layout = object_layout(&object_info.ty, object_info.ptr);
+            let ptr = system_alloc(layout);
+            unsafe { std::ptr::copy_nonoverlapping(object_info.ptr, ptr, layout.size()) };
+            object_info.ptr = ptr;
+        }
+    }
+
+    /// Moves all young objects to the old generation, without collecting any of them.
+    fn promote_all(&mut self) {
+        for handle in std::mem::take(&mut self.young) {
+            self.promote(handle);
+        }
+        self.remembered.clear();
+        self.nursery.reset();
+    }
+
+    /// Marks the old object `handle` as reached, if the old generation is being marked and the
+    /// object was not reached before.
+    fn shade(&mut self, handle: GcPtr) {
+        if !self.marking || self.young.contains(&handle) {
+            return;
+        }
+
+        let object_info: *mut ObjectInfo<T> = handle.into();
+        let object_info = unsafe { &mut *object_info };
+        if object_info.color == Color::White {
+            object_info.color = Color::Gray;
+            self.gray.push(handle);
+        }
+    }
+
+    /// Marks the objects that are reachable from rooted old objects, starting a new mark phase if
+    /// none is in progress. Returns `true` if all reachable objects are marked, or `false` if the
+    /// `deadline` passed first.
+    fn mark(&mut self, deadline: Option<Instant>) -> bool {
+        if !self.marking {
+            self.marking = true;
+
+            let roots: Vec<GcPtr> = self
+                .objects
+                .iter()
+                .filter(|(_, obj)| obj.roots > 0)
+                .map(|(handle, _)| *handle)
+                .collect();
+            for handle in roots {
+                self.shade(handle);
+            }
+        }
+
+        loop {
+            for _ in 0..MARK_BATCH_SIZE {
+                let handle = match self.gray.pop() {
+                    Some(handle) => handle,
+                    None => return true,
+                };
+
+                let object_info: *mut ObjectInfo<T> = handle.into();
+                for reference in unsafe { (*object_info).ty.trace(handle) } {
+                    self.shade(reference);
+                }
+
+                // This object has been traced
+                unsafe { (*object_info).color = Color::Black };
+            }
+
+            if deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+                return false;
+            }
+        }
+    }
+
+    /// Stops the mark phase that is in progress, if any, discarding its progress.
+    fn abort_marking(&mut self) {
+        self.marking = false;
+        self.gray.clear();
+        for obj in self.objects.values_mut() {
+            unsafe {
+                obj.as_mut().get_unchecked_mut().color = Color::White;
+            }
+        }
+    }
+}
+
+impl<T, O> GcRuntime<T> for Generational<T, O>
+where
+    T: TypeMemory + TypeTrace + Clone,
+    O: Observer<Event = Event>,
+{
+    fn alloc(&self, ty: T) -> GcPtr {
+        if ty.element_layout().is_some() {
+            return self.alloc_array(ty, 0);
+        }
+
+        let mut heap = self.heap.write();
+        let ptr = heap.alloc_memory(ty.layout());
+        self.insert(&mut heap, ptr, ty)
+    }
+
+    fn alloc_array(&self, ty: T, capacity: usize) -> GcPtr {
+        let element = ty
+            .element_layout()
+            .expect("cannot allocate an array of a non-array type");
+        let (layout, _) = array_layout(element, capacity);
+
+        let mut heap = self.heap.write();
+        let ptr = heap.alloc_memory(layout);
+        unsafe {
+            ptr.cast::<ArrayHeader>().write(ArrayHeader {
+                length: 0,
+                capacity,
+            })
+        };
+        self.insert(&mut heap, ptr, ty)
+    }
+
+    fn reserve_array(&self, handle: GcPtr, capacity: usize) {
+        let mut heap = self.heap.write();
+
+        // Convert the handle to our internal representation
+        let object_info: *mut ObjectInfo<T> = handle.into();
+        let object_info = unsafe { &mut *object_info };
+
+        let element = object_info
+            .ty
+            .element_layout()
+            .expect("cannot reserve memory for a non-array type");
+        let old_capacity = unsafe { (*object_info.ptr.cast::<ArrayHeader>()).capacity };
+        if old_capacity >= capacity {
+            return;
+        }
+
+        let (old_layout, _) = array_layout(element, old_capacity);
+        let (new_layout, _) = array_layout(element, capacity);
+        let ptr = if heap.nursery.contains(object_info.ptr) {
+            // Memory in the nursery cannot be resized, so the array is copied instead
+            let ptr = heap.alloc_memory(new_layout);
+            unsafe { std::ptr::copy_nonoverlapping(object_info.ptr, ptr, old_layout.size()) };
+            ptr
+        } else {
+            let ptr =
+                unsafe { std::alloc::realloc(object_info.ptr, old_layout, new_layout.size()) };
+            if ptr.is_null() {
+                std::alloc::handle_alloc_error(new_layout);
+            }
+            ptr
+        };
+        unsafe { (*ptr.cast::<ArrayHeader>()).capacity = capacity };
+        object_info.ptr = ptr;
+
+        let mut stats = self.stats.write();
+        stats.log_alloc(new_layout.size() - old_layout.size());
+        self.request_collection(&stats);
+    }
+
+    fn ptr_type(&self, handle: GcPtr) -> T {
+        let _heap = self.heap.read();
+
+        // Convert the handle to our internal representation
+        let object_info: *const ObjectInfo<T> = handle.into();
+
+        // Return the type of the object
+        unsafe { (*object_info).ty.clone() }
+    }
+
+    fn root(&self, handle: GcPtr) {
+        let mut heap = self.heap.write();
+
+        // Convert the handle to our internal representation
+        let object_info: *mut ObjectInfo<T> = handle.into();
+
+        unsafe { (*object_info).roots += 1 };
+
+        // The mark phase already visited the roots that existed when it started
+        heap.shade(handle);
+    }
+
+    fn unroot(&self, handle: GcPtr) {
+        let _heap = self.heap.write();
+
+        // Convert the handle to our internal representation
+        let object_info: *mut ObjectInfo<T> = handle.into();
+
+        unsafe { (*object_info).roots -= 1 };
+    }
+
+    fn write_barrier(&self, handle: GcPtr) {
+        let mut heap = self.heap.write();
+        if heap.young.contains(&handle) {
+            return;
+        }
+
+        // The object might now reference young objects
+        heap.remembered.insert(handle);
+
+        // The object might now reference objects that have not been marked, so it has to be
+        // traced again
+        let object_info: *mut ObjectInfo<T> = handle.into();
+        let object_info = unsafe { &mut *object_info };
+        if heap.marking && object_info.color == Color::Black {
+            object_info.color = Color::Gray;
+            heap.gray.push(handle);
+        }
+    }
+
+    fn stats(&self) -> Stats {
+        self.stats.read().clone()
+    }
+}
+
+impl<T, O> Generational<T, O>
+where
+    T: TypeDesc + TypeMemory + TypeTrace + Clone,
+    O: Observer<Event = Event>,
+{
+    /// Returns a snapshot of all allocated objects, both young and old, and the references between
+    /// them.
+    pub fn heap_snapshot(&self) -> HeapSnapshot {
+        let heap = self.heap.read();
+        HeapSnapshot::new(&heap.objects)
+    }
+}
+
+impl<T, O> MemoryMapper<T> for Generational<T, O>
+where
+    T: TypeDesc + TypeFields<T> + TypeMemory + TypeTrace + Clone + Eq + Hash,
+    O: Observer<Event = Event>,
+{
+    fn map_memory(&self, mapping: Mapping<T, T>) -> Vec<GcPtr> {
+        let mut heap = self.heap.write();
+
+        // Mapping reallocates the memory of objects, which cannot be done in the nursery. The
+        // types of the objects change, so marking has to start over as well.
+        heap.promote_all();
+        heap.abort_marking();
+
+        map_objects(&mut heap.objects, mapping, |handle, size| {
+            self.log_alloc(handle, size)
+        })
+    }
+}
+
+/// Allocates memory with the specified `layout` using the system allocator.
+fn system_alloc(layout: Layout) -> *mut u8 {
+    let ptr = unsafe { std::alloc::alloc(layout) };
+    if ptr.is_null() {
+        std::alloc::handle_alloc_error(layout);
+    }
+    ptr
+}
+
+/// A region of memory in which objects are allocated by incrementing an offset. The memory of
+/// individual objects cannot be freed; the whole nursery is reset after a minor collection.
+#[derive(Debug)]
+struct Nursery {
+    start: *mut u8,
+    size: usize,
+    /// The offset of the first free byte
+    offset: usize,
+}
+
+/// A `Nursery` is only accessed while holding the lock of its `Heap`.
+unsafe impl Send for Nursery {}
+unsafe impl Sync for Nursery {}
+
+impl Nursery {
+    fn new(size: usize) -> Self {
+        let start = if size == 0 {
+            std::ptr::null_mut()
+        } else {
+            system_alloc(Self::layout(size))
+        };
+        Nursery {
+            start,
+            size,
+            offset: 0,
+        }
+    }
+
+    fn layout(size: usize) -> Layout {
+        Layout::from_size_align(size, NURSERY_ALIGN).expect("invalid nursery size")
+    }
+
+    /// Returns memory for the specified `layout`, or `None` if it does not fit.
+    fn alloc(&mut self, layout: Layout) -> Option<*mut u8> {
+        if self.start.is_null() {
+            return None;
+        }
+
+        let start = self.start as usize;
+        let ptr = (start + self.offset + layout.align() - 1) & !(layout.align() - 1);
+        // Zero-sized objects occupy a byte, so their memory is recognized as part of the nursery
+        let end = ptr.checked_add(layout.size().max(1))?;
+        if end > start + self.size {
+            return None;
+        }
+
+        self.offset = end - start;
+        Some(ptr as *mut u8)
+    }
+
+    /// Returns whether `ptr` points into the nursery.
+    fn contains(&self, ptr: *const u8) -> bool {
+        let start = self.start as usize;
+        let ptr = ptr as usize;
+        !self.start.is_null() && ptr >= start && ptr < start + self.size
+    }
+
+    /// Frees the memory of all objects in the nursery.
+    fn reset(&mut self) {
+        self.offset = 0;
+    }
+}
+
+impl Drop for Nursery {
+    fn drop(&mut self) {
+        if !self.start.is_null() {
+            unsafe { std::alloc::dealloc(self.start, Self::layout(self.size)) };
+        }
+    }
+}
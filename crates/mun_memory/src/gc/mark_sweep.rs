@@ -1,17 +1,23 @@
 use crate::{
     cast,
-    gc::{Event, GcPtr, GcRuntime, Observer, RawGcPtr, Stats, TypeTrace},
-    mapping::{self, FieldMapping, MemoryMapper},
-    TypeDesc, TypeMemory,
+    gc::{
+        array_layout, ArrayHeader, Event, GcPtr, GcRuntime, HeapSnapshot, Observer, RawGcPtr,
+        Stats, TriggerPolicy, TypeTrace,
+    },
+    mapping::{self, FieldMapping, MemoryMapper, Migrations},
+    TypeDesc, TypeFields, TypeMemory,
 };
 use mapping::{Conversion, Mapping};
 use parking_lot::RwLock;
 use std::{
+    alloc::Layout,
     collections::{HashMap, VecDeque},
     hash::Hash,
     ops::Deref,
     pin::Pin,
     ptr::NonNull,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Instant,
 };
 
 /// Implements a simple mark-sweep type garbage collector.
@@ -24,6 +30,9 @@ where
     objects: RwLock<HashMap<GcPtr, Pin<Box<ObjectInfo<T>>>>>,
     observer: O,
     stats: RwLock<Stats>,
+    trigger_policy: TriggerPolicy,
+    /// Whether the `trigger_policy` requested a collection since the last collection
+    collection_requested: AtomicBool,
 }
 
 impl<T, O> Default for MarkSweep<T, O>
@@ -36,6 +45,8 @@ where
             objects: RwLock::new(HashMap::new()),
             observer: O::default(),
             stats: RwLock::new(Stats::default()),
+            trigger_policy: TriggerPolicy::default(),
+            collection_requested: AtomicBool::new(false),
         }
     }
 }
@@ -51,27 +62,68 @@ where
             objects: RwLock::new(HashMap::new()),
             observer,
             stats: RwLock::new(Stats::default()),
+            trigger_policy: TriggerPolicy::default(),
+            collection_requested: AtomicBool::new(false),
         }
     }
 
-    /// Logs an allocation
-    fn log_alloc(&self, handle: GcPtr, ty: T) {
+    /// Sets the policy that determines when the collector requests a collection.
+    pub fn with_trigger_policy(mut self, trigger_policy: TriggerPolicy) -> Self {
+        self.trigger_policy = trigger_policy;
+        self
+    }
+
+    /// Returns whether the trigger policy requested a collection since the last collection. The
+    /// collector never collects on its own, so the owner has to call `collect` when it is safe.
+    pub fn collection_requested(&self) -> bool {
+        self.collection_requested.load(Ordering::Relaxed)
+    }
+
+    /// Logs an allocation of `size` bytes
+    fn log_alloc(&self, handle: GcPtr, size: usize) {
         {
             let mut stats = self.stats.write();
-            stats.allocated_memory += ty.layout().size();
+            stats.log_alloc(size);
+            stats.allocated_objects += 1;
+            self.request_collection(&stats);
         }
 
         self.observer.event(Event::Allocation(handle));
     }
 
+    /// Requests a collection if the trigger policy is triggered by the `stats`.
+    fn request_collection(&self, stats: &Stats) {
+        if self.trigger_policy.is_triggered(stats) {
+            self.collection_requested.store(true, Ordering::Relaxed);
+        }
+    }
+
     /// Returns the observer
     pub fn observer(&self) -> &O {
         &self.observer
     }
+
+    /// Stores a newly allocated object, returning its handle.
+    fn insert(&self, object: Pin<Box<ObjectInfo<T>>>) -> GcPtr {
+        // We want to return a pointer to the `ObjectInfo`, to be used as handle.
+        let handle = (object.as_ref().deref() as *const _ as RawGcPtr).into();
+        let size = object_layout(&object.ty, object.ptr).size();
+
+        {
+            let mut objects = self.objects.write();
+            objects.insert(handle, object);
+        }
+
+        self.log_alloc(handle, size);
+        handle
+    }
 }
 
 fn alloc_obj<T: Clone + TypeMemory + TypeTrace>(ty: T) -> Pin<Box<ObjectInfo<T>>> {
-    let ptr = unsafe { std::alloc::alloc(ty.layout()) };
+    let ptr = match ty.element_layout() {
+        Some(element) => alloc_array(element, 0),
+        None => unsafe { std::alloc::alloc(ty.layout()) },
+    };
     Box::pin(ObjectInfo {
         ptr,
         ty,
@@ -80,24 +132,90 @@ fn alloc_obj<T: Clone + TypeMemory + TypeTrace>(ty: T) -> Pin<Box<ObjectInfo<T>>
     })
 }
 
+fn alloc_array_obj<T: Clone + TypeMemory + TypeTrace>(
+    ty: T,
+    capacity: usize,
+) -> Pin<Box<ObjectInfo<T>>> {
+    let element = ty
+        .element_layout()
+        .expect("cannot allocate an array of a non-array type");
+    Box::pin(ObjectInfo {
+        ptr: alloc_array(element, capacity),
+        ty,
+        roots: 0,
+        color: Color::White,
+    })
+}
+
+/// Allocates the memory of a dynamic array with room for `capacity` elements and initializes its
+/// header.
+fn alloc_array(element: Layout, capacity: usize) -> *mut u8 {
+    let (layout, _) = array_layout(element, capacity);
+    let ptr = unsafe { std::alloc::alloc(layout) };
+    if ptr.is_null() {
+        std::alloc::handle_alloc_error(layout);
+    }
+    unsafe {
+        ptr.cast::<ArrayHeader>().write(ArrayHeader {
+            length: 0,
+            capacity,
+        })
+    };
+    ptr
+}
+
+/// Returns the layout of the memory allocated for an object of type `ty`, located at `ptr`.
+/// Dynamic arrays store their capacity in the header that `ptr` points to.
+pub(super) fn object_layout<T: TypeMemory>(ty: &T, ptr: *const u8) -> Layout {
+    match ty.element_layout() {
+        Some(element) => array_layout(element, unsafe { (*ptr.cast::<ArrayHeader>()).capacity }).0,
+        None => ty.layout(),
+    }
+}
+
 impl<T, O> GcRuntime<T> for MarkSweep<T, O>
 where
     T: TypeMemory + TypeTrace + Clone,
     O: Observer<Event = Event>,
 {
     fn alloc(&self, ty: T) -> GcPtr {
-        let object = alloc_obj(ty.clone());
+        let object = alloc_obj(ty);
+        self.insert(object)
+    }
 
-        // We want to return a pointer to the `ObjectInfo`, to be used as handle.
-        let handle = (object.as_ref().deref() as *const _ as RawGcPtr).into();
+    fn alloc_array(&self, ty: T, capacity: usize) -> GcPtr {
+        let object = alloc_array_obj(ty, capacity);
+        self.insert(object)
+    }
 
-        {
-            let mut objects = self.objects.write();
-            objects.insert(handle, object);
+    fn reserve_array(&self, handle: GcPtr, capacity: usize) {
+        let _lock = self.objects.write();
+
+        // Convert the handle to our internal representation
+        let object_info: *mut ObjectInfo<T> = handle.into();
+        let object_info = unsafe { &mut *object_info };
+
+        let element = object_info
+            .ty
+            .element_layout()
+            .expect("cannot reserve memory for a non-array type");
+        let old_capacity = unsafe { (*object_info.ptr.cast::<ArrayHeader>()).capacity };
+        if old_capacity >= capacity {
+            return;
         }
 
-        self.log_alloc(handle, ty);
-        handle
+        let (old_layout, _) = array_layout(element, old_capacity);
+        let (new_layout, _) = array_layout(element, capacity);
+        let ptr = unsafe { std::alloc::realloc(object_info.ptr, old_layout, new_layout.size()) };
+        if ptr.is_null() {
+            std::alloc::handle_alloc_error(new_layout);
+        }
+        unsafe { (*ptr.cast::<ArrayHeader>()).capacity = capacity };
+        object_info.ptr = ptr;
+
+        let mut stats = self.stats.write();
+        stats.log_alloc(new_layout.size() - old_layout.size());
+        self.request_collection(&stats);
     }
 
     fn ptr_type(&self, handle: GcPtr) -> T {
@@ -141,6 +259,7 @@ where
     /// Collects all memory that is no longer referenced by rooted objects. Returns `true` if memory
     /// was reclaimed, `false` otherwise.
     pub fn collect(&self) -> bool {
+        let start = Instant::now();
         self.observer.event(Event::Start);
 
         let mut objects = self.objects.write();
@@ -181,6 +300,7 @@ where
 
         // Sweep all non-reachable objects
         let size_before = objects.len();
+        let mut freed_memory = 0;
         objects.retain(|h, obj| {
             if obj.color == Color::Black {
                 unsafe {
@@ -188,308 +308,405 @@ where
                 }
                 true
             } else {
-                unsafe { std::alloc::dealloc(obj.ptr, obj.ty.layout()) };
+                let layout = object_layout(&obj.ty, obj.ptr);
+                unsafe { std::alloc::dealloc(obj.ptr, layout) };
                 self.observer.event(Event::Deallocation(*h));
                 {
                     let mut stats = self.stats.write();
-                    stats.allocated_memory -= obj.ty.layout().size();
+                    stats.allocated_memory -= layout.size();
+                    stats.allocated_objects -= 1;
                 }
+                freed_memory += layout.size();
                 false
             }
         });
         let size_after = objects.len();
 
+        self.stats
+            .write()
+            .log_collection(start.elapsed(), freed_memory);
+        self.collection_requested.store(false, Ordering::Relaxed);
         self.observer.event(Event::End);
 
         size_before != size_after
     }
 }
 
+impl<T, O> MarkSweep<T, O>
+where
+    T: TypeDesc + TypeMemory + TypeTrace + Clone,
+    O: Observer<Event = Event>,
+{
+    /// Returns a snapshot of all allocated objects and the references between them.
+    pub fn heap_snapshot(&self) -> HeapSnapshot {
+        let objects = self.objects.read();
+        HeapSnapshot::new(&objects)
+    }
+}
+
 impl<T, O> MemoryMapper<T> for MarkSweep<T, O>
 where
-    T: TypeDesc + TypeMemory + TypeTrace + Clone + Eq + Hash,
+    T: TypeDesc + TypeFields<T> + TypeMemory + TypeTrace + Clone + Eq + Hash,
     O: Observer<Event = Event>,
 {
     fn map_memory(&self, mapping: Mapping<T, T>) -> Vec<GcPtr> {
         let mut objects = self.objects.write();
+        map_objects(&mut objects, mapping, |handle, size| {
+            self.log_alloc(handle, size)
+        })
+    }
+}
 
-        // Determine which types are still allocated with deleted types
-        let deleted = objects
-            .iter()
-            .filter_map(|(ptr, object_info)| {
-                if mapping.deletions.contains(&object_info.ty) {
-                    Some(*ptr)
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        // Update type pointers of types that didn't change
-        for (old_ty, new_ty) in mapping.identical {
-            for object_info in objects.values_mut() {
-                if object_info.ty == old_ty {
-                    object_info.set(ObjectInfo {
-                        ptr: object_info.ptr,
-                        roots: object_info.roots,
-                        color: object_info.color,
-                        ty: new_ty.clone(),
-                    });
-                }
+/// Maps the memory of all `objects` according to `mapping`. Objects that are allocated while
+/// mapping are inserted into `objects` and reported through `log_alloc`. Returns the handles of the
+/// objects whose type was deleted.
+pub(super) fn map_objects<T>(
+    objects: &mut HashMap<GcPtr, Pin<Box<ObjectInfo<T>>>>,
+    mapping: Mapping<T, T>,
+    mut log_alloc: impl FnMut(GcPtr, usize),
+) -> Vec<GcPtr>
+where
+    T: TypeDesc + TypeFields<T> + TypeMemory + TypeTrace + Clone + Eq + Hash,
+{
+    // Determine which types are still allocated with deleted types
+    let deleted = objects
+        .iter()
+        .filter_map(|(ptr, object_info)| {
+            if mapping.deletions.contains(&object_info.ty) {
+                Some(*ptr)
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    // Update type pointers of types that didn't change
+    for (old_ty, new_ty) in mapping.identical {
+        for object_info in objects.values_mut() {
+            if object_info.ty == old_ty {
+                object_info.set(ObjectInfo {
+                    ptr: object_info.ptr,
+                    roots: object_info.roots,
+                    color: object_info.color,
+                    ty: new_ty.clone(),
+                });
             }
         }
+    }
 
-        let mut new_allocations = Vec::new();
+    let mut new_allocations = Vec::new();
 
-        for (old_ty, conversion) in mapping.conversions.iter() {
-            for object_info in objects.values_mut() {
-                if object_info.ty == *old_ty {
-                    let src = unsafe { NonNull::new_unchecked(object_info.ptr) };
-                    let dest = unsafe {
-                        NonNull::new_unchecked(std::alloc::alloc_zeroed(conversion.new_ty.layout()))
-                    };
+    for (old_ty, conversion) in mapping.conversions.iter() {
+        for object_info in objects.values_mut() {
+            if object_info.ty == *old_ty {
+                let src = unsafe { NonNull::new_unchecked(object_info.ptr) };
+                let dest = unsafe {
+                    NonNull::new_unchecked(std::alloc::alloc_zeroed(conversion.new_ty.layout()))
+                };
 
-                    map_fields(
-                        self,
-                        &mut new_allocations,
-                        &mapping.conversions,
-                        &conversion.field_mapping,
-                        src,
-                        dest,
-                    );
-
-                    unsafe { std::alloc::dealloc(src.as_ptr(), old_ty.layout()) };
-
-                    object_info.set(ObjectInfo {
-                        ptr: dest.as_ptr(),
-                        roots: object_info.roots,
-                        color: object_info.color,
-                        ty: conversion.new_ty.clone(),
-                    });
-                }
+                map_type(
+                    &mut new_allocations,
+                    &mapping.conversions,
+                    &mapping.migrations,
+                    old_ty,
+                    conversion,
+                    src,
+                    dest,
+                );
+
+                unsafe { std::alloc::dealloc(src.as_ptr(), old_ty.layout()) };
+
+                object_info.set(ObjectInfo {
+                    ptr: dest.as_ptr(),
+                    roots: object_info.roots,
+                    color: object_info.color,
+                    ty: conversion.new_ty.clone(),
+                });
             }
         }
+    }
 
-        // Retroactively store newly allocated objects
-        // This cannot be done while mapping because we hold a mutable reference to objects
-        for object in new_allocations {
-            let ty = object.ty.clone();
-            // We want to return a pointer to the `ObjectInfo`, to
-            // be used as handle.
-            let handle = (object.as_ref().deref() as *const _ as RawGcPtr).into();
-            objects.insert(handle, object);
+    // Retroactively store newly allocated objects
+    // This cannot be done while mapping because we hold a mutable reference to objects
+    for object in new_allocations {
+        let size = object_layout(&object.ty, object.ptr).size();
+        // We want to return a pointer to the `ObjectInfo`, to
+        // be used as handle.
+        let handle = (object.as_ref().deref() as *const _ as RawGcPtr).into();
+        objects.insert(handle, object);
 
-            self.log_alloc(handle, ty);
+        log_alloc(handle, size);
+    }
+
+    return deleted;
+
+    /// Maps the memory of a value of type `old_ty` at `src` to `dest`, using the specified
+    /// `conversion`. For enums, the tag is converted and the fields of the active variant are
+    /// mapped. Afterwards, the user-defined migration of `old_ty` is applied, if any.
+    fn map_type<T>(
+        new_allocations: &mut Vec<Pin<Box<ObjectInfo<T>>>>,
+        conversions: &HashMap<T, Conversion<T>>,
+        migrations: &Migrations<T>,
+        old_ty: &T,
+        conversion: &Conversion<T>,
+        src: NonNull<u8>,
+        dest: NonNull<u8>,
+    ) where
+        T: TypeDesc + TypeFields<T> + TypeMemory + TypeTrace + Clone + Eq + Hash,
+    {
+        if conversion.new_ty.group().is_enum() {
+            let old_tag = unsafe { *src.as_ptr() };
+            if let Some(variant) = conversion.variant_mapping.get(usize::from(old_tag)) {
+                unsafe { *dest.as_ptr() = variant.new_tag };
+                map_fields(
+                    new_allocations,
+                    conversions,
+                    migrations,
+                    &variant.field_mapping,
+                    src,
+                    dest,
+                );
+            }
+        } else {
+            map_fields(
+                new_allocations,
+                conversions,
+                migrations,
+                &conversion.field_mapping,
+                src,
+                dest,
+            );
         }
 
-        return deleted;
-
-        fn map_fields<T, O>(
-            gc: &MarkSweep<T, O>,
-            new_allocations: &mut Vec<Pin<Box<ObjectInfo<T>>>>,
-            conversions: &HashMap<T, Conversion<T>>,
-            mapping: &[FieldMapping<T>],
-            src: NonNull<u8>,
-            dest: NonNull<u8>,
-        ) where
-            T: TypeDesc + TypeMemory + TypeTrace + Clone + Eq + Hash,
-            O: Observer<Event = Event>,
+        // User-defined migrations have access to both the old and the new value
+        let migration = migrations
+            .get(old_ty.name())
+            .filter(|migration| migration.new_type_name == conversion.new_ty.name());
+        if let Some(migration) = migration {
+            // Safety: `src` and `dest` point to values of the old and the new type
+            unsafe {
+                mapping::apply_migration(
+                    &*migration.migration,
+                    old_ty,
+                    &conversion.new_ty,
+                    src,
+                    dest,
+                )
+            };
+        }
+    }
+
+    fn map_fields<T>(
+        new_allocations: &mut Vec<Pin<Box<ObjectInfo<T>>>>,
+        conversions: &HashMap<T, Conversion<T>>,
+        migrations: &Migrations<T>,
+        mapping: &[FieldMapping<T>],
+        src: NonNull<u8>,
+        dest: NonNull<u8>,
+    ) where
+        T: TypeDesc + TypeFields<T> + TypeMemory + TypeTrace + Clone + Eq + Hash,
+    {
+        for FieldMapping {
+            new_ty,
+            new_offset,
+            action,
+        } in mapping.iter()
         {
-            for FieldMapping {
-                new_ty,
-                new_offset,
-                action,
-            } in mapping.iter()
-            {
-                let field_dest = {
-                    let mut dest = dest.as_ptr() as usize;
-                    dest += new_offset;
-                    dest as *mut u8
-                };
+            let field_dest = {
+                let mut dest = dest.as_ptr() as usize;
+                dest += new_offset;
+                dest as *mut u8
+            };
+
+            match action {
+                mapping::Action::Cast { old_offset, old_ty } => {
+                    let field_src = {
+                        let mut src = src.as_ptr() as usize;
+                        src += old_offset;
+                        src as *mut u8
+                    };
 
-                match action {
-                    mapping::Action::Cast { old_offset, old_ty } => {
-                        let field_src = {
-                            let mut src = src.as_ptr() as usize;
-                            src += old_offset;
-                            src as *mut u8
-                        };
-
-                        if old_ty.group().is_struct() {
-                            debug_assert!(new_ty.group().is_struct());
-
-                            // When the name is the same, we are dealing with the same struct,
-                            // but different internals
-                            let is_same_struct = old_ty.name() == new_ty.name();
-
-                            // If the same struct changed, there must also be a conversion
-                            let conversion = conversions.get(old_ty);
-
-                            if old_ty.is_stack_allocated() {
-                                if new_ty.is_stack_allocated() {
-                                    // struct(value) -> struct(value)
-                                    if is_same_struct {
-                                        // Map in-memory struct to in-memory struct
-                                        map_fields(
-                                            gc,
-                                            new_allocations,
-                                            conversions,
-                                            &conversion.as_ref().unwrap().field_mapping,
-                                            unsafe { NonNull::new_unchecked(field_src) },
-                                            unsafe { NonNull::new_unchecked(field_dest) },
-                                        );
-                                    } else {
-                                        // Use previously zero-initialized memory
-                                    }
+                    if old_ty.group().is_struct() || old_ty.group().is_enum() {
+                        debug_assert!(new_ty.group().is_struct() || new_ty.group().is_enum());
+
+                        // When the old type was converted to the new type, we are dealing with
+                        // the same struct, but different internals or a different name. Enums are
+                        // mapped like structs.
+                        let conversion = conversions.get(old_ty).filter(|conversion| {
+                            conversion.new_ty.name() == new_ty.name()
+                                && conversion.new_ty.group() == new_ty.group()
+                        });
+                        let is_same_struct = conversion.is_some();
+
+                        if old_ty.is_stack_allocated() {
+                            if new_ty.is_stack_allocated() {
+                                // struct(value) -> struct(value)
+                                if is_same_struct {
+                                    // Map in-memory struct to in-memory struct
+                                    map_type(
+                                        new_allocations,
+                                        conversions,
+                                        migrations,
+                                        old_ty,
+                                        conversion.unwrap(),
+                                        unsafe { NonNull::new_unchecked(field_src) },
+                                        unsafe { NonNull::new_unchecked(field_dest) },
+                                    );
                                 } else {
-                                    // struct(value) -> struct(gc)
-                                    let object = alloc_obj(new_ty.clone());
-
-                                    // We want to return a pointer to the `ObjectInfo`, to be used as handle.
-                                    let handle =
-                                        (object.as_ref().deref() as *const _ as RawGcPtr).into();
-
-                                    if is_same_struct {
-                                        // Map in-memory struct to heap-allocated struct
-                                        map_fields(
-                                            gc,
-                                            new_allocations,
-                                            conversions,
-                                            &conversion.as_ref().unwrap().field_mapping,
-                                            unsafe { NonNull::new_unchecked(field_src) },
-                                            unsafe { NonNull::new_unchecked(object.ptr) },
-                                        );
-                                    } else {
-                                        // Zero initialize heap-allocated object
-                                        unsafe {
-                                            std::ptr::write_bytes(
-                                                (*object).ptr,
-                                                0,
-                                                new_ty.layout().size(),
-                                            )
-                                        };
-                                    }
-
-                                    // Write handle to field
-                                    let field_handle = field_dest.cast::<GcPtr>();
-                                    unsafe { *field_handle = handle };
-
-                                    new_allocations.push(object);
+                                    // Use previously zero-initialized memory
                                 }
-                            } else if !new_ty.is_stack_allocated() {
-                                // struct(gc) -> struct(gc)
-                                let field_src = field_src.cast::<GcPtr>();
-                                let field_dest = field_dest.cast::<GcPtr>();
+                            } else {
+                                // struct(value) -> struct(gc)
+                                let object = alloc_obj(new_ty.clone());
+
+                                // We want to return a pointer to the `ObjectInfo`, to be used as handle.
+                                let handle =
+                                    (object.as_ref().deref() as *const _ as RawGcPtr).into();
 
                                 if is_same_struct {
-                                    // Only copy the `GcPtr`. Memory will already be mapped.
-                                    unsafe {
-                                        *field_dest = *field_src;
-                                    }
+                                    // Map in-memory struct to heap-allocated struct
+                                    map_type(
+                                        new_allocations,
+                                        conversions,
+                                        migrations,
+                                        old_ty,
+                                        conversion.unwrap(),
+                                        unsafe { NonNull::new_unchecked(field_src) },
+                                        unsafe { NonNull::new_unchecked(object.ptr) },
+                                    );
                                 } else {
-                                    let object = alloc_obj(new_ty.clone());
-
-                                    // We want to return a pointer to the `ObjectInfo`, to
-                                    // be used as handle.
-                                    let handle =
-                                        (object.as_ref().deref() as *const _ as RawGcPtr).into();
-
-                                    // Zero-initialize heap-allocated object
+                                    // Zero initialize heap-allocated object
                                     unsafe {
-                                        std::ptr::write_bytes(object.ptr, 0, new_ty.layout().size())
+                                        std::ptr::write_bytes(
+                                            (*object).ptr,
+                                            0,
+                                            new_ty.layout().size(),
+                                        )
                                     };
+                                }
 
-                                    // Write handle to field
-                                    unsafe {
-                                        *field_dest = handle;
-                                    }
+                                // Write handle to field
+                                let field_handle = field_dest.cast::<GcPtr>();
+                                unsafe { *field_handle = handle };
 
-                                    new_allocations.push(object);
+                                new_allocations.push(object);
+                            }
+                        } else if !new_ty.is_stack_allocated() {
+                            // struct(gc) -> struct(gc)
+                            let field_src = field_src.cast::<GcPtr>();
+                            let field_dest = field_dest.cast::<GcPtr>();
+
+                            if is_same_struct {
+                                // Only copy the `GcPtr`. Memory will already be mapped.
+                                unsafe {
+                                    *field_dest = *field_src;
                                 }
                             } else {
-                                // struct(gc) -> struct(value)
-                                let field_handle = unsafe { *field_src.cast::<GcPtr>() };
+                                let object = alloc_obj(new_ty.clone());
 
-                                // Convert the handle to our internal representation
-                                // Safety: we already hold a write lock on `objects`, so
-                                // this is legal.
-                                let obj: *mut ObjectInfo<T> = field_handle.into();
-                                let obj = unsafe { &*obj };
+                                // We want to return a pointer to the `ObjectInfo`, to
+                                // be used as handle.
+                                let handle =
+                                    (object.as_ref().deref() as *const _ as RawGcPtr).into();
 
-                                if is_same_struct {
-                                    if obj.ty == *old_ty {
-                                        // The object still needs to be mapped
-                                        // Map heap-allocated struct to in-memory struct
-                                        map_fields(
-                                            gc,
-                                            new_allocations,
-                                            conversions,
-                                            &conversion.as_ref().unwrap().field_mapping,
-                                            unsafe { NonNull::new_unchecked(obj.ptr) },
-                                            unsafe { NonNull::new_unchecked(field_dest) },
-                                        );
-                                    } else {
-                                        // The object was already mapped
-                                        debug_assert!(obj.ty == *new_ty);
-
-                                        // Copy from heap-allocated struct to in-memory struct
-                                        unsafe {
-                                            std::ptr::copy_nonoverlapping(
-                                                obj.ptr,
-                                                field_dest,
-                                                obj.ty.layout().size(),
-                                            )
-                                        };
-                                    }
+                                // Zero-initialize heap-allocated object
+                                unsafe {
+                                    std::ptr::write_bytes(object.ptr, 0, new_ty.layout().size())
+                                };
+
+                                // Write handle to field
+                                unsafe {
+                                    *field_dest = handle;
+                                }
+
+                                new_allocations.push(object);
+                            }
+                        } else {
+                            // struct(gc) -> struct(value)
+                            let field_handle = unsafe { *field_src.cast::<GcPtr>() };
+
+                            // An optional reference that is `nil` has no memory to map. Use
+                            // the previously zero-initialized memory instead.
+                            if field_handle.as_ptr().is_null() {
+                                continue;
+                            }
+
+                            // Convert the handle to our internal representation
+                            // Safety: we already hold a write lock on `objects`, so
+                            // this is legal.
+                            let obj: *mut ObjectInfo<T> = field_handle.into();
+                            let obj = unsafe { &*obj };
+
+                            if is_same_struct {
+                                if obj.ty == *old_ty {
+                                    // The object still needs to be mapped
+                                    // Map heap-allocated struct to in-memory struct
+                                    map_type(
+                                        new_allocations,
+                                        conversions,
+                                        migrations,
+                                        old_ty,
+                                        conversion.unwrap(),
+                                        unsafe { NonNull::new_unchecked(obj.ptr) },
+                                        unsafe { NonNull::new_unchecked(field_dest) },
+                                    );
                                 } else {
-                                    // Use previously zero-initialized memory
+                                    // The object was already mapped
+                                    debug_assert!(obj.ty == *new_ty);
+
+                                    // Copy from heap-allocated struct to in-memory struct
+                                    unsafe {
+                                        std::ptr::copy_nonoverlapping(
+                                            obj.ptr,
+                                            field_dest,
+                                            obj.ty.layout().size(),
+                                        )
+                                    };
                                 }
+                            } else {
+                                // Use previously zero-initialized memory
                             }
-                        } else if !cast::try_cast_from_to(
-                            *old_ty.guid(),
-                            *new_ty.guid(),
-                            unsafe { NonNull::new_unchecked(field_src) },
-                            unsafe { NonNull::new_unchecked(field_dest) },
-                        ) {
-                            // Failed to cast. Use the previously zero-initialized value instead
                         }
+                    } else if !cast::try_cast_from_to(
+                        *old_ty.guid(),
+                        *new_ty.guid(),
+                        unsafe { NonNull::new_unchecked(field_src) },
+                        unsafe { NonNull::new_unchecked(field_dest) },
+                    ) {
+                        // Failed to cast. Use the previously zero-initialized value instead
                     }
-                    mapping::Action::Copy { old_offset } => {
-                        let field_src = {
-                            let mut src = src.as_ptr() as usize;
-                            src += old_offset;
-                            src as *mut u8
-                        };
-
-                        unsafe {
-                            std::ptr::copy_nonoverlapping(
-                                field_src,
-                                field_dest,
-                                new_ty.layout().size(),
-                            )
-                        };
-                    }
-                    mapping::Action::Insert => {
-                        if !new_ty.is_stack_allocated() {
-                            let object = alloc_obj(new_ty.clone());
+                }
+                mapping::Action::Copy { old_offset } => {
+                    let field_src = {
+                        let mut src = src.as_ptr() as usize;
+                        src += old_offset;
+                        src as *mut u8
+                    };
 
-                            // We want to return a pointer to the `ObjectInfo`, to be used as
-                            // handle.
-                            let handle = (object.as_ref().deref() as *const _ as RawGcPtr).into();
+                    unsafe {
+                        std::ptr::copy_nonoverlapping(field_src, field_dest, new_ty.layout().size())
+                    };
+                }
+                mapping::Action::Insert => {
+                    if !new_ty.is_stack_allocated() {
+                        let object = alloc_obj(new_ty.clone());
 
-                            // Zero-initialize heap-allocated object
-                            unsafe { std::ptr::write_bytes(object.ptr, 0, new_ty.layout().size()) };
+                        // We want to return a pointer to the `ObjectInfo`, to be used as
+                        // handle.
+                        let handle = (object.as_ref().deref() as *const _ as RawGcPtr).into();
 
-                            // Write handle to field
-                            let field_dest = field_dest.cast::<GcPtr>();
-                            unsafe {
-                                *field_dest = handle;
-                            }
+                        // Zero-initialize heap-allocated object
+                        unsafe { std::ptr::write_bytes(object.ptr, 0, new_ty.layout().size()) };
 
-                            new_allocations.push(object);
-                        } else {
-                            // Use the previously zero-initialized value
+                        // Write handle to field
+                        let field_dest = field_dest.cast::<GcPtr>();
+                        unsafe {
+                            *field_dest = handle;
                         }
+
+                        new_allocations.push(object);
+                    } else {
+                        // Use the previously zero-initialized value
                     }
                 }
             }
@@ -499,7 +716,7 @@ where
 
 /// Coloring used in the Mark Sweep phase.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum Color {
+pub(super) enum Color {
     /// A white object has not been seen yet by the mark phase
     White,
 
@@ -514,7 +731,7 @@ enum Color {
 /// meta information.
 #[derive(Debug)]
 #[repr(C)]
-struct ObjectInfo<T: TypeMemory + TypeTrace + Clone> {
+pub(super) struct ObjectInfo<T: TypeMemory + TypeTrace + Clone> {
     pub ptr: *mut u8,
     pub roots: u32,
     pub color: Color,
@@ -0,0 +1,109 @@
+use crate::{
+    gc::{
+        mark_sweep::{object_layout, ObjectInfo},
+        GcPtr, TypeTrace,
+    },
+    TypeDesc, TypeMemory,
+};
+use serde::Serialize;
+use std::{cmp::Reverse, collections::HashMap, pin::Pin};
+
+/// A snapshot of all objects that are allocated by a garbage collector and the references between
+/// them. This allows finding out why objects are kept alive, e.g. to find memory leaks.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct HeapSnapshot {
+    /// The allocated objects, ordered by their handle
+    pub objects: Vec<ObjectSnapshot>,
+    /// The number of objects and bytes per type, ordered from the most to the least bytes
+    pub types: Vec<TypeStats>,
+}
+
+/// An object in a `HeapSnapshot`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ObjectSnapshot {
+    /// The address of the object's handle, which identifies the object in the snapshot
+    pub handle: usize,
+    /// The name of the object's type
+    pub type_name: String,
+    /// The number of bytes that are allocated for the object
+    pub size: usize,
+    /// The number of times that the object is rooted
+    pub roots: u32,
+    /// The handles of the objects that are referenced by the object
+    pub references: Vec<usize>,
+}
+
+/// The number of objects of a type and the number of bytes that are allocated for them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TypeStats {
+    /// The name of the type
+    pub name: String,
+    /// The number of allocated objects of the type
+    pub objects: usize,
+    /// The number of bytes that are allocated for objects of the type
+    pub memory: usize,
+}
+
+impl HeapSnapshot {
+    /// Constructs a snapshot of the specified `objects`.
+    pub(super) fn new<T>(objects: &HashMap<GcPtr, Pin<Box<ObjectInfo<T>>>>) -> Self
+    where
+        T: TypeDesc + TypeMemory + TypeTrace + Clone,
+    {
+        let mut objects: Vec<ObjectSnapshot> = objects
+            .iter()
+            .map(|(handle, object_info)| ObjectSnapshot {
+                handle: handle_address(*handle),
+                type_name: object_info.ty.name().to_owned(),
+                size: object_layout(&object_info.ty, object_info.ptr).size(),
+                roots: object_info.roots,
+                references: object_info.ty.trace(*handle).map(handle_address).collect(),
+            })
+            .collect();
+        objects.sort_by_key(|object| object.handle);
+
+        let mut types: HashMap<&str, TypeStats> = HashMap::new();
+        for object in objects.iter() {
+            let stats = types
+                .entry(object.type_name.as_str())
+                .or_insert_with(|| TypeStats {
+                    name: object.type_name.clone(),
+                    objects: 0,
+                    memory: 0,
+                });
+            stats.objects += 1;
+            stats.memory += object.size;
+        }
+        let mut types: Vec<TypeStats> = types.into_iter().map(|(_, stats)| stats).collect();
+        types.sort_by(|a, b| {
+            Reverse(a.memory)
+                .cmp(&Reverse(b.memory))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        HeapSnapshot { objects, types }
+    }
+
+    /// Returns the object with the specified `handle`, if it is part of the snapshot.
+    pub fn object(&self, handle: usize) -> Option<&ObjectSnapshot> {
+        self.objects
+            .binary_search_by_key(&handle, |object| object.handle)
+            .ok()
+            .map(|idx| &self.objects[idx])
+    }
+
+    /// Returns the objects that are rooted.
+    pub fn roots(&self) -> impl Iterator<Item = &ObjectSnapshot> {
+        self.objects.iter().filter(|object| object.roots > 0)
+    }
+
+    /// Serializes the snapshot to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a heap snapshot can always be serialized")
+    }
+}
+
+/// Returns the address of `handle`, which identifies an object in a `HeapSnapshot`.
+fn handle_address(handle: GcPtr) -> usize {
+    handle.as_ptr() as usize
+}
@@ -7,7 +7,7 @@ pub mod mapping;
 
 pub mod prelude {
     pub use crate::diff::{diff, Diff, FieldDiff, FieldEditKind};
-    pub use crate::mapping::{Action, FieldMapping};
+    pub use crate::mapping::{Action, FieldMapping, VariantMapping};
 }
 
 /// A trait used to obtain a type's description.
@@ -26,6 +26,8 @@ pub trait TypeMemory: Send + Sync {
     fn layout(&self) -> Layout;
     /// Returns whether the memory is stack-allocated.
     fn is_stack_allocated(&self) -> bool;
+    /// Returns the memory layout of a single element, if this type is a dynamically sized array.
+    fn element_layout(&self) -> Option<Layout>;
 }
 
 /// A trait used to obtain a type's fields.
@@ -34,4 +36,17 @@ pub trait TypeFields<T>: Send + Sync {
     fn fields(&self) -> Vec<(&str, T)>;
     /// Returns the type's fields' offsets.
     fn offsets(&self) -> &[u16];
+    /// Returns the previous name of each of the type's fields, if it was declared with a
+    /// `#[renamed_from(..)]` attribute. An empty `Vec` is returned if no renames were declared.
+    fn renamed_from(&self) -> Vec<Option<&str>> {
+        Vec::new()
+    }
+    /// Returns the type's variants, if it is an enum. Each variant consists of its name, its
+    /// fields, and its fields' offsets relative to the start of the enum.
+    ///
+    /// The tag of an enum is stored as a `u8` at the start of its memory and contains the index
+    /// of its active variant.
+    fn variants(&self) -> Vec<(&str, Vec<(&str, T)>, &[u16])> {
+        Vec::new()
+    }
 }
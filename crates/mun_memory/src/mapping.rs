@@ -1,24 +1,104 @@
 use crate::{
-    diff::{diff, Diff, FieldDiff, FieldEditKind},
+    cast,
+    diff::{diff, field_diff, Diff, FieldDiff, FieldEditKind},
     gc::GcPtr,
     TypeDesc, TypeFields, TypeMemory,
 };
+use abi::HasStaticTypeInfo;
 use std::{
     collections::{HashMap, HashSet},
     hash::Hash,
+    marker::PhantomData,
+    panic::{self, AssertUnwindSafe},
+    ptr::NonNull,
+    sync::Arc,
 };
 
 pub struct Mapping<T: Eq + Hash, U: TypeDesc + TypeMemory> {
     pub deletions: HashSet<T>,
     pub conversions: HashMap<T, Conversion<U>>,
     pub identical: Vec<(T, T)>,
+    /// The user-defined migrations of converted objects, by name of their old type
+    pub migrations: Migrations<U>,
+    /// The field renames that were guessed, because they were not declared
+    pub guessed_renames: Vec<GuessedRename>,
+    /// The field renames that were declared, but of which the old type has no field to rename
+    pub unmatched_renames: Vec<UnmatchedRename>,
+}
+
+/// A rename of a field that was guessed while diffing, because an old and a new field have the
+/// same type and the new field does not declare its previous name with `#[renamed_from(..)]`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuessedRename {
+    /// The name of the type that contains the field
+    pub type_name: String,
+    /// The name of the field in the old type
+    pub old_name: String,
+    /// The name of the field in the new type
+    pub new_name: String,
+}
+
+/// A rename of a field that was declared with `#[renamed_from(..)]`, but of which the old type has
+/// no field with the declared previous name. The new field is initialized as an inserted field.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnmatchedRename {
+    /// The name of the type that contains the field
+    pub type_name: String,
+    /// The declared previous name of the field
+    pub old_name: String,
+    /// The name of the field in the new type
+    pub new_name: String,
+}
+
+/// A user-defined function that migrates an object of which the type changed. It receives the old
+/// and the new value of the object, after the fields of the new value have been mapped from the
+/// old value. This allows migrating data that cannot be mapped automatically, e.g. when a field is
+/// renamed.
+///
+/// If the function panics, the new value retains the fields that were mapped from the old value.
+pub type Migration<T> = dyn Fn(&MigrationValue<T>, &mut MigrationValue<T>) + Send + Sync;
+
+/// A user-defined migration of the objects of a type.
+pub struct TypeMigration<T> {
+    /// The name of the type to which objects are migrated. It differs from the name of the type of
+    /// the old objects if the type was renamed.
+    pub new_type_name: String,
+    /// The function that migrates each object
+    pub migration: Arc<Migration<T>>,
+}
+
+impl<T> Clone for TypeMigration<T> {
+    fn clone(&self) -> Self {
+        Self {
+            new_type_name: self.new_type_name.clone(),
+            migration: self.migration.clone(),
+        }
+    }
+}
+
+/// A collection of migrations, by name of the old type of the objects that they migrate.
+pub type Migrations<T> = HashMap<String, TypeMigration<T>>;
+
+/// The memory of a value that is being migrated, which provides access to its fields.
+pub struct MigrationValue<'a, T> {
+    ty: &'a T,
+    ptr: NonNull<u8>,
+    _lifetime: PhantomData<&'a mut u8>,
 }
 
 pub struct Conversion<T: TypeDesc + TypeMemory> {
     pub field_mapping: Vec<FieldMapping<T>>,
+    /// The mapping of each old enum variant, indexed by its tag. Empty for non-enum types.
+    pub variant_mapping: Vec<VariantMapping<T>>,
     pub new_ty: T,
 }
 
+/// Description of the mapping of an old enum variant to a new enum variant.
+pub struct VariantMapping<T: TypeDesc + TypeMemory> {
+    pub new_tag: u8,
+    pub field_mapping: Vec<FieldMapping<T>>,
+}
+
 /// Description of the mapping of a single field. When stored together with the new index, this
 /// provides all information necessary for a mapping function.
 pub struct FieldMapping<T: TypeDesc + TypeMemory> {
@@ -41,13 +121,23 @@ where
 {
     ///
     pub fn new(old: &[T], new: &[T]) -> Self {
-        let diff = diff(old, new);
+        Self::with_migrations(old, new, Migrations::new())
+    }
+
+    /// Constructs the mapping of the `old` types to the `new` types, which applies the
+    /// user-defined `migrations` to converted objects. A type that was renamed is converted, rather
+    /// than deleted, if a migration to its new name is registered by its old name.
+    pub fn with_migrations(old: &[T], new: &[T], migrations: Migrations<T>) -> Self {
+        let mut diff = diff(old, new);
+        edit_renamed_types(old, new, &migrations, &mut diff);
 
         let mut conversions = HashMap::new();
         let mut deletions = HashSet::new();
         let mut insertions = HashSet::new();
 
         let mut identical = Vec::new();
+        let mut guessed_renames = Vec::new();
+        let mut unmatched_renames = Vec::new();
 
         for diff in diff.iter() {
             match diff {
@@ -61,7 +151,14 @@ where
                 } => {
                     let old_ty = unsafe { *old.get_unchecked(*old_index) };
                     let new_ty = unsafe { *new.get_unchecked(*new_index) };
-                    conversions.insert(old_ty, unsafe { field_mapping(old_ty, new_ty, diff) });
+                    let conversion = if old_ty.group().is_enum() {
+                        variant_mapping(old_ty, new_ty)
+                    } else {
+                        guessed_renames.append(&mut find_guessed_renames(old_ty, new_ty, diff));
+                        unmatched_renames.append(&mut find_unmatched_renames(new_ty, diff));
+                        unsafe { field_mapping(old_ty, new_ty, diff) }
+                    };
+                    conversions.insert(old_ty, conversion);
                 }
                 Diff::Insert { index } => {
                     insertions.insert(unsafe { *new.get_unchecked(*index) });
@@ -82,8 +179,8 @@ where
         // identical types.
         let mut new_candidates: HashSet<T> = new
             .iter()
-            // Filter non-struct types
-            .filter(|ty| ty.group().is_struct())
+            // Filter fundamental types
+            .filter(|ty| !ty.group().is_fundamental())
             // Filter inserted structs
             .filter(|ty| !insertions.contains(*ty))
             .cloned()
@@ -91,8 +188,8 @@ where
 
         let mut old_candidates: HashSet<T> = old
             .iter()
-            // Filter non-struct types
-            .filter(|ty| ty.group().is_struct())
+            // Filter fundamental types
+            .filter(|ty| !ty.group().is_fundamental())
             // Filter deleted structs
             .filter(|ty| !deletions.contains(*ty))
             // Filter edited types
@@ -127,8 +224,231 @@ where
             deletions,
             conversions,
             identical,
+            migrations,
+            guessed_renames,
+            unmatched_renames,
+        }
+    }
+}
+
+/// Replaces the deletion and insertion of a type that was renamed with an edit, if a migration
+/// from its old name to its new name is registered. A renamed type of which the fields did not
+/// change is edited as well, so its migration is applied.
+fn edit_renamed_types<T>(old: &[T], new: &[T], migrations: &Migrations<T>, diff: &mut Vec<Diff>)
+where
+    T: TypeDesc + TypeFields<T> + Copy + Eq,
+{
+    let is_renamed = |old_ty: &T, new_ty: &T| {
+        old_ty.name() != new_ty.name()
+            && old_ty.group() == new_ty.group()
+            && (old_ty.group().is_struct() || old_ty.group().is_enum())
+            && migrations
+                .get(old_ty.name())
+                .map_or(false, |migration| migration.new_type_name == new_ty.name())
+    };
+    let edit = |old_index: usize, new_index: usize| {
+        let (old_ty, new_ty) = (old[old_index], new[new_index]);
+        // The fields of an enum's variants are diffed per variant, when mapping its memory.
+        let diff = if old_ty.group().is_enum() {
+            Vec::new()
+        } else {
+            field_diff(&old_ty.fields(), &new_ty.fields(), &new_ty.renamed_from())
+        };
+        Diff::Edit {
+            diff,
+            old_index,
+            new_index,
+        }
+    };
+
+    // The indices in `diff` of the edits, and of the insertions that they replace
+    let mut edits = Vec::new();
+    let mut replaced_insertions = HashSet::new();
+    for (diff_index, entry) in diff.iter().enumerate() {
+        match *entry {
+            Diff::Delete { index: old_index } => {
+                let insertion =
+                    diff.iter()
+                        .enumerate()
+                        .find_map(|(insert_index, entry)| match *entry {
+                            Diff::Insert { index }
+                                if !replaced_insertions.contains(&insert_index)
+                                    && is_renamed(&old[old_index], &new[index]) =>
+                            {
+                                Some((insert_index, index))
+                            }
+                            _ => None,
+                        });
+                if let Some((insert_index, new_index)) = insertion {
+                    replaced_insertions.insert(insert_index);
+                    edits.push((diff_index, old_index, new_index));
+                }
+            }
+            Diff::Move {
+                old_index,
+                new_index,
+            } if is_renamed(&old[old_index], &new[new_index]) => {
+                edits.push((diff_index, old_index, new_index));
+            }
+            _ => (),
         }
     }
+
+    for (diff_index, old_index, new_index) in edits {
+        diff[diff_index] = edit(old_index, new_index);
+    }
+    *diff = std::mem::take(diff)
+        .into_iter()
+        .enumerate()
+        .filter(|(diff_index, _)| !replaced_insertions.contains(diff_index))
+        .map(|(_, entry)| entry)
+        .collect();
+}
+
+impl<'a, T> MigrationValue<'a, T>
+where
+    T: TypeDesc + TypeFields<T>,
+{
+    /// Constructs a value of type `ty` that is stored at `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to memory that contains a value of type `ty` and that remains valid for
+    /// the lifetime `'a`.
+    pub unsafe fn new(ty: &'a T, ptr: NonNull<u8>) -> Self {
+        Self {
+            ty,
+            ptr,
+            _lifetime: PhantomData,
+        }
+    }
+
+    /// Returns the type of the value.
+    pub fn ty(&self) -> &T {
+        self.ty
+    }
+
+    /// Returns a pointer to the memory of the value.
+    pub fn as_ptr(&self) -> NonNull<u8> {
+        self.ptr
+    }
+
+    /// Returns the type of the field corresponding to the specified `field_name` and a pointer to
+    /// its memory.
+    pub fn field(&self, field_name: &str) -> Result<(T, NonNull<u8>), String> {
+        let offsets = self.ty.offsets();
+        self.ty
+            .fields()
+            .into_iter()
+            .zip(offsets.iter())
+            .find(|((name, _), _)| *name == field_name)
+            .map(|((_, field_ty), offset)| {
+                // Safety: the offset of a field always lies within the memory of its value
+                let ptr = unsafe { self.ptr.as_ptr().add(usize::from(*offset)) };
+                (field_ty, unsafe { NonNull::new_unchecked(ptr) })
+            })
+            .ok_or_else(|| {
+                format!(
+                    "Struct `{}` does not contain field `{}`.",
+                    self.ty.name(),
+                    field_name
+                )
+            })
+    }
+
+    /// Retrieves the value of the field corresponding to the specified `field_name`.
+    pub fn get<V: HasStaticTypeInfo + Copy>(&self, field_name: &str) -> Result<V, String> {
+        let field_ptr = self.typed_field::<V>(field_name)?;
+        Ok(unsafe { field_ptr.as_ptr().read_unaligned() })
+    }
+
+    /// Replaces the value of the field corresponding to the specified `field_name`.
+    pub fn set<V: HasStaticTypeInfo + Copy>(
+        &mut self,
+        field_name: &str,
+        value: V,
+    ) -> Result<(), String> {
+        let field_ptr = self.typed_field::<V>(field_name)?;
+        unsafe { field_ptr.as_ptr().write_unaligned(value) };
+        Ok(())
+    }
+
+    /// Returns a pointer to the field corresponding to the specified `field_name`, if its type is
+    /// `V`.
+    fn typed_field<V: HasStaticTypeInfo>(&self, field_name: &str) -> Result<NonNull<V>, String> {
+        let (field_ty, field_ptr) = self.field(field_name)?;
+        let expected = V::type_info();
+        if *field_ty.guid() != expected.guid {
+            return Err(format!(
+                "Mismatched types for `{}::{}`. Expected: `{}`. Found: `{}`.",
+                self.ty.name(),
+                field_name,
+                expected.name(),
+                field_ty.name(),
+            ));
+        }
+        Ok(field_ptr.cast::<V>())
+    }
+}
+
+/// Returns the field renames in the `diff` of `old_ty` and `new_ty` that were guessed, i.e. that
+/// were not declared by the fields of `new_ty`.
+fn find_guessed_renames<T: TypeDesc + TypeFields<T>>(
+    old_ty: T,
+    new_ty: T,
+    diff: &[FieldDiff],
+) -> Vec<GuessedRename> {
+    let old_fields = old_ty.fields();
+    let new_fields = new_ty.fields();
+    let renamed_from = new_ty.renamed_from();
+
+    diff.iter()
+        .filter_map(|diff| match diff {
+            FieldDiff::Edit {
+                index,
+                kind: FieldEditKind::Rename,
+            } => Some((*index, *index)),
+            FieldDiff::Move {
+                old_index,
+                new_index,
+                edit: Some(FieldEditKind::Rename),
+            } => Some((*old_index, *new_index)),
+            _ => None,
+        })
+        .map(|(old_index, new_index)| (old_fields[old_index].0, new_fields[new_index].0, new_index))
+        .filter(|(old_name, _, new_index)| renamed_from.get(*new_index) != Some(&Some(*old_name)))
+        .map(|(old_name, new_name, _)| GuessedRename {
+            type_name: new_ty.name().to_owned(),
+            old_name: old_name.to_owned(),
+            new_name: new_name.to_owned(),
+        })
+        .collect()
+}
+
+/// Returns the field renames of `new_ty` that were declared, but that did not match a field in the
+/// `diff` of the old type and `new_ty`.
+fn find_unmatched_renames<T: TypeDesc + TypeFields<T>>(
+    new_ty: T,
+    diff: &[FieldDiff],
+) -> Vec<UnmatchedRename> {
+    let new_fields = new_ty.fields();
+    let renamed_from = new_ty.renamed_from();
+
+    diff.iter()
+        .filter_map(|diff| match diff {
+            FieldDiff::Insert { index } => renamed_from
+                .get(*index)
+                .cloned()
+                .flatten()
+                .map(|old_name| (old_name, new_fields[*index].0)),
+            _ => None,
+        })
+        .map(|(old_name, new_name)| UnmatchedRename {
+            type_name: new_ty.name().to_owned(),
+            old_name: old_name.to_owned(),
+            new_name: new_name.to_owned(),
+        })
+        .collect()
 }
 
 /// Given a set of `old_fields` of type `T` and their corresponding `diff`, calculates the mapping
@@ -144,8 +464,165 @@ pub unsafe fn field_mapping<T: Clone + TypeDesc + TypeFields<T> + TypeMemory>(
     new_ty: T,
     diff: &[FieldDiff],
 ) -> Conversion<T> {
-    let old_fields = old_ty.fields();
+    let field_mapping = map_fields(
+        &old_ty.fields(),
+        old_ty.offsets(),
+        &new_ty.fields(),
+        new_ty.offsets(),
+        diff,
+    );
 
+    Conversion {
+        field_mapping,
+        variant_mapping: Vec::new(),
+        new_ty,
+    }
+}
+
+/// Given an `old_ty` and a `new_ty` enum, calculates the mapping of each old variant to a new
+/// variant. Variants are matched by name. The values of a deleted variant are mapped to the first
+/// new variant, whose fields are inserted.
+pub fn variant_mapping<T: Clone + Eq + TypeDesc + TypeFields<T> + TypeMemory>(
+    old_ty: T,
+    new_ty: T,
+) -> Conversion<T> {
+    let new_variants = new_ty.variants();
+    let variant_mapping = old_ty
+        .variants()
+        .into_iter()
+        .map(|(old_name, old_fields, old_offsets)| {
+            let new_variant = new_variants
+                .iter()
+                .enumerate()
+                .find(|(_, (new_name, _, _))| *new_name == old_name);
+
+            if let Some((new_tag, (_, new_fields, new_offsets))) = new_variant {
+                let diff = field_diff(&old_fields, new_fields, &[]);
+                VariantMapping {
+                    new_tag: new_tag as u8,
+                    // Safety: `diff` is based on `old_fields` and `new_fields`
+                    field_mapping: unsafe {
+                        map_fields(&old_fields, old_offsets, new_fields, new_offsets, &diff)
+                    },
+                }
+            } else {
+                VariantMapping {
+                    new_tag: 0,
+                    field_mapping: new_variants
+                        .first()
+                        .map(|(_, new_fields, new_offsets)| {
+                            new_fields
+                                .iter()
+                                .zip(new_offsets.iter())
+                                .map(|((_, new_ty), new_offset)| FieldMapping {
+                                    new_ty: new_ty.clone(),
+                                    new_offset: usize::from(*new_offset),
+                                    action: Action::Insert,
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                }
+            }
+        })
+        .collect();
+
+    Conversion {
+        field_mapping: Vec::new(),
+        variant_mapping,
+        new_ty,
+    }
+}
+
+/// Migrates the value of a global variable of type `old_ty` at `src` to a global variable of type
+/// `new_ty` at `dest`. Similar to struct fields, the value is copied if the types are identical and
+/// cast if a lossless conversion exists. The value of a global variable of which the struct type
+/// changed is migrated by the user-defined migration of its old type, if any. Returns whether the
+/// value was migrated; otherwise `dest` retains its initial value.
+///
+/// The objects of a heap-allocated struct type are migrated by a `MemoryMapper`, so only the
+/// handle of such an object is copied.
+///
+/// # Safety
+///
+/// Expects `src` and `dest` to point to valid values of type `old_ty` and `new_ty`, respectively.
+/// If not, it causes undefined behavior.
+pub unsafe fn map_global<T: TypeDesc + TypeFields<T> + TypeMemory>(
+    old_ty: &T,
+    new_ty: &T,
+    src: NonNull<u8>,
+    dest: NonNull<u8>,
+    migrations: &Migrations<T>,
+) -> bool {
+    if old_ty.guid() == new_ty.guid() {
+        std::ptr::copy_nonoverlapping(src.as_ptr(), dest.as_ptr(), new_ty.layout().size());
+        return true;
+    }
+
+    let migration = migrations
+        .get(old_ty.name())
+        .filter(|migration| migration.new_type_name == new_ty.name());
+    let is_converted = old_ty.group().is_struct()
+        && new_ty.group().is_struct()
+        && (old_ty.name() == new_ty.name() || migration.is_some());
+
+    if !is_converted {
+        cast::try_cast_from_to(*old_ty.guid(), *new_ty.guid(), src, dest)
+    } else if !old_ty.is_stack_allocated() && !new_ty.is_stack_allocated() {
+        *dest.cast::<GcPtr>().as_ptr() = *src.cast::<GcPtr>().as_ptr();
+        true
+    } else if old_ty.is_stack_allocated() && new_ty.is_stack_allocated() {
+        migration.map_or(false, |migration| {
+            apply_migration(&*migration.migration, old_ty, new_ty, src, dest)
+        })
+    } else {
+        false
+    }
+}
+
+/// Applies the user-defined `migration` to the old value of type `old_ty` at `src` and the new
+/// value of type `new_ty` at `dest`. If the migration panics, the new value is restored to the value
+/// that it had before the migration. Returns whether the migration succeeded.
+///
+/// # Safety
+///
+/// Expects `src` and `dest` to point to valid values of type `old_ty` and `new_ty`, respectively.
+/// If not, it causes undefined behavior.
+pub unsafe fn apply_migration<T: TypeDesc + TypeFields<T> + TypeMemory>(
+    migration: &Migration<T>,
+    old_ty: &T,
+    new_ty: &T,
+    src: NonNull<u8>,
+    dest: NonNull<u8>,
+) -> bool {
+    let size = new_ty.layout().size();
+    let mut mapped = vec![0u8; size];
+    std::ptr::copy_nonoverlapping(dest.as_ptr(), mapped.as_mut_ptr(), size);
+
+    let old_value = MigrationValue::new(old_ty, src);
+    let mut new_value = MigrationValue::new(new_ty, dest);
+    // The panic is reported by the panic hook, after which the default conversion is used instead
+    let result = panic::catch_unwind(AssertUnwindSafe(|| migration(&old_value, &mut new_value)));
+    if result.is_err() {
+        std::ptr::copy_nonoverlapping(mapped.as_ptr(), dest.as_ptr(), size);
+    }
+    result.is_ok()
+}
+
+/// Given a set of `old_fields` and their offsets, a set of `new_fields` and their offsets, and the
+/// corresponding `diff`, calculates the `FieldMapping` for each new field.
+///
+/// # Safety
+///
+/// Expects the `diff` to be based on `old_fields` and `new_fields`. If not, it causes undefined
+/// behavior.
+unsafe fn map_fields<T: Clone + TypeDesc + TypeMemory>(
+    old_fields: &[(&str, T)],
+    old_offsets: &[u16],
+    new_fields: &[(&str, T)],
+    new_offsets: &[u16],
+    diff: &[FieldDiff],
+) -> Vec<FieldMapping<T>> {
     let deletions: HashSet<usize> = diff
         .iter()
         .filter_map(|diff| match diff {
@@ -167,6 +644,14 @@ pub unsafe fn field_mapping<T: Clone + TypeDesc + TypeFields<T> + TypeMemory>(
         Insert,
     }
 
+    /// Returns the action that corresponds to the `kind` of edit of a field.
+    fn edit_action(kind: &FieldEditKind) -> ActionDesc {
+        match kind {
+            FieldEditKind::ConvertType | FieldEditKind::RenameAndConvertType => ActionDesc::Cast,
+            FieldEditKind::Rename => ActionDesc::Copy,
+        }
+    }
+
     // Add mappings for all `old_fields`, unless they were deleted or moved.
     let mut mapping: Vec<FieldMappingDesc> = (0..old_fields.len())
         .filter_map(|idx| {
@@ -201,13 +686,7 @@ pub unsafe fn field_mapping<T: Clone + TypeDesc + TypeFields<T> + TypeMemory>(
                 *new_index,
                 FieldMappingDesc {
                     old_index: Some(*old_index),
-                    action: edit.as_ref().map_or(ActionDesc::Copy, |kind| {
-                        if *kind == FieldEditKind::ConvertType {
-                            ActionDesc::Cast
-                        } else {
-                            ActionDesc::Copy
-                        }
-                    }),
+                    action: edit.as_ref().map_or(ActionDesc::Copy, edit_action),
                 },
             )),
             FieldDiff::Delete { .. } | FieldDiff::Edit { .. } => None,
@@ -224,49 +703,40 @@ pub unsafe fn field_mapping<T: Clone + TypeDesc + TypeFields<T> + TypeMemory>(
     for diff in diff.iter() {
         if let FieldDiff::Edit { index, kind } = diff {
             let map = mapping.get_mut(*index).unwrap();
-            map.action = if *kind == FieldEditKind::ConvertType {
-                ActionDesc::Cast
-            } else {
-                ActionDesc::Copy
-            };
+            map.action = edit_action(kind);
         }
     }
 
-    let new_fields = new_ty.fields();
-    let old_offsets = old_ty.offsets();
-    let new_offsets = new_ty.offsets();
-    Conversion {
-        field_mapping: mapping
-            .into_iter()
-            .enumerate()
-            .map(|(new_index, desc)| {
-                let old_offset = desc
-                    .old_index
-                    .map(|idx| usize::from(*old_offsets.get_unchecked(idx)));
-
-                FieldMapping {
-                    new_ty: new_fields.get_unchecked(new_index).1.clone(),
-                    new_offset: usize::from(*new_offsets.get_unchecked(new_index)),
-                    action: match desc.action {
-                        ActionDesc::Cast => Action::Cast {
-                            old_offset: old_offset.unwrap(),
-                            old_ty: old_fields.get_unchecked(desc.old_index.unwrap()).1.clone(),
-                        },
-                        ActionDesc::Copy => Action::Copy {
-                            old_offset: old_offset.unwrap(),
-                        },
-                        ActionDesc::Insert => Action::Insert,
+    mapping
+        .into_iter()
+        .enumerate()
+        .map(|(new_index, desc)| {
+            let old_offset = desc
+                .old_index
+                .map(|idx| usize::from(*old_offsets.get_unchecked(idx)));
+
+            FieldMapping {
+                new_ty: new_fields.get_unchecked(new_index).1.clone(),
+                new_offset: usize::from(*new_offsets.get_unchecked(new_index)),
+                action: match desc.action {
+                    ActionDesc::Cast => Action::Cast {
+                        old_offset: old_offset.unwrap(),
+                        old_ty: old_fields.get_unchecked(desc.old_index.unwrap()).1.clone(),
                     },
-                }
-            })
-            .collect(),
-        new_ty,
-    }
+                    ActionDesc::Copy => Action::Copy {
+                        old_offset: old_offset.unwrap(),
+                    },
+                    ActionDesc::Insert => Action::Insert,
+                },
+            }
+        })
+        .collect()
 }
 
 /// A trait used to map allocated memory using type differences.
 pub trait MemoryMapper<T: Eq + Hash + TypeDesc + TypeMemory> {
-    /// Maps its allocated memory using the provided `mapping`.
+    /// Maps its allocated memory using the provided `mapping`. The migrations of the `mapping`
+    /// are applied to each converted object, before the memory of its old value is released.
     ///
     /// A `Vec<GcPtr>` is returned containing all objects of types that were deleted. The
     /// corresponding types have to remain in-memory until the objects have been deallocated.
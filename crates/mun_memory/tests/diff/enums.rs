@@ -0,0 +1,149 @@
+use super::util::*;
+use mun_memory::diff::{diff, Diff};
+
+fn assert_eq_enum(result: &[TypeInfo], expected: &[TypeInfo]) {
+    assert_eq!(result.len(), expected.len());
+    for (lhs, rhs) in result.iter().zip(expected.iter()) {
+        assert_eq!(lhs.group, rhs.group);
+        assert_eq!(lhs.layout, rhs.layout);
+        assert_eq!(lhs.tail, rhs.tail);
+    }
+}
+
+#[test]
+fn add() {
+    let int = TypeInfo::new_fundamental::<i64>();
+    let float = TypeInfo::new_fundamental::<f64>();
+
+    let enum1 = TypeInfo::new_enum(
+        ENUM1_NAME,
+        ENUM1_GUID,
+        EnumInfo::new(&[
+            ("A", StructInfo::new(&[("0", &int)])),
+            ("B", StructInfo::new(&[("x", &float), ("y", &float)])),
+        ]),
+    );
+
+    let old = &[&int];
+    let new = &[&int, &enum1];
+
+    let diff = diff(old, new);
+    assert_eq!(diff, vec![Diff::Insert { index: 1 }]);
+    assert_eq_enum(&apply_diff(old, new, diff), &[int.clone(), enum1.clone()]);
+}
+
+#[test]
+fn remove() {
+    let int = TypeInfo::new_fundamental::<i64>();
+
+    let enum1 = TypeInfo::new_enum(
+        ENUM1_NAME,
+        ENUM1_GUID,
+        EnumInfo::new(&[
+            ("A", StructInfo::new(&[("0", &int)])),
+            ("B", StructInfo::new(&[])),
+        ]),
+    );
+
+    let old = &[&int, &enum1];
+    let new = &[&int];
+
+    let diff = diff(old, new);
+    assert_eq!(diff, vec![Diff::Delete { index: 1 }]);
+    assert_eq_enum(&apply_diff(old, new, diff), &[int.clone()]);
+}
+
+#[test]
+fn edit_variant() {
+    let int = TypeInfo::new_fundamental::<i64>();
+    let float = TypeInfo::new_fundamental::<f64>();
+
+    let enum1 = TypeInfo::new_enum(
+        ENUM1_NAME,
+        ENUM1_GUID,
+        EnumInfo::new(&[
+            ("A", StructInfo::new(&[("0", &int)])),
+            ("B", StructInfo::new(&[("x", &float), ("y", &float)])),
+            ("C", StructInfo::new(&[])),
+        ]),
+    );
+    let enum2 = TypeInfo::new_enum(
+        ENUM1_NAME,
+        ENUM2_GUID,
+        EnumInfo::new(&[
+            ("A", StructInfo::new(&[("0", &int)])),
+            ("B", StructInfo::new(&[("x", &float), ("y", &int)])),
+            ("C", StructInfo::new(&[])),
+        ]),
+    );
+
+    let old = &[&enum1];
+    let new = &[&enum2];
+
+    let diff = diff(old, new);
+    assert_eq!(
+        diff,
+        vec![Diff::Edit {
+            diff: Vec::new(),
+            old_index: 0,
+            new_index: 0,
+        }]
+    );
+    assert_eq_enum(&apply_diff(old, new, diff), &[enum2.clone()]);
+}
+
+#[test]
+fn replace() {
+    let int = TypeInfo::new_fundamental::<i64>();
+    let float = TypeInfo::new_fundamental::<f64>();
+
+    let enum1 = TypeInfo::new_enum(
+        ENUM1_NAME,
+        ENUM1_GUID,
+        EnumInfo::new(&[
+            ("A", StructInfo::new(&[("0", &int)])),
+            ("B", StructInfo::new(&[("x", &float)])),
+        ]),
+    );
+    let enum2 = TypeInfo::new_enum(
+        ENUM2_NAME,
+        ENUM2_GUID,
+        EnumInfo::new(&[
+            ("C", StructInfo::new(&[("a", &float), ("b", &float)])),
+            ("D", StructInfo::new(&[])),
+            ("E", StructInfo::new(&[("c", &int)])),
+        ]),
+    );
+
+    let old = &[&enum1];
+    let new = &[&enum2];
+
+    let diff = diff(old, new);
+    assert_eq!(
+        diff,
+        vec![Diff::Delete { index: 0 }, Diff::Insert { index: 0 }]
+    );
+    assert_eq_enum(&apply_diff(old, new, diff), &[enum2.clone()]);
+}
+
+#[test]
+fn struct_to_enum() {
+    let int = TypeInfo::new_fundamental::<i64>();
+
+    let struct1 = TypeInfo::new_struct(STRUCT1_NAME, STRUCT1_GUID, StructInfo::new(&[("a", &int)]));
+    let enum1 = TypeInfo::new_enum(
+        STRUCT1_NAME,
+        ENUM1_GUID,
+        EnumInfo::new(&[("A", StructInfo::new(&[("a", &int)]))]),
+    );
+
+    let old = &[&struct1];
+    let new = &[&enum1];
+
+    let diff = diff(old, new);
+    assert_eq!(
+        diff,
+        vec![Diff::Delete { index: 0 }, Diff::Insert { index: 0 }]
+    );
+    assert_eq_enum(&apply_diff(old, new, diff), &[enum1.clone()]);
+}
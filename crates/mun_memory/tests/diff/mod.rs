@@ -1,3 +1,4 @@
+mod enums;
 mod myers;
 mod primitives;
 mod structs;
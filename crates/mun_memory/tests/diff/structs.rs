@@ -509,3 +509,111 @@ fn rename_field2() {
     );
     assert_eq_struct(&apply_diff(old, new, diff), &vec![struct2.clone()]);
 }
+
+#[test]
+fn explicit_rename_fields() {
+    let int = TypeInfo::new_fundamental::<i64>();
+
+    let struct1 = TypeInfo::new_struct(
+        STRUCT1_NAME,
+        STRUCT1_GUID,
+        StructInfo::new(&[("a", &int), ("b", &int)]),
+    );
+    // Without the declared renames, `a` and `b` would be guessed to be renamed in-place
+    let struct2 = TypeInfo::new_struct(
+        STRUCT1_NAME,
+        STRUCT2_GUID,
+        StructInfo::new(&[("c", &int), ("d", &int)])
+            .rename("c", "b")
+            .rename("d", "a"),
+    );
+
+    let old = &[&struct1];
+    let new = &[&struct2];
+
+    let diff = diff(old, new);
+    assert_eq!(
+        diff,
+        vec![Diff::Edit {
+            diff: vec![
+                FieldDiff::Move {
+                    old_index: 0,
+                    new_index: 1,
+                    edit: Some(FieldEditKind::Rename),
+                },
+                FieldDiff::Move {
+                    old_index: 1,
+                    new_index: 0,
+                    edit: Some(FieldEditKind::Rename),
+                }
+            ],
+            old_index: 0,
+            new_index: 0,
+        }]
+    );
+    assert_eq_struct(&apply_diff(old, new, diff), &vec![struct2.clone()]);
+}
+
+#[test]
+fn explicit_rename_unmatched_field() {
+    let int = TypeInfo::new_fundamental::<i64>();
+
+    let struct1 = TypeInfo::new_struct(STRUCT1_NAME, STRUCT1_GUID, StructInfo::new(&[("a", &int)]));
+    // The declared previous name does not exist, so `a` must not be guessed to be renamed to `b`
+    let struct2 = TypeInfo::new_struct(
+        STRUCT1_NAME,
+        STRUCT2_GUID,
+        StructInfo::new(&[("b", &int)]).rename("b", "c"),
+    );
+
+    let old = &[&struct1];
+    let new = &[&struct2];
+
+    let diff = diff(old, new);
+    assert_eq!(
+        diff,
+        vec![Diff::Edit {
+            diff: vec![
+                FieldDiff::Delete { index: 0 },
+                FieldDiff::Insert { index: 0 }
+            ],
+            old_index: 0,
+            new_index: 0,
+        }]
+    );
+    assert_eq_struct(&apply_diff(old, new, diff), &vec![struct2.clone()]);
+}
+
+#[test]
+fn explicit_rename_and_convert_field() {
+    let int = TypeInfo::new_fundamental::<i64>();
+    let float = TypeInfo::new_fundamental::<f64>();
+
+    let struct1 = TypeInfo::new_struct(
+        STRUCT1_NAME,
+        STRUCT1_GUID,
+        StructInfo::new(&[("a", &float), ("b", &int)]),
+    );
+    let struct2 = TypeInfo::new_struct(
+        STRUCT1_NAME,
+        STRUCT2_GUID,
+        StructInfo::new(&[("c", &int), ("b", &int)]).rename("c", "a"),
+    );
+
+    let old = &[&struct1];
+    let new = &[&struct2];
+
+    let diff = diff(old, new);
+    assert_eq!(
+        diff,
+        vec![Diff::Edit {
+            diff: vec![FieldDiff::Edit {
+                index: 0,
+                kind: FieldEditKind::RenameAndConvertType,
+            }],
+            old_index: 0,
+            new_index: 0,
+        }]
+    );
+    assert_eq_struct(&apply_diff(old, new, diff), &vec![struct2.clone()]);
+}
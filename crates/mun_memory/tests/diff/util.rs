@@ -17,10 +17,23 @@ pub const STRUCT2_GUID: abi::Guid = abi::Guid {
         150, 140, 130, 120, 110, 100, 90, 80, 70, 60, 50, 40, 30, 20, 10, 0,
     ],
 };
+pub const ENUM1_NAME: &str = "enum1";
+pub const ENUM1_GUID: abi::Guid = abi::Guid {
+    b: [
+        5, 15, 25, 35, 45, 55, 65, 75, 85, 95, 105, 115, 125, 135, 145, 155,
+    ],
+};
+pub const ENUM2_NAME: &str = "enum2";
+pub const ENUM2_GUID: abi::Guid = abi::Guid {
+    b: [
+        155, 145, 135, 125, 115, 105, 95, 85, 75, 65, 55, 45, 35, 25, 15, 5,
+    ],
+};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct StructInfo {
     fields: Vec<(String, TypeInfo)>,
+    renamed_from: Vec<Option<String>>,
 }
 
 impl StructInfo {
@@ -30,9 +43,22 @@ impl StructInfo {
                 .iter()
                 .map(|(name, ty)| (name.to_string(), (*ty).clone()))
                 .collect(),
+            renamed_from: Vec::new(),
         }
     }
 
+    /// Declares that the field called `name` was previously called `renamed_from`.
+    pub fn rename(mut self, name: &str, renamed_from: &str) -> Self {
+        let index = self
+            .fields
+            .iter()
+            .position(|(field_name, _)| field_name == name)
+            .expect("unknown field");
+        self.renamed_from.resize(self.fields.len(), None);
+        self.renamed_from[index] = Some(renamed_from.to_string());
+        self
+    }
+
     pub fn layout(&self) -> Layout {
         // NOTE: This implementation is naive, but it is merely a test
         let size = self.fields.iter().map(|ty| ty.1.layout.size()).sum();
@@ -46,10 +72,39 @@ impl StructInfo {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EnumInfo {
+    variants: Vec<(String, StructInfo)>,
+}
+
+impl EnumInfo {
+    pub fn new(variants: &[(&str, StructInfo)]) -> Self {
+        Self {
+            variants: variants
+                .iter()
+                .map(|(name, variant)| (name.to_string(), variant.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn layout(&self) -> Layout {
+        // NOTE: This implementation is naive, but it is merely a test
+        let (size, align) = self
+            .variants
+            .iter()
+            .map(|(_, variant)| variant.layout())
+            .fold((0, 1), |(size, align), layout| {
+                (size.max(layout.size()), align.max(layout.align()))
+            });
+        unsafe { Layout::from_size_align_unchecked(size + align, align) }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum TypeInfoTail {
     Empty,
     Struct(StructInfo),
+    Enum(EnumInfo),
 }
 
 #[derive(Clone, Debug)]
@@ -82,6 +137,16 @@ impl TypeInfo {
             tail: TypeInfoTail::Struct(struct_info),
         }
     }
+
+    pub fn new_enum(name: &str, guid: abi::Guid, enum_info: EnumInfo) -> Self {
+        Self {
+            name: name.to_string(),
+            guid,
+            group: abi::TypeGroup::EnumTypes,
+            layout: enum_info.layout(),
+            tail: TypeInfoTail::Enum(enum_info),
+        }
+    }
 }
 
 // TODO: Change Guid to be a hash of field names and field types. For fundamental types, their
@@ -115,12 +180,17 @@ impl TypeMemory for &TypeInfo {
         // NOTE: This contrived test does not support structs
         true
     }
+
+    fn element_layout(&self) -> Option<Layout> {
+        // NOTE: This contrived test does not support arrays
+        None
+    }
 }
 
 impl<'t> TypeFields<&'t TypeInfo> for &'t TypeInfo {
     fn fields(&self) -> Vec<(&str, Self)> {
         match &self.tail {
-            TypeInfoTail::Empty => Vec::new(),
+            TypeInfoTail::Empty | TypeInfoTail::Enum(_) => Vec::new(),
             TypeInfoTail::Struct(s) => s
                 .fields
                 .iter()
@@ -133,6 +203,37 @@ impl<'t> TypeFields<&'t TypeInfo> for &'t TypeInfo {
         // This is a stub, as we don't do any actual memory mapping
         &[]
     }
+
+    fn renamed_from(&self) -> Vec<Option<&str>> {
+        match &self.tail {
+            TypeInfoTail::Struct(s) => s
+                .renamed_from
+                .iter()
+                .map(|name| name.as_ref().map(String::as_str))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn variants(&self) -> Vec<(&str, Vec<(&str, Self)>, &[u16])> {
+        match &self.tail {
+            TypeInfoTail::Enum(e) => e
+                .variants
+                .iter()
+                .map(|(name, variant)| {
+                    let fields = variant
+                        .fields
+                        .iter()
+                        .map(|(name, ty)| (name.as_str(), ty))
+                        .collect();
+
+                    // This is a stub, as we don't do any actual memory mapping
+                    (name.as_str(), fields, &[][..])
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
 }
 
 pub fn apply_myers_diff<'t, T: Copy + Eq>(old: &[T], new: &[T], diff: Vec<myers::Diff>) -> Vec<T> {
@@ -197,6 +298,13 @@ pub(crate) fn apply_diff<'t>(
 }
 
 fn apply_mapping<'t>(old: &mut TypeInfo, new: &TypeInfo, mapping: &[FieldDiff]) {
+    // The variants of an enum are mapped when its memory is mapped, so the diff only indicates
+    // that the enum was edited.
+    if let TypeInfoTail::Enum(_) = &old.tail {
+        *old = new.clone();
+        return;
+    }
+
     if let TypeInfoTail::Struct(old_struct) = &mut old.tail {
         if let TypeInfoTail::Struct(new_struct) = &new.tail {
             let mut combined: Vec<_> = old_struct.fields.iter().cloned().collect();
@@ -254,6 +362,7 @@ fn apply_mapping<'t>(old: &mut TypeInfo, new: &TypeInfo, mapping: &[FieldDiff])
                 match *kind {
                     FieldEditKind::ConvertType => old_field.1 = new_field.1.clone(),
                     FieldEditKind::Rename => old_field.0 = new_field.0.clone(),
+                    FieldEditKind::RenameAndConvertType => *old_field = new_field.clone(),
                 }
             }
 
@@ -266,12 +375,12 @@ fn apply_mapping<'t>(old: &mut TypeInfo, new: &TypeInfo, mapping: &[FieldDiff])
                         unsafe { new_struct.fields.get_unchecked(*index) },
                     ),
                     FieldDiff::Move {
-                        old_index,
                         new_index,
                         edit: Some(kind),
+                        ..
                     } => edit_field(
                         kind,
-                        unsafe { combined.get_unchecked_mut(*old_index) },
+                        unsafe { combined.get_unchecked_mut(*new_index) },
                         unsafe { new_struct.fields.get_unchecked(*new_index) },
                     ),
                     _ => (),
@@ -279,6 +388,7 @@ fn apply_mapping<'t>(old: &mut TypeInfo, new: &TypeInfo, mapping: &[FieldDiff])
             }
 
             old_struct.fields = combined;
+            old_struct.renamed_from = new_struct.renamed_from.clone();
             old.layout = old_struct.layout();
         } else {
             unreachable!()
@@ -1,5 +1,6 @@
 use super::util::{EventAggregator, HasTypeInfo, TypeInfo};
-use mun_memory::gc::{Event, GcRootPtr, GcRuntime, MarkSweep};
+use crate::assert_variant;
+use mun_memory::gc::{Event, GcRootPtr, GcRuntime, MarkSweep, TriggerPolicy};
 use std::sync::Arc;
 
 #[test]
@@ -63,3 +64,80 @@ fn collect_rooted() {
     assert_eq!(events.next(), Some(Event::End));
     assert_eq!(events.next(), None);
 }
+
+#[test]
+fn collect_stats() {
+    let runtime = Arc::new(MarkSweep::<&'static TypeInfo, EventAggregator<Event>>::default());
+    runtime.alloc(i64::type_info());
+    let rooted = GcRootPtr::new(&runtime, runtime.alloc(i64::type_info()));
+
+    let stats = runtime.stats();
+    assert_eq!(stats.allocated_objects, 2);
+    assert_eq!(stats.allocated_memory, 2 * std::mem::size_of::<i64>());
+    assert_eq!(stats.collections, 0);
+
+    // Only the unrooted object is freed
+    runtime.collect();
+    let stats = runtime.stats();
+    assert_eq!(stats.allocated_objects, 1);
+    assert_eq!(stats.collections, 1);
+    assert_eq!(stats.last_freed_memory, std::mem::size_of::<i64>());
+
+    drop(rooted);
+    runtime.collect();
+    let stats = runtime.stats();
+    assert_eq!(stats.allocated_objects, 0);
+    assert_eq!(stats.allocated_memory, 0);
+    assert_eq!(stats.collections, 2);
+    assert_eq!(stats.total_freed_memory, 2 * std::mem::size_of::<i64>());
+    assert!(stats.max_pause >= stats.last_pause);
+    assert!(stats.total_pause >= stats.max_pause);
+}
+
+#[test]
+fn allocation_threshold() {
+    let runtime = MarkSweep::<&'static TypeInfo, EventAggregator<Event>>::default()
+        .with_trigger_policy(TriggerPolicy {
+            allocation_threshold: Some(2 * std::mem::size_of::<i64>()),
+            growth_factor: None,
+        });
+
+    let handle = runtime.alloc(i64::type_info());
+    assert!(!runtime.collection_requested());
+    let handle2 = runtime.alloc(i64::type_info());
+    assert!(runtime.collection_requested());
+
+    // A requested collection is only performed when the owner of the collector asks for it
+    runtime.collect();
+    assert!(!runtime.collection_requested());
+
+    let mut events = runtime.observer().take_all().into_iter();
+    assert_eq!(events.next(), Some(Event::Allocation(handle)));
+    assert_eq!(events.next(), Some(Event::Allocation(handle2)));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_variant!(events.next(), Some(Event::Deallocation(..))); // Don't care about the order
+    assert_variant!(events.next(), Some(Event::Deallocation(..)));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), None);
+}
+
+#[test]
+fn growth_factor() {
+    let runtime = Arc::new(
+        MarkSweep::<&'static TypeInfo, EventAggregator<Event>>::default().with_trigger_policy(
+            TriggerPolicy {
+                allocation_threshold: None,
+                growth_factor: Some(2.0),
+            },
+        ),
+    );
+
+    // Growth is only measured after memory survived a collection
+    let _rooted = GcRootPtr::new(&runtime, runtime.alloc(i64::type_info()));
+    runtime.alloc(i64::type_info());
+    assert!(!runtime.collection_requested());
+    runtime.collect();
+
+    runtime.alloc(i64::type_info());
+    assert!(runtime.collection_requested());
+}
@@ -0,0 +1,102 @@
+use super::util::{EventAggregator, TypeInfo};
+use mun_memory::gc::{
+    array_layout, ArrayHeader, Event, GcPtr, GcRuntime, HasIndirectionPtr, MarkSweep,
+};
+use std::alloc::Layout;
+
+static I64: TypeInfo = TypeInfo {
+    size: std::mem::size_of::<i64>(),
+    alignment: std::mem::align_of::<i64>(),
+    tracer: None,
+    element: None,
+};
+
+static I64_ARRAY: TypeInfo = TypeInfo {
+    size: std::mem::size_of::<ArrayHeader>(),
+    alignment: std::mem::align_of::<ArrayHeader>(),
+    tracer: None,
+    element: Some(&I64),
+};
+
+#[test]
+fn array_layout_offset() {
+    let element = Layout::new::<i64>();
+    let (layout, offset) = array_layout(element, 3);
+
+    assert_eq!(offset, std::mem::size_of::<ArrayHeader>());
+    assert_eq!(layout.size(), offset + 3 * std::mem::size_of::<i64>());
+}
+
+#[test]
+fn alloc_array() {
+    let runtime = MarkSweep::<&'static TypeInfo, EventAggregator<Event>>::default();
+    let handle = runtime.alloc_array(&I64_ARRAY, 4);
+
+    let header = unsafe { *handle.deref::<ArrayHeader>() };
+    assert_eq!(
+        header,
+        ArrayHeader {
+            length: 0,
+            capacity: 4
+        }
+    );
+
+    let (layout, _) = array_layout(Layout::new::<i64>(), 4);
+    assert_eq!(runtime.stats().allocated_memory, layout.size());
+
+    runtime.collect();
+    assert_eq!(runtime.stats().allocated_memory, 0);
+
+    let mut events = runtime.observer().take_all().into_iter();
+    assert_eq!(events.next(), Some(Event::Allocation(handle)));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::Deallocation(handle)));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), None);
+}
+
+#[test]
+fn alloc_empty_array() {
+    let runtime = MarkSweep::<&'static TypeInfo, EventAggregator<Event>>::default();
+    let handle = runtime.alloc(&I64_ARRAY);
+
+    let header = unsafe { *handle.deref::<ArrayHeader>() };
+    assert_eq!(header, ArrayHeader::default());
+}
+
+#[test]
+fn reserve_array() {
+    let runtime = MarkSweep::<&'static TypeInfo, EventAggregator<Event>>::default();
+    let mut handle = runtime.alloc_array(&I64_ARRAY, 2);
+
+    let (_, offset) = array_layout(Layout::new::<i64>(), 2);
+    let elements =
+        |handle: &mut GcPtr| unsafe { handle.deref_mut::<u8>().add(offset).cast::<i64>() };
+
+    unsafe {
+        *elements(&mut handle) = 1;
+        *elements(&mut handle).add(1) = 2;
+        (*handle.deref_mut::<ArrayHeader>()).length = 2;
+    }
+
+    runtime.reserve_array(handle, 16);
+
+    let header = unsafe { *handle.deref::<ArrayHeader>() };
+    assert_eq!(
+        header,
+        ArrayHeader {
+            length: 2,
+            capacity: 16
+        }
+    );
+    assert_eq!(unsafe { *elements(&mut handle) }, 1);
+    assert_eq!(unsafe { *elements(&mut handle).add(1) }, 2);
+
+    let (layout, _) = array_layout(Layout::new::<i64>(), 16);
+    assert_eq!(runtime.stats().allocated_memory, layout.size());
+
+    // Reserving less than the current capacity does nothing
+    runtime.reserve_array(handle, 4);
+    let header = unsafe { *handle.deref::<ArrayHeader>() };
+    assert_eq!(header.capacity, 16);
+}
@@ -0,0 +1,185 @@
+use super::util::{EventAggregator, HasTypeInfo, Trace, TypeInfo};
+use crate::{assert_variant, impl_struct_ty};
+use mun_memory::gc::{
+    array_layout, Event, GcPtr, GcRootPtr, GcRuntime, Generational, HasIndirectionPtr,
+};
+use std::{alloc::Layout, sync::Arc, time::Duration};
+
+type GenerationalGc = Generational<&'static TypeInfo, EventAggregator<Event>>;
+
+struct Node {
+    next: GcPtr,
+}
+
+impl Trace for Node {
+    fn trace(&self, handles: &mut Vec<GcPtr>) {
+        handles.push(self.next)
+    }
+}
+
+impl_struct_ty!(Node);
+
+/// Allocates a node that references itself.
+fn alloc_node(runtime: &GenerationalGc) -> GcPtr {
+    let mut handle = runtime.alloc(Node::type_info());
+    unsafe { (*handle.deref_mut::<Node>()).next = handle };
+    handle
+}
+
+/// Sets the `next` field of the node `handle`, notifying the runtime of the store.
+fn set_next(runtime: &GenerationalGc, mut handle: GcPtr, next: GcPtr) {
+    unsafe { (*handle.deref_mut::<Node>()).next = next };
+    runtime.write_barrier(handle);
+}
+
+#[test]
+fn minor_collection() {
+    let runtime = Arc::new(GenerationalGc::default());
+    let handle = runtime.alloc(i64::type_info());
+    let mut rooted = GcRootPtr::new(&runtime, runtime.alloc(i64::type_info()));
+    unsafe { *rooted.deref_mut::<i64>() = 42 };
+
+    // The unreachable young object is collected and the rooted one is moved to the old generation
+    runtime.collect_minor();
+    assert_eq!(unsafe { *rooted.deref::<i64>() }, 42);
+
+    // Old objects are not collected by a minor collection, even if they are unreachable
+    let rooted_handle = rooted.unroot();
+    runtime.collect_minor();
+
+    runtime.collect();
+
+    let mut events = runtime.observer().take_all().into_iter();
+    assert_eq!(events.next(), Some(Event::Allocation(handle)));
+    assert_eq!(events.next(), Some(Event::Allocation(rooted_handle)));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::Deallocation(handle)));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::Deallocation(rooted_handle)));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), None);
+}
+
+#[test]
+fn minor_collection_stats() {
+    let runtime = Arc::new(GenerationalGc::default());
+    runtime.alloc(i64::type_info());
+    let _rooted = GcRootPtr::new(&runtime, runtime.alloc(i64::type_info()));
+
+    // Minor collections are counted like all other collections
+    runtime.collect_minor();
+    let stats = runtime.stats();
+    assert_eq!(stats.allocated_objects, 1);
+    assert_eq!(stats.collections, 1);
+    assert_eq!(stats.last_freed_memory, std::mem::size_of::<i64>());
+
+    runtime.collect_minor();
+    let stats = runtime.stats();
+    assert_eq!(stats.collections, 2);
+    assert_eq!(stats.last_freed_memory, 0);
+    assert_eq!(stats.total_freed_memory, std::mem::size_of::<i64>());
+}
+
+#[test]
+fn write_barrier_remembers_old_objects() {
+    let runtime = Arc::new(GenerationalGc::default());
+    let old = GcRootPtr::new(&runtime, alloc_node(&runtime));
+    runtime.collect_minor();
+
+    // The young node is only referenced by the old node
+    let young = alloc_node(&runtime);
+    set_next(&runtime, old.handle(), young);
+    runtime.collect_minor();
+
+    // The young node was moved to the old generation
+    let old = old.unroot();
+    runtime.collect();
+
+    let mut events = runtime.observer().take_all().into_iter();
+    assert_eq!(events.next(), Some(Event::Allocation(old)));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), Some(Event::Allocation(young)));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_variant!(events.next(), Some(Event::Deallocation(..))); // Don't care about the order
+    assert_variant!(events.next(), Some(Event::Deallocation(..)));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), None);
+}
+
+#[test]
+fn incremental_collection() {
+    let runtime = Arc::new(GenerationalGc::default());
+
+    // Construct a list that is too long to be marked by a single step
+    let head = GcRootPtr::new(&runtime, alloc_node(&runtime));
+    let mut nodes = vec![head.handle()];
+    for _ in 0..1000 {
+        let node = alloc_node(&runtime);
+        set_next(&runtime, *nodes.last().unwrap(), node);
+        nodes.push(node);
+    }
+    let garbage = GcRootPtr::new(&runtime, alloc_node(&runtime));
+    runtime.collect_minor();
+    let garbage = garbage.unroot();
+    runtime.observer().take_all();
+
+    // The head of the list has been marked, so storing a young node in it requires it to be
+    // traced again
+    runtime.collect_incremental(Duration::from_secs(0));
+    let young = alloc_node(&runtime);
+    set_next(&runtime, young, nodes[1]);
+    set_next(&runtime, head.handle(), young);
+
+    let mut steps = 1;
+    while !runtime.collect_incremental(Duration::from_secs(0)) {
+        steps += 1;
+        assert!(steps < nodes.len(), "incremental collection did not finish");
+    }
+    assert!(steps > 1);
+
+    // Only the unreachable node is collected
+    let events = runtime.observer().take_all();
+    let deallocations: Vec<&Event> = events
+        .iter()
+        .filter(|event| match event {
+            Event::Deallocation(_) => true,
+            _ => false,
+        })
+        .collect();
+    assert_eq!(deallocations, vec![&Event::Deallocation(garbage)]);
+}
+
+#[test]
+fn reserve_young_array() {
+    static I64_ARRAY: TypeInfo = TypeInfo {
+        size: std::mem::size_of::<mun_memory::gc::ArrayHeader>(),
+        alignment: std::mem::align_of::<mun_memory::gc::ArrayHeader>(),
+        tracer: None,
+        element: Some(&TYPE_I64),
+    };
+    static TYPE_I64: TypeInfo = TypeInfo {
+        size: std::mem::size_of::<i64>(),
+        alignment: std::mem::align_of::<i64>(),
+        tracer: None,
+        element: None,
+    };
+
+    let runtime = Arc::new(GenerationalGc::default());
+    let mut array = GcRootPtr::new(&runtime, runtime.alloc_array(&I64_ARRAY, 1));
+    let (_, offset) = array_layout(Layout::new::<i64>(), 1);
+    unsafe { *array.deref_mut::<u8>().add(offset).cast::<i64>() = 7 };
+
+    // The array is moved out of the nursery when it grows
+    runtime.reserve_array(array.handle(), 16);
+    runtime.collect_minor();
+    assert_eq!(unsafe { *array.deref::<u8>().add(offset).cast::<i64>() }, 7);
+
+    let (layout, _) = array_layout(Layout::new::<i64>(), 16);
+    assert_eq!(runtime.stats().allocated_memory, layout.size());
+}
@@ -1,4 +1,6 @@
 mod alloc;
+mod arrays;
+mod generational;
 mod structs;
 #[macro_use]
 mod util;
@@ -1,13 +1,14 @@
 #![allow(dead_code, unused_macros)]
 
+pub use mun_memory::gc::EventAggregator;
 use mun_memory::gc::{self, GcPtr};
-use parking_lot::Mutex;
 use std::alloc::Layout;
 
 pub struct TypeInfo {
     pub size: usize,
     pub alignment: usize,
     pub tracer: Option<&'static fn(handle: GcPtr) -> Vec<GcPtr>>,
+    pub element: Option<&'static TypeInfo>,
 }
 
 pub trait Trace {
@@ -29,7 +30,8 @@ macro_rules! impl_primitive_types {
                 static [<TYPE_ $ty>]: TypeInfo = TypeInfo {
                     size: std::mem::size_of::<$ty>(),
                     alignment: std::mem::align_of::<$ty>(),
-                    tracer: None
+                    tracer: None,
+                    element: None,
                 };
 
                 impl HasTypeInfo for $ty {
@@ -58,7 +60,8 @@ macro_rules! impl_struct_ty {
             static [<TYPE_ $ty>]: TypeInfo = TypeInfo {
                 size: std::mem::size_of::<$ty>(),
                 alignment: std::mem::align_of::<$ty>(),
-                tracer: Some(&([<trace_ $ty>] as fn(handle: GcPtr) -> Vec<GcPtr>))
+                tracer: Some(&([<trace_ $ty>] as fn(handle: GcPtr) -> Vec<GcPtr>)),
+                element: None,
             };
 
             impl HasTypeInfo for $ty {
@@ -82,6 +85,10 @@ impl mun_memory::TypeMemory for &'static TypeInfo {
         // NOTE: This contrived test does not support structs
         true
     }
+
+    fn element_layout(&self) -> Option<Layout> {
+        self.element.map(|element| element.layout())
+    }
 }
 
 impl gc::TypeTrace for &'static TypeInfo {
@@ -97,32 +104,6 @@ impl gc::TypeTrace for &'static TypeInfo {
     }
 }
 
-pub struct EventAggregator<T: Sync + Send + Sized> {
-    events: Mutex<Vec<T>>,
-}
-
-impl<T: Sync + Send + Sized> Default for EventAggregator<T> {
-    fn default() -> Self {
-        EventAggregator {
-            events: Mutex::new(Vec::new()),
-        }
-    }
-}
-
-impl<T: Sync + Send + Sized> EventAggregator<T> {
-    pub fn take_all(&self) -> Vec<T> {
-        self.events.lock().drain(..).collect()
-    }
-}
-
-impl<T: Sync + Send + Sized> gc::Observer for EventAggregator<T> {
-    type Event = T;
-
-    fn event(&self, event: T) {
-        self.events.lock().push(event)
-    }
-}
-
 #[macro_export]
 macro_rules! assert_variant {
     ($value:expr, $pattern:pat) => {{
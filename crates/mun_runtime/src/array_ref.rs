@@ -0,0 +1,337 @@
+use crate::garbage_collector::{inline_layout, GcPtr, GcRootPtr, UnsafeTypeInfo};
+use crate::{
+    marshal::{Marshal, MarshalArgument},
+    reflection::{
+        equals_argument_type, equals_return_type, ArgumentReflection, ReturnTypeReflection,
+    },
+    Runtime,
+};
+use memory::gc::{self, GcRuntime, HasIndirectionPtr};
+use std::cell::RefCell;
+use std::{
+    ptr::{self, NonNull},
+    rc::Rc,
+};
+
+/// Represents a Mun array pointer.
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct RawArray(GcPtr);
+
+impl RawArray {
+    /// Returns a pointer to the array memory.
+    pub unsafe fn get_ptr(&self) -> *const u8 {
+        self.0.deref()
+    }
+}
+
+/// Type-agnostic wrapper for interoperability with a Mun array.
+#[derive(Clone)]
+pub struct ArrayRef {
+    handle: GcRootPtr,
+    runtime: Rc<RefCell<Runtime>>,
+}
+
+impl ArrayRef {
+    /// Creates an `ArrayRef` that wraps a raw Mun array.
+    fn new(runtime: Rc<RefCell<Runtime>>, raw: RawArray) -> Self {
+        let handle = {
+            let runtime_ref = runtime.borrow();
+            // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+            // `Runtime` does not change. As we hold a shared reference to `Runtime`, this is safe.
+            assert!(unsafe {
+                runtime_ref
+                    .gc()
+                    .ptr_type(raw.0)
+                    .into_inner()
+                    .as_ref()
+                    .group
+                    .is_array()
+            });
+
+            GcRootPtr::new(&runtime_ref.gc, raw.0)
+        };
+
+        Self { runtime, handle }
+    }
+
+    /// Consumes the `ArrayRef`, returning a raw Mun array.
+    pub fn into_raw(self) -> RawArray {
+        RawArray(self.handle.handle())
+    }
+
+    /// Returns the type information of the array.
+    pub fn type_info<'r>(array_ref: &Self, runtime_ref: &'r Runtime) -> &'r abi::TypeInfo {
+        // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+        // `Runtime` does not change. As the lifetime of `TypeInfo` is tied to the lifetime of
+        // `Runtime`, this is safe.
+        unsafe {
+            &*runtime_ref
+                .gc
+                .ptr_type(array_ref.handle.handle())
+                .into_inner()
+                .as_ptr()
+        }
+    }
+
+    /// Returns the number of elements in the array.
+    pub fn len(&self) -> usize {
+        let runtime_ref = self.runtime.borrow();
+        let type_info = Self::type_info(self, &runtime_ref);
+
+        // Safety: `as_array` is guaranteed to return `Some` for `ArrayRef`s.
+        let array_info = type_info.as_array().unwrap();
+        if array_info.is_dynamic {
+            unsafe { (*self.handle.deref::<gc::ArrayHeader>()).length }
+        } else {
+            array_info.length as usize
+        }
+    }
+
+    /// Returns `true` if the array contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a pointer to the element at `index`, without doing bounds checking.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be smaller than the capacity of the array.
+    unsafe fn element_ptr_unchecked<T>(
+        &self,
+        array_info: &abi::ArrayInfo,
+        index: usize,
+    ) -> NonNull<T> {
+        let element_layout = inline_layout(array_info.element_type());
+        let elements_ptr = if array_info.is_dynamic {
+            let header = *self.handle.deref::<gc::ArrayHeader>();
+            let (_, offset) = gc::array_layout(element_layout, header.capacity);
+            self.handle.deref::<u8>().add(offset)
+        } else {
+            self.handle.deref::<u8>()
+        };
+
+        let offset = index * element_layout.pad_to_align().size();
+        // The handle is never null
+        NonNull::new_unchecked(elements_ptr.add(offset).cast::<T>() as *mut _)
+    }
+
+    /// Retrieves the value of the element at `index`.
+    pub fn get<T: ReturnTypeReflection>(&self, index: usize) -> Result<T, String> {
+        let len = self.len();
+        let runtime_ref = self.runtime.borrow();
+        let type_info = Self::type_info(self, &runtime_ref);
+
+        // Safety: `as_array` is guaranteed to return `Some` for `ArrayRef`s.
+        let array_info = type_info.as_array().unwrap();
+        if index >= len {
+            return Err(format!(
+                "Index out of bounds for `{}`: the len is {} but the index is {}.",
+                type_info.name(),
+                len,
+                index
+            ));
+        }
+
+        let element_type = array_info.element_type();
+        equals_return_type::<T>(element_type).map_err(|(expected, found)| {
+            format!(
+                "Mismatched types for `{}` element. Expected: `{}`. Found: `{}`.",
+                type_info.name(),
+                expected,
+                found,
+            )
+        })?;
+
+        let element_ptr = unsafe { self.element_ptr_unchecked::<T::Marshalled>(array_info, index) };
+        Ok(Marshal::marshal_from_ptr(
+            element_ptr,
+            self.runtime.clone(),
+            Some(element_type),
+        ))
+    }
+
+    /// Sets the value of the element at `index`.
+    pub fn set<T: ArgumentReflection>(&mut self, index: usize, value: T) -> Result<(), String> {
+        let len = self.len();
+        let runtime_ref = self.runtime.borrow();
+        let type_info = Self::type_info(self, &runtime_ref);
+
+        // Safety: `as_array` is guaranteed to return `Some` for `ArrayRef`s.
+        let array_info = type_info.as_array().unwrap();
+        if index >= len {
+            return Err(format!(
+                "Index out of bounds for `{}`: the len is {} but the index is {}.",
+                type_info.name(),
+                len,
+                index
+            ));
+        }
+
+        let element_type = array_info.element_type();
+        equals_argument_type(&runtime_ref, element_type, &value).map_err(|(expected, found)| {
+            format!(
+                "Mismatched types for `{}` element. Expected: `{}`. Found: `{}`.",
+                type_info.name(),
+                expected,
+                found,
+            )
+        })?;
+
+        let element_ptr = unsafe { self.element_ptr_unchecked::<T::Marshalled>(array_info, index) };
+        MarshalArgument::marshal_to_ptr(
+            value.marshal(&runtime_ref),
+            element_ptr,
+            Some(element_type),
+        );
+        runtime_ref.gc().write_barrier(self.handle.handle());
+        Ok(())
+    }
+
+    /// Appends `value` to the end of a dynamic array.
+    pub fn push<T: ArgumentReflection>(&mut self, value: T) -> Result<(), String> {
+        let runtime_ref = self.runtime.borrow();
+        let type_info = Self::type_info(self, &runtime_ref);
+
+        // Safety: `as_array` is guaranteed to return `Some` for `ArrayRef`s.
+        let array_info = type_info.as_array().unwrap();
+        if !array_info.is_dynamic {
+            return Err(format!(
+                "Cannot push to `{}`: the array has a fixed size.",
+                type_info.name()
+            ));
+        }
+
+        let element_type = array_info.element_type();
+        equals_argument_type(&runtime_ref, element_type, &value).map_err(|(expected, found)| {
+            format!(
+                "Mismatched types for `{}` element. Expected: `{}`. Found: `{}`.",
+                type_info.name(),
+                expected,
+                found,
+            )
+        })?;
+
+        let header = unsafe { *self.handle.deref::<gc::ArrayHeader>() };
+        if header.length == header.capacity {
+            runtime_ref
+                .gc()
+                .reserve_array(self.handle.handle(), (header.capacity * 2).max(4));
+        }
+
+        let element_ptr =
+            unsafe { self.element_ptr_unchecked::<T::Marshalled>(array_info, header.length) };
+        MarshalArgument::marshal_to_ptr(
+            value.marshal(&runtime_ref),
+            element_ptr,
+            Some(element_type),
+        );
+        runtime_ref.gc().write_barrier(self.handle.handle());
+        unsafe { (*self.handle.deref_mut::<gc::ArrayHeader>()).length += 1 };
+        Ok(())
+    }
+}
+
+impl ArgumentReflection for ArrayRef {
+    type Marshalled = RawArray;
+
+    fn type_guid(&self, runtime: &Runtime) -> abi::Guid {
+        // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+        // `Runtime` does not change. As we hold a shared reference to `Runtime`, this is safe.
+        unsafe {
+            runtime
+                .gc()
+                .ptr_type(self.handle.handle())
+                .into_inner()
+                .as_ref()
+                .guid
+        }
+    }
+
+    fn type_name(&self, runtime: &Runtime) -> &str {
+        // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+        // `Runtime` does not change. As we hold a shared reference to `Runtime`, this is safe.
+        unsafe {
+            (&*runtime
+                .gc()
+                .ptr_type(self.handle.handle())
+                .into_inner()
+                .as_ptr())
+                .name()
+        }
+    }
+
+    fn marshal(self, _runtime: &Runtime) -> Self::Marshalled {
+        self.into_raw()
+    }
+}
+
+impl ReturnTypeReflection for ArrayRef {
+    type Marshalled = RawArray;
+
+    fn type_name() -> &'static str {
+        "array"
+    }
+}
+
+impl Marshal<ArrayRef> for RawArray {
+    fn marshal_value(self, runtime: Rc<RefCell<Runtime>>) -> ArrayRef {
+        ArrayRef::new(runtime, self)
+    }
+
+    fn marshal_from_ptr(
+        ptr: NonNull<Self>,
+        runtime: Rc<RefCell<Runtime>>,
+        type_info: Option<&abi::TypeInfo>,
+    ) -> ArrayRef {
+        // `type_info` is only `None` for the `()` type
+        let type_info = type_info.unwrap();
+        let array_info = type_info.as_array().unwrap();
+
+        // Copy the contents of the array based on what kind of pointer we are dealing with
+        let gc_handle = if !array_info.is_dynamic {
+            // For a fixed-size array, `ptr` points to an array value.
+
+            // Create a new object using the runtime's intrinsic
+            let mut gc_handle = {
+                let runtime_ref = runtime.borrow();
+                runtime_ref.gc().alloc(
+                    // Safety: `ty` is a shared reference, so is guaranteed to not be `ptr::null()`.
+                    UnsafeTypeInfo::new(unsafe {
+                        NonNull::new_unchecked(type_info as *const abi::TypeInfo as *mut _)
+                    }),
+                )
+            };
+
+            // Construct
+            let src = ptr.cast::<u8>().as_ptr() as *const _;
+            let dest = unsafe { gc_handle.deref_mut::<u8>() };
+            let size = type_info.size_in_bytes();
+            unsafe { ptr::copy_nonoverlapping(src, dest, size) };
+
+            gc_handle
+        } else {
+            // For a dynamic array, `ptr` points to a `GcPtr`.
+            unsafe { *ptr.cast::<GcPtr>().as_ptr() }
+        };
+
+        ArrayRef::new(runtime, RawArray(gc_handle))
+    }
+}
+
+impl MarshalArgument<ArrayRef> for RawArray {
+    fn marshal_to_ptr(value: RawArray, mut ptr: NonNull<Self>, type_info: Option<&abi::TypeInfo>) {
+        // `type_info` is only `None` for the `()` type
+        let type_info = type_info.unwrap();
+
+        let array_info = type_info.as_array().unwrap();
+        if !array_info.is_dynamic {
+            let dest = ptr.cast::<u8>().as_ptr();
+            let size = type_info.size_in_bytes();
+            unsafe { ptr::copy_nonoverlapping(value.get_ptr(), dest, size as usize) };
+        } else {
+            unsafe { *ptr.as_mut() = value };
+        }
+    }
+}
@@ -9,8 +9,15 @@ mod temp_library;
 
 use self::temp_library::TempLibrary;
 use crate::garbage_collector::{GarbageCollector, UnsafeTypeInfo};
-use memory::mapping::{Mapping, MemoryMapper};
-use std::{collections::HashSet, ptr::NonNull, sync::Arc};
+use memory::mapping::{
+    map_global, GuessedRename, Mapping, MemoryMapper, Migrations, UnmatchedRename,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    mem,
+    ptr::NonNull,
+    sync::Arc,
+};
 
 /// An assembly is a hot reloadable compilation unit, consisting of one or more Mun modules.
 pub struct Assembly {
@@ -146,11 +153,15 @@ impl Assembly {
     }
 
     /// Swaps the assembly's shared library and its information for the library at `library_path`.
+    /// The user-defined `migrations` are applied to objects of which the type changed. Returns the
+    /// field renames that had to be guessed and the declared field renames that did not match a
+    /// field of the old type.
     pub fn swap(
         &mut self,
         library_path: &Path,
         runtime_dispatch_table: &mut DispatchTable,
-    ) -> Result<(), failure::Error> {
+        migrations: &Migrations<UnsafeTypeInfo>,
+    ) -> Result<(Vec<GuessedRename>, Vec<UnmatchedRename>), failure::Error> {
         let mut new_assembly =
             Assembly::load(library_path, self.allocator.clone(), runtime_dispatch_table)?;
 
@@ -180,9 +191,13 @@ impl Assembly {
             })
             .collect();
 
-        let mapping = Mapping::new(&old_types, &new_types);
+        let mut mapping = Mapping::with_migrations(&old_types, &new_types, migrations.clone());
+        let guessed_renames = mem::take(&mut mapping.guessed_renames);
+        let unmatched_renames = mem::take(&mut mapping.unmatched_renames);
         let deleted_objects = self.allocator.map_memory(mapping);
 
+        new_assembly.migrate_globals(&self.info, migrations);
+
         // Remove the old assembly's functions
         for function in self.info.symbols.functions() {
             runtime_dispatch_table.remove_fn(function.prototype.name());
@@ -201,7 +216,47 @@ impl Assembly {
             self.legacy_libs.push(old_assembly.into_library());
         }
 
-        Ok(())
+        Ok((guessed_renames, unmatched_renames))
+    }
+
+    /// Migrates the values of the mutable global variables in `old_info` to the corresponding
+    /// mutable global variables of this assembly. Global variables are matched by name. The
+    /// user-defined `migrations` are applied to global variables of which the type changed.
+    fn migrate_globals(&self, old_info: &AssemblyInfo, migrations: &Migrations<UnsafeTypeInfo>) {
+        let old_globals: HashMap<&str, &abi::GlobalInfo> = old_info
+            .symbols
+            .globals()
+            .iter()
+            .filter(|global| global.is_mutable)
+            .map(|global| (global.name(), global))
+            .collect();
+
+        for new_global in self.info.symbols.globals().iter() {
+            if !new_global.is_mutable {
+                continue;
+            }
+
+            if let Some(old_global) = old_globals.get(new_global.name()) {
+                let old_ty = UnsafeTypeInfo::new(NonNull::from(old_global.type_info()));
+                let new_ty = UnsafeTypeInfo::new(NonNull::from(new_global.type_info()));
+
+                // Safety: The Mun Compiler guarantees that the address of a global variable is
+                // never `ptr::null()` and points to a value of its type.
+                let migrated = unsafe {
+                    map_global(
+                        &old_ty,
+                        &new_ty,
+                        NonNull::new_unchecked(old_global.address.cast::<u8>()),
+                        NonNull::new_unchecked(new_global.address.cast::<u8>()),
+                        migrations,
+                    )
+                };
+
+                if !migrated {
+                    // Failed to cast. The global variable retains its initial value instead
+                }
+            }
+        }
     }
 
     /// Returns the assembly's information.
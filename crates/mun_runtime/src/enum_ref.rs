@@ -0,0 +1,261 @@
+use crate::garbage_collector::{GcPtr, GcRootPtr, UnsafeTypeInfo};
+use crate::{
+    marshal::{Marshal, MarshalArgument},
+    reflection::{equals_return_type, ArgumentReflection, ReturnTypeReflection},
+    Runtime,
+};
+use memory::gc::{GcRuntime, HasIndirectionPtr};
+use std::cell::RefCell;
+use std::{
+    ptr::{self, NonNull},
+    rc::Rc,
+};
+
+/// Represents a Mun enum pointer.
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct RawEnum(GcPtr);
+
+impl RawEnum {
+    /// Returns a pointer to the enum memory.
+    pub unsafe fn get_ptr(&self) -> *const u8 {
+        self.0.deref()
+    }
+}
+
+/// Type-agnostic wrapper for interoperability with a Mun enum.
+#[derive(Clone)]
+pub struct EnumRef {
+    handle: GcRootPtr,
+    runtime: Rc<RefCell<Runtime>>,
+}
+
+impl EnumRef {
+    /// Creates an `EnumRef` that wraps a raw Mun enum.
+    fn new(runtime: Rc<RefCell<Runtime>>, raw: RawEnum) -> Self {
+        let handle = {
+            let runtime_ref = runtime.borrow();
+            // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+            // `Runtime` does not change. As we hold a shared reference to `Runtime`, this is safe.
+            assert!(unsafe {
+                runtime_ref
+                    .gc()
+                    .ptr_type(raw.0)
+                    .into_inner()
+                    .as_ref()
+                    .group
+                    .is_enum()
+            });
+
+            GcRootPtr::new(&runtime_ref.gc, raw.0)
+        };
+
+        Self { runtime, handle }
+    }
+
+    /// Consumes the `EnumRef`, returning a raw Mun enum.
+    pub fn into_raw(self) -> RawEnum {
+        RawEnum(self.handle.handle())
+    }
+
+    /// Returns the type information of the enum.
+    pub fn type_info<'r>(enum_ref: &Self, runtime_ref: &'r Runtime) -> &'r abi::TypeInfo {
+        // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+        // `Runtime` does not change. As the lifetime of `TypeInfo` is tied to the lifetime of
+        // `Runtime`, this is safe.
+        unsafe {
+            &*runtime_ref
+                .gc
+                .ptr_type(enum_ref.handle.handle())
+                .into_inner()
+                .as_ptr()
+        }
+    }
+
+    /// Returns the index of the active variant, which is stored as a `u8` tag at the start of the
+    /// enum.
+    fn variant_index(&self) -> usize {
+        // The handle is never null
+        unsafe { *self.handle.deref::<u8>() as usize }
+    }
+
+    /// Returns a pointer to the field at `field_idx` of the specified variant.
+    ///
+    /// # Safety
+    ///
+    /// `variant_info` must belong to the active variant and `field_idx` must be smaller than its
+    /// number of fields.
+    unsafe fn field_offset_unchecked<T>(
+        &self,
+        variant_info: &abi::StructInfo,
+        field_idx: usize,
+    ) -> NonNull<T> {
+        // Field offsets are relative to the start of the enum
+        let offset = *variant_info.field_offsets().get_unchecked(field_idx);
+        // The handle is never null
+        NonNull::new_unchecked(self.handle.deref::<u8>().add(offset as usize).cast::<T>() as *mut _)
+    }
+
+    /// Returns the name of the active variant.
+    pub fn variant_name(&self) -> String {
+        let runtime_ref = self.runtime.borrow();
+        let type_info = Self::type_info(self, &runtime_ref);
+
+        // Safety: `as_enum` is guaranteed to return `Some` for `EnumRef`s.
+        let enum_info = type_info.as_enum().unwrap();
+        let variant_name = enum_info
+            .variant_names()
+            .nth(self.variant_index())
+            .expect("the tag of an enum always refers to a valid variant")
+            .to_string();
+        variant_name
+    }
+
+    /// Returns whether the active variant corresponds to the specified `variant_name`.
+    pub fn is_variant(&self, variant_name: &str) -> bool {
+        self.variant_name() == variant_name
+    }
+
+    /// Retrieves the value of the field corresponding to the specified `field_name` of the active
+    /// variant. The fields of a tuple variant are named by their index, e.g. `"0"`.
+    pub fn get<T: ReturnTypeReflection>(&self, field_name: &str) -> Result<T, String> {
+        let runtime_ref = self.runtime.borrow();
+        let type_info = Self::type_info(self, &runtime_ref);
+
+        // Safety: `as_enum` is guaranteed to return `Some` for `EnumRef`s.
+        let enum_info = type_info.as_enum().unwrap();
+        let variant_idx = self.variant_index();
+        let variant_info = &enum_info.variant_infos()[variant_idx];
+        let variant_name = enum_info.variant_names().nth(variant_idx).unwrap();
+        let field_idx = abi::StructInfo::find_field_index(
+            &format!("{}::{}", type_info.name(), variant_name),
+            variant_info,
+            field_name,
+        )?;
+
+        // Safety: If we found the `field_idx`, we are guaranteed to also have the `field_type` and
+        // `field_offset`.
+        let field_type = unsafe { variant_info.field_types().get_unchecked(field_idx) };
+        equals_return_type::<T>(field_type).map_err(|(expected, found)| {
+            format!(
+                "Mismatched types for `{}::{}::{}`. Expected: `{}`. Found: `{}`.",
+                type_info.name(),
+                variant_name,
+                field_name,
+                expected,
+                found,
+            )
+        })?;
+
+        let field_ptr =
+            unsafe { self.field_offset_unchecked::<T::Marshalled>(variant_info, field_idx) };
+        Ok(Marshal::marshal_from_ptr(
+            field_ptr,
+            self.runtime.clone(),
+            Some(field_type),
+        ))
+    }
+}
+
+impl ArgumentReflection for EnumRef {
+    type Marshalled = RawEnum;
+
+    fn type_guid(&self, runtime: &Runtime) -> abi::Guid {
+        // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+        // `Runtime` does not change. As we hold a shared reference to `Runtime`, this is safe.
+        unsafe {
+            runtime
+                .gc()
+                .ptr_type(self.handle.handle())
+                .into_inner()
+                .as_ref()
+                .guid
+        }
+    }
+
+    fn type_name(&self, runtime: &Runtime) -> &str {
+        // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+        // `Runtime` does not change. As we hold a shared reference to `Runtime`, this is safe.
+        unsafe {
+            (&*runtime
+                .gc()
+                .ptr_type(self.handle.handle())
+                .into_inner()
+                .as_ptr())
+                .name()
+        }
+    }
+
+    fn marshal(self, _runtime: &Runtime) -> Self::Marshalled {
+        self.into_raw()
+    }
+}
+
+impl ReturnTypeReflection for EnumRef {
+    type Marshalled = RawEnum;
+
+    fn type_name() -> &'static str {
+        "enum"
+    }
+}
+
+impl Marshal<EnumRef> for RawEnum {
+    fn marshal_value(self, runtime: Rc<RefCell<Runtime>>) -> EnumRef {
+        EnumRef::new(runtime, self)
+    }
+
+    fn marshal_from_ptr(
+        ptr: NonNull<Self>,
+        runtime: Rc<RefCell<Runtime>>,
+        type_info: Option<&abi::TypeInfo>,
+    ) -> EnumRef {
+        // `type_info` is only `None` for the `()` type
+        let type_info = type_info.unwrap();
+        let enum_info = type_info.as_enum().unwrap();
+
+        // Copy the contents of the enum based on what kind of pointer we are dealing with
+        let gc_handle = if enum_info.memory_kind == abi::StructMemoryKind::Value {
+            // For a value enum, `ptr` points to an enum value.
+
+            // Create a new object using the runtime's intrinsic
+            let mut gc_handle = {
+                let runtime_ref = runtime.borrow();
+                runtime_ref.gc().alloc(
+                    // Safety: `ty` is a shared reference, so is guaranteed to not be `ptr::null()`.
+                    UnsafeTypeInfo::new(unsafe {
+                        NonNull::new_unchecked(type_info as *const abi::TypeInfo as *mut _)
+                    }),
+                )
+            };
+
+            // Construct
+            let src = ptr.cast::<u8>().as_ptr() as *const _;
+            let dest = unsafe { gc_handle.deref_mut::<u8>() };
+            let size = type_info.size_in_bytes();
+            unsafe { ptr::copy_nonoverlapping(src, dest, size) };
+
+            gc_handle
+        } else {
+            // For a gc enum, `ptr` points to a `GcPtr`.
+            unsafe { *ptr.cast::<GcPtr>().as_ptr() }
+        };
+
+        EnumRef::new(runtime, RawEnum(gc_handle))
+    }
+}
+
+impl MarshalArgument<EnumRef> for RawEnum {
+    fn marshal_to_ptr(value: RawEnum, mut ptr: NonNull<Self>, type_info: Option<&abi::TypeInfo>) {
+        // `type_info` is only `None` for the `()` type
+        let type_info = type_info.unwrap();
+
+        let enum_info = type_info.as_enum().unwrap();
+        if enum_info.memory_kind == abi::StructMemoryKind::Value {
+            let dest = ptr.cast::<u8>().as_ptr();
+            let size = type_info.size_in_bytes();
+            unsafe { ptr::copy_nonoverlapping(value.get_ptr(), dest, size as usize) };
+        } else {
+            unsafe { *ptr.as_mut() = value };
+        }
+    }
+}
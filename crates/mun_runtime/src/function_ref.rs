@@ -0,0 +1,220 @@
+use crate::garbage_collector::{GcPtr, GcRootPtr};
+use crate::{
+    marshal::{Marshal, MarshalArgument},
+    reflection::{ArgumentReflection, ReturnTypeReflection},
+    ArrayRef, EnumRef, Runtime, StructRef,
+};
+use memory::gc::{GcRuntime, HasIndirectionPtr};
+use std::{cell::RefCell, ffi::c_void, mem, ptr::NonNull, rc::Rc};
+
+/// Represents a Mun function pointer.
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct RawFunction(GcPtr);
+
+/// Returns whether the specified type is the type of a function pointer or a closure. Both are
+/// exposed as garbage collected structs of which the name starts with the function's signature.
+pub(crate) fn is_fn_ptr(type_info: &abi::TypeInfo) -> bool {
+    type_info.group.is_struct() && type_info.name().starts_with("fn(")
+}
+
+/// Type-agnostic wrapper for interoperability with a Mun function pointer or closure.
+#[derive(Clone)]
+pub struct FunctionRef {
+    handle: GcRootPtr,
+    runtime: Rc<RefCell<Runtime>>,
+}
+
+impl FunctionRef {
+    /// Creates a `FunctionRef` that wraps a raw Mun function pointer.
+    fn new(runtime: Rc<RefCell<Runtime>>, raw: RawFunction) -> Self {
+        let handle = {
+            let runtime_ref = runtime.borrow();
+            // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+            // `Runtime` does not change. As we hold a shared reference to `Runtime`, this is safe.
+            assert!(is_fn_ptr(unsafe {
+                runtime_ref.gc().ptr_type(raw.0).into_inner().as_ref()
+            }));
+
+            GcRootPtr::new(&runtime_ref.gc, raw.0)
+        };
+
+        Self { runtime, handle }
+    }
+
+    /// Consumes the `FunctionRef`, returning a raw Mun function pointer.
+    pub fn into_raw(self) -> RawFunction {
+        RawFunction(self.handle.handle())
+    }
+
+    /// Returns the type information of the function pointer's object.
+    pub fn type_info<'r>(function_ref: &Self, runtime_ref: &'r Runtime) -> &'r abi::TypeInfo {
+        // Safety: The type returned from `ptr_type` is guaranteed to live at least as long as
+        // `Runtime` does not change. As the lifetime of `TypeInfo` is tied to the lifetime of
+        // `Runtime`, this is safe.
+        unsafe {
+            &*runtime_ref
+                .gc
+                .ptr_type(function_ref.handle.handle())
+                .into_inner()
+                .as_ptr()
+        }
+    }
+
+    /// Returns the signature of the function, e.g. `fn(core::f32) -> core::f32`.
+    pub fn signature<'r>(function_ref: &Self, runtime_ref: &'r Runtime) -> &'r str {
+        let name = Self::type_info(function_ref, runtime_ref).name();
+        // The name of a closure is its signature followed by the types of its captured values
+        name.split(" {closure}").next().unwrap_or(name)
+    }
+
+    /// Returns the function that is called through the function pointer.
+    fn fn_ptr(&self) -> *const c_void {
+        // Safety: The pointer to the function is stored at the start of a function pointer's
+        // memory.
+        unsafe { *self.handle.deref::<*const c_void>() }
+    }
+}
+
+/// Checks whether a function with the specified `signature` can be called with arguments of which
+/// the types are named `arg_names` and whether it returns an `Output`.
+fn check_signature<Output: ReturnTypeReflection>(
+    signature: &str,
+    arg_names: &[&str],
+) -> Result<(), String> {
+    let params = format!("fn({})", arg_names.join(", "));
+    if !signature.starts_with(&params) {
+        return Err(format!(
+            "Invalid argument types for `{}`. Found: `{}`.",
+            signature, params
+        ));
+    }
+
+    let ret_name = &signature[params.len()..];
+    let equals_return_type = if ret_name.is_empty() {
+        <() as ReturnTypeReflection>::type_guid() == Output::type_guid()
+    } else if ret_name.starts_with(" -> ") {
+        let ret_name = &ret_name[4..];
+        // Like the type of a function's return value, garbage collected types can be marshalled
+        // to their type-agnostic wrapper.
+        ret_name == Output::type_name()
+            || (!ret_name.starts_with("core::")
+                && [
+                    <StructRef as ReturnTypeReflection>::type_guid(),
+                    <ArrayRef as ReturnTypeReflection>::type_guid(),
+                    <EnumRef as ReturnTypeReflection>::type_guid(),
+                    <FunctionRef as ReturnTypeReflection>::type_guid(),
+                ]
+                .contains(&Output::type_guid()))
+    } else {
+        false
+    };
+
+    if equals_return_type {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid return type for `{}`. Found: `{}`.",
+            signature,
+            Output::type_name()
+        ))
+    }
+}
+
+macro_rules! impl_invoke {
+    ($(
+        fn $FnName:ident($($Arg:ident: $T:ident),*);
+    )+) => {
+        $(
+            impl FunctionRef {
+                /// Invokes the function with the specified arguments, returning its result. An
+                /// error is returned if the types of the arguments or the return type do not match
+                /// the signature of the function, or if a trap occurs while executing it.
+                #[allow(clippy::too_many_arguments)]
+                pub fn $FnName<$($T: ArgumentReflection,)* Output: ReturnTypeReflection>(
+                    &self,
+                    $($Arg: $T,)*
+                ) -> Result<Output, String> {
+                    let runtime_ref = self.runtime.borrow();
+                    let arg_names: Vec<&str> = vec![$($Arg.type_name(&runtime_ref)),*];
+                    check_signature::<Output>(Self::signature(self, &runtime_ref), &arg_names)?;
+
+                    // The function receives the function pointer's object as its first argument,
+                    // from which a closure loads the values that it captured.
+                    let function: fn(GcPtr, $($T::Marshalled),*) -> Output::Marshalled =
+                        unsafe { mem::transmute(self.fn_ptr()) };
+                    let handle = self.handle.handle();
+                    $(let $Arg = $Arg.marshal(&runtime_ref);)*
+                    let result = crate::trap::catch_trap(|| function(handle, $($Arg),*))
+                        .map_err(|trap| trap.to_string())?;
+
+                    // Marshall the result
+                    Ok(result.marshal_value(self.runtime.clone()))
+                }
+            }
+        )+
+    }
+}
+
+impl_invoke! {
+    fn invoke0();
+    fn invoke1(a: A);
+    fn invoke2(a: A, b: B);
+    fn invoke3(a: A, b: B, c: C);
+    fn invoke4(a: A, b: B, c: C, d: D);
+    fn invoke5(a: A, b: B, c: C, d: D, e: E);
+    fn invoke6(a: A, b: B, c: C, d: D, e: E, f: F);
+}
+
+impl ArgumentReflection for FunctionRef {
+    type Marshalled = RawFunction;
+
+    fn type_guid(&self, runtime: &Runtime) -> abi::Guid {
+        // The `Guid` of a function pointer type is derived from its signature
+        abi::Guid {
+            b: md5::compute(Self::signature(self, runtime)).0,
+        }
+    }
+
+    fn type_name<'r>(&'r self, runtime: &'r Runtime) -> &'r str {
+        Self::signature(self, runtime)
+    }
+
+    fn marshal(self, _runtime: &Runtime) -> Self::Marshalled {
+        self.into_raw()
+    }
+}
+
+impl ReturnTypeReflection for FunctionRef {
+    type Marshalled = RawFunction;
+
+    fn type_name() -> &'static str {
+        "function"
+    }
+}
+
+impl Marshal<FunctionRef> for RawFunction {
+    fn marshal_value(self, runtime: Rc<RefCell<Runtime>>) -> FunctionRef {
+        FunctionRef::new(runtime, self)
+    }
+
+    fn marshal_from_ptr(
+        ptr: NonNull<Self>,
+        runtime: Rc<RefCell<Runtime>>,
+        _type_info: Option<&abi::TypeInfo>,
+    ) -> FunctionRef {
+        // A function pointer is always stored as a `GcPtr`
+        let gc_handle = unsafe { *ptr.cast::<GcPtr>().as_ptr() };
+        FunctionRef::new(runtime, RawFunction(gc_handle))
+    }
+}
+
+impl MarshalArgument<FunctionRef> for RawFunction {
+    fn marshal_to_ptr(
+        value: RawFunction,
+        mut ptr: NonNull<Self>,
+        _type_info: Option<&abi::TypeInfo>,
+    ) {
+        unsafe { *ptr.as_mut() = value };
+    }
+}
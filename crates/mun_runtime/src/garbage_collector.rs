@@ -1,5 +1,8 @@
-use memory::gc::{self, HasIndirectionPtr};
-use std::{alloc::Layout, hash::Hash, ptr::NonNull};
+use memory::{
+    gc::{self, GcRuntime, HasIndirectionPtr},
+    mapping::{Mapping, MemoryMapper},
+};
+use std::{alloc::Layout, hash::Hash, ptr::NonNull, sync::Arc, time::Duration};
 
 /// `UnsafeTypeInfo` is a type that wraps a `NonNull<TypeInfo>` and indicates unsafe interior
 /// operations on the wrapped `TypeInfo`. The unsafety originates from uncertainty about the
@@ -78,38 +81,134 @@ impl memory::TypeFields<UnsafeTypeInfo> for UnsafeTypeInfo {
             &[]
         }
     }
+
+    fn renamed_from(&self) -> Vec<Option<&str>> {
+        if let Some(s) = unsafe { self.0.as_ref().as_struct() } {
+            s.field_renamed_from().collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn variants(&self) -> Vec<(&str, Vec<(&str, Self)>, &[u16])> {
+        if let Some(e) = unsafe { self.0.as_ref().as_enum() } {
+            e.variant_names()
+                .zip(e.variant_infos().iter())
+                .map(|(name, variant)| {
+                    let fields = variant
+                        .field_names()
+                        .zip(variant.field_types().iter().map(|ty| {
+                            // Safety: `ty` is a shared reference, so is guaranteed to not be
+                            // `ptr::null()`.
+                            UnsafeTypeInfo::new(unsafe {
+                                NonNull::new_unchecked(*ty as *const abi::TypeInfo as *mut _)
+                            })
+                        }))
+                        .collect();
+                    (name, fields, variant.field_offsets())
+                })
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
 }
 
 unsafe impl Send for UnsafeTypeInfo {}
 unsafe impl Sync for UnsafeTypeInfo {}
 
+/// Returns whether values of the specified type are garbage collected, i.e. whether they are
+/// stored as a `GcPtr` when they are part of another value.
+fn is_gc_type(ty: &abi::TypeInfo) -> bool {
+    if let Some(s) = ty.as_struct() {
+        s.memory_kind == abi::StructMemoryKind::GC
+    } else if let Some(e) = ty.as_enum() {
+        e.memory_kind == abi::StructMemoryKind::GC
+    } else if let Some(a) = ty.as_array() {
+        a.is_dynamic
+    } else {
+        ty.is_string()
+    }
+}
+
+/// Returns the memory layout of a value of the specified type when it is stored as part of another
+/// value, e.g. as a struct field or an array element.
+pub(crate) fn inline_layout(ty: &abi::TypeInfo) -> Layout {
+    if is_gc_type(ty) {
+        Layout::new::<GcPtr>()
+    } else {
+        Layout::from_size_align(ty.size_in_bytes(), ty.alignment())
+            .unwrap_or_else(|_| panic!("invalid layout from Mun Type: {:?}", ty))
+    }
+}
+
 pub struct Trace {
-    obj: GcPtr,
-    ty: UnsafeTypeInfo,
-    index: usize,
+    iter: std::vec::IntoIter<GcPtr>,
+}
+
+impl Trace {
+    /// Collects all garbage collected objects that are referenced by the object of type `ty` that
+    /// is stored at `ptr`.
+    unsafe fn trace_object(ty: &abi::TypeInfo, ptr: *const u8, handles: &mut Vec<GcPtr>) {
+        if let Some(struct_ty) = ty.as_struct() {
+            for (field_ty, offset) in struct_ty
+                .field_types()
+                .iter()
+                .zip(struct_ty.field_offsets().iter())
+            {
+                Self::trace_value(field_ty, ptr.add(*offset as usize), handles);
+            }
+        } else if let Some(enum_ty) = ty.as_enum() {
+            // The tag of the active variant is stored at the start of the enum and the offsets of
+            // the variant's fields are relative to the start of the enum.
+            let tag = *ptr;
+            if let Some(variant) = enum_ty.variant_infos().get(tag as usize) {
+                for (field_ty, offset) in variant
+                    .field_types()
+                    .iter()
+                    .zip(variant.field_offsets().iter())
+                {
+                    Self::trace_value(field_ty, ptr.add(*offset as usize), handles);
+                }
+            }
+        } else if let Some(array_ty) = ty.as_array() {
+            let element_ty = array_ty.element_type();
+            let element_layout = inline_layout(element_ty);
+            let (ptr, length) = if array_ty.is_dynamic {
+                let header = *ptr.cast::<gc::ArrayHeader>();
+                let (_, offset) = gc::array_layout(element_layout, header.capacity);
+                (ptr.add(offset), header.length)
+            } else {
+                (ptr, array_ty.length as usize)
+            };
+
+            let stride = element_layout.pad_to_align().size();
+            for index in 0..length {
+                Self::trace_value(element_ty, ptr.add(index * stride), handles);
+            }
+        }
+    }
+
+    /// Collects all garbage collected objects that are referenced by the value of type `ty` that is
+    /// stored inline at `ptr`.
+    unsafe fn trace_value(ty: &abi::TypeInfo, ptr: *const u8, handles: &mut Vec<GcPtr>) {
+        if is_gc_type(ty) {
+            // An optional reference that is `nil` does not reference an object
+            let handle = *ptr.cast::<gc::RawGcPtr>();
+            if !handle.is_null() {
+                handles.push(handle.into());
+            }
+        } else {
+            Self::trace_object(ty, ptr, handles);
+        }
+    }
 }
 
 impl Iterator for Trace {
     type Item = GcPtr;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let struct_ty = unsafe { self.ty.0.as_ref() }.as_struct()?;
-        let field_count = struct_ty.field_types().len();
-        while self.index < field_count {
-            let index = self.index;
-            self.index += 1;
-
-            let field_ty = struct_ty.field_types()[index];
-            if let Some(field_struct_ty) = field_ty.as_struct() {
-                if field_struct_ty.memory_kind == abi::StructMemoryKind::GC {
-                    let offset = struct_ty.field_offsets()[index];
-                    return Some(unsafe {
-                        *self.obj.deref::<u8>().add(offset as usize).cast::<GcPtr>()
-                    });
-                }
-            }
-        }
-        None
+        self.iter.next()
     }
 }
 
@@ -121,11 +220,18 @@ impl memory::TypeMemory for UnsafeTypeInfo {
     }
 
     fn is_stack_allocated(&self) -> bool {
-        unsafe {
-            self.0
-                .as_ref()
-                .as_struct()
-                .map_or(true, |s| s.memory_kind == abi::StructMemoryKind::Value)
+        !is_gc_type(unsafe { self.0.as_ref() })
+    }
+
+    fn element_layout(&self) -> Option<Layout> {
+        let ty = unsafe { self.0.as_ref() };
+        if ty.is_string() {
+            // Strings are stored as dynamic arrays of UTF-8 encoded bytes
+            Some(Layout::new::<u8>())
+        } else {
+            ty.as_array()
+                .filter(|a| a.is_dynamic)
+                .map(|a| inline_layout(a.element_type()))
         }
     }
 }
@@ -134,16 +240,161 @@ impl gc::TypeTrace for UnsafeTypeInfo {
     type Trace = Trace;
 
     fn trace(&self, obj: GcPtr) -> Self::Trace {
+        let mut handles = Vec::new();
+        // Safety: the garbage collector only traces live objects of type `self`.
+        unsafe { Trace::trace_object(self.0.as_ref(), obj.deref::<u8>(), &mut handles) };
         Trace {
-            ty: *self,
-            obj,
-            index: 0,
+            iter: handles.into_iter(),
+        }
+    }
+}
+
+/// The kinds of garbage collectors that a `Runtime` can use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GcKind {
+    /// A stop-the-world mark-sweep collector
+    MarkSweep,
+    /// A generational collector that allocates new objects in a nursery of `nursery_size` bytes
+    /// and collects old objects incrementally
+    Generational {
+        /// The size of the nursery in bytes
+        nursery_size: usize,
+    },
+}
+
+impl Default for GcKind {
+    fn default() -> Self {
+        GcKind::MarkSweep
+    }
+}
+
+/// Forwards the events of the garbage collector to a user-defined observer, if there is one.
+#[derive(Default)]
+pub struct EventForwarder(Option<Arc<dyn gc::Observer<Event = gc::Event>>>);
+
+impl gc::Observer for EventForwarder {
+    type Event = gc::Event;
+
+    fn event(&self, event: gc::Event) {
+        if let Some(observer) = &self.0 {
+            observer.event(event);
         }
     }
 }
 
 /// Defines the garbage collector used by the `Runtime`.
-pub type GarbageCollector = gc::MarkSweep<UnsafeTypeInfo, gc::NoopObserver<gc::Event>>;
+pub enum GarbageCollector {
+    /// A stop-the-world mark-sweep collector
+    MarkSweep(gc::MarkSweep<UnsafeTypeInfo, EventForwarder>),
+    /// A generational collector with an incrementally collected old generation
+    Generational(gc::Generational<UnsafeTypeInfo, EventForwarder>),
+}
+
+impl GarbageCollector {
+    /// Constructs a garbage collector of the specified `kind`, which requests collections
+    /// according to the `trigger_policy` and emits its events to the `observer`, if any.
+    pub fn new(
+        kind: GcKind,
+        trigger_policy: gc::TriggerPolicy,
+        observer: Option<Arc<dyn gc::Observer<Event = gc::Event>>>,
+    ) -> Self {
+        let observer = EventForwarder(observer);
+        match kind {
+            GcKind::MarkSweep => GarbageCollector::MarkSweep(
+                gc::MarkSweep::with_observer(observer).with_trigger_policy(trigger_policy),
+            ),
+            GcKind::Generational { nursery_size } => GarbageCollector::Generational(
+                gc::Generational::new(nursery_size, observer).with_trigger_policy(trigger_policy),
+            ),
+        }
+    }
+
+    /// Returns the garbage collector as a `GcRuntime`.
+    fn runtime(&self) -> &dyn GcRuntime<UnsafeTypeInfo> {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc,
+            GarbageCollector::Generational(gc) => gc,
+        }
+    }
+
+    /// Returns whether the trigger policy requested a collection since the last collection.
+    pub fn collection_requested(&self) -> bool {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.collection_requested(),
+            GarbageCollector::Generational(gc) => gc.collection_requested(),
+        }
+    }
+
+    /// Collects all memory that is no longer referenced by rooted objects. Returns `true` if memory
+    /// was reclaimed, `false` otherwise.
+    pub fn collect(&self) -> bool {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.collect(),
+            GarbageCollector::Generational(gc) => gc.collect(),
+        }
+    }
+
+    /// Collects memory that is no longer referenced by rooted objects, spending approximately at
+    /// most `budget` on the collection. A collector that cannot collect incrementally performs a
+    /// full collection instead. Returns `true` if memory was reclaimed, `false` otherwise.
+    pub fn collect_incremental(&self, budget: Duration) -> bool {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.collect(),
+            GarbageCollector::Generational(gc) => gc.collect_incremental(budget),
+        }
+    }
+
+    /// Returns a snapshot of all allocated objects and the references between them.
+    pub fn heap_snapshot(&self) -> gc::HeapSnapshot {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.heap_snapshot(),
+            GarbageCollector::Generational(gc) => gc.heap_snapshot(),
+        }
+    }
+}
+
+impl GcRuntime<UnsafeTypeInfo> for GarbageCollector {
+    fn alloc(&self, ty: UnsafeTypeInfo) -> GcPtr {
+        self.runtime().alloc(ty)
+    }
+
+    fn alloc_array(&self, ty: UnsafeTypeInfo, capacity: usize) -> GcPtr {
+        self.runtime().alloc_array(ty, capacity)
+    }
+
+    fn reserve_array(&self, obj: GcPtr, capacity: usize) {
+        self.runtime().reserve_array(obj, capacity)
+    }
+
+    fn ptr_type(&self, obj: GcPtr) -> UnsafeTypeInfo {
+        self.runtime().ptr_type(obj)
+    }
+
+    fn root(&self, obj: GcPtr) {
+        self.runtime().root(obj)
+    }
+
+    fn unroot(&self, obj: GcPtr) {
+        self.runtime().unroot(obj)
+    }
+
+    fn write_barrier(&self, obj: GcPtr) {
+        self.runtime().write_barrier(obj)
+    }
+
+    fn stats(&self) -> gc::Stats {
+        self.runtime().stats()
+    }
+}
+
+impl MemoryMapper<UnsafeTypeInfo> for GarbageCollector {
+    fn map_memory(&self, mapping: Mapping<UnsafeTypeInfo, UnsafeTypeInfo>) -> Vec<GcPtr> {
+        match self {
+            GarbageCollector::MarkSweep(gc) => gc.map_memory(mapping),
+            GarbageCollector::Generational(gc) => gc.map_memory(mapping),
+        }
+    }
+}
 
 pub use gc::GcPtr;
 pub type GcRootPtr = gc::GcRootPtr<UnsafeTypeInfo, GarbageCollector>;
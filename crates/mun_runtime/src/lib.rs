@@ -4,18 +4,26 @@
 //! compliant shared libraries.
 #![warn(missing_docs)]
 
+mod array_ref;
 mod assembly;
+mod enum_ref;
+mod function_ref;
 #[macro_use]
 mod macros;
 #[macro_use]
 mod garbage_collector;
 mod marshal;
 mod reflection;
+mod string;
 mod struct_ref;
+mod trap;
 
 use failure::Error;
-use garbage_collector::GarbageCollector;
-use memory::gc::{self, GcRuntime};
+use garbage_collector::{GarbageCollector, GcPtr};
+use memory::{
+    gc::{self, GcRuntime, HasIndirectionPtr},
+    mapping::{Migrations, TypeMigration},
+};
 use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
 use rustc_hash::FxHashMap;
 use std::{
@@ -34,13 +42,21 @@ use std::{
 };
 
 pub use crate::{
+    array_ref::ArrayRef,
     assembly::Assembly,
-    garbage_collector::UnsafeTypeInfo,
-    marshal::Marshal,
+    enum_ref::EnumRef,
+    function_ref::FunctionRef,
+    garbage_collector::{GcKind, UnsafeTypeInfo},
+    marshal::{Marshal, MarshalArgument},
     reflection::{ArgumentReflection, ReturnTypeReflection},
     struct_ref::StructRef,
+    trap::Trap,
 };
 pub use abi::IntoFunctionDefinition;
+pub use memory::gc::{
+    Event as GcEvent, EventAggregator as GcEventAggregator, Observer as GcObserver,
+};
+pub use memory::mapping::{GuessedRename, MigrationValue, UnmatchedRename};
 
 /// Options for the construction of a [`Runtime`].
 pub struct RuntimeOptions {
@@ -50,6 +66,18 @@ pub struct RuntimeOptions {
     pub delay: Duration,
     /// Custom user injected functions
     pub user_functions: Vec<(abi::FunctionDefinition, abi::FunctionDefinitionStorage)>,
+    /// The kind of garbage collector that manages the memory of Mun objects
+    pub gc: GcKind,
+    /// The policy that determines when the garbage collector requests a collection, which is
+    /// performed by `Runtime::update`
+    pub gc_trigger_policy: gc::TriggerPolicy,
+    /// Whether to collect garbage every time `Runtime::update` is called
+    pub gc_on_update: bool,
+    /// An observer that receives the events of the garbage collector
+    pub gc_observer: Option<Arc<dyn GcObserver<Event = GcEvent>>>,
+    /// User-defined migrations of objects of which the type changed during hot reloading, by name
+    /// of their old type
+    pub migrations: Migrations<UnsafeTypeInfo>,
 }
 
 /// A builder for the [`Runtime`].
@@ -65,6 +93,11 @@ impl RuntimeBuilder {
                 library_path: library_path.into(),
                 delay: Duration::from_millis(10),
                 user_functions: Default::default(),
+                gc: Default::default(),
+                gc_trigger_policy: Default::default(),
+                gc_on_update: false,
+                gc_observer: None,
+                migrations: Default::default(),
             },
         }
     }
@@ -75,6 +108,41 @@ impl RuntimeBuilder {
         self
     }
 
+    /// Sets the kind of garbage collector that is used.
+    pub fn set_gc(mut self, gc: GcKind) -> Self {
+        self.options.gc = gc;
+        self
+    }
+
+    /// Requests a garbage collection once `bytes` were allocated since the last collection.
+    pub fn set_gc_allocation_threshold(mut self, bytes: usize) -> Self {
+        self.options.gc_trigger_policy.allocation_threshold = Some(bytes);
+        self
+    }
+
+    /// Requests a garbage collection once the allocated memory has grown by `factor` since the
+    /// last collection.
+    pub fn set_gc_growth_factor(mut self, factor: f64) -> Self {
+        self.options.gc_trigger_policy.growth_factor = Some(factor);
+        self
+    }
+
+    /// Sets whether garbage is collected every time `Runtime::update` is called.
+    pub fn set_gc_on_update(mut self, gc_on_update: bool) -> Self {
+        self.options.gc_on_update = gc_on_update;
+        self
+    }
+
+    /// Sets an observer that receives the events of the garbage collector, e.g. to verify when
+    /// objects are allocated and collected.
+    pub fn set_gc_observer<O: GcObserver<Event = GcEvent> + 'static>(
+        mut self,
+        observer: Arc<O>,
+    ) -> Self {
+        self.options.gc_observer = Some(observer);
+        self
+    }
+
     /// Adds a custom user function to the dispatch table.
     pub fn insert_fn<S: AsRef<str>, F: abi::IntoFunctionDefinition>(
         mut self,
@@ -85,6 +153,51 @@ impl RuntimeBuilder {
         self
     }
 
+    /// Adds a migration for objects of the type called `type_name`, which is applied when the type
+    /// changes during hot reloading. The migration receives the old and the new value of each
+    /// object, after the fields of the new value have been mapped from the old value.
+    ///
+    /// If a migration was already added for `type_name`, it is replaced.
+    pub fn insert_migration<S, F>(self, type_name: S, migration: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&MigrationValue<UnsafeTypeInfo>, &mut MigrationValue<UnsafeTypeInfo>)
+            + Send
+            + Sync
+            + 'static,
+    {
+        let type_name = type_name.into();
+        self.insert_rename_migration(type_name.clone(), type_name, migration)
+    }
+
+    /// Adds a migration for objects of the type called `old_type_name`, which is applied when the
+    /// type is renamed to `new_type_name` during hot reloading. Without a migration, the objects of
+    /// a renamed type are not mapped to the new type.
+    ///
+    /// If a migration was already added for `old_type_name`, it is replaced.
+    pub fn insert_rename_migration<S, F>(
+        mut self,
+        old_type_name: S,
+        new_type_name: S,
+        migration: F,
+    ) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&MigrationValue<UnsafeTypeInfo>, &mut MigrationValue<UnsafeTypeInfo>)
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.options.migrations.insert(
+            old_type_name.into(),
+            TypeMigration {
+                new_type_name: new_type_name.into(),
+                migration: Arc::new(migration),
+            },
+        );
+        self
+    }
+
     /// Spawns a [`Runtime`] with the builder's options.
     pub fn spawn(self) -> Result<Rc<RefCell<Runtime>>, Error> {
         Runtime::new(self.options).map(|runtime| Rc::new(RefCell::new(runtime)))
@@ -167,6 +280,10 @@ pub struct Runtime {
     watcher: RecommendedWatcher,
     watcher_rx: Receiver<DebouncedEvent>,
     gc: Arc<GarbageCollector>,
+    gc_on_update: bool,
+    migrations: Migrations<UnsafeTypeInfo>,
+    guessed_renames: Vec<GuessedRename>,
+    unmatched_renames: Vec<UnmatchedRename>,
     _user_functions: Vec<abi::FunctionDefinitionStorage>,
 }
 
@@ -198,6 +315,105 @@ extern "C" fn new(
     handle.into()
 }
 
+extern "C" fn new_array(
+    type_info: *const abi::TypeInfo,
+    capacity: usize,
+    alloc_handle: *mut ffi::c_void,
+) -> *const *mut ffi::c_void {
+    // Safety: `new_array` is only called from within Mun assemblies' core logic, so we are
+    // guaranteed that the `Runtime` and its `GarbageCollector` still exist if this function is
+    // called, and will continue to do so for the duration of this function.
+    let allocator = unsafe { get_allocator(alloc_handle) };
+    // Safety: the Mun Compiler guarantees that `new_array` is never called with `ptr::null()`.
+    let type_info = UnsafeTypeInfo::new(unsafe { NonNull::new_unchecked(type_info as *mut _) });
+    let handle = allocator.alloc_array(type_info, capacity);
+
+    // Prevent destruction of the allocator
+    mem::forget(allocator);
+
+    handle.into()
+}
+
+extern "C" fn array_reserve(
+    handle: *const *mut ffi::c_void,
+    capacity: usize,
+    alloc_handle: *mut ffi::c_void,
+) {
+    // Safety: `array_reserve` is only called from within Mun assemblies' core logic, so we are
+    // guaranteed that the `Runtime` and its `GarbageCollector` still exist if this function is
+    // called, and will continue to do so for the duration of this function.
+    let allocator = unsafe { get_allocator(alloc_handle) };
+    let handle: GcPtr = handle.into();
+
+    // Grow the capacity exponentially to amortize the cost of pushing elements
+    // Safety: the Mun Compiler guarantees that `handle` refers to a dynamic array.
+    let old_capacity = unsafe { (*handle.deref::<gc::ArrayHeader>()).capacity };
+    allocator.reserve_array(handle, capacity.max(old_capacity * 2).max(4));
+
+    // Prevent destruction of the allocator
+    mem::forget(allocator);
+}
+
+extern "C" fn write_barrier(handle: *const *mut ffi::c_void, alloc_handle: *mut ffi::c_void) {
+    // Safety: `write_barrier` is only called from within Mun assemblies' core logic, so we are
+    // guaranteed that the `Runtime` and its `GarbageCollector` still exist if this function is
+    // called, and will continue to do so for the duration of this function.
+    let allocator = unsafe { get_allocator(alloc_handle) };
+    allocator.write_barrier(handle.into());
+
+    // Prevent destruction of the allocator
+    mem::forget(allocator);
+}
+
+extern "C" fn new_string(
+    bytes: *const u8,
+    length: usize,
+    alloc_handle: *mut ffi::c_void,
+) -> *const *mut ffi::c_void {
+    // Safety: `new_string` is only called from within Mun assemblies' core logic, so we are
+    // guaranteed that the `Runtime` and its `GarbageCollector` still exist if this function is
+    // called, and will continue to do so for the duration of this function.
+    let allocator = unsafe { get_allocator(alloc_handle) };
+    // Safety: the Mun Compiler guarantees that `bytes` points to `length` UTF-8 encoded bytes.
+    let bytes = unsafe { std::slice::from_raw_parts(bytes, length) };
+    let handle = string::alloc_string(allocator.as_ref(), bytes);
+
+    // Prevent destruction of the allocator
+    mem::forget(allocator);
+
+    handle.into()
+}
+
+extern "C" fn string_concat(
+    lhs: *const *mut ffi::c_void,
+    rhs: *const *mut ffi::c_void,
+    alloc_handle: *mut ffi::c_void,
+) -> *const *mut ffi::c_void {
+    // Safety: `string_concat` is only called from within Mun assemblies' core logic, so we are
+    // guaranteed that the `Runtime` and its `GarbageCollector` still exist if this function is
+    // called, and will continue to do so for the duration of this function.
+    let allocator = unsafe { get_allocator(alloc_handle) };
+    // Safety: the Mun Compiler guarantees that `lhs` and `rhs` refer to strings.
+    let bytes = unsafe {
+        [
+            string::string_bytes(lhs.into()),
+            string::string_bytes(rhs.into()),
+        ]
+    }
+    .concat();
+    let handle = string::alloc_string(allocator.as_ref(), &bytes);
+
+    // Prevent destruction of the allocator
+    mem::forget(allocator);
+
+    handle.into()
+}
+
+extern "C" fn string_eq(lhs: *const *mut ffi::c_void, rhs: *const *mut ffi::c_void) -> bool {
+    // Safety: the Mun Compiler guarantees that `lhs` and `rhs` refer to strings.
+    unsafe { string::string_bytes(lhs.into()) == string::string_bytes(rhs.into()) }
+}
+
 impl Runtime {
     /// Constructs a new `Runtime` that loads the library at `library_path` and its
     /// dependencies. The `Runtime` contains a file watcher that is triggered with an interval
@@ -212,6 +428,49 @@ impl Runtime {
             new as extern "C" fn(*const abi::TypeInfo, *mut ffi::c_void) -> *const *mut ffi::c_void,
             "new",
         ));
+        options.user_functions.push(IntoFunctionDefinition::into(
+            new_array
+                as extern "C" fn(
+                    *const abi::TypeInfo,
+                    usize,
+                    *mut ffi::c_void,
+                ) -> *const *mut ffi::c_void,
+            "new_array",
+        ));
+        options.user_functions.push(IntoFunctionDefinition::into(
+            array_reserve as extern "C" fn(*const *mut ffi::c_void, usize, *mut ffi::c_void),
+            "array_reserve",
+        ));
+        options.user_functions.push(IntoFunctionDefinition::into(
+            write_barrier as extern "C" fn(*const *mut ffi::c_void, *mut ffi::c_void),
+            "write_barrier",
+        ));
+        options.user_functions.push(IntoFunctionDefinition::into(
+            trap::index_out_of_bounds as extern "C-unwind" fn(usize, usize, *const u8, usize, u32),
+            "index_out_of_bounds",
+        ));
+        options.user_functions.push(IntoFunctionDefinition::into(
+            trap::trap as extern "C-unwind" fn(*const u8, usize, *const u8, usize, u32),
+            "trap",
+        ));
+        options.user_functions.push(IntoFunctionDefinition::into(
+            new_string
+                as extern "C" fn(*const u8, usize, *mut ffi::c_void) -> *const *mut ffi::c_void,
+            "new_string",
+        ));
+        options.user_functions.push(IntoFunctionDefinition::into(
+            string_concat
+                as extern "C" fn(
+                    *const *mut ffi::c_void,
+                    *const *mut ffi::c_void,
+                    *mut ffi::c_void,
+                ) -> *const *mut ffi::c_void,
+            "string_concat",
+        ));
+        options.user_functions.push(IntoFunctionDefinition::into(
+            string_eq as extern "C" fn(*const *mut ffi::c_void, *const *mut ffi::c_void) -> bool,
+            "string_eq",
+        ));
 
         let mut storages = Vec::with_capacity(options.user_functions.len());
         for (info, storage) in options.user_functions.into_iter() {
@@ -225,7 +484,15 @@ impl Runtime {
             dispatch_table,
             watcher,
             watcher_rx: rx,
-            gc: Arc::new(self::garbage_collector::GarbageCollector::default()),
+            gc: Arc::new(GarbageCollector::new(
+                options.gc,
+                options.gc_trigger_policy,
+                options.gc_observer,
+            )),
+            gc_on_update: options.gc_on_update,
+            migrations: options.migrations,
+            guessed_renames: Vec::new(),
+            unmatched_renames: Vec::new(),
             _user_functions: storages,
         };
 
@@ -262,26 +529,114 @@ impl Runtime {
         self.dispatch_table.get_fn(function_name)
     }
 
-    /// Updates the state of the runtime. This includes checking for file changes, and reloading
-    /// compiled assemblies.
+    /// Retrieves the information of the global variable corresponding to `global_name`, if
+    /// available.
+    pub fn get_global_info(&self, global_name: &str) -> Option<&abi::GlobalInfo> {
+        self.assemblies
+            .values()
+            .flat_map(|assembly| assembly.info().symbols.globals().iter())
+            .find(|global| global.name() == global_name)
+    }
+
+    /// Retrieves the value of the global variable corresponding to `global_name`.
+    pub fn get_global<T: ReturnTypeReflection + Copy>(
+        &self,
+        global_name: &str,
+    ) -> Result<T, String> {
+        let global = self
+            .get_global_info(global_name)
+            .ok_or_else(|| format!("Global variable `{}` does not exist.", global_name))?;
+
+        reflection::equals_return_type::<T>(global.type_info()).map_err(|(expected, found)| {
+            format!(
+                "Mismatched types for `{}`. Expected: `{}`. Found: `{}`.",
+                global_name, expected, found,
+            )
+        })?;
+
+        // Safety: The type of the global variable was checked, and the Mun Compiler guarantees
+        // that its address is never `ptr::null()`.
+        Ok(unsafe { *global.address.cast::<T>() })
+    }
+
+    /// Sets the value of the mutable global variable corresponding to `global_name`.
+    pub fn set_global<T: ArgumentReflection + Copy>(
+        &mut self,
+        global_name: &str,
+        value: T,
+    ) -> Result<(), String> {
+        let global = self
+            .get_global_info(global_name)
+            .ok_or_else(|| format!("Global variable `{}` does not exist.", global_name))?;
+
+        if !global.is_mutable {
+            return Err(format!(
+                "Cannot assign to immutable global variable `{}`.",
+                global_name
+            ));
+        }
+
+        reflection::equals_argument_type(self, global.type_info(), &value).map_err(
+            |(expected, found)| {
+                format!(
+                    "Mismatched types for `{}`. Expected: `{}`. Found: `{}`.",
+                    global_name, expected, found,
+                )
+            },
+        )?;
+
+        // Safety: The type and mutability of the global variable were checked, and the Mun
+        // Compiler guarantees that its address is never `ptr::null()`.
+        unsafe { *global.address.cast::<T>() = value };
+        Ok(())
+    }
+
+    /// Updates the state of the runtime. This includes collecting garbage if the garbage collector
+    /// requested a collection or `RuntimeOptions::gc_on_update` is set, checking for file changes,
+    /// and reloading compiled assemblies.
     pub fn update(&mut self) -> bool {
+        // No Mun code is running during an update, so all objects that are still in use are rooted
+        if self.gc_on_update || self.gc.collection_requested() {
+            self.gc.collect();
+        }
+
         while let Ok(event) = self.watcher_rx.try_recv() {
             use notify::DebouncedEvent::*;
             match event {
                 Write(ref path) | Rename(_, ref path) | Create(ref path) => {
                     if let Some(assembly) = self.assemblies.get_mut(path) {
-                        if let Err(e) = assembly.swap(path, &mut self.dispatch_table) {
-                            println!(
+                        match assembly.swap(path, &mut self.dispatch_table, &self.migrations) {
+                            Ok((guessed_renames, unmatched_renames)) => {
+                                println!(
+                                    "Succesfully reloaded assembly: '{}'",
+                                    path.to_string_lossy()
+                                );
+                                for rename in guessed_renames.iter() {
+                                    println!(
+                                        "Guessed that field `{}::{}` was renamed to `{}`. Use \
+                                         `#[renamed_from(\"{}\")]` if this is correct.",
+                                        rename.type_name,
+                                        rename.old_name,
+                                        rename.new_name,
+                                        rename.old_name
+                                    );
+                                }
+                                for rename in unmatched_renames.iter() {
+                                    println!(
+                                        "Field `{}::{}` is renamed from `{}`, but the previous \
+                                         version of the type has no such field.",
+                                        rename.type_name, rename.new_name, rename.old_name
+                                    );
+                                }
+                                self.guessed_renames = guessed_renames;
+                                self.unmatched_renames = unmatched_renames;
+                                return true;
+                            }
+                            Err(e) => println!(
                                 "An error occured while reloading assembly '{}': {:?}",
                                 path.to_string_lossy(),
                                 e
-                            );
-                        } else {
-                            println!(
-                                "Succesfully reloaded assembly: '{}'",
-                                path.to_string_lossy()
-                            );
-                            return true;
+                            ),
                         }
                     }
                 }
@@ -305,10 +660,36 @@ impl Runtime {
         self.gc.collect()
     }
 
+    /// Collects memory that is no longer referenced by rooted objects, spending approximately at
+    /// most `budget` on the collection. This allows collecting garbage every frame without
+    /// exceeding its time budget, if the runtime uses a generational garbage collector; otherwise
+    /// all garbage is collected. Returns `true` if memory was reclaimed, `false` otherwise.
+    pub fn gc_collect_incremental(&self, budget: Duration) -> bool {
+        self.gc.collect_incremental(budget)
+    }
+
     /// Returns statistics about the garbage collector.
     pub fn gc_stats(&self) -> gc::Stats {
         self.gc.stats()
     }
+
+    /// Returns a snapshot of all objects that are allocated by the garbage collector and the
+    /// references between them, which can be exported as JSON to find memory leaks.
+    pub fn gc_heap_snapshot(&self) -> gc::HeapSnapshot {
+        self.gc.heap_snapshot()
+    }
+
+    /// Returns the field renames that were guessed during the last reload, because the renamed
+    /// fields did not declare their previous name with `#[renamed_from(..)]`.
+    pub fn guessed_renames(&self) -> &[GuessedRename] {
+        &self.guessed_renames
+    }
+
+    /// Returns the field renames that were declared with `#[renamed_from(..)]`, but that did not
+    /// match a field of the previous version of their type during the last reload.
+    pub fn unmatched_renames(&self) -> &[UnmatchedRename] {
+        &self.unmatched_renames
+    }
 }
 
 /// Extends a result object with functions that allow retrying of an action.
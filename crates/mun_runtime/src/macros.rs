@@ -22,6 +22,7 @@ macro_rules! invoke_fn_impl {
                 function_name: &'s str,
                 $($Arg: $T,)*
                 output: core::marker::PhantomData<Output>,
+                trap: Option<$crate::Trap>,
             }
 
             impl<'s, $($T: ArgumentReflection,)* Output: ReturnTypeReflection> core::fmt::Debug for $ErrName<'s, $($T,)* Output> {
@@ -52,8 +53,15 @@ macro_rules! invoke_fn_impl {
                         function_name,
                         $($Arg,)*
                         output: core::marker::PhantomData,
+                        trap: None,
                     }
                 }
+
+                /// Returns the runtime error that occurred while executing the function, if the
+                /// invocation failed because of one.
+                pub fn trap(&self) -> Option<&$crate::Trap> {
+                    self.trap.as_ref()
+                }
             }
 
             impl<'s, $($T: ArgumentReflection,)* Output: ReturnTypeReflection> $crate::RetryResultExt for core::result::Result<Output, $ErrName<'s, $($T,)* Output>> {
@@ -147,10 +155,16 @@ macro_rules! invoke_fn_impl {
                             let function: fn($($T::Marshalled),*) -> Output::Marshalled = unsafe {
                                 core::mem::transmute(function_info.fn_ptr)
                             };
-                            let result = function($($Arg.marshal()),*);
-
-                            // Marshall the result
-                            return Ok(result.marshal_value(runtime.clone()))
+                            // The arguments are cloned, so they can be retried if a trap occurs
+                            match crate::trap::catch_trap(|| function($($Arg.clone().marshal(&runtime_ref)),*)) {
+                                // Marshall the result
+                                Ok(result) => Ok(result.marshal_value(runtime.clone())),
+                                Err(trap) => {
+                                    let mut err = $ErrName::new(trap.to_string(), runtime.clone(), function_name, $($Arg),*);
+                                    err.trap = Some(trap);
+                                    Err(err)
+                                }
+                            }
                         }
                         Err(e) => Err($ErrName::new(e, runtime.clone(), function_name, $($Arg),*))
                     }
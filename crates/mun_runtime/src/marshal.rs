@@ -17,7 +17,14 @@ pub trait Marshal<T>: Sized {
         runtime: Rc<RefCell<Runtime>>,
         type_info: Option<&abi::TypeInfo>,
     ) -> T;
+}
 
+/// Used to store values that were marshalled from a `T` in memory. Unlike `Marshal`, this does not
+/// require that the value can be converted back into a `T`, e.g. a `&str` can be passed to Mun but
+/// a Mun string cannot be borrowed as a `&str`.
+///
+/// If no `TypeInfo` is provided, the type is `()`.
+pub trait MarshalArgument<T>: Sized {
     /// Marshals `value` to memory location `ptr`.
     fn marshal_to_ptr(value: Self, ptr: NonNull<Self>, type_info: Option<&abi::TypeInfo>);
 }
@@ -36,7 +43,9 @@ impl<T> Marshal<T> for T {
         // This also requires changes to the `impl Struct`
         unsafe { ptr.as_ptr().read() }
     }
+}
 
+impl<T> MarshalArgument<T> for T {
     fn marshal_to_ptr(value: T, mut ptr: NonNull<Self>, _type_info: Option<&abi::TypeInfo>) {
         unsafe { *ptr.as_mut() = value };
     }
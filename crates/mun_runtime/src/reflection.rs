@@ -1,4 +1,8 @@
-use crate::{marshal::Marshal, Runtime, StructRef};
+use crate::{
+    function_ref::is_fn_ptr,
+    marshal::{Marshal, MarshalArgument},
+    ArrayRef, EnumRef, FunctionRef, Runtime, StructRef,
+};
 use abi::HasStaticTypeInfo;
 
 /// Returns whether the specified argument type matches the `type_info`.
@@ -7,7 +11,7 @@ pub fn equals_argument_type<'e, 'f, T: ArgumentReflection>(
     type_info: &'e abi::TypeInfo,
     arg: &'f T,
 ) -> Result<(), (&'e str, &'f str)> {
-    if type_info.guid != arg.type_guid(runtime) {
+    if !arg.matches_type(runtime, type_info) {
         Err((type_info.name(), arg.type_name(runtime)))
     } else {
         Ok(())
@@ -24,11 +28,29 @@ pub fn equals_return_type<T: ReturnTypeReflection>(
                 return Err((type_info.name(), T::type_name()));
             }
         }
+        abi::TypeGroup::StructTypes if is_fn_ptr(type_info) => {
+            if <FunctionRef as ReturnTypeReflection>::type_guid() != T::type_guid() {
+                return Err(("function", T::type_name()));
+            }
+        }
         abi::TypeGroup::StructTypes => {
-            if <StructRef as ReturnTypeReflection>::type_guid() != T::type_guid() {
+            // A tuple can also be marshalled to a Rust tuple with matching element types
+            if type_info.guid != T::type_guid()
+                && <StructRef as ReturnTypeReflection>::type_guid() != T::type_guid()
+            {
                 return Err(("struct", T::type_name()));
             }
         }
+        abi::TypeGroup::ArrayTypes => {
+            if <ArrayRef as ReturnTypeReflection>::type_guid() != T::type_guid() {
+                return Err(("array", T::type_name()));
+            }
+        }
+        abi::TypeGroup::EnumTypes => {
+            if <EnumRef as ReturnTypeReflection>::type_guid() != T::type_guid() {
+                return Err(("enum", T::type_name()));
+            }
+        }
     }
     Ok(())
 }
@@ -50,9 +72,9 @@ pub trait ReturnTypeReflection: Sized {
 }
 
 /// A type to emulate dynamic typing across compilation units for statically typed values.
-pub trait ArgumentReflection: Sized {
+pub trait ArgumentReflection: Clone {
     /// The resulting type after dereferencing.
-    type Marshalled: Marshal<Self>;
+    type Marshalled: MarshalArgument<Self>;
 
     /// Retrieves the `Guid` of the value's type.
     fn type_guid(&self, runtime: &Runtime) -> abi::Guid;
@@ -60,8 +82,14 @@ pub trait ArgumentReflection: Sized {
     /// Retrieves the name of the value's type.
     fn type_name<'r>(&'r self, runtime: &'r Runtime) -> &'r str;
 
-    /// Marshals the value.
-    fn marshal(self) -> Self::Marshalled;
+    /// Returns whether the value can be used as a value of the type described by `type_info`. By
+    /// default, the `Guid` of the value's type must equal that of `type_info`.
+    fn matches_type(&self, runtime: &Runtime, type_info: &abi::TypeInfo) -> bool {
+        type_info.guid == self.type_guid(runtime)
+    }
+
+    /// Marshals the value. Values that are garbage collected by Mun are allocated in `runtime`.
+    fn marshal(self, runtime: &Runtime) -> Self::Marshalled;
 }
 
 macro_rules! impl_primitive_type {
@@ -78,7 +106,7 @@ macro_rules! impl_primitive_type {
                     Self::type_info().name()
                 }
 
-                fn marshal(self) -> Self::Marshalled {
+                fn marshal(self, _runtime: &Runtime) -> Self::Marshalled {
                     self
                 }
             }
@@ -124,7 +152,7 @@ where
         Self::type_info().name()
     }
 
-    fn marshal(self) -> Self::Marshalled {
+    fn marshal(self, _runtime: &Runtime) -> Self::Marshalled {
         self
     }
 }
@@ -158,7 +186,7 @@ where
         Self::type_info().name()
     }
 
-    fn marshal(self) -> Self::Marshalled {
+    fn marshal(self, _runtime: &Runtime) -> Self::Marshalled {
         self
     }
 }
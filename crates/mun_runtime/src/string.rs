@@ -0,0 +1,148 @@
+use crate::garbage_collector::{GcPtr, UnsafeTypeInfo};
+use crate::{
+    marshal::{Marshal, MarshalArgument},
+    reflection::{ArgumentReflection, ReturnTypeReflection},
+    Runtime,
+};
+use abi::HasStaticTypeInfo;
+use memory::gc::{self, GcRuntime, HasIndirectionPtr};
+use std::{alloc::Layout, cell::RefCell, ptr::NonNull, rc::Rc};
+
+/// Represents a Mun string pointer.
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct RawString(GcPtr);
+
+impl RawString {
+    /// Allocates a Mun string that contains a copy of `value`.
+    pub fn new(runtime: &Runtime, value: &str) -> Self {
+        RawString(alloc_string(runtime.gc(), value.as_bytes()))
+    }
+
+    /// Returns the UTF-8 encoded contents of the string.
+    ///
+    /// # Safety
+    ///
+    /// The returned slice is only valid as long as the string is not collected by the garbage
+    /// collector.
+    pub unsafe fn as_bytes<'a>(&self) -> &'a [u8] {
+        string_bytes(self.0)
+    }
+}
+
+/// Returns the type information of Mun's `string` type.
+pub(crate) fn string_type_info() -> UnsafeTypeInfo {
+    UnsafeTypeInfo::new(NonNull::from(<str as HasStaticTypeInfo>::type_info()))
+}
+
+/// Allocates a string in `gc` that contains a copy of the UTF-8 encoded `bytes`.
+pub(crate) fn alloc_string(gc: &dyn GcRuntime<UnsafeTypeInfo>, bytes: &[u8]) -> GcPtr {
+    let mut handle = gc.alloc_array(string_type_info(), bytes.len());
+
+    // Safety: `alloc_array` allocated room for `bytes.len()` bytes after the header.
+    unsafe {
+        let (_, offset) = gc::array_layout(Layout::new::<u8>(), bytes.len());
+        std::ptr::copy_nonoverlapping(
+            bytes.as_ptr(),
+            handle.deref_mut::<u8>().add(offset),
+            bytes.len(),
+        );
+        (*handle.deref_mut::<gc::ArrayHeader>()).length = bytes.len();
+    }
+
+    handle
+}
+
+/// Returns the UTF-8 encoded contents of the string referred to by `handle`.
+///
+/// # Safety
+///
+/// `handle` must refer to a string and the returned slice is only valid as long as the string is
+/// not collected by the garbage collector.
+pub(crate) unsafe fn string_bytes<'a>(handle: GcPtr) -> &'a [u8] {
+    let header = *handle.deref::<gc::ArrayHeader>();
+    let (_, offset) = gc::array_layout(Layout::new::<u8>(), header.capacity);
+    std::slice::from_raw_parts(handle.deref::<u8>().add(offset), header.length)
+}
+
+impl ArgumentReflection for String {
+    type Marshalled = RawString;
+
+    fn type_guid(&self, _runtime: &Runtime) -> abi::Guid {
+        <str as HasStaticTypeInfo>::type_info().guid
+    }
+
+    fn type_name(&self, _runtime: &Runtime) -> &str {
+        <str as HasStaticTypeInfo>::type_info().name()
+    }
+
+    fn marshal(self, runtime: &Runtime) -> Self::Marshalled {
+        RawString::new(runtime, &self)
+    }
+}
+
+impl<'s> ArgumentReflection for &'s str {
+    type Marshalled = RawString;
+
+    fn type_guid(&self, _runtime: &Runtime) -> abi::Guid {
+        <str as HasStaticTypeInfo>::type_info().guid
+    }
+
+    fn type_name(&self, _runtime: &Runtime) -> &str {
+        <str as HasStaticTypeInfo>::type_info().name()
+    }
+
+    fn marshal(self, runtime: &Runtime) -> Self::Marshalled {
+        RawString::new(runtime, self)
+    }
+}
+
+impl ReturnTypeReflection for String {
+    type Marshalled = RawString;
+
+    fn type_guid() -> abi::Guid {
+        <str as HasStaticTypeInfo>::type_info().guid
+    }
+
+    fn type_name() -> &'static str {
+        <str as HasStaticTypeInfo>::type_info().name()
+    }
+}
+
+impl Marshal<String> for RawString {
+    fn marshal_value(self, _runtime: Rc<RefCell<Runtime>>) -> String {
+        // Safety: Mun strings are always valid UTF-8 and the string is copied before the garbage
+        // collector gets a chance to run.
+        unsafe { String::from(std::str::from_utf8_unchecked(self.as_bytes())) }
+    }
+
+    fn marshal_from_ptr(
+        ptr: NonNull<Self>,
+        runtime: Rc<RefCell<Runtime>>,
+        _type_info: Option<&abi::TypeInfo>,
+    ) -> String {
+        // `ptr` points to a `GcPtr`
+        let value = unsafe { ptr.as_ptr().read() };
+        value.marshal_value(runtime)
+    }
+}
+
+impl MarshalArgument<String> for RawString {
+    fn marshal_to_ptr(
+        value: RawString,
+        mut ptr: NonNull<Self>,
+        _type_info: Option<&abi::TypeInfo>,
+    ) {
+        unsafe { *ptr.as_mut() = value };
+    }
+}
+
+impl<'s> MarshalArgument<&'s str> for RawString {
+    fn marshal_to_ptr(
+        value: RawString,
+        mut ptr: NonNull<Self>,
+        _type_info: Option<&abi::TypeInfo>,
+    ) {
+        unsafe { *ptr.as_mut() = value };
+    }
+}
@@ -1,16 +1,18 @@
 use crate::garbage_collector::{GcPtr, GcRootPtr, UnsafeTypeInfo};
 use crate::{
-    marshal::Marshal,
+    marshal::{Marshal, MarshalArgument},
     reflection::{
         equals_argument_type, equals_return_type, ArgumentReflection, ReturnTypeReflection,
     },
     Runtime,
 };
-use memory::gc::{GcRuntime, HasIndirectionPtr};
+use abi::StaticTypeMap;
+use memory::gc::{GcRuntime, HasIndirectionPtr, RawGcPtr};
 use std::cell::RefCell;
 use std::{
     ptr::{self, NonNull},
     rc::Rc,
+    sync::Once,
 };
 
 /// Represents a Mun struct pointer.
@@ -23,6 +25,18 @@ impl RawStruct {
     pub unsafe fn get_ptr(&self) -> *const u8 {
         self.0.deref()
     }
+
+    /// Returns the representation of `nil`, which does not point to a struct.
+    fn nil() -> Self {
+        let handle: RawGcPtr = ptr::null();
+        RawStruct(handle.into())
+    }
+
+    /// Returns whether this is `nil`.
+    fn is_nil(&self) -> bool {
+        let handle: RawGcPtr = self.0.into();
+        handle.is_null()
+    }
 }
 
 /// Type-agnostic wrapper for interoperability with a Mun struct.
@@ -124,7 +138,7 @@ impl StructRef {
 
     /// Replaces the value of the field corresponding to the specified `field_name` and returns the
     /// old value.
-    pub fn replace<T: ArgumentReflection>(
+    pub fn replace<T: ArgumentReflection + ReturnTypeReflection>(
         &mut self,
         field_name: &str,
         value: T,
@@ -150,10 +164,21 @@ impl StructRef {
             )
         })?;
 
-        let field_ptr =
-            unsafe { self.field_offset_unchecked::<T::Marshalled>(struct_info, field_idx) };
-        let old = Marshal::marshal_from_ptr(field_ptr, self.runtime.clone(), Some(field_type));
-        Marshal::marshal_to_ptr(value.marshal(), field_ptr, Some(field_type));
+        let field_ptr = unsafe {
+            self.field_offset_unchecked::<<T as ArgumentReflection>::Marshalled>(
+                struct_info,
+                field_idx,
+            )
+        };
+        // The old value is read back as a return type, as not every argument type can be
+        // marshalled from Mun, e.g. a `&str`.
+        let old = Marshal::marshal_from_ptr(
+            field_ptr.cast::<<T as ReturnTypeReflection>::Marshalled>(),
+            self.runtime.clone(),
+            Some(field_type),
+        );
+        MarshalArgument::marshal_to_ptr(value.marshal(&runtime_ref), field_ptr, Some(field_type));
+        runtime_ref.gc().write_barrier(self.handle.handle());
         Ok(old)
     }
 
@@ -182,7 +207,8 @@ impl StructRef {
 
         let field_ptr =
             unsafe { self.field_offset_unchecked::<T::Marshalled>(struct_info, field_idx) };
-        Marshal::marshal_to_ptr(value.marshal(), field_ptr, Some(field_type));
+        MarshalArgument::marshal_to_ptr(value.marshal(&runtime_ref), field_ptr, Some(field_type));
+        runtime_ref.gc().write_barrier(self.handle.handle());
         Ok(())
     }
 }
@@ -216,7 +242,7 @@ impl ArgumentReflection for StructRef {
         }
     }
 
-    fn marshal(self) -> Self::Marshalled {
+    fn marshal(self, _runtime: &Runtime) -> Self::Marshalled {
         self.into_raw()
     }
 }
@@ -229,6 +255,93 @@ impl ReturnTypeReflection for StructRef {
     }
 }
 
+/// An optional reference to a garbage collected Mun struct, e.g. `Foo?`, is marshalled as an
+/// `Option<StructRef>`, where `nil` is represented by `None`.
+impl ArgumentReflection for Option<StructRef> {
+    type Marshalled = RawStruct;
+
+    fn type_guid(&self, runtime: &Runtime) -> abi::Guid {
+        match self {
+            Some(struct_ref) => struct_ref.type_guid(runtime),
+            None => <StructRef as ReturnTypeReflection>::type_guid(),
+        }
+    }
+
+    fn type_name(&self, runtime: &Runtime) -> &str {
+        match self {
+            Some(struct_ref) => struct_ref.type_name(runtime),
+            None => "nil",
+        }
+    }
+
+    fn matches_type(&self, runtime: &Runtime, type_info: &abi::TypeInfo) -> bool {
+        match self {
+            Some(struct_ref) => struct_ref.matches_type(runtime, type_info),
+            // The type information of an optional reference is that of the referenced struct, so
+            // `nil` matches any garbage collected struct.
+            None => type_info.as_struct().map_or(false, |struct_info| {
+                struct_info.memory_kind == abi::StructMemoryKind::GC
+            }),
+        }
+    }
+
+    fn marshal(self, _runtime: &Runtime) -> Self::Marshalled {
+        match self {
+            Some(struct_ref) => struct_ref.into_raw(),
+            None => RawStruct::nil(),
+        }
+    }
+}
+
+impl ReturnTypeReflection for Option<StructRef> {
+    type Marshalled = RawStruct;
+
+    fn type_guid() -> abi::Guid {
+        <StructRef as ReturnTypeReflection>::type_guid()
+    }
+
+    fn type_name() -> &'static str {
+        "struct?"
+    }
+}
+
+impl Marshal<Option<StructRef>> for RawStruct {
+    fn marshal_value(self, runtime: Rc<RefCell<Runtime>>) -> Option<StructRef> {
+        if self.is_nil() {
+            None
+        } else {
+            Some(<RawStruct as Marshal<StructRef>>::marshal_value(
+                self, runtime,
+            ))
+        }
+    }
+
+    fn marshal_from_ptr(
+        ptr: NonNull<Self>,
+        runtime: Rc<RefCell<Runtime>>,
+        type_info: Option<&abi::TypeInfo>,
+    ) -> Option<StructRef> {
+        // `type_info` is only `None` for the `()` type
+        let struct_info = type_info.unwrap().as_struct().unwrap();
+
+        // Only a gc struct can be `nil`, in which case `ptr` points to a null `GcPtr`.
+        if struct_info.memory_kind == abi::StructMemoryKind::GC && unsafe { ptr.as_ref() }.is_nil()
+        {
+            None
+        } else {
+            Some(<RawStruct as Marshal<StructRef>>::marshal_from_ptr(
+                ptr, runtime, type_info,
+            ))
+        }
+    }
+}
+
+impl MarshalArgument<Option<StructRef>> for RawStruct {
+    fn marshal_to_ptr(value: RawStruct, ptr: NonNull<Self>, type_info: Option<&abi::TypeInfo>) {
+        <RawStruct as MarshalArgument<StructRef>>::marshal_to_ptr(value, ptr, type_info)
+    }
+}
+
 impl Marshal<StructRef> for RawStruct {
     fn marshal_value(self, runtime: Rc<RefCell<Runtime>>) -> StructRef {
         StructRef::new(runtime, self)
@@ -272,7 +385,9 @@ impl Marshal<StructRef> for RawStruct {
 
         StructRef::new(runtime, RawStruct(gc_handle))
     }
+}
 
+impl MarshalArgument<StructRef> for RawStruct {
     fn marshal_to_ptr(value: RawStruct, mut ptr: NonNull<Self>, type_info: Option<&abi::TypeInfo>) {
         // `type_info` is only `None` for the `()` type
         let type_info = type_info.unwrap();
@@ -287,3 +402,88 @@ impl Marshal<StructRef> for RawStruct {
         }
     }
 }
+
+/// Implements `ReturnTypeReflection` for Rust tuples. A Mun tuple is exposed as a value struct of
+/// which the fields are named after the indices of its elements, so a Rust tuple can only be
+/// marshalled from the `RawStruct` of a Mun tuple with matching element types.
+macro_rules! impl_tuple_reflection {
+    ($(($($ty:ident: $idx:tt),+)),+) => {
+        $(
+            impl<$($ty: ReturnTypeReflection + 'static),+> ReturnTypeReflection for ($($ty,)+) {
+                type Marshalled = RawStruct;
+
+                fn type_name() -> &'static str {
+                    static mut VALUE: Option<StaticTypeMap<String>> = None;
+                    static INIT: Once = Once::new();
+
+                    let map = unsafe {
+                        INIT.call_once(|| {
+                            VALUE = Some(StaticTypeMap::new());
+                        });
+                        VALUE.as_ref().unwrap()
+                    };
+
+                    map.call_once::<Self, _>(|| {
+                        let element_names = [$($ty::type_name()),+];
+                        if element_names.len() == 1 {
+                            format!("({},)", element_names[0])
+                        } else {
+                            format!("({})", element_names.join(", "))
+                        }
+                    })
+                }
+            }
+
+            impl<$($ty: ReturnTypeReflection + 'static),+> Marshal<($($ty,)+)> for RawStruct {
+                fn marshal_value(self, runtime: Rc<RefCell<Runtime>>) -> ($($ty,)+) {
+                    let runtime_ref = runtime.borrow();
+                    // Safety: The type returned from `ptr_type` is guaranteed to live at least as
+                    // long as `Runtime` does not change. As we hold a shared reference to
+                    // `Runtime`, this is safe.
+                    let type_info =
+                        unsafe { runtime_ref.gc().ptr_type(self.0).into_inner().as_ref() };
+
+                    // The elements of a heap-allocated tuple are stored inline, so its memory can
+                    // be marshalled the same way as a tuple that is stored in a struct field.
+                    let ptr = unsafe { NonNull::new_unchecked(self.get_ptr() as *mut RawStruct) };
+                    <RawStruct as Marshal<($($ty,)+)>>::marshal_from_ptr(
+                        ptr,
+                        runtime.clone(),
+                        Some(type_info),
+                    )
+                }
+
+                fn marshal_from_ptr(
+                    ptr: NonNull<Self>,
+                    runtime: Rc<RefCell<Runtime>>,
+                    type_info: Option<&abi::TypeInfo>,
+                ) -> ($($ty,)+) {
+                    // `type_info` is only `None` for the `()` type
+                    let struct_info = type_info.unwrap().as_struct().unwrap();
+                    let ptr = ptr.cast::<u8>().as_ptr();
+                    ($(
+                        Marshal::marshal_from_ptr(
+                            // Safety: The offsets of a tuple's elements are within its memory.
+                            unsafe {
+                                NonNull::new_unchecked(ptr.add(
+                                    struct_info.field_offsets()[$idx] as usize
+                                ).cast::<<$ty as ReturnTypeReflection>::Marshalled>())
+                            },
+                            runtime.clone(),
+                            Some(struct_info.field_types()[$idx]),
+                        ),
+                    )+)
+                }
+            }
+        )+
+    }
+}
+
+impl_tuple_reflection!(
+    (A: 0),
+    (A: 0, B: 1),
+    (A: 0, B: 1, C: 2),
+    (A: 0, B: 1, C: 2, D: 3),
+    (A: 0, B: 1, C: 2, D: 3, E: 4),
+    (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5)
+);
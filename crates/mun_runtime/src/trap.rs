@@ -0,0 +1,98 @@
+use std::{borrow::Cow, fmt, panic, panic::AssertUnwindSafe};
+
+/// A runtime error that occurred while executing Mun code, e.g. a division by zero or a call to
+/// `panic`. A trap unwinds the Mun stack up to the point where Mun code was invoked from Rust.
+///
+/// A trap only records the innermost Mun function, in which the error occurred; the Mun functions
+/// that called it are not recorded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trap {
+    /// A description of the error
+    pub message: String,
+    /// The name of the Mun function in which the error occurred
+    pub function: String,
+    /// The (one-based) line in the source file at which the error occurred
+    pub line: u32,
+}
+
+impl fmt::Display for Trap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\n    at {} (line {})",
+            self.message, self.function, self.line
+        )
+    }
+}
+
+impl std::error::Error for Trap {}
+
+/// Invokes `f`, catching any trap that occurs while it is executing. Panics that did not originate
+/// from a trap continue unwinding.
+pub(crate) fn catch_trap<R>(f: impl FnOnce() -> R) -> Result<R, Trap> {
+    // Safety: A trap can only interrupt Mun code, which does not observe any state that is
+    // broken by unwinding.
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| match payload.downcast::<Trap>() {
+        Ok(trap) => *trap,
+        Err(payload) => panic::resume_unwind(payload),
+    })
+}
+
+/// Raises a trap, which unwinds the stack up to the nearest `catch_trap`.
+fn raise(trap: Trap) -> ! {
+    // `resume_unwind` does not invoke the panic hook, so nothing is printed
+    panic::resume_unwind(Box::new(trap))
+}
+
+/// Returns the UTF-8 string of `length` bytes that starts at `bytes`.
+///
+/// # Safety
+///
+/// `bytes` must point to `length` bytes that live at least as long as `'a`.
+unsafe fn str_from_raw<'a>(bytes: *const u8, length: usize) -> Cow<'a, str> {
+    String::from_utf8_lossy(std::slice::from_raw_parts(bytes, length))
+}
+
+/// Called by Mun code upon a runtime error, e.g. a division by zero or an explicit call to `panic`.
+pub(crate) extern "C-unwind" fn trap(
+    message: *const u8,
+    message_len: usize,
+    function: *const u8,
+    function_len: usize,
+    line: u32,
+) {
+    // Safety: the Mun Compiler guarantees that `message` and `function` point to respectively
+    // `message_len` and `function_len` UTF-8 encoded bytes.
+    let (message, function) = unsafe {
+        (
+            str_from_raw(message, message_len).into_owned(),
+            str_from_raw(function, function_len).into_owned(),
+        )
+    };
+    raise(Trap {
+        message,
+        function,
+        line,
+    })
+}
+
+/// Called by Mun code when an array is indexed outside of its bounds.
+pub(crate) extern "C-unwind" fn index_out_of_bounds(
+    index: usize,
+    length: usize,
+    function: *const u8,
+    function_len: usize,
+    line: u32,
+) {
+    // Safety: the Mun Compiler guarantees that `function` points to `function_len` UTF-8 encoded
+    // bytes.
+    let function = unsafe { str_from_raw(function, function_len).into_owned() };
+    raise(Trap {
+        message: format!(
+            "index out of bounds: the len is {} but the index is {}",
+            length, index
+        ),
+        function,
+        line,
+    })
+}
@@ -54,3 +54,181 @@ fn hotreload_struct_decl() {
     "#,
     );
 }
+
+#[test]
+fn hotreload_enum_decl() {
+    let mut driver = TestDriver::new(
+        r#"
+    enum(gc) Shape {
+        Circle(f32),
+        Rect { w: f32, h: f32 },
+    }
+
+    struct(gc) Holder {
+        shape: Shape,
+    }
+
+    pub fn holder() -> Holder {
+        Holder { shape: Shape::Rect { w: 1.0, h: 2.0 } }
+    }
+    "#,
+    );
+    driver.update(
+        r#"
+    enum(gc) Shape {
+        None,
+        Rect { h: f32, w: f32, d: f32 },
+        Circle(f32),
+    }
+
+    struct(gc) Holder {
+        shape: Shape,
+    }
+
+    pub fn holder() -> Holder {
+        Holder { shape: Shape::None }
+    }
+    "#,
+    );
+}
+
+#[test]
+fn hotreload_method() {
+    let mut driver = TestDriver::new(
+        r"
+    struct Foo {
+        a: i32,
+    }
+
+    impl Foo {
+        fn new(a: i32) -> Self {
+            Self { a }
+        }
+
+        fn get(self) -> i32 {
+            self.a
+        }
+    }
+
+    pub fn main() -> i32 {
+        Foo::new(5).get()
+    }
+    ",
+    );
+    assert_invoke_eq!(i32, 5, driver, "main");
+    driver.update(
+        r"
+    struct Foo {
+        a: i32,
+    }
+
+    impl Foo {
+        fn new(a: i32) -> Self {
+            Self { a }
+        }
+
+        fn get(self) -> i32 {
+            self.a * 2
+        }
+    }
+
+    pub fn main() -> i32 {
+        Foo::new(5).get()
+    }
+    ",
+    );
+    assert_invoke_eq!(i32, 10, driver, "main");
+}
+
+#[test]
+fn hotreload_trait_method() {
+    let mut driver = TestDriver::new(
+        r"
+    trait Value {
+        fn value(self) -> i32;
+    }
+
+    struct Foo {
+        a: i32,
+    }
+
+    impl Value for Foo {
+        fn value(self) -> i32 {
+            self.a
+        }
+    }
+
+    pub fn main() -> i32 {
+        let foo = Foo { a: 5 };
+        Value::value(foo) + foo.value()
+    }
+    ",
+    );
+    assert_invoke_eq!(i32, 10, driver, "main");
+    driver.update(
+        r"
+    trait Value {
+        fn value(self) -> i32;
+    }
+
+    struct Foo {
+        a: i32,
+    }
+
+    impl Value for Foo {
+        fn value(self) -> i32 {
+            self.a * 2
+        }
+    }
+
+    pub fn main() -> i32 {
+        let foo = Foo { a: 5 };
+        Value::value(foo) + foo.value()
+    }
+    ",
+    );
+    assert_invoke_eq!(i32, 20, driver, "main");
+}
+
+#[test]
+fn hotreload_globals() {
+    let mut driver = TestDriver::new(
+        r"
+    static mut SCORE: i32 = 0;
+    static mut LIVES: u8 = 3;
+
+    pub fn add_score(points: i32) -> i32 {
+        SCORE += points;
+        SCORE
+    }
+    ",
+    );
+    assert_invoke_eq!(i32, 5, driver, "add_score", 5i32);
+    assert_eq!(
+        driver.runtime_mut().borrow_mut().set_global("LIVES", 1u8),
+        Ok(())
+    );
+
+    driver.update(
+        r"
+    static mut SCORE: i64 = 0;
+    static mut LIVES: u8 = 5;
+    static mut HEALTH: f32 = 100.0;
+
+    pub fn add_score(points: i64) -> i64 {
+        SCORE += points;
+        SCORE
+    }
+
+    pub fn lives() -> u8 { LIVES }
+    pub fn health() -> f32 { HEALTH }
+    ",
+    );
+
+    // The value of a global variable is retained, and cast if its type changed
+    assert_invoke_eq!(i64, 7, driver, "add_score", 2i64);
+    assert_invoke_eq!(u8, 1, driver, "lives");
+
+    // New global variables use their initial value
+    assert_invoke_eq!(f32, 100.0, driver, "health");
+}
@@ -1,5 +1,6 @@
 use mun_runtime::{
-    invoke_fn, ArgumentReflection, RetryResultExt, ReturnTypeReflection, Runtime, StructRef,
+    invoke_fn, ArgumentReflection, ArrayRef, EnumRef, FunctionRef, RetryResultExt,
+    ReturnTypeReflection, Runtime, StructRef,
 };
 
 #[macro_use]
@@ -199,6 +200,180 @@ fn fibonacci_while() {
     assert_invoke_eq!(i64, 46368, driver, "fibonacci", 24i64);
 }
 
+#[test]
+fn fibonacci_for() {
+    let mut driver = TestDriver::new(
+        r#"
+    pub fn fibonacci(n:i64)->i64 {
+        let a = 0;
+        let b = 1;
+        for _ in 0..n {
+            let sum = a + b;
+            a = b;
+            b = sum;
+        }
+        a
+    }
+    "#,
+    );
+
+    assert_invoke_eq!(i64, 5, driver, "fibonacci", 5i64);
+    assert_invoke_eq!(i64, 89, driver, "fibonacci", 11i64);
+    assert_invoke_eq!(i64, 987, driver, "fibonacci", 16i64);
+    assert_invoke_eq!(i64, 46368, driver, "fibonacci", 24i64);
+}
+
+#[test]
+fn for_ranges() {
+    let mut driver = TestDriver::new(
+        r#"
+    pub fn sum_inclusive(n:i64)->i64 {
+        let sum = 0;
+        for i in 1..=n {
+            sum += i;
+            i = 0;
+        }
+        sum
+    }
+
+    pub fn first_multiple(of:i64, end:i64)->i64 {
+        let found = 0;
+        for i in 1..end {
+            if i % of == 0 {
+                found = i;
+                break;
+            }
+        }
+        found
+    }
+
+    pub fn count_to(end:u8)->u32 {
+        let count:u32 = 0;
+        for _ in 0..=end {
+            count += 1;
+        }
+        count
+    }
+    "#,
+    );
+
+    assert_invoke_eq!(i64, 15, driver, "sum_inclusive", 5i64);
+    assert_invoke_eq!(i64, 0, driver, "sum_inclusive", 0i64);
+    assert_invoke_eq!(i64, 7, driver, "first_multiple", 7i64, 100i64);
+    assert_invoke_eq!(i64, 0, driver, "first_multiple", 7i64, 7i64);
+    assert_invoke_eq!(u32, 256, driver, "count_to", 255u8);
+}
+
+#[test]
+fn arrays() {
+    let mut driver = TestDriver::new(
+        r#"
+    pub fn sum_fixed(n:i64)->i64 {
+        let values = [n, n + 1, n + 2];
+        values[1] += 10;
+        let sum = 0;
+        for i in 0..values.len() {
+            sum += values[i];
+        }
+        sum
+    }
+
+    pub fn sum_dynamic(n:i64)->i64 {
+        let values: [i64] = [];
+        for i in 0..n {
+            values.push(i);
+        }
+        let sum = 0;
+        while values.len() > 0 {
+            sum += values.pop();
+        }
+        sum
+    }
+
+    pub fn new_values(n:i64)->[i64] {
+        let values: [i64] = [1, 2];
+        values.push(n);
+        values
+    }
+
+    pub fn last(values:[i64])->i64 {
+        values[values.len() - 1]
+    }
+    "#,
+    );
+
+    assert_invoke_eq!(i64, 22, driver, "sum_fixed", 3i64);
+    assert_invoke_eq!(i64, 45, driver, "sum_dynamic", 10i64);
+
+    let mut values: ArrayRef = invoke_fn!(driver.runtime_mut(), "new_values", 3i64).unwrap();
+    assert_eq!(values.len(), 3);
+    assert_eq!(values.get::<i64>(2), Ok(3));
+    assert!(values.get::<i64>(3).is_err());
+    assert!(values.get::<f64>(0).is_err());
+
+    values.set(0, 5i64).unwrap();
+    values.push(7i64).unwrap();
+    assert!(values.push(1.0f64).is_err());
+    assert_eq!(values.len(), 4);
+    assert_eq!(values.get::<i64>(0), Ok(5));
+
+    assert_invoke_eq!(i64, 7, driver, "last", values);
+}
+
+#[test]
+fn strings() {
+    let mut driver = TestDriver::new(
+        r#"
+    struct Person {
+        name: string,
+        age: u8,
+    }
+
+    pub fn greet(name: string) -> string {
+        let greeting = "Hello, ";
+        greeting += name;
+        greeting + "!"
+    }
+
+    pub fn name_len(name: string) -> usize {
+        name.len()
+    }
+
+    pub fn is_admin(name: string) -> bool {
+        name == "admin"
+    }
+
+    pub fn new_person(name: string, age: u8) -> Person {
+        Person { name, age }
+    }
+    "#,
+    );
+
+    assert_invoke_eq!(String, "Hello, Mun!", driver, "greet", "Mun");
+    assert_invoke_eq!(
+        String,
+        "Hello, ümlaut!",
+        driver,
+        "greet",
+        "ümlaut".to_string()
+    );
+    assert_invoke_eq!(usize, 7, driver, "name_len", "ümlaut");
+    assert_invoke_eq!(bool, true, driver, "is_admin", "admin");
+    assert_invoke_eq!(bool, false, driver, "is_admin", "user");
+
+    let mut person: StructRef =
+        invoke_fn!(driver.runtime_mut(), "new_person", "Alice", 30u8).unwrap();
+    assert_eq!(person.get::<String>("name"), Ok("Alice".to_string()));
+    assert!(person.get::<i64>("name").is_err());
+
+    person.set("name", "Bob").unwrap();
+    assert_eq!(
+        person.replace("name", "Carol".to_string()),
+        Ok("Bob".to_string())
+    );
+    assert_eq!(person.get::<String>("name"), Ok("Carol".to_string()));
+}
+
 #[test]
 fn true_is_true() {
     let mut driver = TestDriver::new(
@@ -645,3 +820,425 @@ fn signed_and_unsigned_rem() {
     assert_invoke_eq!(i32, -2, driver, "signed");
     assert_invoke_eq!(i32, 2, driver, "unsigned");
 }
+
+#[test]
+fn enums() {
+    let mut driver = TestDriver::new(
+        r#"
+    enum Shape {
+        Circle(f32),
+        Rect { w: f32, h: f32 },
+        None,
+    }
+
+    enum(value) Value {
+        Int(i64),
+        Float(f64),
+    }
+
+    pub fn circle(r: f32) -> Shape {
+        Shape::Circle(r)
+    }
+
+    pub fn rect(w: f32, h: f32) -> Shape {
+        Shape::Rect { w, h }
+    }
+
+    pub fn area(s: Shape) -> f32 {
+        match s {
+            Shape::Circle(r) => 3.0 * r * r,
+            Shape::Rect { w, h } => w * h,
+            Shape::None => 0.0,
+        }
+    }
+
+    pub fn value(i: i64) -> Value {
+        if i > 0 { Value::Int(i) } else { Value::Float(0.5) }
+    }
+
+    pub fn as_float(v: Value) -> f64 {
+        match v {
+            Value::Int(_) => 1.0,
+            Value::Float(f) => f,
+        }
+    }
+    "#,
+    );
+
+    let circle: EnumRef = invoke_fn!(driver.runtime_mut(), "circle", 2.0f32).unwrap();
+    assert_eq!(circle.variant_name(), "Circle");
+    assert!(circle.is_variant("Circle"));
+    assert_eq!(circle.get::<f32>("0"), Ok(2.0));
+    assert!(circle.get::<f32>("w").is_err());
+    assert!(circle.get::<i64>("0").is_err());
+    assert_invoke_eq!(f32, 12.0, driver, "area", circle);
+
+    let rect: EnumRef = invoke_fn!(driver.runtime_mut(), "rect", 2.0f32, 3.0f32).unwrap();
+    assert_eq!(rect.variant_name(), "Rect");
+    assert_eq!(rect.get::<f32>("h"), Ok(3.0));
+    assert_invoke_eq!(f32, 6.0, driver, "area", rect);
+
+    let value: EnumRef = invoke_fn!(driver.runtime_mut(), "value", 0i64).unwrap();
+    assert_eq!(value.variant_name(), "Float");
+    assert_eq!(value.get::<f64>("0"), Ok(0.5));
+    assert_invoke_eq!(f64, 0.5, driver, "as_float", value);
+}
+
+#[test]
+fn marshal_generic_struct() {
+    let mut driver = TestDriver::new(
+        r#"
+    struct(value) Pair<A, B> { first: A, second: B }
+    struct(gc) Wrapper<T>(T);
+
+    fn max<T>(a: T, b: T, a_is_larger: bool) -> T {
+        if a_is_larger { a } else { b }
+    }
+
+    fn first<A, B>(pair: Pair<A, B>) -> A {
+        pair.first
+    }
+
+    pub fn pair_new(first: f32, second: i64) -> Pair<f32, i64> {
+        Pair { first, second }
+    }
+
+    pub fn wrap(pair: Pair<f32, i64>) -> Wrapper<Pair<f32, i64>> {
+        Wrapper(pair)
+    }
+
+    pub fn first_of_pair(pair: Pair<f32, i64>) -> f32 {
+        first(pair)
+    }
+
+    pub fn max_int(a: i64, b: i64) -> i64 {
+        max(a, b, a > b)
+    }
+
+    pub fn max_float(a: f64, b: f64) -> f64 {
+        max(a, b, a > b)
+    }
+
+    pub fn reassign_pair(first: f32, second: i64) -> Pair<f32, i64> {
+        let pair = Pair { first: 0.0, second: 0 };
+        pair = Pair { first, second };
+        pair
+    }
+
+    pub fn reassign_wrapped(wrapper: Wrapper<Pair<f32, i64>>, second: i64) {
+        wrapper.0 = Pair { first: wrapper.0.first, second };
+    }
+    "#,
+    );
+
+    assert_invoke_eq!(i64, 5, driver, "max_int", 3i64, 5i64);
+    assert_invoke_eq!(f64, 3.5, driver, "max_float", 3.5f64, 1.0f64);
+
+    let mut pair: StructRef = invoke_fn!(driver.runtime_mut(), "pair_new", 1.5f32, 2i64).unwrap();
+    assert_eq!(
+        StructRef::type_info(&pair, &driver.runtime_mut().borrow()).name(),
+        "Pair<core::f32, core::i64>"
+    );
+    assert_eq!(pair.get::<f32>("first"), Ok(1.5));
+    assert_eq!(pair.get::<i64>("second"), Ok(2));
+    pair.set("second", 4i64).unwrap();
+    assert_eq!(pair.get::<i64>("second"), Ok(4));
+    assert_invoke_eq!(f32, 1.5, driver, "first_of_pair", pair.clone());
+
+    let wrapper: StructRef = invoke_fn!(driver.runtime_mut(), "wrap", pair).unwrap();
+    let inner = wrapper.get::<StructRef>("0").unwrap();
+    assert_eq!(inner.get::<i64>("second"), Ok(4));
+
+    // Generic struct variables and fields can be reassigned
+    let pair: StructRef = invoke_fn!(driver.runtime_mut(), "reassign_pair", 2.5f32, 3i64).unwrap();
+    assert_eq!(pair.get::<f32>("first"), Ok(2.5));
+    assert_eq!(pair.get::<i64>("second"), Ok(3));
+
+    assert_invoke_eq!((), (), driver, "reassign_wrapped", wrapper.clone(), 8i64);
+    let inner = wrapper.get::<StructRef>("0").unwrap();
+    assert_eq!(inner.get::<f32>("first"), Ok(1.5));
+    assert_eq!(inner.get::<i64>("second"), Ok(8));
+}
+
+#[test]
+fn marshal_tuple() {
+    let mut driver = TestDriver::new(
+        r#"
+    struct(gc) Foo { pair: (i32, f32) }
+    struct(gc) Node { value: i32 }
+    struct(gc) Holder { entry: (Node, i32) }
+    enum Sign { Positive, Negative }
+
+    fn swap(pair: (i32, f32)) -> (f32, i32) {
+        let (a, b) = pair;
+        (b, a)
+    }
+
+    fn sign(a: i32) -> Sign {
+        if a >= 0 { Sign::Positive } else { Sign::Negative }
+    }
+
+    pub fn new_pair(a: i32, b: f32) -> (i32, f32) {
+        (a, b)
+    }
+
+    pub fn second((_, b): (i32, f32)) -> f32 {
+        b
+    }
+
+    pub fn second_swapped(pair: (i32, f32)) -> i32 {
+        swap(pair).1
+    }
+
+    pub fn new_foo(a: i32, b: f32) -> Foo {
+        Foo { pair: (a, b) }
+    }
+
+    pub fn reassign_pair(a: i32, b: f32) -> (i32, f32) {
+        let pair = (0, 0.0);
+        pair = (a, b);
+        pair
+    }
+
+    pub fn set_pair(foo: Foo, a: i32, b: f32) {
+        foo.pair = (a, b);
+    }
+
+    pub fn new_holder() -> Holder {
+        Holder { entry: (Node { value: 0 }, 0) }
+    }
+
+    pub fn set_entry(holder: Holder, value: i32, count: i32) {
+        holder.entry = (Node { value }, count);
+    }
+
+    pub fn entry_sum(holder: Holder) -> i32 {
+        let (node, count) = holder.entry;
+        node.value + count
+    }
+
+    pub fn same_sign(a: i32, b: i32) -> bool {
+        match (sign(a), sign(b)) {
+            (Sign::Positive, Sign::Positive) => true,
+            (Sign::Negative, Sign::Negative) => true,
+            _ => false,
+        }
+    }
+    "#,
+    );
+
+    let pair: (i32, f32) = invoke_fn!(driver.runtime_mut(), "new_pair", 1i32, 2.5f32).unwrap();
+    assert_eq!(pair, (1, 2.5));
+
+    // Tuples can also be accessed as structs of which the fields are named after their indices
+    let pair: StructRef = invoke_fn!(driver.runtime_mut(), "new_pair", 3i32, 4.5f32).unwrap();
+    assert_eq!(
+        StructRef::type_info(&pair, &driver.runtime_mut().borrow()).name(),
+        "(core::i32, core::f32)"
+    );
+    assert_eq!(pair.get::<i32>("0"), Ok(3));
+    assert_eq!(pair.get::<f32>("1"), Ok(4.5));
+    assert_invoke_eq!(f32, 4.5, driver, "second", pair.clone());
+    assert_invoke_eq!(i32, 3, driver, "second_swapped", pair);
+
+    let pair: (i32, f32) = invoke_fn!(driver.runtime_mut(), "reassign_pair", 7i32, 8.5f32).unwrap();
+    assert_eq!(pair, (7, 8.5));
+
+    let foo: StructRef = invoke_fn!(driver.runtime_mut(), "new_foo", 5i32, 6.5f32).unwrap();
+    assert_eq!(foo.get::<(i32, f32)>("pair"), Ok((5, 6.5)));
+    assert_invoke_eq!((), (), driver, "set_pair", foo.clone(), 9i32, 10.5f32);
+    assert_eq!(foo.get::<(i32, f32)>("pair"), Ok((9, 10.5)));
+
+    // The garbage collector is notified of references that are stored in a tuple field
+    let holder: StructRef = invoke_fn!(driver.runtime_mut(), "new_holder").unwrap();
+    driver.runtime_mut().borrow().gc_collect();
+    assert_invoke_eq!((), (), driver, "set_entry", holder.clone(), 3i32, 4i32);
+    driver.runtime_mut().borrow().gc_collect();
+    assert_invoke_eq!(i32, 7, driver, "entry_sum", holder);
+
+    assert_invoke_eq!(bool, true, driver, "same_sign", 1i32, 2i32);
+    assert_invoke_eq!(bool, false, driver, "same_sign", 1i32, -2i32);
+}
+
+#[test]
+fn marshal_function_pointers() {
+    let mut driver = TestDriver::new(
+        r#"
+    struct(gc) Button { on_click: fn(i32) -> i32 }
+
+    fn double(a: i32) -> i32 {
+        a * 2
+    }
+
+    pub fn apply(f: fn(i32) -> i32, a: i32) -> i32 {
+        f(a)
+    }
+
+    pub fn apply_double(a: i32) -> i32 {
+        apply(double, a)
+    }
+
+    pub fn apply_closure(a: i32, k: i32) -> i32 {
+        apply(|x| x * k + 1, a)
+    }
+
+    pub fn multiplier(k: i32) -> fn(i32) -> i32 {
+        |x| x * k
+    }
+
+    pub fn new_button(k: i32) -> Button {
+        Button { on_click: |x: i32| x + k }
+    }
+
+    pub fn click(button: Button, a: i32) -> i32 {
+        let on_click = button.on_click;
+        on_click(a)
+    }
+
+    pub fn apply_reassigned(a: i32, k: i32) -> i32 {
+        let f = |x: i32| x + k;
+        f = |x: i32| x * k;
+        f(a)
+    }
+
+    pub fn set_on_click(button: Button, k: i32) {
+        button.on_click = |x: i32| x - k;
+    }
+    "#,
+    );
+
+    assert_invoke_eq!(i32, 6, driver, "apply_double", 3i32);
+    assert_invoke_eq!(i32, 7, driver, "apply_closure", 3i32, 2i32);
+
+    let triple: FunctionRef = invoke_fn!(driver.runtime_mut(), "multiplier", 3i32).unwrap();
+    assert_eq!(
+        FunctionRef::signature(&triple, &driver.runtime_mut().borrow()),
+        "fn(core::i32) -> core::i32"
+    );
+    assert_eq!(triple.invoke1::<i32, i32>(4), Ok(12));
+    assert!(triple.invoke1::<f32, i32>(4.0).is_err());
+    assert!(triple.invoke1::<i32, f32>(4).is_err());
+
+    // Closures can be passed back to Mun
+    assert_invoke_eq!(i32, 15, driver, "apply", triple.clone(), 5i32);
+
+    let button: StructRef = invoke_fn!(driver.runtime_mut(), "new_button", 10i32).unwrap();
+    let on_click: FunctionRef = button.get("on_click").unwrap();
+    assert_eq!(on_click.invoke1::<i32, i32>(1), Ok(11));
+    assert_invoke_eq!(i32, 12, driver, "click", button.clone(), 2i32);
+
+    // Function pointer variables and fields can be reassigned
+    assert_invoke_eq!(i32, 6, driver, "apply_reassigned", 3i32, 2i32);
+    assert_invoke_eq!((), (), driver, "set_on_click", button.clone(), 4i32);
+    driver.runtime_mut().borrow().gc_collect();
+    assert_invoke_eq!(i32, 6, driver, "click", button, 10i32);
+
+    // The values captured by a closure live as long as the closure is referenced
+    driver.runtime_mut().borrow().gc_collect();
+    assert_eq!(triple.invoke1::<i32, i32>(5), Ok(15));
+}
+
+#[test]
+fn globals() {
+    let mut driver = TestDriver::new(
+        r"
+    const GRAVITY: f32 = 9.81;
+    const HALF_GRAVITY: f32 = GRAVITY / 2.0;
+
+    static MAX_SCORE: i32 = 100;
+    static mut SCORE: i32 = 0;
+
+    pub fn gravity() -> f32 { HALF_GRAVITY * 2.0 }
+
+    pub fn add_score(points: i32) -> i32 {
+        SCORE += points;
+        if SCORE > MAX_SCORE {
+            SCORE = MAX_SCORE;
+        }
+        SCORE
+    }
+    ",
+    );
+
+    assert_invoke_eq!(f32, 9.81, driver, "gravity");
+    assert_invoke_eq!(i32, 5, driver, "add_score", 5i32);
+    assert_invoke_eq!(i32, 12, driver, "add_score", 7i32);
+    assert_invoke_eq!(i32, 100, driver, "add_score", 1000i32);
+
+    {
+        let mut runtime_ref = driver.runtime_mut().borrow_mut();
+
+        // Constants are inlined, so they don't have any storage
+        assert!(runtime_ref.get_global_info("GRAVITY").is_none());
+
+        let max_score = runtime_ref.get_global_info("MAX_SCORE").unwrap();
+        assert!(!max_score.is_mutable);
+        assert_eq!(max_score.type_info().name(), "core::i32");
+        assert_eq!(runtime_ref.get_global::<i32>("MAX_SCORE"), Ok(100));
+        assert!(runtime_ref.set_global("MAX_SCORE", 5i32).is_err());
+
+        assert_eq!(runtime_ref.get_global::<i32>("SCORE"), Ok(100));
+        assert!(runtime_ref.get_global::<f64>("SCORE").is_err());
+        assert!(runtime_ref.set_global("SCORE", 5i64).is_err());
+        assert_eq!(runtime_ref.set_global("SCORE", 20i32), Ok(()));
+    }
+
+    assert_invoke_eq!(i32, 21, driver, "add_score", 1i32);
+}
+
+#[test]
+fn marshal_optional_struct() {
+    let mut driver = TestDriver::new(
+        r#"
+    struct(gc) Node { value: i32, next: Node? }
+
+    pub fn new_node(value: i32, next: Node?) -> Node {
+        Node { value, next }
+    }
+
+    pub fn next(node: Node) -> Node? {
+        node.next
+    }
+
+    pub fn sum(node: Node?) -> i32 {
+        let total = 0;
+        while node != nil {
+            total += node.value;
+            node = node.next;
+        }
+        total
+    }
+    "#,
+    );
+
+    let tail: StructRef =
+        invoke_fn!(driver.runtime_mut(), "new_node", 2i32, None::<StructRef>).unwrap();
+    let mut head: StructRef =
+        invoke_fn!(driver.runtime_mut(), "new_node", 1i32, Some(tail.clone())).unwrap();
+
+    // `nil` is marshalled as `None`
+    let next: Option<StructRef> = invoke_fn!(driver.runtime_mut(), "next", tail.clone()).unwrap();
+    assert!(next.is_none());
+    let next: Option<StructRef> = invoke_fn!(driver.runtime_mut(), "next", head.clone()).unwrap();
+    assert_eq!(next.unwrap().get::<i32>("value"), Ok(2));
+
+    assert_invoke_eq!(i32, 3, driver, "sum", Some(head.clone()));
+    assert_invoke_eq!(i32, 0, driver, "sum", None::<StructRef>);
+
+    // Optional fields can be accessed and set to `nil`
+    assert_eq!(
+        head.get::<Option<StructRef>>("next")
+            .unwrap()
+            .unwrap()
+            .get::<i32>("value"),
+        Ok(2)
+    );
+    head.set("next", None::<StructRef>).unwrap();
+    assert!(head.get::<Option<StructRef>>("next").unwrap().is_none());
+    assert_invoke_eq!(i32, 1, driver, "sum", Some(head.clone()));
+
+    // An unreferenced struct is collected, even if it was referenced by a `nil`-able field
+    drop(tail);
+    driver.runtime_mut().borrow().gc_collect();
+    assert_invoke_eq!(i32, 1, driver, "sum", Some(head));
+}
@@ -1,4 +1,8 @@
-use mun_runtime::{invoke_fn, StructRef};
+use mun_runtime::{
+    invoke_fn, ArrayRef, GcEvent, GcEventAggregator, GcKind, GuessedRename, StructRef,
+    UnmatchedRename,
+};
+use std::{collections::HashSet, sync::Arc, time::Duration};
 
 #[macro_use]
 mod util;
@@ -40,6 +44,279 @@ fn gc_trace() {
     assert_eq!(driver.runtime_mut().borrow().gc_stats().allocated_memory, 0);
 }
 
+#[test]
+fn gc_trace_array() {
+    let mut driver = TestDriver::new(
+        r#"
+    pub struct(gc) Foo {
+        bar: i64,
+    }
+
+    pub fn new_foos(n: i64) -> [Foo] {
+        let foos: [Foo] = [];
+        for i in 0..n {
+            foos.push(Foo { bar: i });
+        }
+        foos
+    }
+    "#,
+    );
+
+    let value: ArrayRef = invoke_fn!(driver.runtime_mut(), "new_foos", 5i64).unwrap();
+
+    // The elements are kept alive by the rooted array
+    assert_eq!(driver.runtime_mut().borrow().gc_collect(), false);
+    assert_eq!(value.len(), 5);
+    let foo: StructRef = value.get(4).unwrap();
+    assert_eq!(foo.get::<i64>("bar"), Ok(4));
+
+    drop(foo);
+    drop(value);
+
+    assert_eq!(driver.runtime_mut().borrow().gc_collect(), true);
+    assert_eq!(driver.runtime_mut().borrow().gc_stats().allocated_memory, 0);
+}
+
+#[test]
+fn gc_trace_string() {
+    let mut driver = TestDriver::new(
+        r#"
+    pub struct(gc) Foo {
+        name: string,
+    }
+
+    pub fn new_foo() -> Foo {
+        Foo { name: "foo" + "bar" }
+    }
+    "#,
+    );
+
+    let value: StructRef = invoke_fn!(driver.runtime_mut(), "new_foo").unwrap();
+
+    // The operands of the concatenation are garbage, but its result is kept alive by `value`
+    assert_eq!(driver.runtime_mut().borrow().gc_collect(), true);
+    assert_eq!(value.get::<String>("name"), Ok("foobar".to_string()));
+
+    drop(value);
+
+    assert_eq!(driver.runtime_mut().borrow().gc_collect(), true);
+    assert_eq!(driver.runtime_mut().borrow().gc_stats().allocated_memory, 0);
+}
+
+#[test]
+fn gc_generational_write_barrier() {
+    let mut driver = TestDriver::new(
+        r#"
+    pub struct(gc) Foo {
+        bar: Bar,
+    }
+
+    pub struct(gc) Bar {
+        baz: i64,
+    }
+
+    pub fn new_foo() -> Foo {
+        Foo { bar: Bar { baz: 1 } }
+    }
+
+    pub fn replace_bar(foo: Foo, baz: i64) {
+        foo.bar = Bar { baz };
+    }
+    "#,
+    )
+    .set_gc(GcKind::Generational { nursery_size: 1024 });
+
+    // Move `foo` to the old generation
+    let foo: StructRef = invoke_fn!(driver.runtime_mut(), "new_foo").unwrap();
+    driver
+        .runtime_mut()
+        .borrow()
+        .gc_collect_incremental(Duration::from_secs(0));
+
+    // The new `Bar` is only referenced by the old `foo`, which is reported by the write barrier
+    let _: () = invoke_fn!(driver.runtime_mut(), "replace_bar", foo.clone(), 2i64).unwrap();
+    driver
+        .runtime_mut()
+        .borrow()
+        .gc_collect_incremental(Duration::from_secs(0));
+    let bar: StructRef = foo.get("bar").unwrap();
+    assert_eq!(bar.get::<i64>("baz"), Ok(2));
+
+    drop(bar);
+    drop(foo);
+
+    assert_eq!(driver.runtime_mut().borrow().gc_collect(), true);
+    assert_eq!(driver.runtime_mut().borrow().gc_stats().allocated_memory, 0);
+}
+
+#[test]
+fn gc_heap_snapshot() {
+    let mut driver = TestDriver::new(
+        r#"
+    pub struct(gc) Foo {
+        a: Bar,
+        b: Bar,
+    }
+
+    pub struct(gc) Bar {
+        value: i64,
+    }
+
+    pub fn new_foo() -> Foo {
+        let bar = Bar { value: 1 };
+        Foo { a: bar, b: bar }
+    }
+    "#,
+    );
+
+    let foo: StructRef = invoke_fn!(driver.runtime_mut(), "new_foo").unwrap();
+    let bar: StructRef = foo.get("a").unwrap();
+    drop(bar);
+
+    let snapshot = driver.runtime_mut().borrow().gc_heap_snapshot();
+    assert_eq!(snapshot.objects.len(), 2);
+
+    // Only `foo` is rooted, and it references the same `Bar` twice
+    let roots: Vec<_> = snapshot.roots().collect();
+    assert_eq!(roots.len(), 1);
+    assert_eq!(roots[0].type_name, "Foo");
+    assert_eq!(roots[0].references.len(), 2);
+    assert_eq!(roots[0].references[0], roots[0].references[1]);
+
+    let bar = snapshot.object(roots[0].references[0]).unwrap();
+    assert_eq!(bar.type_name, "Bar");
+    assert_eq!(bar.roots, 0);
+    assert!(bar.references.is_empty());
+
+    let stats = driver.runtime_mut().borrow().gc_stats();
+    assert_eq!(stats.allocated_objects, 2);
+    assert_eq!(
+        snapshot.types.iter().map(|ty| ty.memory).sum::<usize>(),
+        stats.allocated_memory
+    );
+
+    let json: serde_json::Value = serde_json::from_str(&snapshot.to_json()).unwrap();
+    assert_eq!(json["objects"].as_array().unwrap().len(), 2);
+    assert_eq!(json["types"][0]["objects"], 1);
+
+    drop(foo);
+
+    assert_eq!(driver.runtime_mut().borrow().gc_collect(), true);
+    let stats = driver.runtime_mut().borrow().gc_stats();
+    assert_eq!(stats.allocated_objects, 0);
+    assert_eq!(stats.collections, 1);
+    assert!(stats.last_freed_memory > 0);
+    assert!(driver
+        .runtime_mut()
+        .borrow()
+        .gc_heap_snapshot()
+        .objects
+        .is_empty());
+}
+
+#[test]
+fn gc_allocation_threshold() {
+    let observer = Arc::new(GcEventAggregator::default());
+    let mut driver = TestDriver::new(
+        r#"
+    pub struct(gc) Foo {
+        value: i64,
+    }
+
+    pub fn sum_foos(n: i64) -> i64 {
+        let sum = 0;
+        for i in 0..n {
+            let foo = Foo { value: i };
+            sum += foo.value;
+        }
+        sum
+    }
+    "#,
+    )
+    .set_gc_allocation_threshold(1024)
+    .set_gc_observer(observer.clone());
+
+    // Garbage is not collected while Mun code is running
+    assert_invoke_eq!(i64, 499500, driver, "sum_foos", 1000i64);
+    let stats = driver.runtime_mut().borrow().gc_stats();
+    assert_eq!(stats.collections, 0);
+    assert!(stats.allocated_memory >= 1024);
+
+    let allocated: HashSet<_> = observer
+        .take_all()
+        .into_iter()
+        .map(|event| match event {
+            GcEvent::Allocation(handle) => handle,
+            event => panic!("expected an allocation, found {:?}", event),
+        })
+        .collect();
+    assert_eq!(allocated.len(), 1000);
+
+    // The requested collection is performed by the next update
+    assert_eq!(driver.runtime_mut().borrow_mut().update(), false);
+    let stats = driver.runtime_mut().borrow().gc_stats();
+    assert_eq!(stats.collections, 1);
+    assert_eq!(stats.allocated_memory, 0);
+
+    let mut events = observer.take_all().into_iter();
+    assert_eq!(events.next(), Some(GcEvent::Start));
+    assert_eq!(events.next_back(), Some(GcEvent::End));
+    let deallocated: HashSet<_> = events
+        .map(|event| match event {
+            GcEvent::Deallocation(handle) => handle,
+            event => panic!("expected a deallocation, found {:?}", event),
+        })
+        .collect();
+    assert_eq!(deallocated, allocated);
+
+    // No new collection is requested until the threshold is reached again
+    driver.runtime_mut().borrow_mut().update();
+    assert_eq!(driver.runtime_mut().borrow().gc_stats().collections, 1);
+    assert_eq!(observer.take_all(), Vec::new());
+}
+
+#[test]
+fn gc_on_update() {
+    let observer = Arc::new(GcEventAggregator::default());
+    let mut driver = TestDriver::new(
+        r#"
+    pub struct(gc) Foo {
+        value: i64,
+    }
+
+    pub fn new_foo() -> Foo {
+        Foo { value: 1 }
+    }
+    "#,
+    )
+    .set_gc_on_update(true)
+    .set_gc_observer(observer.clone());
+
+    let foo: StructRef = invoke_fn!(driver.runtime_mut(), "new_foo").unwrap();
+    let mut events = observer.take_all().into_iter();
+    let handle = match events.next() {
+        Some(GcEvent::Allocation(handle)) => handle,
+        event => panic!("expected an allocation, found {:?}", event),
+    };
+    assert_eq!(events.next(), None);
+
+    // The rooted object survives the collection
+    driver.runtime_mut().borrow_mut().update();
+    assert_eq!(driver.runtime_mut().borrow().gc_stats().collections, 1);
+    assert_eq!(foo.get::<i64>("value"), Ok(1));
+    assert_eq!(observer.take_all(), vec![GcEvent::Start, GcEvent::End]);
+
+    drop(foo);
+    driver.runtime_mut().borrow_mut().update();
+    let stats = driver.runtime_mut().borrow().gc_stats();
+    assert_eq!(stats.collections, 2);
+    assert_eq!(stats.allocated_memory, 0);
+    assert_eq!(
+        observer.take_all(),
+        vec![GcEvent::Start, GcEvent::Deallocation(handle), GcEvent::End]
+    );
+}
+
 #[test]
 fn map_struct_insert_field1() {
     let mut driver = TestDriver::new(
@@ -447,6 +724,286 @@ fn map_struct_rename_field2() {
     assert_eq!(foo.get::<f64>("f").unwrap(), c);
 }
 
+#[test]
+fn map_struct_renamed_from() {
+    let mut driver = TestDriver::new(
+        r#"
+        struct Foo {
+            a: i64,
+            b: i64,
+            c: f32,
+        }
+
+        pub fn foo_new(a: i64, b: i64, c: f32) -> Foo {
+            Foo { a, b, c }
+        }
+    "#,
+    );
+
+    let a = 5i64;
+    let b = 7i64;
+    let c = 3.0f32;
+    let foo: StructRef = invoke_fn!(driver.runtime_mut(), "foo_new", a, b, c).unwrap();
+
+    // Without the declared renames, `a` and `b` would be guessed to be renamed to `d` and `e`
+    // respectively, and `c` would be deleted
+    driver.update(
+        r#"
+        struct Foo {
+            #[renamed_from("b")]
+            d: i64,
+            #[renamed_from("a")]
+            e: i64,
+            #[renamed_from("c")]
+            f: f64,
+        }
+    "#,
+    );
+    assert_eq!(foo.get::<i64>("d").unwrap(), b);
+    assert_eq!(foo.get::<i64>("e").unwrap(), a);
+    assert_eq!(foo.get::<f64>("f").unwrap(), c.into());
+    assert!(driver.runtime_mut().borrow().guessed_renames().is_empty());
+}
+
+#[test]
+fn map_struct_guessed_rename() {
+    let mut driver = TestDriver::new(
+        r#"
+        struct Foo {
+            a: i64,
+            b: f64,
+        }
+
+        pub fn foo_new(a: i64, b: f64) -> Foo {
+            Foo { a, b }
+        }
+    "#,
+    );
+
+    let a = 5i64;
+    let b = 1.0f64;
+    let foo: StructRef = invoke_fn!(driver.runtime_mut(), "foo_new", a, b).unwrap();
+
+    driver.update(
+        r#"
+        struct Foo {
+            c: i64,
+            #[renamed_from("b")]
+            d: f64,
+        }
+    "#,
+    );
+    assert_eq!(foo.get::<i64>("c").unwrap(), a);
+    assert_eq!(foo.get::<f64>("d").unwrap(), b);
+    assert_eq!(
+        driver.runtime_mut().borrow().guessed_renames(),
+        &[GuessedRename {
+            type_name: "Foo".to_owned(),
+            old_name: "a".to_owned(),
+            new_name: "c".to_owned(),
+        }]
+    );
+}
+
+#[test]
+fn map_struct_unmatched_rename() {
+    let mut driver = TestDriver::new(
+        r#"
+        struct Foo {
+            a: i64,
+        }
+
+        pub fn foo_new(a: i64) -> Foo {
+            Foo { a }
+        }
+    "#,
+    );
+
+    let a = 5i64;
+    let foo: StructRef = invoke_fn!(driver.runtime_mut(), "foo_new", a).unwrap();
+
+    // The declared rename does not match a field, so `a` is not guessed to be renamed to `b`
+    driver.update(
+        r#"
+        struct Foo {
+            #[renamed_from("c")]
+            b: i64,
+        }
+    "#,
+    );
+    assert_eq!(foo.get::<i64>("b").unwrap(), 0);
+    assert!(driver.runtime_mut().borrow().guessed_renames().is_empty());
+    assert_eq!(
+        driver.runtime_mut().borrow().unmatched_renames(),
+        &[UnmatchedRename {
+            type_name: "Foo".to_owned(),
+            old_name: "c".to_owned(),
+            new_name: "b".to_owned(),
+        }]
+    );
+}
+
+#[test]
+fn map_struct_migration() {
+    let mut driver = TestDriver::new(
+        r#"
+        struct Foo {
+            a: i64,
+            celsius: f64,
+        }
+
+        pub fn foo_new(a: i64, celsius: f64) -> Foo {
+            Foo { a, celsius }
+        }
+    "#,
+    )
+    .insert_migration("Foo", |old, new| {
+        let celsius = old.get::<f64>("celsius").unwrap();
+        new.set("kelvin", celsius + 273.15).unwrap();
+        new.set("rounded", celsius.round() as i32).unwrap();
+    });
+
+    let a = 5i64;
+    let celsius = 21.6f64;
+    let foo: StructRef = invoke_fn!(driver.runtime_mut(), "foo_new", a, celsius).unwrap();
+
+    driver.update(
+        r#"
+        struct Foo {
+            a: i64,
+            kelvin: f64,
+            rounded: i32,
+        }
+    "#,
+    );
+    assert_eq!(foo.get::<i64>("a").unwrap(), a);
+    assert_eq!(foo.get::<f64>("kelvin").unwrap(), celsius + 273.15);
+    assert_eq!(foo.get::<i32>("rounded").unwrap(), 22);
+}
+
+#[test]
+fn map_renamed_struct_migration() {
+    let mut driver = TestDriver::new(
+        r#"
+        struct OldFoo {
+            a: i64,
+            celsius: f64,
+        }
+
+        struct Bar {
+            foo: OldFoo,
+        }
+
+        pub fn bar_new(a: i64, celsius: f64) -> Bar {
+            Bar { foo: OldFoo { a, celsius } }
+        }
+    "#,
+    )
+    .insert_rename_migration("OldFoo", "Foo", |old, new| {
+        let celsius = old.get::<f64>("celsius").unwrap();
+        new.set("kelvin", celsius + 273.15).unwrap();
+    });
+
+    let a = 5i64;
+    let celsius = 21.6f64;
+    let bar: StructRef = invoke_fn!(driver.runtime_mut(), "bar_new", a, celsius).unwrap();
+    let foo = bar.get::<StructRef>("foo").unwrap();
+
+    driver.update(
+        r#"
+        struct Foo {
+            a: i64,
+            kelvin: f64,
+        }
+
+        struct Bar {
+            foo: Foo,
+        }
+    "#,
+    );
+    assert_eq!(foo.get::<i64>("a").unwrap(), a);
+    assert_eq!(foo.get::<f64>("kelvin").unwrap(), celsius + 273.15);
+
+    let foo = bar.get::<StructRef>("foo").unwrap();
+    assert_eq!(foo.get::<i64>("a").unwrap(), a);
+    assert_eq!(foo.get::<f64>("kelvin").unwrap(), celsius + 273.15);
+}
+
+#[test]
+fn map_value_struct_field_migration() {
+    let mut driver = TestDriver::new(
+        r#"
+        struct(value) Temperature {
+            celsius: f64,
+        }
+
+        struct Room {
+            temperature: Temperature,
+        }
+
+        pub fn room_new(celsius: f64) -> Room {
+            Room { temperature: Temperature { celsius } }
+        }
+    "#,
+    )
+    .insert_migration("Temperature", |old, new| {
+        let celsius = old.get::<f64>("celsius").unwrap();
+        new.set("kelvin", celsius + 273.15).unwrap();
+    });
+
+    let celsius = 21.6f64;
+    let room: StructRef = invoke_fn!(driver.runtime_mut(), "room_new", celsius).unwrap();
+
+    driver.update(
+        r#"
+        struct(value) Temperature {
+            kelvin: f64,
+        }
+
+        struct Room {
+            temperature: Temperature,
+        }
+    "#,
+    );
+    let temperature = room.get::<StructRef>("temperature").unwrap();
+    assert_eq!(temperature.get::<f64>("kelvin").unwrap(), celsius + 273.15);
+}
+
+#[test]
+fn map_struct_panicking_migration() {
+    let mut driver = TestDriver::new(
+        r#"
+        struct Foo {
+            a: i64,
+        }
+
+        pub fn foo_new(a: i64) -> Foo {
+            Foo { a }
+        }
+    "#,
+    )
+    .insert_migration("Foo", |_, new| {
+        new.set("b", 1i32).unwrap();
+        panic!("the migration failed");
+    });
+
+    let a = 5i64;
+    let foo: StructRef = invoke_fn!(driver.runtime_mut(), "foo_new", a).unwrap();
+
+    driver.update(
+        r#"
+        struct Foo {
+            a: i64,
+            b: i32,
+        }
+    "#,
+    );
+
+    // The default conversion is used instead
+    assert_eq!(foo.get::<i64>("a").unwrap(), a);
+    assert_eq!(foo.get::<i32>("b").unwrap(), 0);
+}
+
 #[test]
 fn map_struct_all() {
     let mut driver = TestDriver::new(
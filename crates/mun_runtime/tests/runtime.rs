@@ -1,5 +1,6 @@
 mod util;
 
+use mun_runtime::Trap;
 use std::io;
 use util::*;
 
@@ -49,3 +50,135 @@ fn arg_missing_bug() {
 
     driver.spawn().unwrap()
 }
+
+#[test]
+fn multiple_files() {
+    let mut driver = TestDriver::with_files(
+        r"
+    mod math;
+    use package::math::square;
+
+    pub fn main() -> i32 {
+        square(3) + math::double(2)
+    }",
+        &[(
+            "math.mun",
+            r"
+    pub fn square(n: i32) -> i32 {
+        n * n
+    }
+
+    pub fn double(n: i32) -> i32 {
+        n + n
+    }",
+        )],
+    );
+
+    assert_invoke_eq!(i32, 13, driver, "main");
+    assert_invoke_eq!(i32, 16, driver, "math::square", 4i32);
+}
+
+#[test]
+fn traps() {
+    let mut driver = TestDriver::new(
+        r#"
+    pub fn divide(a: i32, b: i32) -> i32 {
+        a / b
+    }
+
+    pub fn fail(a: i32) -> i32 {
+        if a > 0 {
+            panic("a must not be positive");
+        }
+        a
+    }
+
+    pub fn index(i: usize) -> i32 {
+        let a = [1, 2, 3];
+        a[i]
+    }"#,
+    );
+
+    let result: Result<i32, _> =
+        mun_runtime::invoke_fn!(driver.runtime_mut(), "divide", 1i32, 0i32);
+    let err = result.unwrap_err();
+    assert_eq!(
+        err.trap(),
+        Some(&Trap {
+            message: "attempt to divide by zero".to_owned(),
+            function: "divide".to_owned(),
+            line: 3,
+        })
+    );
+    assert_eq!(
+        err.to_string(),
+        "attempt to divide by zero\n    at divide (line 3)"
+    );
+
+    let result: Result<i32, _> =
+        mun_runtime::invoke_fn!(driver.runtime_mut(), "divide", i32::MIN, -1i32);
+    let err = result.unwrap_err();
+    assert_eq!(
+        err.trap().map(|trap| trap.message.as_str()),
+        Some("attempt to divide with overflow")
+    );
+
+    let result: Result<i32, _> = mun_runtime::invoke_fn!(driver.runtime_mut(), "fail", 1i32);
+    let err = result.unwrap_err();
+    assert_eq!(
+        err.trap(),
+        Some(&Trap {
+            message: "a must not be positive".to_owned(),
+            function: "fail".to_owned(),
+            line: 8,
+        })
+    );
+
+    let result: Result<i32, _> = mun_runtime::invoke_fn!(driver.runtime_mut(), "index", 3usize);
+    let err = result.unwrap_err();
+    assert_eq!(
+        err.trap(),
+        Some(&Trap {
+            message: "index out of bounds: the len is 3 but the index is 3".to_owned(),
+            function: "index".to_owned(),
+            line: 15,
+        })
+    );
+
+    // The runtime can still be used after a trap
+    assert_invoke_eq!(i32, 3, driver, "divide", 6i32, 2i32);
+    assert_invoke_eq!(i32, 0, driver, "fail", 0i32);
+    assert_invoke_eq!(i32, 2, driver, "index", 1usize);
+}
+
+#[test]
+fn nested_traps() {
+    let mut driver = TestDriver::new(
+        r#"
+    pub fn inner(a: i32, b: i32) -> i32 {
+        a / b
+    }
+
+    fn middle(a: i32, b: i32) -> i32 {
+        inner(a, b) + 1
+    }
+
+    pub fn outer(a: i32, b: i32) -> i32 {
+        middle(a, b) * 2
+    }"#,
+    );
+
+    // The trap unwinds through the frames of `middle` and `outer`
+    let result: Result<i32, _> = mun_runtime::invoke_fn!(driver.runtime_mut(), "outer", 1i32, 0i32);
+    let err = result.unwrap_err();
+    assert_eq!(
+        err.trap(),
+        Some(&Trap {
+            message: "attempt to divide by zero".to_owned(),
+            function: "inner".to_owned(),
+            line: 3,
+        })
+    );
+
+    assert_invoke_eq!(i32, 6, driver, "outer", 4i32, 2i32);
+}
@@ -1,8 +1,12 @@
 #![allow(dead_code, unused_macros)]
 
 use mun_compiler::{Config, DisplayColor, Driver, FileId, PathOrInline, RelativePathBuf};
-use mun_runtime::{IntoFunctionDefinition, Runtime, RuntimeBuilder};
+use mun_runtime::{
+    GcEvent, GcKind, GcObserver, IntoFunctionDefinition, MigrationValue, Runtime, RuntimeBuilder,
+    UnsafeTypeInfo,
+};
 use std::io::Cursor;
+use std::sync::Arc;
 use std::{cell::RefCell, path::PathBuf, rc::Rc, thread::sleep, time::Duration};
 
 /// Implements a compiler and runtime in one that can invoke functions. Use of the TestDriver
@@ -37,6 +41,12 @@ impl RuntimeOrBuilder {
 impl TestDriver {
     /// Construct a new TestDriver from a single Mun source
     pub fn new(text: &str) -> Self {
+        Self::with_files(text, &[])
+    }
+
+    /// Construct a new TestDriver from the Mun source of a root module and additional source
+    /// files, specified as pairs of relative paths and sources
+    pub fn with_files(text: &str, files: &[(&str, &str)]) -> Self {
         let temp_dir = tempfile::TempDir::new().unwrap();
         let config = Config {
             out_dir: Some(temp_dir.path().to_path_buf()),
@@ -48,6 +58,9 @@ impl TestDriver {
             contents: text.to_owned(),
         };
         let (mut driver, file_id) = Driver::with_file(config, input).unwrap();
+        for (rel_path, contents) in files {
+            driver.add_file(RelativePathBuf::from(*rel_path), contents);
+        }
         let mut compiler_errors: Vec<u8> = Vec::new();
         if driver
             .emit_diagnostics(&mut Cursor::new(&mut compiler_errors))
@@ -118,6 +131,91 @@ impl TestDriver {
         self
     }
 
+    /// Sets the kind of garbage collector that is used by the runtime.
+    pub fn set_gc(mut self, gc: GcKind) -> Self {
+        self.runtime = match self.runtime {
+            RuntimeOrBuilder::Builder(builder) => RuntimeOrBuilder::Builder(builder.set_gc(gc)),
+            _ => unreachable!(),
+        };
+        self
+    }
+
+    /// Sets the number of allocated bytes after which the runtime collects garbage on update.
+    pub fn set_gc_allocation_threshold(mut self, bytes: usize) -> Self {
+        self.runtime = match self.runtime {
+            RuntimeOrBuilder::Builder(builder) => {
+                RuntimeOrBuilder::Builder(builder.set_gc_allocation_threshold(bytes))
+            }
+            _ => unreachable!(),
+        };
+        self
+    }
+
+    /// Sets whether the runtime collects garbage every update.
+    pub fn set_gc_on_update(mut self, gc_on_update: bool) -> Self {
+        self.runtime = match self.runtime {
+            RuntimeOrBuilder::Builder(builder) => {
+                RuntimeOrBuilder::Builder(builder.set_gc_on_update(gc_on_update))
+            }
+            _ => unreachable!(),
+        };
+        self
+    }
+
+    /// Sets an observer that receives the events of the runtime's garbage collector.
+    pub fn set_gc_observer<O: GcObserver<Event = GcEvent> + 'static>(
+        mut self,
+        observer: Arc<O>,
+    ) -> Self {
+        self.runtime = match self.runtime {
+            RuntimeOrBuilder::Builder(builder) => {
+                RuntimeOrBuilder::Builder(builder.set_gc_observer(observer))
+            }
+            _ => unreachable!(),
+        };
+        self
+    }
+
+    /// Adds a migration for objects of the type called `type_name` to the runtime.
+    pub fn insert_migration<F>(mut self, type_name: &str, migration: F) -> Self
+    where
+        F: Fn(&MigrationValue<UnsafeTypeInfo>, &mut MigrationValue<UnsafeTypeInfo>)
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.runtime = match self.runtime {
+            RuntimeOrBuilder::Builder(builder) => {
+                RuntimeOrBuilder::Builder(builder.insert_migration(type_name, migration))
+            }
+            _ => unreachable!(),
+        };
+        self
+    }
+
+    /// Adds a migration for objects of the type called `old_type_name`, which is renamed to
+    /// `new_type_name`, to the runtime.
+    pub fn insert_rename_migration<F>(
+        mut self,
+        old_type_name: &str,
+        new_type_name: &str,
+        migration: F,
+    ) -> Self
+    where
+        F: Fn(&MigrationValue<UnsafeTypeInfo>, &mut MigrationValue<UnsafeTypeInfo>)
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.runtime = match self.runtime {
+            RuntimeOrBuilder::Builder(builder) => RuntimeOrBuilder::Builder(
+                builder.insert_rename_migration(old_type_name, new_type_name, migration),
+            ),
+            _ => unreachable!(),
+        };
+        self
+    }
+
     /// Returns the `Runtime` used by this instance
     pub fn runtime_mut(&mut self) -> &mut Rc<RefCell<Runtime>> {
         self.runtime.spawn().unwrap();
@@ -141,6 +141,11 @@ pub unsafe extern "C" fn mun_runtime_create(
         library_path: library_path.into(),
         delay: Duration::from_millis(delay_ms.into()),
         user_functions,
+        gc: Default::default(),
+        gc_trigger_policy: Default::default(),
+        gc_on_update: false,
+        gc_observer: None,
+        migrations: Default::default(),
     };
 
     let runtime = match Runtime::new(runtime_options) {
@@ -0,0 +1,12 @@
+//! Generic algorithms to search the syntax tree.
+
+use crate::{AstNode, SyntaxNode, TextUnit};
+
+/// Returns the smallest node of type `N` that contains the token(s) at `offset`. If the offset lies
+/// between two tokens both are considered.
+pub fn find_node_at_offset<N: AstNode>(syntax: &SyntaxNode, offset: TextUnit) -> Option<N> {
+    syntax
+        .token_at_offset(offset)
+        .filter_map(|token| token.parent().ancestors().find_map(N::cast))
+        .min_by_key(|node| node.syntax().text_range().len())
+}
@@ -158,6 +158,56 @@ impl BinExpr {
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RangeOp {
+    /// The `..` operator for a half-open range
+    Exclusive,
+    /// The `..=` operator for a closed range
+    Inclusive,
+}
+
+impl ast::RangeExpr {
+    pub fn op_details(&self) -> Option<(SyntaxToken, RangeOp)> {
+        self.syntax()
+            .children_with_tokens()
+            .filter_map(|it| it.into_token())
+            .find_map(|c| {
+                let range_op = match c.kind() {
+                    T![..] => RangeOp::Exclusive,
+                    T![..=] => RangeOp::Inclusive,
+                    _ => return None,
+                };
+                Some((c, range_op))
+            })
+    }
+
+    pub fn op_kind(&self) -> Option<RangeOp> {
+        self.op_details().map(|t| t.1)
+    }
+
+    pub fn op_token(&self) -> Option<SyntaxToken> {
+        self.op_details().map(|t| t.0)
+    }
+
+    pub fn start(&self) -> Option<ast::Expr> {
+        children(self).next()
+    }
+
+    pub fn end(&self) -> Option<ast::Expr> {
+        children(self).nth(1)
+    }
+}
+
+impl ast::IndexExpr {
+    pub fn base(&self) -> Option<ast::Expr> {
+        children(self).next()
+    }
+
+    pub fn index(&self) -> Option<ast::Expr> {
+        children(self).nth(1)
+    }
+}
+
 #[derive(PartialEq, Eq)]
 pub enum FieldKind {
     Name(ast::NameRef),
@@ -207,6 +257,7 @@ pub enum LiteralKind {
     IntNumber,
     FloatNumber,
     Bool,
+    Nil,
 }
 
 impl Literal {
@@ -224,6 +275,7 @@ impl Literal {
             FLOAT_NUMBER => LiteralKind::FloatNumber,
             INT_NUMBER => LiteralKind::IntNumber,
             T![true] | T![false] => LiteralKind::Bool,
+            T![nil] => LiteralKind::Nil,
             _ => unreachable!(),
         }
     }
@@ -243,7 +295,7 @@ impl Literal {
                 let (str, suffix) = split_float_text_and_suffix(text);
                 (SmolStr::new(str), suffix.map(SmolStr::new))
             }
-            LiteralKind::Bool => (text.clone(), None),
+            LiteralKind::Bool | LiteralKind::Nil => (text.clone(), None),
         }
     }
 }
@@ -4,7 +4,7 @@ use crate::{
 };
 use crate::{SmolStr, SyntaxNode};
 use abi::StructMemoryKind;
-use text_unit::TextRange;
+use text_unit::{TextRange, TextUnit};
 
 impl ast::Name {
     pub fn text(&self) -> &SmolStr {
@@ -53,6 +53,58 @@ impl ast::FunctionDef {
 
         TextRange::from_to(start, end)
     }
+
+    /// Returns the `impl` block that contains this function, if the function is defined in one.
+    pub fn impl_block(&self) -> Option<ast::ImplBlock> {
+        let item_list = self.syntax().parent().and_then(ast::ItemList::cast)?;
+        item_list.syntax().parent().and_then(ast::ImplBlock::cast)
+    }
+
+    /// Returns the trait that declares this function, if the function is declared in one.
+    pub fn trait_def(&self) -> Option<ast::TraitDef> {
+        let item_list = self.syntax().parent().and_then(ast::ItemList::cast)?;
+        item_list.syntax().parent().and_then(ast::TraitDef::cast)
+    }
+}
+
+impl ast::ImplBlock {
+    /// Returns the type for which the `impl` block is defined, e.g. `Enemy` in both
+    /// `impl Enemy { .. }` and `impl Update for Enemy { .. }`.
+    pub fn target_type(&self) -> Option<ast::TypeRef> {
+        let for_kw = self.for_kw_offset();
+        self.type_refs().find(|type_ref| match for_kw {
+            Some(for_kw) => type_ref.syntax().text_range().start() > for_kw,
+            None => true,
+        })
+    }
+
+    /// Returns the trait that is implemented by the `impl` block, e.g. `Update` in
+    /// `impl Update for Enemy { .. }`.
+    pub fn target_trait(&self) -> Option<ast::TypeRef> {
+        let for_kw = self.for_kw_offset()?;
+        self.type_refs()
+            .find(|type_ref| type_ref.syntax().text_range().start() < for_kw)
+    }
+
+    fn type_refs(&self) -> impl Iterator<Item = ast::TypeRef> {
+        self.syntax().children().filter_map(ast::TypeRef::cast)
+    }
+
+    fn for_kw_offset(&self) -> Option<TextUnit> {
+        self.syntax()
+            .children_with_tokens()
+            .find(|p| p.kind() == T![for])
+            .map(|kw| kw.text_range().start())
+    }
+}
+
+impl ast::StaticDef {
+    /// Returns true if the static is mutable, e.g. `static mut SCORE: i32 = 0;`.
+    pub fn is_mut(&self) -> bool {
+        self.syntax()
+            .children_with_tokens()
+            .any(|p| p.kind() == T![mut])
+    }
 }
 
 fn text_of_first_token(node: &SyntaxNode) -> &SmolStr {
@@ -69,6 +121,7 @@ pub enum PathSegmentKind {
     Name(ast::NameRef),
     SelfKw,
     SuperKw,
+    PackageKw,
 }
 
 impl ast::PathSegment {
@@ -86,6 +139,7 @@ impl ast::PathSegment {
             match self.syntax().first_child_or_token()?.kind() {
                 T![self] => PathSegmentKind::SelfKw,
                 T![super] => PathSegmentKind::SuperKw,
+                T![package] => PathSegmentKind::PackageKw,
                 _ => return None,
             }
         };
@@ -100,6 +154,15 @@ impl ast::PathSegment {
     }
 }
 
+impl ast::UseTree {
+    /// Returns true if this use tree ends with a glob, e.g. `foo::*`
+    pub fn has_star_token(&self) -> bool {
+        self.syntax()
+            .children_with_tokens()
+            .any(|it| it.kind() == T![*])
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StructKind {
     Record(ast::RecordFieldDefList),
@@ -168,6 +231,34 @@ impl ast::StructDef {
     }
 }
 
+impl ast::EnumDef {
+    pub fn signature_range(&self) -> TextRange {
+        let enum_kw = self
+            .syntax()
+            .children_with_tokens()
+            .find(|p| p.kind() == T![enum])
+            .map(|kw| kw.text_range());
+        let name = self.name().map(|n| n.syntax.text_range());
+
+        let start = enum_kw
+            .map(|kw| kw.start())
+            .unwrap_or_else(|| self.syntax.text_range().start());
+
+        let end = name
+            .map(|name| name.end())
+            .or_else(|| enum_kw.map(|kw| kw.end()))
+            .unwrap_or_else(|| self.syntax().text_range().end());
+
+        TextRange::from_to(start, end)
+    }
+}
+
+impl ast::EnumVariant {
+    pub fn kind(&self) -> StructKind {
+        StructKind::from_node(self)
+    }
+}
+
 pub enum VisibilityKind {
     PubPackage,
     PubSuper,
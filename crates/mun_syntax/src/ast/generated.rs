@@ -46,23 +46,23 @@ impl ArgList {
     }
 }
 
-// BinExpr
+// ArrayExpr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct BinExpr {
+pub struct ArrayExpr {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for BinExpr {
+impl AstNode for ArrayExpr {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            BIN_EXPR => true,
+            ARRAY_EXPR => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(BinExpr { syntax })
+            Some(ArrayExpr { syntax })
         } else {
             None
         }
@@ -71,25 +71,29 @@ impl AstNode for BinExpr {
         &self.syntax
     }
 }
-impl BinExpr {}
+impl ArrayExpr {
+    pub fn exprs(&self) -> impl Iterator<Item = Expr> {
+        super::children(self)
+    }
+}
 
-// BindPat
+// ArrayType
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct BindPat {
+pub struct ArrayType {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for BindPat {
+impl AstNode for ArrayType {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            BIND_PAT => true,
+            ARRAY_TYPE => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(BindPat { syntax })
+            Some(ArrayType { syntax })
         } else {
             None
         }
@@ -98,30 +102,33 @@ impl AstNode for BindPat {
         &self.syntax
     }
 }
-impl ast::NameOwner for BindPat {}
-impl BindPat {
-    pub fn pat(&self) -> Option<Pat> {
+impl ArrayType {
+    pub fn type_ref(&self) -> Option<TypeRef> {
+        super::child_opt(self)
+    }
+
+    pub fn size(&self) -> Option<Expr> {
         super::child_opt(self)
     }
 }
 
-// BlockExpr
+// Attr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct BlockExpr {
+pub struct Attr {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for BlockExpr {
+impl AstNode for Attr {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            BLOCK_EXPR => true,
+            ATTR => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(BlockExpr { syntax })
+            Some(Attr { syntax })
         } else {
             None
         }
@@ -130,33 +137,33 @@ impl AstNode for BlockExpr {
         &self.syntax
     }
 }
-impl BlockExpr {
-    pub fn statements(&self) -> impl Iterator<Item = Stmt> {
-        super::children(self)
+impl Attr {
+    pub fn name_ref(&self) -> Option<NameRef> {
+        super::child_opt(self)
     }
 
-    pub fn expr(&self) -> Option<Expr> {
+    pub fn attr_arg_list(&self) -> Option<AttrArgList> {
         super::child_opt(self)
     }
 }
 
-// BreakExpr
+// AttrArgList
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct BreakExpr {
+pub struct AttrArgList {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for BreakExpr {
+impl AstNode for AttrArgList {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            BREAK_EXPR => true,
+            ATTR_ARG_LIST => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(BreakExpr { syntax })
+            Some(AttrArgList { syntax })
         } else {
             None
         }
@@ -165,29 +172,33 @@ impl AstNode for BreakExpr {
         &self.syntax
     }
 }
-impl BreakExpr {
-    pub fn expr(&self) -> Option<Expr> {
-        super::child_opt(self)
+impl AttrArgList {
+    pub fn args(&self) -> impl Iterator<Item = NameRef> {
+        super::children(self)
+    }
+
+    pub fn literals(&self) -> impl Iterator<Item = Literal> {
+        super::children(self)
     }
 }
 
-// CallExpr
+// BinExpr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct CallExpr {
+pub struct BinExpr {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for CallExpr {
+impl AstNode for BinExpr {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            CALL_EXPR => true,
+            BIN_EXPR => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(CallExpr { syntax })
+            Some(BinExpr { syntax })
         } else {
             None
         }
@@ -196,30 +207,25 @@ impl AstNode for CallExpr {
         &self.syntax
     }
 }
-impl ast::ArgListOwner for CallExpr {}
-impl CallExpr {
-    pub fn expr(&self) -> Option<Expr> {
-        super::child_opt(self)
-    }
-}
+impl BinExpr {}
 
-// Condition
+// BindPat
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Condition {
+pub struct BindPat {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for Condition {
+impl AstNode for BindPat {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            CONDITION => true,
+            BIND_PAT => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(Condition { syntax })
+            Some(BindPat { syntax })
         } else {
             None
         }
@@ -228,35 +234,30 @@ impl AstNode for Condition {
         &self.syntax
     }
 }
-impl Condition {
+impl ast::NameOwner for BindPat {}
+impl BindPat {
     pub fn pat(&self) -> Option<Pat> {
         super::child_opt(self)
     }
-
-    pub fn expr(&self) -> Option<Expr> {
-        super::child_opt(self)
-    }
 }
 
-// Expr
+// BlockExpr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Expr {
+pub struct BlockExpr {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for Expr {
+impl AstNode for BlockExpr {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            LITERAL | PREFIX_EXPR | PATH_EXPR | BIN_EXPR | PAREN_EXPR | CALL_EXPR | FIELD_EXPR
-            | IF_EXPR | LOOP_EXPR | WHILE_EXPR | RETURN_EXPR | BREAK_EXPR | BLOCK_EXPR
-            | RECORD_LIT => true,
+            BLOCK_EXPR => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(Expr { syntax })
+            Some(BlockExpr { syntax })
         } else {
             None
         }
@@ -265,135 +266,33 @@ impl AstNode for Expr {
         &self.syntax
     }
 }
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum ExprKind {
-    Literal(Literal),
-    PrefixExpr(PrefixExpr),
-    PathExpr(PathExpr),
-    BinExpr(BinExpr),
-    ParenExpr(ParenExpr),
-    CallExpr(CallExpr),
-    FieldExpr(FieldExpr),
-    IfExpr(IfExpr),
-    LoopExpr(LoopExpr),
-    WhileExpr(WhileExpr),
-    ReturnExpr(ReturnExpr),
-    BreakExpr(BreakExpr),
-    BlockExpr(BlockExpr),
-    RecordLit(RecordLit),
-}
-impl From<Literal> for Expr {
-    fn from(n: Literal) -> Expr {
-        Expr { syntax: n.syntax }
-    }
-}
-impl From<PrefixExpr> for Expr {
-    fn from(n: PrefixExpr) -> Expr {
-        Expr { syntax: n.syntax }
-    }
-}
-impl From<PathExpr> for Expr {
-    fn from(n: PathExpr) -> Expr {
-        Expr { syntax: n.syntax }
-    }
-}
-impl From<BinExpr> for Expr {
-    fn from(n: BinExpr) -> Expr {
-        Expr { syntax: n.syntax }
-    }
-}
-impl From<ParenExpr> for Expr {
-    fn from(n: ParenExpr) -> Expr {
-        Expr { syntax: n.syntax }
-    }
-}
-impl From<CallExpr> for Expr {
-    fn from(n: CallExpr) -> Expr {
-        Expr { syntax: n.syntax }
-    }
-}
-impl From<FieldExpr> for Expr {
-    fn from(n: FieldExpr) -> Expr {
-        Expr { syntax: n.syntax }
-    }
-}
-impl From<IfExpr> for Expr {
-    fn from(n: IfExpr) -> Expr {
-        Expr { syntax: n.syntax }
-    }
-}
-impl From<LoopExpr> for Expr {
-    fn from(n: LoopExpr) -> Expr {
-        Expr { syntax: n.syntax }
-    }
-}
-impl From<WhileExpr> for Expr {
-    fn from(n: WhileExpr) -> Expr {
-        Expr { syntax: n.syntax }
-    }
-}
-impl From<ReturnExpr> for Expr {
-    fn from(n: ReturnExpr) -> Expr {
-        Expr { syntax: n.syntax }
-    }
-}
-impl From<BreakExpr> for Expr {
-    fn from(n: BreakExpr) -> Expr {
-        Expr { syntax: n.syntax }
-    }
-}
-impl From<BlockExpr> for Expr {
-    fn from(n: BlockExpr) -> Expr {
-        Expr { syntax: n.syntax }
-    }
-}
-impl From<RecordLit> for Expr {
-    fn from(n: RecordLit) -> Expr {
-        Expr { syntax: n.syntax }
+impl BlockExpr {
+    pub fn statements(&self) -> impl Iterator<Item = Stmt> {
+        super::children(self)
     }
-}
 
-impl Expr {
-    pub fn kind(&self) -> ExprKind {
-        match self.syntax.kind() {
-            LITERAL => ExprKind::Literal(Literal::cast(self.syntax.clone()).unwrap()),
-            PREFIX_EXPR => ExprKind::PrefixExpr(PrefixExpr::cast(self.syntax.clone()).unwrap()),
-            PATH_EXPR => ExprKind::PathExpr(PathExpr::cast(self.syntax.clone()).unwrap()),
-            BIN_EXPR => ExprKind::BinExpr(BinExpr::cast(self.syntax.clone()).unwrap()),
-            PAREN_EXPR => ExprKind::ParenExpr(ParenExpr::cast(self.syntax.clone()).unwrap()),
-            CALL_EXPR => ExprKind::CallExpr(CallExpr::cast(self.syntax.clone()).unwrap()),
-            FIELD_EXPR => ExprKind::FieldExpr(FieldExpr::cast(self.syntax.clone()).unwrap()),
-            IF_EXPR => ExprKind::IfExpr(IfExpr::cast(self.syntax.clone()).unwrap()),
-            LOOP_EXPR => ExprKind::LoopExpr(LoopExpr::cast(self.syntax.clone()).unwrap()),
-            WHILE_EXPR => ExprKind::WhileExpr(WhileExpr::cast(self.syntax.clone()).unwrap()),
-            RETURN_EXPR => ExprKind::ReturnExpr(ReturnExpr::cast(self.syntax.clone()).unwrap()),
-            BREAK_EXPR => ExprKind::BreakExpr(BreakExpr::cast(self.syntax.clone()).unwrap()),
-            BLOCK_EXPR => ExprKind::BlockExpr(BlockExpr::cast(self.syntax.clone()).unwrap()),
-            RECORD_LIT => ExprKind::RecordLit(RecordLit::cast(self.syntax.clone()).unwrap()),
-            _ => unreachable!(),
-        }
+    pub fn expr(&self) -> Option<Expr> {
+        super::child_opt(self)
     }
 }
 
-impl Expr {}
-
-// ExprStmt
+// BreakExpr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct ExprStmt {
+pub struct BreakExpr {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for ExprStmt {
+impl AstNode for BreakExpr {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            EXPR_STMT => true,
+            BREAK_EXPR => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(ExprStmt { syntax })
+            Some(BreakExpr { syntax })
         } else {
             None
         }
@@ -402,29 +301,29 @@ impl AstNode for ExprStmt {
         &self.syntax
     }
 }
-impl ExprStmt {
+impl BreakExpr {
     pub fn expr(&self) -> Option<Expr> {
         super::child_opt(self)
     }
 }
 
-// FieldExpr
+// CallExpr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct FieldExpr {
+pub struct CallExpr {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for FieldExpr {
+impl AstNode for CallExpr {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            FIELD_EXPR => true,
+            CALL_EXPR => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(FieldExpr { syntax })
+            Some(CallExpr { syntax })
         } else {
             None
         }
@@ -433,33 +332,30 @@ impl AstNode for FieldExpr {
         &self.syntax
     }
 }
-impl FieldExpr {
+impl ast::ArgListOwner for CallExpr {}
+impl CallExpr {
     pub fn expr(&self) -> Option<Expr> {
         super::child_opt(self)
     }
-
-    pub fn name_ref(&self) -> Option<NameRef> {
-        super::child_opt(self)
-    }
 }
 
-// FunctionDef
+// CastExpr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct FunctionDef {
+pub struct CastExpr {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for FunctionDef {
+impl AstNode for CastExpr {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            FUNCTION_DEF => true,
+            CAST_EXPR => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(FunctionDef { syntax })
+            Some(CastExpr { syntax })
         } else {
             None
         }
@@ -468,41 +364,33 @@ impl AstNode for FunctionDef {
         &self.syntax
     }
 }
-impl ast::NameOwner for FunctionDef {}
-impl ast::VisibilityOwner for FunctionDef {}
-impl ast::DocCommentsOwner for FunctionDef {}
-impl ast::ExternOwner for FunctionDef {}
-impl FunctionDef {
-    pub fn param_list(&self) -> Option<ParamList> {
-        super::child_opt(self)
-    }
-
-    pub fn body(&self) -> Option<BlockExpr> {
+impl CastExpr {
+    pub fn expr(&self) -> Option<Expr> {
         super::child_opt(self)
     }
 
-    pub fn ret_type(&self) -> Option<RetType> {
+    pub fn type_ref(&self) -> Option<TypeRef> {
         super::child_opt(self)
     }
 }
 
-// IfExpr
+// ClosureExpr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct IfExpr {
+pub struct ClosureExpr {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for IfExpr {
+impl AstNode for ClosureExpr {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            IF_EXPR => true,
+            CLOSURE_EXPR => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(IfExpr { syntax })
+            Some(ClosureExpr { syntax })
         } else {
             None
         }
@@ -511,29 +399,37 @@ impl AstNode for IfExpr {
         &self.syntax
     }
 }
-impl IfExpr {
-    pub fn condition(&self) -> Option<Condition> {
+impl ClosureExpr {
+    pub fn param_list(&self) -> Option<ParamList> {
         super::child_opt(self)
     }
-}
 
-// LetStmt
+    pub fn ret_type(&self) -> Option<RetType> {
+        super::child_opt(self)
+    }
+
+    pub fn body(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
+// Condition
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct LetStmt {
+pub struct Condition {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for LetStmt {
+impl AstNode for Condition {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            LET_STMT => true,
+            CONDITION => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(LetStmt { syntax })
+            Some(Condition { syntax })
         } else {
             None
         }
@@ -542,34 +438,1738 @@ impl AstNode for LetStmt {
         &self.syntax
     }
 }
-impl ast::TypeAscriptionOwner for LetStmt {}
-impl LetStmt {
+impl Condition {
     pub fn pat(&self) -> Option<Pat> {
         super::child_opt(self)
     }
 
-    pub fn initializer(&self) -> Option<Expr> {
+    pub fn expr(&self) -> Option<Expr> {
         super::child_opt(self)
     }
 }
 
-// Literal
+// ConstDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Literal {
+pub struct ConstDef {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for Literal {
+impl AstNode for ConstDef {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            CONST_DEF => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ConstDef { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::NameOwner for ConstDef {}
+impl ast::VisibilityOwner for ConstDef {}
+impl ast::AttrsOwner for ConstDef {}
+impl ast::DocCommentsOwner for ConstDef {}
+impl ast::TypeAscriptionOwner for ConstDef {}
+impl ConstDef {
+    pub fn body(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
+// EnumDef
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnumDef {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for EnumDef {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            ENUM_DEF => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(EnumDef { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::NameOwner for EnumDef {}
+impl ast::VisibilityOwner for EnumDef {}
+impl ast::AttrsOwner for EnumDef {}
+impl ast::DocCommentsOwner for EnumDef {}
+impl EnumDef {
+    pub fn memory_type_specifier(&self) -> Option<MemoryTypeSpecifier> {
+        super::child_opt(self)
+    }
+
+    pub fn enum_variant_list(&self) -> Option<EnumVariantList> {
+        super::child_opt(self)
+    }
+}
+
+// EnumVariant
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnumVariant {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for EnumVariant {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            ENUM_VARIANT => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(EnumVariant { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::NameOwner for EnumVariant {}
+impl ast::DocCommentsOwner for EnumVariant {}
+impl EnumVariant {}
+
+// EnumVariantList
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnumVariantList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for EnumVariantList {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            ENUM_VARIANT_LIST => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(EnumVariantList { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl EnumVariantList {
+    pub fn variants(&self) -> impl Iterator<Item = EnumVariant> {
+        super::children(self)
+    }
+}
+
+// Expr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Expr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Expr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            LITERAL | PREFIX_EXPR | PATH_EXPR | BIN_EXPR | RANGE_EXPR | PAREN_EXPR | CALL_EXPR
+            | FIELD_EXPR | METHOD_CALL_EXPR | INDEX_EXPR | ARRAY_EXPR | TUPLE_EXPR | CAST_EXPR
+            | CLOSURE_EXPR | IF_EXPR | LOOP_EXPR | WHILE_EXPR | FOR_EXPR | MATCH_EXPR
+            | RETURN_EXPR | BREAK_EXPR | BLOCK_EXPR | RECORD_LIT => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Expr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprKind {
+    Literal(Literal),
+    PrefixExpr(PrefixExpr),
+    PathExpr(PathExpr),
+    BinExpr(BinExpr),
+    RangeExpr(RangeExpr),
+    ParenExpr(ParenExpr),
+    CallExpr(CallExpr),
+    FieldExpr(FieldExpr),
+    MethodCallExpr(MethodCallExpr),
+    IndexExpr(IndexExpr),
+    ArrayExpr(ArrayExpr),
+    TupleExpr(TupleExpr),
+    CastExpr(CastExpr),
+    ClosureExpr(ClosureExpr),
+    IfExpr(IfExpr),
+    LoopExpr(LoopExpr),
+    WhileExpr(WhileExpr),
+    ForExpr(ForExpr),
+    MatchExpr(MatchExpr),
+    ReturnExpr(ReturnExpr),
+    BreakExpr(BreakExpr),
+    BlockExpr(BlockExpr),
+    RecordLit(RecordLit),
+}
+impl From<Literal> for Expr {
+    fn from(n: Literal) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<PrefixExpr> for Expr {
+    fn from(n: PrefixExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<PathExpr> for Expr {
+    fn from(n: PathExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<BinExpr> for Expr {
+    fn from(n: BinExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<RangeExpr> for Expr {
+    fn from(n: RangeExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<ParenExpr> for Expr {
+    fn from(n: ParenExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<CallExpr> for Expr {
+    fn from(n: CallExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<FieldExpr> for Expr {
+    fn from(n: FieldExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<MethodCallExpr> for Expr {
+    fn from(n: MethodCallExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<IndexExpr> for Expr {
+    fn from(n: IndexExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<ArrayExpr> for Expr {
+    fn from(n: ArrayExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<TupleExpr> for Expr {
+    fn from(n: TupleExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<CastExpr> for Expr {
+    fn from(n: CastExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<ClosureExpr> for Expr {
+    fn from(n: ClosureExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<IfExpr> for Expr {
+    fn from(n: IfExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<LoopExpr> for Expr {
+    fn from(n: LoopExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<WhileExpr> for Expr {
+    fn from(n: WhileExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<ForExpr> for Expr {
+    fn from(n: ForExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<MatchExpr> for Expr {
+    fn from(n: MatchExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<ReturnExpr> for Expr {
+    fn from(n: ReturnExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<BreakExpr> for Expr {
+    fn from(n: BreakExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<BlockExpr> for Expr {
+    fn from(n: BlockExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<RecordLit> for Expr {
+    fn from(n: RecordLit) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+
+impl Expr {
+    pub fn kind(&self) -> ExprKind {
+        match self.syntax.kind() {
+            LITERAL => ExprKind::Literal(Literal::cast(self.syntax.clone()).unwrap()),
+            PREFIX_EXPR => ExprKind::PrefixExpr(PrefixExpr::cast(self.syntax.clone()).unwrap()),
+            PATH_EXPR => ExprKind::PathExpr(PathExpr::cast(self.syntax.clone()).unwrap()),
+            BIN_EXPR => ExprKind::BinExpr(BinExpr::cast(self.syntax.clone()).unwrap()),
+            RANGE_EXPR => ExprKind::RangeExpr(RangeExpr::cast(self.syntax.clone()).unwrap()),
+            PAREN_EXPR => ExprKind::ParenExpr(ParenExpr::cast(self.syntax.clone()).unwrap()),
+            CALL_EXPR => ExprKind::CallExpr(CallExpr::cast(self.syntax.clone()).unwrap()),
+            FIELD_EXPR => ExprKind::FieldExpr(FieldExpr::cast(self.syntax.clone()).unwrap()),
+            METHOD_CALL_EXPR => {
+                ExprKind::MethodCallExpr(MethodCallExpr::cast(self.syntax.clone()).unwrap())
+            }
+            INDEX_EXPR => ExprKind::IndexExpr(IndexExpr::cast(self.syntax.clone()).unwrap()),
+            ARRAY_EXPR => ExprKind::ArrayExpr(ArrayExpr::cast(self.syntax.clone()).unwrap()),
+            TUPLE_EXPR => ExprKind::TupleExpr(TupleExpr::cast(self.syntax.clone()).unwrap()),
+            CAST_EXPR => ExprKind::CastExpr(CastExpr::cast(self.syntax.clone()).unwrap()),
+            CLOSURE_EXPR => ExprKind::ClosureExpr(ClosureExpr::cast(self.syntax.clone()).unwrap()),
+            IF_EXPR => ExprKind::IfExpr(IfExpr::cast(self.syntax.clone()).unwrap()),
+            LOOP_EXPR => ExprKind::LoopExpr(LoopExpr::cast(self.syntax.clone()).unwrap()),
+            WHILE_EXPR => ExprKind::WhileExpr(WhileExpr::cast(self.syntax.clone()).unwrap()),
+            FOR_EXPR => ExprKind::ForExpr(ForExpr::cast(self.syntax.clone()).unwrap()),
+            MATCH_EXPR => ExprKind::MatchExpr(MatchExpr::cast(self.syntax.clone()).unwrap()),
+            RETURN_EXPR => ExprKind::ReturnExpr(ReturnExpr::cast(self.syntax.clone()).unwrap()),
+            BREAK_EXPR => ExprKind::BreakExpr(BreakExpr::cast(self.syntax.clone()).unwrap()),
+            BLOCK_EXPR => ExprKind::BlockExpr(BlockExpr::cast(self.syntax.clone()).unwrap()),
+            RECORD_LIT => ExprKind::RecordLit(RecordLit::cast(self.syntax.clone()).unwrap()),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Expr {}
+
+// ExprStmt
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExprStmt {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ExprStmt {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            EXPR_STMT => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ExprStmt { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ExprStmt {
+    pub fn expr(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
+// FieldExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FieldExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for FieldExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            FIELD_EXPR => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(FieldExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl FieldExpr {
+    pub fn expr(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+
+    pub fn name_ref(&self) -> Option<NameRef> {
+        super::child_opt(self)
+    }
+}
+
+// FnPointerType
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FnPointerType {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for FnPointerType {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            FN_POINTER_TYPE => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(FnPointerType { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl FnPointerType {
+    pub fn param_list(&self) -> Option<ParamList> {
+        super::child_opt(self)
+    }
+
+    pub fn ret_type(&self) -> Option<RetType> {
+        super::child_opt(self)
+    }
+}
+
+// ForExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ForExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ForExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            FOR_EXPR => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ForExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::LoopBodyOwner for ForExpr {}
+impl ForExpr {
+    pub fn pat(&self) -> Option<Pat> {
+        super::child_opt(self)
+    }
+
+    pub fn iterable(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
+// FunctionDef
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FunctionDef {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for FunctionDef {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            FUNCTION_DEF => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(FunctionDef { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::NameOwner for FunctionDef {}
+impl ast::VisibilityOwner for FunctionDef {}
+impl ast::AttrsOwner for FunctionDef {}
+impl ast::DocCommentsOwner for FunctionDef {}
+impl ast::ExternOwner for FunctionDef {}
+impl ast::TypeParamsOwner for FunctionDef {}
+impl FunctionDef {
+    pub fn param_list(&self) -> Option<ParamList> {
+        super::child_opt(self)
+    }
+
+    pub fn body(&self) -> Option<BlockExpr> {
+        super::child_opt(self)
+    }
+
+    pub fn ret_type(&self) -> Option<RetType> {
+        super::child_opt(self)
+    }
+}
+
+// IfExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IfExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for IfExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            IF_EXPR => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(IfExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl IfExpr {
+    pub fn condition(&self) -> Option<Condition> {
+        super::child_opt(self)
+    }
+}
+
+// ImplBlock
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ImplBlock {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ImplBlock {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            IMPL_BLOCK => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ImplBlock { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::AttrsOwner for ImplBlock {}
+impl ast::DocCommentsOwner for ImplBlock {}
+impl ImplBlock {
+    pub fn item_list(&self) -> Option<ItemList> {
+        super::child_opt(self)
+    }
+}
+
+// IndexExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IndexExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for IndexExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            INDEX_EXPR => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(IndexExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl IndexExpr {}
+
+// ItemList
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ItemList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ItemList {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            ITEM_LIST => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ItemList { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::FunctionDefOwner for ItemList {}
+impl ItemList {}
+
+// LetStmt
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LetStmt {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for LetStmt {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            LET_STMT => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(LetStmt { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::TypeAscriptionOwner for LetStmt {}
+impl LetStmt {
+    pub fn pat(&self) -> Option<Pat> {
+        super::child_opt(self)
+    }
+
+    pub fn initializer(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
+// Literal
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Literal {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Literal {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            LITERAL => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Literal { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl Literal {}
+
+// LoopExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LoopExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for LoopExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            LOOP_EXPR => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(LoopExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::LoopBodyOwner for LoopExpr {}
+impl LoopExpr {}
+
+// MatchArm
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MatchArm {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for MatchArm {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            MATCH_ARM => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(MatchArm { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl MatchArm {
+    pub fn pat(&self) -> Option<Pat> {
+        super::child_opt(self)
+    }
+
+    pub fn expr(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
+// MatchArmList
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MatchArmList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for MatchArmList {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            MATCH_ARM_LIST => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(MatchArmList { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl MatchArmList {
+    pub fn arms(&self) -> impl Iterator<Item = MatchArm> {
+        super::children(self)
+    }
+}
+
+// MatchExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MatchExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for MatchExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            MATCH_EXPR => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(MatchExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl MatchExpr {
+    pub fn expr(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+
+    pub fn match_arm_list(&self) -> Option<MatchArmList> {
+        super::child_opt(self)
+    }
+}
+
+// MemoryTypeSpecifier
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MemoryTypeSpecifier {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for MemoryTypeSpecifier {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            MEMORY_TYPE_SPECIFIER => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(MemoryTypeSpecifier { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl MemoryTypeSpecifier {}
+
+// MethodCallExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MethodCallExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for MethodCallExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            METHOD_CALL_EXPR => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(MethodCallExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::ArgListOwner for MethodCallExpr {}
+impl MethodCallExpr {
+    pub fn expr(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+
+    pub fn name_ref(&self) -> Option<NameRef> {
+        super::child_opt(self)
+    }
+}
+
+// Module
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Module {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Module {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            MODULE => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Module { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::NameOwner for Module {}
+impl ast::VisibilityOwner for Module {}
+impl ast::DocCommentsOwner for Module {}
+impl Module {}
+
+// ModuleItem
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ModuleItem {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ModuleItem {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            FUNCTION_DEF | STRUCT_DEF | ENUM_DEF | IMPL_BLOCK | TRAIT_DEF | CONST_DEF
+            | STATIC_DEF | MODULE | USE => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ModuleItem { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModuleItemKind {
+    FunctionDef(FunctionDef),
+    StructDef(StructDef),
+    EnumDef(EnumDef),
+    ImplBlock(ImplBlock),
+    TraitDef(TraitDef),
+    ConstDef(ConstDef),
+    StaticDef(StaticDef),
+    Module(Module),
+    Use(Use),
+}
+impl From<FunctionDef> for ModuleItem {
+    fn from(n: FunctionDef) -> ModuleItem {
+        ModuleItem { syntax: n.syntax }
+    }
+}
+impl From<StructDef> for ModuleItem {
+    fn from(n: StructDef) -> ModuleItem {
+        ModuleItem { syntax: n.syntax }
+    }
+}
+impl From<EnumDef> for ModuleItem {
+    fn from(n: EnumDef) -> ModuleItem {
+        ModuleItem { syntax: n.syntax }
+    }
+}
+impl From<ImplBlock> for ModuleItem {
+    fn from(n: ImplBlock) -> ModuleItem {
+        ModuleItem { syntax: n.syntax }
+    }
+}
+impl From<TraitDef> for ModuleItem {
+    fn from(n: TraitDef) -> ModuleItem {
+        ModuleItem { syntax: n.syntax }
+    }
+}
+impl From<ConstDef> for ModuleItem {
+    fn from(n: ConstDef) -> ModuleItem {
+        ModuleItem { syntax: n.syntax }
+    }
+}
+impl From<StaticDef> for ModuleItem {
+    fn from(n: StaticDef) -> ModuleItem {
+        ModuleItem { syntax: n.syntax }
+    }
+}
+impl From<Module> for ModuleItem {
+    fn from(n: Module) -> ModuleItem {
+        ModuleItem { syntax: n.syntax }
+    }
+}
+impl From<Use> for ModuleItem {
+    fn from(n: Use) -> ModuleItem {
+        ModuleItem { syntax: n.syntax }
+    }
+}
+
+impl ModuleItem {
+    pub fn kind(&self) -> ModuleItemKind {
+        match self.syntax.kind() {
+            FUNCTION_DEF => {
+                ModuleItemKind::FunctionDef(FunctionDef::cast(self.syntax.clone()).unwrap())
+            }
+            STRUCT_DEF => ModuleItemKind::StructDef(StructDef::cast(self.syntax.clone()).unwrap()),
+            ENUM_DEF => ModuleItemKind::EnumDef(EnumDef::cast(self.syntax.clone()).unwrap()),
+            IMPL_BLOCK => ModuleItemKind::ImplBlock(ImplBlock::cast(self.syntax.clone()).unwrap()),
+            TRAIT_DEF => ModuleItemKind::TraitDef(TraitDef::cast(self.syntax.clone()).unwrap()),
+            CONST_DEF => ModuleItemKind::ConstDef(ConstDef::cast(self.syntax.clone()).unwrap()),
+            STATIC_DEF => ModuleItemKind::StaticDef(StaticDef::cast(self.syntax.clone()).unwrap()),
+            MODULE => ModuleItemKind::Module(Module::cast(self.syntax.clone()).unwrap()),
+            USE => ModuleItemKind::Use(Use::cast(self.syntax.clone()).unwrap()),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl ModuleItem {}
+
+// Name
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Name {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Name {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            NAME => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Name { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl Name {}
+
+// NameRef
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NameRef {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for NameRef {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            NAME_REF => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(NameRef { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl NameRef {}
+
+// NeverType
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NeverType {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for NeverType {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            NEVER_TYPE => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(NeverType { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl NeverType {}
+
+// OptionType
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OptionType {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for OptionType {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            OPTION_TYPE => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(OptionType { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl OptionType {
+    pub fn type_ref(&self) -> Option<TypeRef> {
+        super::child_opt(self)
+    }
+}
+
+// Param
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Param {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Param {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            PARAM => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Param { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::TypeAscriptionOwner for Param {}
+impl Param {
+    pub fn pat(&self) -> Option<Pat> {
+        super::child_opt(self)
+    }
+}
+
+// ParamList
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParamList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ParamList {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            PARAM_LIST => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ParamList { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ParamList {
+    pub fn params(&self) -> impl Iterator<Item = Param> {
+        super::children(self)
+    }
+
+    pub fn self_param(&self) -> Option<SelfParam> {
+        super::child_opt(self)
+    }
+}
+
+// ParenExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParenExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ParenExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            PAREN_EXPR => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ParenExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ParenExpr {
+    pub fn expr(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
+// ParenType
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParenType {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ParenType {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            PAREN_TYPE => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ParenType { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ParenType {
+    pub fn type_ref(&self) -> Option<TypeRef> {
+        super::child_opt(self)
+    }
+}
+
+// Pat
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pat {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Pat {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            BIND_PAT | PLACEHOLDER_PAT | PATH_PAT | TUPLE_STRUCT_PAT | TUPLE_PAT | RECORD_PAT => {
+                true
+            }
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Pat { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatKind {
+    BindPat(BindPat),
+    PlaceholderPat(PlaceholderPat),
+    PathPat(PathPat),
+    TupleStructPat(TupleStructPat),
+    TuplePat(TuplePat),
+    RecordPat(RecordPat),
+}
+impl From<BindPat> for Pat {
+    fn from(n: BindPat) -> Pat {
+        Pat { syntax: n.syntax }
+    }
+}
+impl From<PlaceholderPat> for Pat {
+    fn from(n: PlaceholderPat) -> Pat {
+        Pat { syntax: n.syntax }
+    }
+}
+impl From<PathPat> for Pat {
+    fn from(n: PathPat) -> Pat {
+        Pat { syntax: n.syntax }
+    }
+}
+impl From<TupleStructPat> for Pat {
+    fn from(n: TupleStructPat) -> Pat {
+        Pat { syntax: n.syntax }
+    }
+}
+impl From<TuplePat> for Pat {
+    fn from(n: TuplePat) -> Pat {
+        Pat { syntax: n.syntax }
+    }
+}
+impl From<RecordPat> for Pat {
+    fn from(n: RecordPat) -> Pat {
+        Pat { syntax: n.syntax }
+    }
+}
+
+impl Pat {
+    pub fn kind(&self) -> PatKind {
+        match self.syntax.kind() {
+            BIND_PAT => PatKind::BindPat(BindPat::cast(self.syntax.clone()).unwrap()),
+            PLACEHOLDER_PAT => {
+                PatKind::PlaceholderPat(PlaceholderPat::cast(self.syntax.clone()).unwrap())
+            }
+            PATH_PAT => PatKind::PathPat(PathPat::cast(self.syntax.clone()).unwrap()),
+            TUPLE_STRUCT_PAT => {
+                PatKind::TupleStructPat(TupleStructPat::cast(self.syntax.clone()).unwrap())
+            }
+            TUPLE_PAT => PatKind::TuplePat(TuplePat::cast(self.syntax.clone()).unwrap()),
+            RECORD_PAT => PatKind::RecordPat(RecordPat::cast(self.syntax.clone()).unwrap()),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Pat {}
+
+// Path
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Path {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Path {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            PATH => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Path { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl Path {
+    pub fn segment(&self) -> Option<PathSegment> {
+        super::child_opt(self)
+    }
+
+    pub fn qualifier(&self) -> Option<Path> {
+        super::child_opt(self)
+    }
+}
+
+// PathExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PathExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for PathExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            PATH_EXPR => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(PathExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl PathExpr {
+    pub fn path(&self) -> Option<Path> {
+        super::child_opt(self)
+    }
+}
+
+// PathPat
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PathPat {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for PathPat {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            PATH_PAT => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(PathPat { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl PathPat {
+    pub fn path(&self) -> Option<Path> {
+        super::child_opt(self)
+    }
+}
+
+// PathSegment
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PathSegment {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for PathSegment {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            PATH_SEGMENT => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(PathSegment { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl PathSegment {
+    pub fn name_ref(&self) -> Option<NameRef> {
+        super::child_opt(self)
+    }
+
+    pub fn type_arg_list(&self) -> Option<TypeArgList> {
+        super::child_opt(self)
+    }
+}
+
+// PathType
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PathType {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for PathType {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            PATH_TYPE => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(PathType { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl PathType {
+    pub fn path(&self) -> Option<Path> {
+        super::child_opt(self)
+    }
+}
+
+// PlaceholderPat
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlaceholderPat {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for PlaceholderPat {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            PLACEHOLDER_PAT => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(PlaceholderPat { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl PlaceholderPat {}
+
+// PrefixExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PrefixExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for PrefixExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            PREFIX_EXPR => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(PrefixExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl PrefixExpr {
+    pub fn expr(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
+// RangeExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RangeExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for RangeExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            RANGE_EXPR => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(RangeExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl RangeExpr {}
+
+// RecordField
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordField {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for RecordField {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            RECORD_FIELD => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(RecordField { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl RecordField {
+    pub fn name_ref(&self) -> Option<NameRef> {
+        super::child_opt(self)
+    }
+
+    pub fn expr(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
+// RecordFieldDef
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordFieldDef {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for RecordFieldDef {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            RECORD_FIELD_DEF => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(RecordFieldDef { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::NameOwner for RecordFieldDef {}
+impl ast::VisibilityOwner for RecordFieldDef {}
+impl ast::AttrsOwner for RecordFieldDef {}
+impl ast::DocCommentsOwner for RecordFieldDef {}
+impl ast::TypeAscriptionOwner for RecordFieldDef {}
+impl RecordFieldDef {}
+
+// RecordFieldDefList
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordFieldDefList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for RecordFieldDefList {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        match kind {
+            RECORD_FIELD_DEF_LIST => true,
+            _ => false,
+        }
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(RecordFieldDefList { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl RecordFieldDefList {
+    pub fn fields(&self) -> impl Iterator<Item = RecordFieldDef> {
+        super::children(self)
+    }
+}
+
+// RecordFieldList
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecordFieldList {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for RecordFieldList {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            LITERAL => true,
+            RECORD_FIELD_LIST => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(Literal { syntax })
+            Some(RecordFieldList { syntax })
         } else {
             None
         }
@@ -578,25 +2178,33 @@ impl AstNode for Literal {
         &self.syntax
     }
 }
-impl Literal {}
+impl RecordFieldList {
+    pub fn fields(&self) -> impl Iterator<Item = RecordField> {
+        super::children(self)
+    }
 
-// LoopExpr
+    pub fn spread(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
+// RecordFieldPat
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct LoopExpr {
+pub struct RecordFieldPat {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for LoopExpr {
+impl AstNode for RecordFieldPat {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            LOOP_EXPR => true,
+            RECORD_FIELD_PAT => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(LoopExpr { syntax })
+            Some(RecordFieldPat { syntax })
         } else {
             None
         }
@@ -605,26 +2213,33 @@ impl AstNode for LoopExpr {
         &self.syntax
     }
 }
-impl ast::LoopBodyOwner for LoopExpr {}
-impl LoopExpr {}
+impl RecordFieldPat {
+    pub fn name_ref(&self) -> Option<NameRef> {
+        super::child_opt(self)
+    }
 
-// MemoryTypeSpecifier
+    pub fn pat(&self) -> Option<Pat> {
+        super::child_opt(self)
+    }
+}
+
+// RecordFieldPatList
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct MemoryTypeSpecifier {
+pub struct RecordFieldPatList {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for MemoryTypeSpecifier {
+impl AstNode for RecordFieldPatList {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            MEMORY_TYPE_SPECIFIER => true,
+            RECORD_FIELD_PAT_LIST => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(MemoryTypeSpecifier { syntax })
+            Some(RecordFieldPatList { syntax })
         } else {
             None
         }
@@ -633,25 +2248,29 @@ impl AstNode for MemoryTypeSpecifier {
         &self.syntax
     }
 }
-impl MemoryTypeSpecifier {}
+impl RecordFieldPatList {
+    pub fn fields(&self) -> impl Iterator<Item = RecordFieldPat> {
+        super::children(self)
+    }
+}
 
-// ModuleItem
+// RecordLit
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct ModuleItem {
+pub struct RecordLit {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for ModuleItem {
+impl AstNode for RecordLit {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            FUNCTION_DEF | STRUCT_DEF => true,
+            RECORD_LIT => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(ModuleItem { syntax })
+            Some(RecordLit { syntax })
         } else {
             None
         }
@@ -660,53 +2279,33 @@ impl AstNode for ModuleItem {
         &self.syntax
     }
 }
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum ModuleItemKind {
-    FunctionDef(FunctionDef),
-    StructDef(StructDef),
-}
-impl From<FunctionDef> for ModuleItem {
-    fn from(n: FunctionDef) -> ModuleItem {
-        ModuleItem { syntax: n.syntax }
-    }
-}
-impl From<StructDef> for ModuleItem {
-    fn from(n: StructDef) -> ModuleItem {
-        ModuleItem { syntax: n.syntax }
+impl RecordLit {
+    pub fn type_ref(&self) -> Option<TypeRef> {
+        super::child_opt(self)
     }
-}
 
-impl ModuleItem {
-    pub fn kind(&self) -> ModuleItemKind {
-        match self.syntax.kind() {
-            FUNCTION_DEF => {
-                ModuleItemKind::FunctionDef(FunctionDef::cast(self.syntax.clone()).unwrap())
-            }
-            STRUCT_DEF => ModuleItemKind::StructDef(StructDef::cast(self.syntax.clone()).unwrap()),
-            _ => unreachable!(),
-        }
+    pub fn record_field_list(&self) -> Option<RecordFieldList> {
+        super::child_opt(self)
     }
 }
 
-impl ModuleItem {}
-
-// Name
+// RecordPat
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Name {
+pub struct RecordPat {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for Name {
+impl AstNode for RecordPat {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            NAME => true,
+            RECORD_PAT => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(Name { syntax })
+            Some(RecordPat { syntax })
         } else {
             None
         }
@@ -715,25 +2314,33 @@ impl AstNode for Name {
         &self.syntax
     }
 }
-impl Name {}
+impl RecordPat {
+    pub fn path(&self) -> Option<Path> {
+        super::child_opt(self)
+    }
 
-// NameRef
+    pub fn record_field_pat_list(&self) -> Option<RecordFieldPatList> {
+        super::child_opt(self)
+    }
+}
+
+// Rename
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct NameRef {
+pub struct Rename {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for NameRef {
+impl AstNode for Rename {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            NAME_REF => true,
+            RENAME => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(NameRef { syntax })
+            Some(Rename { syntax })
         } else {
             None
         }
@@ -742,25 +2349,26 @@ impl AstNode for NameRef {
         &self.syntax
     }
 }
-impl NameRef {}
+impl ast::NameOwner for Rename {}
+impl Rename {}
 
-// NeverType
+// RetType
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct NeverType {
+pub struct RetType {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for NeverType {
+impl AstNode for RetType {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            NEVER_TYPE => true,
+            RET_TYPE => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(NeverType { syntax })
+            Some(RetType { syntax })
         } else {
             None
         }
@@ -769,25 +2377,29 @@ impl AstNode for NeverType {
         &self.syntax
     }
 }
-impl NeverType {}
+impl RetType {
+    pub fn type_ref(&self) -> Option<TypeRef> {
+        super::child_opt(self)
+    }
+}
 
-// Param
+// ReturnExpr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Param {
+pub struct ReturnExpr {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for Param {
+impl AstNode for ReturnExpr {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            PARAM => true,
+            RETURN_EXPR => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(Param { syntax })
+            Some(ReturnExpr { syntax })
         } else {
             None
         }
@@ -796,30 +2408,29 @@ impl AstNode for Param {
         &self.syntax
     }
 }
-impl ast::TypeAscriptionOwner for Param {}
-impl Param {
-    pub fn pat(&self) -> Option<Pat> {
+impl ReturnExpr {
+    pub fn expr(&self) -> Option<Expr> {
         super::child_opt(self)
     }
 }
 
-// ParamList
+// SelfParam
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct ParamList {
+pub struct SelfParam {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for ParamList {
+impl AstNode for SelfParam {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            PARAM_LIST => true,
+            SELF_PARAM => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(ParamList { syntax })
+            Some(SelfParam { syntax })
         } else {
             None
         }
@@ -828,29 +2439,25 @@ impl AstNode for ParamList {
         &self.syntax
     }
 }
-impl ParamList {
-    pub fn params(&self) -> impl Iterator<Item = Param> {
-        super::children(self)
-    }
-}
+impl SelfParam {}
 
-// ParenExpr
+// SourceFile
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct ParenExpr {
+pub struct SourceFile {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for ParenExpr {
+impl AstNode for SourceFile {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            PAREN_EXPR => true,
+            SOURCE_FILE => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(ParenExpr { syntax })
+            Some(SourceFile { syntax })
         } else {
             None
         }
@@ -859,29 +2466,27 @@ impl AstNode for ParenExpr {
         &self.syntax
     }
 }
-impl ParenExpr {
-    pub fn expr(&self) -> Option<Expr> {
-        super::child_opt(self)
-    }
-}
+impl ast::ModuleItemOwner for SourceFile {}
+impl ast::FunctionDefOwner for SourceFile {}
+impl SourceFile {}
 
-// Pat
+// Stmt
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Pat {
+pub struct Stmt {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for Pat {
+impl AstNode for Stmt {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            BIND_PAT | PLACEHOLDER_PAT => true,
+            LET_STMT | EXPR_STMT => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(Pat { syntax })
+            Some(Stmt { syntax })
         } else {
             None
         }
@@ -891,87 +2496,50 @@ impl AstNode for Pat {
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum PatKind {
-    BindPat(BindPat),
-    PlaceholderPat(PlaceholderPat),
+pub enum StmtKind {
+    LetStmt(LetStmt),
+    ExprStmt(ExprStmt),
 }
-impl From<BindPat> for Pat {
-    fn from(n: BindPat) -> Pat {
-        Pat { syntax: n.syntax }
+impl From<LetStmt> for Stmt {
+    fn from(n: LetStmt) -> Stmt {
+        Stmt { syntax: n.syntax }
     }
 }
-impl From<PlaceholderPat> for Pat {
-    fn from(n: PlaceholderPat) -> Pat {
-        Pat { syntax: n.syntax }
+impl From<ExprStmt> for Stmt {
+    fn from(n: ExprStmt) -> Stmt {
+        Stmt { syntax: n.syntax }
     }
 }
 
-impl Pat {
-    pub fn kind(&self) -> PatKind {
+impl Stmt {
+    pub fn kind(&self) -> StmtKind {
         match self.syntax.kind() {
-            BIND_PAT => PatKind::BindPat(BindPat::cast(self.syntax.clone()).unwrap()),
-            PLACEHOLDER_PAT => {
-                PatKind::PlaceholderPat(PlaceholderPat::cast(self.syntax.clone()).unwrap())
-            }
+            LET_STMT => StmtKind::LetStmt(LetStmt::cast(self.syntax.clone()).unwrap()),
+            EXPR_STMT => StmtKind::ExprStmt(ExprStmt::cast(self.syntax.clone()).unwrap()),
             _ => unreachable!(),
         }
     }
 }
 
-impl Pat {}
-
-// Path
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Path {
-    pub(crate) syntax: SyntaxNode,
-}
-
-impl AstNode for Path {
-    fn can_cast(kind: SyntaxKind) -> bool {
-        match kind {
-            PATH => true,
-            _ => false,
-        }
-    }
-    fn cast(syntax: SyntaxNode) -> Option<Self> {
-        if Self::can_cast(syntax.kind()) {
-            Some(Path { syntax })
-        } else {
-            None
-        }
-    }
-    fn syntax(&self) -> &SyntaxNode {
-        &self.syntax
-    }
-}
-impl Path {
-    pub fn segment(&self) -> Option<PathSegment> {
-        super::child_opt(self)
-    }
-
-    pub fn qualifier(&self) -> Option<Path> {
-        super::child_opt(self)
-    }
-}
+impl Stmt {}
 
-// PathExpr
+// StaticDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct PathExpr {
+pub struct StaticDef {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for PathExpr {
+impl AstNode for StaticDef {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            PATH_EXPR => true,
+            STATIC_DEF => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(PathExpr { syntax })
+            Some(StaticDef { syntax })
         } else {
             None
         }
@@ -980,29 +2548,34 @@ impl AstNode for PathExpr {
         &self.syntax
     }
 }
-impl PathExpr {
-    pub fn path(&self) -> Option<Path> {
+impl ast::NameOwner for StaticDef {}
+impl ast::VisibilityOwner for StaticDef {}
+impl ast::AttrsOwner for StaticDef {}
+impl ast::DocCommentsOwner for StaticDef {}
+impl ast::TypeAscriptionOwner for StaticDef {}
+impl StaticDef {
+    pub fn body(&self) -> Option<Expr> {
         super::child_opt(self)
     }
 }
 
-// PathSegment
+// StructDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct PathSegment {
+pub struct StructDef {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for PathSegment {
+impl AstNode for StructDef {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            PATH_SEGMENT => true,
+            STRUCT_DEF => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(PathSegment { syntax })
+            Some(StructDef { syntax })
         } else {
             None
         }
@@ -1011,29 +2584,34 @@ impl AstNode for PathSegment {
         &self.syntax
     }
 }
-impl PathSegment {
-    pub fn name_ref(&self) -> Option<NameRef> {
+impl ast::NameOwner for StructDef {}
+impl ast::VisibilityOwner for StructDef {}
+impl ast::AttrsOwner for StructDef {}
+impl ast::DocCommentsOwner for StructDef {}
+impl ast::TypeParamsOwner for StructDef {}
+impl StructDef {
+    pub fn memory_type_specifier(&self) -> Option<MemoryTypeSpecifier> {
         super::child_opt(self)
     }
 }
 
-// PathType
+// TraitDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct PathType {
+pub struct TraitDef {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for PathType {
+impl AstNode for TraitDef {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            PATH_TYPE => true,
+            TRAIT_DEF => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(PathType { syntax })
+            Some(TraitDef { syntax })
         } else {
             None
         }
@@ -1042,29 +2620,32 @@ impl AstNode for PathType {
         &self.syntax
     }
 }
-impl PathType {
-    pub fn path(&self) -> Option<Path> {
+impl ast::NameOwner for TraitDef {}
+impl ast::VisibilityOwner for TraitDef {}
+impl ast::DocCommentsOwner for TraitDef {}
+impl TraitDef {
+    pub fn item_list(&self) -> Option<ItemList> {
         super::child_opt(self)
     }
 }
 
-// PlaceholderPat
+// TupleExpr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct PlaceholderPat {
+pub struct TupleExpr {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for PlaceholderPat {
+impl AstNode for TupleExpr {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            PLACEHOLDER_PAT => true,
+            TUPLE_EXPR => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(PlaceholderPat { syntax })
+            Some(TupleExpr { syntax })
         } else {
             None
         }
@@ -1073,25 +2654,29 @@ impl AstNode for PlaceholderPat {
         &self.syntax
     }
 }
-impl PlaceholderPat {}
+impl TupleExpr {
+    pub fn exprs(&self) -> impl Iterator<Item = Expr> {
+        super::children(self)
+    }
+}
 
-// PrefixExpr
+// TupleFieldDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct PrefixExpr {
+pub struct TupleFieldDef {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for PrefixExpr {
+impl AstNode for TupleFieldDef {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            PREFIX_EXPR => true,
+            TUPLE_FIELD_DEF => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(PrefixExpr { syntax })
+            Some(TupleFieldDef { syntax })
         } else {
             None
         }
@@ -1100,29 +2685,30 @@ impl AstNode for PrefixExpr {
         &self.syntax
     }
 }
-impl PrefixExpr {
-    pub fn expr(&self) -> Option<Expr> {
+impl ast::VisibilityOwner for TupleFieldDef {}
+impl TupleFieldDef {
+    pub fn type_ref(&self) -> Option<TypeRef> {
         super::child_opt(self)
     }
 }
 
-// RecordField
+// TupleFieldDefList
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct RecordField {
+pub struct TupleFieldDefList {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for RecordField {
+impl AstNode for TupleFieldDefList {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            RECORD_FIELD => true,
+            TUPLE_FIELD_DEF_LIST => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(RecordField { syntax })
+            Some(TupleFieldDefList { syntax })
         } else {
             None
         }
@@ -1131,33 +2717,29 @@ impl AstNode for RecordField {
         &self.syntax
     }
 }
-impl RecordField {
-    pub fn name_ref(&self) -> Option<NameRef> {
-        super::child_opt(self)
-    }
-
-    pub fn expr(&self) -> Option<Expr> {
-        super::child_opt(self)
+impl TupleFieldDefList {
+    pub fn fields(&self) -> impl Iterator<Item = TupleFieldDef> {
+        super::children(self)
     }
 }
 
-// RecordFieldDef
+// TuplePat
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct RecordFieldDef {
+pub struct TuplePat {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for RecordFieldDef {
+impl AstNode for TuplePat {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            RECORD_FIELD_DEF => true,
+            TUPLE_PAT => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(RecordFieldDef { syntax })
+            Some(TuplePat { syntax })
         } else {
             None
         }
@@ -1166,29 +2748,29 @@ impl AstNode for RecordFieldDef {
         &self.syntax
     }
 }
-impl ast::NameOwner for RecordFieldDef {}
-impl ast::VisibilityOwner for RecordFieldDef {}
-impl ast::DocCommentsOwner for RecordFieldDef {}
-impl ast::TypeAscriptionOwner for RecordFieldDef {}
-impl RecordFieldDef {}
+impl TuplePat {
+    pub fn args(&self) -> impl Iterator<Item = Pat> {
+        super::children(self)
+    }
+}
 
-// RecordFieldDefList
+// TupleStructPat
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct RecordFieldDefList {
+pub struct TupleStructPat {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for RecordFieldDefList {
+impl AstNode for TupleStructPat {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            RECORD_FIELD_DEF_LIST => true,
+            TUPLE_STRUCT_PAT => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(RecordFieldDefList { syntax })
+            Some(TupleStructPat { syntax })
         } else {
             None
         }
@@ -1197,29 +2779,33 @@ impl AstNode for RecordFieldDefList {
         &self.syntax
     }
 }
-impl RecordFieldDefList {
-    pub fn fields(&self) -> impl Iterator<Item = RecordFieldDef> {
+impl TupleStructPat {
+    pub fn args(&self) -> impl Iterator<Item = Pat> {
         super::children(self)
     }
+
+    pub fn path(&self) -> Option<Path> {
+        super::child_opt(self)
+    }
 }
 
-// RecordFieldList
+// TupleType
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct RecordFieldList {
+pub struct TupleType {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for RecordFieldList {
+impl AstNode for TupleType {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            RECORD_FIELD_LIST => true,
+            TUPLE_TYPE => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(RecordFieldList { syntax })
+            Some(TupleType { syntax })
         } else {
             None
         }
@@ -1228,33 +2814,29 @@ impl AstNode for RecordFieldList {
         &self.syntax
     }
 }
-impl RecordFieldList {
-    pub fn fields(&self) -> impl Iterator<Item = RecordField> {
+impl TupleType {
+    pub fn fields(&self) -> impl Iterator<Item = TypeRef> {
         super::children(self)
     }
-
-    pub fn spread(&self) -> Option<Expr> {
-        super::child_opt(self)
-    }
 }
 
-// RecordLit
+// TypeArg
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct RecordLit {
+pub struct TypeArg {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for RecordLit {
+impl AstNode for TypeArg {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            RECORD_LIT => true,
+            TYPE_ARG => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(RecordLit { syntax })
+            Some(TypeArg { syntax })
         } else {
             None
         }
@@ -1263,33 +2845,29 @@ impl AstNode for RecordLit {
         &self.syntax
     }
 }
-impl RecordLit {
+impl TypeArg {
     pub fn type_ref(&self) -> Option<TypeRef> {
         super::child_opt(self)
     }
-
-    pub fn record_field_list(&self) -> Option<RecordFieldList> {
-        super::child_opt(self)
-    }
 }
 
-// RetType
+// TypeArgList
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct RetType {
+pub struct TypeArgList {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for RetType {
+impl AstNode for TypeArgList {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            RET_TYPE => true,
+            TYPE_ARG_LIST => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(RetType { syntax })
+            Some(TypeArgList { syntax })
         } else {
             None
         }
@@ -1298,29 +2876,29 @@ impl AstNode for RetType {
         &self.syntax
     }
 }
-impl RetType {
-    pub fn type_ref(&self) -> Option<TypeRef> {
-        super::child_opt(self)
+impl TypeArgList {
+    pub fn type_args(&self) -> impl Iterator<Item = TypeArg> {
+        super::children(self)
     }
 }
 
-// ReturnExpr
+// TypeParam
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct ReturnExpr {
+pub struct TypeParam {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for ReturnExpr {
+impl AstNode for TypeParam {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            RETURN_EXPR => true,
+            TYPE_PARAM => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(ReturnExpr { syntax })
+            Some(TypeParam { syntax })
         } else {
             None
         }
@@ -1329,29 +2907,26 @@ impl AstNode for ReturnExpr {
         &self.syntax
     }
 }
-impl ReturnExpr {
-    pub fn expr(&self) -> Option<Expr> {
-        super::child_opt(self)
-    }
-}
+impl ast::NameOwner for TypeParam {}
+impl TypeParam {}
 
-// SourceFile
+// TypeParamList
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct SourceFile {
+pub struct TypeParamList {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for SourceFile {
+impl AstNode for TypeParamList {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            SOURCE_FILE => true,
+            TYPE_PARAM_LIST => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(SourceFile { syntax })
+            Some(TypeParamList { syntax })
         } else {
             None
         }
@@ -1360,27 +2935,30 @@ impl AstNode for SourceFile {
         &self.syntax
     }
 }
-impl ast::ModuleItemOwner for SourceFile {}
-impl ast::FunctionDefOwner for SourceFile {}
-impl SourceFile {}
+impl TypeParamList {
+    pub fn type_params(&self) -> impl Iterator<Item = TypeParam> {
+        super::children(self)
+    }
+}
 
-// Stmt
+// TypeRef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Stmt {
+pub struct TypeRef {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for Stmt {
+impl AstNode for TypeRef {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            LET_STMT | EXPR_STMT => true,
+            PATH_TYPE | NEVER_TYPE | ARRAY_TYPE | PAREN_TYPE | TUPLE_TYPE | FN_POINTER_TYPE
+            | OPTION_TYPE => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(Stmt { syntax })
+            Some(TypeRef { syntax })
         } else {
             None
         }
@@ -1390,50 +2968,87 @@ impl AstNode for Stmt {
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum StmtKind {
-    LetStmt(LetStmt),
-    ExprStmt(ExprStmt),
+pub enum TypeRefKind {
+    PathType(PathType),
+    NeverType(NeverType),
+    ArrayType(ArrayType),
+    ParenType(ParenType),
+    TupleType(TupleType),
+    FnPointerType(FnPointerType),
+    OptionType(OptionType),
 }
-impl From<LetStmt> for Stmt {
-    fn from(n: LetStmt) -> Stmt {
-        Stmt { syntax: n.syntax }
+impl From<PathType> for TypeRef {
+    fn from(n: PathType) -> TypeRef {
+        TypeRef { syntax: n.syntax }
     }
 }
-impl From<ExprStmt> for Stmt {
-    fn from(n: ExprStmt) -> Stmt {
-        Stmt { syntax: n.syntax }
+impl From<NeverType> for TypeRef {
+    fn from(n: NeverType) -> TypeRef {
+        TypeRef { syntax: n.syntax }
+    }
+}
+impl From<ArrayType> for TypeRef {
+    fn from(n: ArrayType) -> TypeRef {
+        TypeRef { syntax: n.syntax }
+    }
+}
+impl From<ParenType> for TypeRef {
+    fn from(n: ParenType) -> TypeRef {
+        TypeRef { syntax: n.syntax }
+    }
+}
+impl From<TupleType> for TypeRef {
+    fn from(n: TupleType) -> TypeRef {
+        TypeRef { syntax: n.syntax }
+    }
+}
+impl From<FnPointerType> for TypeRef {
+    fn from(n: FnPointerType) -> TypeRef {
+        TypeRef { syntax: n.syntax }
+    }
+}
+impl From<OptionType> for TypeRef {
+    fn from(n: OptionType) -> TypeRef {
+        TypeRef { syntax: n.syntax }
     }
 }
 
-impl Stmt {
-    pub fn kind(&self) -> StmtKind {
+impl TypeRef {
+    pub fn kind(&self) -> TypeRefKind {
         match self.syntax.kind() {
-            LET_STMT => StmtKind::LetStmt(LetStmt::cast(self.syntax.clone()).unwrap()),
-            EXPR_STMT => StmtKind::ExprStmt(ExprStmt::cast(self.syntax.clone()).unwrap()),
+            PATH_TYPE => TypeRefKind::PathType(PathType::cast(self.syntax.clone()).unwrap()),
+            NEVER_TYPE => TypeRefKind::NeverType(NeverType::cast(self.syntax.clone()).unwrap()),
+            ARRAY_TYPE => TypeRefKind::ArrayType(ArrayType::cast(self.syntax.clone()).unwrap()),
+            PAREN_TYPE => TypeRefKind::ParenType(ParenType::cast(self.syntax.clone()).unwrap()),
+            TUPLE_TYPE => TypeRefKind::TupleType(TupleType::cast(self.syntax.clone()).unwrap()),
+            FN_POINTER_TYPE => {
+                TypeRefKind::FnPointerType(FnPointerType::cast(self.syntax.clone()).unwrap())
+            }
+            OPTION_TYPE => TypeRefKind::OptionType(OptionType::cast(self.syntax.clone()).unwrap()),
             _ => unreachable!(),
         }
     }
 }
 
-impl Stmt {}
+impl TypeRef {}
 
-// StructDef
+// Use
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct StructDef {
+pub struct Use {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for StructDef {
+impl AstNode for Use {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            STRUCT_DEF => true,
+            USE => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(StructDef { syntax })
+            Some(Use { syntax })
         } else {
             None
         }
@@ -1442,32 +3057,30 @@ impl AstNode for StructDef {
         &self.syntax
     }
 }
-impl ast::NameOwner for StructDef {}
-impl ast::VisibilityOwner for StructDef {}
-impl ast::DocCommentsOwner for StructDef {}
-impl StructDef {
-    pub fn memory_type_specifier(&self) -> Option<MemoryTypeSpecifier> {
+impl ast::VisibilityOwner for Use {}
+impl Use {
+    pub fn use_tree(&self) -> Option<UseTree> {
         super::child_opt(self)
     }
 }
 
-// TupleFieldDef
+// UseTree
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct TupleFieldDef {
+pub struct UseTree {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for TupleFieldDef {
+impl AstNode for UseTree {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            TUPLE_FIELD_DEF => true,
+            USE_TREE => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(TupleFieldDef { syntax })
+            Some(UseTree { syntax })
         } else {
             None
         }
@@ -1476,61 +3089,37 @@ impl AstNode for TupleFieldDef {
         &self.syntax
     }
 }
-impl ast::VisibilityOwner for TupleFieldDef {}
-impl TupleFieldDef {
-    pub fn type_ref(&self) -> Option<TypeRef> {
+impl UseTree {
+    pub fn path(&self) -> Option<Path> {
         super::child_opt(self)
     }
-}
-
-// TupleFieldDefList
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct TupleFieldDefList {
-    pub(crate) syntax: SyntaxNode,
-}
 
-impl AstNode for TupleFieldDefList {
-    fn can_cast(kind: SyntaxKind) -> bool {
-        match kind {
-            TUPLE_FIELD_DEF_LIST => true,
-            _ => false,
-        }
-    }
-    fn cast(syntax: SyntaxNode) -> Option<Self> {
-        if Self::can_cast(syntax.kind()) {
-            Some(TupleFieldDefList { syntax })
-        } else {
-            None
-        }
-    }
-    fn syntax(&self) -> &SyntaxNode {
-        &self.syntax
+    pub fn use_tree_list(&self) -> Option<UseTreeList> {
+        super::child_opt(self)
     }
-}
-impl TupleFieldDefList {
-    pub fn fields(&self) -> impl Iterator<Item = TupleFieldDef> {
-        super::children(self)
+
+    pub fn rename(&self) -> Option<Rename> {
+        super::child_opt(self)
     }
 }
 
-// TypeRef
+// UseTreeList
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct TypeRef {
+pub struct UseTreeList {
     pub(crate) syntax: SyntaxNode,
 }
 
-impl AstNode for TypeRef {
+impl AstNode for UseTreeList {
     fn can_cast(kind: SyntaxKind) -> bool {
         match kind {
-            PATH_TYPE | NEVER_TYPE => true,
+            USE_TREE_LIST => true,
             _ => false,
         }
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
-            Some(TypeRef { syntax })
+            Some(UseTreeList { syntax })
         } else {
             None
         }
@@ -1539,34 +3128,12 @@ impl AstNode for TypeRef {
         &self.syntax
     }
 }
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum TypeRefKind {
-    PathType(PathType),
-    NeverType(NeverType),
-}
-impl From<PathType> for TypeRef {
-    fn from(n: PathType) -> TypeRef {
-        TypeRef { syntax: n.syntax }
-    }
-}
-impl From<NeverType> for TypeRef {
-    fn from(n: NeverType) -> TypeRef {
-        TypeRef { syntax: n.syntax }
-    }
-}
-
-impl TypeRef {
-    pub fn kind(&self) -> TypeRefKind {
-        match self.syntax.kind() {
-            PATH_TYPE => TypeRefKind::PathType(PathType::cast(self.syntax.clone()).unwrap()),
-            NEVER_TYPE => TypeRefKind::NeverType(NeverType::cast(self.syntax.clone()).unwrap()),
-            _ => unreachable!(),
-        }
+impl UseTreeList {
+    pub fn use_trees(&self) -> impl Iterator<Item = UseTree> {
+        super::children(self)
     }
 }
 
-impl TypeRef {}
-
 // Visibility
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -32,6 +32,12 @@ pub trait VisibilityOwner: AstNode {
     }
 }
 
+pub trait AttrsOwner: AstNode {
+    fn attrs(&self) -> AstChildren<ast::Attr> {
+        children(self)
+    }
+}
+
 pub trait LoopBodyOwner: AstNode {
     fn loop_body(&self) -> Option<ast::BlockExpr> {
         child_opt(self)
@@ -44,6 +50,12 @@ pub trait ArgListOwner: AstNode {
     }
 }
 
+pub trait TypeParamsOwner: AstNode {
+    fn type_param_list(&self) -> Option<ast::TypeParamList> {
+        child_opt(self)
+    }
+}
+
 pub trait DocCommentsOwner: AstNode {
     fn doc_comments(&self) -> CommentIter {
         CommentIter {
@@ -0,0 +1,334 @@
+//! A formatter for Mun source code.
+//!
+//! The formatter walks the tokens of the lossless syntax tree and replaces all whitespace between
+//! them. Comments are preserved and, because a newline can end a statement in Mun, line breaks
+//! from the original text are never removed. The formatter only:
+//!
+//!  - normalizes the indentation of every line;
+//!  - normalizes the spacing between tokens on the same line (e.g. around operators);
+//!  - places items, statements, and struct fields on lines of their own;
+//!  - collapses consecutive blank lines and removes trailing whitespace.
+
+use crate::{
+    ast::{self, AstNode},
+    Direction,
+    SyntaxKind::*,
+    SyntaxNode, SyntaxToken, T,
+};
+
+/// The text used for a single level of indentation
+const INDENT: &str = "    ";
+
+/// Returns the formatted text of `file`.
+///
+/// The syntax tree of `file` should be free of errors; formatting code that contains syntax errors
+/// might change its meaning.
+pub fn format(file: &ast::SourceFile) -> String {
+    let mut formatter = Formatter::default();
+    for token in file
+        .syntax()
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+    {
+        formatter.token(token);
+    }
+    formatter.finish()
+}
+
+#[derive(Default)]
+struct Formatter {
+    buf: String,
+    /// The last token that was written, if any
+    prev: Option<SyntaxToken>,
+    /// The number of newlines in the whitespace after `prev`
+    newlines: usize,
+    /// The indentation level of the current line
+    line_indent: usize,
+    /// The indentation levels of the lines on which the currently open delimiters reside
+    delimiters: Vec<usize>,
+}
+
+impl Formatter {
+    fn token(&mut self, token: SyntaxToken) {
+        if token.kind() == WHITESPACE {
+            self.newlines += token.text().matches('\n').count();
+            return;
+        }
+
+        if let Some(prev) = self.prev.take() {
+            let newlines = self.line_breaks(&prev, &token);
+            if newlines > 0 {
+                self.line_indent = self.indent(&prev, &token);
+                for _ in 0..newlines {
+                    self.buf.push('\n');
+                }
+                for _ in 0..self.line_indent {
+                    self.buf.push_str(INDENT);
+                }
+            } else if needs_space(&prev, &token) {
+                self.buf.push(' ');
+            }
+        }
+
+        match token.kind() {
+            T!['('] | T!['['] | T!['{'] => self.delimiters.push(self.line_indent),
+            T![')'] | T![']'] | T!['}'] => {
+                self.delimiters.pop();
+            }
+            _ => {}
+        }
+
+        self.buf.push_str(token.text());
+        if needs_trailing_comma(&token) {
+            self.buf.push(',');
+        }
+        self.prev = Some(token);
+        self.newlines = 0;
+    }
+
+    fn finish(mut self) -> String {
+        if !self.buf.is_empty() {
+            self.buf.push('\n');
+        }
+        self.buf
+    }
+
+    /// Returns the number of line breaks to insert between `prev` and `token`. Existing line breaks
+    /// are kept but at most one blank line is retained, except directly after an opening or before
+    /// a closing delimiter.
+    fn line_breaks(&self, prev: &SyntaxToken, token: &SyntaxToken) -> usize {
+        let max = if is_opening_delimiter(prev) || is_closing_delimiter(token) {
+            1
+        } else {
+            2
+        };
+        let forced = if starts_line(token) { 1 } else { 0 };
+        self.newlines.min(max).max(forced)
+    }
+
+    /// Returns the indentation level of a line that starts with `token`.
+    fn indent(&self, prev: &SyntaxToken, token: &SyntaxToken) -> usize {
+        let delimiter_indent = self.delimiters.last().cloned();
+        if is_closing_delimiter(token) {
+            return delimiter_indent.unwrap_or(0);
+        }
+        let indent = delimiter_indent.map_or(0, |indent| indent + 1);
+        if is_continuation(prev, token) {
+            indent + 1
+        } else {
+            indent
+        }
+    }
+}
+
+/// Returns whether `token` is an opening delimiter: `(`, `[`, or `{`.
+fn is_opening_delimiter(token: &SyntaxToken) -> bool {
+    match token.kind() {
+        T!['('] | T!['['] | T!['{'] => true,
+        _ => false,
+    }
+}
+
+/// Returns whether `token` is a closing delimiter: `)`, `]`, or `}`.
+fn is_closing_delimiter(token: &SyntaxToken) -> bool {
+    match token.kind() {
+        T![')'] | T![']'] | T!['}'] => true,
+        _ => false,
+    }
+}
+
+/// Returns whether `token` must be placed at the start of a line. This is the case for the first
+/// token of items, statements, and struct fields, and for the closing brace of blocks that are
+/// spread over multiple lines.
+fn starts_line(token: &SyntaxToken) -> bool {
+    if token.kind() == COMMENT {
+        // A comment could be the trailing comment of the previous line
+        return false;
+    }
+    if token.kind() == T!['}'] {
+        return is_expanded(&token.parent());
+    }
+
+    for node in token.parent().ancestors() {
+        if first_non_trivia_token(&node).as_ref() != Some(token) {
+            break;
+        }
+        match node.parent() {
+            Some(parent) if parent.kind() == SOURCE_FILE => return true,
+            Some(parent) if is_expanded(&parent) => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Returns whether the contents of `node` are placed on separate lines. This is the case for
+/// non-empty function bodies, blocks that contain statements or line breaks, and the fields of a
+/// record struct.
+fn is_expanded(node: &SyntaxNode) -> bool {
+    match node.kind() {
+        BLOCK_EXPR => {
+            let is_empty = node.children_with_tokens().all(|child| match child.kind() {
+                T!['{'] | T!['}'] | WHITESPACE => true,
+                _ => false,
+            });
+            !is_empty
+                && (node.parent().map(|parent| parent.kind()) == Some(FUNCTION_DEF)
+                    || node
+                        .children()
+                        .any(|child| child.kind() == LET_STMT || child.kind() == EXPR_STMT)
+                    || node.text().contains_char('\n'))
+        }
+        RECORD_FIELD_DEF_LIST => node
+            .children()
+            .any(|child| child.kind() == RECORD_FIELD_DEF),
+        _ => false,
+    }
+}
+
+/// Returns the first token of `node` that is not whitespace or a comment.
+fn first_non_trivia_token(node: &SyntaxNode) -> Option<SyntaxToken> {
+    node.descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .find(|token| !token.kind().is_trivia())
+}
+
+/// Returns whether a line that starts with `token` continues the construct on the line of `prev`,
+/// e.g. the operand of a binary expression or a method call chain, and should be indented further.
+fn is_continuation(prev: &SyntaxToken, token: &SyntaxToken) -> bool {
+    // A line that follows a block, like the `else` of an if expression, or an attribute is not a
+    // continuation
+    if prev.kind() == COMMENT
+        || prev.kind() == T!['}']
+        || is_opening_delimiter(prev)
+        || prev.parent().kind() == ATTR
+    {
+        return false;
+    }
+    let prev_ancestors: Vec<SyntaxNode> = prev.parent().ancestors().collect();
+    let common_ancestor = token
+        .parent()
+        .ancestors()
+        .find(|node| prev_ancestors.contains(node));
+    match common_ancestor.map(|node| node.kind()) {
+        None
+        | Some(SOURCE_FILE)
+        | Some(BLOCK_EXPR)
+        | Some(ITEM_LIST)
+        | Some(PARAM_LIST)
+        | Some(ARG_LIST)
+        | Some(ATTR_ARG_LIST)
+        | Some(TYPE_PARAM_LIST)
+        | Some(TYPE_ARG_LIST)
+        | Some(RECORD_FIELD_DEF_LIST)
+        | Some(TUPLE_FIELD_DEF_LIST)
+        | Some(ENUM_VARIANT_LIST)
+        | Some(USE_TREE_LIST)
+        | Some(MATCH_ARM_LIST)
+        | Some(RECORD_FIELD_LIST)
+        | Some(RECORD_FIELD_PAT_LIST)
+        | Some(ARRAY_EXPR)
+        | Some(TUPLE_EXPR)
+        | Some(TUPLE_PAT)
+        | Some(TUPLE_TYPE) => false,
+        _ => true,
+    }
+}
+
+/// Returns whether `token` is a `<` or `>` that delimits a list of generic parameters or
+/// arguments.
+fn is_angle_bracket(token: &SyntaxToken) -> bool {
+    match token.kind() {
+        T![<] | T![>] => match token.parent().kind() {
+            TYPE_ARG_LIST | TYPE_PARAM_LIST => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Returns whether `token` is the operator of a prefix expression.
+fn is_prefix_operator(token: &SyntaxToken) -> bool {
+    let parent = token.parent();
+    parent.kind() == PREFIX_EXPR && parent.first_token().as_ref() == Some(token)
+}
+
+/// Returns whether `token` is one of the `|` that surround the parameters of a closure: `Some(true)`
+/// for the opening and `Some(false)` for the closing `|`.
+fn closure_pipe(token: &SyntaxToken) -> Option<bool> {
+    let parent = token.parent();
+    if token.kind() != T![|] || parent.kind() != PARAM_LIST {
+        None
+    } else {
+        Some(parent.first_token().as_ref() == Some(token))
+    }
+}
+
+/// Returns whether a space is placed between `prev` and `token` when they are on the same line.
+fn needs_space(prev: &SyntaxToken, token: &SyntaxToken) -> bool {
+    if prev.kind() == COMMENT || token.kind() == COMMENT {
+        return true;
+    }
+    match token.kind() {
+        T![,] | T![;] | T![:] | T![.] | T![::] | T![?] | INDEX => return false,
+        T![..] | T![...] | T![..=] => return false,
+        T![')'] | T![']'] => return false,
+        T!['}'] => return prev.kind() != T!['{'] && token.parent().kind() != USE_TREE_LIST,
+        T!['('] | T!['['] => {
+            if no_space_before_delimiter(prev, token) {
+                return false;
+            }
+        }
+        _ => {}
+    }
+    match prev.kind() {
+        T!['('] | T!['['] | T![.] | T![::] => return false,
+        T!['{'] if prev.parent().kind() == USE_TREE_LIST => return false,
+        T![..] | T![...] | T![..=] => return false,
+        _ => {}
+    }
+    if is_angle_bracket(token) || (prev.kind() == T![<] && is_angle_bracket(prev)) {
+        return false;
+    }
+    if is_prefix_operator(prev) {
+        return false;
+    }
+    if closure_pipe(prev) == Some(true) || closure_pipe(token) == Some(false) {
+        return false;
+    }
+    true
+}
+
+/// Returns whether the opening delimiter `token` directly follows `prev`, as in the arguments of a
+/// call, the index of an index expression, `pub(crate)`, `struct(gc)`, or `#[allow(..)]`.
+fn no_space_before_delimiter(prev: &SyntaxToken, token: &SyntaxToken) -> bool {
+    match prev.kind() {
+        IDENT | T![')'] | T![']'] | T![fn] => true,
+        T![>] => is_angle_bracket(prev),
+        _ => match token.parent().kind() {
+            VISIBILITY | MEMORY_TYPE_SPECIFIER | ATTR => true,
+            _ => false,
+        },
+    }
+}
+
+/// Returns whether a `,` has to be inserted after `token` because it is the last token of the last
+/// field of a record struct that is not followed by a comma.
+fn needs_trailing_comma(token: &SyntaxToken) -> bool {
+    let field = match token
+        .parent()
+        .ancestors()
+        .take_while(|node| node.last_token().as_ref() == Some(token))
+        .find(|node| node.kind() == RECORD_FIELD_DEF)
+    {
+        Some(field) => field,
+        None => return false,
+    };
+    let is_expanded = field.parent().map_or(false, |parent| is_expanded(&parent));
+    let next = field
+        .siblings_with_tokens(Direction::Next)
+        .skip(1)
+        .map(|element| element.kind())
+        .find(|kind| !kind.is_trivia());
+    is_expanded && next != Some(T![,])
+}
@@ -12,7 +12,9 @@
 #[macro_use]
 mod syntax_kind;
 
+pub mod algo;
 pub mod ast;
+pub mod formatting;
 mod parsing;
 mod ptr;
 mod syntax_error;
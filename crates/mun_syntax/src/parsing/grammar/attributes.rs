@@ -0,0 +1,43 @@
+use super::*;
+
+/// Parses the attributes that precede a declaration, e.g. `#[allow(unused_variables)]`.
+pub(super) fn outer_attrs(p: &mut Parser) {
+    while p.at(T![#]) {
+        attr(p);
+    }
+}
+
+fn attr(p: &mut Parser) {
+    assert!(p.at(T![#]));
+    let m = p.start();
+    p.bump(T![#]);
+    if p.expect(T!['[']) {
+        name_ref(p);
+        if p.at(T!['(']) {
+            attr_arg_list(p);
+        }
+        p.expect(T![']']);
+    }
+    m.complete(p, ATTR);
+}
+
+/// Parses the arguments of an attribute, e.g. `(unused_variables, unused_mut)` or `("hp")`.
+fn attr_arg_list(p: &mut Parser) {
+    assert!(p.at(T!['(']));
+    let m = p.start();
+    p.bump(T!['(']);
+    while !p.at(EOF) && !p.at(T![')']) {
+        if p.at(STRING) {
+            let literal = p.start();
+            p.bump(STRING);
+            literal.complete(p, LITERAL);
+        } else {
+            name_ref(p);
+        }
+        if !p.at(T![')']) && !p.expect(T![,]) {
+            break;
+        }
+    }
+    p.expect(T![')']);
+    m.complete(p, ATTR_ARG_LIST);
+}
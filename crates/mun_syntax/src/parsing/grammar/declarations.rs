@@ -1,7 +1,9 @@
 use super::*;
 use crate::T;
 
-pub(super) const DECLARATION_RECOVERY_SET: TokenSet = token_set![FN_KW, PUB_KW, STRUCT_KW];
+pub(super) const DECLARATION_RECOVERY_SET: TokenSet = token_set![
+    FN_KW, PUB_KW, STRUCT_KW, ENUM_KW, IMPL_KW, TRAIT_KW, CONST_KW, STATIC_KW, MOD_KW, USE_KW, HASH
+];
 
 pub(super) fn mod_contents(p: &mut Parser) {
     while !p.at(EOF) {
@@ -32,6 +34,7 @@ pub(super) fn declaration(p: &mut Parser) {
 }
 
 pub(super) fn maybe_declaration(p: &mut Parser, m: Marker) -> Result<(), Marker> {
+    attributes::outer_attrs(p);
     opt_visibility(p);
 
     let m = match declarations_without_modifiers(p, m) {
@@ -65,17 +68,131 @@ fn declarations_without_modifiers(p: &mut Parser, m: Marker) -> Result<(), Marke
         T![struct] => {
             adt::struct_def(p, m);
         }
+        T![enum] => {
+            adt::enum_def(p, m);
+        }
+        T![impl] => {
+            impl_block(p, m);
+        }
+        T![trait] => {
+            trait_def(p, m);
+        }
+        T![const] => {
+            const_or_static(p, m, T![const], CONST_DEF);
+        }
+        T![static] => {
+            const_or_static(p, m, T![static], STATIC_DEF);
+        }
+        T![mod] => {
+            mod_def(p, m);
+        }
+        T![use] => {
+            use_item::use_(p, m);
+        }
         _ => return Err(m),
     };
     Ok(())
 }
 
+/// Parses a submodule declaration, e.g. `mod physics;`. The contents of the submodule are stored in
+/// a separate file.
+fn mod_def(p: &mut Parser, m: Marker) {
+    assert!(p.at(T![mod]));
+    p.bump(T![mod]);
+    name_recovery(p, DECLARATION_RECOVERY_SET.union(token_set![T![;]]));
+    p.expect(T![;]);
+    m.complete(p, MODULE);
+}
+
+/// Parses a constant or a global variable, e.g. `const GRAVITY: f32 = 9.81;` or
+/// `static mut SCORE: i32 = 0;`.
+fn const_or_static(p: &mut Parser, m: Marker, kw: SyntaxKind, def: SyntaxKind) {
+    assert!(p.at(kw));
+    p.bump(kw);
+    if kw == T![static] {
+        p.eat(T![mut]);
+    }
+    name_recovery(
+        p,
+        DECLARATION_RECOVERY_SET.union(token_set![T![:], T![=], T![;]]),
+    );
+    if p.at(T![:]) {
+        types::ascription(p);
+    } else {
+        p.error("expected a type");
+    }
+    if p.eat(T![=]) {
+        expressions::expr(p);
+    } else {
+        p.error("expected `=`");
+    }
+    p.expect(T![;]);
+    m.complete(p, def);
+}
+
+/// Parses an impl block, e.g. `impl Foo { fn new() -> Self { .. } }` or
+/// `impl Update for Foo { fn update(self, dt: f32) { .. } }`.
+fn impl_block(p: &mut Parser, m: Marker) {
+    assert!(p.at(T![impl]));
+    p.bump(T![impl]);
+    types::type_(p);
+    if p.eat(T![for]) {
+        types::type_(p);
+    }
+    if p.at(T!['{']) {
+        item_list(p);
+    } else {
+        p.error("expected '{'");
+    }
+    m.complete(p, IMPL_BLOCK);
+}
+
+/// Parses a trait declaration, e.g. `trait Update { fn update(self, dt: f32); }`.
+fn trait_def(p: &mut Parser, m: Marker) {
+    assert!(p.at(T![trait]));
+    p.bump(T![trait]);
+    name_recovery(p, DECLARATION_RECOVERY_SET.union(token_set![T!['{']]));
+    if p.at(T!['{']) {
+        item_list(p);
+    } else {
+        p.error("expected '{'");
+    }
+    m.complete(p, TRAIT_DEF);
+}
+
+/// Parses the functions of an impl block or a trait.
+fn item_list(p: &mut Parser) {
+    assert!(p.at(T!['{']));
+    let m = p.start();
+    p.bump(T!['{']);
+    while !p.at(EOF) && !p.at(T!['}']) {
+        let item = p.start();
+        attributes::outer_attrs(p);
+        opt_visibility(p);
+        if p.at(T![fn]) {
+            fn_def(p);
+            item.complete(p, FUNCTION_DEF);
+        } else {
+            item.abandon(p);
+            if p.at(T!['{']) {
+                error_block(p, "expected a function");
+            } else {
+                p.error_and_bump("expected a function");
+            }
+        }
+    }
+    p.expect(T!['}']);
+    m.complete(p, ITEM_LIST);
+}
+
 pub(super) fn fn_def(p: &mut Parser) {
     assert!(p.at(T![fn]));
     p.bump(T![fn]);
 
     name_recovery(p, DECLARATION_RECOVERY_SET.union(token_set![L_PAREN]));
 
+    type_params::opt_type_param_list(p);
+
     if p.at(T!['(']) {
         params::param_list(p);
     } else {
@@ -91,7 +208,7 @@ pub(super) fn fn_def(p: &mut Parser) {
     }
 }
 
-fn opt_fn_ret_type(p: &mut Parser) -> bool {
+pub(super) fn opt_fn_ret_type(p: &mut Parser) -> bool {
     if p.at(T![->]) {
         let m = p.start();
         p.bump(T![->]);
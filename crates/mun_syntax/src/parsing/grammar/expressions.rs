@@ -2,7 +2,7 @@ use super::*;
 use crate::parsing::grammar::paths::PATH_FIRST;
 
 pub(crate) const LITERAL_FIRST: TokenSet =
-    token_set![TRUE_KW, FALSE_KW, INT_NUMBER, FLOAT_NUMBER, STRING];
+    token_set![TRUE_KW, FALSE_KW, NIL_KW, INT_NUMBER, FLOAT_NUMBER, STRING];
 
 const EXPR_RECOVERY_SET: TokenSet = token_set![LET_KW];
 
@@ -10,11 +10,15 @@ const ATOM_EXPR_FIRST: TokenSet = LITERAL_FIRST.union(PATH_FIRST).union(token_se
     IDENT,
     T!['('],
     T!['{'],
+    T!['['],
     T![if],
     T![loop],
     T![return],
     T![break],
     T![while],
+    T![for],
+    T![match],
+    T![|],
 ]);
 
 const LHS_FIRST: TokenSet = ATOM_EXPR_FIRST.union(token_set![EXCLAMATION, MINUS]);
@@ -141,11 +145,19 @@ fn expr_bp(p: &mut Parser, r: Restrictions, bp: u8) -> (Option<CompletedMarker>,
             break;
         }
 
+        if op == T![as] {
+            lhs = cast_expr(p, lhs);
+            continue;
+        }
+
         let m = lhs.precede(p);
         p.bump(op);
 
         expr_bp(p, r, op_bp + 1);
-        lhs = m.complete(p, BIN_EXPR);
+        lhs = match op {
+            T![..] | T![..=] => m.complete(p, RANGE_EXPR),
+            _ => m.complete(p, BIN_EXPR),
+        };
     }
 
     (Some(lhs), BlockLike::NotBlock)
@@ -182,6 +194,9 @@ fn current_op(p: &Parser) -> (u8, SyntaxKind) {
         T![<] if p.at(T![<<=]) => (1, T![<<=]),
         T![<] if p.at(T![<<]) => (9, T![<<]),
         T![<] => (5, T![<]),
+        T![..] => (2, T![..]),
+        T![..=] => (2, T![..=]),
+        T![as] => (12, T![as]),
         _ => (0, T![_]),
     }
 }
@@ -211,6 +226,7 @@ fn postfix_expr(
     loop {
         lhs = match p.current() {
             T!['('] => call_expr(p, lhs),
+            T!['['] => index_expr(p, lhs),
             T![.] => match postfix_dot_expr(p, lhs) {
                 Ok(it) => it,
                 Err(it) => {
@@ -225,6 +241,14 @@ fn postfix_expr(
     (lhs, BlockLike::NotBlock)
 }
 
+fn cast_expr(p: &mut Parser, lhs: CompletedMarker) -> CompletedMarker {
+    assert!(p.at(T![as]));
+    let m = lhs.precede(p);
+    p.bump(T![as]);
+    types::type_(p);
+    m.complete(p, CAST_EXPR)
+}
+
 fn call_expr(p: &mut Parser, lhs: CompletedMarker) -> CompletedMarker {
     assert!(p.at(T!['(']));
     let m = lhs.precede(p);
@@ -232,6 +256,15 @@ fn call_expr(p: &mut Parser, lhs: CompletedMarker) -> CompletedMarker {
     m.complete(p, CALL_EXPR)
 }
 
+fn index_expr(p: &mut Parser, lhs: CompletedMarker) -> CompletedMarker {
+    assert!(p.at(T!['[']));
+    let m = lhs.precede(p);
+    p.bump(T!['[']);
+    expr(p);
+    p.expect(T![']']);
+    m.complete(p, INDEX_EXPR)
+}
+
 fn arg_list(p: &mut Parser) {
     assert!(p.at(T!['(']));
     let m = p.start();
@@ -257,12 +290,21 @@ fn postfix_dot_expr(
 ) -> Result<CompletedMarker, CompletedMarker> {
     assert!(p.at(T![.]));
     if p.nth(1) == IDENT && p.nth(2) == T!['('] {
-        unimplemented!("Method calls are not supported yet.");
+        return Ok(method_call_expr(p, lhs));
     }
 
     Ok(field_expr(p, lhs))
 }
 
+fn method_call_expr(p: &mut Parser, lhs: CompletedMarker) -> CompletedMarker {
+    assert!(p.at(T![.]) && p.nth(1) == IDENT && p.nth(2) == T!['(']);
+    let m = lhs.precede(p);
+    p.bump(T![.]);
+    name_ref(p);
+    arg_list(p);
+    m.complete(p, METHOD_CALL_EXPR)
+}
+
 fn field_expr(p: &mut Parser, lhs: CompletedMarker) -> CompletedMarker {
     assert!(p.at(T![.]) || p.at(INDEX));
     let m = lhs.precede(p);
@@ -287,20 +329,24 @@ fn atom_expr(p: &mut Parser, r: Restrictions) -> Option<(CompletedMarker, BlockL
     }
 
     let marker = match p.current() {
-        T!['('] => paren_expr(p),
+        T!['('] => paren_or_tuple_expr(p),
         T!['{'] => block_expr(p),
+        T!['['] => array_expr(p),
         T![if] => if_expr(p),
         T![loop] => loop_expr(p),
         T![return] => ret_expr(p),
         T![while] => while_expr(p),
+        T![for] => for_expr(p),
+        T![match] => match_expr(p),
         T![break] => break_expr(p, r),
+        T![|] => closure_expr(p),
         _ => {
             p.error_recover("expected expression", EXPR_RECOVERY_SET);
             return None;
         }
     };
     let blocklike = match marker.kind() {
-        IF_EXPR | WHILE_EXPR | LOOP_EXPR | BLOCK_EXPR => BlockLike::Block,
+        IF_EXPR | WHILE_EXPR | FOR_EXPR | LOOP_EXPR | MATCH_EXPR | BLOCK_EXPR => BlockLike::Block,
         _ => BlockLike::NotBlock,
     };
     Some((marker, blocklike))
@@ -329,13 +375,75 @@ fn literal(p: &mut Parser) -> Option<CompletedMarker> {
     Some(m.complete(p, LITERAL))
 }
 
-fn paren_expr(p: &mut Parser) -> CompletedMarker {
+fn paren_or_tuple_expr(p: &mut Parser) -> CompletedMarker {
     assert!(p.at(T!['(']));
     let m = p.start();
     p.bump(T!['(']);
-    expr(p);
+
+    let mut saw_comma = false;
+    let mut saw_expr = false;
+    while !p.at(EOF) && !p.at(T![')']) {
+        saw_expr = true;
+        if !p.at_ts(EXPR_FIRST) {
+            p.error("expected expression");
+            break;
+        }
+        expr(p);
+        if !p.at(T![')']) {
+            saw_comma = true;
+            if !p.expect(T![,]) {
+                break;
+            }
+        }
+    }
     p.expect(T![')']);
-    m.complete(p, PAREN_EXPR)
+
+    // `(e)` is a parenthesized expression, whereas `()`, `(e,)` and `(a, b)` are tuples
+    m.complete(
+        p,
+        if saw_expr && !saw_comma {
+            PAREN_EXPR
+        } else {
+            TUPLE_EXPR
+        },
+    )
+}
+
+fn array_expr(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(T!['[']));
+    let m = p.start();
+    p.bump(T!['[']);
+    while !p.at(T![']']) && !p.at(EOF) {
+        if !p.at_ts(EXPR_FIRST) {
+            p.error("expected expression");
+            break;
+        }
+
+        expr(p);
+        if !p.at(T![']']) && !p.expect(T![,]) {
+            break;
+        }
+    }
+    p.expect(T![']']);
+    m.complete(p, ARRAY_EXPR)
+}
+
+/// Parses a closure expression, e.g. `|x| x * k` or `|| -> f32 { 1.0 }`
+fn closure_expr(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(T![|]));
+    let m = p.start();
+    if !p.eat(T![||]) {
+        params::param_list_closure(p);
+    }
+    if declarations::opt_fn_ret_type(p) {
+        // A closure with an explicit return type requires a block as its body
+        block(p);
+    } else if p.at_ts(EXPR_FIRST) {
+        expr(p);
+    } else {
+        p.error("expected expression");
+    }
+    m.complete(p, CLOSURE_EXPR)
 }
 
 fn if_expr(p: &mut Parser) -> CompletedMarker {
@@ -398,6 +506,63 @@ fn while_expr(p: &mut Parser) -> CompletedMarker {
     m.complete(p, WHILE_EXPR)
 }
 
+fn for_expr(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(T![for]));
+    let m = p.start();
+    p.bump(T![for]);
+    patterns::pattern(p);
+    p.expect(T![in]);
+    expr_no_struct(p);
+    block(p);
+    m.complete(p, FOR_EXPR)
+}
+
+fn match_expr(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(T![match]));
+    let m = p.start();
+    p.bump(T![match]);
+    expr_no_struct(p);
+    if p.at(T!['{']) {
+        match_arm_list(p);
+    } else {
+        p.error("expected `{`");
+    }
+    m.complete(p, MATCH_EXPR)
+}
+
+fn match_arm_list(p: &mut Parser) {
+    assert!(p.at(T!['{']));
+    let m = p.start();
+    p.bump(T!['{']);
+    while !p.at(EOF) && !p.at(T!['}']) {
+        if p.at(T!['{']) {
+            error_block(p, "expected match arm");
+            continue;
+        }
+
+        let blocklike = match_arm(p);
+        if !p.at(T!['}']) {
+            // A comma is optional after an arm whose expression is block-like
+            if blocklike.is_block() {
+                p.eat(T![,]);
+            } else {
+                p.expect(T![,]);
+            }
+        }
+    }
+    p.expect(T!['}']);
+    m.complete(p, MATCH_ARM_LIST);
+}
+
+fn match_arm(p: &mut Parser) -> BlockLike {
+    let m = p.start();
+    patterns::pattern_r(p, EXPR_RECOVERY_SET.union(token_set![EQ]));
+    p.expect(T![=>]);
+    let (_, blocklike) = expr_stmt(p);
+    m.complete(p, MATCH_ARM);
+    blocklike
+}
+
 fn record_field_list(p: &mut Parser) {
     assert!(p.at(T!['{']));
     let m = p.start();
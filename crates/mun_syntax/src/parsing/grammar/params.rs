@@ -1,32 +1,88 @@
 use super::*;
 
 pub(super) fn param_list(p: &mut Parser) {
-    list(p)
+    list(p, Flavor::Function)
 }
 
-fn list(p: &mut Parser) {
-    assert!(p.at(T!['(']));
+/// Parses the parameter types of a function pointer type, e.g. `(f32, i32)` in
+/// `fn(f32, i32) -> f32`.
+pub(super) fn param_list_fn_pointer(p: &mut Parser) {
+    list(p, Flavor::FnPointer)
+}
+
+/// Parses the parameters of a closure, e.g. `|x, y: f32|`.
+pub(super) fn param_list_closure(p: &mut Parser) {
+    list(p, Flavor::Closure)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Flavor {
+    /// The parameters of a function declaration, which require a type ascription
+    Function,
+    /// The parameters of a function pointer type, which only consist of a type
+    FnPointer,
+    /// The parameters of a closure, for which the type ascription is optional
+    Closure,
+}
+
+fn list(p: &mut Parser, flavor: Flavor) {
+    let (bra, ket) = if flavor == Flavor::Closure {
+        (T![|], T![|])
+    } else {
+        (T!['('], T![')'])
+    };
+    assert!(p.at(bra));
     let m = p.start();
-    p.bump(T!['(']);
-    while !p.at(EOF) && !p.at(T![')']) {
-        if !p.at_ts(VALUE_PARAMETER_FIRST) {
+    p.bump(bra);
+    if flavor == Flavor::Function && p.at(T![self]) {
+        self_param(p);
+        if !p.at(ket) {
+            p.expect(T![,]);
+        }
+    }
+    while !p.at(EOF) && !p.at(ket) {
+        let first = if flavor == Flavor::FnPointer {
+            types::TYPE_FIRST
+        } else {
+            VALUE_PARAMETER_FIRST
+        };
+        if !p.at_ts(first) {
             p.error("expected value parameter");
             break;
         }
-        param(p);
-        if !p.at(T![')']) {
+        param(p, flavor);
+        if !p.at(ket) {
             p.expect(T![,]);
         }
     }
-    p.expect(T![')']);
+    p.expect(ket);
     m.complete(p, PARAM_LIST);
 }
 
 const VALUE_PARAMETER_FIRST: TokenSet = patterns::PATTERN_FIRST;
 
-fn param(p: &mut Parser) {
+/// Parses the `self` parameter of a method.
+fn self_param(p: &mut Parser) {
+    assert!(p.at(T![self]));
+    let m = p.start();
+    p.bump(T![self]);
+    m.complete(p, SELF_PARAM);
+}
+
+fn param(p: &mut Parser, flavor: Flavor) {
     let m = p.start();
-    patterns::pattern(p);
-    types::ascription(p);
+    match flavor {
+        Flavor::Function => {
+            patterns::pattern(p);
+            types::ascription(p);
+        }
+        Flavor::FnPointer => types::type_(p),
+        Flavor::Closure => {
+            patterns::pattern(p);
+            if p.at(T![:]) {
+                types::ascription(p);
+            }
+        }
+    }
     m.complete(p, PARAM);
 }
@@ -1,14 +1,19 @@
 use super::*;
 
-pub(super) const PATH_FIRST: TokenSet = token_set![IDENT, SELF_KW, SUPER_KW, COLONCOLON];
+pub(super) const PATH_FIRST: TokenSet =
+    token_set![IDENT, SELF_KW, SUPER_KW, PACKAGE_KW, COLONCOLON];
 
 pub(super) fn is_path_start(p: &Parser) -> bool {
     match p.current() {
-        IDENT | T![::] => true,
+        IDENT | T![self] | T![super] | T![package] | T![::] => true,
         _ => false,
     }
 }
 
+pub(super) fn use_path(p: &mut Parser) {
+    path(p, Mode::Use)
+}
+
 pub(super) fn type_path(p: &mut Parser) {
     path(p, Mode::Type)
 }
@@ -18,6 +23,7 @@ pub(super) fn expr_path(p: &mut Parser) {
 
 #[derive(Clone, Copy, Eq, PartialEq)]
 enum Mode {
+    Use,
     Type,
     Expr,
 }
@@ -43,7 +49,7 @@ fn path(p: &mut Parser, mode: Mode) {
     }
 }
 
-fn path_segment(p: &mut Parser, _mode: Mode, first: bool) {
+fn path_segment(p: &mut Parser, mode: Mode, first: bool) {
     let m = p.start();
     if first {
         p.eat(T![::]);
@@ -51,8 +57,11 @@ fn path_segment(p: &mut Parser, _mode: Mode, first: bool) {
     match p.current() {
         IDENT => {
             name_ref(p);
+            if mode == Mode::Type {
+                type_args::opt_type_arg_list(p);
+            }
         }
-        T![self] | T![super] => p.bump_any(),
+        T![self] | T![super] | T![package] => p.bump_any(),
         _ => p.error_recover(
             "expected identifier",
             declarations::DECLARATION_RECOVERY_SET,
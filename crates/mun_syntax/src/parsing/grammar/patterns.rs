@@ -2,7 +2,7 @@ use super::*;
 
 pub(super) const PATTERN_FIRST: TokenSet = expressions::LITERAL_FIRST
     .union(paths::PATH_FIRST)
-    .union(token_set![MINUS, UNDERSCORE]);
+    .union(token_set![MINUS, UNDERSCORE, L_PAREN]);
 
 pub(super) fn pattern(p: &mut Parser) {
     pattern_r(p, PATTERN_FIRST);
@@ -14,12 +14,17 @@ pub(super) fn pattern_r(p: &mut Parser, recovery_set: TokenSet) {
 
 fn atom_pat(p: &mut Parser, recovery_set: TokenSet) -> Option<CompletedMarker> {
     let t1 = p.nth(0);
-    if t1 == IDENT {
+    if t1 == IDENT && !p.nth_at(1, T![::]) && !p.nth_at(1, T!['(']) && !p.nth_at(1, T!['{']) {
         return Some(bind_pat(p));
     }
 
+    if paths::is_path_start(p) {
+        return Some(path_pat(p));
+    }
+
     let m = match t1 {
         T![_] => placeholder_pat(p),
+        T!['('] => tuple_pat(p),
         _ => {
             p.error_recover("expected pattern", recovery_set);
             return None;
@@ -28,6 +33,87 @@ fn atom_pat(p: &mut Parser, recovery_set: TokenSet) -> Option<CompletedMarker> {
     Some(m)
 }
 
+/// Parses a pattern that starts with a path, e.g.:
+/// ```mun
+/// Shape::None
+/// Shape::Circle(radius)
+/// Shape::Rect { w, h: height }
+/// ```
+fn path_pat(p: &mut Parser) -> CompletedMarker {
+    assert!(paths::is_path_start(p));
+    let m = p.start();
+    paths::expr_path(p);
+    let kind = match p.current() {
+        T!['('] => {
+            tuple_pat_fields(p);
+            TUPLE_STRUCT_PAT
+        }
+        T!['{'] => {
+            record_field_pat_list(p);
+            RECORD_PAT
+        }
+        _ => PATH_PAT,
+    };
+    m.complete(p, kind)
+}
+
+fn tuple_pat_fields(p: &mut Parser) {
+    assert!(p.at(T!['(']));
+    p.bump(T!['(']);
+    while !p.at(EOF) && !p.at(T![')']) {
+        if !p.at_ts(PATTERN_FIRST) {
+            p.error("expected a pattern");
+            break;
+        }
+        pattern(p);
+        if !p.at(T![')']) {
+            p.expect(T![,]);
+        }
+    }
+    p.expect(T![')']);
+}
+
+fn record_field_pat_list(p: &mut Parser) {
+    assert!(p.at(T!['{']));
+    let m = p.start();
+    p.bump(T!['{']);
+    while !p.at(EOF) && !p.at(T!['}']) {
+        match p.current() {
+            IDENT | INT_NUMBER if p.nth_at(1, T![:]) => {
+                let m = p.start();
+                name_ref_or_index(p);
+                p.bump(T![:]);
+                pattern(p);
+                m.complete(p, RECORD_FIELD_PAT);
+            }
+            IDENT => {
+                let m = p.start();
+                bind_pat(p);
+                m.complete(p, RECORD_FIELD_PAT);
+            }
+            T!['{'] => error_block(p, "expected an identifier"),
+            _ => p.error_and_bump("expected an identifier"),
+        }
+        if !p.at(T!['}']) {
+            p.expect(T![,]);
+        }
+    }
+    p.expect(T!['}']);
+    m.complete(p, RECORD_FIELD_PAT_LIST);
+}
+
+/// Parses a tuple pattern, e.g.:
+/// ```mun
+/// (x, y)
+/// (a, _)
+/// ```
+fn tuple_pat(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(T!['(']));
+    let m = p.start();
+    tuple_pat_fields(p);
+    m.complete(p, TUPLE_PAT)
+}
+
 fn placeholder_pat(p: &mut Parser) -> CompletedMarker {
     assert!(p.at(T![_]));
     let m = p.start();
@@ -0,0 +1,29 @@
+use super::*;
+
+/// Parses an optional list of type arguments of a path in a type, e.g. `<f32, i64>` in
+/// `Pair<f32, i64>`.
+pub(super) fn opt_type_arg_list(p: &mut Parser) {
+    if !p.at(T![<]) {
+        return;
+    }
+    let m = p.start();
+    p.bump(T![<]);
+    while !p.at(EOF) && !p.at(T![>]) {
+        if !p.at_ts(types::TYPE_FIRST) {
+            p.error("expected type argument");
+            break;
+        }
+        type_arg(p);
+        if !p.at(T![>]) {
+            p.expect(T![,]);
+        }
+    }
+    p.expect(T![>]);
+    m.complete(p, TYPE_ARG_LIST);
+}
+
+fn type_arg(p: &mut Parser) {
+    let m = p.start();
+    types::type_(p);
+    m.complete(p, TYPE_ARG);
+}
@@ -1,6 +1,7 @@
 use super::*;
 
-pub(super) const TYPE_FIRST: TokenSet = paths::PATH_FIRST.union(token_set![T![never],]);
+pub(super) const TYPE_FIRST: TokenSet =
+    paths::PATH_FIRST.union(token_set![T![never], T!['['], T!['('], T![fn],]);
 
 pub(super) const TYPE_RECOVERY_SET: TokenSet = token_set![R_PAREN, COMMA];
 
@@ -10,24 +11,91 @@ pub(super) fn ascription(p: &mut Parser) {
 }
 
 pub(super) fn type_(p: &mut Parser) {
-    match p.current() {
+    let m = match p.current() {
         T![never] => never_type(p),
+        T!['['] => array_type(p),
+        T!['('] => paren_or_tuple_type(p),
+        T![fn] => fn_pointer_type(p),
         _ if paths::is_path_start(p) => path_type(p),
         _ => {
             p.error_recover("expected type", TYPE_RECOVERY_SET);
+            return;
         }
+    };
+    opt_option_type(p, m);
+}
+
+/// Parses the `?` of an optional type, e.g. `Foo?`, which wraps the preceding type.
+fn opt_option_type(p: &mut Parser, m: CompletedMarker) {
+    if p.at(T![?]) {
+        let m = m.precede(p);
+        p.bump(T![?]);
+        m.complete(p, OPTION_TYPE);
     }
 }
 
-pub(super) fn path_type(p: &mut Parser) {
+pub(super) fn path_type(p: &mut Parser) -> CompletedMarker {
     let m = p.start();
     paths::type_path(p);
-    m.complete(p, PATH_TYPE);
+    m.complete(p, PATH_TYPE)
 }
 
-fn never_type(p: &mut Parser) {
+fn never_type(p: &mut Parser) -> CompletedMarker {
     assert!(p.at(T![never]));
     let m = p.start();
     p.bump(T![never]);
-    m.complete(p, NEVER_TYPE);
+    m.complete(p, NEVER_TYPE)
+}
+
+fn array_type(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(T!['[']));
+    let m = p.start();
+    p.bump(T!['[']);
+    type_(p);
+    if p.eat(T![;]) {
+        expressions::expr(p);
+    }
+    p.expect(T![']']);
+    m.complete(p, ARRAY_TYPE)
+}
+
+fn paren_or_tuple_type(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(T!['(']));
+    let m = p.start();
+    p.bump(T!['(']);
+    let mut num_types = 0;
+    let mut trailing_comma = false;
+    while !p.at(EOF) && !p.at(T![')']) {
+        num_types += 1;
+        type_(p);
+        if p.eat(T![,]) {
+            trailing_comma = true;
+        } else {
+            trailing_comma = false;
+            break;
+        }
+    }
+    p.expect(T![')']);
+
+    // `(T)` is a parenthesized type, whereas `()`, `(T,)` and `(A, B)` are tuple types
+    let kind = if num_types == 1 && !trailing_comma {
+        PAREN_TYPE
+    } else {
+        TUPLE_TYPE
+    };
+    m.complete(p, kind)
+}
+
+/// Parses a function pointer type, e.g. `fn(f32, f32) -> f32`.
+fn fn_pointer_type(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(T![fn]));
+    let m = p.start();
+    p.bump(T![fn]);
+    if p.at(T!['(']) {
+        params::param_list_fn_pointer(p);
+    } else {
+        p.error("expected parameters");
+    }
+    declarations::opt_fn_ret_type(p);
+    m.complete(p, FN_POINTER_TYPE)
 }
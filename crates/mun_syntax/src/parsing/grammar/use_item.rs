@@ -0,0 +1,71 @@
+use super::*;
+
+pub(super) fn use_(p: &mut Parser, m: Marker) {
+    assert!(p.at(T![use]));
+    p.bump(T![use]);
+    use_tree(p, true);
+    p.expect(T![;]);
+    m.complete(p, USE);
+}
+
+/// Parses a use "tree", such as `foo::bar` in `use foo::bar;`.
+fn use_tree(p: &mut Parser, top_level: bool) {
+    let m = p.start();
+    match p.current() {
+        // Handles a nested use tree list: `use {foo, bar};`
+        T!['{'] => use_tree_list(p),
+        _ if paths::is_path_start(p) => {
+            paths::use_path(p);
+            match p.current() {
+                T![as] => rename(p),
+                T![::] => {
+                    p.bump(T![::]);
+                    match p.current() {
+                        T![*] => p.bump(T![*]),
+                        T!['{'] => use_tree_list(p),
+                        _ => p.error("expected `{` or `*`"),
+                    }
+                }
+                _ => (),
+            }
+        }
+        _ => {
+            m.abandon(p);
+            let msg = "expected one of `self`, `super`, `package`, `{` or an identifier";
+            if top_level {
+                p.error_recover(
+                    msg,
+                    declarations::DECLARATION_RECOVERY_SET.union(token_set![T![;]]),
+                );
+            } else {
+                p.error_and_bump(msg);
+            }
+            return;
+        }
+    }
+    m.complete(p, USE_TREE);
+}
+
+/// Parses a list of use trees, e.g. `{bar, baz::*}` in `use foo::{bar, baz::*};`
+fn use_tree_list(p: &mut Parser) {
+    assert!(p.at(T!['{']));
+    let m = p.start();
+    p.bump(T!['{']);
+    while !p.at(EOF) && !p.at(T!['}']) {
+        use_tree(p, false);
+        if !p.at(T!['}']) {
+            p.expect(T![,]);
+        }
+    }
+    p.expect(T!['}']);
+    m.complete(p, USE_TREE_LIST);
+}
+
+/// Parses the alias of an import, e.g. `as Bar` in `use foo::Foo as Bar;`
+fn rename(p: &mut Parser) {
+    assert!(p.at(T![as]));
+    let m = p.start();
+    p.bump(T![as]);
+    name(p);
+    m.complete(p, RENAME);
+}
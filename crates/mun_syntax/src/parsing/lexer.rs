@@ -66,6 +66,10 @@ fn next_token_inner(c: char, cursor: &mut Cursor) -> SyntaxKind {
         return scan_number(c, cursor);
     }
 
+    if let Some(kind) = scan_dots(c, cursor) {
+        return kind;
+    }
+
     if let Some(kind) = scan_index(c, cursor) {
         return kind;
     }
@@ -101,6 +105,24 @@ fn scan_identifier_or_keyword(c: char, cursor: &mut Cursor) -> SyntaxKind {
     IDENT
 }
 
+/// Scans `..`, `...` and `..=` as a single token. This prevents the second dot of a range like
+/// `0..10` from being lexed as the start of an `INDEX` token.
+fn scan_dots(c: char, cursor: &mut Cursor) -> Option<SyntaxKind> {
+    if c != '.' || !cursor.matches('.') {
+        return None;
+    }
+    cursor.bump();
+    if cursor.matches('.') {
+        cursor.bump();
+        Some(DOTDOTDOT)
+    } else if cursor.matches('=') {
+        cursor.bump();
+        Some(DOTDOTEQ)
+    } else {
+        Some(DOTDOT)
+    }
+}
+
 fn scan_index(c: char, cursor: &mut Cursor) -> Option<SyntaxKind> {
     if c == '.' {
         let mut is_first = true;
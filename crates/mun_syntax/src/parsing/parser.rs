@@ -59,7 +59,6 @@ impl<'t> Parser<'t> {
             T![->] => self.at_composite2(n, T![-], T![>]),
             T![::] => self.at_composite2(n, T![:], T![:]),
             T![!=] => self.at_composite2(n, T![!], T![=]),
-            T![..] => self.at_composite2(n, T![.], T![.]),
             T![*=] => self.at_composite2(n, T![*], T![=]),
             T![/=] => self.at_composite2(n, T![/], T![=]),
             T![&&] => self.at_composite2(n, T![&], T![&]),
@@ -70,13 +69,11 @@ impl<'t> Parser<'t> {
             T![<<] => self.at_composite2(n, T![<], T![<]),
             T![<=] => self.at_composite2(n, T![<], T![=]),
             T![==] => self.at_composite2(n, T![=], T![=]),
-            //T![=>] => self.at_composite2(n, T![=], T![>]),
+            T![=>] => self.at_composite2(n, T![=], T![>]),
             T![>=] => self.at_composite2(n, T![>], T![=]),
             T![>>] => self.at_composite2(n, T![>], T![>]),
             T![|=] => self.at_composite2(n, T![|], T![=]),
             T![||] => self.at_composite2(n, T![|], T![|]),
-            T![...] => self.at_composite3(n, T![.], T![.], T![.]),
-            //T![..=] => self.at_composite3(n, T![.], T![.], T![=]),
             T![<<=] => self.at_composite3(n, T![<], T![<], T![=]),
             T![>>=] => self.at_composite3(n, T![>], T![>], T![=]),
             _ => self.token_source.lookahead_nth(n).kind == kind,
@@ -188,7 +185,6 @@ impl<'t> Parser<'t> {
             | T![->]
             | T![::]
             | T![!=]
-            | T![..]
             | T![*=]
             | T![/=]
             | T![&&]
@@ -199,18 +195,13 @@ impl<'t> Parser<'t> {
             | T![<<]
             | T![<=]
             | T![==]
-            //| T![=>]
+            | T![=>]
             | T![>=]
             | T![>>]
             | T![|=]
-            | T![||]
-            => 2,
-
-            T![...]
-            //| T![..=]
-            | T![<<=]
-            | T![>>=]
-            => 3,
+            | T![||] => 2,
+
+            T![<<=] | T![>>=] => 3,
             _ => 1,
         };
         self.do_bump(kind, n_raw_tokens);
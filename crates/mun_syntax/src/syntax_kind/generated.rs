@@ -41,6 +41,7 @@ pub enum SyntaxKind {
     COMMA,
     EXCLAMATION,
     UNDERSCORE,
+    QUESTION,
     EQEQ,
     NEQ,
     LTEQ,
@@ -60,6 +61,7 @@ pub enum SyntaxKind {
     DOTDOTEQ,
     COLONCOLON,
     THIN_ARROW,
+    FAT_ARROW,
     AMPAMP,
     PIPEPIPE,
     SHL,
@@ -81,12 +83,21 @@ pub enum SyntaxKind {
     MUT_KW,
     CLASS_KW,
     STRUCT_KW,
+    ENUM_KW,
+    IMPL_KW,
+    TRAIT_KW,
+    MATCH_KW,
     NEVER_KW,
     PUB_KW,
     PACKAGE_KW,
     SUPER_KW,
     SELF_KW,
     EXTERN_KW,
+    MOD_KW,
+    USE_KW,
+    AS_KW,
+    CONST_KW,
+    STATIC_KW,
     INT_NUMBER,
     FLOAT_NUMBER,
     STRING,
@@ -102,34 +113,76 @@ pub enum SyntaxKind {
     EXTERN,
     RET_TYPE,
     VISIBILITY,
+    ATTR,
+    ATTR_ARG_LIST,
     PARAM_LIST,
     PARAM,
+    SELF_PARAM,
     STRUCT_DEF,
     MEMORY_TYPE_SPECIFIER,
+    MODULE,
+    USE,
+    USE_TREE,
+    USE_TREE_LIST,
+    RENAME,
     RECORD_FIELD_DEF_LIST,
     RECORD_FIELD_DEF,
     TUPLE_FIELD_DEF_LIST,
     TUPLE_FIELD_DEF,
+    ENUM_DEF,
+    ENUM_VARIANT_LIST,
+    ENUM_VARIANT,
+    IMPL_BLOCK,
+    ITEM_LIST,
+    TRAIT_DEF,
+    CONST_DEF,
+    STATIC_DEF,
     PATH_TYPE,
     NEVER_TYPE,
+    ARRAY_TYPE,
+    PAREN_TYPE,
+    TUPLE_TYPE,
+    FN_POINTER_TYPE,
+    OPTION_TYPE,
+    TYPE_PARAM_LIST,
+    TYPE_PARAM,
+    TYPE_ARG_LIST,
+    TYPE_ARG,
     LET_STMT,
     EXPR_STMT,
     PATH_EXPR,
     PREFIX_EXPR,
     LITERAL,
     BIN_EXPR,
+    RANGE_EXPR,
     PAREN_EXPR,
     CALL_EXPR,
     FIELD_EXPR,
+    METHOD_CALL_EXPR,
+    INDEX_EXPR,
+    ARRAY_EXPR,
+    TUPLE_EXPR,
+    CAST_EXPR,
+    CLOSURE_EXPR,
     IF_EXPR,
     BLOCK_EXPR,
     RETURN_EXPR,
     WHILE_EXPR,
     LOOP_EXPR,
+    FOR_EXPR,
+    MATCH_EXPR,
+    MATCH_ARM_LIST,
+    MATCH_ARM,
     BREAK_EXPR,
     CONDITION,
     BIND_PAT,
     PLACEHOLDER_PAT,
+    PATH_PAT,
+    TUPLE_STRUCT_PAT,
+    TUPLE_PAT,
+    RECORD_PAT,
+    RECORD_FIELD_PAT_LIST,
+    RECORD_FIELD_PAT,
     ARG_LIST,
     NAME,
     NAME_REF,
@@ -170,6 +223,7 @@ macro_rules! T {
     (,) => { $crate::SyntaxKind::COMMA };
     (!) => { $crate::SyntaxKind::EXCLAMATION };
     (_) => { $crate::SyntaxKind::UNDERSCORE };
+    (?) => { $crate::SyntaxKind::QUESTION };
     (==) => { $crate::SyntaxKind::EQEQ };
     (!=) => { $crate::SyntaxKind::NEQ };
     (<=) => { $crate::SyntaxKind::LTEQ };
@@ -189,6 +243,7 @@ macro_rules! T {
     (..=) => { $crate::SyntaxKind::DOTDOTEQ };
     (::) => { $crate::SyntaxKind::COLONCOLON };
     (->) => { $crate::SyntaxKind::THIN_ARROW };
+    (=>) => { $crate::SyntaxKind::FAT_ARROW };
     (&&) => { $crate::SyntaxKind::AMPAMP };
     (||) => { $crate::SyntaxKind::PIPEPIPE };
     (<<) => { $crate::SyntaxKind::SHL };
@@ -210,12 +265,21 @@ macro_rules! T {
     (mut) => { $crate::SyntaxKind::MUT_KW };
     (class) => { $crate::SyntaxKind::CLASS_KW };
     (struct) => { $crate::SyntaxKind::STRUCT_KW };
+    (enum) => { $crate::SyntaxKind::ENUM_KW };
+    (impl) => { $crate::SyntaxKind::IMPL_KW };
+    (trait) => { $crate::SyntaxKind::TRAIT_KW };
+    (match) => { $crate::SyntaxKind::MATCH_KW };
     (never) => { $crate::SyntaxKind::NEVER_KW };
     (pub) => { $crate::SyntaxKind::PUB_KW };
     (package) => { $crate::SyntaxKind::PACKAGE_KW };
     (super) => { $crate::SyntaxKind::SUPER_KW };
     (self) => { $crate::SyntaxKind::SELF_KW };
     (extern) => { $crate::SyntaxKind::EXTERN_KW };
+    (mod) => { $crate::SyntaxKind::MOD_KW };
+    (use) => { $crate::SyntaxKind::USE_KW };
+    (as) => { $crate::SyntaxKind::AS_KW };
+    (const) => { $crate::SyntaxKind::CONST_KW };
+    (static) => { $crate::SyntaxKind::STATIC_KW };
 }
 
 impl From<u16> for SyntaxKind {
@@ -251,12 +315,21 @@ impl SyntaxKind {
             | MUT_KW
             | CLASS_KW
             | STRUCT_KW
+            | ENUM_KW
+            | IMPL_KW
+            | TRAIT_KW
+            | MATCH_KW
             | NEVER_KW
             | PUB_KW
             | PACKAGE_KW
             | SUPER_KW
             | SELF_KW
             | EXTERN_KW
+            | MOD_KW
+            | USE_KW
+            | AS_KW
+            | CONST_KW
+            | STATIC_KW
                 => true,
             _ => false
         }
@@ -288,6 +361,7 @@ impl SyntaxKind {
                 | COMMA
                 | EXCLAMATION
                 | UNDERSCORE
+                | QUESTION
                 | EQEQ
                 | NEQ
                 | LTEQ
@@ -307,6 +381,7 @@ impl SyntaxKind {
                 | DOTDOTEQ
                 | COLONCOLON
                 | THIN_ARROW
+                | FAT_ARROW
                 | AMPAMP
                 | PIPEPIPE
                 | SHL
@@ -352,6 +427,7 @@ impl SyntaxKind {
                 COMMA => &SyntaxInfo { name: "COMMA" },
                 EXCLAMATION => &SyntaxInfo { name: "EXCLAMATION" },
                 UNDERSCORE => &SyntaxInfo { name: "UNDERSCORE" },
+                QUESTION => &SyntaxInfo { name: "QUESTION" },
                 EQEQ => &SyntaxInfo { name: "EQEQ" },
                 NEQ => &SyntaxInfo { name: "NEQ" },
                 LTEQ => &SyntaxInfo { name: "LTEQ" },
@@ -371,6 +447,7 @@ impl SyntaxKind {
                 DOTDOTEQ => &SyntaxInfo { name: "DOTDOTEQ" },
                 COLONCOLON => &SyntaxInfo { name: "COLONCOLON" },
                 THIN_ARROW => &SyntaxInfo { name: "THIN_ARROW" },
+                FAT_ARROW => &SyntaxInfo { name: "FAT_ARROW" },
                 AMPAMP => &SyntaxInfo { name: "AMPAMP" },
                 PIPEPIPE => &SyntaxInfo { name: "PIPEPIPE" },
                 SHL => &SyntaxInfo { name: "SHL" },
@@ -392,12 +469,21 @@ impl SyntaxKind {
                 MUT_KW => &SyntaxInfo { name: "MUT_KW" },
                 CLASS_KW => &SyntaxInfo { name: "CLASS_KW" },
                 STRUCT_KW => &SyntaxInfo { name: "STRUCT_KW" },
+                ENUM_KW => &SyntaxInfo { name: "ENUM_KW" },
+                IMPL_KW => &SyntaxInfo { name: "IMPL_KW" },
+                TRAIT_KW => &SyntaxInfo { name: "TRAIT_KW" },
+                MATCH_KW => &SyntaxInfo { name: "MATCH_KW" },
                 NEVER_KW => &SyntaxInfo { name: "NEVER_KW" },
                 PUB_KW => &SyntaxInfo { name: "PUB_KW" },
                 PACKAGE_KW => &SyntaxInfo { name: "PACKAGE_KW" },
                 SUPER_KW => &SyntaxInfo { name: "SUPER_KW" },
                 SELF_KW => &SyntaxInfo { name: "SELF_KW" },
                 EXTERN_KW => &SyntaxInfo { name: "EXTERN_KW" },
+                MOD_KW => &SyntaxInfo { name: "MOD_KW" },
+                USE_KW => &SyntaxInfo { name: "USE_KW" },
+                AS_KW => &SyntaxInfo { name: "AS_KW" },
+                CONST_KW => &SyntaxInfo { name: "CONST_KW" },
+                STATIC_KW => &SyntaxInfo { name: "STATIC_KW" },
                 INT_NUMBER => &SyntaxInfo { name: "INT_NUMBER" },
                 FLOAT_NUMBER => &SyntaxInfo { name: "FLOAT_NUMBER" },
                 STRING => &SyntaxInfo { name: "STRING" },
@@ -413,34 +499,76 @@ impl SyntaxKind {
                 EXTERN => &SyntaxInfo { name: "EXTERN" },
                 RET_TYPE => &SyntaxInfo { name: "RET_TYPE" },
                 VISIBILITY => &SyntaxInfo { name: "VISIBILITY" },
+                ATTR => &SyntaxInfo { name: "ATTR" },
+                ATTR_ARG_LIST => &SyntaxInfo { name: "ATTR_ARG_LIST" },
                 PARAM_LIST => &SyntaxInfo { name: "PARAM_LIST" },
                 PARAM => &SyntaxInfo { name: "PARAM" },
+                SELF_PARAM => &SyntaxInfo { name: "SELF_PARAM" },
                 STRUCT_DEF => &SyntaxInfo { name: "STRUCT_DEF" },
                 MEMORY_TYPE_SPECIFIER => &SyntaxInfo { name: "MEMORY_TYPE_SPECIFIER" },
+                MODULE => &SyntaxInfo { name: "MODULE" },
+                USE => &SyntaxInfo { name: "USE" },
+                USE_TREE => &SyntaxInfo { name: "USE_TREE" },
+                USE_TREE_LIST => &SyntaxInfo { name: "USE_TREE_LIST" },
+                RENAME => &SyntaxInfo { name: "RENAME" },
                 RECORD_FIELD_DEF_LIST => &SyntaxInfo { name: "RECORD_FIELD_DEF_LIST" },
                 RECORD_FIELD_DEF => &SyntaxInfo { name: "RECORD_FIELD_DEF" },
                 TUPLE_FIELD_DEF_LIST => &SyntaxInfo { name: "TUPLE_FIELD_DEF_LIST" },
                 TUPLE_FIELD_DEF => &SyntaxInfo { name: "TUPLE_FIELD_DEF" },
+                ENUM_DEF => &SyntaxInfo { name: "ENUM_DEF" },
+                ENUM_VARIANT_LIST => &SyntaxInfo { name: "ENUM_VARIANT_LIST" },
+                ENUM_VARIANT => &SyntaxInfo { name: "ENUM_VARIANT" },
+                IMPL_BLOCK => &SyntaxInfo { name: "IMPL_BLOCK" },
+                ITEM_LIST => &SyntaxInfo { name: "ITEM_LIST" },
+                TRAIT_DEF => &SyntaxInfo { name: "TRAIT_DEF" },
+                CONST_DEF => &SyntaxInfo { name: "CONST_DEF" },
+                STATIC_DEF => &SyntaxInfo { name: "STATIC_DEF" },
                 PATH_TYPE => &SyntaxInfo { name: "PATH_TYPE" },
                 NEVER_TYPE => &SyntaxInfo { name: "NEVER_TYPE" },
+                ARRAY_TYPE => &SyntaxInfo { name: "ARRAY_TYPE" },
+                PAREN_TYPE => &SyntaxInfo { name: "PAREN_TYPE" },
+                TUPLE_TYPE => &SyntaxInfo { name: "TUPLE_TYPE" },
+                FN_POINTER_TYPE => &SyntaxInfo { name: "FN_POINTER_TYPE" },
+                OPTION_TYPE => &SyntaxInfo { name: "OPTION_TYPE" },
+                TYPE_PARAM_LIST => &SyntaxInfo { name: "TYPE_PARAM_LIST" },
+                TYPE_PARAM => &SyntaxInfo { name: "TYPE_PARAM" },
+                TYPE_ARG_LIST => &SyntaxInfo { name: "TYPE_ARG_LIST" },
+                TYPE_ARG => &SyntaxInfo { name: "TYPE_ARG" },
                 LET_STMT => &SyntaxInfo { name: "LET_STMT" },
                 EXPR_STMT => &SyntaxInfo { name: "EXPR_STMT" },
                 PATH_EXPR => &SyntaxInfo { name: "PATH_EXPR" },
                 PREFIX_EXPR => &SyntaxInfo { name: "PREFIX_EXPR" },
                 LITERAL => &SyntaxInfo { name: "LITERAL" },
                 BIN_EXPR => &SyntaxInfo { name: "BIN_EXPR" },
+                RANGE_EXPR => &SyntaxInfo { name: "RANGE_EXPR" },
                 PAREN_EXPR => &SyntaxInfo { name: "PAREN_EXPR" },
                 CALL_EXPR => &SyntaxInfo { name: "CALL_EXPR" },
                 FIELD_EXPR => &SyntaxInfo { name: "FIELD_EXPR" },
+                METHOD_CALL_EXPR => &SyntaxInfo { name: "METHOD_CALL_EXPR" },
+                INDEX_EXPR => &SyntaxInfo { name: "INDEX_EXPR" },
+                ARRAY_EXPR => &SyntaxInfo { name: "ARRAY_EXPR" },
+                TUPLE_EXPR => &SyntaxInfo { name: "TUPLE_EXPR" },
+                CAST_EXPR => &SyntaxInfo { name: "CAST_EXPR" },
+                CLOSURE_EXPR => &SyntaxInfo { name: "CLOSURE_EXPR" },
                 IF_EXPR => &SyntaxInfo { name: "IF_EXPR" },
                 BLOCK_EXPR => &SyntaxInfo { name: "BLOCK_EXPR" },
                 RETURN_EXPR => &SyntaxInfo { name: "RETURN_EXPR" },
                 WHILE_EXPR => &SyntaxInfo { name: "WHILE_EXPR" },
                 LOOP_EXPR => &SyntaxInfo { name: "LOOP_EXPR" },
+                FOR_EXPR => &SyntaxInfo { name: "FOR_EXPR" },
+                MATCH_EXPR => &SyntaxInfo { name: "MATCH_EXPR" },
+                MATCH_ARM_LIST => &SyntaxInfo { name: "MATCH_ARM_LIST" },
+                MATCH_ARM => &SyntaxInfo { name: "MATCH_ARM" },
                 BREAK_EXPR => &SyntaxInfo { name: "BREAK_EXPR" },
                 CONDITION => &SyntaxInfo { name: "CONDITION" },
                 BIND_PAT => &SyntaxInfo { name: "BIND_PAT" },
                 PLACEHOLDER_PAT => &SyntaxInfo { name: "PLACEHOLDER_PAT" },
+                PATH_PAT => &SyntaxInfo { name: "PATH_PAT" },
+                TUPLE_STRUCT_PAT => &SyntaxInfo { name: "TUPLE_STRUCT_PAT" },
+                TUPLE_PAT => &SyntaxInfo { name: "TUPLE_PAT" },
+                RECORD_PAT => &SyntaxInfo { name: "RECORD_PAT" },
+                RECORD_FIELD_PAT_LIST => &SyntaxInfo { name: "RECORD_FIELD_PAT_LIST" },
+                RECORD_FIELD_PAT => &SyntaxInfo { name: "RECORD_FIELD_PAT" },
                 ARG_LIST => &SyntaxInfo { name: "ARG_LIST" },
                 NAME => &SyntaxInfo { name: "NAME" },
                 NAME_REF => &SyntaxInfo { name: "NAME_REF" },
@@ -474,12 +602,21 @@ impl SyntaxKind {
                 "mut" => MUT_KW,
                 "class" => CLASS_KW,
                 "struct" => STRUCT_KW,
+                "enum" => ENUM_KW,
+                "impl" => IMPL_KW,
+                "trait" => TRAIT_KW,
+                "match" => MATCH_KW,
                 "never" => NEVER_KW,
                 "pub" => PUB_KW,
                 "package" => PACKAGE_KW,
                 "super" => SUPER_KW,
                 "self" => SELF_KW,
                 "extern" => EXTERN_KW,
+                "mod" => MOD_KW,
+                "use" => USE_KW,
+                "as" => AS_KW,
+                "const" => CONST_KW,
+                "static" => STATIC_KW,
                 _ => return None,
             };
             Some(kw)
@@ -511,6 +648,7 @@ impl SyntaxKind {
                 ',' => COMMA,
                 '!' => EXCLAMATION,
                 '_' => UNDERSCORE,
+                '?' => QUESTION,
                 _ => return None,
             };
             Some(tok)
@@ -1,2 +1,3 @@
+mod formatting;
 mod lexer;
 mod parser;
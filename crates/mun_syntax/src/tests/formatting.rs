@@ -0,0 +1,95 @@
+use crate::{formatting::format, AstNode, Parse, SourceFile, SyntaxKind, T};
+
+/// Asserts that formatting `file` results in a text without syntax errors that has the same
+/// tokens, apart from the trailing commas inserted in structs, and that formatting the result
+/// again does not change it.
+pub(super) fn check_formatting_idempotent(file: &Parse<SourceFile>) {
+    let formatted = format(&file.tree());
+    let reparsed = SourceFile::parse(&formatted);
+    assert!(
+        reparsed.errors().is_empty(),
+        "formatted text contains errors:\n{}",
+        formatted
+    );
+    assert_eq!(
+        significant_tokens(file),
+        significant_tokens(&reparsed),
+        "formatting changed the tokens:\n{}",
+        formatted
+    );
+    assert_eq!(
+        format(&reparsed.tree()),
+        formatted,
+        "formatting is not idempotent"
+    );
+}
+
+/// Returns the kind and text of all tokens that are not whitespace or a comma of a struct's fields.
+fn significant_tokens(file: &Parse<SourceFile>) -> Vec<(SyntaxKind, String)> {
+    file.tree()
+        .syntax()
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| {
+            token.kind() != SyntaxKind::WHITESPACE
+                && !(token.kind() == T![,]
+                    && token.parent().kind() == SyntaxKind::RECORD_FIELD_DEF_LIST)
+        })
+        .map(|token| (token.kind(), token.text().to_string()))
+        .collect()
+}
+
+fn check_format(text: &str, expected: &str) {
+    let file = SourceFile::parse(text);
+    assert!(file.errors().is_empty(), "{}", file.debug_dump());
+    assert_eq!(format(&file.tree()), expected);
+    check_formatting_idempotent(&file);
+}
+
+#[test]
+fn spacing() {
+    check_format(
+        "fn add(a:i32,b:i32)->i32{a+b}",
+        "fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n",
+    );
+}
+
+#[test]
+fn struct_layout() {
+    check_format(
+        "struct Foo{a:f64,b:i32}",
+        "struct Foo {\n    a: f64,\n    b: i32,\n}\n",
+    );
+}
+
+#[test]
+fn comments_and_blank_lines() {
+    check_format(
+        "// A comment\n\n\n\nfn main( ) {\n  let a = -1;   // trailing\n\n\n  let b = a .foo;\n}",
+        "// A comment\n\nfn main() {\n    let a = -1; // trailing\n\n    let b = a.foo;\n}\n",
+    );
+}
+
+#[test]
+fn continuation_lines() {
+    check_format(
+        "fn main() {\nlet a = foo(1,\n2)\n+ bar;\n}",
+        "fn main() {\n    let a = foo(1,\n        2)\n        + bar;\n}\n",
+    );
+}
+
+#[test]
+fn closures_and_blocks() {
+    check_format(
+        "fn main() {\nlet f = |x:i32|x*2;\nif f(1)>2 {\n}else{ }\n}",
+        "fn main() {\n    let f = |x: i32| x * 2;\n    if f(1) > 2 {\n    } else {}\n}\n",
+    );
+}
+
+#[test]
+fn attributes() {
+    check_format(
+        "#[ allow ( unused_variables,unused_mut ) ]fn main() {}",
+        "#[allow(unused_variables, unused_mut)] fn main() {}\n",
+    );
+}
@@ -143,3 +143,14 @@ fn binary_cmp() {
     "#,
     )
 }
+
+#[test]
+fn ranges() {
+    lex_snapshot(
+        r#"
+    0..10
+    0..=10
+    a..b
+    "#,
+    )
+}
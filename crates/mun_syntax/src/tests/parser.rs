@@ -1,9 +1,13 @@
+use super::formatting::check_formatting_idempotent;
 use crate::SourceFile;
 
 fn snapshot_test(text: &str) {
     let text = text.trim().replace("\n    ", "\n");
     let file = SourceFile::parse(&text);
     insta::assert_snapshot!(insta::_macro_support::AutoName, file.debug_dump(), &text);
+    if file.errors().is_empty() {
+        check_formatting_idempotent(&file);
+    }
 }
 
 #[test]
@@ -286,6 +290,19 @@ fn while_expr() {
     )
 }
 
+#[test]
+fn for_expr() {
+    snapshot_test(
+        r#"
+    fn foo() {
+        for i in 0..10 {};
+        for _ in 0..=n { break; };
+        for i in a+1..b*2 {}
+    }
+    "#,
+    )
+}
+
 #[test]
 fn struct_lit() {
     snapshot_test(
@@ -355,3 +372,203 @@ fn extern_fn() {
     "#,
     )
 }
+
+#[test]
+fn module_def() {
+    snapshot_test(
+        r#"
+    mod foo;
+    pub mod bar;
+    mod;        // error: expected a name
+    mod baz     // error: expected SEMI
+    "#,
+    )
+}
+
+#[test]
+fn use_() {
+    snapshot_test(
+        r#"
+    use foo;
+    use package::foo::Bar;
+    pub use super::bar::*;
+    use self::baz::{Foo, Bar as Baz, qux::*};
+    use ;       // error: expected one of `self`, `super`, `package`, `{` or an identifier
+    "#,
+    )
+}
+
+#[test]
+fn array_expr() {
+    snapshot_test(
+        r#"
+    fn main() {
+        let a = [1, 2, 3];
+        let b: [f64] = [];
+        let c: [i32; 3] = [a[0], a[1], a[2],];
+        b.push(1.0);
+        b.len()
+    }
+    "#,
+    )
+}
+
+#[test]
+fn enum_def() {
+    snapshot_test(
+        r#"
+    enum Shape {
+        Circle(f32),
+        Rect { w: f32, h: f32 },
+        None,
+    }
+    pub enum(value) Direction { Left, Right }
+    "#,
+    )
+}
+
+#[test]
+fn match_expr() {
+    snapshot_test(
+        r#"
+    fn area(shape: Shape) -> f32 {
+        match shape {
+            Shape::Circle(r) => r * r,
+            Shape::Rect { w, h: height } => w * height,
+            Shape::None => { 0.0 }
+            _ => 1.0,
+        }
+    }
+    "#,
+    )
+}
+
+#[test]
+fn impl_block() {
+    snapshot_test(
+        r#"
+    impl Foo {
+        fn new() -> Self { Foo }
+        pub fn len(self, b: f32) -> f32 { self.a * b }
+    }
+    "#,
+    )
+}
+
+#[test]
+fn traits() {
+    snapshot_test(
+        r#"
+    pub trait Update {
+        fn update(self, dt: f32);
+    }
+    impl Update for Foo {
+        fn update(self, dt: f32) {}
+    }
+    "#,
+    )
+}
+
+#[test]
+fn generics() {
+    snapshot_test(
+        r#"
+    struct Pair<A, B> { a: A, b: B }
+    fn max<T>(a: T, b: T) -> T { a }
+    fn main() {
+        let p: Pair<f32, [i64]> = Pair { a: 1.0, b: [] };
+        max(1, 2);
+    }
+    "#,
+    )
+}
+
+#[test]
+fn tuples() {
+    snapshot_test(
+        r#"
+    fn main(p: (i32, f32), q: (f32)) -> () {
+        let a: (i32,) = (1,);
+        let (x, _) = (1, (2.0));
+        ()
+    }
+    "#,
+    )
+}
+
+#[test]
+fn cast() {
+    snapshot_test(
+        r#"
+    fn main(a: i32) {
+        let b = a as f64;
+        let c = -a as u8 + 1;
+        let d = a as i64 * 2;
+    }
+    "#,
+    )
+}
+
+#[test]
+fn closures() {
+    snapshot_test(
+        r#"
+    fn main() {
+        let f: fn(f32, f32) -> f32 = add;
+        let g = |x| x * k;
+        let h = |x: f32, y| -> f32 { x + y };
+        let i = || 5;
+        apply(|x| x + 1, 2);
+    }
+    fn apply(f: fn(i32) -> i32, g: fn()) {}
+    "#,
+    )
+}
+
+#[test]
+fn globals() {
+    snapshot_test(
+        r#"
+    const GRAVITY: f32 = 9.81;
+    pub const TWO_PI: f64 = 2.0 * PI;
+    static mut SCORE: i32 = 0;
+    static COUNT = 1;       // error: expected a type
+    "#,
+    )
+}
+
+#[test]
+fn optional_types() {
+    snapshot_test(
+        r#"
+    fn main(a: Foo?) -> Foo? {
+        let b: (Foo?, i32) = (nil, 1);
+        if a != nil { a } else { nil }
+    }
+    "#,
+    )
+}
+
+#[test]
+fn attributes() {
+    snapshot_test(
+        r#"
+    #[allow(unused_variables, unused_mut)]
+    fn main() {}
+    #[allow]
+    static mut SCORE: i32 = 0;
+    "#,
+    )
+}
+
+#[test]
+fn field_attributes() {
+    snapshot_test(
+        r#"
+    struct Foo {
+        #[renamed_from("hp")]
+        health: i32,
+    }
+    "#,
+    )
+}